@@ -379,6 +379,7 @@ async fn run_task_isolated(params: TaskRunParams<'_>) -> TaskResult {
         allow_local_tools: true,
         max_cost_per_day_cents: None,
         max_actions_per_hour: None,
+        module_manifests_path: None,
     };
 
     let cost_guard = Arc::new(enclagent::agent::cost_guard::CostGuard::new(
@@ -397,6 +398,7 @@ async fn run_task_isolated(params: TaskRunParams<'_>) -> TaskResult {
         skills_config: enclagent::config::SkillsConfig::default(),
         hooks: Arc::new(enclagent::hooks::HookRegistry::new()),
         cost_guard,
+        module_catalog: Arc::new(enclagent::platform::ModuleCatalog::curated()),
     };
 
     let mut channels = ChannelManager::new();
@@ -0,0 +1,14 @@
+-- Per-agent settings scoping.
+--
+-- agent_id NULL keeps the existing user-global semantics. Rows with an
+-- agent_id override the global value for that agent only, so the old
+-- (user_id, key) primary key becomes two partial unique indexes.
+
+ALTER TABLE settings ADD COLUMN IF NOT EXISTS agent_id UUID;
+
+ALTER TABLE settings DROP CONSTRAINT IF EXISTS settings_pkey;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_settings_user_agent_key
+    ON settings (user_id, agent_id, key);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_settings_user_global_key
+    ON settings (user_id, key) WHERE agent_id IS NULL;
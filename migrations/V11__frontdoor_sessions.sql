@@ -0,0 +1,8 @@
+-- Persist full frontdoor session snapshots.
+--
+-- The gateway keeps sessions in memory and writes a JSON snapshot here on
+-- every state change so in-flight provisioning survives a restart.
+
+ALTER TABLE frontdoor_sessions ADD COLUMN IF NOT EXISTS payload JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_frontdoor_sessions_wallet ON frontdoor_sessions (wallet_address);
@@ -0,0 +1,4 @@
+-- Composite index for the scheduler's "which routines are due?" query,
+-- ordered by next_fire_at.
+
+CREATE INDEX IF NOT EXISTS idx_routines_enabled_next_fire ON routines (enabled, next_fire_at);
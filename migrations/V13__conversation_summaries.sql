@@ -0,0 +1,13 @@
+-- Rolling conversation summaries.
+--
+-- One row per conversation: a summary of every message up to and including
+-- covered_through_message_id. The agent loads the summary plus the messages
+-- after that watermark to keep LLM context small.
+
+CREATE TABLE IF NOT EXISTS conversation_summaries (
+    conversation_id UUID PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    summary TEXT NOT NULL,
+    token_count INTEGER NOT NULL,
+    covered_through_message_id UUID NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
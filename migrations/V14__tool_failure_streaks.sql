@@ -0,0 +1,11 @@
+-- Per-user consecutive tool failures, used to back off a tool that keeps
+-- failing for one user without disabling it for everyone else.
+
+CREATE TABLE IF NOT EXISTS tool_failure_streaks (
+    tool_name VARCHAR(255) NOT NULL,
+    user_id TEXT NOT NULL,
+    consecutive_failures INTEGER NOT NULL,
+    first_failure_at TIMESTAMPTZ NOT NULL,
+    last_failure_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tool_name, user_id)
+);
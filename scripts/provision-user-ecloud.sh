@@ -390,6 +390,10 @@ process.stdin.on("end", () => {
   outInt("HYPERLIQUID_LEVERAGE_CAP", cfg.leverage_cap);
   out("HYPERLIQUID_KILL_SWITCH_ENABLED", cfg.kill_switch_enabled === false ? "false" : "true");
   out("HYPERLIQUID_KILL_SWITCH_BEHAVIOR", cfg.kill_switch_behavior);
+  if (cfg.kill_switch_behavior === "cancel_and_flatten") {
+    // The signed frontdoor config is the user's explicit flatten confirmation.
+    out("HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN", "true");
+  }
   out("VERIFICATION_BACKEND", cfg.verification_backend);
   out("EIGENCLOUD_ENDPOINT", cfg.verification_eigencloud_endpoint);
   out("EIGENCLOUD_AUTH_SCHEME", cfg.verification_eigencloud_auth_scheme);
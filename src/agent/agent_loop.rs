@@ -72,6 +72,8 @@ pub struct AgentDeps {
     pub hooks: Arc<HookRegistry>,
     /// Cost enforcement guardrails (daily budget, hourly rate limits).
     pub cost_guard: Arc<crate::agent::cost_guard::CostGuard>,
+    /// Module catalog behind module state, capability guards and routing.
+    pub module_catalog: Arc<crate::platform::ModuleCatalog>,
 }
 
 /// The main agent that coordinates all components.
@@ -166,6 +168,10 @@ impl Agent {
         &self.deps.cost_guard
     }
 
+    pub(super) fn module_catalog(&self) -> &crate::platform::ModuleCatalog {
+        &self.deps.module_catalog
+    }
+
     pub(super) fn skill_registry(&self) -> Option<&Arc<std::sync::RwLock<SkillRegistry>>> {
         self.deps.skill_registry.as_ref()
     }
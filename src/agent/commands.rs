@@ -26,52 +26,16 @@ use crate::agent::{
     ExecutionReceipt, ExecutionSide, InformationSharingScope, IntentAuditRecord, IntentEnvelope,
     MessageIntent, RiskLimitsPayload, SimulatedFill, StrategyPayload, VerificationBackend,
     VerificationRecord, VerificationStatus, compile_copytrade_policy, deterministic_hash,
+    verification_assurance_level,
 };
 use crate::channels::{IncomingMessage, StatusUpdate};
 use crate::context::JobContext;
 use crate::error::{ConfigError, Error};
 use crate::llm::ChatMessage;
-use crate::platform::ModuleState;
+use crate::platform::{COMMAND_TABLE, ModuleState};
 use crate::secrets::{VerificationJobCredentialRef, VerificationJobProvider};
 use crate::settings::Settings;
 
-const COMMAND_SUGGESTIONS: &[&str] = &[
-    "help",
-    "model",
-    "version",
-    "tools",
-    "ping",
-    "debug",
-    "positions",
-    "exposure",
-    "funding",
-    "vault",
-    "risk",
-    "pause-agent",
-    "resume-agent",
-    "verify",
-    "receipts",
-    "copy-policy",
-    "copy-status",
-    "connectors",
-    "heartbeat",
-    "summarize",
-    "suggest",
-    "quit",
-    "job",
-    "status",
-    "cancel",
-    "list",
-    "undo",
-    "redo",
-    "compact",
-    "clear",
-    "interrupt",
-    "new",
-    "thread",
-    "resume",
-];
-
 const MAINNET_OVERRIDE_FLAG: &str = "--allow-mainnet";
 const AUTO_ACTIVATE_DISABLE_FLAG: &str = "--no-auto-activate";
 const INCLUDE_PNL_FLAG: &str = "--include-pnl";
@@ -179,7 +143,7 @@ fn command_suggestions(command: &str) -> Vec<&'static str> {
         return Vec::new();
     }
 
-    let mut ranked: Vec<(&'static str, usize)> = COMMAND_SUGGESTIONS
+    let mut ranked: Vec<(&'static str, usize)> = COMMAND_TABLE
         .iter()
         .map(|candidate| (*candidate, levenshtein_distance(&normalized, candidate)))
         .collect();
@@ -1031,13 +995,13 @@ impl Agent {
         if let Some(store) = self.store()
             && let Ok(map) = store.get_all_settings(user_id).await
         {
-            return Settings::from_db_map(&map);
+            return Settings::from_db_map(&map).0;
         }
         Settings::default()
     }
 
     pub(super) async fn load_module_states_for_user(&self, user_id: &str) -> Vec<ModuleState> {
-        let defaults = crate::platform::default_module_states();
+        let defaults = crate::platform::default_module_states(self.module_catalog());
         let Some(store) = self.store() else {
             return defaults;
         };
@@ -1061,9 +1025,14 @@ impl Agent {
         };
 
         if stored.is_empty() {
-            defaults
-        } else {
-            crate::platform::merge_module_states(stored)
+            return defaults;
+        }
+        match crate::platform::merge_module_states(self.module_catalog(), stored) {
+            Ok(states) => states,
+            Err(error) => {
+                tracing::warn!(%error, "Corrupt stored module state; using defaults");
+                defaults
+            }
         }
     }
 
@@ -1078,7 +1047,11 @@ impl Agent {
         }
 
         let module_states = self.load_module_states_for_user(user_id).await;
-        let guard = crate::platform::resolve_capability_guard(required, &module_states);
+        let guard = crate::platform::resolve_capability_guard(
+            self.module_catalog(),
+            required,
+            &module_states,
+        );
         if guard.allowed {
             return None;
         }
@@ -1095,7 +1068,11 @@ impl Agent {
         content: &str,
     ) -> Option<SubmissionResult> {
         let module_states = self.load_module_states_for_user(user_id).await;
-        let route_resolution = crate::platform::resolve_inference_route(content, &module_states);
+        let route_resolution = crate::platform::resolve_inference_route(
+            self.module_catalog(),
+            content,
+            &module_states,
+        );
         if route_resolution.allowed {
             return None;
         }
@@ -1792,17 +1769,28 @@ impl Agent {
             }
         };
 
+        audit_record = audit_record.with_verification_level(verification_assurance_level(
+            &settings.verification_backend.backend,
+            settings.verification_backend.fallback_enabled,
+            settings
+                .verification_backend
+                .fallback_require_signed_receipts,
+        ));
+
         let Some(store) = self.store() else {
             return Ok(SubmissionResult::error(
                 "intent audit store is not configured".to_string(),
             ));
         };
-        if let Err(error) = store.persist_intent_audit_record(&audit_record).await {
-            return Ok(SubmissionResult::error(format!(
-                "failed to persist audit record: {}",
-                error
-            )));
-        }
+        audit_record = match store.append_intent_audit_record(&audit_record).await {
+            Ok(record) => record,
+            Err(error) => {
+                return Ok(SubmissionResult::error(format!(
+                    "failed to persist audit record: {}",
+                    error
+                )));
+            }
+        };
 
         let mut verification_workspace_path: Option<String> = None;
         if let Some(workspace) = self.workspace() {
@@ -502,8 +502,11 @@ impl Agent {
         let required_capabilities = crate::platform::tool_required_capabilities(tool_name);
         if !required_capabilities.is_empty() {
             let module_states = self.load_module_states_for_user(&job_ctx.user_id).await;
-            let guard =
-                crate::platform::resolve_capability_guard(required_capabilities, &module_states);
+            let guard = crate::platform::resolve_capability_guard(
+                self.module_catalog(),
+                required_capabilities,
+                &module_states,
+            );
             if !guard.allowed {
                 return Err(crate::error::ToolError::Disabled {
                     name: tool_name.to_string(),
@@ -557,6 +557,40 @@ impl VerificationStatus {
     }
 }
 
+/// Assurance level implied by a verification backend configuration.
+///
+/// Shared by the frontdoor and the audit chain so both report the same label,
+/// e.g. `primary_plus_signed_fallback` or `custom:<backend>`.
+pub fn verification_assurance_level(
+    backend: &str,
+    fallback_enabled: bool,
+    fallback_require_signed_receipts: bool,
+) -> String {
+    match backend {
+        "eigencloud_primary" => {
+            if fallback_enabled {
+                if fallback_require_signed_receipts {
+                    "primary_plus_signed_fallback".to_string()
+                } else {
+                    "primary_plus_unsigned_fallback".to_string()
+                }
+            } else {
+                "primary_only".to_string()
+            }
+        }
+        "fallback_only" => {
+            if !fallback_enabled {
+                "fallback_only_disabled".to_string()
+            } else if fallback_require_signed_receipts {
+                "signed_fallback_only".to_string()
+            } else {
+                "unsigned_fallback_only".to_string()
+            }
+        }
+        other => format!("custom:{other}"),
+    }
+}
+
 impl std::str::FromStr for VerificationStatus {
     type Err = ();
 
@@ -624,6 +658,8 @@ struct AuditChainPayload<'a> {
     receipt_hash: &'a str,
     verification_hash: Option<&'a str>,
     settlement_hash: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_chain_hash: Option<&'a str>,
 }
 
 /// Canonical audit record linking intent -> receipt -> verification.
@@ -640,6 +676,9 @@ pub struct IntentAuditRecord {
     pub verification_id: Option<Uuid>,
     pub verification_hash: Option<String>,
     pub verification_status: Option<VerificationStatus>,
+    /// Verification assurance level in force when the record was written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_level: Option<String>,
     pub settlement_id: Option<Uuid>,
     pub settlement_hash: Option<String>,
     #[serde(default)]
@@ -660,6 +699,9 @@ impl IntentAuditRecord {
     /// Settings key for the latest audit record per user.
     pub const SETTINGS_KEY: &'static str = "audit.intent_chain.latest";
 
+    /// Settings key for the chain checkpoint left by quota pruning.
+    pub const CHECKPOINT_SETTINGS_KEY: &'static str = "audit.intent_chain.checkpoint";
+
     /// Build a chain record from the normalized artifacts.
     pub fn from_artifacts(
         agent_id: Option<Uuid>,
@@ -680,6 +722,7 @@ impl IntentAuditRecord {
             receipt_hash: &receipt_hash,
             verification_hash: verification_hash.as_deref(),
             settlement_hash: None,
+            previous_chain_hash: None,
         })?;
 
         let workspace_path = format!("{}/{}.json", Self::WORKSPACE_PATH_PREFIX, intent.intent_id);
@@ -695,6 +738,7 @@ impl IntentAuditRecord {
             verification_id: verification.map(|record| record.verification_id),
             verification_hash,
             verification_status: verification.map(|record| record.status),
+            verification_level: None,
             settlement_id: None,
             settlement_hash: None,
             provider_attributions: Vec::new(),
@@ -732,17 +776,46 @@ impl IntentAuditRecord {
         self.revenue_share_fee_usd =
             settlement.map(|record| record.total_fee_usd.normalize().to_string());
 
-        self.chain_hash = deterministic_hash(&AuditChainPayload {
+        self.chain_hash = self.expected_chain_hash(None)?;
+
+        Ok(self)
+    }
+
+    /// Record the verification assurance level in force for this record.
+    ///
+    /// The level is not part of the chain hash.
+    pub fn with_verification_level(mut self, level: impl Into<String>) -> Self {
+        self.verification_level = Some(level.into());
+        self
+    }
+
+    /// Link this record to the previous record in the user's chain.
+    ///
+    /// Recomputes `chain_hash` over the canonical fields plus the prior
+    /// record's hash; `None` marks the head of the chain.
+    pub fn linked_to(
+        mut self,
+        previous_chain_hash: Option<&str>,
+    ) -> Result<Self, serde_json::Error> {
+        self.chain_hash = self.expected_chain_hash(previous_chain_hash)?;
+        Ok(self)
+    }
+
+    /// Chain hash this record should carry when it follows `previous_chain_hash`.
+    pub fn expected_chain_hash(
+        &self,
+        previous_chain_hash: Option<&str>,
+    ) -> Result<String, serde_json::Error> {
+        deterministic_hash(&AuditChainPayload {
             contract_version: INTENT_ARTIFACT_CONTRACT_VERSION,
             hash_algorithm: INTENT_ARTIFACT_HASH_ALGORITHM,
             signal_hash: self.signal_hash.as_deref(),
             intent_hash: &self.intent_hash,
             receipt_hash: &self.receipt_hash,
             verification_hash: self.verification_hash.as_deref(),
-            settlement_hash: settlement_hash.as_deref(),
-        })?;
-
-        Ok(self)
+            settlement_hash: self.settlement_hash.as_deref(),
+            previous_chain_hash,
+        })
     }
 
     /// Path where this audit record should be stored inside the workspace.
@@ -765,6 +838,7 @@ impl IntentAuditRecord {
             "verification_id": self.verification_id,
             "verification_hash": self.verification_hash,
             "verification_status": self.verification_status,
+            "verification_level": self.verification_level,
             "settlement_id": self.settlement_id,
             "settlement_hash": self.settlement_hash,
             "provider_attributions": self.provider_attributions,
@@ -46,6 +46,7 @@ pub use intent::{
     ProviderRevenueAttribution, ProviderSettlementSplit, RevenueShareSettlementReceipt,
     RiskLimitsPayload, SignalFeeSchedule, SignalProviderIdentity, SimulatedFill, StrategyPayload,
     VerificationBackend, VerificationRecord, VerificationStatus, deterministic_hash,
+    verification_assurance_level,
 };
 pub use router::{CompiledCopyTradePolicy, MessageIntent, Router, compile_copytrade_policy};
 pub use routine::{Routine, RoutineAction, RoutineRun, Trigger};
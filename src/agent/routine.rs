@@ -362,6 +362,70 @@ pub struct RoutineRun {
     pub created_at: DateTime<Utc>,
 }
 
+/// Outcome of checking a routine's guardrails before firing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunDecision {
+    /// No guardrail blocks the run.
+    Allow,
+    /// The last run started less than `cooldown` ago; eligible again at `until`.
+    CooldownActive { until: DateTime<Utc> },
+    /// A run with the same trigger detail started within `window`.
+    DedupSuppressed { window: Duration },
+    /// `active` runs are already in progress, at or above `max_concurrent`.
+    ConcurrencyLimited { active: u32 },
+}
+
+/// Decide whether `routine` may fire now given its recent runs.
+///
+/// Checks concurrency, then cooldown (from the newest of `last_run_at` and
+/// the latest run start), then dedup: a run whose `trigger_detail` equals
+/// `trigger_detail` and started inside `dedup_window` suppresses this one.
+pub fn can_run_routine(
+    routine: &Routine,
+    recent_runs: &[RoutineRun],
+    now: DateTime<Utc>,
+    trigger_detail: Option<&str>,
+) -> RunDecision {
+    let guardrails = &routine.guardrails;
+
+    let active = recent_runs
+        .iter()
+        .filter(|run| run.status == RunStatus::Running)
+        .count() as u32;
+    if active >= guardrails.max_concurrent {
+        return RunDecision::ConcurrencyLimited { active };
+    }
+
+    let last_start = recent_runs
+        .iter()
+        .map(|run| run.started_at)
+        .chain(routine.last_run_at)
+        .max();
+    if let Some(last_start) = last_start {
+        let cooldown =
+            chrono::Duration::from_std(guardrails.cooldown).unwrap_or(chrono::Duration::MAX);
+        let until = last_start
+            .checked_add_signed(cooldown)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        if now < until {
+            return RunDecision::CooldownActive { until };
+        }
+    }
+
+    if let (Some(window), Some(detail)) = (guardrails.dedup_window, trigger_detail) {
+        let window_chrono = chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX);
+        let duplicate = recent_runs.iter().any(|run| {
+            run.trigger_detail.as_deref() == Some(detail)
+                && now.signed_duration_since(run.started_at) < window_chrono
+        });
+        if duplicate {
+            return RunDecision::DedupSuppressed { window };
+        }
+    }
+
+    RunDecision::Allow
+}
+
 /// Compute a content hash for event dedup.
 pub fn content_hash(content: &str) -> u64 {
     let mut hasher = DefaultHasher::new();
@@ -376,10 +440,23 @@ pub fn next_cron_fire(schedule: &str) -> Result<Option<DateTime<Utc>>, String> {
     Ok(cron_schedule.upcoming(Utc).next())
 }
 
+/// Next time the scheduler should fire `routine`, for storing in
+/// `next_fire_at` after a run.
+///
+/// Only cron triggers are time-driven; event, webhook and manual routines
+/// (and cron routines with an unparseable schedule) return `None`.
+pub fn recompute_next_fire(routine: &Routine) -> Option<DateTime<Utc>> {
+    match &routine.trigger {
+        Trigger::Cron { schedule } => next_cron_fire(schedule).ok().flatten(),
+        Trigger::Event { .. } | Trigger::Webhook { .. } | Trigger::Manual => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::agent::routine::{
-        RoutineAction, RoutineGuardrails, RunStatus, Trigger, content_hash, next_cron_fire,
+        NotifyConfig, Routine, RoutineAction, RoutineGuardrails, RoutineRun, RunDecision,
+        RunStatus, Trigger, can_run_routine, content_hash, next_cron_fire, recompute_next_fire,
     };
 
     #[test]
@@ -506,4 +583,151 @@ mod tests {
         );
         assert_eq!(Trigger::Manual.type_tag(), "manual");
     }
+
+    #[test]
+    fn test_recompute_next_fire_follows_trigger() {
+        let now = chrono::Utc::now();
+        let mut routine = Routine {
+            id: uuid::Uuid::new_v4(),
+            name: "hourly".to_string(),
+            description: String::new(),
+            user_id: "default".to_string(),
+            enabled: true,
+            trigger: Trigger::Cron {
+                schedule: "0 0 * * * *".to_string(),
+            },
+            action: RoutineAction::Lightweight {
+                prompt: "check".to_string(),
+                context_paths: Vec::new(),
+                max_tokens: 128,
+            },
+            guardrails: RoutineGuardrails::default(),
+            notify: NotifyConfig::default(),
+            last_run_at: None,
+            next_fire_at: None,
+            run_count: 0,
+            consecutive_failures: 0,
+            state: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        };
+        let next = recompute_next_fire(&routine).expect("cron routine has a next fire");
+        assert!(next > now);
+        assert!(next <= now + chrono::Duration::hours(1));
+
+        routine.trigger = Trigger::Cron {
+            schedule: "not a cron".to_string(),
+        };
+        assert!(recompute_next_fire(&routine).is_none());
+
+        routine.trigger = Trigger::Manual;
+        assert!(recompute_next_fire(&routine).is_none());
+    }
+
+    fn guarded_routine(guardrails: RoutineGuardrails) -> Routine {
+        let now = chrono::Utc::now();
+        Routine {
+            id: uuid::Uuid::new_v4(),
+            name: "guarded".to_string(),
+            description: String::new(),
+            user_id: "default".to_string(),
+            enabled: true,
+            trigger: Trigger::Manual,
+            action: RoutineAction::Lightweight {
+                prompt: "check".to_string(),
+                context_paths: Vec::new(),
+                max_tokens: 128,
+            },
+            guardrails,
+            notify: NotifyConfig::default(),
+            last_run_at: None,
+            next_fire_at: None,
+            run_count: 0,
+            consecutive_failures: 0,
+            state: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn run_at(
+        routine: &Routine,
+        started_at: chrono::DateTime<chrono::Utc>,
+        status: RunStatus,
+        trigger_detail: &str,
+    ) -> RoutineRun {
+        RoutineRun {
+            id: uuid::Uuid::new_v4(),
+            routine_id: routine.id,
+            trigger_type: "event".to_string(),
+            trigger_detail: Some(trigger_detail.to_string()),
+            started_at,
+            completed_at: (status != RunStatus::Running).then_some(started_at),
+            status,
+            result_summary: None,
+            tokens_used: None,
+            job_id: None,
+            created_at: started_at,
+        }
+    }
+
+    #[test]
+    fn test_can_run_routine_decisions() {
+        let now = chrono::Utc::now();
+        let minutes = chrono::Duration::minutes;
+        let routine = guarded_routine(RoutineGuardrails {
+            cooldown: std::time::Duration::from_secs(60),
+            max_concurrent: 1,
+            dedup_window: Some(std::time::Duration::from_secs(3_600)),
+        });
+
+        assert_eq!(
+            can_run_routine(&routine, &[], now, Some("a")),
+            RunDecision::Allow
+        );
+
+        let running = [run_at(&routine, now - minutes(30), RunStatus::Running, "a")];
+        assert_eq!(
+            can_run_routine(&routine, &running, now, Some("b")),
+            RunDecision::ConcurrencyLimited { active: 1 }
+        );
+
+        let just_ran = [run_at(
+            &routine,
+            now - chrono::Duration::seconds(10),
+            RunStatus::Ok,
+            "a",
+        )];
+        assert_eq!(
+            can_run_routine(&routine, &just_ran, now, Some("b")),
+            RunDecision::CooldownActive {
+                until: now + chrono::Duration::seconds(50)
+            }
+        );
+
+        let earlier = [run_at(&routine, now - minutes(30), RunStatus::Ok, "a")];
+        assert_eq!(
+            can_run_routine(&routine, &earlier, now, Some("a")),
+            RunDecision::DedupSuppressed {
+                window: std::time::Duration::from_secs(3_600)
+            }
+        );
+        assert_eq!(
+            can_run_routine(&routine, &earlier, now, Some("b")),
+            RunDecision::Allow
+        );
+
+        let outside_window = [run_at(&routine, now - minutes(90), RunStatus::Failed, "a")];
+        assert_eq!(
+            can_run_routine(&routine, &outside_window, now, Some("a")),
+            RunDecision::Allow
+        );
+
+        let mut from_last_run = routine.clone();
+        from_last_run.last_run_at = Some(now - chrono::Duration::seconds(30));
+        assert!(matches!(
+            can_run_routine(&from_last_run, &[], now, None),
+            RunDecision::CooldownActive { .. }
+        ));
+    }
 }
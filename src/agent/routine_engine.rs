@@ -20,7 +20,8 @@ use tokio::sync::{RwLock, mpsc};
 use uuid::Uuid;
 
 use crate::agent::routine::{
-    NotifyConfig, Routine, RoutineAction, RoutineRun, RunStatus, Trigger, next_cron_fire,
+    NotifyConfig, Routine, RoutineAction, RoutineRun, RunDecision, RunStatus, Trigger,
+    can_run_routine, recompute_next_fire,
 };
 use crate::channels::{IncomingMessage, OutgoingResponse};
 use crate::config::RoutineConfig;
@@ -28,6 +29,9 @@ use crate::db::Database;
 use crate::llm::{ChatMessage, CompletionRequest, FinishReason, LlmProvider};
 use crate::workspace::Workspace;
 
+/// Recent runs inspected when applying a routine's `dedup_window`.
+const DEDUP_RECENT_RUNS_LIMIT: i64 = 50;
+
 /// The routine execution engine.
 pub struct RoutineEngine {
     config: RoutineConfig,
@@ -184,6 +188,11 @@ impl RoutineEngine {
                 message.channel,
                 truncate(&message.content, 180)
             );
+            if routine.guardrails.dedup_window.is_some()
+                && !self.check_dedup(routine, &detail).await
+            {
+                continue;
+            }
             self.spawn_fire(routine.clone(), "event", Some(detail));
             fired += 1;
         }
@@ -329,6 +338,32 @@ impl RoutineEngine {
         true
     }
 
+    /// Suppress a repeat event whose detail matches a run inside the
+    /// routine's `dedup_window`.
+    async fn check_dedup(&self, routine: &Routine, detail: &str) -> bool {
+        let runs = match self
+            .store
+            .list_routine_runs(routine.id, DEDUP_RECENT_RUNS_LIMIT)
+            .await
+        {
+            Ok(runs) => runs,
+            Err(e) => {
+                tracing::error!(
+                    routine = %routine.name,
+                    "Failed to load recent runs for dedup: {}", e
+                );
+                return false;
+            }
+        };
+        match can_run_routine(routine, &runs, Utc::now(), Some(detail)) {
+            RunDecision::Allow => true,
+            decision => {
+                tracing::debug!(routine = %routine.name, ?decision, "Skipped: guardrail");
+                false
+            }
+        }
+    }
+
     async fn check_concurrent(&self, routine: &Routine) -> bool {
         match self.store.count_running_routine_runs(routine.id).await {
             Ok(count) => count < routine.guardrails.max_concurrent as i64,
@@ -398,11 +433,7 @@ async fn execute_routine(ctx: EngineContext, routine: Routine, run: RoutineRun)
 
     // Update routine runtime state
     let now = Utc::now();
-    let next_fire = if let Trigger::Cron { ref schedule } = routine.trigger {
-        next_cron_fire(schedule).unwrap_or(None)
-    } else {
-        None
-    };
+    let next_fire = recompute_next_fire(&routine);
 
     let new_failures = if status == RunStatus::Failed {
         routine.consecutive_failures + 1
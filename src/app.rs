@@ -47,6 +47,7 @@ pub struct AppComponents {
     pub skill_registry: Option<Arc<std::sync::RwLock<SkillRegistry>>>,
     pub skill_catalog: Option<Arc<SkillCatalog>>,
     pub cost_guard: Arc<crate::agent::cost_guard::CostGuard>,
+    pub module_catalog: Arc<crate::platform::ModuleCatalog>,
     pub session: Arc<SessionManager>,
 }
 
@@ -139,10 +140,32 @@ impl AppBuilder {
                                     "LIBSQL_AUTH_TOKEN is required when LIBSQL_URL is set"
                                 )
                             })?;
-                    LibSqlBackend::new_remote_replica(db_path, url, token.expose_secret()).await?
+                    let backend =
+                        LibSqlBackend::new_remote_replica(db_path, url, token.expose_secret())
+                            .await?;
+                    if let Err(e) = backend
+                        .sync_with_retry(
+                            crate::db::libsql::STARTUP_SYNC_MAX_RETRIES,
+                            crate::db::libsql::STARTUP_SYNC_BACKOFF,
+                        )
+                        .await
+                    {
+                        tracing::warn!("Initial remote replica sync failed: {}", e);
+                    }
+                    match self.config.database.libsql_read_replica_path {
+                        Some(ref read_path) => {
+                            backend
+                                .with_read_replica(read_path, url, token.expose_secret())
+                                .await?
+                        }
+                        None => backend,
+                    }
                 } else {
                     LibSqlBackend::new_local(db_path).await?
                 };
+                let backend = backend
+                    .with_max_pool_size(self.config.database.pool_size)
+                    .with_intent_audit_quota(self.config.database.intent_audit_quota);
                 backend.run_migrations().await?;
                 tracing::info!("libSQL database connected and migrations applied");
 
@@ -694,6 +717,16 @@ impl AppBuilder {
     pub async fn build_all(mut self) -> Result<AppComponents, anyhow::Error> {
         self.init_database().await?;
         self.init_secrets().await?;
+        let module_catalog = Arc::new(crate::platform::ModuleCatalog::load(
+            self.config.agent.module_manifests_path.as_deref(),
+        )?);
+        if let Some(ref path) = self.config.agent.module_manifests_path {
+            tracing::info!(
+                "Loaded {} module(s) into catalog from {}",
+                module_catalog.modules().len(),
+                path.display()
+            );
+        }
 
         let (llm, cheap_llm) = self.init_llm()?;
         let (safety, tools, embeddings, workspace) = self.init_tools(&llm).await?;
@@ -773,6 +806,7 @@ impl AppBuilder {
             skill_registry,
             skill_catalog,
             cost_guard,
+            module_catalog,
             session: self.session,
         })
     }
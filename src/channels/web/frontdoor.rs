@@ -6,21 +6,22 @@
 //! 3) provision per-user enclave through a configurable command
 //! 4) return redirect URL for the dedicated instance
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::future::Future;
 use std::path::PathBuf;
 use std::process::Stdio;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
 
+use async_trait::async_trait;
 use base64::Engine;
 use chrono::{DateTime, Utc};
 use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey};
-use rand::{Rng, distributions::Alphanumeric};
+use rand::{Rng, RngCore, distributions::Alphanumeric, seq::SliceRandom};
 use sha3::{Digest, Keccak256};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, broadcast};
 use url::Url;
 use uuid::Uuid;
 
@@ -35,12 +36,17 @@ use crate::channels::web::types::{
     FrontdoorOnboardingStep3Payload, FrontdoorOnboardingStep4Payload,
     FrontdoorOnboardingTranscriptArtifactResponse, FrontdoorOnboardingTurn,
     FrontdoorPolicyTemplate, FrontdoorPolicyTemplateConfig, FrontdoorPolicyTemplateLibraryResponse,
-    FrontdoorPolicyTemplateRiskProfile, FrontdoorRuntimeControlRequest,
+    FrontdoorPolicyTemplateRiskProfile, FrontdoorProvisionLogLine, FrontdoorRuntimeControlRequest,
     FrontdoorRuntimeControlResponse, FrontdoorSessionResponse, FrontdoorSessionSummaryResponse,
-    FrontdoorSessionTimelineEvent, FrontdoorSessionTimelineResponse, FrontdoorSuggestConfigRequest,
-    FrontdoorSuggestConfigResponse, FrontdoorTodoEvidenceRefs, FrontdoorUserConfig,
-    FrontdoorVerificationExplanationResponse, FrontdoorVerifyRequest, FrontdoorVerifyResponse,
+    FrontdoorSessionTimelineEvent, FrontdoorSessionTimelineResponse, FrontdoorStatusWebhookPayload,
+    FrontdoorSuggestConfigRequest, FrontdoorSuggestConfigResponse, FrontdoorTodoEvidenceRefs,
+    FrontdoorUserConfig, FrontdoorVerificationExplanationResponse, FrontdoorVerifyRequest,
+    FrontdoorVerifyResponse,
 };
+use crate::config::{HeartbeatConfig, HyperliquidNetwork};
+use crate::db::{FrontdoorSessionRecord, FrontdoorSessionStore};
+use crate::observability::Metrics;
+use crate::util::{hex, path_lock, write_file_atomically};
 
 #[derive(Debug, Clone)]
 pub struct FrontdoorConfig {
@@ -53,6 +59,191 @@ pub struct FrontdoorConfig {
     pub verify_app_base_url: Option<String>,
     pub session_ttl_secs: u64,
     pub poll_interval_ms: u64,
+    /// Platform-wide override that requires signed fallback receipts no matter
+    /// what the submitted user config asks for.
+    pub force_signed_receipts: bool,
+    /// Wallet store records not updated within this window are dropped by
+    /// compaction. `0` keeps records forever.
+    pub wallet_retention_secs: u64,
+    /// Most challenges a single wallet may request within
+    /// `challenge_rate_window_secs`. `0` disables the limit.
+    pub challenge_rate_limit: u32,
+    pub challenge_rate_window_secs: u64,
+    /// Sessions are dropped this long after `expires_at`, giving operators
+    /// time to inspect terminal sessions before they are purged.
+    pub terminal_retention_secs: u64,
+    /// Hard deadline for the provision command; on overrun the child is
+    /// killed and the session fails. `0` disables the deadline.
+    pub provision_timeout_secs: u64,
+    /// Wallet signing prompt rendered by `create_challenge`; see
+    /// [`validate_challenge_template`]. `None` uses the built-in template.
+    pub challenge_template: Option<String>,
+    /// Chains a challenge may be issued for. Requests without a `chain_id`
+    /// use chain `1`, which must then be listed too.
+    pub allowed_chain_ids: Vec<u64>,
+    /// Current terms of service version, recorded on configs that accept them.
+    pub terms_version: String,
+    /// Most times `retry_provision` may re-run provisioning for one session.
+    /// `0` disables retries.
+    pub max_provision_retries: u32,
+    /// Mainnet perp tickers accepted in symbol allowlists. `None` uses the
+    /// built-in list.
+    pub hyperliquid_mainnet_perps: Option<Vec<String>>,
+    /// Testnet counterpart of `hyperliquid_mainnet_perps`.
+    pub hyperliquid_testnet_perps: Option<Vec<String>>,
+    /// Replace the built-in list of each unpinned network with the exchange
+    /// `meta` universe at startup. Off by default so validation never depends
+    /// on a live exchange response.
+    pub hyperliquid_perp_refresh: bool,
+    /// Most recent onboarding turns kept per session, on top of the opening
+    /// greeting; older turns are trimmed. `0` keeps every turn.
+    pub max_onboarding_turns: usize,
+    /// Receives a [`FrontdoorStatusWebhookPayload`] POST whenever a session
+    /// reaches `ready` or `failed`. Delivery is best effort.
+    pub status_webhook_url: Option<String>,
+}
+
+/// Errors returned by [`FrontdoorService`] operations.
+///
+/// `Display` output matches the plain-string errors the HTTP API returned
+/// before this type existed; [`FrontdoorError::status_code`] picks the status.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FrontdoorError {
+    #[error("wallet_address must be a 0x-prefixed 40-hex address")]
+    InvalidWalletAddress,
+    #[error("session_id must be a valid UUID")]
+    InvalidSessionId,
+    #[error("session not found")]
+    SessionNotFound,
+    #[error("challenge expired")]
+    SessionExpired,
+    /// The `Expires At` line embedded in the signed challenge has passed.
+    #[error("signed challenge expired at {expires_at}; request a new challenge")]
+    ChallengeMessageExpired { expires_at: String },
+    #[error("signed message does not match challenge")]
+    SignatureMismatch,
+    /// A named field line of the signed message differs from the challenge.
+    #[error("signed message '{field}' does not match challenge")]
+    ChallengeFieldMismatch { field: &'static str },
+    /// The signature did not recover to the session wallet.
+    #[error("{0}")]
+    SignatureInvalid(String),
+    /// The caller is not the wallet or Privy user the session was issued to.
+    #[error("{0}")]
+    IdentityMismatch(String),
+    #[error("{0}")]
+    PreflightFailed(String),
+    #[error("chain_id {chain_id} is not allowed")]
+    ChainNotAllowed { chain_id: u64 },
+    #[error("too many challenges requested for this wallet; retry in {retry_after_secs}s")]
+    RateLimited { retry_after_secs: i64 },
+    /// The signed challenge nonce was already consumed by an earlier verify.
+    #[error("challenge nonce has already been used")]
+    NonceReplayed,
+    /// The session already used every provisioning retry it is allowed.
+    #[error("provisioning retry limit of {limit} reached; request a new challenge")]
+    RetryLimitReached { limit: u32 },
+    /// Malformed or policy-violating request input.
+    #[error("{0}")]
+    InvalidRequest(String),
+    /// The submitted config failed validation on one or more fields.
+    #[error("{0}")]
+    InvalidConfig(FieldErrors),
+    /// Local artifact or database persistence failed.
+    #[error("{0}")]
+    Storage(String),
+}
+
+impl FrontdoorError {
+    /// HTTP status the gateway responds with for this error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::InvalidWalletAddress
+            | Self::InvalidSessionId
+            | Self::ChainNotAllowed { .. }
+            | Self::InvalidRequest(_)
+            | Self::InvalidConfig(_) => 400,
+            Self::SignatureMismatch
+            | Self::ChallengeFieldMismatch { .. }
+            | Self::SignatureInvalid(_) => 401,
+            Self::IdentityMismatch(_) => 403,
+            Self::SessionNotFound => 404,
+            Self::NonceReplayed | Self::RetryLimitReached { .. } => 409,
+            Self::SessionExpired | Self::ChallengeMessageExpired { .. } => 410,
+            Self::PreflightFailed(_) => 422,
+            Self::RateLimited { .. } => 429,
+            Self::Storage(_) => 500,
+        }
+    }
+}
+
+/// One frontdoor config field that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// `FrontdoorUserConfig` field name, e.g. `request_timeout_ms`.
+    pub field: String,
+    /// Human-readable reason, already naming the field.
+    pub message: String,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Every field problem found in one config, in check order.
+///
+/// Displays as the messages joined with `"; "` for callers that only want a
+/// string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    fn push(&mut self, field: &str, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Record `result`'s error against `field`, returning the value if any.
+    fn capture<T>(&mut self, field: &str, result: Result<T, String>) -> Option<T> {
+        result.map_err(|message| self.push(field, message)).ok()
+    }
+
+    fn into_result(self) -> Result<(), Self> {
+        if self.0.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// Offending field names, in check order (may repeat).
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|e| e.field.as_str())
+    }
+
+    pub fn into_vec(self) -> Vec<FieldError> {
+        self.0
+    }
+}
+
+impl std::ops::Deref for FieldErrors {
+    type Target = [FieldError];
+
+    fn deref(&self) -> &[FieldError] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for FieldErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
@@ -68,7 +259,7 @@ struct WalletSessionRecord {
     updated_at: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 enum SessionStatus {
     AwaitingSignature,
     Provisioning,
@@ -87,9 +278,20 @@ impl SessionStatus {
             Self::Expired => "expired",
         }
     }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "awaiting_signature" => Some(Self::AwaitingSignature),
+            "provisioning" => Some(Self::Provisioning),
+            "ready" => Some(Self::Ready),
+            "failed" => Some(Self::Failed),
+            "expired" => Some(Self::Expired),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 enum ProvisioningSource {
     Unknown,
     Command,
@@ -103,7 +305,50 @@ struct ProvisioningDecision {
     reason: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignatureScheme {
+    PersonalSign,
+    Eip712,
+}
+
+impl SignatureScheme {
+    fn parse(raw: Option<&str>) -> Result<Self, String> {
+        match raw.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("personal_sign") => Ok(Self::PersonalSign),
+            Some("eip712") => Ok(Self::Eip712),
+            Some(other) => Err(format!(
+                "signature_scheme must be personal_sign or eip712, got '{other}'"
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::PersonalSign => "personal_sign",
+            Self::Eip712 => "eip712",
+        }
+    }
+}
+
+/// EIP-712 domain the frontdoor authorization struct is signed under.
 #[derive(Debug, Clone, Copy)]
+struct Eip712MessageDomain<'a> {
+    name: &'a str,
+    version: &'a str,
+    chain_id: u64,
+}
+
+impl Eip712MessageDomain<'static> {
+    fn frontdoor(chain_id: u64) -> Self {
+        Self {
+            name: FRONTDOOR_EIP712_DOMAIN_NAME,
+            version: FRONTDOOR_EIP712_DOMAIN_VERSION,
+            chain_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 enum RuntimeState {
     Running,
     Paused,
@@ -120,16 +365,83 @@ impl RuntimeState {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct OnboardingTurnState {
     role: String,
     message: String,
     created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone)]
+/// Position in the onboarding chat flow.
+///
+/// Persisted as its string form; an unrecognised stored step falls back to
+/// [`OnboardingStep::CaptureObjective`] so onboarding restarts instead of
+/// stalling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(from = "String", into = "String")]
+enum OnboardingStep {
+    #[default]
+    CaptureObjective,
+    ProposePlan,
+    CollectRequiredVariables,
+    ConfirmAndSign,
+    ReadyToSign,
+}
+
+impl OnboardingStep {
+    #[cfg(test)]
+    const ALL: [Self; 5] = [
+        Self::CaptureObjective,
+        Self::ProposePlan,
+        Self::CollectRequiredVariables,
+        Self::ConfirmAndSign,
+        Self::ReadyToSign,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CaptureObjective => "capture_objective",
+            Self::ProposePlan => "propose_plan",
+            Self::CollectRequiredVariables => "collect_required_variables",
+            Self::ConfirmAndSign => "confirm_and_sign",
+            Self::ReadyToSign => "ready_to_sign",
+        }
+    }
+}
+
+impl std::str::FromStr for OnboardingStep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "capture_objective" => Ok(Self::CaptureObjective),
+            "propose_plan" => Ok(Self::ProposePlan),
+            "collect_required_variables" => Ok(Self::CollectRequiredVariables),
+            "confirm_and_sign" => Ok(Self::ConfirmAndSign),
+            "ready_to_sign" => Ok(Self::ReadyToSign),
+            other => Err(format!("unknown onboarding step '{other}'")),
+        }
+    }
+}
+
+impl From<String> for OnboardingStep {
+    fn from(raw: String) -> Self {
+        raw.parse().unwrap_or_else(|err| {
+            tracing::warn!("{err}; restarting onboarding");
+            Self::default()
+        })
+    }
+}
+
+impl From<OnboardingStep> for String {
+    fn from(step: OnboardingStep) -> Self {
+        step.as_str().to_string()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct OnboardingState {
-    current_step: String,
+    current_step: OnboardingStep,
     completed: bool,
     objective: Option<String>,
     missing_fields: Vec<String>,
@@ -143,7 +455,7 @@ struct OnboardingState {
     updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct TimelineEvent {
     seq_id: u64,
     event_type: String,
@@ -153,14 +465,14 @@ struct TimelineEvent {
     created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone)]
-struct FundingPreflightCheckState {
-    check_id: String,
-    status: String,
-    detail: String,
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FundingPreflightCheckState {
+    pub check_id: String,
+    pub status: String,
+    pub detail: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct FundingPreflightState {
     status: String,
     failure_category: Option<String>,
@@ -183,12 +495,16 @@ impl ProvisioningSource {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct ProvisioningSession {
     id: Uuid,
     wallet_address: String,
     privy_user_id: Option<String>,
+    // Bearer tokens stay in memory only; they are never written to the
+    // session store.
+    #[serde(skip)]
     privy_identity_token: Option<String>,
+    #[serde(skip)]
     privy_access_token: Option<String>,
     chain_id: u64,
     message: String,
@@ -204,8 +520,19 @@ struct ProvisioningSession {
     eigen_app_id: Option<String>,
     signature_verification_latency_ms: Option<u64>,
     provisioning_started_at: Option<DateTime<Utc>>,
+    /// Provisioning re-runs started through `retry_provision`.
+    #[serde(default)]
+    provision_retries: u32,
     onboarding: OnboardingState,
     timeline: Vec<TimelineEvent>,
+    /// Latest redacted provisioner output, kept apart from `timeline` so log
+    /// volume never evicts semantic events.
+    #[serde(default)]
+    provision_logs: VecDeque<ProvisionCommandLog>,
+    #[serde(default)]
+    provision_log_lines_total: u64,
+    #[serde(skip, default = "new_timeline_sender")]
+    timeline_tx: broadcast::Sender<FrontdoorSessionTimelineEvent>,
     next_timeline_seq_id: u64,
     funding_preflight: FundingPreflightState,
     error: Option<String>,
@@ -218,18 +545,165 @@ struct ProvisioningSession {
 struct FrontdoorState {
     sessions: HashMap<Uuid, ProvisioningSession>,
     wallets: HashMap<String, WalletSessionRecord>,
+    /// Challenge issue times per wallet inside the current rate window.
+    challenge_issued_at: HashMap<String, Vec<DateTime<Utc>>>,
+    /// Consume times of verified `(wallet, nonce)` pairs, kept for one
+    /// session TTL so a signed challenge cannot be replayed.
+    consumed_nonces: HashMap<(String, String), DateTime<Utc>>,
+}
+
+/// Time source for session lifecycle timestamps, swappable in tests.
+trait FrontdoorClock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+struct SystemClock;
+
+impl FrontdoorClock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Randomness for challenge nonces and generated gateway auth keys,
+/// swappable in tests for a seeded generator.
+trait RngSource: Send + Sync {
+    /// `len` random ASCII alphanumeric characters.
+    fn alphanumeric(&self, len: usize) -> String;
+}
+
+/// Production source: `thread_rng`, a CSPRNG seeded and periodically
+/// reseeded from the OS.
+struct ThreadRngSource;
+
+impl RngSource for ThreadRngSource {
+    fn alphanumeric(&self, len: usize) -> String {
+        sample_alphanumeric(&mut rand::thread_rng(), len)
+    }
+}
+
+impl<R: RngCore + Send> RngSource for std::sync::Mutex<R> {
+    fn alphanumeric(&self, len: usize) -> String {
+        let mut rng = self
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        sample_alphanumeric(&mut *rng, len)
+    }
 }
 
 pub struct FrontdoorService {
     config: FrontdoorConfig,
     state: RwLock<FrontdoorState>,
     store_path: PathBuf,
+    clock: Arc<dyn FrontdoorClock>,
+    preflight_checks: std::sync::RwLock<Vec<Arc<dyn FundingPreflightCheck>>>,
+    session_store: OnceLock<Arc<dyn FrontdoorSessionStore>>,
+    metrics: Arc<dyn Metrics>,
+    rng: Arc<dyn RngSource>,
+    provisioning_backend: Box<dyn ProvisioningBackend>,
+    perp_universe: std::sync::RwLock<HyperliquidPerpUniverse>,
+    /// Background task started by [`FrontdoorService::spawn_reaper`].
+    reaper: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
+impl Drop for FrontdoorService {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Perp tickers accepted in Hyperliquid symbol allowlists, per network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HyperliquidPerpUniverse {
+    mainnet: Vec<String>,
+    testnet: Vec<String>,
+}
+
+impl HyperliquidPerpUniverse {
+    /// Configured lists where set, the built-in fallback lists otherwise.
+    fn from_config(config: &FrontdoorConfig) -> Self {
+        let fallback = |symbols: &[&str]| symbols.iter().map(|s| s.to_string()).collect();
+        Self {
+            mainnet: config
+                .hyperliquid_mainnet_perps
+                .clone()
+                .unwrap_or_else(|| fallback(&HYPERLIQUID_MAINNET_PERPS)),
+            testnet: config
+                .hyperliquid_testnet_perps
+                .clone()
+                .unwrap_or_else(|| fallback(&HYPERLIQUID_TESTNET_PERPS)),
+        }
+    }
+
+    fn symbols(&self, network: HyperliquidNetwork) -> &[String] {
+        match network {
+            HyperliquidNetwork::Mainnet => &self.mainnet,
+            HyperliquidNetwork::Testnet => &self.testnet,
+        }
+    }
+}
+
+/// Terms version used when `GATEWAY_FRONTDOOR_TERMS_VERSION` is unset.
+pub const DEFAULT_TERMS_VERSION: &str = "2026-01";
 const FRONTDOOR_CURRENT_CONFIG_VERSION: u32 = 2;
 const FRONTDOOR_SUPPORTED_CONFIG_VERSIONS: [u32; 2] = [1, 2];
 const ONBOARDING_GATEWAY_AUTH_FROM_CONFIG_MARKER: &str = "__from_config__";
 const FRONTDOOR_TIMELINE_EVENT_CAP: usize = 1200;
+const FRONTDOOR_TIMELINE_BROADCAST_CAPACITY: usize = 256;
+const FRONTDOOR_PROVISION_LOG_CAP: usize = 500;
+const FRONTDOOR_PROVISION_LOG_SUMMARY_EVERY: u64 = 100;
+const FRONTDOOR_STALLED_PROVISIONING_SECS: i64 = 900;
+const FRONTDOOR_WALLET_COMPACTION_INTERVAL_SECS: u64 = 3_600;
+const FRONTDOOR_STATUS_WEBHOOK_TIMEOUT_SECS: u64 = 10;
+/// Ceiling for configured durations (100 years): longer values behave the
+/// same in practice, and anything near `i64` seconds overflows `DateTime`.
+const FRONTDOOR_MAX_CONFIG_DURATION_SECS: u64 = 100 * 365 * 24 * 60 * 60;
+const HYPERLIQUID_META_TIMEOUT_SECS: u64 = 10;
+const PROVISION_LOG_REDACTED: &str = "***redacted***";
+/// `domain_overrides` key every profile accepts; carries preflight check overrides.
+const FRONTDOOR_PREFLIGHT_OVERRIDE_KEY: &str = "frontdoor_preflight";
+const FRONTDOOR_PREFLIGHT_CHECK_TIMEOUT_SECS: u64 = 10;
+/// Launch-time seed artifacts the launchpad stores in `domain_overrides`.
+const LAUNCHPAD_SEED_OVERRIDE_KEYS: [&str; 5] = [
+    "agent_seed_identity_md",
+    "agent_seed_mission_md",
+    "launchpad_setup_mermaid",
+    "launchpad_blueprint_summary",
+    "launchpad_blueprint_model",
+];
+const FRONTDOOR_EIP712_DOMAIN_NAME: &str = "Enclagent Frontdoor";
+const FRONTDOOR_EIP712_DOMAIN_VERSION: &str = "1";
+const FRONTDOOR_EIP712_AUTHORIZATION_TYPE: &str = concat!(
+    "FrontdoorAuthorization(address wallet,string sessionId,string nonce,",
+    "string issuedAt,string expiresAt)"
+);
+/// Symbols a new mainnet config trades until the user picks its own.
+const DEFAULT_MAINNET_SYMBOL_ALLOWLIST: [&str; 2] = ["BTC", "ETH"];
+/// Testnet counterpart of [`DEFAULT_MAINNET_SYMBOL_ALLOWLIST`].
+const DEFAULT_TESTNET_SYMBOL_ALLOWLIST: [&str; 2] = ["BTC", "ETH"];
+
+/// Default `symbol_allowlist` for configs on `network`.
+fn default_symbol_allowlist(network: HyperliquidNetwork) -> Vec<String> {
+    let symbols: &[&str] = match network {
+        HyperliquidNetwork::Mainnet => &DEFAULT_MAINNET_SYMBOL_ALLOWLIST,
+        HyperliquidNetwork::Testnet => &DEFAULT_TESTNET_SYMBOL_ALLOWLIST,
+    };
+    symbols.iter().map(|symbol| symbol.to_string()).collect()
+}
+
+/// Built-in perp tickers for networks without a configured list, uppercased the way `normalize_symbols` stores them (so `kPEPE` is `KPEPE`).
+const HYPERLIQUID_MAINNET_PERPS: [&str; 64] = [
+    "BTC", "ETH", "SOL", "HYPE", "XRP", "DOGE", "ADA", "AVAX", "LINK", "SUI", "ARB", "OP", "APT",
+    "BNB", "LTC", "BCH", "DOT", "NEAR", "ATOM", "TIA", "SEI", "INJ", "TON", "TRX", "XLM", "HBAR",
+    "ETC", "FIL", "AAVE", "UNI", "MKR", "CRV", "LDO", "PENDLE", "ENA", "ONDO", "TAO", "RENDER",
+    "FET", "WLD", "PYTH", "JTO", "JUP", "WIF", "POPCAT", "KPEPE", "KSHIB", "KBONK", "TRUMP",
+    "FARTCOIN", "BERA", "KAITO", "EIGEN", "ZRO", "STX", "ORDI", "DYDX", "GMX", "SNX", "COMP",
+    "IMX", "APE", "BLUR", "MON",
+];
+const HYPERLIQUID_TESTNET_PERPS: [&str; 24] = [
+    "BTC", "ETH", "SOL", "HYPE", "XRP", "DOGE", "ADA", "AVAX", "LINK", "SUI", "ARB", "OP", "APT",
+    "BNB", "LTC", "ATOM", "TIA", "SEI", "INJ", "NEAR", "AAVE", "UNI", "KPEPE", "MON",
+];
 const FRONTDOOR_SUPPORTED_DOMAINS: [&str; 8] = [
     "general",
     "developer",
@@ -242,108 +716,513 @@ const FRONTDOOR_SUPPORTED_DOMAINS: [&str; 8] = [
 ];
 
 impl FrontdoorService {
-    pub fn new(config: FrontdoorConfig) -> Arc<Self> {
-        Self::new_with_store_path(config, default_wallet_store_path())
+    pub fn new(config: FrontdoorConfig, metrics: Arc<dyn Metrics>) -> Arc<Self> {
+        let service = Self::new_with_store_path(
+            config,
+            default_wallet_store_path(),
+            Arc::new(SystemClock),
+            metrics,
+        );
+        if service.config.hyperliquid_perp_refresh
+            && let Ok(handle) = tokio::runtime::Handle::try_current()
+        {
+            let weak = Arc::downgrade(&service);
+            handle.spawn(async move {
+                if let Some(service) = weak.upgrade() {
+                    service.refresh_perp_universe().await;
+                }
+            });
+        }
+        if service.config.wallet_retention_secs > 0
+            && let Ok(handle) = tokio::runtime::Handle::try_current()
+        {
+            let weak = Arc::downgrade(&service);
+            handle.spawn(async move {
+                let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+                    FRONTDOOR_WALLET_COMPACTION_INTERVAL_SECS,
+                ));
+                // The first tick fires immediately; startup already compacted.
+                ticker.tick().await;
+                loop {
+                    ticker.tick().await;
+                    let Some(service) = weak.upgrade() else {
+                        break;
+                    };
+                    if let Err(err) = service.compact_wallet_store().await {
+                        tracing::warn!(
+                            error = %err,
+                            "Failed to compact frontdoor wallet session store"
+                        );
+                    }
+                }
+            });
+        }
+        service
     }
 
-    fn new_with_store_path(config: FrontdoorConfig, store_path: PathBuf) -> Arc<Self> {
-        let wallets = load_wallet_store(&store_path).unwrap_or_default().wallets;
+    fn new_with_store_path(
+        config: FrontdoorConfig,
+        store_path: PathBuf,
+        clock: Arc<dyn FrontdoorClock>,
+        metrics: Arc<dyn Metrics>,
+    ) -> Arc<Self> {
+        Self::new_with_sources(
+            config,
+            store_path,
+            clock,
+            metrics,
+            Arc::new(ThreadRngSource),
+        )
+    }
+
+    fn new_with_sources(
+        config: FrontdoorConfig,
+        store_path: PathBuf,
+        clock: Arc<dyn FrontdoorClock>,
+        metrics: Arc<dyn Metrics>,
+        rng: Arc<dyn RngSource>,
+    ) -> Arc<Self> {
+        let provisioning_backend = provisioning_backend_from_config(&config);
+        Self::new_with_backend(
+            config,
+            store_path,
+            clock,
+            metrics,
+            rng,
+            provisioning_backend,
+        )
+    }
+
+    fn new_with_backend(
+        config: FrontdoorConfig,
+        store_path: PathBuf,
+        clock: Arc<dyn FrontdoorClock>,
+        metrics: Arc<dyn Metrics>,
+        rng: Arc<dyn RngSource>,
+        provisioning_backend: Box<dyn ProvisioningBackend>,
+    ) -> Arc<Self> {
+        let mut wallets = load_wallet_store(&store_path).unwrap_or_default().wallets;
+        if prune_wallet_records(&mut wallets, config.wallet_retention_secs, clock.now()) > 0 {
+            let store = WalletSessionStore {
+                wallets: wallets.clone(),
+            };
+            if let Err(err) = persist_wallet_store(&store_path, &store) {
+                tracing::warn!(
+                    error = %err,
+                    "Failed to persist compacted frontdoor wallet session store"
+                );
+            }
+        }
+        let perp_universe = HyperliquidPerpUniverse::from_config(&config);
         Arc::new(Self {
             config,
             state: RwLock::new(FrontdoorState {
                 sessions: HashMap::new(),
                 wallets,
+                challenge_issued_at: HashMap::new(),
+                consumed_nonces: HashMap::new(),
             }),
             store_path,
+            clock,
+            preflight_checks: std::sync::RwLock::new(default_funding_preflight_checks()),
+            session_store: OnceLock::new(),
+            metrics,
+            rng,
+            provisioning_backend,
+            perp_universe: std::sync::RwLock::new(perp_universe),
+            reaper: std::sync::Mutex::new(None),
         })
     }
 
     #[cfg(test)]
     fn new_for_tests(config: FrontdoorConfig, store_path: PathBuf) -> Arc<Self> {
-        Self::new_with_store_path(config, store_path)
+        Self::new_with_store_path(
+            config,
+            store_path,
+            Arc::new(SystemClock),
+            Arc::new(crate::observability::NoopMetrics),
+        )
     }
 
-    pub fn bootstrap(&self) -> FrontdoorBootstrapResponse {
-        let command_configured = parse_provision_command_template(
-            self.config.provision_command.as_deref().unwrap_or_default(),
-        )
-        .is_ok();
-        let default_url_configured =
-            is_non_empty_config_value(self.config.default_instance_url.as_deref());
-        let provisioning_backend = if command_configured {
-            ProvisioningSource::Command.as_str().to_string()
-        } else if self.config.allow_default_instance_fallback && default_url_configured {
-            ProvisioningSource::DefaultInstanceUrl.as_str().to_string()
-        } else {
-            ProvisioningSource::Unconfigured.as_str().to_string()
-        };
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
 
-        FrontdoorBootstrapResponse {
-            enabled: true,
-            require_privy: self.config.require_privy,
-            privy_app_id: self.config.privy_app_id.clone(),
-            privy_client_id: self.config.privy_client_id.clone(),
-            provisioning_backend,
-            dynamic_provisioning_enabled: command_configured,
-            default_instance_url_configured: default_url_configured,
-            default_instance_fallback_enabled: self.config.allow_default_instance_fallback
-                && default_url_configured,
-            default_instance_looks_eigencloud: self
-                .config
-                .default_instance_url
-                .as_deref()
-                .is_some_and(looks_like_eigencloud_url),
-            poll_interval_ms: self.config.poll_interval_ms,
-            mandatory_steps: mandatory_frontdoor_steps(),
+    /// Replace the built-in perp lists with the exchange `meta` universe for
+    /// each network without a configured list. A failed fetch keeps the
+    /// current list. Runs at startup only when `hyperliquid_perp_refresh` is set.
+    pub async fn refresh_perp_universe(&self) {
+        for (network, configured) in [
+            (
+                HyperliquidNetwork::Mainnet,
+                self.config.hyperliquid_mainnet_perps.is_some(),
+            ),
+            (
+                HyperliquidNetwork::Testnet,
+                self.config.hyperliquid_testnet_perps.is_some(),
+            ),
+        ] {
+            if configured {
+                continue;
+            }
+            match fetch_hyperliquid_perp_universe(network).await {
+                Ok(symbols) => {
+                    let mut universe = self
+                        .perp_universe
+                        .write()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    match network {
+                        HyperliquidNetwork::Mainnet => universe.mainnet = symbols,
+                        HyperliquidNetwork::Testnet => universe.testnet = symbols,
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        ?network,
+                        error = %err,
+                        "Failed to load Hyperliquid perp universe; keeping the built-in list"
+                    );
+                }
+            }
         }
     }
 
-    pub fn config_contract(&self) -> FrontdoorConfigContractResponse {
-        FrontdoorConfigContractResponse {
-            contract_id: "enclagent.frontdoor.launchpad".to_string(),
-            current_config_version: FRONTDOOR_CURRENT_CONFIG_VERSION,
-            supported_config_versions: FRONTDOOR_SUPPORTED_CONFIG_VERSIONS.to_vec(),
-            supported_domains: FRONTDOOR_SUPPORTED_DOMAINS
-                .iter()
-                .map(|domain| (*domain).to_string())
-                .collect(),
-            domain_profiles: frontdoor_domain_profiles(),
-            mandatory_steps: mandatory_frontdoor_steps(),
-            enums: FrontdoorConfigEnums {
-                hyperliquid_network: vec!["testnet".to_string(), "mainnet".to_string()],
-                paper_live_policy: vec![
-                    "paper_only".to_string(),
-                    "paper_first".to_string(),
-                    "live_allowed".to_string(),
-                ],
-                custody_mode: vec![
-                    "operator_wallet".to_string(),
-                    "user_wallet".to_string(),
-                    "dual_mode".to_string(),
-                ],
-                information_sharing_scope: vec![
-                    "none".to_string(),
-                    "signals_only".to_string(),
-                    "signals_and_execution".to_string(),
-                    "full_audit".to_string(),
-                ],
-                kill_switch_behavior: vec![
-                    "pause_agent".to_string(),
-                    "cancel_open_orders".to_string(),
-                    "cancel_and_flatten".to_string(),
-                ],
-                verification_backend: vec![
-                    "eigencloud_primary".to_string(),
-                    "fallback_only".to_string(),
-                ],
-                verification_eigencloud_auth_scheme: vec![
-                    "bearer".to_string(),
-                    "api_key".to_string(),
-                ],
-            },
-            defaults: FrontdoorConfigDefaults {
-                profile_domain: "general".to_string(),
-                hyperliquid_network: "testnet".to_string(),
+    fn perp_universe(&self) -> HyperliquidPerpUniverse {
+        self.perp_universe
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Append a funding preflight check after the built-in ones.
+    pub fn register_funding_preflight_check(&self, check: Arc<dyn FundingPreflightCheck>) {
+        self.preflight_checks
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(check);
+    }
+
+    /// Attach durable session storage and restore any sessions it holds.
+    ///
+    /// Every later session mutation is written through to `store`. Rows past
+    /// the expiry grace window are deleted rather than restored. Sessions
+    /// caught mid-provisioning are marked failed, since nothing resumes them.
+    /// Returns the number of sessions restored.
+    pub async fn attach_session_store(
+        &self,
+        store: Arc<dyn FrontdoorSessionStore>,
+    ) -> Result<usize, FrontdoorError> {
+        if self.session_store.set(Arc::clone(&store)).is_err() {
+            return Err(FrontdoorError::Storage(
+                "frontdoor session store is already attached".to_string(),
+            ));
+        }
+        store
+            .delete_frontdoor_sessions_expired_before(self.session_grace_cutoff())
+            .await
+            .map_err(|e| {
+                FrontdoorError::Storage(format!("failed pruning persisted frontdoor sessions: {e}"))
+            })?;
+        let records = store.list_frontdoor_sessions().await.map_err(|e| {
+            FrontdoorError::Storage(format!("failed loading persisted frontdoor sessions: {e}"))
+        })?;
+
+        let mut state = self.state.write().await;
+        let mut restored = 0;
+        let mut interrupted = Vec::new();
+        for record in records {
+            let mut session: ProvisioningSession = match serde_json::from_value(record.payload) {
+                Ok(session) => session,
+                Err(err) => {
+                    tracing::warn!(
+                        session_id = %record.session_id,
+                        wallet = %record.wallet_address,
+                        error = %err,
+                        "Skipping unreadable persisted frontdoor session"
+                    );
+                    continue;
+                }
+            };
+            if matches!(session.status, SessionStatus::Provisioning) {
+                mark_provisioning_interrupted(&mut session, self.now());
+                interrupted.push(session.id);
+            }
+            if let std::collections::hash_map::Entry::Vacant(slot) =
+                state.sessions.entry(session.id)
+            {
+                slot.insert(session);
+                restored += 1;
+            }
+        }
+        self.purge_expired(&mut state);
+        let records: Vec<_> = interrupted
+            .iter()
+            .filter_map(|id| state.sessions.get(id))
+            .map(|session| self.session_record(session))
+            .collect();
+        drop(state);
+        for record in records {
+            self.write_session_record(record).await;
+        }
+        Ok(restored)
+    }
+
+    fn challenge_rate_window(&self) -> chrono::Duration {
+        config_duration(self.config.challenge_rate_window_secs)
+    }
+
+    fn session_ttl(&self) -> chrono::Duration {
+        config_duration(self.config.session_ttl_secs)
+    }
+
+    fn terminal_retention(&self) -> chrono::Duration {
+        config_duration(self.config.terminal_retention_secs)
+    }
+
+    fn session_grace_cutoff(&self) -> DateTime<Utc> {
+        self.now() - self.terminal_retention()
+    }
+
+    /// Purge expired sessions every heartbeat interval instead of only when a
+    /// request arrives. Replaces any reaper already running; the task holds a
+    /// weak reference and ends on [`Self::shutdown`] or when the service drops.
+    pub fn spawn_reaper(self: &Arc<Self>, heartbeat: &HeartbeatConfig) {
+        let interval = std::time::Duration::from_secs(heartbeat.interval_secs.max(1));
+        let weak = Arc::downgrade(self);
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick fires immediately; nothing has expired yet.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(service) = weak.upgrade() else {
+                    break;
+                };
+                let mut state = service.state.write().await;
+                service.purge_expired(&mut state);
+            }
+        });
+        let previous = self
+            .reaper
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .replace(handle);
+        if let Some(previous) = previous {
+            previous.abort();
+        }
+    }
+
+    /// Stop the background reaper, if one is running.
+    pub fn shutdown(&self) {
+        let handle = self
+            .reaper
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        if let Some(handle) = handle {
+            handle.abort();
+        }
+    }
+
+    /// Drop expired sessions from `state`. The matching store rows are
+    /// deleted in the background so callers never wait on the store while
+    /// holding the state lock.
+    fn purge_expired(&self, state: &mut FrontdoorState) {
+        let purged = purge_expired_sessions(
+            state,
+            self.now(),
+            self.challenge_rate_window(),
+            self.terminal_retention(),
+            self.session_ttl(),
+        );
+        if purged == 0 {
+            return;
+        }
+        let Some(store) = self.session_store.get().cloned() else {
+            return;
+        };
+        let cutoff = self.session_grace_cutoff();
+        tokio::spawn(async move {
+            if let Err(err) = store.delete_frontdoor_sessions_expired_before(cutoff).await {
+                tracing::warn!(error = %err, "Failed to delete expired frontdoor sessions");
+            }
+        });
+    }
+
+    /// Snapshot `session` for the attached store, if any, with credentials
+    /// blanked. Take it under the state lock and pass it to
+    /// [`Self::write_session_record`] once the lock is released.
+    fn session_record(&self, session: &ProvisioningSession) -> Option<FrontdoorSessionRecord> {
+        self.session_store.get()?;
+        let mut payload = match serde_json::to_value(session) {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::warn!(
+                    session_id = %session.id,
+                    error = %err,
+                    "Failed to serialize frontdoor session"
+                );
+                return None;
+            }
+        };
+        redact_persisted_secrets(&mut payload);
+        Some(FrontdoorSessionRecord {
+            session_id: session.id,
+            user_id: session.wallet_address.clone(),
+            wallet_address: session.wallet_address.clone(),
+            version: i64::try_from(session.version).unwrap_or(i64::MAX),
+            status: session.status.as_str().to_string(),
+            detail: session.detail.clone(),
+            privy_user_id: session.privy_user_id.clone(),
+            profile_name: session.config.as_ref().map(|cfg| cfg.profile_name.clone()),
+            instance_url: session.instance_url.clone(),
+            verify_url: session.verify_url.clone(),
+            eigen_app_id: session.eigen_app_id.clone(),
+            error: session.error.clone(),
+            payload,
+            created_at: session.created_at,
+            updated_at: session.updated_at,
+            expires_at: session.expires_at,
+        })
+    }
+
+    /// Write a snapshot from [`Self::session_record`] to the attached store.
+    /// Failures are logged; the in-memory session remains authoritative.
+    async fn write_session_record(&self, record: Option<FrontdoorSessionRecord>) {
+        let (Some(store), Some(record)) = (self.session_store.get(), record) else {
+            return;
+        };
+        if let Err(err) = store.upsert_frontdoor_session(&record).await {
+            tracing::warn!(
+                session_id = %record.session_id,
+                wallet = %record.wallet_address,
+                error = %err,
+                "Failed to persist frontdoor session"
+            );
+        }
+    }
+
+    async fn persist_session_by_id(&self, session_id: Uuid) {
+        if self.session_store.get().is_none() {
+            return;
+        }
+        let record = {
+            let state = self.state.read().await;
+            state
+                .sessions
+                .get(&session_id)
+                .and_then(|session| self.session_record(session))
+        };
+        self.write_session_record(record).await;
+    }
+
+    fn funding_preflight_checks(&self) -> Vec<Arc<dyn FundingPreflightCheck>> {
+        self.preflight_checks
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Drop wallet records older than `wallet_retention_secs` and rewrite the
+    /// store once if anything was pruned. Returns the number of records dropped.
+    pub async fn compact_wallet_store(&self) -> Result<usize, FrontdoorError> {
+        let mut state = self.state.write().await;
+        let pruned = prune_wallet_records(
+            &mut state.wallets,
+            self.config.wallet_retention_secs,
+            self.now(),
+        );
+        if pruned > 0 {
+            let store = WalletSessionStore {
+                wallets: state.wallets.clone(),
+            };
+            persist_wallet_store(&self.store_path, &store).map_err(FrontdoorError::Storage)?;
+        }
+        Ok(pruned)
+    }
+
+    pub fn bootstrap(&self) -> FrontdoorBootstrapResponse {
+        let command_configured = parse_provision_command_template(
+            self.config.provision_command.as_deref().unwrap_or_default(),
+        )
+        .is_ok();
+        let default_url_configured =
+            is_non_empty_config_value(self.config.default_instance_url.as_deref());
+        let provisioning_backend = if command_configured {
+            ProvisioningSource::Command.as_str().to_string()
+        } else if self.config.allow_default_instance_fallback && default_url_configured {
+            ProvisioningSource::DefaultInstanceUrl.as_str().to_string()
+        } else {
+            ProvisioningSource::Unconfigured.as_str().to_string()
+        };
+
+        FrontdoorBootstrapResponse {
+            enabled: true,
+            require_privy: self.config.require_privy,
+            privy_app_id: self.config.privy_app_id.clone(),
+            privy_client_id: self.config.privy_client_id.clone(),
+            provisioning_backend,
+            dynamic_provisioning_enabled: command_configured,
+            default_instance_url_configured: default_url_configured,
+            default_instance_fallback_enabled: self.config.allow_default_instance_fallback
+                && default_url_configured,
+            default_instance_looks_eigencloud: self
+                .config
+                .default_instance_url
+                .as_deref()
+                .is_some_and(looks_like_eigencloud_url),
+            poll_interval_ms: self.config.poll_interval_ms,
+            mandatory_steps: mandatory_frontdoor_steps(),
+            terms_version: self.config.terms_version.clone(),
+        }
+    }
+
+    pub fn config_contract(&self) -> FrontdoorConfigContractResponse {
+        FrontdoorConfigContractResponse {
+            contract_id: "enclagent.frontdoor.launchpad".to_string(),
+            current_config_version: FRONTDOOR_CURRENT_CONFIG_VERSION,
+            supported_config_versions: FRONTDOOR_SUPPORTED_CONFIG_VERSIONS.to_vec(),
+            supported_domains: FRONTDOOR_SUPPORTED_DOMAINS
+                .iter()
+                .map(|domain| (*domain).to_string())
+                .collect(),
+            domain_profiles: frontdoor_domain_profiles(),
+            mandatory_steps: mandatory_frontdoor_steps(),
+            enums: FrontdoorConfigEnums {
+                hyperliquid_network: vec!["testnet".to_string(), "mainnet".to_string()],
+                paper_live_policy: vec![
+                    "paper_only".to_string(),
+                    "paper_first".to_string(),
+                    "live_allowed".to_string(),
+                ],
+                custody_mode: vec![
+                    "operator_wallet".to_string(),
+                    "user_wallet".to_string(),
+                    "dual_mode".to_string(),
+                ],
+                information_sharing_scope: vec![
+                    "none".to_string(),
+                    "signals_only".to_string(),
+                    "signals_and_execution".to_string(),
+                    "full_audit".to_string(),
+                ],
+                kill_switch_behavior: vec![
+                    "pause_agent".to_string(),
+                    "cancel_open_orders".to_string(),
+                    "cancel_and_flatten".to_string(),
+                ],
+                verification_backend: vec![
+                    "eigencloud_primary".to_string(),
+                    "fallback_only".to_string(),
+                ],
+                verification_eigencloud_auth_scheme: vec![
+                    "bearer".to_string(),
+                    "api_key".to_string(),
+                ],
+            },
+            defaults: FrontdoorConfigDefaults {
+                profile_domain: "general".to_string(),
+                hyperliquid_network: "testnet".to_string(),
                 paper_live_policy: "paper_only".to_string(),
                 request_timeout_ms: 10_000,
                 max_retries: 3,
@@ -354,7 +1233,7 @@ impl FrontdoorService {
                 per_trade_notional_cap_usd: 100,
                 max_leverage: 2,
                 max_slippage_bps: 50,
-                symbol_allowlist: vec!["BTC".to_string(), "ETH".to_string()],
+                symbol_allowlist: default_symbol_allowlist(HyperliquidNetwork::Testnet),
                 symbol_denylist: Vec::new(),
                 custody_mode: "user_wallet".to_string(),
                 information_sharing_scope: "signals_only".to_string(),
@@ -372,7 +1251,7 @@ impl FrontdoorService {
 
     pub fn policy_template_library(&self) -> FrontdoorPolicyTemplateLibraryResponse {
         FrontdoorPolicyTemplateLibraryResponse {
-            generated_at: Utc::now().to_rfc3339(),
+            generated_at: self.now().to_rfc3339(),
             templates: frontdoor_policy_templates(),
         }
     }
@@ -380,9 +1259,9 @@ impl FrontdoorService {
     pub fn suggest_config(
         &self,
         req: FrontdoorSuggestConfigRequest,
-    ) -> Result<FrontdoorSuggestConfigResponse, String> {
+    ) -> Result<FrontdoorSuggestConfigResponse, FrontdoorError> {
         let connected_wallet = normalize_wallet_address(&req.wallet_address)
-            .ok_or_else(|| "wallet_address must be a 0x-prefixed 40-hex address".to_string())?;
+            .ok_or(FrontdoorError::InvalidWalletAddress)?;
         let domain = normalize_domain_name(
             req.domain
                 .as_deref()
@@ -390,7 +1269,8 @@ impl FrontdoorService {
                 .trim()
                 .to_ascii_lowercase()
                 .as_str(),
-        )?;
+        )
+        .map_err(FrontdoorError::InvalidRequest)?;
 
         let mut assumptions = Vec::new();
         let mut warnings = Vec::new();
@@ -400,6 +1280,7 @@ impl FrontdoorService {
                 &connected_wallet,
                 req.gateway_auth_key.as_deref(),
                 domain.as_str(),
+                self.rng.as_ref(),
             )
         });
 
@@ -409,11 +1290,12 @@ impl FrontdoorService {
                 .as_deref()
                 .filter(|v| is_valid_gateway_auth_key(v))
                 .map(str::to_string)
-                .unwrap_or_else(generate_gateway_auth_key);
+                .unwrap_or_else(|| generate_gateway_auth_key(self.rng.as_ref()));
         }
 
         config.profile_domain = domain.clone();
         config.accept_terms = true;
+        config.accepted_terms_version = Some(self.config.terms_version.clone());
 
         apply_intent_overrides(
             &mut config,
@@ -422,10 +1304,24 @@ impl FrontdoorService {
             &mut assumptions,
             &mut warnings,
         );
-        normalize_suggested_config(&mut config, &connected_wallet, &mut assumptions);
+        normalize_suggested_config(
+            &mut config,
+            &connected_wallet,
+            self.rng.as_ref(),
+            &self.perp_universe(),
+            &mut assumptions,
+            &mut warnings,
+        );
+        if self.config.force_signed_receipts {
+            enforce_signed_fallback_receipts(&mut config, &mut assumptions);
+        }
         config.inference_warnings = warnings.clone();
-        validate_user_config(&config)?;
-        validate_wallet_association(&config, &connected_wallet)?;
+        // The acceptance time is stamped at verify, when the user actually signs.
+        let mut accepted = config.clone();
+        stamp_terms_acceptance(&mut accepted, self.now());
+        validate_user_config(&accepted).map_err(FrontdoorError::InvalidConfig)?;
+        validate_wallet_association(&config, &connected_wallet)
+            .map_err(FrontdoorError::InvalidRequest)?;
 
         Ok(FrontdoorSuggestConfigResponse {
             config,
@@ -438,23 +1334,65 @@ impl FrontdoorService {
     pub async fn create_challenge(
         &self,
         req: FrontdoorChallengeRequest,
-    ) -> Result<FrontdoorChallengeResponse, String> {
+    ) -> Result<FrontdoorChallengeResponse, FrontdoorError> {
         let wallet = normalize_wallet_address(&req.wallet_address)
-            .ok_or_else(|| "wallet_address must be a 0x-prefixed 40-hex address".to_string())?;
+            .ok_or(FrontdoorError::InvalidWalletAddress)?;
+        // The chain is rendered into the signed message, so the field check
+        // in `verify_and_start` binds the signature to it.
+        let chain_id = req.chain_id.unwrap_or(1);
+        if !self.config.allowed_chain_ids.contains(&chain_id) {
+            return Err(FrontdoorError::ChainNotAllowed { chain_id });
+        }
+        // The id is rendered into the signed message; a line break could
+        // smuggle in a field line such as `Expires At`.
+        if req
+            .privy_user_id
+            .as_deref()
+            .is_some_and(|id| id.contains(char::is_control))
+        {
+            return Err(FrontdoorError::InvalidRequest(
+                "privy_user_id must not contain control characters".to_string(),
+            ));
+        }
 
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
+        if self.config.challenge_rate_limit > 0
+            && let Some(issued) = state.challenge_issued_at.get(&wallet)
+            && issued.len() >= self.config.challenge_rate_limit as usize
+        {
+            let retry_after = issued
+                .iter()
+                .min()
+                .map(|oldest| (*oldest + self.challenge_rate_window() - self.now()).num_seconds())
+                .unwrap_or(0)
+                .max(1);
+            return Err(FrontdoorError::RateLimited {
+                retry_after_secs: retry_after,
+            });
+        }
 
         let version = state.wallets.get(&wallet).map_or(1, |r| r.version + 1);
         let session_id = Uuid::new_v4();
-        let nonce = random_nonce(24);
-        let now = Utc::now();
-        let expires_at = now + chrono::Duration::seconds(self.config.session_ttl_secs as i64);
-        let chain_id = req.chain_id.unwrap_or(1);
+        let nonce = random_nonce(self.rng.as_ref(), 24);
+        let now = self.now();
+        let expires_at = now + self.session_ttl();
         let privy = req.privy_user_id.as_deref().unwrap_or("wallet_only");
-        let message = format!(
-            "Enclagent Gasless Authorization Transaction\nWallet: {wallet}\nPrivy Link: {privy}\nChain ID: {chain_id}\nSession ID: {session_id}\nVersion: v{version}\nNonce: {nonce}\nIssued At: {}\n\nSign this gasless authorization transaction to verify wallet control and start provisioning your dedicated Enclagent enclave.",
-            now.to_rfc3339()
+        let message = render_challenge_message(
+            self.config
+                .challenge_template
+                .as_deref()
+                .unwrap_or(DEFAULT_CHALLENGE_TEMPLATE),
+            &[
+                ("wallet", wallet.as_str()),
+                ("privy_user_id", privy),
+                ("chain_id", &chain_id.to_string()),
+                ("session_id", &session_id.to_string()),
+                ("version", &version.to_string()),
+                ("nonce", &nonce),
+                ("issued_at", &now.to_rfc3339()),
+                ("expires_at", &expires_at.to_rfc3339()),
+            ],
         );
 
         let mut session = ProvisioningSession {
@@ -477,8 +1415,12 @@ impl FrontdoorService {
             eigen_app_id: None,
             signature_verification_latency_ms: None,
             provisioning_started_at: None,
+            provision_retries: 0,
             onboarding: default_onboarding_state(session_id, now),
             timeline: Vec::new(),
+            provision_logs: VecDeque::new(),
+            provision_log_lines_total: 0,
+            timeline_tx: new_timeline_sender(),
             next_timeline_seq_id: 1,
             funding_preflight: pending_funding_preflight(now),
             error: None,
@@ -501,12 +1443,23 @@ impl FrontdoorService {
             &todo_snapshot,
             "system",
         );
-        self.persist_onboarding_transcript(&session)?;
+        self.persist_onboarding_transcript(&session)
+            .map_err(FrontdoorError::Storage)?;
+        let record = self.session_record(&session);
         state.sessions.insert(session_id, session);
+        if self.config.challenge_rate_limit > 0 {
+            state
+                .challenge_issued_at
+                .entry(wallet.clone())
+                .or_default()
+                .push(now);
+        }
+        drop(state);
+        self.write_session_record(record).await;
 
         Ok(FrontdoorChallengeResponse {
             session_id: session_id.to_string(),
-            wallet_address: wallet,
+            wallet_address: to_checksummed_address(&wallet),
             message,
             expires_at: expires_at.to_rfc3339(),
             version,
@@ -516,34 +1469,114 @@ impl FrontdoorService {
     pub async fn verify_and_start(
         self: Arc<Self>,
         req: FrontdoorVerifyRequest,
-    ) -> Result<FrontdoorVerifyResponse, String> {
+    ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+        // Both outcomes can mutate the session (expiry, verification failure,
+        // provisioning start), so write it through either way.
+        let session_id = Uuid::parse_str(req.session_id.trim()).ok();
+        let result = Arc::clone(&self).verify_and_start_inner(req).await;
+        if let Some(session_id) = session_id {
+            self.persist_session_by_id(session_id).await;
+        }
+        result
+    }
+
+    async fn verify_and_start_inner(
+        self: Arc<Self>,
+        mut req: FrontdoorVerifyRequest,
+    ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
         let wallet = normalize_wallet_address(&req.wallet_address)
-            .ok_or_else(|| "wallet_address must be a 0x-prefixed 40-hex address".to_string())?;
+            .ok_or(FrontdoorError::InvalidWalletAddress)?;
+        if !address_checksum_matches(&req.wallet_address) {
+            return Err(FrontdoorError::InvalidRequest(
+                "wallet_address has an invalid EIP-55 checksum".to_string(),
+            ));
+        }
         if !is_signature_like(&req.signature) {
-            return Err("signature must be a 65-byte hex string (0x-prefixed)".to_string());
+            return Err(FrontdoorError::InvalidRequest(
+                "signature must be a 65-byte hex string (0x-prefixed)".to_string(),
+            ));
         }
-        validate_user_config(&req.config)?;
-        validate_wallet_association(&req.config, &wallet)?;
-
-        let session_id = Uuid::parse_str(req.session_id.trim())
-            .map_err(|_| "session_id must be a valid UUID".to_string())?;
+        let signature_scheme = SignatureScheme::parse(req.signature_scheme.as_deref())
+            .map_err(FrontdoorError::InvalidRequest)?;
+        let mut policy_overrides = Vec::new();
+        if self.config.force_signed_receipts {
+            enforce_signed_fallback_receipts(&mut req.config, &mut policy_overrides);
+        }
+        // Re-apply the suggest-config bounds so a hand-built config cannot skip
+        // them; anything clamping cannot fix is still rejected by validation.
+        stamp_terms_acceptance(&mut req.config, self.now());
+        let requested_config = req.config.clone();
+        clamp_config_bounds(&mut req.config);
+        let config_warnings = config_clamp_warnings(&requested_config, &req.config);
+        validate_user_config(&req.config).map_err(FrontdoorError::InvalidConfig)?;
+        validate_wallet_association(&req.config, &wallet)
+            .map_err(FrontdoorError::InvalidRequest)?;
+
+        let session_id =
+            Uuid::parse_str(req.session_id.trim()).map_err(|_| FrontdoorError::InvalidSessionId)?;
+
+        // Only pay for preflight when the session exists for this wallet; the
+        // locked checks below still decide the outcome.
+        let session_wallet = self
+            .state
+            .read()
+            .await
+            .sessions
+            .get(&session_id)
+            .map(|session| session.wallet_address.clone())
+            .ok_or(FrontdoorError::SessionNotFound)?;
+        if session_wallet != wallet {
+            return Err(FrontdoorError::IdentityMismatch(
+                "wallet_address does not match challenge session".to_string(),
+            ));
+        }
+        let preflight = self.run_funding_preflight(&wallet, &req.config).await;
 
         {
-            let mut state = self.state.write().await;
-            purge_expired_sessions(&mut state);
+            let mut guard = self.state.write().await;
+            self.purge_expired(&mut guard);
+            let state = &mut *guard;
             let session = state
                 .sessions
                 .get_mut(&session_id)
-                .ok_or_else(|| "session not found".to_string())?;
+                .ok_or(FrontdoorError::SessionNotFound)?;
 
             if session.wallet_address != wallet {
-                return Err("wallet_address does not match challenge session".to_string());
+                return Err(FrontdoorError::IdentityMismatch(
+                    "wallet_address does not match challenge session".to_string(),
+                ));
             }
             if self.config.require_privy {
                 let expected = session.privy_user_id.as_deref().unwrap_or("").trim();
                 let provided = req.privy_user_id.as_deref().unwrap_or("").trim();
                 if !expected.is_empty() && !provided.is_empty() && expected != provided {
-                    return Err("privy_user_id mismatch".to_string());
+                    return Err(FrontdoorError::IdentityMismatch(
+                        "privy_user_id mismatch".to_string(),
+                    ));
+                }
+            }
+
+            // Report an unsigned session's lapsed challenge by the expiry the
+            // wallet was asked to sign, even after purging already marked it
+            // expired. It is read from the issued message, never the request.
+            // Templates without `{expires_at}` rely on the session check alone.
+            if matches!(
+                session.status,
+                SessionStatus::AwaitingSignature | SessionStatus::Expired
+            ) && let Some(raw) =
+                challenge_message_field(&session.message, "Expires At").map(str::to_string)
+            {
+                let embedded = DateTime::parse_from_rfc3339(&raw).map_err(|_| {
+                    FrontdoorError::InvalidRequest(
+                        "challenge message has an invalid 'Expires At' timestamp".to_string(),
+                    )
+                })?;
+                if embedded < self.now() {
+                    session.status = SessionStatus::Expired;
+                    session.updated_at = self.now();
+                    session.detail =
+                        "Challenge expired. Request a new signature challenge.".to_string();
+                    return Err(FrontdoorError::ChallengeMessageExpired { expires_at: raw });
                 }
             }
 
@@ -554,32 +1587,84 @@ impl FrontdoorService {
                     session_id: session_id.to_string(),
                     status: session.status.as_str().to_string(),
                     detail: session.detail.clone(),
+                    config_warnings: Vec::new(),
                 });
             }
 
-            if session.expires_at < Utc::now() {
+            if session.expires_at < self.now() {
                 session.status = SessionStatus::Expired;
-                session.updated_at = Utc::now();
+                session.updated_at = self.now();
                 session.detail =
                     "Challenge expired. Request a new signature challenge.".to_string();
-                return Err("challenge expired".to_string());
+                return Err(FrontdoorError::SessionExpired);
             }
 
-            if !message_matches(&req.message, &session.message) {
-                return Err("signed message does not match challenge".to_string());
+            let signed_fields =
+                parse_challenge_message(&req.message).map_err(FrontdoorError::InvalidRequest)?;
+            let nonce_key = (wallet.clone(), signed_fields.nonce.clone());
+            if state.consumed_nonces.contains_key(&nonce_key) {
+                return Err(FrontdoorError::NonceReplayed);
+            }
+            // The field compare only names what differs; the line compare
+            // below binds the whole text, statement and Privy link included.
+            let issued_fields = parse_challenge_message(&session.message)
+                .map_err(FrontdoorError::InvalidRequest)?;
+            if let Some(field) = signed_fields.first_mismatch(&issued_fields) {
+                return Err(FrontdoorError::ChallengeFieldMismatch { field });
+            }
+            // Wallets may reflow whitespace, so compare trimmed non-blank lines.
+            if normalized_challenge_lines(&req.message)
+                .ne(normalized_challenge_lines(&session.message))
+            {
+                return Err(FrontdoorError::SignatureMismatch);
             }
             let signature_started = Instant::now();
-            verify_wallet_signature(&req.message, &req.signature, &wallet)?;
+            match signature_scheme {
+                SignatureScheme::PersonalSign => {
+                    verify_wallet_signature(&req.message, &req.signature, &wallet)
+                        .map_err(FrontdoorError::SignatureInvalid)?
+                }
+                SignatureScheme::Eip712 => {
+                    // The typed data carries no free text, so it must bind the
+                    // timestamps itself for the expiry checks to mean anything.
+                    let (Some(issued_at), Some(expires_at)) = (
+                        issued_fields.issued_at.as_deref(),
+                        issued_fields.expires_at.as_deref(),
+                    ) else {
+                        return Err(FrontdoorError::InvalidRequest(
+                            "eip712 signatures require 'Issued At' and 'Expires At' challenge lines"
+                                .to_string(),
+                        ));
+                    };
+                    let prehash = eip712_authorization_hash(
+                        &Eip712MessageDomain::frontdoor(session.chain_id),
+                        &session.id.to_string(),
+                        &wallet,
+                        &issued_fields.nonce,
+                        issued_at,
+                        expires_at,
+                    )
+                    .map_err(FrontdoorError::InvalidRequest)?;
+                    verify_wallet_signature_prehash(&prehash, &req.signature, &wallet)
+                        .map_err(FrontdoorError::SignatureInvalid)?
+                }
+            }
             let signature_latency_ms = (signature_started
                 .elapsed()
                 .as_millis()
                 .min(u128::from(u64::MAX))) as u64;
+            self.metrics.record_histogram(
+                "frontdoor_signature_verification_latency_ms",
+                signature_latency_ms as f64,
+                &[("scheme", signature_scheme.as_str())],
+            );
+            state.consumed_nonces.insert(nonce_key, self.now());
 
             session.config = Some(req.config.clone());
             session.privy_identity_token = req.privy_identity_token.clone();
             session.privy_access_token = req.privy_access_token.clone();
             session.signature_verification_latency_ms = Some(signature_latency_ms);
-            session.onboarding.current_step = "ready_to_sign".to_string();
+            session.onboarding.current_step = OnboardingStep::ReadyToSign;
             session.onboarding.completed = true;
             if session.onboarding.objective.is_none() {
                 session.onboarding.objective = req.config.inference_summary.clone();
@@ -592,8 +1677,9 @@ impl FrontdoorService {
                 signature_action: "Signature verification complete. Provisioning in progress."
                     .to_string(),
             });
-            session.onboarding.updated_at = Utc::now();
-            self.persist_onboarding_transcript(session)?;
+            session.onboarding.updated_at = self.now();
+            self.persist_onboarding_transcript(session)
+                .map_err(FrontdoorError::Storage)?;
             push_timeline_event(
                 session,
                 "signature_verified",
@@ -601,90 +1687,26 @@ impl FrontdoorService {
                 "Wallet signature verified",
                 "system",
             );
-
-            let preflight = evaluate_funding_preflight(session, &req.config);
-            session.funding_preflight = preflight.clone();
-            if preflight.status != "passed" {
-                session.status = SessionStatus::Failed;
-                session.updated_at = Utc::now();
-                session.detail = format!(
-                    "Funding preflight failed ({})",
-                    preflight.failure_category.as_deref().unwrap_or("policy")
+            for note in &policy_overrides {
+                push_timeline_event(
+                    session,
+                    "platform_policy_override",
+                    "awaiting_signature",
+                    note,
+                    "system",
                 );
-                session.error = Some(format!(
-                    "funding preflight failed: {}",
-                    preflight.failure_category.as_deref().unwrap_or("policy")
-                ));
-                let detail = session.detail.clone();
+            }
+            for warning in &config_warnings {
                 push_timeline_event(
                     session,
-                    "funding_preflight_failed",
-                    "failed",
-                    &detail,
+                    "config_clamped",
+                    "awaiting_signature",
+                    warning,
                     "system",
                 );
-                let summary = todo_status_summary(&build_gateway_todos(session));
-                push_timeline_event(session, "todo_snapshot", "failed", &summary, "system");
-                return Err(session
-                    .error
-                    .clone()
-                    .unwrap_or_else(|| "funding preflight failed".to_string()));
             }
-            push_timeline_event(
-                session,
-                "funding_preflight_passed",
-                "awaiting_signature",
-                "Funding preflight checks passed",
-                "system",
-            );
-
-            let command_configured = self
-                .config
-                .provision_command
-                .as_deref()
-                .map(|value| !value.trim().is_empty())
-                .unwrap_or(false);
-            let default_fallback_ready = self.config.allow_default_instance_fallback
-                && self
-                    .config
-                    .default_instance_url
-                    .as_deref()
-                    .map(|value| !value.trim().is_empty())
-                    .unwrap_or(false);
-            let provisional_source = if command_configured {
-                ProvisioningSource::Command
-            } else if default_fallback_ready {
-                ProvisioningSource::DefaultInstanceUrl
-            } else {
-                ProvisioningSource::Unconfigured
-            };
-            session.provisioning_source = provisional_source;
 
-            session.status = SessionStatus::Provisioning;
-            session.updated_at = Utc::now();
-            session.error = None;
-            session.detail = "Provisioning dedicated enclave...".to_string();
-            session.provisioning_started_at = Some(Utc::now());
-            push_timeline_event(
-                session,
-                "provisioning_decision",
-                "provisioning",
-                &format!(
-                    "Provisioning backend selected: {} (dedicated={})",
-                    provisional_source.as_str(),
-                    provisional_source.dedicated_instance()
-                ),
-                "system",
-            );
-            push_timeline_event(
-                session,
-                "provisioning_started",
-                "provisioning",
-                "Provision command queued",
-                "system",
-            );
-            let summary = todo_status_summary(&build_gateway_todos(session));
-            push_timeline_event(session, "todo_snapshot", "provisioning", &summary, "system");
+            self.start_provisioning(session, preflight)?;
         }
 
         let svc = Arc::clone(&self);
@@ -696,30 +1718,255 @@ impl FrontdoorService {
             session_id: session_id.to_string(),
             status: "provisioning".to_string(),
             detail: "Provisioning started".to_string(),
+            config_warnings,
         })
     }
 
-    pub async fn get_session(&self, session_id: Uuid) -> Option<FrontdoorSessionResponse> {
-        let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
-        let session = state.sessions.get(&session_id)?;
-        Some(render_session_response(session))
+    /// Run the registered funding preflight checks. Checks may call out to
+    /// external services, so callers run this before taking the state lock.
+    async fn run_funding_preflight(
+        &self,
+        wallet: &str,
+        config: &FrontdoorUserConfig,
+    ) -> FundingPreflightState {
+        evaluate_funding_preflight(&self.funding_preflight_checks(), wallet, config, self.now())
+            .await
     }
 
+    /// Record `preflight` on `session` and, when it passed, move the session
+    /// to `Provisioning`. The caller spawns `run_provision` once the state
+    /// lock is released.
+    fn start_provisioning(
+        &self,
+        session: &mut ProvisioningSession,
+        preflight: FundingPreflightState,
+    ) -> Result<(), FrontdoorError> {
+        let stage = session.status.as_str();
+        session.funding_preflight = preflight.clone();
+        if preflight.status != "passed" {
+            session.status = SessionStatus::Failed;
+            session.updated_at = self.now();
+            session.detail = format!(
+                "Funding preflight failed ({})",
+                preflight.failure_category.as_deref().unwrap_or("policy")
+            );
+            session.error = Some(format!(
+                "funding preflight failed: {}",
+                preflight.failure_category.as_deref().unwrap_or("policy")
+            ));
+            let detail = session.detail.clone();
+            push_timeline_event(
+                session,
+                "funding_preflight_failed",
+                "failed",
+                &detail,
+                "system",
+            );
+            let summary = todo_status_summary(&build_gateway_todos(session));
+            push_timeline_event(session, "todo_snapshot", "failed", &summary, "system");
+            self.notify_status_webhook(session);
+            return Err(FrontdoorError::PreflightFailed(
+                session
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "funding preflight failed".to_string()),
+            ));
+        }
+        push_timeline_event(
+            session,
+            "funding_preflight_passed",
+            stage,
+            "Funding preflight checks passed",
+            "system",
+        );
+
+        let command_configured = self
+            .config
+            .provision_command
+            .as_deref()
+            .map(|value| !value.trim().is_empty())
+            .unwrap_or(false);
+        let default_fallback_ready = self.config.allow_default_instance_fallback
+            && self
+                .config
+                .default_instance_url
+                .as_deref()
+                .map(|value| !value.trim().is_empty())
+                .unwrap_or(false);
+        let provisional_source = if command_configured {
+            ProvisioningSource::Command
+        } else if default_fallback_ready {
+            ProvisioningSource::DefaultInstanceUrl
+        } else {
+            ProvisioningSource::Unconfigured
+        };
+        session.provisioning_source = provisional_source;
+
+        session.status = SessionStatus::Provisioning;
+        session.updated_at = self.now();
+        session.error = None;
+        session.detail = "Provisioning dedicated enclave...".to_string();
+        session.provisioning_started_at = Some(self.now());
+        push_timeline_event(
+            session,
+            "provisioning_decision",
+            "provisioning",
+            &format!(
+                "Provisioning backend selected: {} (dedicated={})",
+                provisional_source.as_str(),
+                provisional_source.dedicated_instance()
+            ),
+            "system",
+        );
+        push_timeline_event(
+            session,
+            "provisioning_started",
+            "provisioning",
+            "Provision command queued",
+            "system",
+        );
+        let summary = todo_status_summary(&build_gateway_todos(session));
+        push_timeline_event(session, "todo_snapshot", "provisioning", &summary, "system");
+        Ok(())
+    }
+
+    /// Re-run preflight and provisioning for a failed session whose wallet
+    /// signature was already verified, without asking for a new signature.
+    ///
+    /// Each session may retry at most `max_provision_retries` times, and only
+    /// before its challenge expires.
+    pub async fn retry_provision(
+        self: Arc<Self>,
+        session_id: Uuid,
+    ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+        let result = Arc::clone(&self).retry_provision_inner(session_id).await;
+        self.persist_session_by_id(session_id).await;
+        result
+    }
+
+    async fn retry_provision_inner(
+        self: Arc<Self>,
+        session_id: Uuid,
+    ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+        // A verified session's config never changes, so preflight can run
+        // against a copy taken before the write lock.
+        let verified = self
+            .state
+            .read()
+            .await
+            .sessions
+            .get(&session_id)
+            .and_then(|session| {
+                session.signature_verification_latency_ms?;
+                Some((session.wallet_address.clone(), session.config.clone()?))
+            });
+        let preflight = match verified {
+            Some((wallet, config)) => Some(self.run_funding_preflight(&wallet, &config).await),
+            None => None,
+        };
+
+        {
+            let mut guard = self.state.write().await;
+            self.purge_expired(&mut guard);
+            let session = guard
+                .sessions
+                .get_mut(&session_id)
+                .ok_or(FrontdoorError::SessionNotFound)?;
+
+            // A session verified only after the unlocked read has no preflight
+            // yet; it is refused like an unverified one.
+            let preflight = match (
+                &session.config,
+                session.signature_verification_latency_ms,
+                preflight,
+            ) {
+                (Some(config), Some(_), Some(preflight)) => {
+                    if config.gateway_auth_key.trim().is_empty() {
+                        return Err(FrontdoorError::InvalidRequest(
+                            "session was restored without its credentials; start a new session"
+                                .to_string(),
+                        ));
+                    }
+                    preflight
+                }
+                _ => {
+                    return Err(FrontdoorError::InvalidRequest(
+                        "session has no verified wallet signature to retry with".to_string(),
+                    ));
+                }
+            };
+            if !matches!(session.status, SessionStatus::Failed) {
+                return Err(FrontdoorError::InvalidRequest(format!(
+                    "only failed sessions can be retried (status: {})",
+                    session.status.as_str()
+                )));
+            }
+            if session.expires_at < self.now() {
+                return Err(FrontdoorError::SessionExpired);
+            }
+            let limit = self.config.max_provision_retries;
+            if session.provision_retries >= limit {
+                return Err(FrontdoorError::RetryLimitReached { limit });
+            }
+
+            session.provision_retries += 1;
+            let note = format!(
+                "Provisioning retry {} of {limit} requested",
+                session.provision_retries
+            );
+            push_timeline_event(session, "provision_retry", "failed", &note, "system");
+            self.start_provisioning(session, preflight)?;
+        }
+
+        let svc = Arc::clone(&self);
+        tokio::spawn(async move {
+            svc.run_provision(session_id).await;
+        });
+
+        Ok(FrontdoorVerifyResponse {
+            session_id: session_id.to_string(),
+            status: "provisioning".to_string(),
+            detail: "Provisioning retry started".to_string(),
+            config_warnings: Vec::new(),
+        })
+    }
+
+    pub async fn get_session(&self, session_id: Uuid) -> Option<FrontdoorSessionResponse> {
+        let mut state = self.state.write().await;
+        self.purge_expired(&mut state);
+        let session = state.sessions.get(&session_id)?;
+        Some(render_session_response(session))
+    }
+
+    /// Page through session summaries, newest first.
+    ///
+    /// `total` counts every session matching the filters, before `offset` and
+    /// `limit` are applied.
     pub async fn list_sessions(
         &self,
         wallet_filter: Option<&str>,
+        status_filter: Option<&str>,
+        offset: usize,
         limit: usize,
-    ) -> Result<(usize, Vec<FrontdoorSessionSummaryResponse>), String> {
+    ) -> Result<(usize, Vec<FrontdoorSessionSummaryResponse>), FrontdoorError> {
         let normalized_wallet = match wallet_filter {
-            Some(raw) => Some(normalize_wallet_address(raw).ok_or_else(|| {
-                "wallet_address must be a 0x-prefixed 40-hex address".to_string()
+            Some(raw) => {
+                Some(normalize_wallet_address(raw).ok_or(FrontdoorError::InvalidWalletAddress)?)
+            }
+            None => None,
+        };
+        let status = match status_filter {
+            Some(raw) => Some(SessionStatus::parse(raw).ok_or_else(|| {
+                FrontdoorError::InvalidRequest(format!(
+                    "status must be awaiting_signature, provisioning, ready, failed, or expired, got '{}'",
+                    raw.trim()
+                ))
             })?),
             None => None,
         };
 
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
 
         let mut filtered: Vec<FrontdoorSessionSummaryResponse> = state
             .sessions
@@ -728,31 +1975,39 @@ impl FrontdoorService {
                 Some(wallet) => &session.wallet_address == wallet,
                 None => true,
             })
+            .filter(|session| match status.as_ref() {
+                Some(status) => session.status.as_str() == status.as_str(),
+                None => true,
+            })
             .map(render_session_summary)
             .collect();
         filtered.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
 
         let total = filtered.len();
         let capped_limit = limit.min(100);
-        filtered.truncate(capped_limit);
+        let page = filtered
+            .into_iter()
+            .skip(offset)
+            .take(capped_limit)
+            .collect();
 
-        Ok((total, filtered))
+        Ok((total, page))
     }
 
     pub async fn list_sessions_full(
         &self,
         wallet_filter: Option<&str>,
         limit: usize,
-    ) -> Result<(usize, Vec<FrontdoorSessionResponse>), String> {
+    ) -> Result<(usize, Vec<FrontdoorSessionResponse>), FrontdoorError> {
         let normalized_wallet = match wallet_filter {
-            Some(raw) => Some(normalize_wallet_address(raw).ok_or_else(|| {
-                "wallet_address must be a 0x-prefixed 40-hex address".to_string()
-            })?),
+            Some(raw) => {
+                Some(normalize_wallet_address(raw).ok_or(FrontdoorError::InvalidWalletAddress)?)
+            }
             None => None,
         };
 
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
 
         let mut filtered: Vec<FrontdoorSessionResponse> = state
             .sessions
@@ -771,6 +2026,37 @@ impl FrontdoorService {
         Ok((total, filtered))
     }
 
+    /// Sessions an operator should triage: failed, stalled in provisioning, or
+    /// settled with blocking required todos. Most severe first, then most recent.
+    pub async fn sessions_needing_attention(
+        &self,
+        limit: usize,
+    ) -> Vec<FrontdoorSessionSummaryResponse> {
+        let mut state = self.state.write().await;
+        self.purge_expired(&mut state);
+
+        let now = self.now();
+        let mut flagged: Vec<(u8, DateTime<Utc>, FrontdoorSessionSummaryResponse)> = state
+            .sessions
+            .values()
+            .filter_map(|session| {
+                let severity = session_attention_severity(session, now)?;
+                Some((
+                    severity,
+                    session.updated_at,
+                    render_session_summary(session),
+                ))
+            })
+            .collect();
+        flagged.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+
+        flagged
+            .into_iter()
+            .take(limit.min(100))
+            .map(|(_, _, summary)| summary)
+            .collect()
+    }
+
     pub fn experience_manifest(&self) -> FrontdoorExperienceManifestResponse {
         FrontdoorExperienceManifestResponse {
             manifest_version: 1,
@@ -866,7 +2152,7 @@ impl FrontdoorService {
         session_id: Uuid,
     ) -> Option<FrontdoorOnboardingStateResponse> {
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
         let session = state.sessions.get(&session_id)?;
         Some(render_onboarding_state(session))
     }
@@ -877,60 +2163,88 @@ impl FrontdoorService {
     ) -> Option<FrontdoorOnboardingTranscriptArtifactResponse> {
         {
             let mut state = self.state.write().await;
-            purge_expired_sessions(&mut state);
+            self.purge_expired(&mut state);
             if let Some(session) = state.sessions.get(&session_id) {
                 return Some(render_onboarding_transcript_artifact(session));
             }
         }
 
+        // Artifacts are renamed into place whole, so a missing file is the only
+        // expected miss; anything unparsable means the file was tampered with.
         let path = onboarding_transcript_path(&self.store_path, session_id);
-        let data = std::fs::read(path).ok()?;
-        serde_json::from_slice::<FrontdoorOnboardingTranscriptArtifactResponse>(&data).ok()
+        let data = std::fs::read(&path).ok()?;
+        match serde_json::from_slice::<FrontdoorOnboardingTranscriptArtifactResponse>(&data) {
+            Ok(artifact) => Some(artifact),
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    "Ignoring unreadable onboarding transcript artifact: {}",
+                    e
+                );
+                None
+            }
+        }
     }
 
     pub async fn onboarding_chat(
         &self,
         req: FrontdoorOnboardingChatRequest,
-    ) -> Result<FrontdoorOnboardingChatResponse, String> {
-        let session_id = Uuid::parse_str(req.session_id.trim())
-            .map_err(|_| "session_id must be a valid UUID".to_string())?;
+    ) -> Result<FrontdoorOnboardingChatResponse, FrontdoorError> {
+        let session_id =
+            Uuid::parse_str(req.session_id.trim()).map_err(|_| FrontdoorError::InvalidSessionId)?;
         let message = req.message.trim();
         if message.is_empty() {
-            return Err("message must be non-empty".to_string());
+            return Err(FrontdoorError::InvalidRequest(
+                "message must be non-empty".to_string(),
+            ));
         }
 
+        let perp_universe = self.perp_universe();
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
         let session = state
             .sessions
             .get_mut(&session_id)
-            .ok_or_else(|| "session not found".to_string())?;
+            .ok_or(FrontdoorError::SessionNotFound)?;
 
-        let now = Utc::now();
+        let now = self.now();
         session.onboarding.transcript.push(OnboardingTurnState {
             role: "user".to_string(),
             message: message.to_string(),
             created_at: now,
         });
 
-        let assistant_message = match session.onboarding.current_step.as_str() {
-            "capture_objective" => {
+        let assistant_message = match session.onboarding.current_step {
+            _ if onboarding_rewind_requested(&message.to_ascii_lowercase()) => {
+                if session.onboarding.completed
+                    || session.onboarding.current_step == OnboardingStep::ReadyToSign
+                {
+                    "Onboarding is already complete and cannot be rewound. Continue with signature verification.".to_string()
+                } else {
+                    rewind_onboarding_to_objective(&mut session.onboarding);
+                    "Returned to Step 1. Describe your objective to generate a new plan."
+                        .to_string()
+                }
+            }
+            OnboardingStep::CaptureObjective => {
                 session.onboarding.objective = Some(message.to_string());
                 session.onboarding.step2_payload = Some(build_onboarding_step2_payload(
                     &session.wallet_address,
                     message,
+                    self.rng.as_ref(),
+                    &perp_universe,
                 ));
                 session.onboarding.step3_payload = Some(build_onboarding_step3_payload(
                     &session.onboarding.captured_variables,
                 ));
                 session.onboarding.step4_payload = None;
-                session.onboarding.current_step = "propose_plan".to_string();
+                session.onboarding.current_step = OnboardingStep::ProposePlan;
                 session.onboarding.completed = false;
                 session.onboarding.missing_fields =
                     unresolved_required_fields(&session.onboarding.step3_payload);
                 "Step 2 complete. Proposed config, risk posture, and module plan are generated. Reply 'confirm plan' to continue or provide a revised objective.".to_string()
             }
-            "propose_plan" => {
+            OnboardingStep::ProposePlan => {
                 let lower = message.to_ascii_lowercase();
                 if onboarding_plan_confirmed(&lower) {
                     session.onboarding.step3_payload = Some(build_onboarding_step3_payload(
@@ -938,12 +2252,12 @@ impl FrontdoorService {
                     ));
                     session.onboarding.missing_fields =
                         unresolved_required_fields(&session.onboarding.step3_payload);
-                    session.onboarding.current_step = "confirm_and_sign".to_string();
+                    session.onboarding.current_step = OnboardingStep::ConfirmAndSign;
                     session.onboarding.step4_payload = Some(build_onboarding_step4_payload(
                         &session.onboarding.missing_fields,
                     ));
                     if !session.onboarding.missing_fields.is_empty() {
-                        session.onboarding.current_step = "collect_required_variables".to_string();
+                        session.onboarding.current_step = OnboardingStep::CollectRequiredVariables;
                         format!(
                             "Step 3 required variables are still unresolved: {}. Provide key=value assignments.",
                             session.onboarding.missing_fields.join(", ")
@@ -956,6 +2270,8 @@ impl FrontdoorService {
                     session.onboarding.step2_payload = Some(build_onboarding_step2_payload(
                         &session.wallet_address,
                         message,
+                        self.rng.as_ref(),
+                        &perp_universe,
                     ));
                     session.onboarding.step3_payload = Some(build_onboarding_step3_payload(
                         &session.onboarding.captured_variables,
@@ -966,18 +2282,31 @@ impl FrontdoorService {
                     "Step 2 updated. Reply 'confirm plan' when the proposed policy and modules are acceptable.".to_string()
                 }
             }
-            "collect_required_variables" => {
+            OnboardingStep::CollectRequiredVariables => {
                 let assignments = parse_onboarding_assignments(message);
                 for (field, value) in assignments {
                     session.onboarding.captured_variables.insert(field, value);
                 }
+                // Record the terms version in force when the user accepted.
+                let captured = &mut session.onboarding.captured_variables;
+                if captured
+                    .get("accept_terms")
+                    .is_some_and(|value| onboarding_terms_accepted(value))
+                {
+                    captured.insert(
+                        "accepted_terms_version".to_string(),
+                        self.config.terms_version.clone(),
+                    );
+                } else {
+                    captured.remove("accepted_terms_version");
+                }
                 session.onboarding.step3_payload = Some(build_onboarding_step3_payload(
                     &session.onboarding.captured_variables,
                 ));
                 session.onboarding.missing_fields =
                     unresolved_required_fields(&session.onboarding.step3_payload);
                 if session.onboarding.missing_fields.is_empty() {
-                    session.onboarding.current_step = "confirm_and_sign".to_string();
+                    session.onboarding.current_step = OnboardingStep::ConfirmAndSign;
                     session.onboarding.step4_payload = Some(build_onboarding_step4_payload(
                         &session.onboarding.missing_fields,
                     ));
@@ -989,12 +2318,12 @@ impl FrontdoorService {
                     )
                 }
             }
-            "confirm_and_sign" => {
+            OnboardingStep::ConfirmAndSign => {
                 let lower = message.to_ascii_lowercase();
                 if onboarding_signature_confirmed(&lower)
                     && session.onboarding.missing_fields.is_empty()
                 {
-                    session.onboarding.current_step = "ready_to_sign".to_string();
+                    session.onboarding.current_step = OnboardingStep::ReadyToSign;
                     session.onboarding.completed = true;
                     session.onboarding.step4_payload = Some(FrontdoorOnboardingStep4Payload {
                         ready_to_sign: true,
@@ -1007,7 +2336,7 @@ impl FrontdoorService {
                     "Onboarding complete. Submit signature verification to trigger provisioning."
                         .to_string()
                 } else if !session.onboarding.missing_fields.is_empty() {
-                    session.onboarding.current_step = "collect_required_variables".to_string();
+                    session.onboarding.current_step = OnboardingStep::CollectRequiredVariables;
                     session.onboarding.step4_payload = Some(build_onboarding_step4_payload(
                         &session.onboarding.missing_fields,
                     ));
@@ -1020,20 +2349,24 @@ impl FrontdoorService {
                         .to_string()
                 }
             }
-            "ready_to_sign" => {
+            OnboardingStep::ReadyToSign => {
                 session.onboarding.completed = true;
                 "Onboarding already complete. Continue with signature verification.".to_string()
             }
-            _ => "Onboarding state advanced. Continue to signature and provisioning.".to_string(),
         };
 
         session.onboarding.updated_at = now;
         session.onboarding.transcript.push(OnboardingTurnState {
             role: "assistant".to_string(),
             message: assistant_message.clone(),
-            created_at: Utc::now(),
+            created_at: self.now(),
         });
-        self.persist_onboarding_transcript(session)?;
+        trim_onboarding_transcript(
+            &mut session.onboarding.transcript,
+            self.config.max_onboarding_turns,
+        );
+        self.persist_onboarding_transcript(session)
+            .map_err(FrontdoorError::Storage)?;
         push_timeline_event(
             session,
             "onboarding_chat",
@@ -1041,12 +2374,16 @@ impl FrontdoorService {
             "Onboarding transcript updated",
             "user",
         );
-
-        Ok(FrontdoorOnboardingChatResponse {
+        let record = self.session_record(session);
+        let response = FrontdoorOnboardingChatResponse {
             session_id: session.id.to_string(),
             assistant_message,
             state: render_onboarding_state(session),
-        })
+        };
+        drop(state);
+        self.write_session_record(record).await;
+
+        Ok(response)
     }
 
     pub async fn session_timeline(
@@ -1054,31 +2391,49 @@ impl FrontdoorService {
         session_id: Uuid,
     ) -> Option<FrontdoorSessionTimelineResponse> {
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
         let session = state.sessions.get(&session_id)?;
         Some(FrontdoorSessionTimelineResponse {
             session_id: session.id.to_string(),
-            events: session
-                .timeline
-                .iter()
-                .map(|event| FrontdoorSessionTimelineEvent {
-                    seq_id: event.seq_id,
-                    event_type: event.event_type.clone(),
-                    status: event.status.clone(),
-                    detail: event.detail.clone(),
-                    actor: event.actor.clone(),
-                    created_at: event.created_at.to_rfc3339(),
-                })
-                .collect(),
+            events: session.timeline.iter().map(render_timeline_event).collect(),
         })
     }
 
+    /// Latest provisioner output lines for a session, oldest first and
+    /// redacted. Empty for unknown sessions.
+    pub async fn provision_logs(&self, session_id: Uuid) -> Vec<FrontdoorProvisionLogLine> {
+        let state = self.state.read().await;
+        let Some(session) = state.sessions.get(&session_id) else {
+            return Vec::new();
+        };
+        session
+            .provision_logs
+            .iter()
+            .map(|entry| FrontdoorProvisionLogLine {
+                source: entry.source.clone(),
+                stream: entry.stream.clone(),
+                line: entry.line.clone(),
+            })
+            .collect()
+    }
+
+    /// Stream timeline events for a session as they are appended. Only events
+    /// pushed after subscribing are delivered; `session_timeline` has history.
+    pub async fn subscribe_timeline(
+        &self,
+        session_id: Uuid,
+    ) -> Option<broadcast::Receiver<FrontdoorSessionTimelineEvent>> {
+        let state = self.state.read().await;
+        let session = state.sessions.get(&session_id)?;
+        Some(session.timeline_tx.subscribe())
+    }
+
     pub async fn verification_explanation(
         &self,
         session_id: Uuid,
     ) -> Option<FrontdoorVerificationExplanationResponse> {
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
         let session = state.sessions.get(&session_id)?;
         let config = session.config.as_ref();
         Some(FrontdoorVerificationExplanationResponse {
@@ -1099,10 +2454,12 @@ impl FrontdoorService {
         &self,
         session_id: Uuid,
         req: FrontdoorRuntimeControlRequest,
-    ) -> Result<FrontdoorRuntimeControlResponse, String> {
+    ) -> Result<FrontdoorRuntimeControlResponse, FrontdoorError> {
         let action = req.action.trim().to_ascii_lowercase();
         if action.is_empty() {
-            return Err("action is required".to_string());
+            return Err(FrontdoorError::InvalidRequest(
+                "action is required".to_string(),
+            ));
         }
         let actor = req
             .actor
@@ -1112,11 +2469,11 @@ impl FrontdoorService {
             .unwrap_or("frontdoor_operator");
 
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
         let session = state
             .sessions
             .get_mut(&session_id)
-            .ok_or_else(|| "session not found".to_string())?;
+            .ok_or(FrontdoorError::SessionNotFound)?;
 
         let (status, detail) = match action.as_str() {
             "pause" => {
@@ -1149,20 +2506,20 @@ impl FrontdoorService {
             }
             "rotate_auth_key" => {
                 if let Some(cfg) = session.config.as_mut() {
-                    cfg.gateway_auth_key = generate_gateway_auth_key();
+                    cfg.gateway_auth_key = generate_gateway_auth_key(self.rng.as_ref());
                     ("applied", "Gateway auth key rotated")
                 } else {
                     ("blocked", "Session config not available")
                 }
             }
             _ => {
-                return Err(
+                return Err(FrontdoorError::InvalidRequest(
                     "action must be one of: pause, resume, terminate, rotate_auth_key".to_string(),
-                );
+                ));
             }
         };
 
-        session.updated_at = Utc::now();
+        session.updated_at = self.now();
         push_timeline_event(
             session,
             "runtime_control",
@@ -1178,15 +2535,19 @@ impl FrontdoorService {
             &summary,
             actor,
         );
-
-        Ok(FrontdoorRuntimeControlResponse {
+        let record = self.session_record(session);
+        let response = FrontdoorRuntimeControlResponse {
             session_id: session.id.to_string(),
             action,
             status: status.to_string(),
             runtime_state: session.runtime_state.as_str().to_string(),
             detail: detail.to_string(),
             updated_at: session.updated_at.to_rfc3339(),
-        })
+        };
+        drop(state);
+        self.write_session_record(record).await;
+
+        Ok(response)
     }
 
     pub async fn gateway_todos_for_session(
@@ -1194,7 +2555,7 @@ impl FrontdoorService {
         session_id: Uuid,
     ) -> Option<FrontdoorGatewayTodosResponse> {
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
         let session = state.sessions.get(&session_id)?;
         Some(build_gateway_todos(session))
     }
@@ -1204,15 +2565,15 @@ impl FrontdoorService {
         wallet_filter: Option<&str>,
         session_id: Option<Uuid>,
         limit: usize,
-    ) -> Result<(usize, Vec<FrontdoorGatewayTodosResponse>), String> {
+    ) -> Result<(usize, Vec<FrontdoorGatewayTodosResponse>), FrontdoorError> {
         let normalized_wallet = match wallet_filter {
-            Some(raw) => Some(normalize_wallet_address(raw).ok_or_else(|| {
-                "wallet_address must be a 0x-prefixed 40-hex address".to_string()
-            })?),
+            Some(raw) => {
+                Some(normalize_wallet_address(raw).ok_or(FrontdoorError::InvalidWalletAddress)?)
+            }
             None => None,
         };
         let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
+        self.purge_expired(&mut state);
 
         let mut filtered: Vec<FrontdoorGatewayTodosResponse> = state
             .sessions
@@ -1242,15 +2603,37 @@ impl FrontdoorService {
         &self,
         session_id: Uuid,
     ) -> Option<FrontdoorFundingPreflightResponse> {
-        let mut state = self.state.write().await;
-        purge_expired_sessions(&mut state);
-        let session = state.sessions.get_mut(&session_id)?;
-        if session.funding_preflight.status == "pending"
-            && let Some(cfg) = session.config.clone()
-        {
-            session.funding_preflight = evaluate_funding_preflight(session, &cfg);
-        }
-        Some(render_funding_preflight_response(session))
+        let pending = {
+            let mut state = self.state.write().await;
+            self.purge_expired(&mut state);
+            let session = state.sessions.get(&session_id)?;
+            if session.funding_preflight.status != "pending" {
+                return Some(render_funding_preflight_response(session));
+            }
+            session
+                .config
+                .clone()
+                .map(|cfg| (session.wallet_address.clone(), cfg))
+        };
+        let preflight = match pending {
+            Some((wallet, cfg)) => Some(self.run_funding_preflight(&wallet, &cfg).await),
+            None => None,
+        };
+
+        let (response, record) = {
+            let mut state = self.state.write().await;
+            let session = state.sessions.get_mut(&session_id)?;
+            let mut record = None;
+            if let Some(preflight) = preflight
+                && session.funding_preflight.status == "pending"
+            {
+                session.funding_preflight = preflight;
+                record = self.session_record(session);
+            }
+            (render_funding_preflight_response(session), record)
+        };
+        self.write_session_record(record).await;
+        Some(response)
     }
 
     async fn emit_provision_log(&self, session_id: Uuid, entry: &ProvisionCommandLog) {
@@ -1262,10 +2645,38 @@ impl FrontdoorService {
         let Some(session) = state.sessions.get_mut(&session_id) else {
             return;
         };
-        let status = session.status.as_str().to_string();
+        let mut secrets: Vec<&str> = Vec::new();
+        if let Some(cfg) = session.config.as_ref() {
+            secrets.push(&cfg.gateway_auth_key);
+            if let Some(key) = cfg.eigencloud_auth_key.as_deref() {
+                secrets.push(key);
+            }
+        }
+        secrets.extend(session.privy_identity_token.as_deref());
+        secrets.extend(session.privy_access_token.as_deref());
+        let line = redact_provision_line(line, &secrets);
         let detail = format!("[{}/{}] {}", entry.source, entry.stream, line);
-        push_timeline_event(session, "provision_log", &status, &detail, "provisioner");
-        session.updated_at = Utc::now();
+        session.provision_logs.push_back(ProvisionCommandLog {
+            source: entry.source.clone(),
+            stream: entry.stream.clone(),
+            line,
+        });
+        if session.provision_logs.len() > FRONTDOOR_PROVISION_LOG_CAP {
+            session.provision_logs.pop_front();
+        }
+        session.provision_log_lines_total = session.provision_log_lines_total.saturating_add(1);
+
+        // Only the first line and a periodic summary reach the timeline.
+        let total = session.provision_log_lines_total;
+        if total == 1 || total % FRONTDOOR_PROVISION_LOG_SUMMARY_EVERY == 0 {
+            let status = session.status.as_str().to_string();
+            let summary = format!("{total} provisioner log lines; latest {detail}");
+            push_timeline_event(session, "provision_log", &status, &summary, "provisioner");
+        }
+        session.updated_at = self.now();
+        let record = self.session_record(session);
+        drop(state);
+        self.write_session_record(record).await;
     }
 
     async fn run_provision(self: Arc<Self>, session_id: Uuid) {
@@ -1278,7 +2689,6 @@ impl FrontdoorService {
             version,
             cfg,
             onboarding_objective,
-            command,
             default_url,
             allow_default_fallback,
             verify_base_url,
@@ -1299,7 +2709,6 @@ impl FrontdoorService {
                 session.version,
                 cfg,
                 session.onboarding.objective.clone(),
-                self.config.provision_command.clone(),
                 self.config.default_instance_url.clone(),
                 self.config.allow_default_instance_fallback,
                 self.config.verify_app_base_url.clone(),
@@ -1346,59 +2755,30 @@ impl FrontdoorService {
             } else {
                 provisioning_decision.reason.clone()
             };
-            let (result, source) = match command.as_deref().map(str::trim) {
-                Some(raw_template) if !raw_template.is_empty() => {
-                    match parse_provision_command_template(raw_template) {
-                        Ok(parsed_template) => {
-                            let service = Arc::clone(&self);
-                            (
-                                execute_provision_command_with_stream(
-                                    parsed_template.as_str(),
-                                    &command_input,
-                                    move |entry| {
-                                        let service = Arc::clone(&service);
-                                        async move {
-                                            service.emit_provision_log(session_id, &entry).await;
-                                        }
-                                    },
-                                )
-                                .await,
-                                ProvisioningSource::Command,
-                            )
-                        }
-                        Err(_template_err)
-                            if allow_default_fallback && normalized_default_url.is_ok() =>
-                        {
-                            (
-                                provision_from_default_url(&normalized_default_url),
-                                ProvisioningSource::DefaultInstanceUrl,
-                            )
-                        }
-                        Err(template_err) => (
-                            Err(format!("provision_command is malformed: {template_err}")),
-                            ProvisioningSource::Unconfigured,
-                        ),
-                    }
-                }
-                _ if allow_default_fallback && normalized_default_url.is_ok() => (
-                    provision_from_default_url(&normalized_default_url),
-                    ProvisioningSource::DefaultInstanceUrl,
-                ),
-                _ => (
-                    Err("No valid provisioning command configured. Static fallback is disabled; set GATEWAY_FRONTDOOR_PROVISION_COMMAND or opt in to GATEWAY_FRONTDOOR_ALLOW_DEFAULT_INSTANCE_FALLBACK=1 with GATEWAY_FRONTDOOR_DEFAULT_INSTANCE_URL.".to_string()),
-                    ProvisioningSource::Unconfigured,
-                ),
-            };
-            (result, source, decision_detail)
+            let result = self
+                .provisioning_backend
+                .provision(&command_input, &ServiceProvisionLogSink(self.as_ref()))
+                .await;
+            (result, self.provisioning_backend.source(), decision_detail)
         };
 
         let mut state = self.state.write().await;
         let mut wallet_record: Option<WalletSessionRecord> = None;
-        {
+        let session_record = {
             let Some(session) = state.sessions.get_mut(&session_id) else {
                 return;
             };
             session.provisioning_source = provisioning_source;
+            self.metrics.increment_counter(
+                "frontdoor_provision_total",
+                &[
+                    ("source", provisioning_source.as_str()),
+                    (
+                        "outcome",
+                        if result.is_ok() { "success" } else { "failure" },
+                    ),
+                ],
+            );
             push_timeline_event(
                 session,
                 "provisioning_decision",
@@ -1416,7 +2796,7 @@ impl FrontdoorService {
                     session.eigen_app_id = provisioned.eigen_app_id.clone();
                     session.error = None;
                     session.detail = "Enclave is live. Redirect ready.".to_string();
-                    session.updated_at = Utc::now();
+                    session.updated_at = self.now();
                     session.provisioning_started_at = None;
 
                     tracing::info!(
@@ -1448,14 +2828,14 @@ impl FrontdoorService {
                         version: session.version,
                         last_instance_url: provisioned.instance_url,
                         last_profile_name: cfg.profile_name.clone(),
-                        updated_at: Utc::now().to_rfc3339(),
+                        updated_at: self.now().to_rfc3339(),
                     });
                 }
                 Err(err) => {
                     session.status = SessionStatus::Failed;
                     session.detail = "Provisioning failed".to_string();
                     session.error = Some(err);
-                    session.updated_at = Utc::now();
+                    session.updated_at = self.now();
                     session.provisioning_started_at = None;
 
                     tracing::warn!(
@@ -1487,7 +2867,9 @@ impl FrontdoorService {
                     push_timeline_event(session, "todo_snapshot", "failed", &summary, "system");
                 }
             }
-        }
+            self.notify_status_webhook(session);
+            self.session_record(session)
+        };
 
         if let Some(record) = wallet_record {
             state.wallets.insert(wallet.clone(), record);
@@ -1507,6 +2889,8 @@ impl FrontdoorService {
                 );
             }
         }
+        drop(state);
+        self.write_session_record(session_record).await;
     }
 
     fn persist_onboarding_transcript(&self, session: &ProvisioningSession) -> Result<(), String> {
@@ -1518,10 +2902,37 @@ impl FrontdoorService {
         }
         let data = serde_json::to_vec_pretty(&artifact)
             .map_err(|e| format!("failed serializing onboarding transcript artifact: {e}"))?;
-        std::fs::write(path, data)
+        write_file_atomically(&path, &data)
             .map_err(|e| format!("failed writing onboarding transcript artifact: {e}"))?;
         Ok(())
     }
+
+    /// POST `session`'s terminal status to `status_webhook_url`, if set.
+    ///
+    /// Delivery runs on its own task so the caller never waits on the
+    /// receiver; failures are logged and otherwise ignored.
+    fn notify_status_webhook(&self, session: &ProvisioningSession) {
+        let Some(url) = self.config.status_webhook_url.clone() else {
+            return;
+        };
+        let payload = FrontdoorStatusWebhookPayload {
+            session_id: session.id.to_string(),
+            wallet_address: session.wallet_address.clone(),
+            status: session.status.as_str().to_string(),
+            detail: session.detail.clone(),
+            instance_url: session.instance_url.clone(),
+        };
+        tokio::spawn(async move {
+            if let Err(err) = post_status_webhook(&url, &payload).await {
+                tracing::warn!(
+                    session_id = %payload.session_id,
+                    status = %payload.status,
+                    error = %err,
+                    "Failed to deliver frontdoor status webhook"
+                );
+            }
+        });
+    }
 }
 
 fn default_wallet_store_path() -> PathBuf {
@@ -1579,96 +2990,276 @@ fn provision_from_default_url(
     }
 }
 
-fn parse_provision_command_template(template: &str) -> Result<String, String> {
-    let trimmed = template.trim();
-    if trimmed.is_empty() {
-        return Err("provision command template is empty".to_string());
-    }
+/// Receives provisioner output lines while a backend runs; the service
+/// redacts them before storing.
+#[async_trait]
+trait ProvisionLogSink: Send + Sync {
+    async fn emit(&self, session_id: Uuid, entry: &ProvisionCommandLog);
+}
 
-    let allowed_placeholders: HashSet<&str> = HashSet::from([
-        "session_id",
-        "wallet_address",
-        "privy_user_id",
-        "privy_identity_token",
-        "privy_access_token",
-        "chain_id",
-        "version",
-        "config_version",
-        "profile_domain",
-        "domain_overrides_json",
-        "profile_name",
-        "custody_mode",
-        "operator_wallet_address",
-        "user_wallet_address",
-        "vault_address",
-        "gateway_auth_key",
-        "eigencloud_auth_key",
-        "verification_backend",
-        "verification_eigencloud_endpoint",
-        "verification_eigencloud_auth_scheme",
-        "verification_eigencloud_timeout_ms",
-        "verification_fallback_enabled",
-        "verification_fallback_signing_key_id",
-        "verification_fallback_chain_path",
-        "verification_fallback_require_signed_receipts",
-        "verify_app_base_url",
-        "inference_summary",
-        "inference_confidence",
-        "config_json",
-        "config_b64",
-    ]);
+struct ServiceProvisionLogSink<'a>(&'a FrontdoorService);
 
-    let bytes = trimmed.as_bytes();
-    let mut idx = 0usize;
-    while idx < bytes.len() {
-        if bytes[idx] != b'{' || (idx > 0 && bytes[idx - 1] == b'$') {
-            idx = idx.saturating_add(1);
-            continue;
-        }
-        let mut end = idx.saturating_add(1);
-        while end < bytes.len() && bytes[end] != b'}' {
-            end = end.saturating_add(1);
-        }
-        if end >= bytes.len() {
-            return Err("provision command template has unmatched '{'".to_string());
-        }
-        let token = &trimmed[idx + 1..end];
-        if !token.is_empty()
-            && token
-                .chars()
-                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
-            && !allowed_placeholders.contains(token)
-        {
-            return Err(format!("unsupported placeholder '{{{token}}}'"));
-        }
-        idx = end.saturating_add(1);
+#[async_trait]
+impl ProvisionLogSink for ServiceProvisionLogSink<'_> {
+    async fn emit(&self, session_id: Uuid, entry: &ProvisionCommandLog) {
+        self.0.emit_provision_log(session_id, entry).await;
     }
+}
 
-    Ok(trimmed.to_string())
+/// Produces the instance for a verified session.
+///
+/// The frontdoor picks one backend from config at startup; the shared
+/// runtime shortcut in `run_provision` bypasses it.
+#[async_trait]
+trait ProvisioningBackend: Send + Sync {
+    fn source(&self) -> ProvisioningSource;
+
+    async fn provision(
+        &self,
+        input: &ProvisionCommandInput<'_>,
+        logs: &dyn ProvisionLogSink,
+    ) -> Result<ProvisioningResult, String>;
 }
 
-fn is_non_empty_config_value(value: Option<&str>) -> bool {
-    value.map(|v| !v.trim().is_empty()).unwrap_or(false)
+/// Runs the configured provision command through `/bin/sh`.
+struct ShellProvisioningBackend {
+    template: String,
+    timeout: Option<std::time::Duration>,
 }
 
-fn decide_provisioning_decision(objective_hint: Option<&str>) -> ProvisioningDecision {
-    let Some(objective) = objective_hint
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    else {
-        return ProvisioningDecision {
-            prefer_shared_runtime: false,
-            reason:
-                "No explicit objective requested shared runtime; defaulting to dedicated provisioning."
-                    .to_string(),
-        };
-    };
+#[async_trait]
+impl ProvisioningBackend for ShellProvisioningBackend {
+    fn source(&self) -> ProvisioningSource {
+        ProvisioningSource::Command
+    }
 
-    let lower = objective.to_ascii_lowercase();
-    let shared_runtime_hints = [
-        "shared runtime",
-        "shared gateway",
-        "reuse existing runtime",
+    async fn provision(
+        &self,
+        input: &ProvisionCommandInput<'_>,
+        logs: &dyn ProvisionLogSink,
+    ) -> Result<ProvisioningResult, String> {
+        let session_id = input.session_id;
+        execute_provision_command_with_stream(
+            &self.template,
+            input,
+            self.timeout,
+            |entry| async move { logs.emit(session_id, &entry).await },
+        )
+        .await
+    }
+}
+
+/// Hands every session the configured default instance URL.
+struct StaticUrlBackend {
+    default_url: Option<String>,
+}
+
+#[async_trait]
+impl ProvisioningBackend for StaticUrlBackend {
+    fn source(&self) -> ProvisioningSource {
+        ProvisioningSource::DefaultInstanceUrl
+    }
+
+    async fn provision(
+        &self,
+        _input: &ProvisionCommandInput<'_>,
+        _logs: &dyn ProvisionLogSink,
+    ) -> Result<ProvisioningResult, String> {
+        provision_from_default_url(&Ok(self.default_url.clone()))
+    }
+}
+
+/// Fails every provision with the reason no usable backend is configured.
+struct UnconfiguredBackend {
+    reason: String,
+}
+
+#[async_trait]
+impl ProvisioningBackend for UnconfiguredBackend {
+    fn source(&self) -> ProvisioningSource {
+        ProvisioningSource::Unconfigured
+    }
+
+    async fn provision(
+        &self,
+        _input: &ProvisionCommandInput<'_>,
+        _logs: &dyn ProvisionLogSink,
+    ) -> Result<ProvisioningResult, String> {
+        Err(self.reason.clone())
+    }
+}
+
+fn provisioning_backend_from_config(config: &FrontdoorConfig) -> Box<dyn ProvisioningBackend> {
+    let static_url = config
+        .allow_default_instance_fallback
+        .then(|| normalize_default_instance_url(config.default_instance_url.as_deref()).ok())
+        .flatten()
+        .map(|default_url| {
+            Box::new(StaticUrlBackend { default_url }) as Box<dyn ProvisioningBackend>
+        });
+
+    match config.provision_command.as_deref().map(str::trim) {
+        Some(raw_template) if !raw_template.is_empty() => {
+            match parse_provision_command_template(raw_template) {
+                Ok(template) => Box::new(ShellProvisioningBackend {
+                    template,
+                    timeout: (config.provision_timeout_secs > 0)
+                        .then(|| std::time::Duration::from_secs(config.provision_timeout_secs)),
+                }),
+                Err(template_err) => static_url.unwrap_or_else(|| {
+                    Box::new(UnconfiguredBackend {
+                        reason: format!("provision_command is malformed: {template_err}"),
+                    })
+                }),
+            }
+        }
+        _ => static_url.unwrap_or_else(|| {
+            Box::new(UnconfiguredBackend {
+                reason: "No valid provisioning command configured. Static fallback is disabled; set GATEWAY_FRONTDOOR_PROVISION_COMMAND or opt in to GATEWAY_FRONTDOOR_ALLOW_DEFAULT_INSTANCE_FALLBACK=1 with GATEWAY_FRONTDOOR_DEFAULT_INSTANCE_URL.".to_string(),
+            })
+        }),
+    }
+}
+
+/// Mask every occurrence of a known secret value in a provisioner log line.
+///
+/// Longer secrets are replaced first so a secret that contains another is
+/// never left partially visible. Blank secrets are ignored.
+fn redact_provision_line(line: &str, secrets: &[&str]) -> String {
+    let mut secrets: Vec<&str> = secrets
+        .iter()
+        .map(|secret| secret.trim())
+        .filter(|secret| !secret.is_empty())
+        .collect();
+    secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+    secrets
+        .into_iter()
+        .fold(line.to_string(), |redacted, secret| {
+            redacted.replace(secret, PROVISION_LOG_REDACTED)
+        })
+}
+
+/// Placeholders a challenge template may reference.
+const CHALLENGE_TEMPLATE_PLACEHOLDERS: [&str; 8] = [
+    "wallet",
+    "privy_user_id",
+    "chain_id",
+    "session_id",
+    "version",
+    "nonce",
+    "issued_at",
+    "expires_at",
+];
+
+const DEFAULT_CHALLENGE_TEMPLATE: &str = "Enclagent Gasless Authorization Transaction\nWallet: {wallet}\nPrivy Link: {privy_user_id}\nChain ID: {chain_id}\nSession ID: {session_id}\nVersion: v{version}\nNonce: {nonce}\nIssued At: {issued_at}\nExpires At: {expires_at}\n\nSign this gasless authorization transaction to verify wallet control and start provisioning your dedicated Enclagent enclave.";
+
+/// Check a custom challenge template before it is used for signing prompts.
+///
+/// Only the placeholders in [`CHALLENGE_TEMPLATE_PLACEHOLDERS`] are allowed,
+/// and the template must keep a `Nonce: {nonce}` line so verification can
+/// recover the nonce from the signed message, plus a `Chain ID: {chain_id}`
+/// line so the signature is bound to the allowed chain it was issued for.
+pub fn validate_challenge_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("challenge template is empty".to_string());
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            return Err("challenge template has unmatched '{'".to_string());
+        };
+        let token = &rest[start + 1..start + len];
+        if !CHALLENGE_TEMPLATE_PLACEHOLDERS.contains(&token) {
+            return Err(format!(
+                "unsupported placeholder '{{{token}}}'; expected one of {}",
+                CHALLENGE_TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|name| format!("{{{name}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    if !template.lines().any(|line| line.trim() == "Nonce: {nonce}") {
+        return Err("challenge template must contain a 'Nonce: {nonce}' line".to_string());
+    }
+    if !template
+        .lines()
+        .any(|line| line.trim() == "Chain ID: {chain_id}")
+    {
+        return Err("challenge template must contain a 'Chain ID: {chain_id}' line".to_string());
+    }
+    Ok(())
+}
+
+fn render_challenge_message(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+fn parse_provision_command_template(template: &str) -> Result<String, String> {
+    let trimmed = template.trim();
+    if trimmed.is_empty() {
+        return Err("provision command template is empty".to_string());
+    }
+
+    let bytes = trimmed.as_bytes();
+    let mut idx = 0usize;
+    while idx < bytes.len() {
+        if bytes[idx] != b'{' || (idx > 0 && bytes[idx - 1] == b'$') {
+            idx = idx.saturating_add(1);
+            continue;
+        }
+        let mut end = idx.saturating_add(1);
+        while end < bytes.len() && bytes[end] != b'}' {
+            end = end.saturating_add(1);
+        }
+        if end >= bytes.len() {
+            return Err("provision command template has unmatched '{'".to_string());
+        }
+        let token = &trimmed[idx + 1..end];
+        if !token.is_empty()
+            && token
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && !PROVISION_PLACEHOLDERS
+                .iter()
+                .any(|placeholder| placeholder.token == token)
+        {
+            return Err(format!("unsupported placeholder '{{{token}}}'"));
+        }
+        idx = end.saturating_add(1);
+    }
+
+    Ok(trimmed.to_string())
+}
+
+fn is_non_empty_config_value(value: Option<&str>) -> bool {
+    value.map(|v| !v.trim().is_empty()).unwrap_or(false)
+}
+
+fn decide_provisioning_decision(objective_hint: Option<&str>) -> ProvisioningDecision {
+    let Some(objective) = objective_hint
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return ProvisioningDecision {
+            prefer_shared_runtime: false,
+            reason:
+                "No explicit objective requested shared runtime; defaulting to dedicated provisioning."
+                    .to_string(),
+        };
+    };
+
+    let lower = objective.to_ascii_lowercase();
+    let shared_runtime_hints = [
+        "shared runtime",
+        "shared gateway",
+        "reuse existing runtime",
         "reuse existing instance",
         "no enclave",
         "without enclave",
@@ -1706,34 +3297,16 @@ fn verification_assurance_level(config: Option<&FrontdoorUserConfig>) -> String
         return "unknown".to_string();
     };
 
-    match config.verification_backend.as_str() {
-        "eigencloud_primary" => {
-            if config.verification_fallback_enabled {
-                if config.verification_fallback_require_signed_receipts {
-                    "primary_plus_signed_fallback".to_string()
-                } else {
-                    "primary_plus_unsigned_fallback".to_string()
-                }
-            } else {
-                "primary_only".to_string()
-            }
-        }
-        "fallback_only" => {
-            if !config.verification_fallback_enabled {
-                "fallback_only_disabled".to_string()
-            } else if config.verification_fallback_require_signed_receipts {
-                "signed_fallback_only".to_string()
-            } else {
-                "unsigned_fallback_only".to_string()
-            }
-        }
-        other => format!("custom:{other}"),
-    }
+    crate::agent::verification_assurance_level(
+        &config.verification_backend,
+        config.verification_fallback_enabled,
+        config.verification_fallback_require_signed_receipts,
+    )
 }
 
 fn default_onboarding_state(session_id: Uuid, now: DateTime<Utc>) -> OnboardingState {
     OnboardingState {
-        current_step: "capture_objective".to_string(),
+        current_step: OnboardingStep::CaptureObjective,
         completed: false,
         objective: None,
         missing_fields: vec![
@@ -1762,13 +3335,25 @@ fn default_onboarding_state(session_id: Uuid, now: DateTime<Utc>) -> OnboardingS
     }
 }
 
+/// Keep the opening greeting and the `max_turns` most recent turns.
+/// `0` disables trimming.
+fn trim_onboarding_transcript(transcript: &mut Vec<OnboardingTurnState>, max_turns: usize) {
+    if max_turns == 0 || transcript.len() <= max_turns + 1 {
+        return;
+    }
+    let excess = transcript.len() - max_turns - 1;
+    transcript.drain(1..=excess);
+}
+
 fn build_onboarding_step2_payload(
     connected_wallet: &str,
     objective: &str,
+    rng: &dyn RngSource,
+    perp_universe: &HyperliquidPerpUniverse,
 ) -> FrontdoorOnboardingStep2Payload {
     let mut assumptions = Vec::new();
     let mut warnings = Vec::new();
-    let mut config = default_frontdoor_user_config(connected_wallet, None, "general");
+    let mut config = default_frontdoor_user_config(connected_wallet, None, "general", rng);
     apply_intent_overrides(
         &mut config,
         objective,
@@ -1776,7 +3361,14 @@ fn build_onboarding_step2_payload(
         &mut assumptions,
         &mut warnings,
     );
-    normalize_suggested_config(&mut config, connected_wallet, &mut assumptions);
+    normalize_suggested_config(
+        &mut config,
+        connected_wallet,
+        rng,
+        perp_universe,
+        &mut assumptions,
+        &mut warnings,
+    );
     let module_plan = frontdoor_domain_profiles()
         .into_iter()
         .find(|profile| profile.domain == config.profile_domain)
@@ -1849,7 +3441,7 @@ fn build_onboarding_step3_payload(
             "accept_terms" => {
                 if value.is_empty() {
                     "open"
-                } else if matches!(value.to_ascii_lowercase().as_str(), "true" | "1" | "yes") {
+                } else if onboarding_terms_accepted(&value) {
                     "resolved"
                 } else {
                     "blocked"
@@ -1884,6 +3476,13 @@ fn build_onboarding_step3_payload(
     }
 }
 
+fn onboarding_terms_accepted(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "1" | "yes"
+    )
+}
+
 fn build_onboarding_step4_payload(
     unresolved_required_fields: &[String],
 ) -> FrontdoorOnboardingStep4Payload {
@@ -1965,6 +3564,24 @@ fn onboarding_plan_confirmed(message_lower: &str) -> bool {
         || message_lower.trim() == "confirm"
 }
 
+fn onboarding_rewind_requested(message_lower: &str) -> bool {
+    matches!(message_lower.trim(), "back" | "restart objective")
+}
+
+/// Reset onboarding to objective capture, discarding the plan and any
+/// captured variables. The transcript is kept.
+fn rewind_onboarding_to_objective(onboarding: &mut OnboardingState) {
+    onboarding.current_step = OnboardingStep::CaptureObjective;
+    onboarding.completed = false;
+    onboarding.objective = None;
+    onboarding.step2_payload = None;
+    onboarding.step3_payload = None;
+    onboarding.step4_payload = None;
+    onboarding.captured_variables.clear();
+    onboarding.missing_fields =
+        unresolved_required_fields(&Some(build_onboarding_step3_payload(&HashMap::new())));
+}
+
 fn onboarding_signature_confirmed(message_lower: &str) -> bool {
     message_lower.contains("confirm sign")
         || message_lower.contains("confirm signature")
@@ -2011,14 +3628,18 @@ fn push_timeline_event(
 ) {
     let seq_id = session.next_timeline_seq_id;
     session.next_timeline_seq_id = session.next_timeline_seq_id.saturating_add(1);
-    session.timeline.push(TimelineEvent {
+    let event = TimelineEvent {
         seq_id,
         event_type: event_type.to_string(),
         status: status.to_string(),
         detail: detail.to_string(),
         actor: actor.to_string(),
         created_at: Utc::now(),
-    });
+    };
+    let rendered = render_timeline_event(&event);
+    session.timeline.push(event);
+    // No subscribers is the common case; a failed send is not an error.
+    let _ = session.timeline_tx.send(rendered);
     if session.timeline.len() > FRONTDOOR_TIMELINE_EVENT_CAP {
         let overflow = session.timeline.len() - FRONTDOOR_TIMELINE_EVENT_CAP;
         session.timeline.drain(0..overflow);
@@ -2042,10 +3663,21 @@ fn push_timeline_event(
     );
 }
 
+fn render_timeline_event(event: &TimelineEvent) -> FrontdoorSessionTimelineEvent {
+    FrontdoorSessionTimelineEvent {
+        seq_id: event.seq_id,
+        event_type: event.event_type.clone(),
+        status: event.status.clone(),
+        detail: event.detail.clone(),
+        actor: event.actor.clone(),
+        created_at: event.created_at.to_rfc3339(),
+    }
+}
+
 fn render_onboarding_state(session: &ProvisioningSession) -> FrontdoorOnboardingStateResponse {
     FrontdoorOnboardingStateResponse {
         session_id: session.id.to_string(),
-        current_step: session.onboarding.current_step.clone(),
+        current_step: session.onboarding.current_step.as_str().to_string(),
         completed: session.onboarding.completed,
         objective: session.onboarding.objective.clone(),
         missing_fields: session.onboarding.missing_fields.clone(),
@@ -2077,8 +3709,8 @@ fn render_onboarding_transcript_artifact(
             .clone()
             .unwrap_or_else(|| onboarding_artifact_id(session.id)),
         session_id: session.id.to_string(),
-        wallet_address: session.wallet_address.clone(),
-        current_step: session.onboarding.current_step.clone(),
+        wallet_address: to_checksummed_address(&session.wallet_address),
+        current_step: session.onboarding.current_step.as_str().to_string(),
         completed: session.onboarding.completed,
         objective: session.onboarding.objective.clone(),
         step2_payload: session.onboarding.step2_payload.clone(),
@@ -2311,6 +3943,24 @@ fn build_gateway_todos(session: &ProvisioningSession) -> FrontdoorGatewayTodosRe
     }
 }
 
+fn session_attention_severity(session: &ProvisioningSession, now: DateTime<Utc>) -> Option<u8> {
+    match session.status {
+        SessionStatus::Failed => Some(3),
+        SessionStatus::Provisioning => session
+            .provisioning_started_at
+            .filter(|started| {
+                now.signed_duration_since(*started)
+                    > chrono::Duration::seconds(FRONTDOOR_STALLED_PROVISIONING_SECS)
+            })
+            .map(|_| 2),
+        // Waiting on the user or already aged out; nothing for an operator to do.
+        SessionStatus::AwaitingSignature | SessionStatus::Expired => None,
+        SessionStatus::Ready => build_gateway_todos(session)
+            .has_blocking_required_todos
+            .then_some(1),
+    }
+}
+
 fn todo_status_summary(payload: &FrontdoorGatewayTodosResponse) -> String {
     payload.todo_status_summary.clone()
 }
@@ -2345,175 +3995,329 @@ fn gateway_todo_priority(severity: &str, status: &str) -> u8 {
     }
 }
 
-fn evaluate_funding_preflight(
-    session: &ProvisioningSession,
-    config: &FrontdoorUserConfig,
-) -> FundingPreflightState {
-    let wallet_bound = validate_wallet_association(config, &session.wallet_address).is_ok();
-    let auth_ready_default = if config.verification_backend == "eigencloud_primary"
-        && config.verification_eigencloud_auth_scheme == "api_key"
-    {
-        config
-            .eigencloud_auth_key
-            .as_deref()
-            .map(|value| !value.trim().is_empty())
-            .unwrap_or(false)
-    } else {
-        true
-    };
+/// A funding readiness check run before provisioning starts.
+///
+/// Checks run in registration order, each bounded by [`Self::timeout`]. Among
+/// failing checks, the lowest [`Self::failure_precedence`] supplies the
+/// preflight `failure_category`.
+#[async_trait]
+pub trait FundingPreflightCheck: Send + Sync {
+    fn id(&self) -> &str;
 
-    let gas_ready = preflight_override_bool(config, "gas_ready").unwrap_or(true);
-    let fee_ready = preflight_override_bool(config, "fee_ready").unwrap_or(true);
-    let auth_ready = preflight_override_bool(config, "auth_ready").unwrap_or(auth_ready_default);
-    let policy_ready =
-        preflight_override_bool(config, "policy_ready").unwrap_or(config.accept_terms);
-
-    let checks = vec![
-        FundingPreflightCheckState {
-            check_id: "wallet_binding".to_string(),
-            status: if wallet_bound { "passed" } else { "failed" }.to_string(),
-            detail: if wallet_bound {
-                "Connected wallet and config wallet association match.".to_string()
-            } else {
-                "Connected wallet does not satisfy custody-mode wallet association.".to_string()
-            },
-        },
-        FundingPreflightCheckState {
-            check_id: "auth_material".to_string(),
-            status: if auth_ready { "passed" } else { "failed" }.to_string(),
-            detail: if auth_ready {
-                "Verification backend auth requirements satisfied.".to_string()
-            } else {
-                "Missing or invalid verification auth material.".to_string()
-            },
-        },
-        FundingPreflightCheckState {
-            check_id: "gas_budget".to_string(),
-            status: if gas_ready { "passed" } else { "failed" }.to_string(),
-            detail: if gas_ready {
-                "Gas readiness checks passed.".to_string()
-            } else {
-                "Insufficient gas readiness for provisioning.".to_string()
-            },
-        },
-        FundingPreflightCheckState {
-            check_id: "platform_fee".to_string(),
-            status: if fee_ready { "passed" } else { "failed" }.to_string(),
-            detail: if fee_ready {
-                "Platform fee readiness checks passed.".to_string()
-            } else {
-                "Insufficient platform fee readiness for provisioning.".to_string()
-            },
-        },
-        FundingPreflightCheckState {
-            check_id: "policy".to_string(),
-            status: if policy_ready { "passed" } else { "failed" }.to_string(),
-            detail: if policy_ready {
-                "Policy acceptance and gating checks passed.".to_string()
-            } else {
-                "Policy checks failed (accept_terms or policy override).".to_string()
-            },
-        },
-    ];
+    /// Category reported when this check decides the failure (`auth`, `gas`, ...).
+    fn failure_category(&self) -> &str;
 
-    let failure_category = if !wallet_bound {
-        Some("policy".to_string())
-    } else if !auth_ready {
-        Some("auth".to_string())
-    } else if !policy_ready {
-        Some("policy".to_string())
-    } else if !gas_ready {
-        Some("gas".to_string())
-    } else if !fee_ready {
-        Some("fee".to_string())
-    } else {
-        None
-    };
+    /// Rank among failing checks; lower wins and ties go to the earlier
+    /// check. The default ranks after every built-in check.
+    fn failure_precedence(&self) -> u8 {
+        u8::MAX
+    }
 
-    FundingPreflightState {
-        status: if failure_category.is_none() {
-            "passed".to_string()
-        } else {
-            "failed".to_string()
-        },
-        failure_category,
-        checks,
-        updated_at: Utc::now(),
+    /// Longest `evaluate` may run before the check is reported as failed.
+    fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(FRONTDOOR_PREFLIGHT_CHECK_TIMEOUT_SECS)
     }
+
+    async fn evaluate(&self, cfg: &FrontdoorUserConfig, wallet: &str)
+    -> FundingPreflightCheckState;
 }
 
-fn preflight_override_bool(config: &FrontdoorUserConfig, key: &str) -> Option<bool> {
-    config
-        .domain_overrides
-        .get("frontdoor_preflight")
-        .and_then(|value| value.as_object())
-        .and_then(|map| map.get(key))
-        .and_then(|value| value.as_bool())
+fn preflight_check_state(
+    check_id: &str,
+    passed: bool,
+    passed_detail: &str,
+    failed_detail: &str,
+) -> FundingPreflightCheckState {
+    FundingPreflightCheckState {
+        check_id: check_id.to_string(),
+        status: if passed { "passed" } else { "failed" }.to_string(),
+        detail: if passed { passed_detail } else { failed_detail }.to_string(),
+    }
 }
 
-fn render_session_response(session: &ProvisioningSession) -> FrontdoorSessionResponse {
-    let config = session.config.as_ref();
-    let todos = build_gateway_todos(session);
-    let verification_level = verification_assurance_level(config);
-    FrontdoorSessionResponse {
-        session_id: session.id.to_string(),
-        wallet_address: session.wallet_address.clone(),
-        privy_user_id: session.privy_user_id.clone(),
-        version: session.version,
-        status: session.status.as_str().to_string(),
-        detail: session.detail.clone(),
-        provisioning_source: session.provisioning_source.as_str().to_string(),
-        dedicated_instance: session.provisioning_source.dedicated_instance(),
-        launched_on_eigencloud: session_launched_on_eigencloud(session).unwrap_or(false),
-        verification_backend: config
-            .map(|c| c.verification_backend.clone())
-            .unwrap_or_else(|| "unknown".to_string()),
-        verification_level,
-        verification_fallback_enabled: config
-            .map(|c| c.verification_fallback_enabled)
-            .unwrap_or(false),
-        verification_fallback_require_signed_receipts: config
-            .map(|c| c.verification_fallback_require_signed_receipts)
-            .unwrap_or(false),
-        instance_url: session.instance_url.clone(),
-        app_url: session.app_url.clone(),
-        verify_url: session.verify_url.clone(),
-        eigen_app_id: session.eigen_app_id.clone(),
-        error: session.error.clone(),
-        created_at: session.created_at.to_rfc3339(),
-        updated_at: session.updated_at.to_rfc3339(),
-        expires_at: session.expires_at.to_rfc3339(),
-        profile_name: config.map(|c| c.profile_name.clone()),
-        todo_open_required_count: todos.todo_open_required_count,
-        todo_open_recommended_count: todos.todo_open_recommended_count,
-        todo_status_summary: todos.todo_status_summary,
-        runtime_state: session.runtime_state.as_str().to_string(),
-        funding_preflight_status: session.funding_preflight.status.clone(),
-        funding_preflight_failure_category: session.funding_preflight.failure_category.clone(),
+struct WalletBindingCheck;
+
+#[async_trait]
+impl FundingPreflightCheck for WalletBindingCheck {
+    fn id(&self) -> &str {
+        "wallet_binding"
+    }
+
+    fn failure_category(&self) -> &str {
+        "policy"
+    }
+
+    fn failure_precedence(&self) -> u8 {
+        0
+    }
+
+    async fn evaluate(
+        &self,
+        cfg: &FrontdoorUserConfig,
+        wallet: &str,
+    ) -> FundingPreflightCheckState {
+        preflight_check_state(
+            self.id(),
+            validate_wallet_association(cfg, wallet).is_ok(),
+            "Connected wallet and config wallet association match.",
+            "Connected wallet does not satisfy custody-mode wallet association.",
+        )
     }
 }
 
-fn render_session_summary(session: &ProvisioningSession) -> FrontdoorSessionSummaryResponse {
-    let config = session.config.as_ref();
-    let todos = build_gateway_todos(session);
-    let verification_level = verification_assurance_level(config);
-    FrontdoorSessionSummaryResponse {
-        session_ref: public_session_ref(session),
-        wallet_address: session.wallet_address.clone(),
-        version: session.version,
-        status: session.status.as_str().to_string(),
-        detail: session.detail.clone(),
-        provisioning_source: session.provisioning_source.as_str().to_string(),
-        dedicated_instance: session.provisioning_source.dedicated_instance(),
-        launched_on_eigencloud: session_launched_on_eigencloud(session).unwrap_or(false),
-        verification_backend: config
-            .map(|c| c.verification_backend.clone())
-            .unwrap_or_else(|| "unknown".to_string()),
-        verification_level,
-        verification_fallback_enabled: config
-            .map(|c| c.verification_fallback_enabled)
-            .unwrap_or(false),
-        verification_fallback_require_signed_receipts: config
+struct AuthMaterialCheck;
+
+#[async_trait]
+impl FundingPreflightCheck for AuthMaterialCheck {
+    fn id(&self) -> &str {
+        "auth_material"
+    }
+
+    fn failure_category(&self) -> &str {
+        "auth"
+    }
+
+    fn failure_precedence(&self) -> u8 {
+        1
+    }
+
+    async fn evaluate(
+        &self,
+        cfg: &FrontdoorUserConfig,
+        _wallet: &str,
+    ) -> FundingPreflightCheckState {
+        let auth_ready_default = if cfg.verification_backend == "eigencloud_primary"
+            && cfg.verification_eigencloud_auth_scheme == "api_key"
+        {
+            cfg.eigencloud_auth_key
+                .as_deref()
+                .map(|value| !value.trim().is_empty())
+                .unwrap_or(false)
+        } else {
+            true
+        };
+        preflight_check_state(
+            self.id(),
+            preflight_override_bool(cfg, "auth_ready").unwrap_or(auth_ready_default),
+            "Verification backend auth requirements satisfied.",
+            "Missing or invalid verification auth material.",
+        )
+    }
+}
+
+struct GasBudgetCheck;
+
+#[async_trait]
+impl FundingPreflightCheck for GasBudgetCheck {
+    fn id(&self) -> &str {
+        "gas_budget"
+    }
+
+    fn failure_category(&self) -> &str {
+        "gas"
+    }
+
+    fn failure_precedence(&self) -> u8 {
+        3
+    }
+
+    async fn evaluate(
+        &self,
+        cfg: &FrontdoorUserConfig,
+        _wallet: &str,
+    ) -> FundingPreflightCheckState {
+        preflight_check_state(
+            self.id(),
+            preflight_override_bool(cfg, "gas_ready").unwrap_or(true),
+            "Gas readiness checks passed.",
+            "Insufficient gas readiness for provisioning.",
+        )
+    }
+}
+
+struct PlatformFeeCheck;
+
+#[async_trait]
+impl FundingPreflightCheck for PlatformFeeCheck {
+    fn id(&self) -> &str {
+        "platform_fee"
+    }
+
+    fn failure_category(&self) -> &str {
+        "fee"
+    }
+
+    fn failure_precedence(&self) -> u8 {
+        4
+    }
+
+    async fn evaluate(
+        &self,
+        cfg: &FrontdoorUserConfig,
+        _wallet: &str,
+    ) -> FundingPreflightCheckState {
+        preflight_check_state(
+            self.id(),
+            preflight_override_bool(cfg, "fee_ready").unwrap_or(true),
+            "Platform fee readiness checks passed.",
+            "Insufficient platform fee readiness for provisioning.",
+        )
+    }
+}
+
+struct PolicyCheck;
+
+#[async_trait]
+impl FundingPreflightCheck for PolicyCheck {
+    fn id(&self) -> &str {
+        "policy"
+    }
+
+    fn failure_category(&self) -> &str {
+        "policy"
+    }
+
+    fn failure_precedence(&self) -> u8 {
+        2
+    }
+
+    async fn evaluate(
+        &self,
+        cfg: &FrontdoorUserConfig,
+        _wallet: &str,
+    ) -> FundingPreflightCheckState {
+        preflight_check_state(
+            self.id(),
+            preflight_override_bool(cfg, "policy_ready").unwrap_or(cfg.accept_terms),
+            "Policy acceptance and gating checks passed.",
+            "Policy checks failed (accept_terms or policy override).",
+        )
+    }
+}
+
+/// Built-in checks in their reported order. Failure categories keep their own
+/// precedence: wallet binding, auth, policy, gas, then fee.
+fn default_funding_preflight_checks() -> Vec<Arc<dyn FundingPreflightCheck>> {
+    vec![
+        Arc::new(WalletBindingCheck),
+        Arc::new(AuthMaterialCheck),
+        Arc::new(GasBudgetCheck),
+        Arc::new(PlatformFeeCheck),
+        Arc::new(PolicyCheck),
+    ]
+}
+
+async fn evaluate_funding_preflight(
+    checks: &[Arc<dyn FundingPreflightCheck>],
+    wallet: &str,
+    config: &FrontdoorUserConfig,
+    now: DateTime<Utc>,
+) -> FundingPreflightState {
+    let mut states = Vec::with_capacity(checks.len());
+    let mut failure: Option<(u8, &str)> = None;
+    for check in checks {
+        let timeout = check.timeout();
+        let state = match tokio::time::timeout(timeout, check.evaluate(config, wallet)).await {
+            Ok(state) => state,
+            Err(_) => FundingPreflightCheckState {
+                check_id: check.id().to_string(),
+                status: "failed".to_string(),
+                detail: format!("Check timed out after {} ms.", timeout.as_millis()),
+            },
+        };
+        if state.status != "passed"
+            && failure.is_none_or(|(precedence, _)| check.failure_precedence() < precedence)
+        {
+            failure = Some((check.failure_precedence(), check.failure_category()));
+        }
+        states.push(state);
+    }
+    let failure_category = failure.map(|(_, category)| category.to_string());
+
+    FundingPreflightState {
+        status: if failure_category.is_none() {
+            "passed".to_string()
+        } else {
+            "failed".to_string()
+        },
+        failure_category,
+        checks: states,
+        updated_at: now,
+    }
+}
+
+fn preflight_override_bool(config: &FrontdoorUserConfig, key: &str) -> Option<bool> {
+    config
+        .domain_overrides
+        .get(FRONTDOOR_PREFLIGHT_OVERRIDE_KEY)
+        .and_then(|value| value.as_object())
+        .and_then(|map| map.get(key))
+        .and_then(|value| value.as_bool())
+}
+
+fn render_session_response(session: &ProvisioningSession) -> FrontdoorSessionResponse {
+    let config = session.config.as_ref();
+    let todos = build_gateway_todos(session);
+    let verification_level = verification_assurance_level(config);
+    FrontdoorSessionResponse {
+        session_id: session.id.to_string(),
+        wallet_address: to_checksummed_address(&session.wallet_address),
+        privy_user_id: session.privy_user_id.clone(),
+        version: session.version,
+        status: session.status.as_str().to_string(),
+        detail: session.detail.clone(),
+        provisioning_source: session.provisioning_source.as_str().to_string(),
+        dedicated_instance: session.provisioning_source.dedicated_instance(),
+        launched_on_eigencloud: session_launched_on_eigencloud(session).unwrap_or(false),
+        verification_backend: config
+            .map(|c| c.verification_backend.clone())
+            .unwrap_or_else(|| "unknown".to_string()),
+        verification_level,
+        verification_fallback_enabled: config
+            .map(|c| c.verification_fallback_enabled)
+            .unwrap_or(false),
+        verification_fallback_require_signed_receipts: config
+            .map(|c| c.verification_fallback_require_signed_receipts)
+            .unwrap_or(false),
+        instance_url: session.instance_url.clone(),
+        app_url: session.app_url.clone(),
+        verify_url: session.verify_url.clone(),
+        eigen_app_id: session.eigen_app_id.clone(),
+        error: session.error.clone(),
+        created_at: session.created_at.to_rfc3339(),
+        updated_at: session.updated_at.to_rfc3339(),
+        expires_at: session.expires_at.to_rfc3339(),
+        profile_name: config.map(|c| c.profile_name.clone()),
+        todo_open_required_count: todos.todo_open_required_count,
+        todo_open_recommended_count: todos.todo_open_recommended_count,
+        todo_status_summary: todos.todo_status_summary,
+        runtime_state: session.runtime_state.as_str().to_string(),
+        funding_preflight_status: session.funding_preflight.status.clone(),
+        funding_preflight_failure_category: session.funding_preflight.failure_category.clone(),
+    }
+}
+
+fn render_session_summary(session: &ProvisioningSession) -> FrontdoorSessionSummaryResponse {
+    let config = session.config.as_ref();
+    let todos = build_gateway_todos(session);
+    let verification_level = verification_assurance_level(config);
+    FrontdoorSessionSummaryResponse {
+        session_ref: public_session_ref(session),
+        wallet_address: to_checksummed_address(&session.wallet_address),
+        version: session.version,
+        status: session.status.as_str().to_string(),
+        detail: session.detail.clone(),
+        provisioning_source: session.provisioning_source.as_str().to_string(),
+        dedicated_instance: session.provisioning_source.dedicated_instance(),
+        launched_on_eigencloud: session_launched_on_eigencloud(session).unwrap_or(false),
+        verification_backend: config
+            .map(|c| c.verification_backend.clone())
+            .unwrap_or_else(|| "unknown".to_string()),
+        verification_level,
+        verification_fallback_enabled: config
+            .map(|c| c.verification_fallback_enabled)
+            .unwrap_or(false),
+        verification_fallback_require_signed_receipts: config
             .map(|c| c.verification_fallback_require_signed_receipts)
             .unwrap_or(false),
         created_at: session.created_at.to_rfc3339(),
@@ -2529,8 +4333,13 @@ fn render_session_summary(session: &ProvisioningSession) -> FrontdoorSessionSumm
     }
 }
 
+/// Short, non-secret reference that is unique per wallet and version: the
+/// first 10 hex chars of `keccak256(lowercase wallet || ":" || version)`.
 fn public_session_ref(session: &ProvisioningSession) -> String {
-    format!("v{}", session.version)
+    let wallet = session.wallet_address.to_ascii_lowercase();
+    let version = session.version.to_string();
+    let digest = keccak256_concat(&[wallet.as_bytes(), b":", version.as_bytes()]);
+    hex::encode(&digest[..5], false)
 }
 
 fn session_launched_on_eigencloud(session: &ProvisioningSession) -> Option<bool> {
@@ -2728,7 +4537,7 @@ struct ProvisionCommandInput<'a> {
     verify_base_url: Option<&'a str>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct ProvisionCommandLog {
     source: String,
     stream: String,
@@ -2752,208 +4561,237 @@ fn classify_provision_log_source(line: &str) -> &'static str {
     }
 }
 
-fn build_provision_command(
-    template: &str,
-    input: &ProvisionCommandInput<'_>,
-) -> Result<Command, String> {
-    let config_json = serde_json::to_string(input.config)
-        .map_err(|e| format!("config serialization failed: {e}"))?;
-    let config_b64 =
-        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(config_json.as_bytes());
-    let eigencloud_auth_key = input.config.eigencloud_auth_key.clone().unwrap_or_default();
-    let verify_app_base_url = input.verify_base_url.unwrap_or("").to_string();
+/// A `{token}` accepted in `GATEWAY_FRONTDOOR_PROVISION_COMMAND`.
+///
+/// The command never sees values inline: each token is rewritten to a
+/// reference to `env_key`, which is exported with `value`'s result. `value`
+/// also receives the serialized user config.
+struct Placeholder {
+    token: &'static str,
+    env_key: &'static str,
+    value: fn(&ProvisionCommandInput<'_>, &str) -> String,
+}
 
-    let replacements = [
-        (
-            "{session_id}",
-            "ENCLAGENT_FRONTDOOR_SESSION_ID",
-            input.session_id.to_string(),
-        ),
-        (
-            "{wallet_address}",
-            "ENCLAGENT_FRONTDOOR_WALLET_ADDRESS",
-            input.wallet.to_string(),
-        ),
-        (
-            "{privy_user_id}",
-            "ENCLAGENT_FRONTDOOR_PRIVY_USER_ID",
-            input.privy_user_id.unwrap_or("").to_string(),
-        ),
-        (
-            "{privy_identity_token}",
-            "ENCLAGENT_FRONTDOOR_PRIVY_IDENTITY_TOKEN",
-            input.privy_identity_token.unwrap_or("").to_string(),
-        ),
-        (
-            "{privy_access_token}",
-            "ENCLAGENT_FRONTDOOR_PRIVY_ACCESS_TOKEN",
-            input.privy_access_token.unwrap_or("").to_string(),
-        ),
-        (
-            "{chain_id}",
-            "ENCLAGENT_FRONTDOOR_CHAIN_ID",
-            input.chain_id.to_string(),
-        ),
-        (
-            "{version}",
-            "ENCLAGENT_FRONTDOOR_VERSION",
-            input.version.to_string(),
-        ),
-        (
-            "{config_version}",
-            "ENCLAGENT_FRONTDOOR_CONFIG_VERSION",
-            input.config.config_version.to_string(),
-        ),
-        (
-            "{profile_domain}",
-            "ENCLAGENT_FRONTDOOR_PROFILE_DOMAIN",
-            input.config.profile_domain.clone(),
-        ),
-        (
-            "{domain_overrides_json}",
-            "ENCLAGENT_FRONTDOOR_DOMAIN_OVERRIDES_JSON",
+/// Every placeholder the provision command template may use; both
+/// [`parse_provision_command_template`] and [`build_provision_command`] read
+/// this list, so anything the parser accepts is substituted.
+const PROVISION_PLACEHOLDERS: &[Placeholder] = &[
+    Placeholder {
+        token: "session_id",
+        env_key: "ENCLAGENT_FRONTDOOR_SESSION_ID",
+        value: |input, _| input.session_id.to_string(),
+    },
+    Placeholder {
+        token: "wallet_address",
+        env_key: "ENCLAGENT_FRONTDOOR_WALLET_ADDRESS",
+        value: |input, _| input.wallet.to_string(),
+    },
+    Placeholder {
+        token: "privy_user_id",
+        env_key: "ENCLAGENT_FRONTDOOR_PRIVY_USER_ID",
+        value: |input, _| input.privy_user_id.unwrap_or("").to_string(),
+    },
+    Placeholder {
+        token: "privy_identity_token",
+        env_key: "ENCLAGENT_FRONTDOOR_PRIVY_IDENTITY_TOKEN",
+        value: |input, _| input.privy_identity_token.unwrap_or("").to_string(),
+    },
+    Placeholder {
+        token: "privy_access_token",
+        env_key: "ENCLAGENT_FRONTDOOR_PRIVY_ACCESS_TOKEN",
+        value: |input, _| input.privy_access_token.unwrap_or("").to_string(),
+    },
+    Placeholder {
+        token: "chain_id",
+        env_key: "ENCLAGENT_FRONTDOOR_CHAIN_ID",
+        value: |input, _| input.chain_id.to_string(),
+    },
+    Placeholder {
+        token: "version",
+        env_key: "ENCLAGENT_FRONTDOOR_VERSION",
+        value: |input, _| input.version.to_string(),
+    },
+    Placeholder {
+        token: "config_version",
+        env_key: "ENCLAGENT_FRONTDOOR_CONFIG_VERSION",
+        value: |input, _| input.config.config_version.to_string(),
+    },
+    Placeholder {
+        token: "profile_domain",
+        env_key: "ENCLAGENT_FRONTDOOR_PROFILE_DOMAIN",
+        value: |input, _| input.config.profile_domain.clone(),
+    },
+    Placeholder {
+        token: "domain_overrides_json",
+        env_key: "ENCLAGENT_FRONTDOOR_DOMAIN_OVERRIDES_JSON",
+        value: |input, _| {
             serde_json::to_string(&input.config.domain_overrides)
-                .unwrap_or_else(|_| "{}".to_string()),
-        ),
-        (
-            "{profile_name}",
-            "ENCLAGENT_FRONTDOOR_PROFILE_NAME",
-            input.config.profile_name.clone(),
-        ),
-        (
-            "{custody_mode}",
-            "ENCLAGENT_FRONTDOOR_CUSTODY_MODE",
-            input.config.custody_mode.clone(),
-        ),
-        (
-            "{operator_wallet_address}",
-            "ENCLAGENT_FRONTDOOR_OPERATOR_WALLET_ADDRESS",
+                .unwrap_or_else(|_| "{}".to_string())
+        },
+    },
+    Placeholder {
+        token: "profile_name",
+        env_key: "ENCLAGENT_FRONTDOOR_PROFILE_NAME",
+        value: |input, _| input.config.profile_name.clone(),
+    },
+    Placeholder {
+        token: "custody_mode",
+        env_key: "ENCLAGENT_FRONTDOOR_CUSTODY_MODE",
+        value: |input, _| input.config.custody_mode.clone(),
+    },
+    Placeholder {
+        token: "operator_wallet_address",
+        env_key: "ENCLAGENT_FRONTDOOR_OPERATOR_WALLET_ADDRESS",
+        value: |input, _| {
             input
                 .config
                 .operator_wallet_address
                 .clone()
-                .unwrap_or_default(),
-        ),
-        (
-            "{user_wallet_address}",
-            "ENCLAGENT_FRONTDOOR_USER_WALLET_ADDRESS",
-            input.config.user_wallet_address.clone().unwrap_or_default(),
-        ),
-        (
-            "{vault_address}",
-            "ENCLAGENT_FRONTDOOR_VAULT_ADDRESS",
-            input.config.vault_address.clone().unwrap_or_default(),
-        ),
-        (
-            "{gateway_auth_key}",
-            "ENCLAGENT_FRONTDOOR_GATEWAY_AUTH_KEY",
-            input.config.gateway_auth_key.clone(),
-        ),
-        (
-            "{eigencloud_auth_key}",
-            "ENCLAGENT_FRONTDOOR_EIGENCLOUD_AUTH_KEY",
-            eigencloud_auth_key,
-        ),
-        (
-            "{verification_backend}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_BACKEND",
-            input.config.verification_backend.clone(),
-        ),
-        (
-            "{verification_eigencloud_endpoint}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_EIGENCLOUD_ENDPOINT",
+                .unwrap_or_default()
+        },
+    },
+    Placeholder {
+        token: "user_wallet_address",
+        env_key: "ENCLAGENT_FRONTDOOR_USER_WALLET_ADDRESS",
+        value: |input, _| input.config.user_wallet_address.clone().unwrap_or_default(),
+    },
+    Placeholder {
+        token: "vault_address",
+        env_key: "ENCLAGENT_FRONTDOOR_VAULT_ADDRESS",
+        value: |input, _| input.config.vault_address.clone().unwrap_or_default(),
+    },
+    Placeholder {
+        token: "gateway_auth_key",
+        env_key: "ENCLAGENT_FRONTDOOR_GATEWAY_AUTH_KEY",
+        value: |input, _| input.config.gateway_auth_key.clone(),
+    },
+    Placeholder {
+        token: "eigencloud_auth_key",
+        env_key: "ENCLAGENT_FRONTDOOR_EIGENCLOUD_AUTH_KEY",
+        value: |input, _| input.config.eigencloud_auth_key.clone().unwrap_or_default(),
+    },
+    Placeholder {
+        token: "verification_backend",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_BACKEND",
+        value: |input, _| input.config.verification_backend.clone(),
+    },
+    Placeholder {
+        token: "verification_eigencloud_endpoint",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_EIGENCLOUD_ENDPOINT",
+        value: |input, _| {
             input
                 .config
                 .verification_eigencloud_endpoint
                 .clone()
-                .unwrap_or_default(),
-        ),
-        (
-            "{verification_eigencloud_auth_scheme}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_EIGENCLOUD_AUTH_SCHEME",
-            input.config.verification_eigencloud_auth_scheme.clone(),
-        ),
-        (
-            "{verification_eigencloud_timeout_ms}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_EIGENCLOUD_TIMEOUT_MS",
-            input.config.verification_eigencloud_timeout_ms.to_string(),
-        ),
-        (
-            "{verification_fallback_enabled}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_ENABLED",
-            if input.config.verification_fallback_enabled {
-                "true".to_string()
-            } else {
-                "false".to_string()
-            },
-        ),
-        (
-            "{verification_fallback_signing_key_id}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_SIGNING_KEY_ID",
+                .unwrap_or_default()
+        },
+    },
+    Placeholder {
+        token: "verification_eigencloud_auth_scheme",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_EIGENCLOUD_AUTH_SCHEME",
+        value: |input, _| input.config.verification_eigencloud_auth_scheme.clone(),
+    },
+    Placeholder {
+        token: "verification_eigencloud_timeout_ms",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_EIGENCLOUD_TIMEOUT_MS",
+        value: |input, _| input.config.verification_eigencloud_timeout_ms.to_string(),
+    },
+    Placeholder {
+        token: "verification_fallback_enabled",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_ENABLED",
+        value: |input, _| input.config.verification_fallback_enabled.to_string(),
+    },
+    Placeholder {
+        token: "verification_fallback_signing_key_id",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_SIGNING_KEY_ID",
+        value: |input, _| {
             input
                 .config
                 .verification_fallback_signing_key_id
                 .clone()
-                .unwrap_or_default(),
-        ),
-        (
-            "{verification_fallback_chain_path}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_CHAIN_PATH",
+                .unwrap_or_default()
+        },
+    },
+    Placeholder {
+        token: "verification_fallback_chain_path",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_CHAIN_PATH",
+        value: |input, _| {
             input
                 .config
                 .verification_fallback_chain_path
                 .clone()
-                .unwrap_or_default(),
-        ),
-        (
-            "{verification_fallback_require_signed_receipts}",
-            "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_REQUIRE_SIGNED_RECEIPTS",
-            if input.config.verification_fallback_require_signed_receipts {
-                "true".to_string()
-            } else {
-                "false".to_string()
-            },
-        ),
-        (
-            "{verify_app_base_url}",
-            "ENCLAGENT_FRONTDOOR_VERIFY_APP_BASE_URL",
-            verify_app_base_url,
-        ),
-        (
-            "{inference_summary}",
-            "ENCLAGENT_FRONTDOOR_INFERENCE_SUMMARY",
-            input.config.inference_summary.clone().unwrap_or_default(),
-        ),
-        (
-            "{inference_confidence}",
-            "ENCLAGENT_FRONTDOOR_INFERENCE_CONFIDENCE",
+                .unwrap_or_default()
+        },
+    },
+    Placeholder {
+        token: "verification_fallback_require_signed_receipts",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFICATION_FALLBACK_REQUIRE_SIGNED_RECEIPTS",
+        value: |input, _| {
+            input
+                .config
+                .verification_fallback_require_signed_receipts
+                .to_string()
+        },
+    },
+    Placeholder {
+        token: "verify_app_base_url",
+        env_key: "ENCLAGENT_FRONTDOOR_VERIFY_APP_BASE_URL",
+        value: |input, _| input.verify_base_url.unwrap_or("").to_string(),
+    },
+    Placeholder {
+        token: "inference_summary",
+        env_key: "ENCLAGENT_FRONTDOOR_INFERENCE_SUMMARY",
+        value: |input, _| input.config.inference_summary.clone().unwrap_or_default(),
+    },
+    Placeholder {
+        token: "inference_confidence",
+        env_key: "ENCLAGENT_FRONTDOOR_INFERENCE_CONFIDENCE",
+        value: |input, _| {
             input
                 .config
                 .inference_confidence
                 .map(|v| v.to_string())
-                .unwrap_or_default(),
-        ),
-        (
-            "{config_json}",
-            "ENCLAGENT_FRONTDOOR_CONFIG_JSON",
-            config_json.clone(),
-        ),
-        ("{config_b64}", "ENCLAGENT_FRONTDOOR_CONFIG_B64", config_b64),
-    ];
+                .unwrap_or_default()
+        },
+    },
+    Placeholder {
+        token: "config_json",
+        env_key: "ENCLAGENT_FRONTDOOR_CONFIG_JSON",
+        value: |_, config_json| config_json.to_string(),
+    },
+    Placeholder {
+        token: "config_b64",
+        env_key: "ENCLAGENT_FRONTDOOR_CONFIG_B64",
+        value: |_, config_json| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(config_json.as_bytes())
+        },
+    },
+];
+
+fn build_provision_command(
+    template: &str,
+    input: &ProvisionCommandInput<'_>,
+) -> Result<Command, String> {
+    let config_json = serde_json::to_string(input.config)
+        .map_err(|e| format!("config serialization failed: {e}"))?;
 
     let mut cmd = template.to_string();
-    for (placeholder, env_key, _) in &replacements {
-        let env_ref = format!("${{{env_key}}}");
-        let quoted_env_ref = format!("\"${{{env_key}}}\"");
-        cmd = cmd.replace(&format!("'{}'", placeholder), &quoted_env_ref);
-        cmd = cmd.replace(&format!("\"{}\"", placeholder), &quoted_env_ref);
-        cmd = cmd.replace(placeholder, &env_ref);
+    for placeholder in PROVISION_PLACEHOLDERS {
+        let token = format!("{{{}}}", placeholder.token);
+        let env_ref = format!("${{{}}}", placeholder.env_key);
+        let quoted_env_ref = format!("\"${{{}}}\"", placeholder.env_key);
+        cmd = cmd.replace(&format!("'{token}'"), &quoted_env_ref);
+        cmd = cmd.replace(&format!("\"{token}\""), &quoted_env_ref);
+        cmd = cmd.replace(&token, &env_ref);
     }
 
     let mut command = Command::new("/bin/sh");
     command.arg("-c").arg(cmd);
-    for (_, env_key, value) in &replacements {
-        command.env(env_key, value);
+    for placeholder in PROVISION_PLACEHOLDERS {
+        command.env(
+            placeholder.env_key,
+            (placeholder.value)(input, &config_json),
+        );
     }
     Ok(command)
 }
@@ -2961,6 +4799,7 @@ fn build_provision_command(
 async fn execute_provision_command_with_stream<F, Fut>(
     template: &str,
     input: &ProvisionCommandInput<'_>,
+    timeout: Option<std::time::Duration>,
     mut on_log: F,
 ) -> Result<ProvisioningResult, String>
 where
@@ -2990,9 +4829,22 @@ where
     let mut heartbeat = tokio::time::interval(std::time::Duration::from_secs(20));
     heartbeat.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
     heartbeat.tick().await;
+    let deadline = timeout.map(|limit| tokio::time::Instant::now() + limit);
+    let deadline_elapsed = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(deadline_elapsed);
+    let mut timed_out = false;
 
     while !(stdout_complete && stderr_complete) {
         tokio::select! {
+            _ = &mut deadline_elapsed => {
+                timed_out = true;
+                break;
+            }
             maybe_line = async {
                 if let Some(lines) = stdout_lines.as_mut() {
                     lines.next_line().await
@@ -3073,10 +4925,31 @@ where
         }
     }
 
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("failed waiting for provision command: {e}"))?;
+    let status = if timed_out {
+        None
+    } else {
+        tokio::select! {
+            status = child.wait() => Some(
+                status.map_err(|e| format!("failed waiting for provision command: {e}"))?,
+            ),
+            _ = &mut deadline_elapsed => None,
+        }
+    };
+    let Some(status) = status else {
+        let limit_secs = timeout.map(|limit| limit.as_secs()).unwrap_or_default();
+        if let Err(err) = child.start_kill() {
+            tracing::warn!(error = %err, "Failed to kill timed out provision command");
+        }
+        let _ = child.wait().await;
+        let detail = format!("provision command timed out after {limit_secs}s and was killed");
+        on_log(ProvisionCommandLog {
+            source: "provision".to_string(),
+            stream: "provision_timeout".to_string(),
+            line: detail.clone(),
+        })
+        .await;
+        return Err(detail);
+    };
     if !status.success() {
         return Err(format!(
             "provision command failed with status {}: {}",
@@ -3104,41 +4977,143 @@ fn load_wallet_store(path: &PathBuf) -> Result<WalletSessionStore, String> {
     serde_json::from_slice(&data).map_err(|e| format!("failed parsing store json: {e}"))
 }
 
-fn persist_wallet_store(path: &PathBuf, store: &WalletSessionStore) -> Result<(), String> {
+/// Replace the wallet store with `store`, a full snapshot of the in-memory map.
+///
+/// Services sharing the file serialize on [`path_lock`], and readers only ever
+/// see a complete store.
+fn persist_wallet_store(path: &std::path::Path, store: &WalletSessionStore) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| format!("failed creating store dir: {e}"))?;
     }
     let serialized = serde_json::to_vec_pretty(store)
         .map_err(|e| format!("failed serializing store json: {e}"))?;
-    std::fs::write(path, serialized).map_err(|e| format!("failed writing store: {e}"))
+    let lock = path_lock(path);
+    let _guard = lock
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    write_file_atomically(path, &serialized).map_err(|e| format!("failed writing store: {e}"))
 }
 
-fn purge_expired_sessions(state: &mut FrontdoorState) {
-    let now = Utc::now();
-    let mut expired_ids = Vec::new();
-    for (id, session) in &mut state.sessions {
-        if session.expires_at < now
-            && matches!(
-                session.status,
-                SessionStatus::AwaitingSignature | SessionStatus::Provisioning
+/// `secs` as a duration, capped at [`FRONTDOOR_MAX_CONFIG_DURATION_SECS`] so
+/// timestamp arithmetic with it cannot overflow.
+fn config_duration(secs: u64) -> chrono::Duration {
+    chrono::Duration::seconds(secs.min(FRONTDOOR_MAX_CONFIG_DURATION_SECS) as i64)
+}
+
+fn prune_wallet_records(
+    wallets: &mut HashMap<String, WalletSessionRecord>,
+    retention_secs: u64,
+    now: DateTime<Utc>,
+) -> usize {
+    if retention_secs == 0 {
+        return 0;
+    }
+    // A window reaching past the earliest representable time keeps everything.
+    let Some(cutoff) = i64::try_from(retention_secs)
+        .ok()
+        .and_then(chrono::TimeDelta::try_seconds)
+        .and_then(|retention| now.checked_sub_signed(retention))
+    else {
+        return 0;
+    };
+    let before = wallets.len();
+    // Records with an unreadable timestamp are kept; they cannot be shown stale.
+    wallets.retain(|_, record| {
+        DateTime::parse_from_rfc3339(&record.updated_at)
+            .map(|updated_at| updated_at.with_timezone(&Utc) >= cutoff)
+            .unwrap_or(true)
+    });
+    before - wallets.len()
+}
+
+/// Mark lapsed sessions expired, drop those more than `retention` past
+/// `expires_at`, and forget challenge timestamps older than
+/// `challenge_window` and consumed nonces older than `nonce_ttl`. Returns the
+/// number of sessions dropped.
+/// Fail a restored session whose provisioning run died with the previous
+/// process.
+fn mark_provisioning_interrupted(session: &mut ProvisioningSession, now: DateTime<Utc>) {
+    let detail = "Provisioning interrupted by a restart";
+    session.status = SessionStatus::Failed;
+    session.detail = detail.to_string();
+    session.error = Some(detail.to_ascii_lowercase());
+    session.updated_at = now;
+    session.provisioning_started_at = None;
+    push_timeline_event(session, "provisioning_failed", "failed", detail, "system");
+}
+
+/// Payload keys holding credentials. They are blanked before a session is
+/// written to the store, so restored sessions cannot be provisioned again.
+const FRONTDOOR_PERSISTED_SECRET_FIELDS: [&str; 2] = ["gateway_auth_key", "eigencloud_auth_key"];
+
+fn redact_persisted_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if FRONTDOOR_PERSISTED_SECRET_FIELDS.contains(&key.as_str()) {
+                    *field = if field.is_string() {
+                        serde_json::Value::String(String::new())
+                    } else {
+                        serde_json::Value::Null
+                    };
+                } else {
+                    redact_persisted_secrets(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_persisted_secrets),
+        _ => {}
+    }
+}
+
+fn purge_expired_sessions(
+    state: &mut FrontdoorState,
+    now: DateTime<Utc>,
+    challenge_window: chrono::Duration,
+    retention: chrono::Duration,
+    nonce_ttl: chrono::Duration,
+) -> usize {
+    state.challenge_issued_at.retain(|_, issued| {
+        issued.retain(|at| *at + challenge_window > now);
+        !issued.is_empty()
+    });
+    state
+        .consumed_nonces
+        .retain(|_, consumed_at| *consumed_at + nonce_ttl > now);
+
+    let mut expired_ids = Vec::new();
+    for (id, session) in &mut state.sessions {
+        if session.expires_at < now
+            && matches!(
+                session.status,
+                SessionStatus::AwaitingSignature | SessionStatus::Provisioning
             )
         {
             session.status = SessionStatus::Expired;
             session.updated_at = now;
             session.detail = "Session expired".to_string();
         }
-        if session.expires_at + chrono::Duration::hours(6) < now {
+        if session.expires_at + retention < now {
             expired_ids.push(*id);
         }
     }
+    let removed = expired_ids.len();
     for id in expired_ids {
         state.sessions.remove(&id);
     }
+    removed
+}
+
+fn new_timeline_sender() -> broadcast::Sender<FrontdoorSessionTimelineEvent> {
+    broadcast::channel(FRONTDOOR_TIMELINE_BROADCAST_CAPACITY).0
+}
+
+fn random_nonce(rng: &dyn RngSource, len: usize) -> String {
+    rng.alphanumeric(len)
 }
 
-fn random_nonce(len: usize) -> String {
-    rand::thread_rng()
-        .sample_iter(&Alphanumeric)
+fn sample_alphanumeric<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
+    rng.sample_iter(&Alphanumeric)
         .take(len)
         .map(char::from)
         .collect()
@@ -3161,7 +5136,21 @@ fn verify_wallet_signature(
     signature_hex: &str,
     expected_wallet: &str,
 ) -> Result<(), String> {
-    let signature_bytes = decode_hex_prefixed(signature_hex)?;
+    verify_wallet_signature_prehash(
+        &eip191_personal_sign_hash(message),
+        signature_hex,
+        expected_wallet,
+    )
+}
+
+/// Recover the signer of a 65-byte `r || s || v` signature over `prehash` and
+/// check it against `expected_wallet`. Shared by personal_sign and EIP-712.
+fn verify_wallet_signature_prehash(
+    prehash: &[u8; 32],
+    signature_hex: &str,
+    expected_wallet: &str,
+) -> Result<(), String> {
+    let signature_bytes = hex::decode(signature_hex).map_err(|e| e.to_string())?;
     if signature_bytes.len() != 65 {
         return Err("signature must decode to 65 bytes".to_string());
     }
@@ -3169,8 +5158,7 @@ fn verify_wallet_signature(
     let sig = EcdsaSignature::try_from(&signature_bytes[..64])
         .map_err(|e| format!("invalid ECDSA signature bytes: {e}"))?;
     let recovery_id = normalize_recovery_id(signature_bytes[64])?;
-    let prehash = eip191_personal_sign_hash(message);
-    let verifying_key = VerifyingKey::recover_from_prehash(&prehash, &sig, recovery_id)
+    let verifying_key = VerifyingKey::recover_from_prehash(prehash, &sig, recovery_id)
         .map_err(|e| format!("failed recovering signer from signature: {e}"))?;
     let recovered_wallet = ethereum_address_from_verifying_key(&verifying_key)?;
     let expected = normalize_wallet_address(expected_wallet)
@@ -3202,67 +5190,207 @@ fn eip191_personal_sign_hash(message: &str) -> [u8; 32] {
     out
 }
 
-fn ethereum_address_from_verifying_key(key: &VerifyingKey) -> Result<String, String> {
-    let encoded = key.to_encoded_point(false);
-    let pubkey = encoded.as_bytes();
-    if pubkey.len() != 65 || pubkey[0] != 0x04 {
-        return Err("unexpected recovered public key format".to_string());
-    }
+/// EIP-712 digest of the `FrontdoorAuthorization` struct for a challenge:
+/// `keccak256(0x1901 || domainSeparator || hashStruct(authorization))`.
+/// `issued_at` and `expires_at` come from the challenge's `Issued At` and
+/// `Expires At` lines, so the signature covers the expiry verify enforces.
+fn eip712_authorization_hash(
+    message_domain: &Eip712MessageDomain<'_>,
+    session_id: &str,
+    wallet: &str,
+    nonce: &str,
+    issued_at: &str,
+    expires_at: &str,
+) -> Result<[u8; 32], String> {
+    let wallet = normalize_wallet_address(wallet)
+        .ok_or_else(|| "wallet_address must be a valid EVM address".to_string())?;
+    let wallet_bytes = hex::decode(&wallet).map_err(|e| e.to_string())?;
+
+    let mut chain_id_word = [0u8; 32];
+    chain_id_word[24..].copy_from_slice(&message_domain.chain_id.to_be_bytes());
+    let domain_separator = keccak256_concat(&[
+        &keccak256_concat(&[b"EIP712Domain(string name,string version,uint256 chainId)"]),
+        &keccak256_concat(&[message_domain.name.as_bytes()]),
+        &keccak256_concat(&[message_domain.version.as_bytes()]),
+        &chain_id_word,
+    ]);
+
+    let mut wallet_word = [0u8; 32];
+    wallet_word[12..].copy_from_slice(&wallet_bytes);
+    let struct_hash = keccak256_concat(&[
+        &keccak256_concat(&[FRONTDOOR_EIP712_AUTHORIZATION_TYPE.as_bytes()]),
+        &wallet_word,
+        &keccak256_concat(&[session_id.as_bytes()]),
+        &keccak256_concat(&[nonce.as_bytes()]),
+        &keccak256_concat(&[issued_at.as_bytes()]),
+        &keccak256_concat(&[expires_at.as_bytes()]),
+    ]);
+
+    Ok(keccak256_concat(&[
+        b"\x19\x01",
+        &domain_separator,
+        &struct_hash,
+    ]))
+}
 
+fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
     let mut hasher = Keccak256::new();
-    hasher.update(&pubkey[1..]);
+    for part in parts {
+        hasher.update(part);
+    }
     let digest = hasher.finalize();
-    let address = &digest[12..];
-    Ok(format!("0x{}", encode_hex_lower(address)))
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest);
+    out
 }
 
-fn decode_hex_prefixed(value: &str) -> Result<Vec<u8>, String> {
-    let trimmed = value.trim();
-    if !trimmed.starts_with("0x") {
-        return Err("hex string must be 0x-prefixed".to_string());
-    }
-    let hex = &trimmed[2..];
-    if hex.is_empty() {
-        return Err("hex string is empty".to_string());
-    }
-    if !hex.len().is_multiple_of(2) {
-        return Err("hex string must have an even number of characters".to_string());
-    }
+/// Security-relevant fields of a challenge message. Fields a custom template
+/// leaves out are `None`; only `Nonce` is always required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChallengeFields {
+    /// Lowercased wallet address.
+    wallet: Option<String>,
+    privy_link: Option<String>,
+    chain_id: Option<u64>,
+    session_id: Option<Uuid>,
+    version: Option<u64>,
+    nonce: String,
+    issued_at: Option<String>,
+    expires_at: Option<String>,
+}
 
-    let mut out = Vec::with_capacity(hex.len() / 2);
-    let bytes = hex.as_bytes();
-    for pair in bytes.chunks_exact(2) {
-        let hi = decode_hex_nibble(pair[0]).ok_or_else(|| "invalid hex character".to_string())?;
-        let lo = decode_hex_nibble(pair[1]).ok_or_else(|| "invalid hex character".to_string())?;
-        out.push((hi << 4) | lo);
+impl ChallengeFields {
+    /// Name of the first field line that differs from `issued`, if any.
+    fn first_mismatch(&self, issued: &Self) -> Option<&'static str> {
+        [
+            ("Wallet", self.wallet == issued.wallet),
+            ("Privy Link", self.privy_link == issued.privy_link),
+            ("Chain ID", self.chain_id == issued.chain_id),
+            ("Session ID", self.session_id == issued.session_id),
+            ("Version", self.version == issued.version),
+            ("Nonce", self.nonce == issued.nonce),
+            ("Issued At", self.issued_at == issued.issued_at),
+            ("Expires At", self.expires_at == issued.expires_at),
+        ]
+        .into_iter()
+        .find_map(|(field, matches)| (!matches).then_some(field))
     }
-    Ok(out)
 }
 
-fn decode_hex_nibble(byte: u8) -> Option<u8> {
-    match byte {
-        b'0'..=b'9' => Some(byte - b'0'),
-        b'a'..=b'f' => Some(byte - b'a' + 10),
-        b'A'..=b'F' => Some(byte - b'A' + 10),
-        _ => None,
-    }
+/// Trimmed, non-blank lines of a challenge message, in order.
+fn normalized_challenge_lines(message: &str) -> impl Iterator<Item = &str> {
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
 }
 
-fn encode_hex_lower(bytes: &[u8]) -> String {
-    let mut out = String::with_capacity(bytes.len() * 2);
-    for b in bytes {
-        out.push(nibble_to_hex(b >> 4));
-        out.push(nibble_to_hex(b & 0x0f));
+/// Extract [`ChallengeFields`] by line prefix, ignoring line order,
+/// indentation, and surrounding whitespace. A field given twice is rejected
+/// so a message cannot carry a decoy value ahead of the real one.
+fn parse_challenge_message(message: &str) -> Result<ChallengeFields, String> {
+    const FIELDS: [&str; 8] = [
+        "Wallet",
+        "Privy Link",
+        "Chain ID",
+        "Session ID",
+        "Version",
+        "Nonce",
+        "Issued At",
+        "Expires At",
+    ];
+    let mut values: [Option<&str>; 8] = [None; 8];
+    for line in message.lines() {
+        let line = line.trim();
+        for (slot, field) in values.iter_mut().zip(FIELDS) {
+            let Some(value) = line
+                .strip_prefix(field)
+                .and_then(|rest| rest.trim_start().strip_prefix(':'))
+            else {
+                continue;
+            };
+            if slot.is_some() {
+                return Err(format!("challenge message repeats the '{field}' line"));
+            }
+            *slot = Some(value.trim());
+        }
     }
-    out
+    let [
+        wallet,
+        privy_link,
+        chain_id,
+        session_id,
+        version,
+        nonce,
+        issued_at,
+        expires_at,
+    ] = values;
+
+    let nonce = nonce
+        .filter(|nonce| !nonce.is_empty())
+        .ok_or_else(|| "challenge message is missing a nonce".to_string())?;
+    let chain_id = chain_id
+        .map(|raw| {
+            raw.parse::<u64>()
+                .map_err(|_| "challenge message has an invalid 'Chain ID'".to_string())
+        })
+        .transpose()?;
+    let wallet = wallet
+        .map(|raw| {
+            normalize_wallet_address(raw)
+                .ok_or_else(|| "challenge message has an invalid 'Wallet'".to_string())
+        })
+        .transpose()?;
+    let session_id = session_id
+        .map(|raw| {
+            Uuid::parse_str(raw)
+                .map_err(|_| "challenge message has an invalid 'Session ID'".to_string())
+        })
+        .transpose()?;
+    let version = version
+        .map(|raw| {
+            raw.strip_prefix('v')
+                .unwrap_or(raw)
+                .parse::<u64>()
+                .map_err(|_| "challenge message has an invalid 'Version'".to_string())
+        })
+        .transpose()?;
+
+    Ok(ChallengeFields {
+        wallet,
+        privy_link: privy_link.map(str::to_string),
+        chain_id,
+        session_id,
+        version,
+        nonce: nonce.to_string(),
+        issued_at: issued_at.map(str::to_string),
+        expires_at: expires_at.map(str::to_string),
+    })
+}
+
+/// Value of the `Field: value` line in a challenge message. Lines are matched
+/// trimmed, the same way [`validate_challenge_template`] checks them.
+fn challenge_message_field<'a>(message: &'a str, field: &str) -> Option<&'a str> {
+    message.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix(field)
+            .and_then(|rest| rest.strip_prefix(": "))
+            .map(str::trim)
+    })
 }
 
-fn nibble_to_hex(nibble: u8) -> char {
-    match nibble {
-        0..=9 => (b'0' + nibble) as char,
-        10..=15 => (b'a' + (nibble - 10)) as char,
-        _ => '0',
+fn ethereum_address_from_verifying_key(key: &VerifyingKey) -> Result<String, String> {
+    let encoded = key.to_encoded_point(false);
+    let pubkey = encoded.as_bytes();
+    if pubkey.len() != 65 || pubkey[0] != 0x04 {
+        return Err("unexpected recovered public key format".to_string());
     }
+
+    let mut hasher = Keccak256::new();
+    hasher.update(&pubkey[1..]);
+    let digest = hasher.finalize();
+    let address = &digest[12..];
+    Ok(hex::encode(address, true))
 }
 
 fn normalize_wallet_address(value: &str) -> Option<String> {
@@ -3277,8 +5405,41 @@ fn normalize_wallet_address(value: &str) -> Option<String> {
     Some(trimmed.to_ascii_lowercase())
 }
 
-fn message_matches(candidate: &str, expected: &str) -> bool {
-    candidate.trim() == expected.trim()
+/// Render an address in EIP-55 mixed case. Input that is not a 0x-prefixed
+/// 40-hex address is returned trimmed but otherwise unchanged.
+fn to_checksummed_address(addr: &str) -> String {
+    let Some(lower) = normalize_wallet_address(addr) else {
+        return addr.trim().to_string();
+    };
+    let hex = &lower[2..];
+    let hash = Keccak256::digest(hex.as_bytes());
+    let mut out = String::with_capacity(42);
+    out.push_str("0x");
+    for (idx, ch) in hex.chars().enumerate() {
+        let byte = hash[idx / 2];
+        let nibble = if idx % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+        if ch.is_ascii_alphabetic() && nibble >= 8 {
+            out.push(ch.to_ascii_uppercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// All-lowercase and all-uppercase addresses carry no checksum and pass;
+/// mixed-case addresses must match their EIP-55 rendering exactly.
+fn address_checksum_matches(addr: &str) -> bool {
+    let trimmed = addr.trim();
+    let Some(hex) = trimmed.strip_prefix("0x") else {
+        return false;
+    };
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    if !(has_lower && has_upper) {
+        return true;
+    }
+    to_checksummed_address(trimmed) == trimmed
 }
 
 fn mandatory_frontdoor_steps() -> Vec<String> {
@@ -3428,6 +5589,10 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
         "business_ops".to_string(),
         "communications".to_string(),
     ];
+    let seed_override_keys: Vec<String> = LAUNCHPAD_SEED_OVERRIDE_KEYS
+        .iter()
+        .map(|key| key.to_string())
+        .collect();
 
     vec![
         FrontdoorDomainProfile {
@@ -3450,6 +5615,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "inference_warnings".to_string(),
                 "enable_memory".to_string(),
             ],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "developer".to_string(),
@@ -3463,6 +5629,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "accept_terms".to_string(),
             ],
             optional_fields: vec!["domain_overrides".to_string(), "enable_memory".to_string()],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "creative".to_string(),
@@ -3476,6 +5643,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "accept_terms".to_string(),
             ],
             optional_fields: vec!["domain_overrides".to_string(), "enable_memory".to_string()],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "research".to_string(),
@@ -3489,6 +5657,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "accept_terms".to_string(),
             ],
             optional_fields: vec!["domain_overrides".to_string(), "enable_memory".to_string()],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "business_ops".to_string(),
@@ -3502,6 +5671,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "accept_terms".to_string(),
             ],
             optional_fields: vec!["domain_overrides".to_string(), "enable_memory".to_string()],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "communications".to_string(),
@@ -3515,6 +5685,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "accept_terms".to_string(),
             ],
             optional_fields: vec!["domain_overrides".to_string(), "enable_memory".to_string()],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "hyperliquid".to_string(),
@@ -3550,6 +5721,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "eigencloud_auth_key".to_string(),
                 "verification_fallback_chain_path".to_string(),
             ],
+            allowed_override_keys: seed_override_keys.clone(),
         },
         FrontdoorDomainProfile {
             domain: "eigenda".to_string(),
@@ -3573,6 +5745,7 @@ fn frontdoor_domain_profiles() -> Vec<FrontdoorDomainProfile> {
                 "enable_memory".to_string(),
                 "verification_fallback_chain_path".to_string(),
             ],
+            allowed_override_keys: seed_override_keys,
         },
     ]
 }
@@ -3581,6 +5754,7 @@ fn default_frontdoor_user_config(
     wallet: &str,
     gateway_auth_key: Option<&str>,
     domain: &str,
+    rng: &dyn RngSource,
 ) -> FrontdoorUserConfig {
     FrontdoorUserConfig {
         config_version: FRONTDOOR_CURRENT_CONFIG_VERSION,
@@ -3603,7 +5777,7 @@ fn default_frontdoor_user_config(
         per_trade_notional_cap_usd: 100,
         max_leverage: 2,
         max_slippage_bps: 50,
-        symbol_allowlist: vec!["BTC".to_string(), "ETH".to_string()],
+        symbol_allowlist: default_symbol_allowlist(HyperliquidNetwork::Testnet),
         symbol_denylist: Vec::new(),
         custody_mode: "user_wallet".to_string(),
         operator_wallet_address: None,
@@ -3616,7 +5790,7 @@ fn default_frontdoor_user_config(
         gateway_auth_key: gateway_auth_key
             .filter(|v| is_valid_gateway_auth_key(v))
             .map(str::to_string)
-            .unwrap_or_else(generate_gateway_auth_key),
+            .unwrap_or_else(|| generate_gateway_auth_key(rng)),
         eigencloud_auth_key: None,
         verification_backend: "eigencloud_primary".to_string(),
         verification_eigencloud_endpoint: None,
@@ -3627,11 +5801,50 @@ fn default_frontdoor_user_config(
         verification_fallback_chain_path: None,
         verification_fallback_require_signed_receipts: true,
         accept_terms: true,
+        accepted_terms_version: None,
+        accepted_terms_at: None,
+    }
+}
+
+/// A fresh `Strong` gateway auth key; the rare draw that falls short is
+/// discarded. A source that misses [`GATEWAY_AUTH_KEY_MAX_DRAWS`] times in a
+/// row is not trusted further, and the key comes from
+/// [`structured_gateway_auth_key`] on `thread_rng` instead.
+fn generate_gateway_auth_key(rng: &dyn RngSource) -> String {
+    for _ in 0..GATEWAY_AUTH_KEY_MAX_DRAWS {
+        let key = format!("lc_{}", random_nonce(rng, 24));
+        if gateway_auth_key_strength(&key) == KeyStrength::Strong {
+            return key;
+        }
     }
+    tracing::warn!(
+        draws = GATEWAY_AUTH_KEY_MAX_DRAWS,
+        "RNG source produced no strong gateway auth key; using the structured construction"
+    );
+    structured_gateway_auth_key(&mut rand::thread_rng())
 }
 
-fn generate_gateway_auth_key() -> String {
-    format!("lc_{}", random_nonce(24))
+/// `lc_` plus 24 characters cycling uppercase, digit, lowercase, each class
+/// sampled without replacement. That gives 24 distinct characters across all
+/// four classes and no letter or digit run long enough to hold a weak
+/// pattern, so the result is always `Strong`.
+fn structured_gateway_auth_key<R: Rng + ?Sized>(rng: &mut R) -> String {
+    const CLASSES: [&[u8]; 3] = [
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        b"0123456789",
+        b"abcdefghijklmnopqrstuvwxyz",
+    ];
+    let picks: Vec<Vec<u8>> = CLASSES
+        .iter()
+        .map(|class| class.choose_multiple(rng, 8).copied().collect())
+        .collect();
+    let mut key = String::from("lc_");
+    for i in 0..8 {
+        for pick in &picks {
+            key.push(char::from(pick[i]));
+        }
+    }
+    key
 }
 
 fn normalize_domain_name(value: &str) -> Result<String, String> {
@@ -3653,7 +5866,71 @@ fn normalize_domain_name(value: &str) -> Result<String, String> {
     Ok(normalized)
 }
 
-fn is_valid_gateway_auth_key(value: &str) -> bool {
+/// Substrings that make a gateway auth key guessable regardless of length.
+const WEAK_GATEWAY_AUTH_KEY_PATTERNS: [&str; 8] = [
+    "password",
+    "passwd",
+    "changeme",
+    "letmein",
+    "qwerty",
+    "12345678",
+    "abcdefgh",
+    "secretkey",
+];
+/// Distinct characters an `Acceptable` gateway auth key needs.
+const GATEWAY_AUTH_KEY_MIN_DISTINCT_CHARS: usize = 8;
+/// Distinct characters a `Strong` gateway auth key needs.
+const GATEWAY_AUTH_KEY_STRONG_DISTINCT_CHARS: usize = 12;
+/// Length a `Strong` gateway auth key needs.
+const GATEWAY_AUTH_KEY_STRONG_LEN: usize = 24;
+/// Draws [`generate_gateway_auth_key`] takes from its source before falling
+/// back to [`structured_gateway_auth_key`].
+const GATEWAY_AUTH_KEY_MAX_DRAWS: usize = 16;
+
+/// How hard a gateway auth key is to guess, ordered weakest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum KeyStrength {
+    /// Too few distinct characters, or contains an obvious pattern.
+    Weak,
+    Acceptable,
+    /// Long, varied, and mixing at least three character classes.
+    Strong,
+}
+
+/// Rate `key` by its character variety; length and charset rules are
+/// checked separately by [`is_valid_gateway_auth_key`].
+fn gateway_auth_key_strength(key: &str) -> KeyStrength {
+    let key = key.trim();
+    let lower = key.to_ascii_lowercase();
+    let distinct = key.chars().collect::<HashSet<_>>().len();
+    if distinct < GATEWAY_AUTH_KEY_MIN_DISTINCT_CHARS
+        || WEAK_GATEWAY_AUTH_KEY_PATTERNS
+            .iter()
+            .any(|pattern| lower.contains(pattern))
+    {
+        return KeyStrength::Weak;
+    }
+
+    let classes = [
+        key.chars().any(|c| c.is_ascii_lowercase()),
+        key.chars().any(|c| c.is_ascii_uppercase()),
+        key.chars().any(|c| c.is_ascii_digit()),
+        key.chars().any(|c| c.is_ascii_punctuation()),
+    ]
+    .into_iter()
+    .filter(|present| *present)
+    .count();
+    if key.len() >= GATEWAY_AUTH_KEY_STRONG_LEN
+        && distinct >= GATEWAY_AUTH_KEY_STRONG_DISTINCT_CHARS
+        && classes >= 3
+    {
+        KeyStrength::Strong
+    } else {
+        KeyStrength::Acceptable
+    }
+}
+
+fn is_well_formed_gateway_auth_key(value: &str) -> bool {
     let trimmed = value.trim();
     trimmed.len() >= 16
         && trimmed.len() <= 128
@@ -3661,6 +5938,11 @@ fn is_valid_gateway_auth_key(value: &str) -> bool {
         && !trimmed.chars().any(char::is_whitespace)
 }
 
+fn is_valid_gateway_auth_key(value: &str) -> bool {
+    is_well_formed_gateway_auth_key(value)
+        && gateway_auth_key_strength(value) >= KeyStrength::Acceptable
+}
+
 fn onboarding_gateway_auth_key_supplied(value: &str) -> bool {
     let trimmed = value.trim();
     is_valid_gateway_auth_key(trimmed)
@@ -3876,20 +6158,8 @@ fn infer_symbols_from_intent(intent: &str) -> Vec<String> {
 
 fn derive_profile_name_from_intent(intent: &str, connected_wallet: &str) -> String {
     const PROFILE_NAME_STOP_WORDS: &[&str] = &[
-        "with",
-        "that",
-        "this",
-        "from",
-        "into",
-        "your",
-        "user",
-        "launch",
-        "agent",
-        "session",
-        "profile",
-        "and",
-        "for",
-        "the",
+        "with", "that", "this", "from", "into", "your", "user", "launch", "agent", "session",
+        "profile", "and", "for", "the",
     ];
 
     let wallet_hex = connected_wallet
@@ -3934,6 +6204,31 @@ fn derive_profile_name_from_intent(intent: &str, connected_wallet: &str) -> Stri
     candidate
 }
 
+/// Drop denylisted symbols from the allowlist; the denylist always wins.
+///
+/// An allowlist emptied here is left empty so `validate_user_config` rejects it.
+fn reconcile_symbol_denylist(config: &mut FrontdoorUserConfig, assumptions: &mut Vec<String>) {
+    let denied: HashSet<String> = config
+        .symbol_denylist
+        .iter()
+        .map(|symbol| symbol.trim().to_ascii_uppercase())
+        .collect();
+    let mut removed = Vec::new();
+    config.symbol_allowlist.retain(|symbol| {
+        let keep = !denied.contains(&symbol.trim().to_ascii_uppercase());
+        if !keep {
+            removed.push(symbol.clone());
+        }
+        keep
+    });
+    if !removed.is_empty() {
+        assumptions.push(format!(
+            "Removed denylisted symbols from symbol_allowlist: {}.",
+            removed.join(", ")
+        ));
+    }
+}
+
 fn contains_any_lower(haystack: &str, patterns: &[&str]) -> bool {
     patterns.iter().any(|needle| haystack.contains(needle))
 }
@@ -3941,7 +6236,10 @@ fn contains_any_lower(haystack: &str, patterns: &[&str]) -> bool {
 fn normalize_suggested_config(
     config: &mut FrontdoorUserConfig,
     connected_wallet: &str,
+    rng: &dyn RngSource,
+    perp_universe: &HyperliquidPerpUniverse,
     assumptions: &mut Vec<String>,
+    warnings: &mut Vec<String>,
 ) {
     if !FRONTDOOR_SUPPORTED_CONFIG_VERSIONS.contains(&config.config_version) {
         config.config_version = FRONTDOOR_CURRENT_CONFIG_VERSION;
@@ -3964,7 +6262,10 @@ fn normalize_suggested_config(
             .trim()
             .eq_ignore_ascii_case("launchpad_profile")
     {
-        let seed = config.inference_summary.as_deref().unwrap_or("enclagent session");
+        let seed = config
+            .inference_summary
+            .as_deref()
+            .unwrap_or("enclagent session");
         config.profile_name = derive_profile_name_from_intent(seed, connected_wallet);
         assumptions.push("Generated profile_name from intent and connected wallet.".to_string());
     }
@@ -3989,27 +6290,36 @@ fn normalize_suggested_config(
         config.symbol_denylist.clear();
     }
 
-    config.request_timeout_ms = config.request_timeout_ms.clamp(1_000, 120_000);
-    config.max_retries = config.max_retries.min(10);
-    config.retry_backoff_ms = config.retry_backoff_ms.min(30_000);
+    let requested_position_size_usd = config.max_position_size_usd;
+    clamp_config_bounds(config);
+    if hyperliquid_profile
+        && config.max_position_size_usd < requested_position_size_usd
+        && config.max_position_size_usd == config.max_allocation_usd
+    {
+        assumptions.push(format!(
+            "Capped max_position_size_usd at max_allocation_usd ({}).",
+            config.max_allocation_usd
+        ));
+    }
 
     if hyperliquid_profile {
-        config.max_position_size_usd = config.max_position_size_usd.max(1);
-        config.leverage_cap = config.leverage_cap.clamp(1, 20);
-        config.max_allocation_usd = config.max_allocation_usd.max(1);
-        config.per_trade_notional_cap_usd = config.per_trade_notional_cap_usd.max(1);
-        if config.per_trade_notional_cap_usd > config.max_allocation_usd {
-            config.per_trade_notional_cap_usd = config.max_allocation_usd;
-        }
-        config.max_leverage = config.max_leverage.clamp(1, 20);
-        if config.max_leverage > config.leverage_cap {
-            config.max_leverage = config.leverage_cap;
-        }
-        config.max_slippage_bps = config.max_slippage_bps.clamp(1, 5_000);
-
+        let network = if config.hyperliquid_network == "mainnet" {
+            HyperliquidNetwork::Mainnet
+        } else {
+            HyperliquidNetwork::Testnet
+        };
+        let (known, dropped_warning) =
+            validate_symbols_against_universe(&config.symbol_allowlist, perp_universe, network);
+        config.symbol_allowlist = known;
+        warnings.extend(dropped_warning);
         if config.symbol_allowlist.is_empty() {
-            config.symbol_allowlist = vec!["BTC".to_string(), "ETH".to_string()];
+            config.symbol_allowlist = default_symbol_allowlist(network);
+            assumptions.push(format!(
+                "No known Hyperliquid symbols in symbol_allowlist; defaulted to {}.",
+                config.symbol_allowlist.join(" and ")
+            ));
         }
+        reconcile_symbol_denylist(config, assumptions);
     }
     if !matches!(
         config.custody_mode.as_str(),
@@ -4039,6 +6349,20 @@ fn normalize_suggested_config(
     ) {
         config.information_sharing_scope = "signals_only".to_string();
     }
+    // Operator custody reports executions back to the user, so it needs at
+    // least signals_and_execution sharing. User custody is left as chosen.
+    if (config.custody_mode == "operator_wallet" || config.custody_mode == "dual_mode")
+        && matches!(
+            config.information_sharing_scope.as_str(),
+            "none" | "signals_only"
+        )
+    {
+        assumptions.push(format!(
+            "Raised information_sharing_scope from {} to signals_and_execution for {} custody.",
+            config.information_sharing_scope, config.custody_mode
+        ));
+        config.information_sharing_scope = "signals_and_execution".to_string();
+    }
     if !matches!(
         config.kill_switch_behavior.as_str(),
         "pause_agent" | "cancel_open_orders" | "cancel_and_flatten"
@@ -4047,7 +6371,7 @@ fn normalize_suggested_config(
     }
 
     if !is_valid_gateway_auth_key(&config.gateway_auth_key) {
-        config.gateway_auth_key = generate_gateway_auth_key();
+        config.gateway_auth_key = generate_gateway_auth_key(rng);
     }
 
     if !matches!(
@@ -4065,144 +6389,469 @@ fn normalize_suggested_config(
     ) {
         config.verification_eigencloud_auth_scheme = "bearer".to_string();
     }
-    config.verification_eigencloud_timeout_ms =
-        config.verification_eigencloud_timeout_ms.clamp(1, 120_000);
-
-    if hyperliquid_profile
-        && config.hyperliquid_network == "mainnet"
-        && config.paper_live_policy == "live_allowed"
-        && config.max_position_size_usd > 1_000_000
-    {
-        config.max_position_size_usd = 1_000_000;
-    }
 
     config.accept_terms = true;
 }
 
-fn validate_user_config(config: &FrontdoorUserConfig) -> Result<(), String> {
-    if !FRONTDOOR_SUPPORTED_CONFIG_VERSIONS.contains(&config.config_version) {
-        return Err(format!(
-            "config_version must be one of {}",
-            FRONTDOOR_SUPPORTED_CONFIG_VERSIONS
-                .iter()
-                .map(std::string::ToString::to_string)
-                .collect::<Vec<_>>()
-                .join(", ")
-        ));
-    }
-
-    let domain = normalize_domain_name(&config.profile_domain)?;
-    let hyperliquid_profile = domain == "hyperliquid";
-    if config.domain_overrides.len() > 32 {
-        return Err("domain_overrides must include at most 32 keys".to_string());
-    }
-    for (key, value) in &config.domain_overrides {
-        if key.trim().is_empty() {
-            return Err("domain_overrides keys must be non-empty".to_string());
-        }
-        if key.len() > 64 {
-            return Err("domain_overrides keys must be <= 64 chars".to_string());
+/// Keep the symbols listed on `network`, normalized to uppercase and deduped
+/// in order. Unknown tickers are dropped and named in the returned warning.
+fn validate_symbols_against_universe(
+    symbols: &[String],
+    perp_universe: &HyperliquidPerpUniverse,
+    network: HyperliquidNetwork,
+) -> (Vec<String>, Option<String>) {
+    let universe = perp_universe.symbols(network);
+    let mut known = Vec::new();
+    let mut dropped = Vec::new();
+    for symbol in symbols {
+        let symbol = symbol.trim().to_ascii_uppercase();
+        if symbol.is_empty() || known.contains(&symbol) || dropped.contains(&symbol) {
+            continue;
         }
-        let serialized = serde_json::to_string(value)
-            .map_err(|e| format!("domain_overrides value serialization failed: {e}"))?;
-        if serialized.len() > 4_096 {
-            return Err(format!(
-                "domain_overrides value for key '{key}' exceeds 4096 bytes"
-            ));
+        if universe.contains(&symbol) {
+            known.push(symbol);
+        } else {
+            dropped.push(symbol);
         }
     }
-    if let Some(summary) = config.inference_summary.as_deref()
-        && summary.len() > 2_048
-    {
-        return Err("inference_summary must be <= 2048 chars".to_string());
-    }
-    if let Some(confidence) = config.inference_confidence
-        && !(0.0..=1.0).contains(&confidence)
-    {
-        return Err("inference_confidence must be between 0.0 and 1.0".to_string());
-    }
-    if config.inference_warnings.len() > 32 {
-        return Err("inference_warnings must include at most 32 entries".to_string());
+    let warning = (!dropped.is_empty()).then(|| {
+        let network = match network {
+            HyperliquidNetwork::Mainnet => "mainnet",
+            HyperliquidNetwork::Testnet => "testnet",
+        };
+        format!(
+            "Dropped symbols not listed on Hyperliquid {network}: {}.",
+            dropped.join(", ")
+        )
+    });
+    (known, warning)
+}
+
+/// Send one status webhook; non-2xx responses count as failures.
+async fn post_status_webhook(
+    url: &str,
+    payload: &FrontdoorStatusWebhookPayload,
+) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(
+            FRONTDOOR_STATUS_WEBHOOK_TIMEOUT_SECS,
+        ))
+        .build()
+        .map_err(|e| format!("client init failed: {e}"))?;
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| format!("{url} unreachable: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("{url} returned HTTP {}", response.status()));
     }
-    if config.inference_warnings.iter().any(|v| v.len() > 512) {
-        return Err("inference_warnings entries must be <= 512 chars".to_string());
+    Ok(())
+}
+
+/// Fetch the listed perp tickers from the Hyperliquid `meta` info endpoint.
+async fn fetch_hyperliquid_perp_universe(
+    network: HyperliquidNetwork,
+) -> Result<Vec<String>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(
+            HYPERLIQUID_META_TIMEOUT_SECS,
+        ))
+        .build()
+        .map_err(|e| format!("client init failed: {e}"))?;
+    let endpoint = format!("{}/info", network.default_api_base_url());
+    let response = client
+        .post(&endpoint)
+        .json(&serde_json::json!({ "type": "meta" }))
+        .send()
+        .await
+        .map_err(|e| format!("{endpoint} unreachable: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("{endpoint} returned HTTP {}", response.status()));
     }
+    let meta: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("{endpoint} returned invalid JSON: {e}"))?;
+    parse_meta_universe(&meta)
+}
 
-    if config.profile_name.trim().is_empty() {
-        return Err("profile_name is required".to_string());
+/// Uppercased names of the perps in a `meta` response, skipping delisted ones.
+fn parse_meta_universe(meta: &serde_json::Value) -> Result<Vec<String>, String> {
+    let entries = meta
+        .get("universe")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| "meta response has no universe array".to_string())?;
+    let mut symbols: Vec<String> = Vec::new();
+    for entry in entries {
+        if entry
+            .get("isDelisted")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let Some(name) = entry.get("name").and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        let name = name.trim().to_ascii_uppercase();
+        if !name.is_empty() && !symbols.contains(&name) {
+            symbols.push(name);
+        }
     }
-    if config.profile_name.len() > 64 {
-        return Err("profile_name must be <= 64 chars".to_string());
+    if symbols.is_empty() {
+        return Err("meta universe lists no perps".to_string());
     }
+    Ok(symbols)
+}
 
-    if hyperliquid_profile {
-        let network = config.hyperliquid_network.trim().to_ascii_lowercase();
-        if network != "testnet" && network != "mainnet" {
-            return Err("hyperliquid_network must be testnet or mainnet".to_string());
-        }
+/// Clamp numeric risk and transport limits into the bounds `validate_user_config`
+/// enforces. Shared by suggest-config normalization and verify.
+fn clamp_config_bounds(config: &mut FrontdoorUserConfig) {
+    let hyperliquid_profile =
+        normalize_domain_name(&config.profile_domain).is_ok_and(|domain| domain == "hyperliquid");
 
-        let policy = config.paper_live_policy.trim().to_ascii_lowercase();
-        if policy != "paper_only" && policy != "paper_first" && policy != "live_allowed" {
-            return Err(
-                "paper_live_policy must be paper_only, paper_first, or live_allowed".to_string(),
-            );
-        }
-        if network == "mainnet"
-            && policy == "live_allowed"
-            && config.max_position_size_usd > 1_000_000
-        {
-            return Err(
-                "mainnet live_allowed sessions require max_position_size_usd <= 1000000"
-                    .to_string(),
-            );
-        }
+    config.request_timeout_ms = config.request_timeout_ms.clamp(1_000, 120_000);
+    config.max_retries = config.max_retries.min(10);
+    config.retry_backoff_ms = config.retry_backoff_ms.min(30_000);
 
-        if let Some(api) = config.hyperliquid_api_base_url.as_deref() {
-            validate_optional_url(api, &["http", "https"], "hyperliquid_api_base_url")?;
-        }
-        if let Some(ws) = config.hyperliquid_ws_url.as_deref() {
-            validate_optional_url(ws, &["ws", "wss"], "hyperliquid_ws_url")?;
-        }
-        if config.request_timeout_ms < 1_000 || config.request_timeout_ms > 120_000 {
-            return Err("request_timeout_ms must be between 1000 and 120000".to_string());
+    if hyperliquid_profile {
+        config.max_position_size_usd = config.max_position_size_usd.max(1);
+        config.leverage_cap = config.leverage_cap.clamp(1, 20);
+        config.max_allocation_usd = config.max_allocation_usd.max(1);
+        config.per_trade_notional_cap_usd = config.per_trade_notional_cap_usd.max(1);
+        if config.per_trade_notional_cap_usd > config.max_allocation_usd {
+            config.per_trade_notional_cap_usd = config.max_allocation_usd;
         }
-        if config.max_retries > 10 {
-            return Err("max_retries must be between 0 and 10".to_string());
+        if config.max_position_size_usd > config.max_allocation_usd {
+            config.max_position_size_usd = config.max_allocation_usd;
         }
-        if config.retry_backoff_ms > 30_000 {
-            return Err("retry_backoff_ms must be <= 30000".to_string());
+        config.max_leverage = config.max_leverage.clamp(1, 20);
+        if config.max_leverage > config.leverage_cap {
+            config.max_leverage = config.leverage_cap;
+        }
+        config.max_slippage_bps = config.max_slippage_bps.clamp(1, 5_000);
+    }
+
+    config.verification_eigencloud_timeout_ms =
+        config.verification_eigencloud_timeout_ms.clamp(1, 120_000);
+
+    if hyperliquid_profile
+        && config
+            .hyperliquid_network
+            .trim()
+            .eq_ignore_ascii_case("mainnet")
+        && config
+            .paper_live_policy
+            .trim()
+            .eq_ignore_ascii_case("live_allowed")
+        && config.max_position_size_usd > 1_000_000
+    {
+        config.max_position_size_usd = 1_000_000;
+    }
+}
+
+/// Describe every numeric field that differs between the submitted and the
+/// resolved config, e.g. `leverage_cap clamped from 50 to 20`.
+fn config_clamp_warnings(
+    requested: &FrontdoorUserConfig,
+    resolved: &FrontdoorUserConfig,
+) -> Vec<String> {
+    let fields: [(&str, u64, u64); 10] = [
+        (
+            "request_timeout_ms",
+            requested.request_timeout_ms,
+            resolved.request_timeout_ms,
+        ),
+        (
+            "max_retries",
+            u64::from(requested.max_retries),
+            u64::from(resolved.max_retries),
+        ),
+        (
+            "retry_backoff_ms",
+            requested.retry_backoff_ms,
+            resolved.retry_backoff_ms,
+        ),
+        (
+            "max_position_size_usd",
+            requested.max_position_size_usd,
+            resolved.max_position_size_usd,
+        ),
+        (
+            "leverage_cap",
+            u64::from(requested.leverage_cap),
+            u64::from(resolved.leverage_cap),
+        ),
+        (
+            "max_allocation_usd",
+            requested.max_allocation_usd,
+            resolved.max_allocation_usd,
+        ),
+        (
+            "per_trade_notional_cap_usd",
+            requested.per_trade_notional_cap_usd,
+            resolved.per_trade_notional_cap_usd,
+        ),
+        (
+            "max_leverage",
+            u64::from(requested.max_leverage),
+            u64::from(resolved.max_leverage),
+        ),
+        (
+            "max_slippage_bps",
+            u64::from(requested.max_slippage_bps),
+            u64::from(resolved.max_slippage_bps),
+        ),
+        (
+            "verification_eigencloud_timeout_ms",
+            requested.verification_eigencloud_timeout_ms,
+            resolved.verification_eigencloud_timeout_ms,
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(field, before, after)| format!("{field} clamped from {before} to {after}"))
+        .collect()
+}
+
+fn enforce_signed_fallback_receipts(
+    config: &mut FrontdoorUserConfig,
+    assumptions: &mut Vec<String>,
+) {
+    if !config.verification_fallback_require_signed_receipts {
+        config.verification_fallback_require_signed_receipts = true;
+        assumptions.push(
+            "Platform policy forces signed fallback receipts; overrode verification_fallback_require_signed_receipts=false."
+                .to_string(),
+        );
+    }
+}
+
+/// Record `now` as the terms acceptance time unless the config already has one.
+fn stamp_terms_acceptance(config: &mut FrontdoorUserConfig, now: DateTime<Utc>) {
+    if config.accept_terms && config.accepted_terms_at.is_none() {
+        config.accepted_terms_at = Some(now.to_rfc3339());
+    }
+}
+
+fn validate_user_config(config: &FrontdoorUserConfig) -> Result<(), FieldErrors> {
+    let mut errors = FieldErrors::default();
+
+    if !FRONTDOOR_SUPPORTED_CONFIG_VERSIONS.contains(&config.config_version) {
+        errors.push(
+            "config_version",
+            format!(
+                "config_version must be one of {}",
+                FRONTDOOR_SUPPORTED_CONFIG_VERSIONS
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+    }
+
+    let domain = errors.capture(
+        "profile_domain",
+        normalize_domain_name(&config.profile_domain),
+    );
+    let hyperliquid_profile = domain.as_deref() == Some("hyperliquid");
+    // Domains without a built-in profile keep free-form overrides.
+    let allowed_override_keys = domain.as_deref().and_then(|domain| {
+        frontdoor_domain_profiles()
+            .into_iter()
+            .find(|profile| profile.domain == domain)
+            .map(|profile| profile.allowed_override_keys)
+    });
+    if config.domain_overrides.len() > 32 {
+        errors.push(
+            "domain_overrides",
+            "domain_overrides must include at most 32 keys",
+        );
+    }
+    for (key, value) in &config.domain_overrides {
+        if key.trim().is_empty() {
+            errors.push(
+                "domain_overrides",
+                "domain_overrides keys must be non-empty",
+            );
+        }
+        if key.len() > 64 {
+            errors.push(
+                "domain_overrides",
+                "domain_overrides keys must be <= 64 chars",
+            );
+        }
+        if let Some(allowed) = allowed_override_keys.as_ref()
+            && key != FRONTDOOR_PREFLIGHT_OVERRIDE_KEY
+            && !allowed.iter().any(|allowed| allowed == key)
+        {
+            errors.push(
+                "domain_overrides",
+                format!(
+                    "domain_overrides key '{key}' is not allowed for profile_domain '{}'",
+                    domain.as_deref().unwrap_or_default()
+                ),
+            );
+        }
+        match serde_json::to_string(value) {
+            Ok(serialized) if serialized.len() > 4_096 => errors.push(
+                "domain_overrides",
+                format!("domain_overrides value for key '{key}' exceeds 4096 bytes"),
+            ),
+            Ok(_) => {}
+            Err(e) => errors.push(
+                "domain_overrides",
+                format!("domain_overrides value serialization failed: {e}"),
+            ),
+        }
+    }
+    if let Some(summary) = config.inference_summary.as_deref()
+        && summary.len() > 2_048
+    {
+        errors.push(
+            "inference_summary",
+            "inference_summary must be <= 2048 chars",
+        );
+    }
+    if let Some(confidence) = config.inference_confidence
+        && !(0.0..=1.0).contains(&confidence)
+    {
+        errors.push(
+            "inference_confidence",
+            "inference_confidence must be between 0.0 and 1.0",
+        );
+    }
+    if config.inference_warnings.len() > 32 {
+        errors.push(
+            "inference_warnings",
+            "inference_warnings must include at most 32 entries",
+        );
+    }
+    if config.inference_warnings.iter().any(|v| v.len() > 512) {
+        errors.push(
+            "inference_warnings",
+            "inference_warnings entries must be <= 512 chars",
+        );
+    }
+
+    if config.profile_name.trim().is_empty() {
+        errors.push("profile_name", "profile_name is required");
+    }
+    if config.profile_name.len() > 64 {
+        errors.push("profile_name", "profile_name must be <= 64 chars");
+    }
+
+    if hyperliquid_profile {
+        let network = config.hyperliquid_network.trim().to_ascii_lowercase();
+        if network != "testnet" && network != "mainnet" {
+            errors.push(
+                "hyperliquid_network",
+                "hyperliquid_network must be testnet or mainnet",
+            );
+        }
+
+        let policy = config.paper_live_policy.trim().to_ascii_lowercase();
+        if policy != "paper_only" && policy != "paper_first" && policy != "live_allowed" {
+            errors.push(
+                "paper_live_policy",
+                "paper_live_policy must be paper_only, paper_first, or live_allowed",
+            );
+        }
+        if network == "mainnet"
+            && policy == "live_allowed"
+            && config.max_position_size_usd > 1_000_000
+        {
+            errors.push(
+                "max_position_size_usd",
+                "mainnet live_allowed sessions require max_position_size_usd <= 1000000",
+            );
+        }
+
+        if let Some(api) = config.hyperliquid_api_base_url.as_deref() {
+            errors.capture(
+                "hyperliquid_api_base_url",
+                validate_optional_url(api, &["http", "https"], "hyperliquid_api_base_url"),
+            );
+        }
+        if let Some(ws) = config.hyperliquid_ws_url.as_deref() {
+            errors.capture(
+                "hyperliquid_ws_url",
+                validate_optional_url(ws, &["ws", "wss"], "hyperliquid_ws_url"),
+            );
+        }
+        if config.request_timeout_ms < 1_000 || config.request_timeout_ms > 120_000 {
+            errors.push(
+                "request_timeout_ms",
+                "request_timeout_ms must be between 1000 and 120000",
+            );
+        }
+        if config.max_retries > 10 {
+            errors.push("max_retries", "max_retries must be between 0 and 10");
+        }
+        if config.retry_backoff_ms > 30_000 {
+            errors.push("retry_backoff_ms", "retry_backoff_ms must be <= 30000");
         }
 
         if config.max_position_size_usd == 0 {
-            return Err("max_position_size_usd must be > 0".to_string());
+            errors.push("max_position_size_usd", "max_position_size_usd must be > 0");
         }
         if config.leverage_cap == 0 || config.leverage_cap > 20 {
-            return Err("leverage_cap must be between 1 and 20".to_string());
+            errors.push("leverage_cap", "leverage_cap must be between 1 and 20");
         }
 
         if config.max_allocation_usd == 0 {
-            return Err("max_allocation_usd must be > 0".to_string());
+            errors.push("max_allocation_usd", "max_allocation_usd must be > 0");
         }
         if config.per_trade_notional_cap_usd == 0 {
-            return Err("per_trade_notional_cap_usd must be > 0".to_string());
+            errors.push(
+                "per_trade_notional_cap_usd",
+                "per_trade_notional_cap_usd must be > 0",
+            );
         }
         if config.per_trade_notional_cap_usd > config.max_allocation_usd {
-            return Err("per_trade_notional_cap_usd must be <= max_allocation_usd".to_string());
+            errors.push(
+                "per_trade_notional_cap_usd",
+                "per_trade_notional_cap_usd must be <= max_allocation_usd",
+            );
+        }
+        if config.max_position_size_usd > config.max_allocation_usd {
+            errors.push(
+                "max_position_size_usd",
+                "max_position_size_usd must be <= max_allocation_usd",
+            );
         }
         if config.max_leverage == 0 || config.max_leverage > 20 {
-            return Err("max_leverage must be between 1 and 20".to_string());
+            errors.push("max_leverage", "max_leverage must be between 1 and 20");
         }
         if config.max_leverage > config.leverage_cap {
-            return Err("max_leverage must be <= leverage_cap".to_string());
+            errors.push("max_leverage", "max_leverage must be <= leverage_cap");
         }
         if config.max_slippage_bps == 0 || config.max_slippage_bps > 5_000 {
-            return Err("max_slippage_bps must be between 1 and 5000".to_string());
+            errors.push(
+                "max_slippage_bps",
+                "max_slippage_bps must be between 1 and 5000",
+            );
         }
 
-        let allowlist = normalize_symbols(&config.symbol_allowlist, "symbol_allowlist", true)?;
-        let denylist = normalize_symbols(&config.symbol_denylist, "symbol_denylist", false)?;
-        if !allowlist.is_disjoint(&denylist) {
-            return Err("symbol_allowlist and symbol_denylist must not overlap".to_string());
+        let allowlist = errors.capture(
+            "symbol_allowlist",
+            normalize_symbols(&config.symbol_allowlist, "symbol_allowlist", true),
+        );
+        let denylist = errors.capture(
+            "symbol_denylist",
+            normalize_symbols(&config.symbol_denylist, "symbol_denylist", false),
+        );
+        if let (Some(allowlist), Some(denylist)) = (allowlist, denylist)
+            && allowlist.is_subset(&denylist)
+        {
+            errors.push(
+                "symbol_denylist",
+                "symbol_denylist excludes every symbol in symbol_allowlist",
+            );
         }
     }
 
@@ -4211,26 +6860,39 @@ fn validate_user_config(config: &FrontdoorUserConfig) -> Result<(), String> {
         && custody_mode != "user_wallet"
         && custody_mode != "dual_mode"
     {
-        return Err("custody_mode must be operator_wallet, user_wallet, or dual_mode".to_string());
+        errors.push(
+            "custody_mode",
+            "custody_mode must be operator_wallet, user_wallet, or dual_mode",
+        );
     }
-    let operator_wallet = normalize_optional_wallet(
-        config.operator_wallet_address.as_deref(),
+    let operator_wallet = errors.capture(
         "operator_wallet_address",
-    )?;
-    let user_wallet =
-        normalize_optional_wallet(config.user_wallet_address.as_deref(), "user_wallet_address")?;
-    normalize_optional_wallet(config.vault_address.as_deref(), "vault_address")?;
+        normalize_optional_wallet(
+            config.operator_wallet_address.as_deref(),
+            "operator_wallet_address",
+        ),
+    );
+    let user_wallet = errors.capture(
+        "user_wallet_address",
+        normalize_optional_wallet(config.user_wallet_address.as_deref(), "user_wallet_address"),
+    );
+    errors.capture(
+        "vault_address",
+        normalize_optional_wallet(config.vault_address.as_deref(), "vault_address"),
+    );
+    // A malformed wallet was already reported above; only flag absent ones.
     if (custody_mode == "operator_wallet" || custody_mode == "dual_mode")
-        && operator_wallet.is_none()
+        && operator_wallet == Some(None)
     {
-        return Err(
-            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode"
-                .to_string(),
+        errors.push(
+            "operator_wallet_address",
+            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode",
         );
     }
-    if (custody_mode == "user_wallet" || custody_mode == "dual_mode") && user_wallet.is_none() {
-        return Err(
-            "user_wallet_address is required for custody_mode user_wallet/dual_mode".to_string(),
+    if (custody_mode == "user_wallet" || custody_mode == "dual_mode") && user_wallet == Some(None) {
+        errors.push(
+            "user_wallet_address",
+            "user_wallet_address is required for custody_mode user_wallet/dual_mode",
         );
     }
 
@@ -4240,7 +6902,10 @@ fn validate_user_config(config: &FrontdoorUserConfig) -> Result<(), String> {
         && information_sharing_scope != "signals_and_execution"
         && information_sharing_scope != "full_audit"
     {
-        return Err("information_sharing_scope must be none, signals_only, signals_and_execution, or full_audit".to_string());
+        errors.push(
+            "information_sharing_scope",
+            "information_sharing_scope must be none, signals_only, signals_and_execution, or full_audit",
+        );
     }
 
     let kill_switch_behavior = config.kill_switch_behavior.trim().to_ascii_lowercase();
@@ -4248,72 +6913,126 @@ fn validate_user_config(config: &FrontdoorUserConfig) -> Result<(), String> {
         && kill_switch_behavior != "cancel_open_orders"
         && kill_switch_behavior != "cancel_and_flatten"
     {
-        return Err(
-            "kill_switch_behavior must be pause_agent, cancel_open_orders, or cancel_and_flatten"
-                .to_string(),
+        errors.push(
+            "kill_switch_behavior",
+            "kill_switch_behavior must be pause_agent, cancel_open_orders, or cancel_and_flatten",
         );
     }
 
     let gateway_auth_key = config.gateway_auth_key.trim();
-    if !is_valid_gateway_auth_key(gateway_auth_key) {
-        return Err("gateway_auth_key must be printable ASCII with no whitespace".to_string());
+    if !is_well_formed_gateway_auth_key(gateway_auth_key) {
+        errors.push(
+            "gateway_auth_key",
+            "gateway_auth_key must be printable ASCII with no whitespace",
+        );
+    } else if gateway_auth_key_strength(gateway_auth_key) == KeyStrength::Weak {
+        errors.push(
+            "gateway_auth_key",
+            format!(
+                "gateway_auth_key is too weak: use at least {GATEWAY_AUTH_KEY_MIN_DISTINCT_CHARS} distinct characters and avoid common patterns"
+            ),
+        );
     }
     if let Some(token) = config.eigencloud_auth_key.as_deref()
         && !token.trim().is_empty()
         && token.trim().len() < 8
     {
-        return Err("eigencloud_auth_key, when set, must be at least 8 chars".to_string());
+        errors.push(
+            "eigencloud_auth_key",
+            "eigencloud_auth_key, when set, must be at least 8 chars",
+        );
     }
     let verification_backend = config.verification_backend.trim().to_ascii_lowercase();
     if verification_backend != "eigencloud_primary" && verification_backend != "fallback_only" {
-        return Err("verification_backend must be eigencloud_primary or fallback_only".to_string());
+        errors.push(
+            "verification_backend",
+            "verification_backend must be eigencloud_primary or fallback_only",
+        );
     }
     if let Some(endpoint) = config.verification_eigencloud_endpoint.as_deref() {
-        validate_optional_url(
-            endpoint,
-            &["http", "https"],
+        errors.capture(
             "verification_eigencloud_endpoint",
-        )?;
+            validate_optional_url(
+                endpoint,
+                &["http", "https"],
+                "verification_eigencloud_endpoint",
+            ),
+        );
     }
     let verification_auth_scheme = config
         .verification_eigencloud_auth_scheme
         .trim()
         .to_ascii_lowercase();
     if verification_auth_scheme != "bearer" && verification_auth_scheme != "api_key" {
-        return Err("verification_eigencloud_auth_scheme must be bearer or api_key".to_string());
+        errors.push(
+            "verification_eigencloud_auth_scheme",
+            "verification_eigencloud_auth_scheme must be bearer or api_key",
+        );
     }
     if config.verification_eigencloud_timeout_ms == 0
         || config.verification_eigencloud_timeout_ms > 120_000
     {
-        return Err("verification_eigencloud_timeout_ms must be between 1 and 120000".to_string());
+        errors.push(
+            "verification_eigencloud_timeout_ms",
+            "verification_eigencloud_timeout_ms must be between 1 and 120000",
+        );
     }
     if verification_backend == "fallback_only" && !config.verification_fallback_enabled {
-        return Err(
-            "verification_fallback_enabled must be true when verification_backend=fallback_only"
-                .to_string(),
+        errors.push(
+            "verification_fallback_enabled",
+            "verification_fallback_enabled must be true when verification_backend=fallback_only",
         );
     }
     if let Some(value) = config.verification_fallback_signing_key_id.as_deref()
         && value.len() > 128
     {
-        return Err("verification_fallback_signing_key_id must be <= 128 chars".to_string());
+        errors.push(
+            "verification_fallback_signing_key_id",
+            "verification_fallback_signing_key_id must be <= 128 chars",
+        );
     }
     if let Some(value) = config.verification_fallback_chain_path.as_deref() {
         let trimmed = value.trim();
         if trimmed.is_empty() {
-            return Err(
-                "verification_fallback_chain_path must be omitted or non-empty".to_string(),
+            errors.push(
+                "verification_fallback_chain_path",
+                "verification_fallback_chain_path must be omitted or non-empty",
             );
         }
         if trimmed.contains(['\n', '\r']) {
-            return Err("verification_fallback_chain_path must not include newlines".to_string());
+            errors.push(
+                "verification_fallback_chain_path",
+                "verification_fallback_chain_path must not include newlines",
+            );
         }
     }
 
     if !config.accept_terms {
-        return Err("accept_terms must be true".to_string());
+        errors.push("accept_terms", "accept_terms must be true");
+    } else {
+        if config
+            .accepted_terms_version
+            .as_deref()
+            .is_none_or(|version| version.trim().is_empty())
+        {
+            errors.push(
+                "accepted_terms_version",
+                "accepted_terms_version is required when accept_terms is true",
+            );
+        }
+        match config.accepted_terms_at.as_deref() {
+            None => errors.push(
+                "accepted_terms_at",
+                "accepted_terms_at is required when accept_terms is true",
+            ),
+            Some(at) if DateTime::parse_from_rfc3339(at.trim()).is_err() => errors.push(
+                "accepted_terms_at",
+                "accepted_terms_at must be an RFC 3339 timestamp",
+            ),
+            Some(_) => {}
+        }
     }
-    Ok(())
+    errors.into_result()
 }
 
 fn validate_wallet_association(
@@ -4323,16 +7042,48 @@ fn validate_wallet_association(
     let connected = normalize_wallet_address(connected_wallet)
         .ok_or_else(|| "connected wallet is not a valid EVM address".to_string())?;
     let custody_mode = config.custody_mode.trim().to_ascii_lowercase();
-    if custody_mode != "user_wallet" && custody_mode != "dual_mode" {
-        return Ok(());
-    }
-    let configured =
-        normalize_optional_wallet(config.user_wallet_address.as_deref(), "user_wallet_address")?;
-    if configured.as_deref() != Some(connected.as_str()) {
-        return Err(
-            "user_wallet_address must match the connected wallet for user_wallet/dual_mode"
-                .to_string(),
-        );
+    match custody_mode.as_str() {
+        "user_wallet" => {
+            let configured = normalize_optional_wallet(
+                config.user_wallet_address.as_deref(),
+                "user_wallet_address",
+            )?;
+            if configured.as_deref() != Some(connected.as_str()) {
+                return Err(
+                    "user_wallet_address must match the connected wallet for user_wallet/dual_mode"
+                        .to_string(),
+                );
+            }
+        }
+        "dual_mode" => {
+            let operator = normalize_optional_wallet(
+                config.operator_wallet_address.as_deref(),
+                "operator_wallet_address",
+            )?;
+            let user = normalize_optional_wallet(
+                config.user_wallet_address.as_deref(),
+                "user_wallet_address",
+            )?;
+            let (Some(operator), Some(user)) = (operator, user) else {
+                return Err(
+                    "dual_mode requires both operator_wallet_address and user_wallet_address"
+                        .to_string(),
+                );
+            };
+            if operator == user {
+                return Err(
+                    "dual_mode requires distinct operator_wallet_address and user_wallet_address"
+                        .to_string(),
+                );
+            }
+            if connected != operator && connected != user {
+                return Err(
+                    "connected wallet must match operator_wallet_address or user_wallet_address for dual_mode"
+                        .to_string(),
+                );
+            }
+        }
+        _ => {}
     }
     Ok(())
 }
@@ -4399,11 +7150,43 @@ mod tests {
     use k256::ecdsa::SigningKey;
     use tempfile::tempdir;
 
+    use crate::observability::{MetricLabels, NoopMetrics};
+
     use crate::channels::web::types::{
         FrontdoorChallengeRequest, FrontdoorOnboardingChatRequest, FrontdoorRuntimeControlRequest,
         FrontdoorUserConfig, FrontdoorVerifyRequest,
     };
 
+    /// Baseline config for service tests; override fields with struct update syntax.
+    fn test_frontdoor_config() -> FrontdoorConfig {
+        FrontdoorConfig {
+            require_privy: false,
+            privy_app_id: None,
+            privy_client_id: None,
+            provision_command: None,
+            default_instance_url: None,
+            allow_default_instance_fallback: false,
+            verify_app_base_url: None,
+            session_ttl_secs: 900,
+            poll_interval_ms: 1000,
+            force_signed_receipts: false,
+            wallet_retention_secs: 0,
+            challenge_rate_limit: 0,
+            challenge_rate_window_secs: 0,
+            terminal_retention_secs: 21_600,
+            provision_timeout_secs: 1_800,
+            challenge_template: None,
+            allowed_chain_ids: vec![1],
+            terms_version: DEFAULT_TERMS_VERSION.to_string(),
+            max_provision_retries: 3,
+            hyperliquid_mainnet_perps: None,
+            hyperliquid_testnet_perps: None,
+            hyperliquid_perp_refresh: false,
+            max_onboarding_turns: 200,
+            status_webhook_url: None,
+        }
+    }
+
     #[test]
     fn wallet_normalization_and_validation() {
         let valid = "0x9431Cf5DA0CE60664661341db650763B08286B18";
@@ -4414,145 +7197,123 @@ mod tests {
     }
 
     #[test]
-    fn signature_shape_validation() {
-        let sig = format!("0x{}", "a".repeat(130));
-        assert!(is_signature_like(&sig));
-        assert!(!is_signature_like("0xabc"));
-        assert!(!is_signature_like("abc"));
-        assert!(!is_signature_like(&format!("0x{}", "z".repeat(130))));
+    fn checksummed_address_matches_eip55_vectors() {
+        for expected in [
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let lower = expected.to_ascii_lowercase();
+            assert_eq!(to_checksummed_address(&lower), expected);
+            assert_eq!(to_checksummed_address(expected), expected);
+            assert!(address_checksum_matches(expected));
+            assert!(address_checksum_matches(&lower));
+            assert!(address_checksum_matches(&format!(
+                "0x{}",
+                expected[2..].to_ascii_uppercase()
+            )));
+        }
+
+        // vitalik.eth with the casing of its first letter flipped.
+        assert!(!address_checksum_matches(
+            "0xD8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+        ));
+        assert_eq!(to_checksummed_address("not-an-address"), "not-an-address");
     }
 
     #[test]
-    fn verifies_wallet_signature_recovery() {
-        let private_key = decode_hex_prefixed(
-            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-        )
-        .expect("private key");
-        let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
-        let wallet =
-            ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
-        let message = "Enclagent test message";
-        let prehash = eip191_personal_sign_hash(message);
-        let (sig, recid) = signing_key
-            .sign_prehash_recoverable(&prehash)
-            .expect("sign message");
-        let mut sig_bytes = sig.to_bytes().to_vec();
-        sig_bytes.push(recid.to_byte() + 27);
-        let signature = format!("0x{}", encode_hex_lower(&sig_bytes));
+    fn run_provision_uses_configured_backend() {
+        struct CannedBackend;
 
-        verify_wallet_signature(message, &signature, &wallet).expect("signature should verify");
-        assert!(
-            verify_wallet_signature(
-                message,
-                &signature,
-                "0x0000000000000000000000000000000000000001"
-            )
-            .is_err()
-        );
-    }
+        #[async_trait]
+        impl ProvisioningBackend for CannedBackend {
+            fn source(&self) -> ProvisioningSource {
+                ProvisioningSource::Command
+            }
+
+            async fn provision(
+                &self,
+                input: &ProvisionCommandInput<'_>,
+                logs: &dyn ProvisionLogSink,
+            ) -> Result<ProvisioningResult, String> {
+                logs.emit(
+                    input.session_id,
+                    &ProvisionCommandLog {
+                        source: "native".to_string(),
+                        stream: "stdout".to_string(),
+                        line: "provisioned via api".to_string(),
+                    },
+                )
+                .await;
+                Ok(ProvisioningResult {
+                    instance_url: "https://native.example/gateway".to_string(),
+                    app_url: Some("https://native.example/app".to_string()),
+                    verify_url: None,
+                    eigen_app_id: Some("app-native".to_string()),
+                })
+            }
+        }
 
-    #[test]
-    fn frontdoor_flow_reaches_ready_state_after_valid_signature() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
             let tmp = tempdir().expect("tempdir");
-            let store_path = tmp.path().join("wallet_sessions.json");
-            let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: false,
-                    privy_app_id: None,
-                    privy_client_id: None,
-                    provision_command: None,
-                    default_instance_url: Some(
-                        "https://session.example/gateway?token=demo".to_string(),
-                    ),
-                    allow_default_instance_fallback: true,
-                    verify_app_base_url: Some(
-                        "https://verify-sepolia.eigencloud.xyz/app".to_string(),
-                    ),
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 100,
-                },
-                store_path,
+            let mut config =
+                SimulatedProvisioning::ready("https://shared.example.com").frontdoor_config();
+            config.allow_default_instance_fallback = false;
+            let service = FrontdoorService::new_with_backend(
+                config,
+                tmp.path().join("wallet_sessions.json"),
+                Arc::new(SystemClock),
+                Arc::new(crate::observability::NoopMetrics),
+                Arc::new(ThreadRngSource),
+                Box::new(CannedBackend),
             );
-
-            let private_key = decode_hex_prefixed(
-                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-            )
-            .expect("private key");
-            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
-            let wallet =
-                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
-
+            let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
             let challenge = service
                 .create_challenge(FrontdoorChallengeRequest {
-                    wallet_address: wallet.clone(),
+                    wallet_address: wallet.to_string(),
                     privy_user_id: None,
                     chain_id: Some(1),
                 })
                 .await
                 .expect("challenge");
-            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
-
-            let prehash = eip191_personal_sign_hash(&challenge.message);
-            let (sig, recid) = signing_key
-                .sign_prehash_recoverable(&prehash)
-                .expect("sign challenge");
-            let mut sig_bytes = sig.to_bytes().to_vec();
-            sig_bytes.push(recid.to_byte() + 27);
-            let signature = format!("0x{}", encode_hex_lower(&sig_bytes));
-
-            service
-                .clone()
-                .verify_and_start(FrontdoorVerifyRequest {
-                    session_id: challenge.session_id.clone(),
-                    wallet_address: wallet.clone(),
-                    privy_user_id: None,
-                    privy_identity_token: None,
-                    privy_access_token: None,
-                    message: challenge.message.clone(),
-                    signature,
-                    config: sample_user_config(&wallet),
-                })
-                .await
-                .expect("verify and start");
-
-            let mut ready = None;
-            for _ in 0..40 {
-                let session = service
-                    .get_session(session_uuid)
-                    .await
-                    .expect("session should exist");
-                if session.status == "ready" {
-                    ready = Some(session);
-                    break;
-                }
-                assert_ne!(session.status, "failed", "session failed unexpectedly");
-                assert_ne!(session.status, "expired", "session expired unexpectedly");
-                tokio::time::sleep(Duration::from_millis(25)).await;
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            {
+                let mut state = service.state.write().await;
+                let session = state.sessions.get_mut(&session_id).expect("session");
+                let mut cfg = sample_user_config(wallet);
+                cfg.inference_summary = Some("dedicated trading agent".to_string());
+                session.config = Some(cfg);
+                session.status = SessionStatus::Provisioning;
             }
 
-            let ready = ready.expect("session should become ready");
+            Arc::clone(&service).run_provision(session_id).await;
+
+            let state = service.state.read().await;
+            let session = &state.sessions[&session_id];
+            assert!(matches!(session.status, SessionStatus::Ready));
             assert_eq!(
-                ready.instance_url.as_deref(),
-                Some("https://session.example/gateway?token=demo")
+                session.instance_url.as_deref(),
+                Some("https://native.example/gateway")
             );
-            assert_eq!(ready.wallet_address, wallet);
-            assert_eq!(ready.profile_name.as_deref(), Some("demo_profile"));
-            assert_eq!(ready.provisioning_source, "default_instance_url");
-            assert!(!ready.dedicated_instance);
-            assert!(!ready.launched_on_eigencloud);
-            assert_eq!(ready.verification_backend, "eigencloud_primary");
-            assert_eq!(ready.verification_level, "primary_plus_signed_fallback");
+            assert_eq!(session.eigen_app_id.as_deref(), Some("app-native"));
+            assert!(matches!(
+                session.provisioning_source,
+                ProvisioningSource::Command
+            ));
+            assert_eq!(session.provision_logs.len(), 1);
         });
     }
 
     #[test]
-    fn frontdoor_verify_is_idempotent_after_ready() {
-        let rt = tokio::runtime::Builder::new_current_thread()
+    fn concurrent_provision_completions_persist_every_wallet() {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(4)
             .enable_all()
             .build()
             .expect("tokio runtime");
@@ -4560,221 +7321,637 @@ mod tests {
             let tmp = tempdir().expect("tempdir");
             let store_path = tmp.path().join("wallet_sessions.json");
             let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: false,
-                    privy_app_id: None,
-                    privy_client_id: None,
-                    provision_command: None,
-                    default_instance_url: Some(
-                        "https://session.example/gateway?token=demo".to_string(),
-                    ),
-                    allow_default_instance_fallback: true,
-                    verify_app_base_url: Some(
-                        "https://verify-sepolia.eigencloud.xyz/app".to_string(),
-                    ),
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 100,
-                },
-                store_path,
+                SimulatedProvisioning::ready("https://shared.example.com").frontdoor_config(),
+                store_path.clone(),
             );
 
-            let private_key = decode_hex_prefixed(
-                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-            )
-            .expect("private key");
-            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
-            let wallet =
-                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
-
-            let challenge = service
-                .create_challenge(FrontdoorChallengeRequest {
-                    wallet_address: wallet.clone(),
-                    privy_user_id: None,
-                    chain_id: Some(1),
-                })
-                .await
-                .expect("challenge");
-            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
-
-            let prehash = eip191_personal_sign_hash(&challenge.message);
-            let (sig, recid) = signing_key
-                .sign_prehash_recoverable(&prehash)
-                .expect("sign challenge");
-            let mut sig_bytes = sig.to_bytes().to_vec();
-            sig_bytes.push(recid.to_byte() + 27);
-            let signature = format!("0x{}", encode_hex_lower(&sig_bytes));
-
-            let verify_response = service
-                .clone()
-                .verify_and_start(FrontdoorVerifyRequest {
-                    session_id: challenge.session_id.clone(),
-                    wallet_address: wallet.clone(),
-                    privy_user_id: None,
-                    privy_identity_token: None,
-                    privy_access_token: None,
-                    message: challenge.message.clone(),
-                    signature: signature.clone(),
-                    config: sample_user_config(&wallet),
-                })
-                .await
-                .expect("first verify and start");
-            assert_eq!(verify_response.status, "provisioning");
-
-            for _ in 0..40 {
-                let session = service
-                    .get_session(session_uuid)
+            let mut session_ids = Vec::new();
+            for i in 1..=12u64 {
+                let wallet = format!("0x{i:040x}");
+                let challenge = service
+                    .create_challenge(FrontdoorChallengeRequest {
+                        wallet_address: wallet.clone(),
+                        privy_user_id: None,
+                        chain_id: Some(1),
+                    })
                     .await
-                    .expect("session should exist");
-                if session.status == "ready" {
-                    break;
-                }
-                assert_ne!(session.status, "failed", "session failed unexpectedly");
-                assert_ne!(session.status, "expired", "session expired unexpectedly");
-                tokio::time::sleep(Duration::from_millis(25)).await;
+                    .expect("challenge");
+                let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+                let mut state = service.state.write().await;
+                let session = state.sessions.get_mut(&session_id).expect("session");
+                session.config = Some(sample_user_config(&wallet));
+                session.status = SessionStatus::Provisioning;
+                session_ids.push(session_id);
             }
 
-            let timeline_before = service
-                .session_timeline(session_uuid)
-                .await
-                .expect("timeline before replay");
-            let provisioning_started_before = timeline_before
-                .events
+            let handles: Vec<_> = session_ids
                 .iter()
-                .filter(|event| event.event_type == "provisioning_started")
+                .map(|session_id| tokio::spawn(Arc::clone(&service).run_provision(*session_id)))
+                .collect();
+            for handle in handles {
+                handle.await.expect("provision task");
+            }
+
+            let persisted = load_wallet_store(&store_path).expect("wallet store");
+            assert_eq!(persisted.wallets.len(), 12);
+            for i in 1..=12u64 {
+                assert!(
+                    persisted.wallets.contains_key(&format!("0x{i:040x}")),
+                    "wallet {i} missing"
+                );
+            }
+            let leftovers = std::fs::read_dir(tmp.path())
+                .expect("store dir")
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
                 .count();
-            assert_eq!(provisioning_started_before, 1);
+            assert_eq!(leftovers, 0);
+        });
+    }
 
-            let replay_response = service
-                .clone()
-                .verify_and_start(FrontdoorVerifyRequest {
-                    session_id: challenge.session_id,
-                    wallet_address: wallet.clone(),
-                    privy_user_id: None,
-                    privy_identity_token: None,
-                    privy_access_token: None,
-                    message: challenge.message,
-                    signature,
-                    config: sample_user_config(&wallet),
-                })
-                .await
-                .expect("replay verify should be idempotent");
-            assert_eq!(replay_response.status, "ready");
+    #[test]
+    fn provision_log_flood_keeps_semantic_timeline_events() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let challenge = harness.challenge().await;
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            {
+                let mut state = harness.service.state.write().await;
+                let session = state.sessions.get_mut(&session_id).expect("session");
+                push_timeline_event(
+                    session,
+                    "provisioning_decision",
+                    "provisioning",
+                    "command",
+                    "system",
+                );
+            }
 
-            tokio::time::sleep(Duration::from_millis(50)).await;
+            for i in 0..600 {
+                harness
+                    .service
+                    .emit_provision_log(
+                        session_id,
+                        &ProvisionCommandLog {
+                            source: "provisioner".to_string(),
+                            stream: "stdout".to_string(),
+                            line: format!("line {i}"),
+                        },
+                    )
+                    .await;
+            }
+            {
+                let mut state = harness.service.state.write().await;
+                let session = state.sessions.get_mut(&session_id).expect("session");
+                push_timeline_event(session, "provisioning_completed", "ready", "done", "system");
+            }
 
-            let timeline_after = service
-                .session_timeline(session_uuid)
-                .await
-                .expect("timeline after replay");
-            let provisioning_started_after = timeline_after
-                .events
+            let state = harness.service.state.read().await;
+            let timeline = &state.sessions[&session_id].timeline;
+            assert!(
+                timeline
+                    .iter()
+                    .any(|event| event.event_type == "provisioning_decision")
+            );
+            assert!(
+                timeline
+                    .iter()
+                    .any(|event| event.event_type == "provisioning_completed")
+            );
+            let summaries = timeline
                 .iter()
-                .filter(|event| event.event_type == "provisioning_started")
+                .filter(|event| event.event_type == "provision_log")
                 .count();
-            assert_eq!(provisioning_started_after, 1);
+            assert_eq!(summaries, 7);
+            drop(state);
+
+            let logs = harness.service.provision_logs(session_id).await;
+            assert_eq!(logs.len(), FRONTDOOR_PROVISION_LOG_CAP);
+            assert_eq!(logs.first().expect("first").line, "line 100");
+            assert_eq!(logs.last().expect("last").line, "line 599");
+            assert!(
+                harness
+                    .service
+                    .provision_logs(Uuid::new_v4())
+                    .await
+                    .is_empty()
+            );
         });
     }
 
     #[test]
-    fn frontdoor_privy_mode_accepts_wallet_signature_without_siwe_tokens() {
+    fn verify_accepts_any_casing_but_rejects_wrong_checksum() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
-            let tmp = tempdir().expect("tempdir");
-            let store_path = tmp.path().join("wallet_sessions.json");
-            let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: true,
-                    privy_app_id: Some("app_123".to_string()),
-                    privy_client_id: None,
-                    provision_command: None,
-                    default_instance_url: Some(
-                        "https://session.example/gateway?token=demo".to_string(),
-                    ),
-                    allow_default_instance_fallback: true,
-                    verify_app_base_url: None,
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 100,
-                },
-                store_path,
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let challenge = harness.challenge().await;
+            assert_eq!(
+                challenge.wallet_address,
+                "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
             );
 
-            let private_key = decode_hex_prefixed(
-                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-            )
-            .expect("private key");
-            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
-            let wallet =
-                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
-
-            let challenge = service
-                .create_challenge(FrontdoorChallengeRequest {
-                    wallet_address: wallet.clone(),
-                    privy_user_id: Some(format!("wallet:{wallet}")),
-                    chain_id: Some(1),
-                })
+            let err = harness
+                .verify_as(&challenge, "0xF39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
                 .await
-                .expect("challenge");
-
-            let prehash = eip191_personal_sign_hash(&challenge.message);
-            let (sig, recid) = signing_key
-                .sign_prehash_recoverable(&prehash)
-                .expect("sign challenge");
-            let mut sig_bytes = sig.to_bytes().to_vec();
-            sig_bytes.push(recid.to_byte() + 27);
-            let signature = format!("0x{}", encode_hex_lower(&sig_bytes));
+                .expect_err("bad checksum");
+            assert!(matches!(err, FrontdoorError::InvalidRequest(_)), "{err:?}");
+            assert!(err.to_string().contains("EIP-55"), "{err}");
 
-            service
-                .clone()
-                .verify_and_start(FrontdoorVerifyRequest {
-                    session_id: challenge.session_id,
-                    wallet_address: wallet.clone(),
-                    privy_user_id: None,
-                    privy_identity_token: None,
-                    privy_access_token: None,
-                    message: challenge.message,
-                    signature,
-                    config: sample_user_config(&wallet),
-                })
+            let verified = harness
+                .verify_as(&challenge, &challenge.wallet_address)
                 .await
-                .expect("verify and start");
+                .expect("checksummed wallet verifies");
+            assert_eq!(verified.status, "provisioning");
         });
     }
 
     #[test]
-    fn intent_can_skip_dedicated_spinup_when_shared_fallback_is_available() {
+    fn verify_checks_expiry_embedded_in_signed_challenge() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
-            let tmp = tempdir().expect("tempdir");
-            let store_path = tmp.path().join("wallet_sessions.json");
-            let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: false,
-                    privy_app_id: None,
-                    privy_client_id: None,
-                    provision_command: Some(
-                        "printf 'https://dedicated.example/gateway?token=dedicated\\n'".to_string(),
-                    ),
-                    default_instance_url: Some(
-                        "https://shared.example/gateway?token=shared".to_string(),
-                    ),
-                    allow_default_instance_fallback: true,
-                    verify_app_base_url: None,
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 100,
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let challenge = harness.challenge().await;
+            let expires_at =
+                challenge_message_field(&challenge.message, "Expires At").expect("expires at line");
+            let issued_at =
+                challenge_message_field(&challenge.message, "Issued At").expect("issued at line");
+            let ttl = DateTime::parse_from_rfc3339(expires_at).expect("expires at")
+                - DateTime::parse_from_rfc3339(issued_at).expect("issued at");
+            assert_eq!(ttl.num_seconds(), 900);
+            let verified = harness.verify(&challenge).await.expect("fresh challenge");
+            assert_eq!(verified.status, "provisioning");
+
+            let mut stale = harness.challenge().await;
+            let stale_expires_at = challenge_message_field(&stale.message, "Expires At")
+                .expect("expires at line")
+                .to_string();
+            harness.clock.advance(chrono::Duration::seconds(901));
+
+            // A later expiry in the submitted message does not extend the challenge.
+            let issued_message = stale.message.clone();
+            stale.message = issued_message.replace(&stale_expires_at, "2099-01-01T00:00:00+00:00");
+            let err = harness.verify(&stale).await.expect_err("tampered expiry");
+            assert_eq!(
+                err,
+                FrontdoorError::ChallengeMessageExpired {
+                    expires_at: stale_expires_at.clone(),
+                }
+            );
+            stale.message = issued_message;
+
+            let err = harness.verify(&stale).await.expect_err("expired message");
+            assert_eq!(
+                err,
+                FrontdoorError::ChallengeMessageExpired {
+                    expires_at: stale_expires_at,
+                }
+            );
+            assert_eq!(err.status_code(), 410);
+            let session = harness
+                .service
+                .get_session(harness.session_uuid(&stale))
+                .await
+                .expect("session");
+            assert_eq!(session.status, "expired");
+
+            let err = harness
+                .service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: harness.wallet.clone(),
+                    privy_user_id: Some(
+                        "did:privy:abc\nExpires At: 2099-01-01T00:00:00Z".to_string(),
+                    ),
+                    chain_id: Some(1),
+                })
+                .await
+                .expect_err("control characters in privy id");
+            assert!(matches!(err, FrontdoorError::InvalidRequest(_)));
+        });
+    }
+
+    #[test]
+    fn retry_provision_recovers_from_transient_failure() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::flaky(
+                "https://simulated.example/gateway",
+                1,
+            ));
+            let challenge = harness.challenge().await;
+            harness.verify(&challenge).await.expect("verify");
+            let failed = harness.wait_for_status(&challenge, "failed").await;
+            assert!(failed.instance_url.is_none());
+
+            let retried = harness
+                .service
+                .clone()
+                .retry_provision(harness.session_uuid(&challenge))
+                .await
+                .expect("retry");
+            assert_eq!(retried.status, "provisioning");
+            let ready = harness.wait_for_status(&challenge, "ready").await;
+            assert_eq!(
+                ready.instance_url.as_deref(),
+                Some("https://simulated.example/gateway")
+            );
+        });
+    }
+
+    #[test]
+    fn retry_provision_rejects_unsigned_and_expired_sessions() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::flaky(
+                "https://simulated.example/gateway",
+                1,
+            ));
+            let challenge = harness.challenge().await;
+            let session_uuid = harness.session_uuid(&challenge);
+            let err = harness
+                .service
+                .clone()
+                .retry_provision(session_uuid)
+                .await
+                .expect_err("unsigned session");
+            assert!(matches!(err, FrontdoorError::InvalidRequest(_)), "{err:?}");
+
+            harness.verify(&challenge).await.expect("verify");
+            harness.wait_for_status(&challenge, "failed").await;
+            harness.clock.advance(chrono::Duration::seconds(901));
+            let err = harness
+                .service
+                .clone()
+                .retry_provision(session_uuid)
+                .await
+                .expect_err("expired session");
+            assert_eq!(err, FrontdoorError::SessionExpired);
+        });
+    }
+
+    #[test]
+    fn retry_provision_stops_at_configured_limit() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::flaky(
+                "https://simulated.example/gateway",
+                usize::MAX,
+            ));
+            let challenge = harness.challenge().await;
+            let session_uuid = harness.session_uuid(&challenge);
+            harness.verify(&challenge).await.expect("verify");
+            harness.wait_for_status(&challenge, "failed").await;
+
+            for _ in 0..3 {
+                harness
+                    .service
+                    .clone()
+                    .retry_provision(session_uuid)
+                    .await
+                    .expect("retry within limit");
+                harness.wait_for_status(&challenge, "failed").await;
+            }
+            let err = harness
+                .service
+                .clone()
+                .retry_provision(session_uuid)
+                .await
+                .expect_err("retry past limit");
+            assert_eq!(err, FrontdoorError::RetryLimitReached { limit: 3 });
+            assert_eq!(err.status_code(), 409);
+        });
+    }
+
+    #[test]
+    fn signature_shape_validation() {
+        let sig = format!("0x{}", "a".repeat(130));
+        assert!(is_signature_like(&sig));
+        assert!(!is_signature_like("0xabc"));
+        assert!(!is_signature_like("abc"));
+        assert!(!is_signature_like(&format!("0x{}", "z".repeat(130))));
+    }
+
+    #[test]
+    fn verifies_wallet_signature_recovery() {
+        let private_key =
+            hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .expect("private key");
+        let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+        let wallet =
+            ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+        let message = "Enclagent test message";
+        let prehash = eip191_personal_sign_hash(message);
+        let (sig, recid) = signing_key
+            .sign_prehash_recoverable(&prehash)
+            .expect("sign message");
+        let mut sig_bytes = sig.to_bytes().to_vec();
+        sig_bytes.push(recid.to_byte() + 27);
+        let signature = hex::encode(&sig_bytes, true);
+
+        verify_wallet_signature(message, &signature, &wallet).expect("signature should verify");
+        assert!(
+            verify_wallet_signature(
+                message,
+                &signature,
+                "0x0000000000000000000000000000000000000001"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn verifies_eip712_authorization_signature_recovery() {
+        // Fixed vector computed outside this crate: a generic EIP-712 typed-data
+        // encoder with RFC 6979 signing (low-s), checked first against the Mail
+        // example from the EIP-712 specification. Typed data:
+        //   domain  {name: "Enclagent Frontdoor", version: "1", chainId: 1}
+        //   FrontdoorAuthorization {wallet, sessionId, nonce, issuedAt, expiresAt}
+        //   as below
+        // signed by the Hardhat/Anvil account #0 key.
+        const EXPECTED_DIGEST: &str =
+            "b8162da7f755f1894ec6178bc1f242766283b7e1c630a07d618c31b8775dad35";
+        const EXPECTED_SIGNATURE: &str = "0x9cbc7f1097a6f4e5e3453b70affe888bfe09c8ff8e27be2765147cef74108f0c49dc9a84c6a924c2c20930439fb6287c9c5965afe1c4f50b2a20fef2bc79a6811c";
+
+        let wallet = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+        assert_eq!(
+            hex::encode(
+                &keccak256_concat(&[b"EIP712Domain(string name,string version,uint256 chainId)"]),
+                false
+            ),
+            "c2f8787176b8ac6bf7215b4adcc1e069bf4ab82d9ab1df05a57a91d425935b6e"
+        );
+
+        let session_id = "5b2f6c1e-8a4d-4f43-9d1e-0c6f1a2b3c4d";
+        let nonce = "fixednonce0123456789abcd";
+        let issued_at = "2026-01-01T00:00:00+00:00";
+        let expires_at = "2026-01-01T00:15:00+00:00";
+        let prehash = eip712_authorization_hash(
+            &Eip712MessageDomain::frontdoor(1),
+            session_id,
+            wallet,
+            nonce,
+            issued_at,
+            expires_at,
+        )
+        .expect("typed data hash");
+        assert_eq!(hex::encode(&prehash, false), EXPECTED_DIGEST);
+
+        let signature_bytes = hex::decode(EXPECTED_SIGNATURE).expect("signature hex");
+        let sig = EcdsaSignature::try_from(&signature_bytes[..64]).expect("signature bytes");
+        let recovery_id = normalize_recovery_id(signature_bytes[64]).expect("recovery id");
+        let recovered = VerifyingKey::recover_from_prehash(&prehash, &sig, recovery_id)
+            .expect("fixed signature should recover");
+        assert_eq!(
+            ethereum_address_from_verifying_key(&recovered).expect("wallet"),
+            wallet
+        );
+        verify_wallet_signature_prehash(&prehash, EXPECTED_SIGNATURE, wallet)
+            .expect("eip712 signature should recover to the wallet");
+
+        let other_chain = eip712_authorization_hash(
+            &Eip712MessageDomain::frontdoor(8453),
+            session_id,
+            wallet,
+            nonce,
+            issued_at,
+            expires_at,
+        )
+        .expect("typed data hash");
+        assert_ne!(prehash, other_chain);
+        let later_expiry = eip712_authorization_hash(
+            &Eip712MessageDomain::frontdoor(1),
+            session_id,
+            wallet,
+            nonce,
+            issued_at,
+            "2099-01-01T00:00:00+00:00",
+        )
+        .expect("typed data hash");
+        assert_ne!(prehash, later_expiry);
+        assert!(verify_wallet_signature_prehash(&other_chain, EXPECTED_SIGNATURE, wallet).is_err());
+        assert!(verify_wallet_signature(session_id, EXPECTED_SIGNATURE, wallet).is_err());
+    }
+
+    #[test]
+    fn verify_accepts_eip712_signature_scheme() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    default_instance_url: Some(
+                        "https://session.example/gateway?token=demo".to_string(),
+                    ),
+                    allow_default_instance_fallback: true,
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
                 },
-                store_path,
+                tmp.path().join("wallet_sessions.json"),
             );
+            let private_key =
+                hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                    .expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
 
-            let private_key = decode_hex_prefixed(
-                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let field = |name| challenge_message_field(&challenge.message, name).expect(name);
+            let prehash = eip712_authorization_hash(
+                &Eip712MessageDomain::frontdoor(1),
+                &challenge.session_id,
+                &wallet,
+                field("Nonce"),
+                field("Issued At"),
+                field("Expires At"),
             )
-            .expect("private key");
+            .expect("typed data hash");
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign typed data");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            let signature = hex::encode(&sig_bytes, true);
+
+            let personal_sign_attempt = service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature: signature.clone(),
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
+                })
+                .await;
+            assert!(personal_sign_attempt.is_err());
+
+            let verified = service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature,
+                    signature_scheme: Some("eip712".to_string()),
+                    config: sample_user_config(&wallet),
+                })
+                .await
+                .expect("eip712 verify");
+            assert_eq!(verified.session_id, challenge.session_id);
+        });
+    }
+
+    #[test]
+    fn eip712_signature_binds_the_challenge_expiry() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+
+            // A signature over a later expiry does not match the issued one.
+            let challenge = harness.challenge().await;
+            let expires_at = challenge_message_field(&challenge.message, "Expires At")
+                .expect("expires at line")
+                .to_string();
+            let err = harness
+                .verify_eip712(
+                    &challenge,
+                    harness.sign_eip712(&challenge, "2099-01-01T00:00:00+00:00"),
+                )
+                .await
+                .expect_err("signature over another expiry");
+            assert!(
+                matches!(err, FrontdoorError::SignatureInvalid(_)),
+                "{err:?}"
+            );
+            let verified = harness
+                .verify_eip712(&challenge, harness.sign_eip712(&challenge, &expires_at))
+                .await
+                .expect("signed expiry verifies");
+            assert_eq!(verified.status, "provisioning");
+
+            let stale = harness.challenge().await;
+            let stale_expires_at = challenge_message_field(&stale.message, "Expires At")
+                .expect("expires at line")
+                .to_string();
+            let signature = harness.sign_eip712(&stale, &stale_expires_at);
+            harness.clock.advance(chrono::Duration::seconds(901));
+            let err = harness
+                .verify_eip712(&stale, signature)
+                .await
+                .expect_err("expired eip712 challenge");
+            assert_eq!(
+                err,
+                FrontdoorError::ChallengeMessageExpired {
+                    expires_at: stale_expires_at,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn eip712_requires_signed_challenge_timestamps() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let provisioning = SimulatedProvisioning::ready("https://simulated.example/gateway");
+            let config = FrontdoorConfig {
+                challenge_template: Some(
+                    "Acme Wallet Login\nChain ID: {chain_id}\nSession ID: {session_id}\nNonce: {nonce}"
+                        .to_string(),
+                ),
+                ..provisioning.frontdoor_config()
+            };
+            let harness = FrontdoorTestHarness::with_config(provisioning, config);
+            let challenge = harness.challenge().await;
+            assert!(challenge_message_field(&challenge.message, "Expires At").is_none());
+
+            let err = harness
+                .verify_eip712(&challenge, harness.sign_eip712(&challenge, ""))
+                .await
+                .expect_err("eip712 without signed timestamps");
+            assert!(matches!(err, FrontdoorError::InvalidRequest(_)), "{err:?}");
+            assert!(err.to_string().contains("Expires At"), "{err}");
+
+            let verified = harness.verify(&challenge).await.expect("personal_sign");
+            assert_eq!(verified.status, "provisioning");
+        });
+    }
+
+    #[test]
+    fn frontdoor_flow_reaches_ready_state_after_valid_signature() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let store_path = tmp.path().join("wallet_sessions.json");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    default_instance_url: Some(
+                        "https://session.example/gateway?token=demo".to_string(),
+                    ),
+                    allow_default_instance_fallback: true,
+                    verify_app_base_url: Some(
+                        "https://verify-sepolia.eigencloud.xyz/app".to_string(),
+                    ),
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
+                },
+                store_path,
+            );
+
+            let private_key =
+                hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                    .expect("private key");
             let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
             let wallet =
                 ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
@@ -4795,11 +7972,8 @@ mod tests {
                 .expect("sign challenge");
             let mut sig_bytes = sig.to_bytes().to_vec();
             sig_bytes.push(recid.to_byte() + 27);
-            let signature = format!("0x{}", encode_hex_lower(&sig_bytes));
+            let signature = hex::encode(&sig_bytes, true);
 
-            let mut cfg = sample_user_config(&wallet);
-            cfg.inference_summary =
-                Some("shared runtime only, no enclave spinup; reuse existing instance".to_string());
             service
                 .clone()
                 .verify_and_start(FrontdoorVerifyRequest {
@@ -4810,7 +7984,8 @@ mod tests {
                     privy_access_token: None,
                     message: challenge.message.clone(),
                     signature,
-                    config: cfg,
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
                 })
                 .await
                 .expect("verify and start");
@@ -4831,629 +8006,3855 @@ mod tests {
             }
 
             let ready = ready.expect("session should become ready");
-            assert_eq!(ready.provisioning_source, "default_instance_url");
             assert_eq!(
                 ready.instance_url.as_deref(),
-                Some("https://shared.example/gateway?token=shared")
+                Some("https://session.example/gateway?token=demo")
             );
+            assert_eq!(ready.wallet_address, to_checksummed_address(&wallet));
+            assert_eq!(ready.profile_name.as_deref(), Some("demo_profile"));
+            assert_eq!(ready.provisioning_source, "default_instance_url");
             assert!(!ready.dedicated_instance);
+            assert!(!ready.launched_on_eigencloud);
+            assert_eq!(ready.verification_backend, "eigencloud_primary");
+            assert_eq!(ready.verification_level, "primary_plus_signed_fallback");
         });
     }
 
     #[test]
-    fn provisioning_decision_prefers_shared_runtime_for_shared_hints() {
-        let decision = decide_provisioning_decision(Some("No enclave please; shared runtime only"));
-        assert!(decision.prefer_shared_runtime);
-    }
-
-    #[test]
-    fn provisioning_decision_defaults_to_dedicated_when_hint_is_absent() {
-        let decision = decide_provisioning_decision(Some("Launch isolated dedicated enclave"));
-        assert!(!decision.prefer_shared_runtime);
-    }
-
-    #[test]
-    fn extract_provision_result_from_command_output() {
-        let json =
-            r#"{"instance_url":"https://session.example/gateway?token=abc","app_id":"0xabc"}"#;
-        let result =
-            execute_provision_output(json, Some("https://verify-sepolia.eigencloud.xyz/app"))
-                .expect("json output");
-        assert_eq!(
-            result.instance_url,
-            "https://session.example/gateway?token=abc"
-        );
-        assert_eq!(
-            result.verify_url.as_deref(),
-            Some("https://verify-sepolia.eigencloud.xyz/app/0xabc")
-        );
-        assert_eq!(result.eigen_app_id.as_deref(), Some("0xabc"));
-        assert!(result.app_url.is_none());
+    fn verify_clamps_out_of_bounds_config_and_rejects_invalid_config() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    default_instance_url: Some(
+                        "https://session.example/gateway?token=demo".to_string(),
+                    ),
+                    allow_default_instance_fallback: true,
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
+                },
+                tmp.path().join("wallet_sessions.json"),
+            );
+            let private_key =
+                hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                    .expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
 
-        let json_with_app_only =
-            r#"{"app_url":"https://sepolia.eigencloud.xyz/app/0xdef","app_id":"0xdef"}"#;
-        let result = execute_provision_output(
-            json_with_app_only,
-            Some("https://verify-sepolia.eigencloud.xyz/app"),
-        )
-        .expect("json output with app url");
-        assert_eq!(
-            result.instance_url,
-            "https://sepolia.eigencloud.xyz/app/0xdef"
-        );
-        assert_eq!(
-            result.app_url.as_deref(),
-            Some("https://sepolia.eigencloud.xyz/app/0xdef")
-        );
-        assert_eq!(
-            result.verify_url.as_deref(),
-            Some("https://verify-sepolia.eigencloud.xyz/app/0xdef")
-        );
-        assert_eq!(result.eigen_app_id.as_deref(), Some("0xdef"));
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let prehash = eip191_personal_sign_hash(&challenge.message);
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            let signature = hex::encode(&sig_bytes, true);
 
-        let plain = "line1\nhttps://foo.example/path\n";
-        let result = execute_provision_output(plain, None).expect("plain url");
-        assert_eq!(result.instance_url, "https://foo.example/path");
-        assert!(result.app_url.is_none());
-        assert!(result.verify_url.is_none());
+            let mut unaccepted = sample_user_config(&wallet);
+            unaccepted.leverage_cap = 50;
+            unaccepted.accept_terms = false;
+            let rejected = service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature: signature.clone(),
+                    signature_scheme: None,
+                    config: unaccepted,
+                })
+                .await
+                .expect_err("terms must still be accepted");
+            assert_eq!(rejected.to_string(), "accept_terms must be true");
+
+            let mut over_leveraged = sample_user_config(&wallet);
+            over_leveraged.leverage_cap = 50;
+            over_leveraged.max_leverage = 40;
+            over_leveraged.max_slippage_bps = 9_000;
+            let verified = service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature,
+                    signature_scheme: None,
+                    config: over_leveraged,
+                })
+                .await
+                .expect("over-leveraged config is clamped, not rejected");
+            assert_eq!(
+                verified.config_warnings,
+                vec![
+                    "leverage_cap clamped from 50 to 20".to_string(),
+                    "max_leverage clamped from 40 to 20".to_string(),
+                    "max_slippage_bps clamped from 9000 to 5000".to_string(),
+                ]
+            );
 
-        let verify_only = r#"{"instance_url":"https://verify-sepolia.eigencloud.xyz/app/0x1234","verify_url":"https://verify-sepolia.eigencloud.xyz/app/0x1234","app_id":"0x1234"}"#;
-        let result = execute_provision_output(verify_only, None).expect("verify-only output");
-        assert_eq!(
-            result.instance_url,
-            "https://sepolia.eigencloud.xyz/app/0x1234"
-        );
-        assert_eq!(
-            result.app_url.as_deref(),
-            Some("https://sepolia.eigencloud.xyz/app/0x1234")
-        );
-        assert_eq!(
-            result.verify_url.as_deref(),
-            Some("https://verify-sepolia.eigencloud.xyz/app/0x1234")
-        );
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            let state = service.state.read().await;
+            let session = state.sessions.get(&session_id).expect("session");
+            let stored = session.config.as_ref().expect("stored config");
+            assert_eq!(stored.leverage_cap, 20);
+            assert_eq!(stored.max_leverage, 20);
+            assert_eq!(
+                session
+                    .timeline
+                    .iter()
+                    .filter(|event| event.event_type == "config_clamped")
+                    .count(),
+                3
+            );
+        });
     }
 
     #[test]
-    fn build_provision_command_expands_single_quoted_placeholders() {
+    fn frontdoor_verify_is_idempotent_after_ready() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
-            let wallet = "0xe10e3def5348cb4151a8b99beebfd43646bade59".to_string();
-            let config = sample_user_config(&wallet);
-            let session_id = Uuid::new_v4();
-            let input = ProvisionCommandInput {
-                session_id,
-                wallet: &wallet,
-                privy_user_id: None,
-                privy_identity_token: None,
-                privy_access_token: None,
-                chain_id: 1,
-                version: 1,
-                config: &config,
-                verify_base_url: Some("https://verify-sepolia.eigencloud.xyz/app"),
-            };
-
-            let mut command = build_provision_command(
-                "printf '%s|%s|%s' '{wallet_address}' '{session_id}' '{config_b64}'",
-                &input,
-            )
-            .expect("command");
-            let output = command.output().await.expect("command output");
-            assert!(output.status.success());
-            let stdout = String::from_utf8(output.stdout).expect("stdout utf8");
-            let parts: Vec<&str> = stdout.trim().split('|').collect();
-            assert_eq!(parts.len(), 3);
-            assert_eq!(parts[0], wallet);
-            assert_eq!(parts[1], session_id.to_string());
-
-            let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
-                .decode(parts[2])
-                .expect("decode config_b64");
-            let decoded_cfg: FrontdoorUserConfig =
-                serde_json::from_slice(&decoded).expect("decoded config json");
-            assert_eq!(decoded_cfg.profile_name, config.profile_name);
-            assert_eq!(decoded_cfg.gateway_auth_key, config.gateway_auth_key);
-        });
-    }
+            let tmp = tempdir().expect("tempdir");
+            let store_path = tmp.path().join("wallet_sessions.json");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    default_instance_url: Some(
+                        "https://session.example/gateway?token=demo".to_string(),
+                    ),
+                    allow_default_instance_fallback: true,
+                    verify_app_base_url: Some(
+                        "https://verify-sepolia.eigencloud.xyz/app".to_string(),
+                    ),
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
+                },
+                store_path,
+            );
 
-    #[test]
-    fn suggest_config_maps_intent_to_validated_config() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
-        );
+            let private_key =
+                hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                    .expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
 
-        let suggested = service
-            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
-                intent: "conservative BTC-only paper trading with full audit".to_string(),
-                domain: Some("hyperliquid".to_string()),
-                gateway_auth_key: Some("supersecuregatewaykey01".to_string()),
-                base_config: None,
-            })
-            .expect("suggest config");
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
 
-        assert!(suggested.validated);
-        assert_eq!(suggested.config.profile_domain, "hyperliquid");
-        assert_eq!(suggested.config.paper_live_policy, "paper_only");
-        assert_eq!(suggested.config.symbol_allowlist, vec!["BTC".to_string()]);
-        assert_eq!(suggested.config.information_sharing_scope, "full_audit");
-        assert_ne!(suggested.config.profile_name, "launchpad_profile");
-        assert!(suggested.config.profile_name.contains("btc"));
-    }
+            let prehash = eip191_personal_sign_hash(&challenge.message);
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            let signature = hex::encode(&sig_bytes, true);
 
-    #[test]
-    fn suggest_config_enforces_connected_wallet_for_user_and_dual_custody() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
-        );
+            let verify_response = service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature: signature.clone(),
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
+                })
+                .await
+                .expect("first verify and start");
+            assert_eq!(verify_response.status, "provisioning");
 
-        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
-        let mismatched_wallet = "0x8ba1f109551bD432803012645Ac136ddd64DBA72";
+            for _ in 0..40 {
+                let session = service
+                    .get_session(session_uuid)
+                    .await
+                    .expect("session should exist");
+                if session.status == "ready" {
+                    break;
+                }
+                assert_ne!(session.status, "failed", "session failed unexpectedly");
+                assert_ne!(session.status, "expired", "session expired unexpectedly");
+                tokio::time::sleep(Duration::from_millis(25)).await;
+            }
 
-        let base_user_wallet = sample_user_config(mismatched_wallet);
-        let user_wallet_err = service
-            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                wallet_address: connected_wallet.to_string(),
-                intent: "use my own wallet".to_string(),
-                domain: Some("hyperliquid".to_string()),
-                gateway_auth_key: None,
-                base_config: Some(base_user_wallet),
-            })
-            .expect_err("mismatched user_wallet should fail");
+            let timeline_before = service
+                .session_timeline(session_uuid)
+                .await
+                .expect("timeline before replay");
+            let provisioning_started_before = timeline_before
+                .events
+                .iter()
+                .filter(|event| event.event_type == "provisioning_started")
+                .count();
+            assert_eq!(provisioning_started_before, 1);
+
+            let replay_response = service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id,
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message,
+                    signature,
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
+                })
+                .await
+                .expect("replay verify should be idempotent");
+            assert_eq!(replay_response.status, "ready");
+
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            let timeline_after = service
+                .session_timeline(session_uuid)
+                .await
+                .expect("timeline after replay");
+            let provisioning_started_after = timeline_after
+                .events
+                .iter()
+                .filter(|event| event.event_type == "provisioning_started")
+                .count();
+            assert_eq!(provisioning_started_after, 1);
+        });
+    }
+
+    #[test]
+    fn frontdoor_privy_mode_accepts_wallet_signature_without_siwe_tokens() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let store_path = tmp.path().join("wallet_sessions.json");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    require_privy: true,
+                    privy_app_id: Some("app_123".to_string()),
+                    default_instance_url: Some(
+                        "https://session.example/gateway?token=demo".to_string(),
+                    ),
+                    allow_default_instance_fallback: true,
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
+                },
+                store_path,
+            );
+
+            let private_key =
+                hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                    .expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: Some(format!("wallet:{wallet}")),
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+
+            let prehash = eip191_personal_sign_hash(&challenge.message);
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            let signature = hex::encode(&sig_bytes, true);
+
+            service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id,
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message,
+                    signature,
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
+                })
+                .await
+                .expect("verify and start");
+        });
+    }
+
+    #[test]
+    fn intent_can_skip_dedicated_spinup_when_shared_fallback_is_available() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let store_path = tmp.path().join("wallet_sessions.json");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    provision_command: Some(
+                        "printf 'https://dedicated.example/gateway?token=dedicated\\n'".to_string(),
+                    ),
+                    default_instance_url: Some(
+                        "https://shared.example/gateway?token=shared".to_string(),
+                    ),
+                    allow_default_instance_fallback: true,
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
+                },
+                store_path,
+            );
+
+            let private_key =
+                hex::decode("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                    .expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
+
+            let prehash = eip191_personal_sign_hash(&challenge.message);
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            let signature = hex::encode(&sig_bytes, true);
+
+            let mut cfg = sample_user_config(&wallet);
+            cfg.inference_summary =
+                Some("shared runtime only, no enclave spinup; reuse existing instance".to_string());
+            service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature,
+                    signature_scheme: None,
+                    config: cfg,
+                })
+                .await
+                .expect("verify and start");
+
+            let mut ready = None;
+            for _ in 0..40 {
+                let session = service
+                    .get_session(session_uuid)
+                    .await
+                    .expect("session should exist");
+                if session.status == "ready" {
+                    ready = Some(session);
+                    break;
+                }
+                assert_ne!(session.status, "failed", "session failed unexpectedly");
+                assert_ne!(session.status, "expired", "session expired unexpectedly");
+                tokio::time::sleep(Duration::from_millis(25)).await;
+            }
+
+            let ready = ready.expect("session should become ready");
+            assert_eq!(ready.provisioning_source, "default_instance_url");
+            assert_eq!(
+                ready.instance_url.as_deref(),
+                Some("https://shared.example/gateway?token=shared")
+            );
+            assert!(!ready.dedicated_instance);
+        });
+    }
+
+    #[test]
+    fn provisioning_decision_prefers_shared_runtime_for_shared_hints() {
+        let decision = decide_provisioning_decision(Some("No enclave please; shared runtime only"));
+        assert!(decision.prefer_shared_runtime);
+    }
+
+    #[test]
+    fn provisioning_decision_defaults_to_dedicated_when_hint_is_absent() {
+        let decision = decide_provisioning_decision(Some("Launch isolated dedicated enclave"));
+        assert!(!decision.prefer_shared_runtime);
+    }
+
+    #[test]
+    fn extract_provision_result_from_command_output() {
+        let json =
+            r#"{"instance_url":"https://session.example/gateway?token=abc","app_id":"0xabc"}"#;
+        let result =
+            execute_provision_output(json, Some("https://verify-sepolia.eigencloud.xyz/app"))
+                .expect("json output");
         assert_eq!(
-            user_wallet_err,
-            "user_wallet_address must match the connected wallet for user_wallet/dual_mode"
+            result.instance_url,
+            "https://session.example/gateway?token=abc"
+        );
+        assert_eq!(
+            result.verify_url.as_deref(),
+            Some("https://verify-sepolia.eigencloud.xyz/app/0xabc")
         );
+        assert_eq!(result.eigen_app_id.as_deref(), Some("0xabc"));
+        assert!(result.app_url.is_none());
+
+        let json_with_app_only =
+            r#"{"app_url":"https://sepolia.eigencloud.xyz/app/0xdef","app_id":"0xdef"}"#;
+        let result = execute_provision_output(
+            json_with_app_only,
+            Some("https://verify-sepolia.eigencloud.xyz/app"),
+        )
+        .expect("json output with app url");
+        assert_eq!(
+            result.instance_url,
+            "https://sepolia.eigencloud.xyz/app/0xdef"
+        );
+        assert_eq!(
+            result.app_url.as_deref(),
+            Some("https://sepolia.eigencloud.xyz/app/0xdef")
+        );
+        assert_eq!(
+            result.verify_url.as_deref(),
+            Some("https://verify-sepolia.eigencloud.xyz/app/0xdef")
+        );
+        assert_eq!(result.eigen_app_id.as_deref(), Some("0xdef"));
+
+        let plain = "line1\nhttps://foo.example/path\n";
+        let result = execute_provision_output(plain, None).expect("plain url");
+        assert_eq!(result.instance_url, "https://foo.example/path");
+        assert!(result.app_url.is_none());
+        assert!(result.verify_url.is_none());
+
+        let verify_only = r#"{"instance_url":"https://verify-sepolia.eigencloud.xyz/app/0x1234","verify_url":"https://verify-sepolia.eigencloud.xyz/app/0x1234","app_id":"0x1234"}"#;
+        let result = execute_provision_output(verify_only, None).expect("verify-only output");
+        assert_eq!(
+            result.instance_url,
+            "https://sepolia.eigencloud.xyz/app/0x1234"
+        );
+        assert_eq!(
+            result.app_url.as_deref(),
+            Some("https://sepolia.eigencloud.xyz/app/0x1234")
+        );
+        assert_eq!(
+            result.verify_url.as_deref(),
+            Some("https://verify-sepolia.eigencloud.xyz/app/0x1234")
+        );
+    }
+
+    #[test]
+    fn redact_provision_line_masks_secrets_and_keeps_context() {
+        let wallet = "0xe10e3def5348cb4151a8b99beebfd43646bade59";
+        let config = sample_user_config(wallet);
+        let line = format!(
+            "export ENCLAGENT_FRONTDOOR_GATEWAY_AUTH_KEY={} && deploy --wallet {wallet}",
+            config.gateway_auth_key
+        );
+
+        let redacted = redact_provision_line(&line, &[&config.gateway_auth_key, "", "  "]);
+
+        assert_eq!(
+            redacted,
+            format!(
+                "export ENCLAGENT_FRONTDOOR_GATEWAY_AUTH_KEY=***redacted*** && deploy --wallet {wallet}"
+            )
+        );
+        assert!(!redacted.contains(&config.gateway_auth_key));
+        assert_eq!(
+            redact_provision_line("no secrets here", &[]),
+            "no secrets here"
+        );
+    }
+
+    #[test]
+    fn build_provision_command_expands_single_quoted_placeholders() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let wallet = "0xe10e3def5348cb4151a8b99beebfd43646bade59".to_string();
+            let config = sample_user_config(&wallet);
+            let session_id = Uuid::new_v4();
+            let input = ProvisionCommandInput {
+                session_id,
+                wallet: &wallet,
+                privy_user_id: None,
+                privy_identity_token: None,
+                privy_access_token: None,
+                chain_id: 1,
+                version: 1,
+                config: &config,
+                verify_base_url: Some("https://verify-sepolia.eigencloud.xyz/app"),
+            };
+
+            let mut command = build_provision_command(
+                "printf '%s|%s|%s' '{wallet_address}' '{session_id}' '{config_b64}'",
+                &input,
+            )
+            .expect("command");
+            let output = command.output().await.expect("command output");
+            assert!(output.status.success());
+            let stdout = String::from_utf8(output.stdout).expect("stdout utf8");
+            let parts: Vec<&str> = stdout.trim().split('|').collect();
+            assert_eq!(parts.len(), 3);
+            assert_eq!(parts[0], wallet);
+            assert_eq!(parts[1], session_id.to_string());
+
+            let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(parts[2])
+                .expect("decode config_b64");
+            let decoded_cfg: FrontdoorUserConfig =
+                serde_json::from_slice(&decoded).expect("decoded config json");
+            assert_eq!(decoded_cfg.profile_name, config.profile_name);
+            assert_eq!(decoded_cfg.gateway_auth_key, config.gateway_auth_key);
+        });
+    }
+
+    #[test]
+    fn suggest_config_maps_intent_to_validated_config() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+
+        let suggested = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                intent: "conservative BTC-only paper trading with full audit".to_string(),
+                domain: Some("hyperliquid".to_string()),
+                gateway_auth_key: Some("supersecuregatewaykey01".to_string()),
+                base_config: None,
+            })
+            .expect("suggest config");
+
+        assert!(suggested.validated);
+        assert_eq!(suggested.config.profile_domain, "hyperliquid");
+        assert_eq!(suggested.config.paper_live_policy, "paper_only");
+        assert_eq!(suggested.config.symbol_allowlist, vec!["BTC".to_string()]);
+        assert_eq!(suggested.config.information_sharing_scope, "full_audit");
+        assert_ne!(suggested.config.profile_name, "launchpad_profile");
+        assert!(suggested.config.profile_name.contains("btc"));
+    }
+
+    #[test]
+    fn symbol_universe_drops_unknown_tickers_with_warning() {
+        let symbols = vec![
+            "btc".to_string(),
+            "BTCC".to_string(),
+            " eth ".to_string(),
+            "ETHH".to_string(),
+            "BTC".to_string(),
+            "kPEPE".to_string(),
+        ];
+
+        let universe = HyperliquidPerpUniverse::from_config(&test_frontdoor_config());
+        let (known, warning) =
+            validate_symbols_against_universe(&symbols, &universe, HyperliquidNetwork::Mainnet);
+        assert_eq!(known, vec!["BTC", "ETH", "KPEPE"]);
+        let warning = warning.expect("dropped symbols warning");
+        assert!(warning.contains("mainnet"), "{warning}");
+        assert!(warning.contains("BTCC, ETHH"), "{warning}");
+
+        let (known, warning) = validate_symbols_against_universe(
+            &["BTC".to_string()],
+            &universe,
+            HyperliquidNetwork::Testnet,
+        );
+        assert_eq!(known, vec!["BTC"]);
+        assert!(warning.is_none());
+
+        let pinned = HyperliquidPerpUniverse::from_config(&FrontdoorConfig {
+            hyperliquid_testnet_perps: Some(vec!["ETH".to_string()]),
+            ..test_frontdoor_config()
+        });
+        let (known, warning) = validate_symbols_against_universe(
+            &["BTC".to_string()],
+            &pinned,
+            HyperliquidNetwork::Testnet,
+        );
+        assert!(known.is_empty());
+        assert!(
+            warning
+                .expect("dropped symbols warning")
+                .contains("testnet")
+        );
+    }
+
+    #[test]
+    fn meta_universe_skips_delisted_perps() {
+        let meta = serde_json::json!({
+            "universe": [
+                {"name": "BTC", "szDecimals": 5, "maxLeverage": 40},
+                {"name": "kPEPE", "szDecimals": 0, "maxLeverage": 10},
+                {"name": "MATIC", "szDecimals": 1, "maxLeverage": 20, "isDelisted": true},
+            ]
+        });
+        assert_eq!(
+            parse_meta_universe(&meta).expect("universe"),
+            vec!["BTC".to_string(), "KPEPE".to_string()]
+        );
+        assert!(parse_meta_universe(&serde_json::json!({"universe": []})).is_err());
+        assert!(parse_meta_universe(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn suggest_config_surfaces_dropped_symbols_and_falls_back_to_majors() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let suggest = |intent: &str| {
+            service
+                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    intent: intent.to_string(),
+                    domain: Some("hyperliquid".to_string()),
+                    gateway_auth_key: Some("supersecuregatewaykey01".to_string()),
+                    base_config: None,
+                })
+                .expect("suggest config")
+        };
+
+        let mixed = suggest("paper trade BTCC ETHH and solana");
+        assert!(mixed.validated);
+        assert_eq!(mixed.config.symbol_allowlist, vec!["SOL".to_string()]);
+        assert!(
+            mixed
+                .warnings
+                .iter()
+                .any(|warning| warning.contains("BTCC, ETHH")),
+            "{:?}",
+            mixed.warnings
+        );
+        assert_eq!(mixed.config.inference_warnings, mixed.warnings);
+
+        let bogus = suggest("paper trade BTCC and ETHH only");
+        assert!(bogus.validated);
+        assert_eq!(
+            bogus.config.symbol_allowlist,
+            vec!["BTC".to_string(), "ETH".to_string()]
+        );
+        assert!(
+            bogus
+                .assumptions
+                .iter()
+                .any(|assumption| assumption.contains("defaulted to BTC and ETH"))
+        );
+    }
+
+    #[test]
+    fn default_symbol_allowlist_is_shared_by_every_default() {
+        let majors = vec!["BTC".to_string(), "ETH".to_string()];
+        assert_eq!(
+            default_symbol_allowlist(HyperliquidNetwork::Mainnet),
+            majors
+        );
+        assert_eq!(
+            default_symbol_allowlist(HyperliquidNetwork::Testnet),
+            majors
+        );
+
+        let wallet = "0x9431cf5da0ce60664661341db650763b08286b18";
+        let config = default_frontdoor_user_config(wallet, None, "general", &ThreadRngSource);
+        assert_eq!(config.hyperliquid_network, "testnet");
+        assert_eq!(
+            config.symbol_allowlist,
+            default_symbol_allowlist(HyperliquidNetwork::Testnet)
+        );
+
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        assert_eq!(
+            service.config_contract().defaults.symbol_allowlist,
+            default_symbol_allowlist(HyperliquidNetwork::Testnet)
+        );
+
+        for (network, name) in [
+            (HyperliquidNetwork::Mainnet, "mainnet"),
+            (HyperliquidNetwork::Testnet, "testnet"),
+        ] {
+            let mut config = sample_user_config(wallet);
+            config.hyperliquid_network = name.to_string();
+            config.paper_live_policy = "paper_only".to_string();
+            config.symbol_allowlist = vec!["BTCC".to_string()];
+            let mut assumptions = Vec::new();
+            let mut warnings = Vec::new();
+            normalize_suggested_config(
+                &mut config,
+                wallet,
+                &ThreadRngSource,
+                &HyperliquidPerpUniverse::from_config(&test_frontdoor_config()),
+                &mut assumptions,
+                &mut warnings,
+            );
+            assert_eq!(config.symbol_allowlist, default_symbol_allowlist(network));
+        }
+    }
+
+    #[test]
+    fn force_signed_receipts_overrides_user_opt_out_only_when_enabled() {
+        for force_signed_receipts in [true, false] {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    force_signed_receipts,
+                    ..test_frontdoor_config()
+                },
+                tmp.path().join("wallet_sessions.json"),
+            );
+            let wallet = "0x9431cf5da0ce60664661341db650763b08286b18";
+            let mut base_config = sample_user_config(wallet);
+            base_config.verification_fallback_require_signed_receipts = false;
+
+            let suggested = service
+                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                    wallet_address: wallet.to_string(),
+                    intent: String::new(),
+                    domain: Some("general".to_string()),
+                    gateway_auth_key: None,
+                    base_config: Some(base_config),
+                })
+                .expect("suggest config");
+
+            assert_eq!(
+                suggested
+                    .config
+                    .verification_fallback_require_signed_receipts,
+                force_signed_receipts
+            );
+            assert_eq!(
+                suggested
+                    .assumptions
+                    .iter()
+                    .any(|note| note.contains("Platform policy forces signed fallback receipts")),
+                force_signed_receipts
+            );
+        }
+    }
+
+    #[test]
+    fn verify_forces_signed_receipts_on_opted_out_config_only_when_enabled() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            for force_signed_receipts in [true, false] {
+                let provisioning =
+                    SimulatedProvisioning::ready("https://simulated.example/gateway");
+                let config = FrontdoorConfig {
+                    force_signed_receipts,
+                    ..provisioning.frontdoor_config()
+                };
+                let harness = FrontdoorTestHarness::with_config(provisioning, config);
+                let challenge = harness.challenge().await;
+                let mut opted_out = sample_user_config(&harness.wallet);
+                opted_out.verification_fallback_require_signed_receipts = false;
+                harness
+                    .verify_with_config(&challenge, opted_out)
+                    .await
+                    .expect("verify");
+
+                let state = harness.service.state.read().await;
+                let session = state
+                    .sessions
+                    .get(&harness.session_uuid(&challenge))
+                    .expect("session");
+                let stored = session.config.as_ref().expect("stored config");
+                assert_eq!(
+                    stored.verification_fallback_require_signed_receipts,
+                    force_signed_receipts
+                );
+                assert_eq!(
+                    session
+                        .timeline
+                        .iter()
+                        .any(|event| event.event_type == "platform_policy_override"),
+                    force_signed_receipts
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn suggest_config_enforces_connected_wallet_for_user_and_dual_custody() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let mismatched_wallet = "0x8ba1f109551bD432803012645Ac136ddd64DBA72";
+
+        let base_user_wallet = sample_user_config(mismatched_wallet);
+        let user_wallet_err = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: connected_wallet.to_string(),
+                intent: "use my own wallet".to_string(),
+                domain: Some("hyperliquid".to_string()),
+                gateway_auth_key: None,
+                base_config: Some(base_user_wallet),
+            })
+            .expect_err("mismatched user_wallet should fail");
+        assert_eq!(
+            user_wallet_err.to_string(),
+            "user_wallet_address must match the connected wallet for user_wallet/dual_mode"
+        );
+
+        let mut base_dual_mode = sample_user_config(mismatched_wallet);
+        base_dual_mode.custody_mode = "dual_mode".to_string();
+        base_dual_mode.operator_wallet_address =
+            Some("0x1111111111111111111111111111111111111111".to_string());
+
+        let dual_mode_err = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: connected_wallet.to_string(),
+                intent: "dual mode".to_string(),
+                domain: Some("hyperliquid".to_string()),
+                gateway_auth_key: None,
+                base_config: Some(base_dual_mode),
+            })
+            .expect_err("mismatched dual_mode user wallet should fail");
+        assert_eq!(
+            dual_mode_err.to_string(),
+            "connected wallet must match operator_wallet_address or user_wallet_address for dual_mode"
+        );
+    }
+
+    #[test]
+    fn operator_and_dual_custody_require_operator_wallet_in_validation() {
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+
+        let mut operator_wallet_mode = sample_user_config(connected_wallet);
+        operator_wallet_mode.custody_mode = "operator_wallet".to_string();
+        operator_wallet_mode.operator_wallet_address = None;
+        let operator_wallet_err = validate_user_config(&operator_wallet_mode)
+            .expect_err("operator_wallet must require operator wallet");
+        assert_eq!(
+            operator_wallet_err.to_string(),
+            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode"
+        );
+
+        let mut dual_mode = sample_user_config(connected_wallet);
+        dual_mode.custody_mode = "dual_mode".to_string();
+        dual_mode.operator_wallet_address = None;
+        let dual_mode_err =
+            validate_user_config(&dual_mode).expect_err("dual_mode must require operator wallet");
+        assert_eq!(
+            dual_mode_err.to_string(),
+            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode"
+        );
+    }
+
+    #[test]
+    fn suggest_config_normalizes_missing_operator_wallet_in_operator_or_dual_modes() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        for intent in ["operator wallet", "dual mode"] {
+            let mut base = sample_user_config(connected_wallet);
+            base.operator_wallet_address = None;
+
+            let suggested = service
+                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                    wallet_address: connected_wallet.to_string(),
+                    intent: intent.to_string(),
+                    domain: Some("hyperliquid".to_string()),
+                    gateway_auth_key: None,
+                    base_config: Some(base),
+                })
+                .expect("suggest config should normalize missing operator wallet");
+
+            assert_eq!(suggested.config.custody_mode, "user_wallet");
+            assert!(
+                suggested
+                    .assumptions
+                    .iter()
+                    .any(|assumption| assumption
+                        == "Operator wallet missing; normalized custody_mode to user_wallet for safe launch.")
+            );
+            let normalized_wallet = normalize_wallet_address(
+                suggested
+                    .config
+                    .user_wallet_address
+                    .as_deref()
+                    .expect("suggested config must include user wallet"),
+            )
+            .expect("suggested wallet must be valid");
+            assert_eq!(
+                normalized_wallet,
+                "0x9431cf5da0ce60664661341db650763b08286b18"
+            );
+        }
+    }
+
+    #[test]
+    fn suggest_config_caps_position_size_at_allocation() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            SimulatedProvisioning::ready("https://shared.example.com").frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let suggest = |base: FrontdoorUserConfig| {
+            service
+                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                    wallet_address: connected_wallet.to_string(),
+                    intent: "trade majors".to_string(),
+                    domain: Some("hyperliquid".to_string()),
+                    gateway_auth_key: None,
+                    base_config: Some(base),
+                })
+                .expect("suggest config")
+        };
+
+        let mut oversized = sample_user_config(connected_wallet);
+        oversized.max_position_size_usd = 50_000;
+        oversized.max_allocation_usd = 2_000;
+        let suggested = suggest(oversized);
+        assert_eq!(suggested.config.max_position_size_usd, 2_000);
+        assert_eq!(suggested.config.max_allocation_usd, 2_000);
+        assert!(
+            suggested
+                .assumptions
+                .iter()
+                .any(|a| a == "Capped max_position_size_usd at max_allocation_usd (2000).")
+        );
+        let mut accepted = suggested.config.clone();
+        stamp_terms_acceptance(&mut accepted, Utc::now());
+        assert!(validate_user_config(&accepted).is_ok());
+
+        let sane = sample_user_config(connected_wallet);
+        let suggested = suggest(sane.clone());
+        assert_eq!(
+            suggested.config.max_position_size_usd,
+            sane.max_position_size_usd
+        );
+        assert_eq!(suggested.config.max_allocation_usd, sane.max_allocation_usd);
+        assert_eq!(
+            suggested.config.per_trade_notional_cap_usd,
+            sane.per_trade_notional_cap_usd
+        );
+        assert!(
+            !suggested
+                .assumptions
+                .iter()
+                .any(|a| a.starts_with("Capped max_position_size_usd"))
+        );
+
+        let mut inconsistent = sample_user_config(connected_wallet);
+        inconsistent.max_position_size_usd = 50_000;
+        let errors = validate_user_config(&inconsistent).expect_err("position above allocation");
+        assert_eq!(
+            errors.fields().collect::<Vec<_>>(),
+            vec!["max_position_size_usd"]
+        );
+    }
+
+    #[test]
+    fn suggest_config_normalizes_hyperliquid_fields_for_non_hyperliquid_domains() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let mut base = sample_user_config(connected_wallet);
+        base.hyperliquid_network = "mainnet".to_string();
+        base.paper_live_policy = "live_allowed".to_string();
+        base.symbol_allowlist = vec!["BTC".to_string()];
+        base.symbol_denylist = vec!["ETH".to_string()];
+
+        let suggested = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: connected_wallet.to_string(),
+                intent: "mainnet live trading with btc-only".to_string(),
+                domain: Some("general".to_string()),
+                gateway_auth_key: None,
+                base_config: Some(base),
+            })
+            .expect("suggest config");
+
+        assert!(suggested.validated);
+        assert_eq!(suggested.config.profile_domain, "general");
+        assert_eq!(suggested.config.hyperliquid_network, "testnet");
+        assert_eq!(suggested.config.paper_live_policy, "paper_only");
+        assert!(suggested.config.symbol_allowlist.is_empty());
+        assert!(suggested.config.symbol_denylist.is_empty());
+    }
+
+    #[test]
+    fn suggest_config_keeps_addon_domains_explicit() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let general_suggested = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: connected_wallet.to_string(),
+                intent: "hyperliquid eigenda mainnet workflow".to_string(),
+                domain: None,
+                gateway_auth_key: None,
+                base_config: None,
+            })
+            .expect("general suggest config");
+        assert_eq!(general_suggested.config.profile_domain, "general");
+
+        let eigenda_suggested = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: connected_wallet.to_string(),
+                intent: "receipt continuity".to_string(),
+                domain: Some("eigenda".to_string()),
+                gateway_auth_key: None,
+                base_config: None,
+            })
+            .expect("eigenda suggest config");
+        assert_eq!(eigenda_suggested.config.profile_domain, "eigenda");
+    }
+
+    #[test]
+    fn domain_profiles_keep_addon_modules_explicit() {
+        let profiles = frontdoor_domain_profiles();
+        let general = profiles
+            .iter()
+            .find(|profile| profile.domain == "general")
+            .expect("general profile");
+        let hyperliquid = profiles
+            .iter()
+            .find(|profile| profile.domain == "hyperliquid")
+            .expect("hyperliquid profile");
+        let eigenda = profiles
+            .iter()
+            .find(|profile| profile.domain == "eigenda")
+            .expect("eigenda profile");
+
+        assert!(
+            !general
+                .default_modules
+                .iter()
+                .any(|module| module == "hyperliquid_addon" || module == "eigenda_addon")
+        );
+        assert!(
+            hyperliquid
+                .default_modules
+                .iter()
+                .any(|module| module == "hyperliquid_addon")
+        );
+        assert!(
+            !hyperliquid
+                .default_modules
+                .iter()
+                .any(|module| module == "eigenda_addon")
+        );
+        assert!(
+            eigenda
+                .default_modules
+                .iter()
+                .any(|module| module == "eigenda_addon")
+        );
+        assert!(
+            !eigenda
+                .default_modules
+                .iter()
+                .any(|module| module == "hyperliquid_addon")
+        );
+    }
+
+    #[test]
+    fn config_contract_reports_supported_versions() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let contract = service.config_contract();
+        assert_eq!(
+            contract.current_config_version,
+            FRONTDOOR_CURRENT_CONFIG_VERSION
+        );
+        assert!(
+            contract
+                .supported_config_versions
+                .contains(&FRONTDOOR_CURRENT_CONFIG_VERSION)
+        );
+        assert!(
+            contract
+                .mandatory_steps
+                .contains(&"configure_runtime_profile_and_risk".to_string())
+        );
+    }
+
+    #[test]
+    fn policy_template_library_exposes_common_objective_presets() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+
+        let library = service.policy_template_library();
+        assert!(!library.templates.is_empty());
+        assert!(
+            library
+                .templates
+                .iter()
+                .any(|template| template.template_id == "general_safe_baseline")
+        );
+        assert!(
+            library
+                .templates
+                .iter()
+                .any(|template| template.template_id == "hyperliquid_paper_operator")
+        );
+    }
+
+    #[test]
+    fn list_sessions_filters_by_wallet() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                test_frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+            );
+
+            let wallet_a = "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string();
+            let wallet_b = "0x8ba1f109551bD432803012645Ac136ddd64DBA72".to_string();
+
+            service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet_a.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge a");
+            service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet_b.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge b");
+
+            let (total, sessions) = service
+                .list_sessions(Some(&wallet_a), None, 0, 10)
+                .await
+                .expect("list sessions");
+            assert_eq!(total, 1);
+            assert_eq!(sessions.len(), 1);
+            assert_eq!(
+                sessions[0].wallet_address.to_ascii_lowercase(),
+                "0x9431cf5da0ce60664661341db650763b08286b18"
+            );
+            assert!(address_checksum_matches(&sessions[0].wallet_address));
+            assert_eq!(sessions[0].provisioning_source, "unknown");
+        });
+    }
+
+    #[test]
+    fn session_ref_is_unique_per_wallet_and_stable() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                test_frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+            );
+
+            let wallet_a = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+            let wallet_b = "0x8ba1f109551bD432803012645Ac136ddd64DBA72";
+            for wallet in [wallet_a, wallet_b] {
+                service
+                    .create_challenge(FrontdoorChallengeRequest {
+                        wallet_address: wallet.to_string(),
+                        privy_user_id: None,
+                        chain_id: Some(1),
+                    })
+                    .await
+                    .expect("challenge");
+            }
+
+            let summary = |wallet: &'static str| {
+                let service = &service;
+                async move {
+                    let (_, sessions) = service
+                        .list_sessions(Some(wallet), None, 0, 10)
+                        .await
+                        .expect("list sessions");
+                    assert_eq!(sessions.len(), 1);
+                    sessions.into_iter().next().expect("session")
+                }
+            };
+            let a = summary(wallet_a).await;
+            let b = summary(wallet_b).await;
+            assert_eq!(a.version, 1);
+            assert_eq!(b.version, 1);
+            assert_ne!(a.session_ref, b.session_ref);
+            assert_eq!(a.session_ref.len(), 10);
+            assert!(a.session_ref.chars().all(|c| c.is_ascii_hexdigit()));
+            assert_eq!(summary(wallet_a).await.session_ref, a.session_ref);
+
+            let state = service.state.read().await;
+            let session = state
+                .sessions
+                .values()
+                .find(|session| session.wallet_address.eq_ignore_ascii_case(wallet_a))
+                .expect("session a");
+            assert_eq!(public_session_ref(session), a.session_ref);
+            let mut rechecksummed = session.clone();
+            rechecksummed.wallet_address = wallet_a.to_string();
+            assert_eq!(public_session_ref(&rechecksummed), a.session_ref);
+        });
+    }
+
+    #[test]
+    fn list_sessions_filters_by_status_and_pages() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                SimulatedProvisioning::ready("https://shared.example.com").frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+            );
+            let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+            let mut ids = Vec::new();
+            for _ in 0..5 {
+                let challenge = service
+                    .create_challenge(FrontdoorChallengeRequest {
+                        wallet_address: wallet.to_string(),
+                        privy_user_id: None,
+                        chain_id: Some(1),
+                    })
+                    .await
+                    .expect("challenge");
+                ids.push(Uuid::parse_str(&challenge.session_id).expect("session id"));
+            }
+            let expected: Vec<String> = {
+                let mut state = service.state.write().await;
+                let now = Utc::now();
+                let statuses = [
+                    SessionStatus::Failed,
+                    SessionStatus::Ready,
+                    SessionStatus::Failed,
+                    SessionStatus::AwaitingSignature,
+                    SessionStatus::Failed,
+                ];
+                for (offset, (id, status)) in ids.iter().zip(statuses).enumerate() {
+                    let session = state.sessions.get_mut(id).expect("session");
+                    session.status = status;
+                    session.updated_at = now - chrono::Duration::seconds(offset as i64);
+                }
+                [ids[0], ids[2], ids[4]]
+                    .iter()
+                    .map(|id| public_session_ref(&state.sessions[id]))
+                    .collect()
+            };
+
+            let (total, failed) = service
+                .list_sessions(Some(wallet), Some("FAILED"), 0, 10)
+                .await
+                .expect("failed sessions");
+            assert_eq!(total, 3);
+            let refs: Vec<String> = failed.iter().map(|s| s.session_ref.clone()).collect();
+            assert_eq!(refs, expected);
+            assert!(failed.iter().all(|s| s.status == "failed"));
+
+            let (total, page) = service
+                .list_sessions(Some(wallet), Some("failed"), 1, 1)
+                .await
+                .expect("second page");
+            assert_eq!(total, 3);
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].session_ref, expected[1]);
+
+            let (total, all) = service
+                .list_sessions(Some(wallet), None, 4, 10)
+                .await
+                .expect("all sessions");
+            assert_eq!(total, 5);
+            assert_eq!(all.len(), 1);
+
+            let err = service
+                .list_sessions(Some(wallet), Some("stuck"), 0, 10)
+                .await
+                .expect_err("unknown status");
+            assert!(matches!(err, FrontdoorError::InvalidRequest(_)), "{err:?}");
+        });
+    }
+
+    #[test]
+    fn sessions_needing_attention_orders_by_severity_then_recency() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                test_frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+            );
+
+            let mut ids = Vec::new();
+            for _ in 0..7 {
+                let challenge = service
+                    .create_challenge(FrontdoorChallengeRequest {
+                        wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                        privy_user_id: None,
+                        chain_id: Some(1),
+                    })
+                    .await
+                    .expect("challenge");
+                ids.push(Uuid::parse_str(&challenge.session_id).expect("session id"));
+            }
+
+            let now = Utc::now();
+            {
+                let mut state = service.state.write().await;
+                let mut seed = |id: Uuid,
+                                status: SessionStatus,
+                                source: ProvisioningSource,
+                                started_mins_ago: Option<i64>,
+                                updated_mins_ago: i64| {
+                    let session = state.sessions.get_mut(&id).expect("seeded session");
+                    session.status = status;
+                    session.provisioning_source = source;
+                    session.provisioning_started_at =
+                        started_mins_ago.map(|mins| now - chrono::Duration::minutes(mins));
+                    session.updated_at = now - chrono::Duration::minutes(updated_mins_ago);
+                };
+                // Healthy: awaiting signature, fresh provisioning, fully ready.
+                seed(
+                    ids[0],
+                    SessionStatus::AwaitingSignature,
+                    ProvisioningSource::Unknown,
+                    None,
+                    0,
+                );
+                seed(
+                    ids[1],
+                    SessionStatus::Provisioning,
+                    ProvisioningSource::Unknown,
+                    Some(1),
+                    0,
+                );
+                seed(
+                    ids[2],
+                    SessionStatus::Ready,
+                    ProvisioningSource::Command,
+                    None,
+                    0,
+                );
+                // Problematic: blocked ready, stalled provisioning, two failures.
+                seed(
+                    ids[3],
+                    SessionStatus::Ready,
+                    ProvisioningSource::DefaultInstanceUrl,
+                    None,
+                    1,
+                );
+                seed(
+                    ids[4],
+                    SessionStatus::Provisioning,
+                    ProvisioningSource::Unknown,
+                    Some(60),
+                    30,
+                );
+                seed(
+                    ids[5],
+                    SessionStatus::Failed,
+                    ProvisioningSource::Command,
+                    None,
+                    20,
+                );
+                seed(
+                    ids[6],
+                    SessionStatus::Failed,
+                    ProvisioningSource::Command,
+                    None,
+                    5,
+                );
+                state
+                    .sessions
+                    .get_mut(&ids[2])
+                    .expect("ready session")
+                    .funding_preflight
+                    .status = "passed".to_string();
+            }
+
+            let flagged = service.sessions_needing_attention(10).await;
+            let statuses: Vec<&str> = flagged.iter().map(|s| s.status.as_str()).collect();
+            assert_eq!(statuses, vec!["failed", "failed", "provisioning", "ready"]);
+            assert!(flagged[0].updated_at > flagged[1].updated_at);
+            assert_eq!(flagged[3].provisioning_source, "default_instance_url");
+
+            let capped = service.sessions_needing_attention(2).await;
+            assert_eq!(capped.len(), 2);
+            assert!(capped.iter().all(|s| s.status == "failed"));
+        });
+    }
+
+    #[test]
+    fn onboarding_transcript_reads_never_observe_partial_writes() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let session = rt.block_on(async {
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            service
+                .state
+                .read()
+                .await
+                .sessions
+                .get(&session_id)
+                .cloned()
+                .expect("session")
+        });
+        let path = onboarding_transcript_path(&service.store_path, session.id);
+
+        let writer_service = Arc::clone(&service);
+        let writer = std::thread::spawn(move || {
+            let mut session = session;
+            for turn in 0..200 {
+                session.onboarding.transcript.push(OnboardingTurnState {
+                    role: "user".to_string(),
+                    message: format!("turn {turn} {}", "x".repeat(turn * 16)),
+                    created_at: Utc::now(),
+                });
+                writer_service
+                    .persist_onboarding_transcript(&session)
+                    .expect("persist transcript");
+            }
+        });
+
+        let mut reads = 0usize;
+        while !writer.is_finished() {
+            let data = std::fs::read(&path).expect("artifact exists after challenge");
+            serde_json::from_slice::<FrontdoorOnboardingTranscriptArtifactResponse>(&data)
+                .expect("artifact is always complete JSON");
+            reads += 1;
+        }
+        writer.join().expect("writer thread");
+        assert!(reads > 0);
+
+        let leftovers: Vec<_> = std::fs::read_dir(path.parent().expect("artifact dir"))
+            .expect("read artifact dir")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+
+        let missing = rt.block_on(service.onboarding_transcript(Uuid::new_v4()));
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn wallet_store_compaction_prunes_stale_records() {
+        let tmp = tempdir().expect("tempdir");
+        let store_path = tmp.path().join("wallet_sessions.json");
+        let now = Utc::now();
+        let record = |age_days: i64| WalletSessionRecord {
+            version: 3,
+            last_instance_url: "https://session.example/gateway".to_string(),
+            last_profile_name: "demo_profile".to_string(),
+            updated_at: (now - chrono::Duration::days(age_days)).to_rfc3339(),
+        };
+        let stale_wallet = "0x9431cf5da0ce60664661341db650763b08286b18".to_string();
+        let recent_wallet = "0x8ba1f109551bd432803012645ac136ddd64dba72".to_string();
+        persist_wallet_store(
+            &store_path,
+            &WalletSessionStore {
+                wallets: HashMap::from([
+                    (stale_wallet.clone(), record(120)),
+                    (recent_wallet.clone(), record(2)),
+                ]),
+            },
+        )
+        .expect("seed store");
+
+        let service = FrontdoorService::new_for_tests(
+            FrontdoorConfig {
+                wallet_retention_secs: 30 * 24 * 3600,
+                ..test_frontdoor_config()
+            },
+            store_path.clone(),
+        );
+
+        let on_disk = load_wallet_store(&store_path).expect("compacted store");
+        assert!(!on_disk.wallets.contains_key(&stale_wallet));
+        assert!(on_disk.wallets.contains_key(&recent_wallet));
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            assert!(
+                service
+                    .state
+                    .read()
+                    .await
+                    .wallets
+                    .contains_key(&recent_wallet)
+            );
+            assert_eq!(service.compact_wallet_store().await.expect("compact"), 0);
+
+            service
+                .state
+                .write()
+                .await
+                .wallets
+                .insert(stale_wallet.clone(), record(45));
+            assert_eq!(service.compact_wallet_store().await.expect("compact"), 1);
+            let state = service.state.read().await;
+            assert!(!state.wallets.contains_key(&stale_wallet));
+            assert!(state.wallets.contains_key(&recent_wallet));
+        });
+    }
+
+    #[test]
+    fn oversized_wallet_retention_keeps_every_record() {
+        let now = Utc::now();
+        let mut wallets = HashMap::from([(
+            "0x9431cf5da0ce60664661341db650763b08286b18".to_string(),
+            WalletSessionRecord {
+                version: 1,
+                last_instance_url: "https://session.example/gateway".to_string(),
+                last_profile_name: "demo_profile".to_string(),
+                updated_at: (now - chrono::Duration::days(3650)).to_rfc3339(),
+            },
+        )]);
+        for retention_secs in [8_000_000_000_000, 10_000_000_000_000_000, u64::MAX] {
+            assert_eq!(prune_wallet_records(&mut wallets, retention_secs, now), 0);
+        }
+        assert_eq!(wallets.len(), 1);
+    }
+
+    #[test]
+    fn experience_manifest_includes_state_inputs() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            test_frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let manifest = service.experience_manifest();
+        assert_eq!(manifest.manifest_version, 1);
+        assert!(manifest.steps.len() >= 4);
+        assert!(
+            manifest
+                .steps
+                .iter()
+                .all(|step| !step.state_inputs.is_empty())
+        );
+        assert!(
+            manifest
+                .steps
+                .iter()
+                .any(|step| step.step_id == "verification"
+                    && step.state_inputs.iter().any(|field| field == "signature"))
+        );
+    }
+
+    #[test]
+    fn onboarding_timeline_runtime_todos_and_preflight_are_deterministic() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    default_instance_url: Some("https://session.example/gateway".to_string()),
+                    allow_default_instance_fallback: true,
+                    verify_app_base_url: Some(
+                        "https://verify-sepolia.eigencloud.xyz/app".to_string(),
+                    ),
+                    poll_interval_ms: 100,
+                    ..test_frontdoor_config()
+                },
+                tmp.path().join("wallet_sessions.json"),
+            );
+
+            let private_key = hex::decode(
+                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            )
+            .expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
+
+            let onboarding = service
+                .onboarding_state(session_uuid)
+                .await
+                .expect("onboarding");
+            assert_eq!(onboarding.current_step, "capture_objective");
+            assert_eq!(onboarding.transcript.len(), 1);
+
+            let empty_chat = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "   ".to_string(),
+                })
+                .await;
+            assert!(empty_chat.is_err());
+
+            let chat = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "Launch conservative strategy profile".to_string(),
+                })
+                .await
+                .expect("chat");
+            assert_eq!(chat.state.current_step, "propose_plan");
+            assert!(!chat.state.completed);
+
+            let confirm_plan = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "confirm plan".to_string(),
+                })
+                .await
+                .expect("confirm plan");
+            assert_eq!(confirm_plan.state.current_step, "collect_required_variables");
+            assert!(!confirm_plan.state.missing_fields.is_empty());
+
+            let fill_required = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "profile_name=demo_profile, gateway_auth_key=__from_config__, accept_terms=true".to_string(),
+                })
+                .await
+                .expect("fill required");
+            assert_eq!(fill_required.state.current_step, "confirm_and_sign");
+            assert!(fill_required.state.missing_fields.is_empty());
+
+            let confirm_sign = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "confirm sign".to_string(),
+                })
+                .await
+                .expect("confirm sign");
+            assert_eq!(confirm_sign.state.current_step, "ready_to_sign");
+            assert!(confirm_sign.state.completed);
+
+            let timeline = service
+                .session_timeline(session_uuid)
+                .await
+                .expect("timeline");
+            assert!(timeline.events.len() >= 3);
+            let mut prev = 0u64;
+            for event in &timeline.events {
+                assert!(event.seq_id > prev);
+                prev = event.seq_id;
+            }
+
+            let prehash = eip191_personal_sign_hash(&challenge.message);
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            let signature = hex::encode(&sig_bytes, true);
+
+            service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature,
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
+                })
+                .await
+                .expect("verify and start");
+
+            let verification = service
+                .verification_explanation(session_uuid)
+                .await
+                .expect("verification explanation");
+            assert_eq!(verification.backend, "eigencloud_primary");
+            assert_eq!(verification.level, "primary_plus_signed_fallback");
+
+            let preflight = service
+                .funding_preflight(session_uuid)
+                .await
+                .expect("funding preflight");
+            assert_eq!(preflight.status, "passed");
+            assert!(
+                preflight
+                    .checks
+                    .iter()
+                    .all(|check| !check.check_id.is_empty() && !check.status.is_empty())
+            );
+
+            let invalid_runtime = service
+                .runtime_control(
+                    session_uuid,
+                    FrontdoorRuntimeControlRequest {
+                        action: "invalid".to_string(),
+                        actor: None,
+                    },
+                )
+                .await;
+            assert!(invalid_runtime.is_err());
+
+            let runtime = service
+                .runtime_control(
+                    session_uuid,
+                    FrontdoorRuntimeControlRequest {
+                        action: "pause".to_string(),
+                        actor: Some("operator".to_string()),
+                    },
+                )
+                .await
+                .expect("runtime control");
+            assert!(runtime.status == "applied" || runtime.status == "noop");
+            assert_eq!(runtime.runtime_state, "paused");
+
+            let todos = service
+                .gateway_todos_for_session(session_uuid)
+                .await
+                .expect("gateway todos");
+            assert!(!todos.todos.is_empty());
+            assert!(
+                todos
+                    .todos
+                    .iter()
+                    .all(|todo| !todo.todo_id.is_empty() && !todo.owner.is_empty())
+            );
+
+            let session_payload = service.get_session(session_uuid).await.expect("session");
+            let json = serde_json::to_value(session_payload).expect("session json");
+            let obj = json.as_object().expect("object");
+            assert!(obj.contains_key("launched_on_eigencloud"));
+            assert!(obj.contains_key("verification_backend"));
+            assert!(obj.contains_key("verification_level"));
+            assert!(obj.contains_key("verification_fallback_enabled"));
+            assert!(obj.contains_key("verification_fallback_require_signed_receipts"));
+        });
+    }
+
+    #[test]
+    fn operator_vs_public_monitor_payloads_are_separated() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                test_frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+            );
+
+            let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string();
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+
+            let (public_total, public_sessions) = service
+                .list_sessions(Some(&wallet), None, 0, 10)
+                .await
+                .expect("public sessions");
+            assert_eq!(public_total, 1);
+            assert_eq!(public_sessions.len(), 1);
+            assert_eq!(public_sessions[0].session_ref.len(), 10);
+            assert!(
+                !challenge
+                    .session_id
+                    .contains(&public_sessions[0].session_ref)
+            );
+            assert_eq!(public_sessions[0].verification_backend, "unknown");
+            assert_eq!(public_sessions[0].verification_level, "unknown");
+            assert!(!public_sessions[0].verification_fallback_enabled);
+            assert!(!public_sessions[0].verification_fallback_require_signed_receipts);
+            assert!(!public_sessions[0].launched_on_eigencloud);
+
+            let public_json = serde_json::to_value(&public_sessions[0]).expect("public json");
+            let public_obj = public_json.as_object().expect("public object");
+            assert!(!public_obj.contains_key("session_id"));
+            assert!(!public_obj.contains_key("instance_url"));
+            assert!(!public_obj.contains_key("app_url"));
+            assert!(!public_obj.contains_key("verify_url"));
+            assert!(!public_obj.contains_key("eigen_app_id"));
+            assert!(!public_obj.contains_key("error"));
+
+            let (operator_total, operator_sessions) = service
+                .list_sessions_full(Some(&wallet), 10)
+                .await
+                .expect("operator sessions");
+            assert_eq!(operator_total, 1);
+            assert_eq!(operator_sessions.len(), 1);
+            assert_eq!(operator_sessions[0].session_id, challenge.session_id);
+            assert_eq!(operator_sessions[0].verification_backend, "unknown");
+            assert_eq!(operator_sessions[0].verification_level, "unknown");
+
+            let operator_json = serde_json::to_value(&operator_sessions[0]).expect("operator json");
+            let operator_obj = operator_json.as_object().expect("operator object");
+            assert!(operator_obj.contains_key("session_id"));
+            assert!(operator_obj.contains_key("launched_on_eigencloud"));
+            assert!(operator_obj.contains_key("verification_backend"));
+            assert!(operator_obj.contains_key("verification_level"));
+            assert!(operator_obj.contains_key("verification_fallback_enabled"));
+            assert!(operator_obj.contains_key("verification_fallback_require_signed_receipts"));
+        });
+    }
+
+    #[test]
+    fn onboarding_gateway_auth_marker_counts_as_resolved_requirement() {
+        let mut captured = HashMap::new();
+        captured.insert("profile_name".to_string(), "demo_profile".to_string());
+        captured.insert(
+            "gateway_auth_key".to_string(),
+            "__from_config__".to_string(),
+        );
+        captured.insert("accept_terms".to_string(), "true".to_string());
+
+        let payload = build_onboarding_step3_payload(&captured);
+        assert_eq!(payload.unresolved_required_count, 0);
+        let gateway = payload
+            .required_variables
+            .iter()
+            .find(|item| item.field == "gateway_auth_key")
+            .expect("gateway_auth_key required variable");
+        assert_eq!(gateway.status, "resolved");
+    }
+
+    #[test]
+    fn timeline_subscribers_receive_runtime_control_events() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let challenge = harness.challenge().await;
+            let session_uuid = harness.session_uuid(&challenge);
+            assert!(
+                harness
+                    .service
+                    .subscribe_timeline(Uuid::new_v4())
+                    .await
+                    .is_none()
+            );
+            let mut receiver = harness
+                .service
+                .subscribe_timeline(session_uuid)
+                .await
+                .expect("subscribe");
+
+            harness
+                .service
+                .runtime_control(
+                    session_uuid,
+                    FrontdoorRuntimeControlRequest {
+                        action: "pause".to_string(),
+                        actor: Some("operator".to_string()),
+                    },
+                )
+                .await
+                .expect("pause");
+
+            let event = receiver.try_recv().expect("runtime_control event");
+            assert_eq!(event.event_type, "runtime_control");
+            assert_eq!(event.actor, "operator");
+            assert!(event.detail.contains("action=pause"));
+            let snapshot = receiver.try_recv().expect("todo_snapshot event");
+            assert_eq!(snapshot.event_type, "todo_snapshot");
+            assert_eq!(snapshot.seq_id, event.seq_id + 1);
+
+            // History stays available to late subscribers via the timeline read.
+            let timeline = harness
+                .service
+                .session_timeline(session_uuid)
+                .await
+                .expect("timeline");
+            assert_eq!(
+                timeline.events.last().map(|event| event.seq_id),
+                Some(snapshot.seq_id)
+            );
+        });
+    }
+
+    #[test]
+    fn registered_preflight_check_failure_sets_category() {
+        struct RpcBalanceCheck;
+
+        #[async_trait]
+        impl FundingPreflightCheck for RpcBalanceCheck {
+            fn id(&self) -> &str {
+                "rpc_balance"
+            }
+
+            fn failure_category(&self) -> &str {
+                "balance"
+            }
+
+            async fn evaluate(
+                &self,
+                _cfg: &FrontdoorUserConfig,
+                wallet: &str,
+            ) -> FundingPreflightCheckState {
+                FundingPreflightCheckState {
+                    check_id: self.id().to_string(),
+                    status: "failed".to_string(),
+                    detail: format!("{wallet} holds no collateral"),
+                }
+            }
+        }
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            harness
+                .service
+                .register_funding_preflight_check(Arc::new(RpcBalanceCheck));
+
+            let challenge = harness.challenge().await;
+            let err = harness.verify(&challenge).await.expect_err("preflight");
+            assert_eq!(
+                err,
+                FrontdoorError::PreflightFailed("funding preflight failed: balance".to_string())
+            );
+
+            let preflight = harness
+                .service
+                .funding_preflight(harness.session_uuid(&challenge))
+                .await
+                .expect("funding preflight");
+            assert_eq!(preflight.status, "failed");
+            assert_eq!(preflight.failure_category.as_deref(), Some("balance"));
+            let custom = preflight
+                .checks
+                .iter()
+                .find(|check| check.check_id == "rpc_balance")
+                .expect("custom check reported");
+            assert_eq!(custom.status, "failed");
+            assert!(
+                preflight
+                    .checks
+                    .iter()
+                    .filter(|check| check.check_id != "rpc_balance")
+                    .all(|check| check.status == "passed")
+            );
+        });
+    }
+
+    #[test]
+    fn preflight_keeps_check_order_and_category_precedence() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let wallet = "0x9431cf5da0ce60664661341db650763b08286b18";
+            let mut config =
+                default_frontdoor_user_config(wallet, None, "general", &ThreadRngSource);
+            config.domain_overrides.insert(
+                FRONTDOOR_PREFLIGHT_OVERRIDE_KEY.to_string(),
+                serde_json::json!({ "gas_ready": false, "policy_ready": false }),
+            );
+
+            let preflight = evaluate_funding_preflight(
+                &default_funding_preflight_checks(),
+                wallet,
+                &config,
+                Utc::now(),
+            )
+            .await;
+            let ids: Vec<&str> = preflight
+                .checks
+                .iter()
+                .map(|check| check.check_id.as_str())
+                .collect();
+            assert_eq!(
+                ids,
+                [
+                    "wallet_binding",
+                    "auth_material",
+                    "gas_budget",
+                    "platform_fee",
+                    "policy"
+                ]
+            );
+            assert_eq!(preflight.failure_category.as_deref(), Some("policy"));
+        });
+    }
+
+    #[test]
+    fn stalled_preflight_check_times_out_as_failed() {
+        struct StalledCheck;
+
+        #[async_trait]
+        impl FundingPreflightCheck for StalledCheck {
+            fn id(&self) -> &str {
+                "rpc_balance"
+            }
+
+            fn failure_category(&self) -> &str {
+                "balance"
+            }
+
+            fn timeout(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(20)
+            }
+
+            async fn evaluate(
+                &self,
+                _cfg: &FrontdoorUserConfig,
+                _wallet: &str,
+            ) -> FundingPreflightCheckState {
+                std::future::pending().await
+            }
+        }
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            harness
+                .service
+                .register_funding_preflight_check(Arc::new(StalledCheck));
+
+            let challenge = harness.challenge().await;
+            let err = harness.verify(&challenge).await.expect_err("preflight");
+            assert_eq!(
+                err,
+                FrontdoorError::PreflightFailed("funding preflight failed: balance".to_string())
+            );
+
+            let preflight = harness
+                .service
+                .funding_preflight(harness.session_uuid(&challenge))
+                .await
+                .expect("funding preflight");
+            let stalled = preflight
+                .checks
+                .iter()
+                .find(|check| check.check_id == "rpc_balance")
+                .expect("stalled check reported");
+            assert_eq!(stalled.status, "failed");
+            assert!(stalled.detail.contains("timed out"), "{}", stalled.detail);
+        });
+    }
+
+    /// Accept webhook POSTs on a local port, answer `200 OK`, and forward
+    /// each JSON body to the returned receiver.
+    async fn spawn_webhook_receiver() -> (
+        String,
+        tokio::sync::mpsc::UnboundedReceiver<FrontdoorStatusWebhookPayload>,
+    ) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind webhook receiver");
+        let url = format!(
+            "http://{}/hooks/frontdoor",
+            listener.local_addr().expect("addr")
+        );
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 1024];
+                let body = loop {
+                    let read = stream.read(&mut buf).await.expect("read request");
+                    assert!(read > 0, "connection closed before the body arrived");
+                    raw.extend_from_slice(&buf[..read]);
+                    let text = String::from_utf8_lossy(&raw);
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .expect("content-length header");
+                    if body.len() >= length {
+                        break body.to_string();
+                    }
+                };
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await
+                    .expect("write response");
+                let _ = tx.send(serde_json::from_str(&body).expect("webhook payload"));
+            }
+        });
+        (url, rx)
+    }
+
+    #[test]
+    fn status_webhook_fires_once_when_session_is_ready() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let (url, mut webhooks) = spawn_webhook_receiver().await;
+            let provisioning = SimulatedProvisioning::ready("https://simulated.example/gateway");
+            let config = FrontdoorConfig {
+                status_webhook_url: Some(url),
+                ..provisioning.frontdoor_config()
+            };
+            let harness = FrontdoorTestHarness::with_config(provisioning, config);
+
+            let challenge = harness.challenge().await;
+            harness.verify(&challenge).await.expect("verify");
+            harness.wait_for_status(&challenge, "ready").await;
+
+            let payload = tokio::time::timeout(std::time::Duration::from_secs(5), webhooks.recv())
+                .await
+                .expect("webhook delivered")
+                .expect("receiver open");
+            assert_eq!(payload.session_id, challenge.session_id);
+            assert_eq!(payload.wallet_address, harness.wallet);
+            assert_eq!(payload.status, "ready");
+            assert_eq!(payload.detail, "Enclave is live. Redirect ready.");
+            assert_eq!(
+                payload.instance_url.as_deref(),
+                Some("https://simulated.example/gateway")
+            );
+
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            assert!(webhooks.try_recv().is_err(), "webhook fired more than once");
+        });
+    }
+
+    #[test]
+    fn harness_drives_onboarded_session_to_ready() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let started_at = harness.clock.now();
+
+            let challenge = harness.challenge().await;
+            for message in [
+                "Launch conservative strategy profile",
+                "confirm plan",
+                "profile_name=demo_profile, gateway_auth_key=__from_config__, accept_terms=true",
+                "confirm sign",
+            ] {
+                harness.clock.advance(chrono::Duration::seconds(5));
+                harness.chat(&challenge, message).await;
+            }
+            let onboarding = harness
+                .service
+                .onboarding_state(harness.session_uuid(&challenge))
+                .await
+                .expect("onboarding");
+            assert_eq!(onboarding.current_step, "ready_to_sign");
+            assert!(onboarding.completed);
+
+            harness.clock.advance(chrono::Duration::seconds(30));
+            let verified = harness.verify(&challenge).await.expect("verify");
+            assert_eq!(verified.status, "provisioning");
+
+            let ready = harness.wait_for_status(&challenge, "ready").await;
+            assert_eq!(
+                ready.wallet_address,
+                "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+            );
+            assert_eq!(
+                ready.instance_url.as_deref(),
+                Some("https://simulated.example/gateway")
+            );
+            assert_eq!(ready.provisioning_source, "default_instance_url");
+            assert_eq!(ready.profile_name.as_deref(), Some("demo_profile"));
+            assert_eq!(ready.created_at, started_at.to_rfc3339());
+            assert_eq!(
+                ready.updated_at,
+                (started_at + chrono::Duration::seconds(50)).to_rfc3339()
+            );
+            assert_eq!(
+                ready.expires_at,
+                (started_at + chrono::Duration::seconds(900)).to_rfc3339()
+            );
+            assert_eq!(ready.error, None);
+
+            let record = harness.wallet_record().await.expect("wallet record");
+            assert_eq!(
+                record.last_instance_url,
+                "https://simulated.example/gateway"
+            );
+            assert_eq!(record.last_profile_name, "demo_profile");
+        });
+    }
+
+    /// Clock that only moves when a test advances it.
+    struct MockClock {
+        now: std::sync::Mutex<DateTime<Utc>>,
+    }
+
+    impl MockClock {
+        fn new(start: DateTime<Utc>) -> Self {
+            Self {
+                now: std::sync::Mutex::new(start),
+            }
+        }
+
+        fn advance(&self, by: chrono::Duration) {
+            let mut now = self.now.lock().expect("mock clock lock");
+            *now += by;
+        }
+    }
+
+    impl FrontdoorClock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.lock().expect("mock clock lock")
+        }
+    }
+
+    #[test]
+    fn frontdoor_error_status_codes() {
+        assert_eq!(FrontdoorError::SessionNotFound.status_code(), 404);
+        assert_eq!(FrontdoorError::SignatureMismatch.status_code(), 401);
+        assert_eq!(FrontdoorError::InvalidWalletAddress.status_code(), 400);
+        assert_eq!(
+            FrontdoorError::RateLimited {
+                retry_after_secs: 5
+            }
+            .status_code(),
+            429
+        );
+        assert_eq!(
+            FrontdoorError::SessionNotFound.to_string(),
+            "session not found"
+        );
+        assert_eq!(
+            FrontdoorError::SignatureMismatch.to_string(),
+            "signed message does not match challenge"
+        );
+    }
+
+    #[test]
+    fn create_challenge_rate_limits_per_wallet_window() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let clock = Arc::new(MockClock::new(
+                DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                    .expect("start time")
+                    .with_timezone(&Utc),
+            ));
+            let mut config = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            config.challenge_rate_limit = 3;
+            config.challenge_rate_window_secs = 60;
+            let service = FrontdoorService::new_with_store_path(
+                config,
+                tmp.path().join("wallet_sessions.json"),
+                clock.clone(),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = |wallet: &str| {
+                service.create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+            };
+            let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+
+            for _ in 0..3 {
+                challenge(wallet).await.expect("challenge within limit");
+                clock.advance(chrono::Duration::seconds(10));
+            }
+            let err = challenge(wallet).await.expect_err("limit exceeded");
+            assert_eq!(
+                err,
+                FrontdoorError::RateLimited {
+                    retry_after_secs: 30
+                }
+            );
+            assert_eq!(
+                err.to_string(),
+                "too many challenges requested for this wallet; retry in 30s"
+            );
+            challenge("0x0000000000000000000000000000000000000001")
+                .await
+                .expect("other wallets are unaffected");
+
+            clock.advance(chrono::Duration::seconds(30));
+            challenge(wallet)
+                .await
+                .expect("oldest challenge left the window");
+            challenge(wallet).await.expect_err("window is full again");
+        });
+    }
+
+    #[test]
+    fn purge_uses_configured_terminal_retention() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let clock = Arc::new(MockClock::new(
+                DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                    .expect("start time")
+                    .with_timezone(&Utc),
+            ));
+            let mut config = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            config.session_ttl_secs = 60;
+            config.terminal_retention_secs = 120;
+            let service = FrontdoorService::new_with_store_path(
+                config,
+                tmp.path().join("wallet_sessions.json"),
+                clock.clone(),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = |wallet: &str| {
+                service.create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+            };
+
+            let stale = challenge("0x9431Cf5DA0CE60664661341db650763B08286B18")
+                .await
+                .expect("stale challenge");
+            let stale_id = Uuid::parse_str(&stale.session_id).expect("session id");
+
+            clock.advance(chrono::Duration::seconds(90));
+            let expired = service
+                .get_session(stale_id)
+                .await
+                .expect("still retained after expiry");
+            assert_eq!(expired.status, "expired");
+
+            clock.advance(chrono::Duration::seconds(100));
+            let fresh = challenge("0x0000000000000000000000000000000000000001")
+                .await
+                .expect("fresh challenge");
+            let fresh_id = Uuid::parse_str(&fresh.session_id).expect("session id");
+
+            assert!(service.get_session(stale_id).await.is_none());
+            assert!(service.get_session(fresh_id).await.is_some());
+        });
+    }
+
+    #[test]
+    fn oversized_challenge_rate_window_still_limits() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    challenge_rate_limit: 1,
+                    challenge_rate_window_secs: u64::MAX,
+                    ..test_frontdoor_config()
+                },
+                tmp.path().join("wallet_sessions.json"),
+            );
+            let challenge = || {
+                service.create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+            };
+            challenge().await.expect("first challenge");
+            let err = challenge().await.expect_err("window never lapses");
+            assert!(
+                matches!(err, FrontdoorError::RateLimited { retry_after_secs } if retry_after_secs > 0),
+                "{err:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn oversized_terminal_retention_does_not_panic_on_purge() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_for_tests(
+                FrontdoorConfig {
+                    session_ttl_secs: u64::MAX,
+                    terminal_retention_secs: u64::MAX,
+                    ..test_frontdoor_config()
+                },
+                tmp.path().join("wallet_sessions.json"),
+            );
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            assert!(service.get_session(session_id).await.is_some());
+            assert_eq!(
+                service.terminal_retention(),
+                chrono::Duration::seconds(FRONTDOOR_MAX_CONFIG_DURATION_SECS as i64)
+            );
+        });
+    }
+
+    #[test]
+    fn reaper_purges_expired_sessions_without_requests() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let clock = Arc::new(MockClock::new(
+                DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                    .expect("start time")
+                    .with_timezone(&Utc),
+            ));
+            let mut config = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            config.session_ttl_secs = 60;
+            config.terminal_retention_secs = 120;
+            let service = FrontdoorService::new_with_store_path(
+                config,
+                tmp.path().join("wallet_sessions.json"),
+                clock.clone(),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = |wallet: &str| {
+                service.create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+            };
+            let retained = |id: Uuid| {
+                let service = &service;
+                async move { service.state.read().await.sessions.contains_key(&id) }
+            };
+
+            let stale = challenge("0x9431Cf5DA0CE60664661341db650763B08286B18")
+                .await
+                .expect("stale challenge");
+            let stale_id = Uuid::parse_str(&stale.session_id).expect("session id");
+
+            service.spawn_reaper(&HeartbeatConfig {
+                interval_secs: 5,
+                ..HeartbeatConfig::default()
+            });
+            clock.advance(chrono::Duration::seconds(200));
+            assert!(retained(stale_id).await, "nothing purges before a tick");
+
+            tokio::time::sleep(std::time::Duration::from_secs(6)).await;
+            assert!(!retained(stale_id).await, "reaper purged the session");
+
+            service.shutdown();
+            let later = challenge("0x0000000000000000000000000000000000000001")
+                .await
+                .expect("later challenge");
+            let later_id = Uuid::parse_str(&later.session_id).expect("session id");
+            clock.advance(chrono::Duration::seconds(200));
+            tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            assert!(retained(later_id).await, "stopped reaper no longer purges");
+        });
+    }
+
+    /// Provisioning outcome served through the shared-runtime URL path, so no
+    /// provision command is spawned.
+    struct SimulatedProvisioning {
+        instance_url: String,
+        /// Provision attempts that fail before one succeeds.
+        transient_failures: usize,
+    }
+
+    impl SimulatedProvisioning {
+        fn ready(instance_url: &str) -> Self {
+            Self {
+                instance_url: instance_url.to_string(),
+                transient_failures: 0,
+            }
+        }
+
+        fn flaky(instance_url: &str, transient_failures: usize) -> Self {
+            Self {
+                instance_url: instance_url.to_string(),
+                transient_failures,
+            }
+        }
+
+        fn backend(&self, config: &FrontdoorConfig) -> Box<dyn ProvisioningBackend> {
+            if self.transient_failures == 0 {
+                return provisioning_backend_from_config(config);
+            }
+            Box::new(FlakyBackend {
+                instance_url: self.instance_url.clone(),
+                remaining_failures: std::sync::atomic::AtomicUsize::new(self.transient_failures),
+            })
+        }
+
+        fn frontdoor_config(&self) -> FrontdoorConfig {
+            FrontdoorConfig {
+                default_instance_url: Some(self.instance_url.clone()),
+                allow_default_instance_fallback: true,
+                verify_app_base_url: Some("https://verify-sepolia.eigencloud.xyz/app".to_string()),
+                poll_interval_ms: 100,
+                ..test_frontdoor_config()
+            }
+        }
+    }
+
+    /// Fails the first `remaining_failures` provisions, then hands out
+    /// `instance_url` like the default-instance backend.
+    struct FlakyBackend {
+        instance_url: String,
+        remaining_failures: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ProvisioningBackend for FlakyBackend {
+        fn source(&self) -> ProvisioningSource {
+            ProvisioningSource::DefaultInstanceUrl
+        }
+
+        async fn provision(
+            &self,
+            _input: &ProvisionCommandInput<'_>,
+            _logs: &dyn ProvisionLogSink,
+        ) -> Result<ProvisioningResult, String> {
+            use std::sync::atomic::Ordering;
+            let failing = self
+                .remaining_failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err("simulated transient provisioning failure".to_string());
+            }
+            provision_from_default_url(&Ok(Some(self.instance_url.clone())))
+        }
+    }
+
+    /// End-to-end frontdoor fixture: a service on a mock clock and simulated
+    /// provisioning, a scratch wallet store, and a test key for signing challenges.
+    struct FrontdoorTestHarness {
+        service: Arc<FrontdoorService>,
+        clock: Arc<MockClock>,
+        signing_key: SigningKey,
+        wallet: String,
+        _store_dir: tempfile::TempDir,
+    }
+
+    impl FrontdoorTestHarness {
+        const TEST_PRIVATE_KEY: &str =
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+        fn new(provisioning: SimulatedProvisioning) -> Self {
+            let config = provisioning.frontdoor_config();
+            Self::with_config(provisioning, config)
+        }
+
+        /// Like [`Self::new`], with `config` in place of the simulated one.
+        fn with_config(provisioning: SimulatedProvisioning, config: FrontdoorConfig) -> Self {
+            let store_dir = tempdir().expect("tempdir");
+            let clock = Arc::new(MockClock::new(
+                DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                    .expect("start time")
+                    .with_timezone(&Utc),
+            ));
+            let backend = provisioning.backend(&config);
+            let service = FrontdoorService::new_with_backend(
+                config,
+                store_dir.path().join("wallet_sessions.json"),
+                clock.clone(),
+                Arc::new(NoopMetrics),
+                Arc::new(ThreadRngSource),
+                backend,
+            );
+            let private_key = hex::decode(Self::TEST_PRIVATE_KEY).expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+            Self {
+                service,
+                clock,
+                signing_key,
+                wallet,
+                _store_dir: store_dir,
+            }
+        }
+
+        /// EIP-191 personal_sign of `message` with the harness key.
+        fn sign(&self, message: &str) -> String {
+            let prehash = eip191_personal_sign_hash(message);
+            let (sig, recid) = self
+                .signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            hex::encode(&sig_bytes, true)
+        }
+
+        /// EIP-712 signature over `challenge` with `expires_at` as the signed
+        /// expiry; fields the template leaves out are signed as empty strings.
+        fn sign_eip712(&self, challenge: &FrontdoorChallengeResponse, expires_at: &str) -> String {
+            let field = |name| challenge_message_field(&challenge.message, name).unwrap_or("");
+            let prehash = eip712_authorization_hash(
+                &Eip712MessageDomain::frontdoor(1),
+                &challenge.session_id,
+                &self.wallet,
+                field("Nonce"),
+                field("Issued At"),
+                expires_at,
+            )
+            .expect("typed data hash");
+            let (sig, recid) = self
+                .signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign typed data");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            hex::encode(&sig_bytes, true)
+        }
+
+        fn session_uuid(&self, challenge: &FrontdoorChallengeResponse) -> Uuid {
+            Uuid::parse_str(&challenge.session_id).expect("session uuid")
+        }
+
+        async fn challenge(&self) -> FrontdoorChallengeResponse {
+            self.service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: self.wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge")
+        }
+
+        async fn chat(&self, challenge: &FrontdoorChallengeResponse, message: &str) {
+            self.service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: message.to_string(),
+                })
+                .await
+                .expect("onboarding chat");
+        }
+
+        async fn verify(
+            &self,
+            challenge: &FrontdoorChallengeResponse,
+        ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+            self.verify_as(challenge, &self.wallet).await
+        }
+
+        /// Verify with `wallet_address` as submitted, e.g. in a different casing.
+        async fn verify_as(
+            &self,
+            challenge: &FrontdoorChallengeResponse,
+            wallet_address: &str,
+        ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+            self.service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet_address.to_string(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature: self.sign(&challenge.message),
+                    signature_scheme: None,
+                    config: sample_user_config(&self.wallet),
+                })
+                .await
+        }
+
+        /// Verify with `message` signed in place of the issued challenge text.
+        async fn verify_message(
+            &self,
+            challenge: &FrontdoorChallengeResponse,
+            message: &str,
+        ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+            self.service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: self.wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: message.to_string(),
+                    signature: self.sign(message),
+                    signature_scheme: None,
+                    config: sample_user_config(&self.wallet),
+                })
+                .await
+        }
+
+        /// Verify the issued challenge with an `eip712` `signature`.
+        async fn verify_eip712(
+            &self,
+            challenge: &FrontdoorChallengeResponse,
+            signature: String,
+        ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+            self.service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: self.wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature,
+                    signature_scheme: Some("eip712".to_string()),
+                    config: sample_user_config(&self.wallet),
+                })
+                .await
+        }
+
+        /// Verify with a caller-built config instead of [`sample_user_config`].
+        async fn verify_with_config(
+            &self,
+            challenge: &FrontdoorChallengeResponse,
+            config: FrontdoorUserConfig,
+        ) -> Result<FrontdoorVerifyResponse, FrontdoorError> {
+            self.service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: self.wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature: self.sign(&challenge.message),
+                    signature_scheme: None,
+                    config,
+                })
+                .await
+        }
+
+        /// Yield to the spawned provisioning task until the session reaches
+        /// `status`. Never sleeps, so the mock clock stays the only time source.
+        async fn wait_for_status(
+            &self,
+            challenge: &FrontdoorChallengeResponse,
+            status: &str,
+        ) -> FrontdoorSessionResponse {
+            let session_uuid = self.session_uuid(challenge);
+            for _ in 0..200 {
+                let session = self
+                    .service
+                    .get_session(session_uuid)
+                    .await
+                    .expect("session should exist");
+                if session.status == status {
+                    return session;
+                }
+                tokio::task::yield_now().await;
+            }
+            panic!("session never reached status {status}");
+        }
+
+        async fn wallet_record(&self) -> Option<WalletSessionRecord> {
+            self.service
+                .state
+                .read()
+                .await
+                .wallets
+                .get(&self.wallet)
+                .cloned()
+        }
+    }
+
+    fn sample_user_config(wallet: &str) -> FrontdoorUserConfig {
+        FrontdoorUserConfig {
+            config_version: FRONTDOOR_CURRENT_CONFIG_VERSION,
+            profile_domain: "hyperliquid".to_string(),
+            domain_overrides: HashMap::new(),
+            inference_summary: None,
+            inference_confidence: None,
+            inference_warnings: Vec::new(),
+            profile_name: "demo_profile".to_string(),
+            hyperliquid_network: "testnet".to_string(),
+            paper_live_policy: "paper_only".to_string(),
+            hyperliquid_api_base_url: None,
+            hyperliquid_ws_url: None,
+            request_timeout_ms: 10_000,
+            max_retries: 3,
+            retry_backoff_ms: 500,
+            max_position_size_usd: 1_000,
+            leverage_cap: 2,
+            max_allocation_usd: 1_000,
+            per_trade_notional_cap_usd: 100,
+            max_leverage: 2,
+            max_slippage_bps: 50,
+            symbol_allowlist: vec!["BTC".to_string(), "ETH".to_string()],
+            symbol_denylist: vec![],
+            custody_mode: "user_wallet".to_string(),
+            operator_wallet_address: None,
+            user_wallet_address: Some(wallet.to_string()),
+            vault_address: None,
+            information_sharing_scope: "signals_only".to_string(),
+            kill_switch_enabled: true,
+            kill_switch_behavior: "pause_agent".to_string(),
+            enable_memory: true,
+            gateway_auth_key: "supersecuregatewaykey01".to_string(),
+            eigencloud_auth_key: None,
+            verification_backend: "eigencloud_primary".to_string(),
+            verification_eigencloud_endpoint: None,
+            verification_eigencloud_auth_scheme: "bearer".to_string(),
+            verification_eigencloud_timeout_ms: 5_000,
+            verification_fallback_enabled: true,
+            verification_fallback_signing_key_id: None,
+            verification_fallback_chain_path: None,
+            verification_fallback_require_signed_receipts: true,
+            accept_terms: true,
+            accepted_terms_version: Some(DEFAULT_TERMS_VERSION.to_string()),
+            accepted_terms_at: Some("2026-01-01T00:00:00Z".to_string()),
+        }
+    }
+
+    #[cfg(feature = "libsql")]
+    #[test]
+    fn restored_sessions_are_redacted_and_interrupted_provisioning_fails() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let (db, _db_dir) = crate::testing::test_db().await;
+            let store: Arc<dyn FrontdoorSessionStore> = db;
+            let config = test_frontdoor_config();
+            let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+
+            let service =
+                FrontdoorService::new_for_tests(config.clone(), tmp.path().join("wallets.json"));
+            service
+                .attach_session_store(Arc::clone(&store))
+                .await
+                .expect("attach store");
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session uuid");
+            {
+                let mut state = service.state.write().await;
+                let session = state.sessions.get_mut(&session_id).expect("session");
+                let mut user_config = sample_user_config(&session.wallet_address);
+                user_config.eigencloud_auth_key = Some("eigencloudsecretkey01".to_string());
+                session.config = Some(user_config);
+                session.signature_verification_latency_ms = Some(1);
+                session.status = SessionStatus::Provisioning;
+            }
+            service.persist_session_by_id(session_id).await;
 
-        let mut base_dual_mode = sample_user_config(mismatched_wallet);
-        base_dual_mode.custody_mode = "dual_mode".to_string();
-        base_dual_mode.operator_wallet_address =
-            Some("0x1111111111111111111111111111111111111111".to_string());
+            let payload = store.list_frontdoor_sessions().await.expect("list")[0]
+                .payload
+                .to_string();
+            assert!(!payload.contains("supersecuregatewaykey01"), "{payload}");
+            assert!(!payload.contains("eigencloudsecretkey01"), "{payload}");
 
-        let dual_mode_err = service
-            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                wallet_address: connected_wallet.to_string(),
-                intent: "dual mode".to_string(),
-                domain: Some("hyperliquid".to_string()),
-                gateway_auth_key: None,
-                base_config: Some(base_dual_mode),
-            })
-            .expect_err("mismatched dual_mode user wallet should fail");
-        assert_eq!(
-            dual_mode_err,
-            "user_wallet_address must match the connected wallet for user_wallet/dual_mode"
-        );
+            let restarted =
+                FrontdoorService::new_for_tests(config, tmp.path().join("wallets.json"));
+            assert_eq!(
+                restarted
+                    .attach_session_store(Arc::clone(&store))
+                    .await
+                    .expect("attach store"),
+                1
+            );
+            let session = restarted
+                .get_session(session_id)
+                .await
+                .expect("restored session");
+            assert_eq!(session.status, "failed");
+            assert!(session.detail.contains("interrupted"), "{}", session.detail);
+            assert_eq!(
+                store.list_frontdoor_sessions().await.expect("list")[0].status,
+                "failed"
+            );
+
+            let err = Arc::clone(&restarted)
+                .retry_provision(session_id)
+                .await
+                .expect_err("redacted session cannot be retried");
+            assert!(
+                matches!(&err, FrontdoorError::InvalidRequest(message) if message.contains("start a new session")),
+                "{err}"
+            );
+        });
     }
 
+    #[cfg(feature = "libsql")]
     #[test]
-    fn operator_and_dual_custody_require_operator_wallet_in_validation() {
-        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+    fn persisted_session_survives_service_restart() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let (db, _db_dir) = crate::testing::test_db().await;
+            let store: Arc<dyn FrontdoorSessionStore> = db;
+            let config = test_frontdoor_config();
 
-        let mut operator_wallet_mode = sample_user_config(connected_wallet);
-        operator_wallet_mode.custody_mode = "operator_wallet".to_string();
-        operator_wallet_mode.operator_wallet_address = None;
-        let operator_wallet_err = validate_user_config(&operator_wallet_mode)
-            .expect_err("operator_wallet must require operator wallet");
-        assert_eq!(
-            operator_wallet_err,
-            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode"
-        );
+            let service =
+                FrontdoorService::new_for_tests(config.clone(), tmp.path().join("wallets.json"));
+            assert_eq!(
+                service
+                    .attach_session_store(Arc::clone(&store))
+                    .await
+                    .expect("attach store"),
+                0
+            );
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session uuid");
+            service
+                .runtime_control(
+                    session_id,
+                    FrontdoorRuntimeControlRequest {
+                        action: "pause".to_string(),
+                        actor: None,
+                    },
+                )
+                .await
+                .expect("pause runtime");
+            let timeline_before = service
+                .session_timeline(session_id)
+                .await
+                .expect("timeline before restart");
 
-        let mut dual_mode = sample_user_config(connected_wallet);
-        dual_mode.custody_mode = "dual_mode".to_string();
-        dual_mode.operator_wallet_address = None;
-        let dual_mode_err =
-            validate_user_config(&dual_mode).expect_err("dual_mode must require operator wallet");
-        assert_eq!(
-            dual_mode_err,
-            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode"
-        );
+            let restarted =
+                FrontdoorService::new_for_tests(config, tmp.path().join("wallets.json"));
+            assert!(restarted.get_session(session_id).await.is_none());
+            assert_eq!(
+                restarted
+                    .attach_session_store(store)
+                    .await
+                    .expect("attach store"),
+                1
+            );
+
+            let session = restarted
+                .get_session(session_id)
+                .await
+                .expect("restored session");
+            assert_eq!(session.status, "awaiting_signature");
+            let timeline_after = restarted
+                .session_timeline(session_id)
+                .await
+                .expect("timeline after restart");
+            let event_types = |events: &[FrontdoorSessionTimelineEvent]| {
+                events
+                    .iter()
+                    .map(|event| (event.seq_id, event.event_type.clone()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                event_types(&timeline_after.events),
+                event_types(&timeline_before.events)
+            );
+            assert!(
+                timeline_after
+                    .events
+                    .iter()
+                    .any(|event| event.event_type == "runtime_control")
+            );
+        });
     }
 
     #[test]
-    fn suggest_config_normalizes_missing_operator_wallet_in_operator_or_dual_modes() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
-        );
+    fn every_onboarding_step_round_trips_and_has_a_handler() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_with_store_path(
+                SimulatedProvisioning::ready("https://simulated.example/gateway")
+                    .frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+                Arc::new(SystemClock),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
 
-        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
-        for intent in ["operator wallet", "dual mode"] {
-            let mut base = sample_user_config(connected_wallet);
-            base.operator_wallet_address = None;
+            for step in OnboardingStep::ALL {
+                assert_eq!(step.as_str().parse::<OnboardingStep>(), Ok(step));
 
-            let suggested = service
-                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                    wallet_address: connected_wallet.to_string(),
-                    intent: intent.to_string(),
-                    domain: Some("hyperliquid".to_string()),
-                    gateway_auth_key: None,
-                    base_config: Some(base),
+                service
+                    .state
+                    .write()
+                    .await
+                    .sessions
+                    .get_mut(&session_id)
+                    .expect("session")
+                    .onboarding
+                    .current_step = step;
+                let reply = service
+                    .onboarding_chat(FrontdoorOnboardingChatRequest {
+                        session_id: challenge.session_id.clone(),
+                        message: "hello".to_string(),
+                    })
+                    .await
+                    .expect("chat");
+                let assistant = reply.state.transcript.last().expect("assistant turn");
+                assert_eq!(assistant.role, "assistant", "step {}", step.as_str());
+                assert!(!assistant.message.is_empty(), "step {}", step.as_str());
+            }
+        });
+    }
+
+    #[test]
+    fn onboarding_rewind_from_required_variables_returns_to_objective() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_with_store_path(
+                SimulatedProvisioning::ready("https://simulated.example/gateway")
+                    .frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+                Arc::new(SystemClock),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
                 })
-                .expect("suggest config should normalize missing operator wallet");
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+
+            for message in [
+                "Launch conservative strategy profile",
+                "confirm plan",
+                "profile_name=demo_profile",
+            ] {
+                service
+                    .onboarding_chat(FrontdoorOnboardingChatRequest {
+                        session_id: challenge.session_id.clone(),
+                        message: message.to_string(),
+                    })
+                    .await
+                    .expect("chat");
+            }
+            let before = service
+                .onboarding_state(session_id)
+                .await
+                .expect("onboarding");
+            assert_eq!(before.current_step, "collect_required_variables");
+            assert!(!before.missing_fields.contains(&"profile_name".to_string()));
 
-            assert_eq!(suggested.config.custody_mode, "user_wallet");
+            let rewound = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "Restart objective".to_string(),
+                })
+                .await
+                .expect("rewind");
+            assert_eq!(rewound.state.current_step, "capture_objective");
+            assert!(!rewound.state.completed);
+            assert!(rewound.state.objective.is_none());
+            assert!(rewound.state.step2_payload.is_none());
+            assert!(rewound.state.step3_payload.is_none());
+            assert!(rewound.state.step4_payload.is_none());
+            assert_eq!(
+                rewound.state.missing_fields,
+                vec!["profile_name", "gateway_auth_key", "accept_terms"]
+            );
+            assert_eq!(rewound.state.transcript.len(), before.transcript.len() + 2);
+            let user_turn = &rewound.state.transcript[before.transcript.len()];
+            assert_eq!(user_turn.role, "user");
+            assert_eq!(user_turn.message, "Restart objective");
             assert!(
-                suggested
-                    .assumptions
-                    .iter()
-                    .any(|assumption| assumption
-                        == "Operator wallet missing; normalized custody_mode to user_wallet for safe launch.")
+                service
+                    .state
+                    .read()
+                    .await
+                    .sessions
+                    .get(&session_id)
+                    .expect("session")
+                    .onboarding
+                    .captured_variables
+                    .is_empty()
             );
-            let normalized_wallet = normalize_wallet_address(
-                suggested
-                    .config
-                    .user_wallet_address
-                    .as_deref()
-                    .expect("suggested config must include user wallet"),
-            )
-            .expect("suggested wallet must be valid");
+
+            let replanned = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "Launch aggressive momentum profile".to_string(),
+                })
+                .await
+                .expect("new objective");
+            assert_eq!(replanned.state.current_step, "propose_plan");
             assert_eq!(
-                normalized_wallet,
-                "0x9431cf5da0ce60664661341db650763b08286b18"
+                replanned.state.objective.as_deref(),
+                Some("Launch aggressive momentum profile")
             );
-        }
+        });
     }
 
     #[test]
-    fn suggest_config_normalizes_hyperliquid_fields_for_non_hyperliquid_domains() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
-        );
+    fn onboarding_transcript_is_trimmed_to_configured_turns() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_with_store_path(
+                FrontdoorConfig {
+                    max_onboarding_turns: 4,
+                    ..SimulatedProvisioning::ready("https://simulated.example/gateway")
+                        .frontdoor_config()
+                },
+                tmp.path().join("wallet_sessions.json"),
+                Arc::new(SystemClock),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            let greeting = service
+                .onboarding_state(session_id)
+                .await
+                .expect("onboarding")
+                .transcript[0]
+                .clone();
+
+            let mut reply = None;
+            for objective in ["first objective", "second objective", "third objective"] {
+                reply = Some(
+                    service
+                        .onboarding_chat(FrontdoorOnboardingChatRequest {
+                            session_id: challenge.session_id.clone(),
+                            message: objective.to_string(),
+                        })
+                        .await
+                        .expect("chat"),
+                );
+            }
+            let transcript = reply.expect("reply").state.transcript;
+            assert_eq!(transcript.len(), 5);
+            assert_eq!(transcript[0].role, "assistant");
+            assert_eq!(transcript[0].message, greeting.message);
+            let users: Vec<&str> = transcript
+                .iter()
+                .filter(|turn| turn.role == "user")
+                .map(|turn| turn.message.as_str())
+                .collect();
+            assert_eq!(users, vec!["second objective", "third objective"]);
 
-        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
-        let mut base = sample_user_config(connected_wallet);
-        base.hyperliquid_network = "mainnet".to_string();
-        base.paper_live_policy = "live_allowed".to_string();
-        base.symbol_allowlist = vec!["BTC".to_string()];
-        base.symbol_denylist = vec!["ETH".to_string()];
+            let artifact = service
+                .onboarding_transcript(session_id)
+                .await
+                .expect("artifact");
+            assert_eq!(artifact.transcript.len(), 5);
+            assert_eq!(artifact.transcript[0].message, greeting.message);
+            let persisted: serde_json::Value = serde_json::from_slice(
+                &std::fs::read(onboarding_transcript_path(&service.store_path, session_id))
+                    .expect("read persisted artifact"),
+            )
+            .expect("parse persisted artifact");
+            assert_eq!(persisted["transcript"].as_array().map(Vec::len), Some(5));
+        });
+    }
 
-        let suggested = service
-            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                wallet_address: connected_wallet.to_string(),
-                intent: "mainnet live trading with btc-only".to_string(),
-                domain: Some("general".to_string()),
-                gateway_auth_key: None,
-                base_config: Some(base),
-            })
-            .expect("suggest config");
+    #[test]
+    fn onboarding_rewind_is_refused_after_completion() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let service = FrontdoorService::new_with_store_path(
+                SimulatedProvisioning::ready("https://simulated.example/gateway")
+                    .frontdoor_config(),
+                tmp.path().join("wallet_sessions.json"),
+                Arc::new(SystemClock),
+                Arc::new(NoopMetrics),
+            );
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
 
-        assert!(suggested.validated);
-        assert_eq!(suggested.config.profile_domain, "general");
-        assert_eq!(suggested.config.hyperliquid_network, "testnet");
-        assert_eq!(suggested.config.paper_live_policy, "paper_only");
-        assert!(suggested.config.symbol_allowlist.is_empty());
-        assert!(suggested.config.symbol_denylist.is_empty());
+            for message in [
+                "Launch conservative strategy profile",
+                "confirm plan",
+                "profile_name=demo_profile, gateway_auth_key=__from_config__, accept_terms=true",
+                "confirm sign",
+            ] {
+                service
+                    .onboarding_chat(FrontdoorOnboardingChatRequest {
+                        session_id: challenge.session_id.clone(),
+                        message: message.to_string(),
+                    })
+                    .await
+                    .expect("chat");
+            }
+
+            let refused = service
+                .onboarding_chat(FrontdoorOnboardingChatRequest {
+                    session_id: challenge.session_id.clone(),
+                    message: "back".to_string(),
+                })
+                .await
+                .expect("rewind attempt");
+            assert_eq!(refused.state.current_step, "ready_to_sign");
+            assert!(refused.state.completed);
+            assert!(refused.state.missing_fields.is_empty());
+            assert_eq!(
+                refused.state.objective.as_deref(),
+                Some("Launch conservative strategy profile")
+            );
+            assert!(refused.assistant_message.contains("cannot be rewound"));
+        });
     }
 
     #[test]
-    fn suggest_config_keeps_addon_domains_explicit() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
+    fn unknown_persisted_onboarding_step_falls_back_to_capture_objective() {
+        let step: OnboardingStep = serde_json::from_str("\"bogus_step\"").expect("deserialize");
+        assert_eq!(step, OnboardingStep::CaptureObjective);
+        assert_eq!(
+            serde_json::to_string(&OnboardingStep::CollectRequiredVariables).expect("serialize"),
+            "\"collect_required_variables\""
         );
-
-        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
-        let general_suggested = service
-            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                wallet_address: connected_wallet.to_string(),
-                intent: "hyperliquid eigenda mainnet workflow".to_string(),
-                domain: None,
-                gateway_auth_key: None,
-                base_config: None,
-            })
-            .expect("general suggest config");
-        assert_eq!(general_suggested.config.profile_domain, "general");
-
-        let eigenda_suggested = service
-            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
-                wallet_address: connected_wallet.to_string(),
-                intent: "receipt continuity".to_string(),
-                domain: Some("eigenda".to_string()),
-                gateway_auth_key: None,
-                base_config: None,
-            })
-            .expect("eigenda suggest config");
-        assert_eq!(eigenda_suggested.config.profile_domain, "eigenda");
     }
 
     #[test]
-    fn domain_profiles_keep_addon_modules_explicit() {
-        let profiles = frontdoor_domain_profiles();
-        let general = profiles
-            .iter()
-            .find(|profile| profile.domain == "general")
-            .expect("general profile");
-        let hyperliquid = profiles
-            .iter()
-            .find(|profile| profile.domain == "hyperliquid")
-            .expect("hyperliquid profile");
-        let eigenda = profiles
-            .iter()
-            .find(|profile| profile.domain == "eigenda")
-            .expect("eigenda profile");
+    fn verify_rejects_replayed_challenge_nonce() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let first = harness.challenge().await;
+            harness.verify(&first).await.expect("first verify");
 
-        assert!(
-            !general
-                .default_modules
-                .iter()
-                .any(|module| module == "hyperliquid_addon" || module == "eigenda_addon")
-        );
-        assert!(
-            hyperliquid
-                .default_modules
-                .iter()
-                .any(|module| module == "hyperliquid_addon")
-        );
-        assert!(
-            !hyperliquid
-                .default_modules
-                .iter()
-                .any(|module| module == "eigenda_addon")
-        );
-        assert!(
-            eigenda
-                .default_modules
-                .iter()
-                .any(|module| module == "eigenda_addon")
-        );
-        assert!(
-            !eigenda
-                .default_modules
-                .iter()
-                .any(|module| module == "hyperliquid_addon")
-        );
+            let second = harness.challenge().await;
+            let replay = harness
+                .service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: second.session_id.clone(),
+                    wallet_address: harness.wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: first.message.clone(),
+                    signature: harness.sign(&first.message),
+                    signature_scheme: None,
+                    config: sample_user_config(&harness.wallet),
+                })
+                .await;
+            assert!(matches!(replay, Err(FrontdoorError::NonceReplayed)));
+            harness.verify(&second).await.expect("fresh nonce verifies");
+
+            harness.clock.advance(chrono::Duration::seconds(901));
+            harness
+                .service
+                .get_session(harness.session_uuid(&second))
+                .await;
+            assert!(
+                harness
+                    .service
+                    .state
+                    .read()
+                    .await
+                    .consumed_nonces
+                    .is_empty()
+            );
+        });
     }
 
     #[test]
-    fn config_contract_reports_supported_versions() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
+    fn parse_challenge_message_extracts_fields() {
+        let session_id = Uuid::new_v4();
+        let message = render_challenge_message(
+            DEFAULT_CHALLENGE_TEMPLATE,
+            &[
+                ("wallet", "0x9431Cf5DA0CE60664661341db650763B08286B18"),
+                ("privy_user_id", "did:privy:abc"),
+                ("chain_id", "8453"),
+                ("session_id", &session_id.to_string()),
+                ("version", "3"),
+                ("nonce", "AbC123"),
+                ("issued_at", "2026-01-01T00:00:00+00:00"),
+                ("expires_at", "2026-01-01T00:15:00+00:00"),
+            ],
         );
-        let contract = service.config_contract();
+        let fields = parse_challenge_message(&message).expect("parse");
         assert_eq!(
-            contract.current_config_version,
-            FRONTDOOR_CURRENT_CONFIG_VERSION
+            fields,
+            ChallengeFields {
+                wallet: Some("0x9431cf5da0ce60664661341db650763b08286b18".to_string()),
+                privy_link: Some("did:privy:abc".to_string()),
+                chain_id: Some(8453),
+                session_id: Some(session_id),
+                version: Some(3),
+                nonce: "AbC123".to_string(),
+                issued_at: Some("2026-01-01T00:00:00+00:00".to_string()),
+                expires_at: Some("2026-01-01T00:15:00+00:00".to_string()),
+            }
         );
-        assert!(
-            contract
-                .supported_config_versions
-                .contains(&FRONTDOOR_CURRENT_CONFIG_VERSION)
+
+        let mut reordered: Vec<String> = message
+            .lines()
+            .rev()
+            .map(|line| format!("  {}  ", line.replacen(": ", " :   ", 1)))
+            .collect();
+        reordered.insert(2, String::new());
+        assert_eq!(
+            parse_challenge_message(&reordered.join("\r\n")).expect("parse reordered"),
+            fields
         );
-        assert!(
-            contract
-                .mandatory_steps
-                .contains(&"configure_runtime_profile_and_risk".to_string())
+
+        let err = parse_challenge_message(&format!("Nonce: decoy\n{message}"))
+            .expect_err("repeated nonce");
+        assert!(err.contains("'Nonce'"), "{err}");
+        assert_eq!(
+            parse_challenge_message("Chain ID: 1").expect_err("no nonce"),
+            "challenge message is missing a nonce"
         );
     }
 
     #[test]
-    fn policy_template_library_exposes_common_objective_presets() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
-        );
+    fn verify_accepts_reformatted_challenge_and_rejects_mismatched_session_id() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
 
-        let library = service.policy_template_library();
-        assert!(!library.templates.is_empty());
-        assert!(
-            library
-                .templates
-                .iter()
-                .any(|template| template.template_id == "general_safe_baseline")
-        );
-        assert!(
-            library
-                .templates
+            let first = harness.challenge().await;
+            let second = harness.challenge().await;
+            let swapped = first.message.replace(&first.session_id, &second.session_id);
+            let err = harness
+                .verify_message(&first, &swapped)
+                .await
+                .expect_err("session id mismatch");
+            assert_eq!(
+                err,
+                FrontdoorError::ChallengeFieldMismatch {
+                    field: "Session ID"
+                }
+            );
+
+            let relinked = first
+                .message
+                .replace("Privy Link: ", "Privy Link: did:privy:attacker");
+            let err = harness
+                .verify_message(&first, &relinked)
+                .await
+                .expect_err("privy link mismatch");
+            assert_eq!(
+                err,
+                FrontdoorError::ChallengeFieldMismatch {
+                    field: "Privy Link"
+                }
+            );
+
+            let restated = first.message.replace(
+                "Sign this gasless authorization transaction to verify wallet control and start provisioning your dedicated Enclagent enclave.",
+                "Sign in to GameX",
+            );
+            assert_ne!(restated, first.message);
+            let err = harness
+                .verify_message(&first, &restated)
+                .await
+                .expect_err("statement mismatch");
+            assert_eq!(err, FrontdoorError::SignatureMismatch);
+
+            let reformatted = first
+                .message
+                .lines()
+                .map(|line| format!("   {line}   "))
+                .collect::<Vec<_>>()
+                .join("\r\n\r\n");
+            assert_ne!(reformatted.trim(), first.message.trim());
+            let verified = harness
+                .verify_message(&first, &reformatted)
+                .await
+                .expect("reformatted challenge verifies");
+            assert_ne!(verified.status, "awaiting_signature");
+        });
+    }
+
+    #[test]
+    fn provision_command_overrunning_timeout_is_killed_and_fails_session() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let mut config = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            config.provision_command = Some("sleep 30".to_string());
+            config.allow_default_instance_fallback = false;
+            config.provision_timeout_secs = 1;
+            let service =
+                FrontdoorService::new_for_tests(config, tmp.path().join("wallet_sessions.json"));
+
+            let private_key =
+                hex::decode(FrontdoorTestHarness::TEST_PRIVATE_KEY).expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+            let challenge = service
+                .create_challenge(FrontdoorChallengeRequest {
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    chain_id: Some(1),
+                })
+                .await
+                .expect("challenge");
+            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
+            let prehash = eip191_personal_sign_hash(&challenge.message);
+            let (sig, recid) = signing_key
+                .sign_prehash_recoverable(&prehash)
+                .expect("sign challenge");
+            let mut sig_bytes = sig.to_bytes().to_vec();
+            sig_bytes.push(recid.to_byte() + 27);
+            service
+                .clone()
+                .verify_and_start(FrontdoorVerifyRequest {
+                    session_id: challenge.session_id.clone(),
+                    wallet_address: wallet.clone(),
+                    privy_user_id: None,
+                    privy_identity_token: None,
+                    privy_access_token: None,
+                    message: challenge.message.clone(),
+                    signature: hex::encode(&sig_bytes, true),
+                    signature_scheme: None,
+                    config: sample_user_config(&wallet),
+                })
+                .await
+                .expect("verify and start");
+
+            let started = Instant::now();
+            let mut failed = None;
+            while started.elapsed() < Duration::from_secs(10) {
+                let session = service
+                    .get_session(session_uuid)
+                    .await
+                    .expect("session should exist");
+                if session.status == "failed" {
+                    failed = Some(session);
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            let failed = failed.expect("session should fail on provision timeout");
+            assert!(started.elapsed() < Duration::from_secs(10));
+            assert!(
+                failed
+                    .error
+                    .as_deref()
+                    .unwrap_or_default()
+                    .contains("timed out after 1s"),
+                "unexpected error: {:?}",
+                failed.error
+            );
+        });
+    }
+
+    /// Counter name with its recorded `(label, value)` pairs.
+    type RecordedCounter = (String, Vec<(String, String)>);
+
+    /// Metrics fake that records every counter increment and histogram sample.
+    #[derive(Default)]
+    struct RecordingMetrics {
+        counters: std::sync::Mutex<Vec<RecordedCounter>>,
+        histograms: std::sync::Mutex<Vec<(String, f64)>>,
+    }
+
+    impl RecordingMetrics {
+        fn counter_count(&self, name: &str, labels: &[(&str, &str)]) -> usize {
+            self.counters
+                .lock()
+                .expect("counters")
                 .iter()
-                .any(|template| template.template_id == "hyperliquid_paper_operator")
-        );
+                .filter(|(counter, recorded)| {
+                    counter == name
+                        && labels.iter().all(|(key, value)| {
+                            recorded.iter().any(|(k, v)| k == key && v == value)
+                        })
+                })
+                .count()
+        }
+    }
+
+    impl Metrics for RecordingMetrics {
+        fn increment_counter(&self, name: &str, labels: MetricLabels<'_>) {
+            self.counters.lock().expect("counters").push((
+                name.to_string(),
+                labels
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            ));
+        }
+
+        fn record_histogram(&self, name: &str, value: f64, _labels: MetricLabels<'_>) {
+            self.histograms
+                .lock()
+                .expect("histograms")
+                .push((name.to_string(), value));
+        }
+    }
+
+    #[test]
+    fn provision_outcomes_increment_labelled_counters() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let tmp = tempdir().expect("tempdir");
+            let metrics = Arc::new(RecordingMetrics::default());
+            let private_key =
+                hex::decode(FrontdoorTestHarness::TEST_PRIVATE_KEY).expect("private key");
+            let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
+            let wallet =
+                ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
+
+            let run = |config: FrontdoorConfig, store: &str| {
+                let service = FrontdoorService::new_with_store_path(
+                    config,
+                    tmp.path().join(store),
+                    Arc::new(SystemClock),
+                    metrics.clone(),
+                );
+                let wallet = wallet.clone();
+                let signing_key = signing_key.clone();
+                async move {
+                    let challenge = service
+                        .create_challenge(FrontdoorChallengeRequest {
+                            wallet_address: wallet.clone(),
+                            privy_user_id: None,
+                            chain_id: Some(1),
+                        })
+                        .await
+                        .expect("challenge");
+                    let prehash = eip191_personal_sign_hash(&challenge.message);
+                    let (sig, recid) = signing_key
+                        .sign_prehash_recoverable(&prehash)
+                        .expect("sign challenge");
+                    let mut sig_bytes = sig.to_bytes().to_vec();
+                    sig_bytes.push(recid.to_byte() + 27);
+                    service
+                        .clone()
+                        .verify_and_start(FrontdoorVerifyRequest {
+                            session_id: challenge.session_id.clone(),
+                            wallet_address: wallet.clone(),
+                            privy_user_id: None,
+                            privy_identity_token: None,
+                            privy_access_token: None,
+                            message: challenge.message.clone(),
+                            signature: hex::encode(&sig_bytes, true),
+                            signature_scheme: None,
+                            config: sample_user_config(&wallet),
+                        })
+                        .await
+                        .expect("verify and start");
+                    let session_uuid =
+                        Uuid::parse_str(&challenge.session_id).expect("session uuid");
+                    for _ in 0..200 {
+                        let status = service
+                            .get_session(session_uuid)
+                            .await
+                            .expect("session should exist")
+                            .status;
+                        if status == "ready" || status == "failed" {
+                            return status;
+                        }
+                        tokio::time::sleep(Duration::from_millis(25)).await;
+                    }
+                    panic!("provisioning never finished");
+                }
+            };
+
+            let ready = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            assert_eq!(run(ready.clone(), "ready.json").await, "ready");
+            assert_eq!(
+                metrics.counter_count(
+                    "frontdoor_provision_total",
+                    &[("source", "default_instance_url"), ("outcome", "success")],
+                ),
+                1
+            );
+
+            let mut failing = ready;
+            failing.provision_command = Some("exit 3".to_string());
+            failing.allow_default_instance_fallback = false;
+            assert_eq!(run(failing, "failing.json").await, "failed");
+            assert_eq!(
+                metrics.counter_count(
+                    "frontdoor_provision_total",
+                    &[("source", "command"), ("outcome", "failure")],
+                ),
+                1
+            );
+            assert_eq!(
+                metrics.counter_count("frontdoor_provision_total", &[("outcome", "success")]),
+                1
+            );
+            let histograms = metrics.histograms.lock().expect("histograms");
+            assert_eq!(histograms.len(), 2);
+            assert!(
+                histograms
+                    .iter()
+                    .all(|(name, _)| name == "frontdoor_signature_verification_latency_ms")
+            );
+        });
     }
 
     #[test]
-    fn list_sessions_filters_by_wallet() {
+    fn create_challenge_enforces_allowed_chain_ids() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
             let tmp = tempdir().expect("tempdir");
-            let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: false,
-                    privy_app_id: None,
-                    privy_client_id: None,
-                    provision_command: None,
-                    default_instance_url: None,
-                    allow_default_instance_fallback: false,
-                    verify_app_base_url: None,
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 1000,
-                },
-                tmp.path().join("wallet_sessions.json"),
-            );
-
-            let wallet_a = "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string();
-            let wallet_b = "0x8ba1f109551bD432803012645Ac136ddd64DBA72".to_string();
+            let mut config = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            config.allowed_chain_ids = vec![8453];
+            let service =
+                FrontdoorService::new_for_tests(config, tmp.path().join("wallet_sessions.json"));
+            let wallet = "0x9431cf5da0ce60664661341db650763b08286b18";
+            let request = |chain_id| FrontdoorChallengeRequest {
+                wallet_address: wallet.to_string(),
+                privy_user_id: None,
+                chain_id,
+            };
 
-            service
-                .create_challenge(FrontdoorChallengeRequest {
-                    wallet_address: wallet_a.clone(),
-                    privy_user_id: None,
-                    chain_id: Some(1),
-                })
-                .await
-                .expect("challenge a");
-            service
-                .create_challenge(FrontdoorChallengeRequest {
-                    wallet_address: wallet_b.clone(),
-                    privy_user_id: None,
-                    chain_id: Some(1),
-                })
-                .await
-                .expect("challenge b");
+            for chain_id in [Some(1), None] {
+                let err = service
+                    .create_challenge(request(chain_id))
+                    .await
+                    .expect_err("chain 1 is not allowed");
+                assert!(matches!(
+                    err,
+                    FrontdoorError::ChainNotAllowed { chain_id: 1 }
+                ));
+                assert_eq!(err.status_code(), 400);
+            }
 
-            let (total, sessions) = service
-                .list_sessions(Some(&wallet_a), 10)
+            let challenge = service
+                .create_challenge(request(Some(8453)))
                 .await
-                .expect("list sessions");
-            assert_eq!(total, 1);
-            assert_eq!(sessions.len(), 1);
+                .expect("allowed chain");
             assert_eq!(
-                sessions[0].wallet_address,
-                "0x9431cf5da0ce60664661341db650763b08286b18"
+                challenge_message_field(&challenge.message, "Chain ID"),
+                Some("8453")
+            );
+            let session_id = Uuid::parse_str(&challenge.session_id).expect("session id");
+            let state = service.state.read().await;
+            assert_eq!(
+                state.sessions.get(&session_id).expect("session").chain_id,
+                8453
             );
-            assert_eq!(sessions[0].provisioning_source, "unknown");
         });
     }
 
     #[test]
-    fn experience_manifest_includes_state_inputs() {
-        let tmp = tempdir().expect("tempdir");
-        let service = FrontdoorService::new_for_tests(
-            FrontdoorConfig {
-                require_privy: false,
-                privy_app_id: None,
-                privy_client_id: None,
-                provision_command: None,
-                default_instance_url: None,
-                allow_default_instance_fallback: false,
-                verify_app_base_url: None,
-                session_ttl_secs: 900,
-                poll_interval_ms: 1000,
-            },
-            tmp.path().join("wallet_sessions.json"),
-        );
-        let manifest = service.experience_manifest();
-        assert_eq!(manifest.manifest_version, 1);
-        assert!(manifest.steps.len() >= 4);
-        assert!(
-            manifest
-                .steps
-                .iter()
-                .all(|step| !step.state_inputs.is_empty())
-        );
-        assert!(
-            manifest
-                .steps
-                .iter()
-                .any(|step| step.step_id == "verification"
-                    && step.state_inputs.iter().any(|field| field == "signature"))
-        );
-    }
-
-    #[test]
-    fn onboarding_timeline_runtime_todos_and_preflight_are_deterministic() {
+    fn custom_challenge_template_renders_and_verifies() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
-            let tmp = tempdir().expect("tempdir");
-            let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: false,
-                    privy_app_id: None,
-                    privy_client_id: None,
-                    provision_command: None,
-                    default_instance_url: Some("https://session.example/gateway".to_string()),
-                    allow_default_instance_fallback: true,
-                    verify_app_base_url: Some(
-                        "https://verify-sepolia.eigencloud.xyz/app".to_string(),
-                    ),
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 100,
-                },
-                tmp.path().join("wallet_sessions.json"),
-            );
+            // Indented field lines pass validation, so they must parse too.
+            let template = "Acme Wallet Login\nWallet: {wallet}\nChain ID: {chain_id}\nSession ID: {session_id}\nVersion: v{version}\n  Nonce: {nonce}\n  Issued At: {issued_at}\n  Expires At: {expires_at}";
+            validate_challenge_template(template).expect("valid template");
 
-            let private_key = decode_hex_prefixed(
-                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
-            )
-            .expect("private key");
+            let tmp = tempdir().expect("tempdir");
+            let mut config = SimulatedProvisioning::ready("https://simulated.example/gateway")
+                .frontdoor_config();
+            config.challenge_template = Some(template.to_string());
+            config.allowed_chain_ids = vec![1, 10];
+            let service =
+                FrontdoorService::new_for_tests(config, tmp.path().join("wallet_sessions.json"));
+            let private_key = hex::decode(FrontdoorTestHarness::TEST_PRIVATE_KEY)
+                .expect("private key");
             let signing_key = SigningKey::from_slice(&private_key).expect("signing key");
             let wallet =
                 ethereum_address_from_verifying_key(signing_key.verifying_key()).expect("wallet");
@@ -5462,77 +11863,26 @@ mod tests {
                 .create_challenge(FrontdoorChallengeRequest {
                     wallet_address: wallet.clone(),
                     privy_user_id: None,
-                    chain_id: Some(1),
+                    chain_id: Some(10),
                 })
                 .await
                 .expect("challenge");
-            let session_uuid = Uuid::parse_str(&challenge.session_id).expect("session uuid");
-
-            let onboarding = service
-                .onboarding_state(session_uuid)
-                .await
-                .expect("onboarding");
-            assert_eq!(onboarding.current_step, "capture_objective");
-            assert_eq!(onboarding.transcript.len(), 1);
-
-            let empty_chat = service
-                .onboarding_chat(FrontdoorOnboardingChatRequest {
-                    session_id: challenge.session_id.clone(),
-                    message: "   ".to_string(),
-                })
-                .await;
-            assert!(empty_chat.is_err());
-
-            let chat = service
-                .onboarding_chat(FrontdoorOnboardingChatRequest {
-                    session_id: challenge.session_id.clone(),
-                    message: "Launch conservative strategy profile".to_string(),
-                })
-                .await
-                .expect("chat");
-            assert_eq!(chat.state.current_step, "propose_plan");
-            assert!(!chat.state.completed);
-
-            let confirm_plan = service
-                .onboarding_chat(FrontdoorOnboardingChatRequest {
-                    session_id: challenge.session_id.clone(),
-                    message: "confirm plan".to_string(),
-                })
-                .await
-                .expect("confirm plan");
-            assert_eq!(confirm_plan.state.current_step, "collect_required_variables");
-            assert!(!confirm_plan.state.missing_fields.is_empty());
-
-            let fill_required = service
-                .onboarding_chat(FrontdoorOnboardingChatRequest {
-                    session_id: challenge.session_id.clone(),
-                    message: "profile_name=demo_profile, gateway_auth_key=__from_config__, accept_terms=true".to_string(),
-                })
-                .await
-                .expect("fill required");
-            assert_eq!(fill_required.state.current_step, "confirm_and_sign");
-            assert!(fill_required.state.missing_fields.is_empty());
-
-            let confirm_sign = service
-                .onboarding_chat(FrontdoorOnboardingChatRequest {
-                    session_id: challenge.session_id.clone(),
-                    message: "confirm sign".to_string(),
-                })
-                .await
-                .expect("confirm sign");
-            assert_eq!(confirm_sign.state.current_step, "ready_to_sign");
-            assert!(confirm_sign.state.completed);
-
-            let timeline = service
-                .session_timeline(session_uuid)
-                .await
-                .expect("timeline");
-            assert!(timeline.events.len() >= 3);
-            let mut prev = 0u64;
-            for event in &timeline.events {
-                assert!(event.seq_id > prev);
-                prev = event.seq_id;
-            }
+            assert!(challenge.message.starts_with("Acme Wallet Login\n"));
+            assert!(!challenge.message.contains("Enclagent"));
+            assert!(!challenge.message.contains('{'));
+            assert_eq!(
+                challenge_message_field(&challenge.message, "Session ID"),
+                Some(challenge.session_id.as_str())
+            );
+            assert_eq!(challenge_message_field(&challenge.message, "Chain ID"), Some("10"));
+            assert_eq!(
+                challenge_message_field(&challenge.message, "Nonce").map(str::len),
+                Some(24)
+            );
+            assert_eq!(
+                challenge_message_field(&challenge.message, "Expires At"),
+                Some(challenge.expires_at.as_str())
+            );
 
             let prehash = eip191_personal_sign_hash(&challenge.message);
             let (sig, recid) = signing_key
@@ -5540,8 +11890,6 @@ mod tests {
                 .expect("sign challenge");
             let mut sig_bytes = sig.to_bytes().to_vec();
             sig_bytes.push(recid.to_byte() + 27);
-            let signature = format!("0x{}", encode_hex_lower(&sig_bytes));
-
             service
                 .clone()
                 .verify_and_start(FrontdoorVerifyRequest {
@@ -5551,217 +11899,532 @@ mod tests {
                     privy_identity_token: None,
                     privy_access_token: None,
                     message: challenge.message.clone(),
-                    signature,
+                    signature: hex::encode(&sig_bytes, true),
+                    signature_scheme: None,
                     config: sample_user_config(&wallet),
                 })
                 .await
-                .expect("verify and start");
+                .expect("custom template verifies");
+        });
+    }
 
-            let verification = service
-                .verification_explanation(session_uuid)
-                .await
-                .expect("verification explanation");
-            assert_eq!(verification.backend, "eigencloud_primary");
-            assert_eq!(verification.level, "primary_plus_signed_fallback");
+    #[test]
+    fn challenge_template_validation_rejects_bad_templates() {
+        validate_challenge_template(DEFAULT_CHALLENGE_TEMPLATE).expect("default template");
+
+        let err = validate_challenge_template("Login {wallet} at {hostname}\nNonce: {nonce}")
+            .expect_err("unknown placeholder");
+        assert!(
+            err.contains("unsupported placeholder '{hostname}'"),
+            "{err}"
+        );
+
+        let err = validate_challenge_template("Login {wallet").expect_err("unmatched brace");
+        assert!(err.contains("unmatched"), "{err}");
+
+        let err = validate_challenge_template("Login {wallet} with {nonce}")
+            .expect_err("nonce line required");
+        assert!(err.contains("Nonce: {nonce}"), "{err}");
+
+        let err = validate_challenge_template("Login {wallet}\nNonce: {nonce}")
+            .expect_err("chain_id required");
+        assert!(err.contains("Chain ID: {chain_id}"), "{err}");
+
+        let err = validate_challenge_template("Network: {chain_id}\nNonce: {nonce}")
+            .expect_err("chain_id outside a Chain ID line is never verified");
+        assert!(err.contains("Chain ID: {chain_id}"), "{err}");
+        validate_challenge_template("  Chain ID: {chain_id}\nNonce: {nonce}")
+            .expect("indented Chain ID line");
+    }
+
+    #[test]
+    fn validate_user_config_checks_domain_override_keys() {
+        let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let mut config = sample_user_config(wallet);
+        config.domain_overrides.insert(
+            "agent_seed_mission_md".to_string(),
+            serde_json::json!("# Mission"),
+        );
+        assert!(validate_user_config(&config).is_ok());
+
+        config.domain_overrides.insert(
+            FRONTDOOR_PREFLIGHT_OVERRIDE_KEY.to_string(),
+            serde_json::json!({ "gas_ready": false }),
+        );
+        assert!(validate_user_config(&config).is_ok());
+
+        config.domain_overrides.insert(
+            "frotdoor_preflight".to_string(),
+            serde_json::json!({ "gas_ready": false }),
+        );
+        let errors = validate_user_config(&config).expect_err("typo must be rejected");
+        assert_eq!(
+            errors.fields().collect::<Vec<_>>(),
+            vec!["domain_overrides"]
+        );
+        assert_eq!(
+            errors[0].message,
+            format!(
+                "domain_overrides key 'frotdoor_preflight' is not allowed for profile_domain '{}'",
+                config.profile_domain
+            )
+        );
+    }
+
+    #[test]
+    fn validate_user_config_reports_every_offending_field() {
+        let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let mut config = sample_user_config(wallet);
+        config.verification_eigencloud_timeout_ms = 0;
+        config.custody_mode = "operator_wallet".to_string();
+        config.operator_wallet_address = None;
+        config.profile_domain = "Not A Domain!".to_string();
+
+        let errors = validate_user_config(&config).expect_err("config must fail validation");
+        let fields: Vec<&str> = errors.fields().collect();
+        assert_eq!(
+            fields,
+            vec![
+                "profile_domain",
+                "operator_wallet_address",
+                "verification_eigencloud_timeout_ms",
+            ]
+        );
+        assert_eq!(
+            errors[1].message,
+            "operator_wallet_address is required for custody_mode operator_wallet/dual_mode"
+        );
+        assert_eq!(
+            errors.to_string(),
+            errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+
+        let err = FrontdoorError::InvalidConfig(errors);
+        assert_eq!(err.status_code(), 400);
+        assert!(
+            err.to_string()
+                .contains("verification_eigencloud_timeout_ms")
+        );
+
+        assert!(validate_user_config(&sample_user_config(wallet)).is_ok());
+    }
+
+    #[test]
+    fn seeded_rng_source_generates_stable_gateway_auth_keys() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let seeded =
+            || -> Arc<dyn RngSource> { Arc::new(std::sync::Mutex::new(StdRng::seed_from_u64(7))) };
+
+        let key = generate_gateway_auth_key(seeded().as_ref());
+        assert_eq!(key, "lc_aBJTDJpinRv8mLvQeIYugoZB");
+        assert!(is_valid_gateway_auth_key(&key));
+        assert_eq!(generate_gateway_auth_key(seeded().as_ref()), key);
+
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_with_sources(
+            SimulatedProvisioning::ready("https://simulated.example/gateway").frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+            Arc::new(SystemClock),
+            Arc::new(NoopMetrics),
+            seeded(),
+        );
+        let suggested = service
+            .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                wallet_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                intent: "paper trade BTC".to_string(),
+                domain: Some("hyperliquid".to_string()),
+                gateway_auth_key: None,
+                base_config: None,
+            })
+            .expect("suggest config");
+        assert_eq!(suggested.config.gateway_auth_key, key);
+    }
+
+    #[test]
+    fn dual_mode_wallet_association_requires_two_distinct_wallets() {
+        let connected = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let operator = "0x1111111111111111111111111111111111111111";
+        let mut config = sample_user_config(connected);
+        config.custody_mode = "dual_mode".to_string();
+
+        config.operator_wallet_address = None;
+        assert_eq!(
+            validate_wallet_association(&config, connected).unwrap_err(),
+            "dual_mode requires both operator_wallet_address and user_wallet_address"
+        );
+
+        config.operator_wallet_address = Some(connected.to_ascii_lowercase());
+        assert_eq!(
+            validate_wallet_association(&config, connected).unwrap_err(),
+            "dual_mode requires distinct operator_wallet_address and user_wallet_address"
+        );
+
+        config.operator_wallet_address = Some(operator.to_string());
+        assert!(validate_wallet_association(&config, connected).is_ok());
+        assert!(validate_wallet_association(&config, operator).is_ok());
+        assert!(
+            validate_wallet_association(&config, "0x8ba1f109551bD432803012645Ac136ddd64DBA72")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn operator_custody_raises_information_sharing_scope() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            SimulatedProvisioning::ready("https://shared.example.com").frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let suggest = |custody_mode: &str, scope: &str| {
+            let mut base = sample_user_config(connected_wallet);
+            base.custody_mode = custody_mode.to_string();
+            if custody_mode != "user_wallet" {
+                base.operator_wallet_address =
+                    Some("0x1111111111111111111111111111111111111111".to_string());
+            }
+            base.information_sharing_scope = scope.to_string();
+            service
+                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                    wallet_address: connected_wallet.to_string(),
+                    intent: "steady paper trading".to_string(),
+                    domain: Some("hyperliquid".to_string()),
+                    gateway_auth_key: None,
+                    base_config: Some(base),
+                })
+                .expect("suggest config")
+        };
 
-            let preflight = service
-                .funding_preflight(session_uuid)
-                .await
-                .expect("funding preflight");
-            assert_eq!(preflight.status, "passed");
+        for (custody_mode, scope) in [("operator_wallet", "none"), ("dual_mode", "signals_only")] {
+            let suggested = suggest(custody_mode, scope);
+            assert_eq!(
+                suggested.config.information_sharing_scope,
+                "signals_and_execution"
+            );
             assert!(
-                preflight
-                    .checks
+                suggested
+                    .assumptions
                     .iter()
-                    .all(|check| !check.check_id.is_empty() && !check.status.is_empty())
+                    .any(|assumption| assumption.contains("information_sharing_scope")),
+                "{:?}",
+                suggested.assumptions
             );
+        }
 
-            let invalid_runtime = service
-                .runtime_control(
-                    session_uuid,
-                    FrontdoorRuntimeControlRequest {
-                        action: "invalid".to_string(),
-                        actor: None,
-                    },
-                )
-                .await;
-            assert!(invalid_runtime.is_err());
-
-            let runtime = service
-                .runtime_control(
-                    session_uuid,
-                    FrontdoorRuntimeControlRequest {
-                        action: "pause".to_string(),
-                        actor: Some("operator".to_string()),
-                    },
-                )
-                .await
-                .expect("runtime control");
-            assert!(runtime.status == "applied" || runtime.status == "noop");
-            assert_eq!(runtime.runtime_state, "paused");
+        let audited = suggest("operator_wallet", "full_audit");
+        assert_eq!(audited.config.information_sharing_scope, "full_audit");
 
-            let todos = service
-                .gateway_todos_for_session(session_uuid)
-                .await
-                .expect("gateway todos");
-            assert!(!todos.todos.is_empty());
+        for scope in ["none", "full_audit"] {
+            let suggested = suggest("user_wallet", scope);
+            assert_eq!(suggested.config.custody_mode, "user_wallet");
+            assert_eq!(suggested.config.information_sharing_scope, scope);
             assert!(
-                todos
-                    .todos
+                !suggested
+                    .assumptions
                     .iter()
-                    .all(|todo| !todo.todo_id.is_empty() && !todo.owner.is_empty())
+                    .any(|assumption| assumption.contains("information_sharing_scope"))
             );
+        }
+    }
 
-            let session_payload = service.get_session(session_uuid).await.expect("session");
-            let json = serde_json::to_value(session_payload).expect("session json");
-            let obj = json.as_object().expect("object");
-            assert!(obj.contains_key("launched_on_eigencloud"));
-            assert!(obj.contains_key("verification_backend"));
-            assert!(obj.contains_key("verification_level"));
-            assert!(obj.contains_key("verification_fallback_enabled"));
-            assert!(obj.contains_key("verification_fallback_require_signed_receipts"));
-        });
+    #[test]
+    fn symbol_denylist_overrides_allowlist() {
+        let tmp = tempdir().expect("tempdir");
+        let service = FrontdoorService::new_for_tests(
+            SimulatedProvisioning::ready("https://shared.example.com").frontdoor_config(),
+            tmp.path().join("wallet_sessions.json"),
+        );
+        let connected_wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let suggest = |domain: &str, allowlist: &[&str], denylist: &[&str]| {
+            let mut base = sample_user_config(connected_wallet);
+            base.symbol_allowlist = allowlist.iter().map(|s| s.to_string()).collect();
+            base.symbol_denylist = denylist.iter().map(|s| s.to_string()).collect();
+            service
+                .suggest_config(crate::channels::web::types::FrontdoorSuggestConfigRequest {
+                    wallet_address: connected_wallet.to_string(),
+                    intent: "steady paper trading".to_string(),
+                    domain: Some(domain.to_string()),
+                    gateway_auth_key: None,
+                    base_config: Some(base),
+                })
+                .expect("suggest config")
+        };
+
+        let overlapping = suggest("hyperliquid", &["BTC", "ETH"], &["eth"]);
+        assert!(overlapping.validated);
+        assert_eq!(overlapping.config.symbol_allowlist, vec!["BTC".to_string()]);
+        assert!(
+            overlapping
+                .assumptions
+                .iter()
+                .any(|assumption| assumption.contains("denylisted symbols")
+                    && assumption.contains("ETH")),
+            "{:?}",
+            overlapping.assumptions
+        );
+
+        let general = suggest("general", &["BTC", "ETH"], &["ETH"]);
+        assert!(general.config.symbol_allowlist.is_empty());
+        assert!(general.config.symbol_denylist.is_empty());
+
+        let mut emptied = sample_user_config(connected_wallet);
+        emptied.symbol_allowlist = vec!["BTC".to_string(), "ETH".to_string()];
+        emptied.symbol_denylist = vec!["BTC".to_string(), "ETH".to_string(), "SOL".to_string()];
+        let errors = validate_user_config(&emptied).expect_err("denylist empties allowlist");
+        assert!(
+            errors.iter().any(|error| error.field == "symbol_denylist"
+                && error.message.contains("excludes every symbol")),
+            "{errors}"
+        );
     }
 
     #[test]
-    fn operator_vs_public_monitor_payloads_are_separated() {
+    fn validate_user_config_requires_terms_version_and_time_when_accepted() {
+        let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18";
+        let mut config = sample_user_config(wallet);
+        config.accepted_terms_version = None;
+        config.accepted_terms_at = None;
+        let errors = validate_user_config(&config).expect_err("terms version required");
+        assert_eq!(
+            errors.fields().collect::<Vec<_>>(),
+            vec!["accepted_terms_version", "accepted_terms_at"]
+        );
+
+        config.accepted_terms_version = Some(DEFAULT_TERMS_VERSION.to_string());
+        config.accepted_terms_at = Some("yesterday".to_string());
+        let errors = validate_user_config(&config).expect_err("timestamp must parse");
+        assert_eq!(
+            errors.fields().collect::<Vec<_>>(),
+            vec!["accepted_terms_at"]
+        );
+
+        stamp_terms_acceptance(&mut config, Utc::now());
+        assert_eq!(config.accepted_terms_at.as_deref(), Some("yesterday"));
+        config.accepted_terms_at = None;
+        stamp_terms_acceptance(&mut config, Utc::now());
+        assert!(validate_user_config(&config).is_ok());
+    }
+
+    #[test]
+    fn verify_requires_terms_version_and_stamps_acceptance_time() {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("tokio runtime");
         rt.block_on(async {
-            let tmp = tempdir().expect("tempdir");
-            let service = FrontdoorService::new_for_tests(
-                FrontdoorConfig {
-                    require_privy: false,
-                    privy_app_id: None,
-                    privy_client_id: None,
-                    provision_command: None,
-                    default_instance_url: None,
-                    allow_default_instance_fallback: false,
-                    verify_app_base_url: None,
-                    session_ttl_secs: 900,
-                    poll_interval_ms: 1000,
-                },
-                tmp.path().join("wallet_sessions.json"),
-            );
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
 
-            let wallet = "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string();
-            let challenge = service
-                .create_challenge(FrontdoorChallengeRequest {
-                    wallet_address: wallet.clone(),
-                    privy_user_id: None,
-                    chain_id: Some(1),
-                })
+            let challenge = harness.challenge().await;
+            let mut unversioned = sample_user_config(&harness.wallet);
+            unversioned.accepted_terms_version = None;
+            unversioned.accepted_terms_at = None;
+            let err = harness
+                .verify_with_config(&challenge, unversioned)
                 .await
-                .expect("challenge");
+                .expect_err("terms version required");
+            assert_eq!(
+                err.to_string(),
+                "accepted_terms_version is required when accept_terms is true"
+            );
 
-            let (public_total, public_sessions) = service
-                .list_sessions(Some(&wallet), 10)
+            let challenge = harness.challenge().await;
+            let mut versioned = sample_user_config(&harness.wallet);
+            versioned.accepted_terms_at = None;
+            harness
+                .verify_with_config(&challenge, versioned)
                 .await
-                .expect("public sessions");
-            assert_eq!(public_total, 1);
-            assert_eq!(public_sessions.len(), 1);
-            assert_eq!(public_sessions[0].session_ref, "v1");
-            assert_eq!(public_sessions[0].verification_backend, "unknown");
-            assert_eq!(public_sessions[0].verification_level, "unknown");
-            assert!(!public_sessions[0].verification_fallback_enabled);
-            assert!(!public_sessions[0].verification_fallback_require_signed_receipts);
-            assert!(!public_sessions[0].launched_on_eigencloud);
+                .expect("versioned acceptance verifies");
 
-            let public_json = serde_json::to_value(&public_sessions[0]).expect("public json");
-            let public_obj = public_json.as_object().expect("public object");
-            assert!(!public_obj.contains_key("session_id"));
-            assert!(!public_obj.contains_key("instance_url"));
-            assert!(!public_obj.contains_key("app_url"));
-            assert!(!public_obj.contains_key("verify_url"));
-            assert!(!public_obj.contains_key("eigen_app_id"));
-            assert!(!public_obj.contains_key("error"));
+            let state = harness.service.state.read().await;
+            let session = state
+                .sessions
+                .get(&harness.session_uuid(&challenge))
+                .expect("session");
+            let stored = session.config.as_ref().expect("stored config");
+            assert_eq!(
+                stored.accepted_terms_version.as_deref(),
+                Some(DEFAULT_TERMS_VERSION)
+            );
+            assert_eq!(
+                stored.accepted_terms_at.as_deref(),
+                Some(harness.clock.now().to_rfc3339().as_str())
+            );
+        });
+    }
 
-            let (operator_total, operator_sessions) = service
-                .list_sessions_full(Some(&wallet), 10)
-                .await
-                .expect("operator sessions");
-            assert_eq!(operator_total, 1);
-            assert_eq!(operator_sessions.len(), 1);
-            assert_eq!(operator_sessions[0].session_id, challenge.session_id);
-            assert_eq!(operator_sessions[0].verification_backend, "unknown");
-            assert_eq!(operator_sessions[0].verification_level, "unknown");
+    #[test]
+    fn onboarding_accept_terms_captures_current_terms_version() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        rt.block_on(async {
+            let harness = FrontdoorTestHarness::new(SimulatedProvisioning::ready(
+                "https://simulated.example/gateway",
+            ));
+            let challenge = harness.challenge().await;
+            harness.chat(&challenge, "Run a paper BTC strategy").await;
+            harness.chat(&challenge, "confirm plan").await;
+            harness.chat(&challenge, "profile_name=demo_profile").await;
+
+            let captured = |state: &FrontdoorState| {
+                state.sessions[&harness.session_uuid(&challenge)]
+                    .onboarding
+                    .captured_variables
+                    .get("accepted_terms_version")
+                    .cloned()
+            };
+            assert_eq!(captured(&*harness.service.state.read().await), None);
 
-            let operator_json = serde_json::to_value(&operator_sessions[0]).expect("operator json");
-            let operator_obj = operator_json.as_object().expect("operator object");
-            assert!(operator_obj.contains_key("session_id"));
-            assert!(operator_obj.contains_key("launched_on_eigencloud"));
-            assert!(operator_obj.contains_key("verification_backend"));
-            assert!(operator_obj.contains_key("verification_level"));
-            assert!(operator_obj.contains_key("verification_fallback_enabled"));
-            assert!(operator_obj.contains_key("verification_fallback_require_signed_receipts"));
+            harness.chat(&challenge, "I accept the terms").await;
+            assert_eq!(
+                captured(&*harness.service.state.read().await).as_deref(),
+                Some(DEFAULT_TERMS_VERSION)
+            );
         });
     }
 
     #[test]
-    fn onboarding_gateway_auth_marker_counts_as_resolved_requirement() {
-        let mut captured = HashMap::new();
-        captured.insert("profile_name".to_string(), "demo_profile".to_string());
-        captured.insert(
-            "gateway_auth_key".to_string(),
-            "__from_config__".to_string(),
+    fn gateway_auth_key_strength_rejects_repeated_characters() {
+        let key = "aaaaaaaaaaaaaaaa";
+        assert!(is_well_formed_gateway_auth_key(key));
+        assert_eq!(gateway_auth_key_strength(key), KeyStrength::Weak);
+        assert!(!is_valid_gateway_auth_key(key));
+        assert_eq!(
+            gateway_auth_key_strength("MyPassword-2026-xyz!"),
+            KeyStrength::Weak
         );
-        captured.insert("accept_terms".to_string(), "true".to_string());
 
-        let payload = build_onboarding_step3_payload(&captured);
-        assert_eq!(payload.unresolved_required_count, 0);
-        let gateway = payload
-            .required_variables
-            .iter()
-            .find(|item| item.field == "gateway_auth_key")
-            .expect("gateway_auth_key required variable");
-        assert_eq!(gateway.status, "resolved");
+        let mut config = sample_user_config("0x9431Cf5DA0CE60664661341db650763B08286B18");
+        config.gateway_auth_key = key.to_string();
+        let errors = validate_user_config(&config).expect_err("weak key rejected");
+        assert_eq!(
+            errors.fields().collect::<Vec<_>>(),
+            vec!["gateway_auth_key"]
+        );
+        assert!(errors.to_string().contains("too weak"));
     }
 
-    fn sample_user_config(wallet: &str) -> FrontdoorUserConfig {
-        FrontdoorUserConfig {
-            config_version: FRONTDOOR_CURRENT_CONFIG_VERSION,
-            profile_domain: "hyperliquid".to_string(),
-            domain_overrides: HashMap::new(),
-            inference_summary: None,
-            inference_confidence: None,
-            inference_warnings: Vec::new(),
-            profile_name: "demo_profile".to_string(),
-            hyperliquid_network: "testnet".to_string(),
-            paper_live_policy: "paper_only".to_string(),
-            hyperliquid_api_base_url: None,
-            hyperliquid_ws_url: None,
-            request_timeout_ms: 10_000,
-            max_retries: 3,
-            retry_backoff_ms: 500,
-            max_position_size_usd: 1_000,
-            leverage_cap: 2,
-            max_allocation_usd: 1_000,
-            per_trade_notional_cap_usd: 100,
-            max_leverage: 2,
-            max_slippage_bps: 50,
-            symbol_allowlist: vec!["BTC".to_string(), "ETH".to_string()],
-            symbol_denylist: vec![],
-            custody_mode: "user_wallet".to_string(),
-            operator_wallet_address: None,
-            user_wallet_address: Some(wallet.to_string()),
-            vault_address: None,
-            information_sharing_scope: "signals_only".to_string(),
-            kill_switch_enabled: true,
-            kill_switch_behavior: "pause_agent".to_string(),
-            enable_memory: true,
-            gateway_auth_key: "supersecuregatewaykey01".to_string(),
-            eigencloud_auth_key: None,
-            verification_backend: "eigencloud_primary".to_string(),
-            verification_eigencloud_endpoint: None,
-            verification_eigencloud_auth_scheme: "bearer".to_string(),
-            verification_eigencloud_timeout_ms: 5_000,
-            verification_fallback_enabled: true,
-            verification_fallback_signing_key_id: None,
-            verification_fallback_chain_path: None,
-            verification_fallback_require_signed_receipts: true,
-            accept_terms: true,
+    #[test]
+    fn gateway_auth_key_strength_accepts_borderline_key() {
+        // Exactly the minimum distinct characters, but short and repetitive.
+        let key = "k3yFrnt7k3yFrnt7";
+        assert_eq!(
+            key.chars().collect::<HashSet<_>>().len(),
+            GATEWAY_AUTH_KEY_MIN_DISTINCT_CHARS
+        );
+        assert_eq!(gateway_auth_key_strength(key), KeyStrength::Acceptable);
+        assert!(is_valid_gateway_auth_key(key));
+        assert_eq!(
+            gateway_auth_key_strength("k3yFrntkk3yFrntk"),
+            KeyStrength::Weak
+        );
+    }
+
+    #[test]
+    fn generated_gateway_auth_keys_are_strong() {
+        let rng = ThreadRngSource;
+        for _ in 0..200 {
+            let key = generate_gateway_auth_key(&rng);
+            assert_eq!(
+                gateway_auth_key_strength(&key),
+                KeyStrength::Strong,
+                "{key}"
+            );
+            assert!(is_valid_gateway_auth_key(&key));
+        }
+    }
+
+    #[test]
+    fn degenerate_rng_source_still_yields_a_strong_gateway_auth_key() {
+        struct Constant;
+        impl RngSource for Constant {
+            fn alphanumeric(&self, len: usize) -> String {
+                "a".repeat(len)
+            }
+        }
+
+        let key = generate_gateway_auth_key(&Constant);
+        assert_eq!(
+            gateway_auth_key_strength(&key),
+            KeyStrength::Strong,
+            "{key}"
+        );
+        assert!(is_valid_gateway_auth_key(&key));
+        assert_ne!(key, format!("lc_{}", "a".repeat(24)));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let key = structured_gateway_auth_key(&mut rng);
+            assert_eq!(key.len(), 27);
+            assert_eq!(key[3..].chars().collect::<HashSet<_>>().len(), 24, "{key}");
+            assert_eq!(
+                gateway_auth_key_strength(&key),
+                KeyStrength::Strong,
+                "{key}"
+            );
         }
     }
+
+    #[test]
+    fn provision_placeholders_accepted_by_parser_are_all_substituted() {
+        let tokens: HashSet<&str> = PROVISION_PLACEHOLDERS.iter().map(|p| p.token).collect();
+        assert_eq!(
+            tokens.len(),
+            PROVISION_PLACEHOLDERS.len(),
+            "duplicate token"
+        );
+
+        let template = PROVISION_PLACEHOLDERS
+            .iter()
+            .map(|placeholder| format!("{{{}}}", placeholder.token))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parsed = parse_provision_command_template(&format!("echo {template}"))
+            .expect("every placeholder parses");
+        assert!(parse_provision_command_template("echo {not_a_placeholder}").is_err());
+
+        let wallet = "0xe10e3def5348cb4151a8b99beebfd43646bade59";
+        let config = sample_user_config(wallet);
+        let input = ProvisionCommandInput {
+            session_id: Uuid::new_v4(),
+            wallet,
+            privy_user_id: None,
+            privy_identity_token: None,
+            privy_access_token: None,
+            chain_id: 1,
+            version: 1,
+            config: &config,
+            verify_base_url: None,
+        };
+        let command = build_provision_command(&parsed, &input).expect("command");
+        let script = command
+            .as_std()
+            .get_args()
+            .nth(1)
+            .and_then(|arg| arg.to_str())
+            .expect("shell script")
+            .to_string();
+
+        let substituted: HashSet<&str> = PROVISION_PLACEHOLDERS
+            .iter()
+            .filter(|placeholder| !script.contains(&format!("{{{}}}", placeholder.token)))
+            .filter(|placeholder| {
+                command
+                    .as_std()
+                    .get_envs()
+                    .any(|(key, value)| key == placeholder.env_key && value.is_some())
+            })
+            .map(|placeholder| placeholder.token)
+            .collect();
+        assert_eq!(substituted, tokens);
+    }
 }
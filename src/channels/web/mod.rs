@@ -33,7 +33,7 @@ use tokio_stream::wrappers::ReceiverStream;
 use crate::agent::SessionManager;
 use crate::channels::{Channel, IncomingMessage, MessageStream, OutgoingResponse, StatusUpdate};
 use crate::config::GatewayConfig;
-use crate::db::Database;
+use crate::db::{Database, FrontdoorSessionStore};
 use crate::error::ChannelError;
 use crate::extensions::ExtensionManager;
 use crate::orchestrator::job_manager::ContainerJobManager;
@@ -60,7 +60,7 @@ impl GatewayChannel {
     /// Create a new gateway channel.
     ///
     /// If no auth token is configured, generates a random one and prints it.
-    pub fn new(config: GatewayConfig) -> Self {
+    pub fn new(config: GatewayConfig, metrics: Arc<dyn crate::observability::Metrics>) -> Self {
         let auth_token = config.auth_token.clone().unwrap_or_else(|| {
             use rand::Rng;
             let token: String = rand::thread_rng()
@@ -88,18 +88,37 @@ impl GatewayChannel {
             llm_provider: None,
             skill_registry: None,
             skill_catalog: None,
+            module_catalog: Arc::new(crate::platform::ModuleCatalog::curated()),
             frontdoor: config.frontdoor.clone().map(|fd| {
-                frontdoor::FrontdoorService::new(frontdoor::FrontdoorConfig {
-                    require_privy: fd.require_privy,
-                    privy_app_id: fd.privy_app_id,
-                    privy_client_id: fd.privy_client_id,
-                    provision_command: fd.provision_command,
-                    default_instance_url: fd.default_instance_url,
-                    allow_default_instance_fallback: fd.allow_default_instance_fallback,
-                    verify_app_base_url: fd.verify_app_base_url,
-                    session_ttl_secs: fd.session_ttl_secs,
-                    poll_interval_ms: fd.poll_interval_ms,
-                })
+                frontdoor::FrontdoorService::new(
+                    frontdoor::FrontdoorConfig {
+                        require_privy: fd.require_privy,
+                        privy_app_id: fd.privy_app_id,
+                        privy_client_id: fd.privy_client_id,
+                        provision_command: fd.provision_command,
+                        default_instance_url: fd.default_instance_url,
+                        allow_default_instance_fallback: fd.allow_default_instance_fallback,
+                        verify_app_base_url: fd.verify_app_base_url,
+                        session_ttl_secs: fd.session_ttl_secs,
+                        poll_interval_ms: fd.poll_interval_ms,
+                        force_signed_receipts: fd.force_signed_receipts,
+                        wallet_retention_secs: fd.wallet_retention_secs,
+                        challenge_rate_limit: fd.challenge_rate_limit,
+                        challenge_rate_window_secs: fd.challenge_rate_window_secs,
+                        terminal_retention_secs: fd.terminal_retention_secs,
+                        provision_timeout_secs: fd.provision_timeout_secs,
+                        challenge_template: fd.challenge_template,
+                        allowed_chain_ids: fd.allowed_chain_ids,
+                        terms_version: fd.terms_version,
+                        max_provision_retries: fd.max_provision_retries,
+                        hyperliquid_mainnet_perps: fd.hyperliquid_mainnet_perps,
+                        hyperliquid_testnet_perps: fd.hyperliquid_testnet_perps,
+                        hyperliquid_perp_refresh: fd.hyperliquid_perp_refresh,
+                        max_onboarding_turns: fd.max_onboarding_turns,
+                        status_webhook_url: fd.status_webhook_url,
+                    },
+                    metrics,
+                )
             }),
             chat_rate_limiter: server::RateLimiter::new(30, 60),
         });
@@ -130,6 +149,7 @@ impl GatewayChannel {
             llm_provider: self.state.llm_provider.clone(),
             skill_registry: self.state.skill_registry.clone(),
             skill_catalog: self.state.skill_catalog.clone(),
+            module_catalog: Arc::clone(&self.state.module_catalog),
             frontdoor: self.state.frontdoor.clone(),
             chat_rate_limiter: server::RateLimiter::new(30, 60),
         };
@@ -207,6 +227,12 @@ impl GatewayChannel {
         self
     }
 
+    /// Inject the module catalog the module and routing APIs consult.
+    pub fn with_module_catalog(mut self, catalog: Arc<crate::platform::ModuleCatalog>) -> Self {
+        self.rebuild_state(|s| s.module_catalog = catalog);
+        self
+    }
+
     /// Inject the LLM provider for OpenAI-compatible API proxy.
     pub fn with_llm_provider(mut self, llm: Arc<dyn crate::llm::LlmProvider>) -> Self {
         self.rebuild_state(|s| s.llm_provider = Some(llm));
@@ -244,6 +270,18 @@ impl Channel for GatewayChannel {
                 ),
             })?;
 
+        if let (Some(frontdoor), Some(store)) = (&self.state.frontdoor, &self.state.store) {
+            let session_store: Arc<dyn FrontdoorSessionStore> = store.clone();
+            match frontdoor.attach_session_store(session_store).await {
+                Ok(restored) => {
+                    tracing::info!(restored, "Restored persisted frontdoor sessions");
+                }
+                Err(err) => {
+                    tracing::warn!(error = %err, "Frontdoor session persistence unavailable");
+                }
+            }
+        }
+
         server::start_server(addr, self.state.clone(), self.auth_token.clone()).await?;
 
         Ok(Box::pin(ReceiverStream::new(rx)))
@@ -373,6 +411,9 @@ impl Channel for GatewayChannel {
         if let Some(tx) = self.state.shutdown_tx.write().await.take() {
             let _ = tx.send(());
         }
+        if let Some(frontdoor) = &self.state.frontdoor {
+            frontdoor.shutdown();
+        }
         *self.state.msg_tx.write().await = None;
         Ok(())
     }
@@ -150,6 +150,8 @@ pub struct GatewayState {
     pub skill_registry: Option<Arc<std::sync::RwLock<crate::skills::SkillRegistry>>>,
     /// Skill catalog for searching the ClawHub registry.
     pub skill_catalog: Option<Arc<crate::skills::catalog::SkillCatalog>>,
+    /// Module catalog behind the module management and routing APIs.
+    pub module_catalog: Arc<crate::platform::ModuleCatalog>,
     /// Frontdoor wallet-auth/session provisioning flow (user-facing mode).
     pub frontdoor: Option<Arc<crate::channels::web::frontdoor::FrontdoorService>>,
     /// Rate limiter for chat endpoints (30 messages per 60 seconds).
@@ -217,6 +219,10 @@ pub async fn start_server(
             "/api/frontdoor/session/{session_id}",
             get(frontdoor_session_handler),
         )
+        .route(
+            "/api/frontdoor/session/{session_id}/retry",
+            post(frontdoor_retry_provision_handler),
+        )
         .route(
             "/api/frontdoor/session/{session_id}/timeline",
             get(frontdoor_session_timeline_handler),
@@ -575,6 +581,7 @@ async fn frontdoor_bootstrap_handler(
             default_instance_looks_eigencloud: false,
             poll_interval_ms: 1500,
             mandatory_steps: Vec::new(),
+            terms_version: crate::channels::web::frontdoor::DEFAULT_TERMS_VERSION.to_string(),
         })
     }
 }
@@ -609,6 +616,13 @@ async fn frontdoor_experience_manifest_handler(
     Ok(Json(frontdoor.experience_manifest()))
 }
 
+fn frontdoor_error_response(
+    err: crate::channels::web::frontdoor::FrontdoorError,
+) -> (StatusCode, String) {
+    let status = StatusCode::from_u16(err.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    (status, err.to_string())
+}
+
 async fn frontdoor_onboarding_state_handler(
     State(state): State<Arc<GatewayState>>,
     Query(query): Query<FrontdoorOnboardingStateQuery>,
@@ -638,7 +652,7 @@ async fn frontdoor_onboarding_chat_handler(
         .onboarding_chat(req)
         .await
         .map(Json)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .map_err(frontdoor_error_response)
 }
 
 async fn frontdoor_challenge_handler(
@@ -653,7 +667,7 @@ async fn frontdoor_challenge_handler(
         .create_challenge(req)
         .await
         .map(Json)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .map_err(frontdoor_error_response)
 }
 
 async fn frontdoor_suggest_config_handler(
@@ -667,7 +681,7 @@ async fn frontdoor_suggest_config_handler(
     frontdoor
         .suggest_config(req)
         .map(Json)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .map_err(frontdoor_error_response)
 }
 
 async fn frontdoor_verify_handler(
@@ -683,7 +697,7 @@ async fn frontdoor_verify_handler(
         .verify_and_start(req)
         .await
         .map(Json)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .map_err(frontdoor_error_response)
 }
 
 async fn frontdoor_session_handler(
@@ -703,6 +717,24 @@ async fn frontdoor_session_handler(
         .ok_or((StatusCode::NOT_FOUND, "session not found".to_string()))
 }
 
+async fn frontdoor_retry_provision_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(session_id): Path<String>,
+) -> Result<Json<FrontdoorVerifyResponse>, (StatusCode, String)> {
+    let frontdoor = state.frontdoor.as_ref().ok_or((
+        StatusCode::NOT_FOUND,
+        "Frontdoor provisioning is not enabled".to_string(),
+    ))?;
+    let session_id = Uuid::parse_str(session_id.trim())
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid session id".to_string()))?;
+    frontdoor
+        .clone()
+        .retry_provision(session_id)
+        .await
+        .map(Json)
+        .map_err(frontdoor_error_response)
+}
+
 async fn frontdoor_session_timeline_handler(
     State(state): State<Arc<GatewayState>>,
     Path(session_id): Path<String>,
@@ -752,7 +784,7 @@ async fn frontdoor_runtime_control_handler(
         .runtime_control(session_id, req)
         .await
         .map(Json)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+        .map_err(frontdoor_error_response)
 }
 
 async fn frontdoor_gateway_todos_handler(
@@ -802,14 +834,21 @@ async fn frontdoor_sessions_handler(
         "wallet_address query parameter is required".to_string(),
     ))?;
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0);
     let (total, sessions) = frontdoor
-        .list_sessions(Some(wallet_address.as_str()), limit)
+        .list_sessions(
+            Some(wallet_address.as_str()),
+            query.status.as_deref(),
+            offset,
+            limit,
+        )
         .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .map_err(frontdoor_error_response)?;
     Ok(Json(FrontdoorSessionMonitorResponse {
         generated_at: chrono::Utc::now().to_rfc3339(),
         wallet_address,
         limit,
+        offset,
         total,
         sessions,
     }))
@@ -827,7 +866,7 @@ async fn frontdoor_operator_sessions_handler(
     let (total, sessions) = frontdoor
         .list_sessions_full(query.wallet_address.as_deref(), limit)
         .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .map_err(frontdoor_error_response)?;
     Ok(Json(FrontdoorOperatorSessionMonitorResponse {
         generated_at: chrono::Utc::now().to_rfc3339(),
         wallet_address: query.wallet_address,
@@ -856,7 +895,7 @@ async fn gateway_todos_handler(
     let (total, sessions) = frontdoor
         .gateway_todos(query.wallet_address.as_deref(), session_id, limit)
         .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .map_err(frontdoor_error_response)?;
     Ok(Json(GatewayTodoListResponse {
         generated_at: chrono::Utc::now().to_rfc3339(),
         total,
@@ -878,7 +917,11 @@ async fn chat_send_handler(
     }
 
     let module_states = load_module_state(&state).await;
-    let route_resolution = crate::platform::resolve_inference_route(&req.content, &module_states);
+    let route_resolution = crate::platform::resolve_inference_route(
+        &state.module_catalog,
+        &req.content,
+        &module_states,
+    );
     if !route_resolution.allowed {
         return Err((
             StatusCode::FORBIDDEN,
@@ -921,6 +964,7 @@ async fn chat_send_handler(
     metadata.insert(
         "module_capabilities".to_string(),
         serde_json::json!(crate::platform::module_capability_keys(
+            &state.module_catalog,
             &effective_module_id
         )),
     );
@@ -3222,9 +3266,11 @@ const PLATFORM_ORG_MEMBERS_KEY: &str = "platform.org.members";
 
 // --- Modules handlers ---
 
-async fn modules_catalog_handler() -> Json<ModuleCatalogResponse> {
+async fn modules_catalog_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Json<ModuleCatalogResponse> {
     Json(ModuleCatalogResponse {
-        modules: crate::platform::curated_module_catalog(),
+        modules: state.module_catalog.modules().to_vec(),
     })
 }
 
@@ -3264,7 +3310,7 @@ async fn modules_health_handler(
     let checks = serde_json::json!({
         "module_id": module.module_id,
         "enabled": module.enabled,
-        "catalog_entry_exists": crate::platform::module_exists(&module_id),
+        "catalog_entry_exists": crate::platform::module_exists(&state.module_catalog, &module_id),
         "verification_status": verification.status,
         "verification_backend": verification.backend,
     });
@@ -3286,7 +3332,7 @@ async fn modules_config_handler(
     Path(module_id): Path<String>,
     Json(body): Json<ModuleConfigUpdateRequest>,
 ) -> Result<Json<ModuleUpdateResponse>, (StatusCode, String)> {
-    if !crate::platform::module_exists(&module_id) {
+    if !crate::platform::module_exists(&state.module_catalog, &module_id) {
         return Err((StatusCode::NOT_FOUND, "Unknown module".to_string()));
     }
 
@@ -3333,7 +3379,7 @@ async fn update_module_enabled(
     module_id: &str,
     enabled: bool,
 ) -> Result<Json<ModuleUpdateResponse>, (StatusCode, String)> {
-    if !crate::platform::module_exists(module_id) {
+    if !crate::platform::module_exists(&state.module_catalog, module_id) {
         return Err((StatusCode::NOT_FOUND, "Unknown module".to_string()));
     }
 
@@ -3350,6 +3396,7 @@ async fn update_module_enabled(
         .position(|m| m.module_id == module_id)
         .ok_or((StatusCode::NOT_FOUND, "Unknown module".to_string()))?;
 
+    let current = modules.clone();
     modules[module_idx].enabled = enabled;
     modules[module_idx].status = if enabled {
         "enabled".to_string()
@@ -3357,6 +3404,11 @@ async fn update_module_enabled(
         "disabled".to_string()
     };
     modules[module_idx].updated_at = now;
+    if let Err(violations) =
+        crate::platform::validate_module_transition(&state.module_catalog, &current, &modules)
+    {
+        return Err((StatusCode::CONFLICT, violations.join(" ")));
+    }
     let updated_module = modules[module_idx].clone();
     let serialized = serde_json::to_value(&modules)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
@@ -3376,7 +3428,7 @@ async fn update_module_enabled(
 }
 
 async fn load_module_state(state: &GatewayState) -> Vec<ModuleState> {
-    let defaults = crate::platform::default_module_states();
+    let defaults = crate::platform::default_module_states(&state.module_catalog);
     let Some(store) = state.store.as_ref() else {
         return defaults;
     };
@@ -3400,9 +3452,14 @@ async fn load_module_state(state: &GatewayState) -> Vec<ModuleState> {
     };
 
     if stored.is_empty() {
-        defaults
-    } else {
-        crate::platform::merge_module_states(stored)
+        return defaults;
+    }
+    match crate::platform::merge_module_states(&state.module_catalog, stored) {
+        Ok(states) => states,
+        Err(error) => {
+            tracing::warn!(%error, "Corrupt stored module state; using defaults");
+            defaults
+        }
     }
 }
 
@@ -3672,7 +3729,7 @@ async fn load_effective_settings(state: &GatewayState) -> crate::settings::Setti
     };
 
     match store.get_all_settings(&state.user_id).await {
-        Ok(map) => crate::settings::Settings::from_db_map(&map),
+        Ok(map) => crate::settings::Settings::from_db_map(&map).0,
         Err(e) => {
             tracing::warn!("Failed to load settings for status surfaces: {}", e);
             crate::settings::Settings::default()
@@ -1447,6 +1447,8 @@ function readConfig() {
     verification_fallback_require_signed_receipts:
       verificationFallbackRequireSignedReceipts,
     accept_terms: acceptTerms,
+    accepted_terms_version:
+      acceptTerms && state.bootstrap ? state.bootstrap.terms_version || null : null,
   };
 }
 
@@ -763,6 +763,8 @@ pub struct FrontdoorBootstrapResponse {
     pub default_instance_looks_eigencloud: bool,
     pub poll_interval_ms: u64,
     pub mandatory_steps: Vec<String>,
+    /// Terms version clients must echo as `accepted_terms_version`.
+    pub terms_version: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -849,6 +851,12 @@ pub struct FrontdoorUserConfig {
     #[serde(default = "default_true")]
     pub verification_fallback_require_signed_receipts: bool,
     pub accept_terms: bool,
+    /// Terms version the user accepted; required when `accept_terms` is set.
+    #[serde(default)]
+    pub accepted_terms_version: Option<String>,
+    /// RFC 3339 acceptance time; stamped at verify when absent.
+    #[serde(default)]
+    pub accepted_terms_at: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -887,6 +895,9 @@ pub struct FrontdoorVerifyRequest {
     pub privy_access_token: Option<String>,
     pub message: String,
     pub signature: String,
+    /// Signing scheme used by the wallet: `personal_sign` (default) or `eip712`.
+    #[serde(default)]
+    pub signature_scheme: Option<String>,
     pub config: FrontdoorUserConfig,
 }
 
@@ -964,6 +975,9 @@ pub struct FrontdoorDomainProfile {
     pub default_modules: Vec<String>,
     pub required_fields: Vec<String>,
     pub optional_fields: Vec<String>,
+    /// `domain_overrides` keys accepted for this domain, besides the reserved
+    /// `frontdoor_preflight`.
+    pub allowed_override_keys: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1010,6 +1024,19 @@ pub struct FrontdoorVerifyResponse {
     pub session_id: String,
     pub status: String,
     pub detail: String,
+    /// Fields clamped into policy bounds when the submitted config was resolved.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub config_warnings: Vec<String>,
+}
+
+/// Body POSTed to the frontdoor status webhook on `ready` and `failed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrontdoorStatusWebhookPayload {
+    pub session_id: String,
+    pub wallet_address: String,
+    pub status: String,
+    pub detail: String,
+    pub instance_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1018,6 +1045,11 @@ pub struct FrontdoorSessionListQuery {
     pub wallet_address: Option<String>,
     #[serde(default)]
     pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Lifecycle state to match, e.g. `failed`.
+    #[serde(default)]
+    pub status: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1065,6 +1097,7 @@ pub struct FrontdoorSessionMonitorResponse {
     pub generated_at: String,
     pub wallet_address: String,
     pub limit: usize,
+    pub offset: usize,
     pub total: usize,
     pub sessions: Vec<FrontdoorSessionSummaryResponse>,
 }
@@ -1231,7 +1264,7 @@ pub struct FrontdoorSessionTimelineResponse {
     pub events: Vec<FrontdoorSessionTimelineEvent>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FrontdoorSessionTimelineEvent {
     pub seq_id: u64,
     pub event_type: String,
@@ -1241,6 +1274,13 @@ pub struct FrontdoorSessionTimelineEvent {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct FrontdoorProvisionLogLine {
+    pub source: String,
+    pub stream: String,
+    pub line: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct FrontdoorVerificationExplanationResponse {
     pub session_id: String,
@@ -29,6 +29,13 @@ pub enum ConfigCommand {
         filter: Option<String>,
     },
 
+    /// Show each setting's effective value and the layer it came from
+    Explain {
+        /// Show only settings matching this prefix (e.g., "agent", "heartbeat")
+        #[arg(short, long)]
+        filter: Option<String>,
+    },
+
     /// Get a specific setting value
     Get {
         /// Setting path (e.g., "agent.max_parallel_jobs")
@@ -52,6 +59,9 @@ pub enum ConfigCommand {
 
     /// Show the settings storage info
     Path,
+
+    /// Check env and config files, reporting every invalid value at once
+    Validate,
 }
 
 /// Run a config command.
@@ -59,6 +69,11 @@ pub enum ConfigCommand {
 /// Connects to the database to read/write settings. Falls back to disk
 /// if the database is not available.
 pub async fn run_config_command(cmd: ConfigCommand) -> anyhow::Result<()> {
+    // Validation must not depend on a config that may be the thing that's broken.
+    if matches!(cmd, ConfigCommand::Validate) {
+        return validate_config().await;
+    }
+
     // Try to connect to the DB for settings access
     let db: Option<Arc<dyn crate::db::Database>> = match connect_db().await {
         Ok(d) => Some(d),
@@ -75,10 +90,12 @@ pub async fn run_config_command(cmd: ConfigCommand) -> anyhow::Result<()> {
     match cmd {
         ConfigCommand::Init { output, force } => init_toml(db_ref, output, force).await,
         ConfigCommand::List { filter } => list_settings(db_ref, filter).await,
+        ConfigCommand::Explain { filter } => explain_settings(db_ref, filter).await,
         ConfigCommand::Get { path } => get_setting(db_ref, &path).await,
         ConfigCommand::Set { path, value } => set_setting(db_ref, &path, &value).await,
         ConfigCommand::Reset { path } => reset_setting(db_ref, &path).await,
         ConfigCommand::Path => show_path(db_ref.is_some()),
+        ConfigCommand::Validate => validate_config().await,
     }
 }
 
@@ -98,7 +115,7 @@ const DEFAULT_USER_ID: &str = "default";
 async fn load_settings(store: Option<&dyn crate::db::Database>) -> Settings {
     if let Some(store) = store {
         match store.get_all_settings(DEFAULT_USER_ID).await {
-            Ok(map) if !map.is_empty() => return Settings::from_db_map(&map),
+            Ok(map) if !map.is_empty() => return Settings::from_db_map(&map).0,
             _ => {}
         }
     }
@@ -138,6 +155,45 @@ async fn list_settings(
     Ok(())
 }
 
+/// Show where each setting's effective value comes from (env > TOML > DB > default).
+async fn explain_settings(
+    store: Option<&dyn crate::db::Database>,
+    filter: Option<String>,
+) -> anyhow::Result<()> {
+    let store = store.ok_or_else(|| {
+        anyhow::anyhow!("Database connection required to explain settings. Check DATABASE_URL.")
+    })?;
+    let resolved = crate::config::Config::explain(store, DEFAULT_USER_ID)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let max_key_len = resolved.iter().map(|r| r.key.len()).max().unwrap_or(0);
+
+    for resolution in resolved {
+        if let Some(ref f) = filter
+            && !resolution.key.starts_with(f)
+        {
+            continue;
+        }
+
+        let display_value = if resolution.value.len() > 60 {
+            format!("{}...", &resolution.value[..57])
+        } else {
+            resolution.value
+        };
+
+        println!(
+            "  {:width$}  {:7}  {}",
+            resolution.key,
+            resolution.source.as_str(),
+            display_value,
+            width = max_key_len
+        );
+    }
+
+    Ok(())
+}
+
 /// Get a specific setting.
 async fn get_setting(store: Option<&dyn crate::db::Database>, path: &str) -> anyhow::Result<()> {
     let settings = load_settings(store).await;
@@ -230,6 +286,28 @@ async fn init_toml(
     Ok(())
 }
 
+/// Validate the env/TOML config and print every error found.
+async fn validate_config() -> anyhow::Result<()> {
+    let errors = match crate::config::Config::validate_env().await {
+        Ok(()) => {
+            println!("Configuration is valid.");
+            return Ok(());
+        }
+        Err(errors) => errors,
+    };
+
+    println!("Found {} configuration error(s):", errors.len());
+    for err in &errors {
+        match err {
+            crate::error::ConfigError::InvalidValue { key, message } => {
+                println!("  {}: {}", key, message);
+            }
+            other => println!("  {}", other),
+        }
+    }
+    anyhow::bail!("configuration is invalid")
+}
+
 /// Show the settings storage info.
 fn show_path(has_db: bool) -> anyhow::Result<()> {
     if has_db {
@@ -484,18 +484,7 @@ async fn check_verification_backend_reachability(
 
             let timeout =
                 Duration::from_millis(verification.eigencloud.timeout_ms.clamp(1_000, 20_000));
-            let auth_header = match (
-                verification.eigencloud.auth_scheme,
-                verification.eigencloud.auth_token.as_deref().map(str::trim),
-            ) {
-                (_, Some("")) | (_, None) => None,
-                (crate::config::EigenCloudAuthScheme::Bearer, Some(token)) => {
-                    Some(("Authorization", format!("Bearer {token}")))
-                }
-                (crate::config::EigenCloudAuthScheme::ApiKey, Some(token)) => {
-                    Some(("X-API-Key", token.to_string()))
-                }
-            };
+            let auth_header = verification.eigencloud.auth_header();
 
             probe_http_endpoint("EigenCloud backend", endpoint, timeout, auth_header).await
         }
@@ -784,7 +773,7 @@ async fn probe_http_endpoint(
     label: &str,
     endpoint: &str,
     timeout: Duration,
-    auth_header: Option<(&'static str, String)>,
+    auth_header: Option<(String, String)>,
 ) -> CheckResult {
     let endpoint = endpoint.trim();
     if endpoint.is_empty() {
@@ -541,6 +541,7 @@ async fn get_secrets_store() -> anyhow::Result<Arc<dyn SecretsStore + Send + Syn
                 .await
                 .map_err(|e| anyhow::anyhow!("{}", e))?
         };
+        let backend = backend.with_max_pool_size(config.database.pool_size);
         backend
             .run_migrations()
             .await
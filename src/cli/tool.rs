@@ -759,6 +759,7 @@ async fn auth_tool(name: String, dir: Option<PathBuf>, user_id: String) -> anyho
                     .await
                     .map_err(|e| anyhow::anyhow!("{}", e))?
             };
+            let backend = backend.with_max_pool_size(config.database.pool_size);
             backend
                 .run_migrations()
                 .await
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::config::helpers::optional_env;
@@ -23,6 +24,8 @@ pub struct AgentConfig {
     pub max_cost_per_day_cents: Option<u64>,
     /// Maximum LLM/tool actions per hour. None = unlimited.
     pub max_actions_per_hour: Option<u64>,
+    /// JSON or TOML file of extra module manifests merged after the Core-8.
+    pub module_manifests_path: Option<PathBuf>,
 }
 
 impl AgentConfig {
@@ -115,6 +118,7 @@ impl AgentConfig {
                     key: "MAX_ACTIONS_PER_HOUR".to_string(),
                     message: format!("must be a positive integer: {e}"),
                 })?,
+            module_manifests_path: optional_env("MODULE_MANIFESTS_PATH")?.map(PathBuf::from),
         })
     }
 }
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use secrecy::SecretString;
 
-use crate::config::helpers::optional_env;
+use crate::config::helpers::{optional_env, validate_optional_url};
 use crate::error::ConfigError;
 use crate::settings::Settings;
 
@@ -56,6 +56,37 @@ pub struct GatewayFrontdoorConfig {
     pub verify_app_base_url: Option<String>,
     pub session_ttl_secs: u64,
     pub poll_interval_ms: u64,
+    /// Mirrors `VerificationBackendConfig::force_signed_receipts`; filled in by
+    /// `Config::build` once the verification backend has been resolved.
+    pub force_signed_receipts: bool,
+    /// Retention window for wallet session store records (0 keeps them forever).
+    pub wallet_retention_secs: u64,
+    /// Per-wallet challenge cap within `challenge_rate_window_secs` (0 disables).
+    pub challenge_rate_limit: u32,
+    pub challenge_rate_window_secs: u64,
+    /// How long sessions are kept after `expires_at` before being purged.
+    pub terminal_retention_secs: u64,
+    /// Hard deadline for the provision command (0 disables).
+    pub provision_timeout_secs: u64,
+    /// Custom wallet signing prompt; `\n` escapes become newlines.
+    pub challenge_template: Option<String>,
+    /// Chains wallets may sign challenges for (default: `[1]`).
+    pub allowed_chain_ids: Vec<u64>,
+    /// Current terms of service version recorded on accepted configs.
+    pub terms_version: String,
+    /// Provisioning retries allowed per failed session (0 disables).
+    pub max_provision_retries: u32,
+    /// Pinned Hyperliquid mainnet perp tickers; `None` uses the built-in list.
+    pub hyperliquid_mainnet_perps: Option<Vec<String>>,
+    /// Pinned Hyperliquid testnet perp tickers; `None` uses the built-in list.
+    pub hyperliquid_testnet_perps: Option<Vec<String>>,
+    /// Load unpinned perp lists from the exchange `meta` endpoint at startup
+    /// (default: off).
+    pub hyperliquid_perp_refresh: bool,
+    /// Recent onboarding transcript turns kept per session (0 keeps all).
+    pub max_onboarding_turns: usize,
+    /// Endpoint notified when a session reaches `ready` or `failed`.
+    pub status_webhook_url: Option<String>,
 }
 
 impl ChannelsConfig {
@@ -131,6 +162,119 @@ impl ChannelsConfig {
                             message: format!("must be a valid integer: {e}"),
                         })?
                         .unwrap_or(1500),
+                    force_signed_receipts: false,
+                    wallet_retention_secs: optional_env("GATEWAY_FRONTDOOR_WALLET_RETENTION_SECS")?
+                        .map(|s| s.parse())
+                        .transpose()
+                        .map_err(|e| ConfigError::InvalidValue {
+                            key: "GATEWAY_FRONTDOOR_WALLET_RETENTION_SECS".to_string(),
+                            message: format!("must be a valid integer: {e}"),
+                        })?
+                        .unwrap_or(90 * 24 * 60 * 60),
+                    challenge_rate_limit: optional_env("GATEWAY_FRONTDOOR_CHALLENGE_RATE_LIMIT")?
+                        .map(|s| s.parse())
+                        .transpose()
+                        .map_err(|e| ConfigError::InvalidValue {
+                            key: "GATEWAY_FRONTDOOR_CHALLENGE_RATE_LIMIT".to_string(),
+                            message: format!("must be a valid integer: {e}"),
+                        })?
+                        .unwrap_or(10),
+                    challenge_rate_window_secs: optional_env(
+                        "GATEWAY_FRONTDOOR_CHALLENGE_RATE_WINDOW_SECS",
+                    )?
+                    .map(|s| s.parse())
+                    .transpose()
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "GATEWAY_FRONTDOOR_CHALLENGE_RATE_WINDOW_SECS".to_string(),
+                        message: format!("must be a valid integer: {e}"),
+                    })?
+                    .unwrap_or(600),
+                    terminal_retention_secs: optional_env(
+                        "GATEWAY_FRONTDOOR_TERMINAL_RETENTION_SECS",
+                    )?
+                    .map(|s| s.parse())
+                    .transpose()
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "GATEWAY_FRONTDOOR_TERMINAL_RETENTION_SECS".to_string(),
+                        message: format!("must be a valid integer: {e}"),
+                    })?
+                    .unwrap_or(6 * 60 * 60),
+                    provision_timeout_secs: optional_env(
+                        "GATEWAY_FRONTDOOR_PROVISION_TIMEOUT_SECS",
+                    )?
+                    .map(|s| s.parse())
+                    .transpose()
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "GATEWAY_FRONTDOOR_PROVISION_TIMEOUT_SECS".to_string(),
+                        message: format!("must be a valid integer: {e}"),
+                    })?
+                    .unwrap_or(30 * 60),
+                    challenge_template: optional_env("GATEWAY_FRONTDOOR_CHALLENGE_TEMPLATE")?
+                        .map(|raw| {
+                            let template = raw.replace("\\n", "\n");
+                            crate::channels::web::frontdoor::validate_challenge_template(&template)
+                                .map(|()| template)
+                                .map_err(|message| ConfigError::InvalidValue {
+                                    key: "GATEWAY_FRONTDOOR_CHALLENGE_TEMPLATE".to_string(),
+                                    message,
+                                })
+                        })
+                        .transpose()?,
+                    allowed_chain_ids: optional_env("GATEWAY_FRONTDOOR_ALLOWED_CHAIN_IDS")?
+                        .map(|raw| parse_chain_ids(&raw))
+                        .transpose()?
+                        .unwrap_or_else(|| vec![1]),
+                    terms_version: optional_env("GATEWAY_FRONTDOOR_TERMS_VERSION")?
+                        .map(|raw| raw.trim().to_string())
+                        .filter(|version| !version.is_empty())
+                        .unwrap_or_else(|| {
+                            crate::channels::web::frontdoor::DEFAULT_TERMS_VERSION.to_string()
+                        }),
+                    max_provision_retries: optional_env("GATEWAY_FRONTDOOR_MAX_PROVISION_RETRIES")?
+                        .map(|s| s.parse())
+                        .transpose()
+                        .map_err(|e| ConfigError::InvalidValue {
+                            key: "GATEWAY_FRONTDOOR_MAX_PROVISION_RETRIES".to_string(),
+                            message: format!("must be a valid integer: {e}"),
+                        })?
+                        .unwrap_or(3),
+                    hyperliquid_mainnet_perps: optional_env(
+                        "GATEWAY_FRONTDOOR_HYPERLIQUID_MAINNET_PERPS",
+                    )?
+                    .map(|raw| {
+                        parse_perp_symbols(&raw, "GATEWAY_FRONTDOOR_HYPERLIQUID_MAINNET_PERPS")
+                    })
+                    .transpose()?,
+                    hyperliquid_testnet_perps: optional_env(
+                        "GATEWAY_FRONTDOOR_HYPERLIQUID_TESTNET_PERPS",
+                    )?
+                    .map(|raw| {
+                        parse_perp_symbols(&raw, "GATEWAY_FRONTDOOR_HYPERLIQUID_TESTNET_PERPS")
+                    })
+                    .transpose()?,
+                    hyperliquid_perp_refresh: optional_env(
+                        "GATEWAY_FRONTDOOR_HYPERLIQUID_PERP_REFRESH",
+                    )?
+                    .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
+                    .unwrap_or(false),
+                    max_onboarding_turns: optional_env("GATEWAY_FRONTDOOR_MAX_ONBOARDING_TURNS")?
+                        .map(|s| s.parse())
+                        .transpose()
+                        .map_err(|e| ConfigError::InvalidValue {
+                            key: "GATEWAY_FRONTDOOR_MAX_ONBOARDING_TURNS".to_string(),
+                            message: format!("must be a valid integer: {e}"),
+                        })?
+                        .unwrap_or(200),
+                    status_webhook_url: optional_env("GATEWAY_FRONTDOOR_STATUS_WEBHOOK_URL")?
+                        .map(|url| {
+                            validate_optional_url(
+                                Some(&url),
+                                &["http", "https"],
+                                "GATEWAY_FRONTDOOR_STATUS_WEBHOOK_URL",
+                            )
+                            .map(|()| url)
+                        })
+                        .transpose()?,
                 })
             } else {
                 None
@@ -207,6 +351,45 @@ fn first_non_empty_env(keys: &[&str]) -> Result<Option<String>, ConfigError> {
     Ok(None)
 }
 
+/// Parse a comma-separated, non-empty list of chain ids.
+fn parse_chain_ids(raw: &str) -> Result<Vec<u64>, ConfigError> {
+    let invalid = |message: String| ConfigError::InvalidValue {
+        key: "GATEWAY_FRONTDOOR_ALLOWED_CHAIN_IDS".to_string(),
+        message,
+    };
+    let chain_ids = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse::<u64>()
+                .map_err(|e| invalid(format!("'{id}' is not a valid chain id: {e}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if chain_ids.is_empty() {
+        return Err(invalid("must list at least one chain id".to_string()));
+    }
+    Ok(chain_ids)
+}
+
+/// Parse a comma-separated ticker list, uppercased and deduped in order.
+fn parse_perp_symbols(raw: &str, key: &str) -> Result<Vec<String>, ConfigError> {
+    let mut symbols = Vec::new();
+    for symbol in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let symbol = symbol.to_ascii_uppercase();
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    if symbols.is_empty() {
+        return Err(ConfigError::InvalidValue {
+            key: key.to_string(),
+            message: "must list at least one ticker".to_string(),
+        });
+    }
+    Ok(symbols)
+}
+
 fn normalize_env_value(raw: &str) -> String {
     let trimmed = raw.trim();
     let maybe_unquoted = trimmed
@@ -224,10 +407,8 @@ fn normalize_env_value(raw: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::helpers::ENV_MUTEX;
     use crate::settings::Settings;
-    use std::sync::Mutex;
-
-    static ENV_MUTEX: Mutex<()> = Mutex::new(());
 
     fn clear_frontdoor_env() {
         // SAFETY: Guarded by ENV_MUTEX in tests.
@@ -247,9 +428,45 @@ mod tests {
             std::env::remove_var("NEXT_PUBLIC_PRIVY_CLIENT_ID");
             std::env::remove_var("GATEWAY_FRONTDOOR_ALLOW_DEFAULT_INSTANCE_FALLBACK");
             std::env::remove_var("FRONTDOOR_ALLOW_DEFAULT_INSTANCE_FALLBACK");
+            std::env::remove_var("GATEWAY_FRONTDOOR_ALLOWED_CHAIN_IDS");
+            std::env::remove_var("GATEWAY_FRONTDOOR_HYPERLIQUID_MAINNET_PERPS");
+            std::env::remove_var("GATEWAY_FRONTDOOR_HYPERLIQUID_TESTNET_PERPS");
+            std::env::remove_var("GATEWAY_FRONTDOOR_HYPERLIQUID_PERP_REFRESH");
         }
     }
 
+    #[test]
+    fn frontdoor_perp_refresh_is_opt_in() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_frontdoor_env();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("GATEWAY_ENABLED", "true");
+            std::env::set_var("GATEWAY_FRONTDOOR_ENABLED", "true");
+        }
+        let frontdoor = |cfg: ChannelsConfig| {
+            cfg.gateway
+                .expect("gateway config missing")
+                .frontdoor
+                .expect("frontdoor config missing")
+        };
+
+        let settings = Settings::default();
+        let cfg = frontdoor(ChannelsConfig::resolve(&settings).expect("channels resolve"));
+        assert!(!cfg.hyperliquid_perp_refresh);
+        assert!(cfg.hyperliquid_mainnet_perps.is_none());
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("GATEWAY_FRONTDOOR_HYPERLIQUID_PERP_REFRESH", "true");
+        }
+        let cfg = frontdoor(ChannelsConfig::resolve(&settings).expect("channels resolve"));
+        assert!(cfg.hyperliquid_perp_refresh);
+
+        clear_frontdoor_env();
+    }
+
     #[test]
     fn frontdoor_accepts_railway_legacy_privy_aliases() {
         let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
@@ -337,4 +554,41 @@ mod tests {
 
         clear_frontdoor_env();
     }
+
+    #[test]
+    fn frontdoor_allowed_chain_ids_default_to_mainnet() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_frontdoor_env();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("GATEWAY_ENABLED", "true");
+            std::env::set_var("GATEWAY_FRONTDOOR_ENABLED", "true");
+        }
+        let resolve_frontdoor = || {
+            ChannelsConfig::resolve(&Settings::default())
+                .map(|cfg| cfg.gateway.and_then(|gateway| gateway.frontdoor))
+        };
+        let frontdoor = resolve_frontdoor()
+            .expect("channels resolve")
+            .expect("frontdoor config missing");
+        assert_eq!(frontdoor.allowed_chain_ids, vec![1]);
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("GATEWAY_FRONTDOOR_ALLOWED_CHAIN_IDS", "1, 8453,");
+        }
+        let frontdoor = resolve_frontdoor()
+            .expect("channels resolve")
+            .expect("frontdoor config missing");
+        assert_eq!(frontdoor.allowed_chain_ids, vec![1, 8453]);
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("GATEWAY_FRONTDOOR_ALLOWED_CHAIN_IDS", "mainnet");
+        }
+        assert!(resolve_frontdoor().is_err());
+
+        clear_frontdoor_env();
+    }
 }
@@ -39,6 +39,40 @@ impl std::str::FromStr for DatabaseBackend {
     }
 }
 
+/// What happens when a user's intent audit records reach their quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntentAuditQuotaPolicy {
+    /// Fail the new write with `DatabaseError::QuotaExceeded` (default).
+    #[default]
+    Reject,
+    /// Delete the user's oldest records to make room. The newest pruned
+    /// record's `chain_hash` is kept as a checkpoint that `verify_chain`
+    /// starts from, so the kept records still verify.
+    PruneOldest,
+}
+
+impl std::str::FromStr for IntentAuditQuotaPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "prune" | "prune_oldest" => Ok(Self::PruneOldest),
+            _ => Err(format!(
+                "invalid intent audit quota policy '{}', expected 'reject' or 'prune'",
+                s
+            )),
+        }
+    }
+}
+
+/// Per-user cap on stored intent audit records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntentAuditQuota {
+    pub max_records_per_user: u32,
+    pub policy: IntentAuditQuotaPolicy,
+}
+
 /// Database configuration.
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -47,6 +81,8 @@ pub struct DatabaseConfig {
 
     // -- PostgreSQL fields --
     pub url: SecretString,
+    /// Most connections open at once (`DATABASE_POOL_SIZE`). Also caps how
+    /// many libSQL connections may be checked out concurrently.
     pub pool_size: usize,
 
     // -- libSQL fields --
@@ -56,6 +92,12 @@ pub struct DatabaseConfig {
     pub libsql_url: Option<String>,
     /// Turso auth token (required when libsql_url is set).
     pub libsql_auth_token: Option<SecretString>,
+    /// Separate embedded-replica file serving read queries (optional, only
+    /// used with libsql_url).
+    pub libsql_read_replica_path: Option<PathBuf>,
+
+    /// Per-user intent audit record cap (`None` when unlimited).
+    pub intent_audit_quota: Option<IntentAuditQuota>,
 }
 
 impl DatabaseConfig {
@@ -98,6 +140,7 @@ impl DatabaseConfig {
 
         let libsql_url = optional_env("LIBSQL_URL")?;
         let libsql_auth_token = optional_env("LIBSQL_AUTH_TOKEN")?.map(SecretString::from);
+        let libsql_read_replica_path = optional_env("LIBSQL_READ_REPLICA_PATH")?.map(PathBuf::from);
 
         if libsql_url.is_some() && libsql_auth_token.is_none() {
             return Err(ConfigError::MissingRequired {
@@ -105,6 +148,25 @@ impl DatabaseConfig {
                 hint: "LIBSQL_AUTH_TOKEN is required when LIBSQL_URL is set".to_string(),
             });
         }
+        if libsql_read_replica_path.is_some() && libsql_url.is_none() {
+            return Err(ConfigError::MissingRequired {
+                key: "LIBSQL_URL".to_string(),
+                hint: "LIBSQL_URL is required when LIBSQL_READ_REPLICA_PATH is set".to_string(),
+            });
+        }
+
+        let max_records_per_user: u32 = parse_optional_env("INTENT_AUDIT_MAX_RECORDS_PER_USER", 0)?;
+        let intent_audit_quota = if max_records_per_user > 0 {
+            Some(IntentAuditQuota {
+                max_records_per_user,
+                policy: parse_optional_env(
+                    "INTENT_AUDIT_QUOTA_POLICY",
+                    IntentAuditQuotaPolicy::Reject,
+                )?,
+            })
+        } else {
+            None
+        };
 
         Ok(Self {
             backend,
@@ -113,6 +175,8 @@ impl DatabaseConfig {
             libsql_path,
             libsql_url,
             libsql_auth_token,
+            libsql_read_replica_path,
+            intent_audit_quota,
         })
     }
 
@@ -0,0 +1,238 @@
+//! Per-setting provenance for `enclagent config explain`.
+//!
+//! Resolves every settings key through the same layers as
+//! [`Config::from_db_with_toml_layers`] and reports which layer won.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::Config;
+use crate::config::helpers::optional_env;
+use crate::error::ConfigError;
+use crate::settings::Settings;
+
+/// Env vars that override a settings key, as read by the config builders.
+const SETTING_ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("agent.job_timeout_secs", "AGENT_JOB_TIMEOUT_SECS"),
+    ("agent.max_parallel_jobs", "AGENT_MAX_PARALLEL_JOBS"),
+    ("agent.max_repair_attempts", "SELF_REPAIR_MAX_ATTEMPTS"),
+    ("agent.name", "AGENT_NAME"),
+    (
+        "agent.repair_check_interval_secs",
+        "SELF_REPAIR_CHECK_INTERVAL_SECS",
+    ),
+    (
+        "agent.session_idle_timeout_secs",
+        "SESSION_IDLE_TIMEOUT_SECS",
+    ),
+    ("agent.stuck_threshold_secs", "AGENT_STUCK_THRESHOLD_SECS"),
+    ("agent.use_planning", "AGENT_USE_PLANNING"),
+    ("channels.telegram_owner_id", "TELEGRAM_OWNER_ID"),
+    ("embeddings.enabled", "EMBEDDING_ENABLED"),
+    ("embeddings.model", "EMBEDDING_MODEL"),
+    ("embeddings.provider", "EMBEDDING_PROVIDER"),
+    ("heartbeat.enabled", "HEARTBEAT_ENABLED"),
+    ("heartbeat.interval_secs", "HEARTBEAT_INTERVAL_SECS"),
+    ("heartbeat.notify_channel", "HEARTBEAT_NOTIFY_CHANNEL"),
+    ("heartbeat.notify_user", "HEARTBEAT_NOTIFY_USER"),
+    (
+        "hyperliquid_runtime.api_base_url",
+        "HYPERLIQUID_API_BASE_URL",
+    ),
+    ("hyperliquid_runtime.max_retries", "HYPERLIQUID_MAX_RETRIES"),
+    ("hyperliquid_runtime.network", "HYPERLIQUID_NETWORK"),
+    (
+        "hyperliquid_runtime.paper_live_policy",
+        "HYPERLIQUID_PAPER_LIVE_POLICY",
+    ),
+    (
+        "hyperliquid_runtime.retry_backoff_ms",
+        "HYPERLIQUID_RETRY_BACKOFF_MS",
+    ),
+    ("hyperliquid_runtime.timeout_ms", "HYPERLIQUID_TIMEOUT_MS"),
+    ("hyperliquid_runtime.ws_url", "HYPERLIQUID_WS_URL"),
+    ("tunnel.cf_token", "TUNNEL_CF_TOKEN"),
+    ("tunnel.custom_command", "TUNNEL_CUSTOM_COMMAND"),
+    ("tunnel.custom_health_url", "TUNNEL_CUSTOM_HEALTH_URL"),
+    ("tunnel.custom_url_pattern", "TUNNEL_CUSTOM_URL_PATTERN"),
+    ("tunnel.ngrok_domain", "TUNNEL_NGROK_DOMAIN"),
+    ("tunnel.ngrok_token", "TUNNEL_NGROK_TOKEN"),
+    ("tunnel.provider", "TUNNEL_PROVIDER"),
+    ("tunnel.public_url", "TUNNEL_URL"),
+    ("tunnel.ts_funnel", "TUNNEL_TS_FUNNEL"),
+    ("tunnel.ts_hostname", "TUNNEL_TS_HOSTNAME"),
+    ("verification_backend.backend", "VERIFICATION_BACKEND"),
+    (
+        "verification_backend.eigencloud_auth_scheme",
+        "EIGENCLOUD_AUTH_SCHEME",
+    ),
+    (
+        "verification_backend.eigencloud_auth_token",
+        "EIGENCLOUD_AUTH_TOKEN",
+    ),
+    (
+        "verification_backend.eigencloud_endpoint",
+        "EIGENCLOUD_ENDPOINT",
+    ),
+    (
+        "verification_backend.eigencloud_timeout_ms",
+        "EIGENCLOUD_TIMEOUT_MS",
+    ),
+    (
+        "verification_backend.fallback_chain_path",
+        "VERIFICATION_FALLBACK_CHAIN_PATH",
+    ),
+    (
+        "verification_backend.fallback_enabled",
+        "VERIFICATION_FALLBACK_ENABLED",
+    ),
+    (
+        "verification_backend.fallback_require_signed_receipts",
+        "VERIFICATION_FALLBACK_REQUIRE_SIGNED_RECEIPTS",
+    ),
+    (
+        "verification_backend.fallback_signing_key_id",
+        "VERIFICATION_FALLBACK_SIGNING_KEY_ID",
+    ),
+    (
+        "verification_backend.force_signed_receipts",
+        "VERIFICATION_FORCE_SIGNED_RECEIPTS",
+    ),
+    (
+        "wallet_vault_policy.custody_mode",
+        "HYPERLIQUID_CUSTODY_MODE",
+    ),
+    (
+        "wallet_vault_policy.kill_switch_behavior",
+        "HYPERLIQUID_KILL_SWITCH_BEHAVIOR",
+    ),
+    (
+        "wallet_vault_policy.kill_switch_confirm_flatten",
+        "HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN",
+    ),
+    (
+        "wallet_vault_policy.kill_switch_enabled",
+        "HYPERLIQUID_KILL_SWITCH_ENABLED",
+    ),
+    (
+        "wallet_vault_policy.leverage_cap",
+        "HYPERLIQUID_LEVERAGE_CAP",
+    ),
+    (
+        "wallet_vault_policy.max_position_size_usd",
+        "HYPERLIQUID_MAX_POSITION_SIZE_USD",
+    ),
+    (
+        "wallet_vault_policy.operator_wallet_address",
+        "HYPERLIQUID_OPERATOR_WALLET_ADDRESS",
+    ),
+    (
+        "wallet_vault_policy.per_symbol_position_caps",
+        "HYPERLIQUID_PER_SYMBOL_POSITION_CAPS",
+    ),
+    (
+        "wallet_vault_policy.user_wallet_address",
+        "HYPERLIQUID_USER_WALLET_ADDRESS",
+    ),
+    (
+        "wallet_vault_policy.vault_address",
+        "HYPERLIQUID_VAULT_ADDRESS",
+    ),
+];
+
+/// The layer a resolved setting came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    Env,
+    Toml,
+    Db,
+    Default,
+}
+
+impl ConfigSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Env => "env",
+            Self::Toml => "toml",
+            Self::Db => "db",
+            Self::Default => "default",
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A settings key, its effective value, and the layer that supplied it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigResolution {
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+impl Config {
+    /// Explain where each setting's effective value comes from.
+    ///
+    /// Uses the default TOML config path, like [`Config::from_db`].
+    pub async fn explain(
+        store: &(dyn crate::db::SettingsStore + Sync),
+        user_id: &str,
+    ) -> Result<Vec<ConfigResolution>, ConfigError> {
+        Self::explain_with_toml_layers(store, user_id, &[]).await
+    }
+
+    /// Explain settings resolved with TOML config files overlaid in order.
+    ///
+    /// Priority matches loading: env var > TOML config file > DB settings >
+    /// default. Results are sorted by key.
+    pub async fn explain_with_toml_layers(
+        store: &(dyn crate::db::SettingsStore + Sync),
+        user_id: &str,
+        toml_paths: &[&std::path::Path],
+    ) -> Result<Vec<ConfigResolution>, ConfigError> {
+        let _ = dotenvy::dotenv();
+        crate::bootstrap::load_enclagent_env();
+
+        let db_map = match store.get_all_settings(user_id).await {
+            Ok(map) => map,
+            Err(e) => {
+                tracing::warn!("Failed to load settings from DB, using defaults: {}", e);
+                HashMap::new()
+            }
+        };
+        let (db_settings, _) = Settings::from_db_map(&db_map);
+        let mut layered = db_settings.clone();
+        Self::apply_toml_layers(&mut layered, toml_paths)?;
+
+        let db_values: HashMap<String, String> = db_settings.list().into_iter().collect();
+        let mut resolved: BTreeMap<String, ConfigResolution> = BTreeMap::new();
+        for (key, value) in layered.list() {
+            let source = if db_values.get(&key) != Some(&value) {
+                ConfigSource::Toml
+            } else if db_map.contains_key(&key) {
+                ConfigSource::Db
+            } else {
+                ConfigSource::Default
+            };
+            resolved.insert(key.clone(), ConfigResolution { key, value, source });
+        }
+
+        for (key, env_var) in SETTING_ENV_OVERRIDES {
+            if let Some(value) = optional_env(env_var)? {
+                resolved.insert(
+                    (*key).to_string(),
+                    ConfigResolution {
+                        key: (*key).to_string(),
+                        value,
+                        source: ConfigSource::Env,
+                    },
+                );
+            }
+        }
+
+        Ok(resolved.into_values().collect())
+    }
+}
@@ -2,6 +2,10 @@ use crate::error::ConfigError;
 
 use super::INJECTED_VARS;
 
+/// Serializes tests that mutate process env vars across every config module.
+#[cfg(test)]
+pub(crate) static ENV_MUTEX: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 pub(crate) fn optional_env(key: &str) -> Result<Option<String>, ConfigError> {
     // Check real env vars first (always win over injected secrets)
     match std::env::var(key) {
@@ -16,13 +20,45 @@ pub(crate) fn optional_env(key: &str) -> Result<Option<String>, ConfigError> {
     }
 
     // Fall back to thread-safe overlay (secrets injected from DB)
-    if let Some(val) = INJECTED_VARS.get().and_then(|map| map.get(key)) {
+    let injected = INJECTED_VARS
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(val) = injected.get(key) {
         return Ok(Some(val.clone()));
     }
 
     Ok(None)
 }
 
+/// Check that `value`, when present, is an absolute URL with one of
+/// `schemes` and a hostname.
+pub(crate) fn validate_optional_url(
+    value: Option<&str>,
+    schemes: &[&str],
+    key: &str,
+) -> Result<(), ConfigError> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    let invalid = |message: String| ConfigError::InvalidValue {
+        key: key.to_string(),
+        message,
+    };
+    let parsed =
+        url::Url::parse(value).map_err(|e| invalid(format!("not a valid URL '{value}': {e}")))?;
+    if !schemes.contains(&parsed.scheme()) {
+        return Err(invalid(format!(
+            "must use {} scheme, got '{}'",
+            schemes.join(" or "),
+            parsed.scheme()
+        )));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(invalid(format!("must include a hostname, got '{value}'")));
+    }
+    Ok(())
+}
+
 pub(crate) fn parse_optional_env<T>(key: &str, default: T) -> Result<T, ConfigError>
 where
     T: std::str::FromStr,
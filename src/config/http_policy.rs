@@ -0,0 +1,115 @@
+//! Request timeout and retry policy shared by the HTTP backends.
+//!
+//! `HyperliquidRuntimeConfig` and `EigenCloudConfig` each resolve their own
+//! timeout (and, for Hyperliquid, a retry budget); both hand out an
+//! [`HttpPolicy`] so clients are built and retried the same way everywhere.
+
+use std::time::Duration;
+
+/// Upper bound on establishing a connection, whatever the request timeout.
+const MAX_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout and retry budget for requests to one backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpPolicy {
+    /// Whole-request timeout, from connect through reading the body.
+    pub timeout: Duration,
+    /// Retries after the first attempt; zero means a single attempt.
+    pub max_retries: u32,
+    /// Base backoff; retry `n` waits `backoff * n`.
+    pub backoff: Duration,
+}
+
+impl HttpPolicy {
+    /// Build a client that applies this policy's timeouts to every request.
+    ///
+    /// The connect timeout is the request timeout capped at 10s, so a slow
+    /// handshake cannot use up the whole budget of a long request.
+    pub fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.timeout.min(MAX_CONNECT_TIMEOUT))
+            .build()
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Delay before the retry that follows failed attempt `attempt` (1-based).
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(attempt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        EigenCloudAuthScheme, EigenCloudConfig, HyperliquidNetwork, HyperliquidRuntimeConfig,
+        PaperLivePolicy,
+    };
+
+    /// Accept one connection and hold it open without ever responding.
+    async fn serve_slowly() -> String {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let addr = listener.local_addr().expect("mock addr");
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+        format!("http://{addr}")
+    }
+
+    async fn assert_times_out(policy: HttpPolicy) {
+        let url = serve_slowly().await;
+        let started = std::time::Instant::now();
+        let err = policy
+            .client()
+            .get(&url)
+            .send()
+            .await
+            .expect_err("slow server should time out");
+        assert!(err.is_timeout(), "{err:?}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn hyperliquid_policy_client_applies_timeout() {
+        let cfg = HyperliquidRuntimeConfig {
+            network: HyperliquidNetwork::Testnet,
+            api_base_url: HyperliquidNetwork::Testnet
+                .default_api_base_url()
+                .to_string(),
+            ws_url: HyperliquidNetwork::Testnet.default_ws_url().to_string(),
+            timeout_ms: 200,
+            max_retries: 2,
+            retry_backoff_ms: 50,
+            paper_live_policy: PaperLivePolicy::PaperFirst,
+        };
+        let policy = cfg.http_policy();
+        assert_eq!(policy.timeout, Duration::from_millis(200));
+        assert_eq!(policy.max_retries, 2);
+        assert_eq!(policy.retry_delay(2), Duration::from_millis(100));
+
+        assert_times_out(policy).await;
+    }
+
+    #[tokio::test]
+    async fn eigencloud_policy_client_applies_timeout() {
+        let cfg = EigenCloudConfig {
+            endpoint: None,
+            auth_scheme: EigenCloudAuthScheme::Bearer,
+            auth_token: None,
+            timeout_ms: 200,
+        };
+        let policy = cfg.http_policy();
+        assert_eq!(policy.timeout, Duration::from_millis(200));
+        assert_eq!(policy.max_retries, 0);
+
+        assert_times_out(policy).await;
+    }
+}
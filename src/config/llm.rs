@@ -1,9 +1,9 @@
 use std::path::PathBuf;
 
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
 
 use crate::config::helpers::{optional_env, parse_optional_env};
-use crate::error::ConfigError;
+use crate::error::{ConfigError, LlmError};
 use crate::settings::Settings;
 
 /// Which LLM backend to use.
@@ -104,18 +104,36 @@ pub struct TinfoilConfig {
 pub struct LlmConfig {
     /// Which backend to use (default: NearAi)
     pub backend: LlmBackend,
+    /// Backends to fail over to, in order, after `backend`. Never contains
+    /// `backend` itself or duplicates.
+    pub fallbacks: Vec<LlmBackend>,
     /// NEAR AI config (always populated for NEAR AI embeddings, etc.)
     pub nearai: NearAiConfig,
-    /// Direct OpenAI config (populated when backend=openai)
+    /// Direct OpenAI config (populated when backend or a fallback is openai)
     pub openai: Option<OpenAiDirectConfig>,
-    /// Direct Anthropic config (populated when backend=anthropic)
+    /// Direct Anthropic config (populated when backend or a fallback is anthropic)
     pub anthropic: Option<AnthropicDirectConfig>,
-    /// Ollama config (populated when backend=ollama)
+    /// Ollama config (populated when backend or a fallback is ollama)
     pub ollama: Option<OllamaConfig>,
-    /// OpenAI-compatible config (populated when backend=openai_compatible)
+    /// OpenAI-compatible config (populated when backend or a fallback is openai_compatible)
     pub openai_compatible: Option<OpenAiCompatibleConfig>,
-    /// Tinfoil config (populated when backend=tinfoil)
+    /// Tinfoil config (populated when backend or a fallback is tinfoil)
     pub tinfoil: Option<TinfoilConfig>,
+    /// Probe the backend during `Config` build so an unreachable provider
+    /// fails at startup instead of on the first message (default: false).
+    pub startup_probe: bool,
+    /// Timeout for [`LlmConfig::probe`] requests (default: 5000).
+    pub probe_timeout_ms: u64,
+}
+
+/// Outcome of a successful [`LlmConfig::probe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlmProbeResult {
+    pub backend: LlmBackend,
+    /// Model id reported by the backend (falls back to the configured model
+    /// when the response does not name one).
+    pub model: String,
+    pub latency: std::time::Duration,
 }
 
 /// API mode for NEAR AI.
@@ -217,6 +235,8 @@ impl LlmConfig {
         } else {
             LlmBackend::NearAi
         };
+        let fallbacks = resolve_fallback_backends(backend, settings)?;
+        let uses = |candidate: LlmBackend| backend == candidate || fallbacks.contains(&candidate);
 
         // Resolve NEAR AI config only when backend is NearAi (or when explicitly configured)
         let nearai_api_key = optional_env("NEARAI_API_KEY")?.map(SecretString::from);
@@ -266,8 +286,8 @@ impl LlmConfig {
             failover_cooldown_threshold: parse_optional_env("LLM_FAILOVER_THRESHOLD", 3)?,
         };
 
-        // Resolve provider-specific configs based on backend
-        let openai = if backend == LlmBackend::OpenAi {
+        // Resolve provider-specific configs for the backend and every fallback
+        let openai = if uses(LlmBackend::OpenAi) {
             // API key may be injected later from encrypted secrets after DB init.
             let api_key = optional_env("OPENAI_API_KEY")?.map(SecretString::from);
             let model = optional_env("OPENAI_MODEL")?
@@ -278,7 +298,7 @@ impl LlmConfig {
             None
         };
 
-        let anthropic = if backend == LlmBackend::Anthropic {
+        let anthropic = if uses(LlmBackend::Anthropic) {
             // API key may be injected later from encrypted secrets after DB init.
             let api_key = optional_env("ANTHROPIC_API_KEY")?.map(SecretString::from);
             let model = optional_env("ANTHROPIC_MODEL")?
@@ -294,7 +314,7 @@ impl LlmConfig {
             None
         };
 
-        let ollama = if backend == LlmBackend::Ollama {
+        let ollama = if uses(LlmBackend::Ollama) {
             let base_url = optional_env("OLLAMA_BASE_URL")?
                 .or_else(|| settings.ollama_base_url.clone())
                 .unwrap_or_else(|| "http://localhost:11434".to_string());
@@ -306,7 +326,7 @@ impl LlmConfig {
             None
         };
 
-        let openai_compatible = if backend == LlmBackend::OpenAiCompatible {
+        let openai_compatible = if uses(LlmBackend::OpenAiCompatible) {
             let base_url = optional_env("LLM_BASE_URL")?
                 .or_else(|| settings.openai_compatible_base_url.clone())
                 .ok_or_else(|| ConfigError::MissingRequired {
@@ -326,7 +346,7 @@ impl LlmConfig {
             None
         };
 
-        let tinfoil = if backend == LlmBackend::Tinfoil {
+        let tinfoil = if uses(LlmBackend::Tinfoil) {
             let api_key = optional_env("TINFOIL_API_KEY")?
                 .map(SecretString::from)
                 .ok_or_else(|| ConfigError::MissingRequired {
@@ -339,16 +359,242 @@ impl LlmConfig {
             None
         };
 
+        let probe_timeout_ms = parse_optional_env("LLM_PROBE_TIMEOUT_MS", 5_000)?;
+        if probe_timeout_ms == 0 {
+            return Err(ConfigError::InvalidValue {
+                key: "LLM_PROBE_TIMEOUT_MS".to_string(),
+                message: "must be > 0".to_string(),
+            });
+        }
+
         Ok(Self {
             backend,
+            fallbacks,
             nearai,
             openai,
             anthropic,
             ollama,
             openai_compatible,
             tinfoil,
+            startup_probe: parse_optional_env("LLM_STARTUP_PROBE", false)?,
+            probe_timeout_ms,
+        })
+    }
+
+    /// The primary backend followed by its fallbacks, in failover order.
+    pub fn backends_in_order(&self) -> Vec<&LlmBackend> {
+        std::iter::once(&self.backend)
+            .chain(self.fallbacks.iter())
+            .collect()
+    }
+
+    /// Issue one minimal request against the configured backend.
+    ///
+    /// Ollama and NEAR AI list models; OpenAI-style backends and Anthropic
+    /// request a single-token completion. Bounded by `probe_timeout_ms`.
+    pub async fn probe(&self) -> Result<LlmProbeResult, LlmError> {
+        let provider = self.backend.to_string();
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(self.probe_timeout_ms))
+            .build()?;
+        let not_configured = || LlmError::AuthFailed {
+            provider: provider.clone(),
+        };
+
+        let started = std::time::Instant::now();
+        let model = match self.backend {
+            LlmBackend::Ollama => {
+                let oll = self.ollama.as_ref().ok_or_else(not_configured)?;
+                let url = format!("{}/api/tags", oll.base_url.trim_end_matches('/'));
+                let body = probe_send(&provider, client.get(url)).await?;
+                let names: Vec<&str> = body["models"]
+                    .as_array()
+                    .map(|models| models.iter().filter_map(|m| m["name"].as_str()).collect())
+                    .unwrap_or_default();
+                names
+                    .iter()
+                    .find(|name| {
+                        **name == oll.model || name.strip_suffix(":latest") == Some(&oll.model)
+                    })
+                    .map(|name| name.to_string())
+                    .ok_or_else(|| LlmError::ModelNotAvailable {
+                        provider: provider.clone(),
+                        model: oll.model.clone(),
+                    })?
+            }
+            LlmBackend::NearAi => {
+                let url = format!("{}/v1/models", self.nearai.base_url.trim_end_matches('/'));
+                let mut request = client.get(url);
+                if let Some(key) = self.nearai.api_key.as_ref() {
+                    request = request.bearer_auth(key.expose_secret());
+                }
+                probe_send(&provider, request).await?;
+                self.nearai.model.clone()
+            }
+            LlmBackend::OpenAi => {
+                let cfg = self.openai.as_ref().ok_or_else(not_configured)?;
+                probe_chat_completion(
+                    &provider,
+                    &client,
+                    "https://api.openai.com/v1",
+                    Some(&cfg.api_key),
+                    &cfg.model,
+                )
+                .await?
+            }
+            LlmBackend::OpenAiCompatible => {
+                let cfg = self.openai_compatible.as_ref().ok_or_else(not_configured)?;
+                probe_chat_completion(
+                    &provider,
+                    &client,
+                    &cfg.base_url,
+                    cfg.api_key.as_ref(),
+                    &cfg.model,
+                )
+                .await?
+            }
+            LlmBackend::Tinfoil => {
+                let cfg = self.tinfoil.as_ref().ok_or_else(not_configured)?;
+                probe_chat_completion(
+                    &provider,
+                    &client,
+                    crate::llm::TINFOIL_BASE_URL,
+                    Some(&cfg.api_key),
+                    &cfg.model,
+                )
+                .await?
+            }
+            LlmBackend::Anthropic => {
+                let cfg = self.anthropic.as_ref().ok_or_else(not_configured)?;
+                let base = cfg
+                    .base_url
+                    .as_deref()
+                    .unwrap_or("https://api.anthropic.com")
+                    .trim_end_matches('/');
+                let request = client
+                    .post(format!("{base}/v1/messages"))
+                    .header("x-api-key", cfg.api_key.expose_secret())
+                    .header("anthropic-version", "2023-06-01")
+                    .json(&serde_json::json!({
+                        "model": cfg.model,
+                        "max_tokens": 1,
+                        "messages": [{"role": "user", "content": "ping"}],
+                    }));
+                let body = probe_send(&provider, request).await?;
+                body["model"].as_str().unwrap_or(&cfg.model).to_string()
+            }
+        };
+
+        Ok(LlmProbeResult {
+            backend: self.backend,
+            model,
+            latency: started.elapsed(),
+        })
+    }
+}
+
+/// Parse the fallback chain: `LLM_FALLBACK_BACKENDS` (comma-separated) wins
+/// over the `llm_fallback_backends` setting.
+///
+/// Entries repeating the primary or an earlier fallback are dropped. Invalid
+/// names fail resolution when they come from env and are skipped with a
+/// warning when they come from settings, matching `llm_backend`.
+fn resolve_fallback_backends(
+    primary: LlmBackend,
+    settings: &Settings,
+) -> Result<Vec<LlmBackend>, ConfigError> {
+    let mut parsed = Vec::new();
+    if let Some(raw) = optional_env("LLM_FALLBACK_BACKENDS")? {
+        for name in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            parsed.push(
+                name.parse::<LlmBackend>()
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "LLM_FALLBACK_BACKENDS".to_string(),
+                        message: e,
+                    })?,
+            );
+        }
+    } else {
+        for name in &settings.llm_fallback_backends {
+            match name.trim().parse::<LlmBackend>() {
+                Ok(backend) => parsed.push(backend),
+                Err(e) => tracing::warn!(
+                    "Invalid llm_fallback_backends entry '{}' in settings: {}. Skipping.",
+                    name,
+                    e
+                ),
+            }
+        }
+    }
+
+    let mut fallbacks: Vec<LlmBackend> = Vec::new();
+    for backend in parsed {
+        if backend != primary && !fallbacks.contains(&backend) {
+            fallbacks.push(backend);
+        }
+    }
+    Ok(fallbacks)
+}
+
+/// Send a probe request and decode its JSON body, mapping HTTP failures.
+async fn probe_send(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<serde_json::Value, LlmError> {
+    let response = request.send().await?;
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(LlmError::AuthFailed {
+            provider: provider.to_string(),
+        });
+    }
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(LlmError::RateLimited {
+            provider: provider.to_string(),
+            retry_after: None,
+        });
+    }
+    if !status.is_success() {
+        return Err(LlmError::RequestFailed {
+            provider: provider.to_string(),
+            reason: format!("probe returned HTTP {status}"),
+        });
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| LlmError::InvalidResponse {
+            provider: provider.to_string(),
+            reason: e.to_string(),
         })
+}
+
+async fn probe_chat_completion(
+    provider: &str,
+    client: &reqwest::Client,
+    base_url: &str,
+    api_key: Option<&SecretString>,
+    model: &str,
+) -> Result<String, LlmError> {
+    let mut request = client
+        .post(format!(
+            "{}/chat/completions",
+            base_url.trim_end_matches('/')
+        ))
+        .json(&serde_json::json!({
+            "model": model,
+            "max_tokens": 1,
+            "messages": [{"role": "user", "content": "ping"}],
+        }));
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key.expose_secret());
     }
+    let body = probe_send(provider, request).await?;
+    Ok(body["model"].as_str().unwrap_or(model).to_string())
 }
 
 /// Get the default session file path (~/.enclagent/session.json).
@@ -362,11 +608,8 @@ fn default_session_path() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::helpers::ENV_MUTEX;
     use crate::settings::Settings;
-    use std::sync::Mutex;
-
-    /// Serializes env-mutating tests to prevent parallel races.
-    static ENV_MUTEX: Mutex<()> = Mutex::new(());
 
     /// Clear all openai-compatible-related env vars.
     fn clear_openai_compatible_env() {
@@ -406,6 +649,102 @@ mod tests {
         }
     }
 
+    fn clear_fallback_env() {
+        // SAFETY: Only called under ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("LLM_BACKEND");
+            std::env::remove_var("LLM_FALLBACK_BACKENDS");
+            std::env::remove_var("LLM_BASE_URL");
+            std::env::remove_var("LLM_MODEL");
+            std::env::remove_var("OLLAMA_MODEL");
+            std::env::remove_var("OLLAMA_BASE_URL");
+            std::env::remove_var("OPENAI_API_KEY");
+            std::env::remove_var("TINFOIL_API_KEY");
+            std::env::remove_var("TINFOIL_MODEL");
+        }
+    }
+
+    #[test]
+    fn fallback_backends_resolve_in_order_with_their_own_configs() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_fallback_env();
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::set_var("TINFOIL_API_KEY", "test-tinfoil-key");
+            std::env::set_var(
+                "LLM_FALLBACK_BACKENDS",
+                "openai_compatible, ollama, tinfoil, openai_compatible",
+            );
+        }
+
+        let settings = Settings {
+            llm_backend: Some("tinfoil".to_string()),
+            openai_compatible_base_url: Some("https://openrouter.ai/api/v1".to_string()),
+            ollama_base_url: Some("http://ollama.internal:11434".to_string()),
+            ..Default::default()
+        };
+
+        let cfg = LlmConfig::resolve(&settings).expect("resolve should succeed");
+        assert_eq!(
+            cfg.backends_in_order(),
+            vec![
+                &LlmBackend::Tinfoil,
+                &LlmBackend::OpenAiCompatible,
+                &LlmBackend::Ollama
+            ]
+        );
+        assert!(cfg.tinfoil.is_some());
+        assert_eq!(
+            cfg.openai_compatible
+                .expect("openai-compatible fallback")
+                .base_url,
+            "https://openrouter.ai/api/v1"
+        );
+        assert_eq!(
+            cfg.ollama.expect("ollama fallback").base_url,
+            "http://ollama.internal:11434"
+        );
+        assert!(cfg.openai.is_none());
+
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::set_var("LLM_FALLBACK_BACKENDS", "not_a_backend");
+        }
+        assert!(LlmConfig::resolve(&settings).is_err());
+
+        clear_fallback_env();
+        let from_settings = Settings {
+            llm_backend: Some("ollama".to_string()),
+            llm_fallback_backends: vec!["bogus".to_string(), "nearai".to_string()],
+            ..Default::default()
+        };
+        let cfg = LlmConfig::resolve(&from_settings).expect("resolve should succeed");
+        assert_eq!(
+            cfg.backends_in_order(),
+            vec![&LlmBackend::Ollama, &LlmBackend::NearAi]
+        );
+    }
+
+    #[test]
+    fn no_fallbacks_resolves_only_the_primary() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_fallback_env();
+
+        let settings = Settings {
+            llm_backend: Some("ollama".to_string()),
+            ..Default::default()
+        };
+
+        let cfg = LlmConfig::resolve(&settings).expect("resolve should succeed");
+        assert!(cfg.fallbacks.is_empty());
+        assert_eq!(cfg.backends_in_order(), vec![&LlmBackend::Ollama]);
+        assert!(cfg.ollama.is_some());
+        assert!(cfg.openai_compatible.is_none());
+        assert!(cfg.tinfoil.is_none());
+        assert!(cfg.anthropic.is_none());
+        assert!(cfg.openai.is_none());
+    }
+
     #[test]
     fn openai_compatible_uses_selected_model_when_llm_model_unset() {
         let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
@@ -637,4 +976,90 @@ mod tests {
             std::env::remove_var("ANTHROPIC_API_KEY");
         }
     }
+
+    /// Serve one canned HTTP response on an ephemeral port.
+    async fn serve_once(body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let addr = listener.local_addr().expect("mock addr");
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+        });
+        format!("http://{addr}")
+    }
+
+    fn ollama_probe_config(base_url: String, model: &str) -> LlmConfig {
+        LlmConfig {
+            backend: LlmBackend::Ollama,
+            fallbacks: Vec::new(),
+            nearai: NearAiConfig {
+                model: "unused".to_string(),
+                cheap_model: None,
+                base_url: "https://api.near.ai".to_string(),
+                auth_base_url: "https://private.near.ai".to_string(),
+                session_path: PathBuf::from("/tmp/test-session.json"),
+                api_mode: NearAiApiMode::Responses,
+                api_key: None,
+                fallback_model: None,
+                max_retries: 3,
+                circuit_breaker_threshold: None,
+                circuit_breaker_recovery_secs: 30,
+                response_cache_enabled: false,
+                response_cache_ttl_secs: 3600,
+                response_cache_max_entries: 1000,
+                failover_cooldown_secs: 300,
+                failover_cooldown_threshold: 3,
+            },
+            openai: None,
+            anthropic: None,
+            ollama: Some(OllamaConfig {
+                base_url,
+                model: model.to_string(),
+            }),
+            openai_compatible: None,
+            tinfoil: None,
+            startup_probe: true,
+            probe_timeout_ms: 2_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn probe_lists_ollama_models_and_records_latency() {
+        let base_url =
+            serve_once(r#"{"models":[{"name":"qwen2:7b"},{"name":"llama3:latest"}]}"#).await;
+        let cfg = ollama_probe_config(base_url, "llama3");
+
+        let result = cfg.probe().await.expect("probe should succeed");
+
+        assert_eq!(result.backend, LlmBackend::Ollama);
+        assert_eq!(result.model, "llama3:latest");
+        assert!(result.latency > std::time::Duration::ZERO);
+        assert!(result.latency < std::time::Duration::from_millis(cfg.probe_timeout_ms));
+    }
+
+    #[tokio::test]
+    async fn probe_reports_missing_ollama_model() {
+        let base_url = serve_once(r#"{"models":[{"name":"qwen2:7b"}]}"#).await;
+        let cfg = ollama_probe_config(base_url, "llama3");
+
+        let err = cfg.probe().await.expect_err("model is not pulled");
+        assert!(
+            matches!(err, LlmError::ModelNotAvailable { ref model, .. } if model == "llama3"),
+            "{err}"
+        );
+    }
 }
@@ -2,17 +2,22 @@
 //!
 //! Settings are loaded with priority: env var > database > default.
 //! `DATABASE_URL` lives in `~/.enclagent/.env` (loaded via dotenvy early
-//! in startup). Everything else comes from env vars, the DB settings
-//! table, or auto-detection.
+//! in startup); extra env files can be supplied via
+//! [`Config::from_env_with_env_files`]. Everything else comes from env vars,
+//! the DB settings table, or auto-detection.
 
 mod agent;
 mod builder;
 mod channels;
 mod database;
 mod embeddings;
+mod explain;
 mod heartbeat;
 pub(crate) mod helpers;
+mod http_policy;
 mod llm;
+mod policy_override;
+mod retry;
 mod routines;
 mod safety;
 mod sandbox;
@@ -23,22 +28,31 @@ mod wasm;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::sync::{LazyLock, PoisonError, RwLock};
 
 use crate::error::ConfigError;
-use crate::settings::Settings;
+use crate::settings::{CopyTradingSettings, Settings};
 
 // Re-export all public types so `crate::config::FooConfig` continues to work.
 pub use self::agent::AgentConfig;
 pub use self::builder::BuilderModeConfig;
 pub use self::channels::{ChannelsConfig, CliConfig, GatewayConfig, HttpConfig};
-pub use self::database::{DatabaseBackend, DatabaseConfig, default_libsql_path};
+pub use self::database::{
+    DatabaseBackend, DatabaseConfig, IntentAuditQuota, IntentAuditQuotaPolicy, default_libsql_path,
+};
 pub use self::embeddings::EmbeddingsConfig;
+pub use self::explain::{ConfigResolution, ConfigSource};
 pub use self::heartbeat::HeartbeatConfig;
+pub use self::http_policy::HttpPolicy;
 pub use self::llm::{
-    AnthropicDirectConfig, LlmBackend, LlmConfig, NearAiApiMode, NearAiConfig, OllamaConfig,
-    OpenAiCompatibleConfig, OpenAiDirectConfig, TinfoilConfig,
+    AnthropicDirectConfig, LlmBackend, LlmConfig, LlmProbeResult, NearAiApiMode, NearAiConfig,
+    OllamaConfig, OpenAiCompatibleConfig, OpenAiDirectConfig, TinfoilConfig,
+};
+pub use self::policy_override::{
+    PolicyOverride, PolicyOverrideError, apply_policy_override, load_policy_override,
+    set_policy_override,
 };
+pub use self::retry::{AttemptTimedOut, Retryable, retry_delay, retry_with_backoff};
 pub use self::routines::RoutineConfig;
 pub use self::safety::SafetyConfig;
 pub use self::sandbox::{ClaudeCodeConfig, SandboxModeConfig};
@@ -52,7 +66,16 @@ pub use self::wasm::WasmConfig;
 /// Used by `inject_llm_keys_from_secrets()` to make API keys available to
 /// `optional_env()` without unsafe `set_var` calls. `optional_env()` checks
 /// real env vars first, then falls back to this overlay.
-static INJECTED_VARS: OnceLock<HashMap<String, String>> = OnceLock::new();
+/// Calling it again swaps in rotated values without a restart.
+static INJECTED_VARS: LazyLock<RwLock<HashMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Secret names mirrored into the overlay, keyed by the env var they back.
+const LLM_SECRET_ENV_MAPPINGS: [(&str, &str); 3] = [
+    ("llm_openai_api_key", "OPENAI_API_KEY"),
+    ("llm_anthropic_api_key", "ANTHROPIC_API_KEY"),
+    ("llm_compatible_api_key", "LLM_API_KEY"),
+];
 
 /// Main configuration for the agent.
 #[derive(Debug, Clone)]
@@ -96,7 +119,7 @@ impl HyperliquidNetwork {
         }
     }
 
-    fn default_api_base_url(self) -> &'static str {
+    pub(crate) fn default_api_base_url(self) -> &'static str {
         match self {
             Self::Mainnet => "https://api.hyperliquid.xyz",
             Self::Testnet => "https://api.hyperliquid-testnet.xyz",
@@ -111,7 +134,11 @@ impl HyperliquidNetwork {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Paper/live execution policy, ordered from most to least restrictive.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
 pub enum PaperLivePolicy {
     PaperOnly,
     PaperFirst,
@@ -119,6 +146,14 @@ pub enum PaperLivePolicy {
 }
 
 impl PaperLivePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PaperOnly => "paper_only",
+            Self::PaperFirst => "paper_first",
+            Self::LiveAllowed => "live_allowed",
+        }
+    }
+
     fn parse(value: &str, key: &str) -> Result<Self, ConfigError> {
         match normalize_variant(value).as_str() {
             "paper" | "paper_only" => Ok(Self::PaperOnly),
@@ -295,8 +330,20 @@ impl HyperliquidRuntimeConfig {
             paper_live_policy,
         })
     }
+
+    /// Timeout and retry budget for Hyperliquid API requests.
+    pub fn http_policy(&self) -> HttpPolicy {
+        HttpPolicy {
+            timeout: std::time::Duration::from_millis(self.timeout_ms),
+            max_retries: self.max_retries,
+            backoff: std::time::Duration::from_millis(self.retry_backoff_ms),
+        }
+    }
 }
 
+/// Upper bound for any single per-symbol position cap, in USD units.
+pub const PER_SYMBOL_POSITION_CAP_CEILING_USD: u64 = 10_000_000;
+
 /// Wallet and vault policy resolved config (Wizard Step 9).
 #[derive(Debug, Clone)]
 pub struct WalletVaultPolicyConfig {
@@ -308,6 +355,11 @@ pub struct WalletVaultPolicyConfig {
     pub leverage_cap: u32,
     pub kill_switch_enabled: bool,
     pub kill_switch_behavior: KillSwitchBehavior,
+    /// Operator explicitly acknowledged that the kill switch may flatten
+    /// positions. Always `true` when `kill_switch_behavior` is `CancelAndFlatten`.
+    pub flatten_confirmed: bool,
+    /// Position caps keyed by upper-case symbol; see [`Self::position_cap_for`].
+    pub per_symbol_position_caps: HashMap<String, u64>,
 }
 
 impl WalletVaultPolicyConfig {
@@ -363,20 +415,144 @@ impl WalletVaultPolicyConfig {
             "HYPERLIQUID_KILL_SWITCH_BEHAVIOR",
         )?;
 
+        let flatten_confirmed = helpers::optional_env("HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN")?
+            .map(|s| s.parse())
+            .transpose()
+            .map_err(|e| ConfigError::InvalidValue {
+                key: "HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN".to_string(),
+                message: format!("must be 'true' or 'false': {e}"),
+            })?
+            .unwrap_or(settings.wallet_vault_policy.kill_switch_confirm_flatten);
+        if kill_switch_behavior == KillSwitchBehavior::CancelAndFlatten && !flatten_confirmed {
+            return Err(ConfigError::InvalidValue {
+                key: "HYPERLIQUID_KILL_SWITCH_BEHAVIOR".to_string(),
+                message: "cancel_and_flatten closes live positions; set \
+                          HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN=true to confirm"
+                    .to_string(),
+            });
+        }
+
+        let per_symbol_position_caps =
+            match helpers::optional_env("HYPERLIQUID_PER_SYMBOL_POSITION_CAPS")? {
+                Some(raw) => serde_json::from_str(&raw).map_err(|e| ConfigError::InvalidValue {
+                    key: "HYPERLIQUID_PER_SYMBOL_POSITION_CAPS".to_string(),
+                    message: format!("must be a JSON object of symbol to USD cap: {e}"),
+                })?,
+                None => settings
+                    .wallet_vault_policy
+                    .per_symbol_position_caps
+                    .clone(),
+            };
+        let per_symbol_position_caps = normalize_per_symbol_position_caps(per_symbol_position_caps)
+            .map_err(|message| ConfigError::InvalidValue {
+                key: "HYPERLIQUID_PER_SYMBOL_POSITION_CAPS".to_string(),
+                message,
+            })?;
+
+        let operator_wallet_address = helpers::optional_env("HYPERLIQUID_OPERATOR_WALLET_ADDRESS")?
+            .or_else(|| settings.wallet_vault_policy.operator_wallet_address.clone());
+        let user_wallet_address = helpers::optional_env("HYPERLIQUID_USER_WALLET_ADDRESS")?
+            .or_else(|| settings.wallet_vault_policy.user_wallet_address.clone());
+        if custody_mode == CustodyMode::DualMode {
+            let present = |addr: &Option<String>| {
+                addr.as_deref()
+                    .map(str::trim)
+                    .filter(|a| !a.is_empty())
+                    .map(str::to_ascii_lowercase)
+            };
+            match (
+                present(&operator_wallet_address),
+                present(&user_wallet_address),
+            ) {
+                (Some(operator), Some(user)) if operator == user => {
+                    return Err(ConfigError::InvalidValue {
+                        key: "HYPERLIQUID_CUSTODY_MODE".to_string(),
+                        message: "dual_mode requires distinct operator and user wallet addresses"
+                            .to_string(),
+                    });
+                }
+                (Some(_), Some(_)) => {}
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        key: "HYPERLIQUID_CUSTODY_MODE".to_string(),
+                        message: "dual_mode requires both HYPERLIQUID_OPERATOR_WALLET_ADDRESS \
+                                  and HYPERLIQUID_USER_WALLET_ADDRESS"
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
         Ok(Self {
             custody_mode,
-            operator_wallet_address: helpers::optional_env("HYPERLIQUID_OPERATOR_WALLET_ADDRESS")?
-                .or_else(|| settings.wallet_vault_policy.operator_wallet_address.clone()),
-            user_wallet_address: helpers::optional_env("HYPERLIQUID_USER_WALLET_ADDRESS")?
-                .or_else(|| settings.wallet_vault_policy.user_wallet_address.clone()),
+            operator_wallet_address,
+            user_wallet_address,
             vault_address: helpers::optional_env("HYPERLIQUID_VAULT_ADDRESS")?
                 .or_else(|| settings.wallet_vault_policy.vault_address.clone()),
             max_position_size_usd,
             leverage_cap,
             kill_switch_enabled,
             kill_switch_behavior,
+            flatten_confirmed,
+            per_symbol_position_caps,
         })
     }
+
+    /// Position cap for `symbol`, falling back to `max_position_size_usd`.
+    pub fn position_cap_for(&self, symbol: &str) -> u64 {
+        self.per_symbol_position_caps
+            .get(&symbol.trim().to_ascii_uppercase())
+            .copied()
+            .unwrap_or(self.max_position_size_usd)
+    }
+}
+
+/// Upper-case the symbols and check every cap is in
+/// `1..=PER_SYMBOL_POSITION_CAP_CEILING_USD`.
+fn normalize_per_symbol_position_caps(
+    caps: HashMap<String, u64>,
+) -> Result<HashMap<String, u64>, String> {
+    let mut normalized = HashMap::with_capacity(caps.len());
+    for (symbol, cap) in caps {
+        let key = symbol.trim().to_ascii_uppercase();
+        if key.is_empty() {
+            return Err("symbol must not be empty".to_string());
+        }
+        if cap == 0 || cap > PER_SYMBOL_POSITION_CAP_CEILING_USD {
+            return Err(format!(
+                "cap for {key} must be between 1 and {PER_SYMBOL_POSITION_CAP_CEILING_USD}, got {cap}"
+            ));
+        }
+        if normalized.insert(key.clone(), cap).is_some() {
+            return Err(format!("symbol {key} is listed more than once"));
+        }
+    }
+    Ok(normalized)
+}
+
+/// Notional in USD after adverse slippage of `max_slippage_bps`, rounded up.
+pub fn worst_case_notional(base_notional_usd: u64, max_slippage_bps: u32) -> u64 {
+    let slippage = (u128::from(base_notional_usd) * u128::from(max_slippage_bps)).div_ceil(10_000);
+    base_notional_usd.saturating_add(u64::try_from(slippage).unwrap_or(u64::MAX))
+}
+
+/// Check `notional` against the per-trade cap, both as requested and once
+/// the configured slippage is applied.
+pub fn within_trade_caps(notional: u64, cfg: &CopyTradingSettings) -> Result<(), String> {
+    let cap = cfg.per_trade_notional_cap_usd;
+    if notional > cap {
+        return Err(format!(
+            "trade notional {notional} exceeds per-trade cap {cap}"
+        ));
+    }
+    let worst_case = worst_case_notional(notional, cfg.max_slippage_bps);
+    if worst_case > cap {
+        return Err(format!(
+            "worst-case notional {worst_case} at {} bps slippage exceeds per-trade cap {cap}",
+            cfg.max_slippage_bps
+        ));
+    }
+    Ok(())
 }
 
 /// Verification backend resolved config (Wizard Step 10).
@@ -385,6 +561,8 @@ pub struct VerificationBackendConfig {
     pub backend: VerificationBackendKind,
     pub eigencloud: EigenCloudConfig,
     pub fallback: SignedFallbackReceiptChainConfig,
+    /// Require signed fallback receipts platform-wide, overriding user configs.
+    pub force_signed_receipts: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -395,6 +573,39 @@ pub struct EigenCloudConfig {
     pub timeout_ms: u64,
 }
 
+impl EigenCloudConfig {
+    /// Timeout for EigenCloud requests; failed submissions are not retried.
+    pub fn http_policy(&self) -> HttpPolicy {
+        HttpPolicy {
+            timeout: std::time::Duration::from_millis(self.timeout_ms),
+            max_retries: 0,
+            backoff: std::time::Duration::ZERO,
+        }
+    }
+
+    /// How `auth_token` is presented to the EigenCloud endpoint.
+    pub fn auth_scheme(&self) -> EigenCloudAuthScheme {
+        self.auth_scheme
+    }
+
+    /// HTTP header carrying `auth_token` for the configured scheme.
+    ///
+    /// Returns `None` when no (non-blank) token is configured.
+    pub fn auth_header(&self) -> Option<(String, String)> {
+        let token = self
+            .auth_token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())?;
+        Some(match self.auth_scheme {
+            EigenCloudAuthScheme::Bearer => {
+                ("Authorization".to_string(), format!("Bearer {token}"))
+            }
+            EigenCloudAuthScheme::ApiKey => ("X-API-Key".to_string(), token.to_string()),
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SignedFallbackReceiptChainConfig {
     pub enabled: bool,
@@ -467,14 +678,64 @@ impl VerificationBackendConfig {
                         .fallback_require_signed_receipts,
                 );
 
+        let endpoint = helpers::optional_env("EIGENCLOUD_ENDPOINT")?
+            .or_else(|| settings.verification_backend.eigencloud_endpoint.clone())
+            .map(|endpoint| endpoint.trim().to_string())
+            .filter(|endpoint| !endpoint.is_empty());
+        helpers::validate_optional_url(
+            endpoint.as_deref(),
+            &["http", "https"],
+            "EIGENCLOUD_ENDPOINT",
+        )?;
+        // Without an endpoint EigenCloud can only degrade to fallback receipts,
+        // so it is required once those are switched off.
+        if backend == VerificationBackendKind::EigenCloudPrimary && endpoint.is_none() {
+            if !fallback_enabled {
+                return Err(ConfigError::InvalidValue {
+                    key: "EIGENCLOUD_ENDPOINT".to_string(),
+                    message: "is required when VERIFICATION_BACKEND=eigencloud_primary \
+                              and fallback receipts are disabled"
+                        .to_string(),
+                });
+            }
+            tracing::warn!(
+                "EIGENCLOUD_ENDPOINT is not set; verification will use signed fallback receipts"
+            );
+        }
+        // EigenCloud rejects unauthenticated submissions, so the token is
+        // required on the same terms as the endpoint.
+        let auth_token = helpers::optional_env("EIGENCLOUD_AUTH_TOKEN")?
+            .or_else(|| settings.verification_backend.eigencloud_auth_token.clone())
+            .filter(|token| !token.trim().is_empty());
+        if backend == VerificationBackendKind::EigenCloudPrimary && auth_token.is_none() {
+            if !fallback_enabled {
+                return Err(ConfigError::InvalidValue {
+                    key: "EIGENCLOUD_AUTH_TOKEN".to_string(),
+                    message: "is required when VERIFICATION_BACKEND=eigencloud_primary \
+                              and fallback receipts are disabled"
+                        .to_string(),
+                });
+            }
+            tracing::warn!(
+                "EIGENCLOUD_AUTH_TOKEN is not set; verification will use signed fallback receipts"
+            );
+        }
+
+        let force_signed_receipts = helpers::optional_env("VERIFICATION_FORCE_SIGNED_RECEIPTS")?
+            .map(|s| s.parse())
+            .transpose()
+            .map_err(|e| ConfigError::InvalidValue {
+                key: "VERIFICATION_FORCE_SIGNED_RECEIPTS".to_string(),
+                message: format!("must be 'true' or 'false': {e}"),
+            })?
+            .unwrap_or(settings.verification_backend.force_signed_receipts);
+
         Ok(Self {
             backend,
             eigencloud: EigenCloudConfig {
-                endpoint: helpers::optional_env("EIGENCLOUD_ENDPOINT")?
-                    .or_else(|| settings.verification_backend.eigencloud_endpoint.clone()),
+                endpoint,
                 auth_scheme,
-                auth_token: helpers::optional_env("EIGENCLOUD_AUTH_TOKEN")?
-                    .or_else(|| settings.verification_backend.eigencloud_auth_token.clone()),
+                auth_token,
                 timeout_ms,
             },
             fallback: SignedFallbackReceiptChainConfig {
@@ -489,6 +750,7 @@ impl VerificationBackendConfig {
                 chain_path,
                 require_signed_receipts,
             },
+            force_signed_receipts,
         })
     }
 }
@@ -505,6 +767,19 @@ fn default_fallback_receipt_chain_path() -> PathBuf {
         .join("receipt-chain.ndjson")
 }
 
+/// Surface stored settings that failed to parse and fell back to defaults.
+fn log_setting_warnings(warnings: &[crate::settings::SettingWarning]) {
+    for warning in warnings {
+        tracing::warn!(
+            key = %warning.key,
+            raw_value = %warning.raw_value,
+            expected = %warning.expected,
+            "{}",
+            warning
+        );
+    }
+}
+
 impl Config {
     /// Load configuration from environment variables and the database.
     ///
@@ -517,26 +792,78 @@ impl Config {
         Self::from_db_with_toml(store, user_id, None).await
     }
 
+    /// Load configuration for a single agent.
+    ///
+    /// Agent-scoped DB settings shadow the user-global ones; keys the agent
+    /// has not overridden fall through to the global value.
+    pub async fn from_db_for_agent(
+        store: &(dyn crate::db::SettingsStore + Sync),
+        user_id: &str,
+        agent_id: uuid::Uuid,
+    ) -> Result<Self, ConfigError> {
+        let _ = dotenvy::dotenv();
+        crate::bootstrap::load_enclagent_env();
+
+        let global = match store.get_all_settings(user_id).await {
+            Ok(map) => map,
+            Err(e) => {
+                tracing::warn!("Failed to load settings from DB, using defaults: {}", e);
+                HashMap::new()
+            }
+        };
+        let agent = match store.get_all_settings_for_agent(user_id, agent_id).await {
+            Ok(map) => map,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load agent {} settings from DB, using user-global values: {}",
+                    agent_id,
+                    e
+                );
+                HashMap::new()
+            }
+        };
+        let (db_settings, warnings) = Settings::from_db_maps(&global, &agent);
+        log_setting_warnings(&warnings);
+
+        Self::build(&db_settings).await
+    }
+
     /// Load from DB with an optional TOML config file overlay.
     pub async fn from_db_with_toml(
         store: &(dyn crate::db::SettingsStore + Sync),
         user_id: &str,
         toml_path: Option<&std::path::Path>,
+    ) -> Result<Self, ConfigError> {
+        let layers: Vec<&std::path::Path> = toml_path.into_iter().collect();
+        Self::from_db_with_toml_layers(store, user_id, &layers).await
+    }
+
+    /// Load from DB with TOML config files overlaid in order (later files win).
+    ///
+    /// See [`Config::from_env_with_toml_layers`] for how missing files are handled.
+    pub async fn from_db_with_toml_layers(
+        store: &(dyn crate::db::SettingsStore + Sync),
+        user_id: &str,
+        toml_paths: &[&std::path::Path],
     ) -> Result<Self, ConfigError> {
         let _ = dotenvy::dotenv();
         crate::bootstrap::load_enclagent_env();
 
         // Load all settings from DB into a Settings struct
         let mut db_settings = match store.get_all_settings(user_id).await {
-            Ok(map) => Settings::from_db_map(&map),
+            Ok(map) => {
+                let (settings, warnings) = Settings::from_db_map(&map);
+                log_setting_warnings(&warnings);
+                settings
+            }
             Err(e) => {
                 tracing::warn!("Failed to load settings from DB, using defaults: {}", e);
                 Settings::default()
             }
         };
 
-        // Overlay TOML config file (values win over DB settings)
-        Self::apply_toml_overlay(&mut db_settings, toml_path)?;
+        // Overlay TOML config files (values win over DB settings)
+        Self::apply_toml_layers(&mut db_settings, toml_paths)?;
 
         Self::build(&db_settings).await
     }
@@ -556,17 +883,91 @@ impl Config {
     /// Load from env with an optional TOML config file overlay.
     pub async fn from_env_with_toml(
         toml_path: Option<&std::path::Path>,
+    ) -> Result<Self, ConfigError> {
+        let layers: Vec<&std::path::Path> = toml_path.into_iter().collect();
+        Self::from_env_with_toml_layers(&layers).await
+    }
+
+    /// Load from env with TOML config files overlaid in order (later files win).
+    ///
+    /// The first path is required, like an explicit `--config`; later paths are
+    /// optional local overrides that are skipped with a warning when missing.
+    /// An empty slice falls back to the default `~/.enclagent/config.toml`.
+    pub async fn from_env_with_toml_layers(
+        toml_paths: &[&std::path::Path],
     ) -> Result<Self, ConfigError> {
         let _ = dotenvy::dotenv();
         crate::bootstrap::load_enclagent_env();
         let mut settings = Settings::load();
 
-        // Overlay TOML config file (values win over JSON settings)
-        Self::apply_toml_overlay(&mut settings, toml_path)?;
+        // Overlay TOML config files (values win over JSON settings)
+        Self::apply_toml_layers(&mut settings, toml_paths)?;
 
         Self::build(&settings).await
     }
 
+    /// Load from env after first loading the given dotenv files in order.
+    ///
+    /// Like the default `./.env` and `~/.enclagent/.env` loads, these never
+    /// overwrite vars that are already set, so real process env vars still
+    /// win and earlier files take priority over later ones and the defaults.
+    /// A missing or unparseable file is an error, like an explicit `--env-file`.
+    pub async fn from_env_with_env_files(
+        env_files: &[&std::path::Path],
+    ) -> Result<Self, ConfigError> {
+        Self::load_env_files(env_files)?;
+        Self::from_env().await
+    }
+
+    /// Load each dotenv file in order without overwriting existing vars.
+    fn load_env_files(env_files: &[&std::path::Path]) -> Result<(), ConfigError> {
+        for path in env_files {
+            dotenvy::from_path(path).map_err(|e| {
+                ConfigError::ParseError(format!(
+                    "Failed to load env file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            tracing::debug!("Loaded env file {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// Merge each TOML file into settings in order via `Settings::merge_from`.
+    fn apply_toml_layers(
+        settings: &mut Settings,
+        toml_paths: &[&std::path::Path],
+    ) -> Result<(), ConfigError> {
+        let Some((first, rest)) = toml_paths.split_first() else {
+            return Self::apply_toml_overlay(settings, None);
+        };
+        Self::apply_toml_overlay(settings, Some(first))?;
+
+        for path in rest {
+            match Settings::load_toml(path) {
+                Ok(Some(toml_settings)) => {
+                    settings.merge_from(&toml_settings);
+                    tracing::debug!("Loaded TOML config layer from {}", path.display());
+                }
+                Ok(None) => {
+                    tracing::warn!(
+                        "Optional config layer not found, skipping: {}",
+                        path.display()
+                    );
+                }
+                Err(e) => {
+                    return Err(ConfigError::ParseError(format!(
+                        "Failed to load config file {}: {}",
+                        path.display(),
+                        e
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Load and merge a TOML config file into settings.
     ///
     /// If `explicit_path` is `Some`, loads from that path (errors are fatal).
@@ -607,14 +1008,85 @@ impl Config {
         Ok(())
     }
 
+    /// Run every resolver against `settings` without building a config,
+    /// collecting all failures instead of stopping at the first one.
+    pub async fn validate_all(settings: &Settings) -> Result<(), Vec<ConfigError>> {
+        fn collect<T>(errors: &mut Vec<ConfigError>, result: Result<T, ConfigError>) {
+            if let Err(err) = result {
+                errors.push(err);
+            }
+        }
+
+        let mut errors = Vec::new();
+        collect(&mut errors, DatabaseConfig::resolve());
+        collect(&mut errors, LlmConfig::resolve(settings));
+        collect(&mut errors, EmbeddingsConfig::resolve(settings));
+        collect(&mut errors, TunnelConfig::resolve(settings));
+        collect(&mut errors, ChannelsConfig::resolve(settings));
+        collect(&mut errors, AgentConfig::resolve(settings));
+        collect(&mut errors, SafetyConfig::resolve());
+        collect(&mut errors, WasmConfig::resolve());
+        collect(&mut errors, SecretsConfig::resolve().await);
+        collect(&mut errors, BuilderModeConfig::resolve());
+        collect(&mut errors, HeartbeatConfig::resolve(settings));
+        collect(&mut errors, HyperliquidRuntimeConfig::resolve(settings));
+        collect(&mut errors, WalletVaultPolicyConfig::resolve(settings));
+        collect(&mut errors, VerificationBackendConfig::resolve(settings));
+        collect(&mut errors, RoutineConfig::resolve());
+        collect(&mut errors, SandboxModeConfig::resolve());
+        collect(&mut errors, ClaudeCodeConfig::resolve());
+        collect(&mut errors, SkillsConfig::resolve());
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Load settings the way [`Config::from_env`] does and validate them with
+    /// [`Config::validate_all`].
+    pub async fn validate_env() -> Result<(), Vec<ConfigError>> {
+        let _ = dotenvy::dotenv();
+        crate::bootstrap::load_enclagent_env();
+        let mut settings = Settings::load();
+        Self::apply_toml_overlay(&mut settings, None).map_err(|err| vec![err])?;
+
+        Self::validate_all(&settings).await
+    }
+
     /// Build config from settings (shared by from_env and from_db).
     async fn build(settings: &Settings) -> Result<Self, ConfigError> {
+        let verification_backend = VerificationBackendConfig::resolve(settings)?;
+        let mut channels = ChannelsConfig::resolve(settings)?;
+        if let Some(frontdoor) = channels
+            .gateway
+            .as_mut()
+            .and_then(|gateway| gateway.frontdoor.as_mut())
+        {
+            frontdoor.force_signed_receipts = verification_backend.force_signed_receipts;
+        }
+
+        let llm = LlmConfig::resolve(settings)?;
+        if llm.startup_probe {
+            let probe = llm.probe().await.map_err(|e| ConfigError::InvalidValue {
+                key: "LLM_STARTUP_PROBE".to_string(),
+                message: format!("{} backend probe failed: {e}", llm.backend),
+            })?;
+            tracing::info!(
+                backend = %probe.backend,
+                model = %probe.model,
+                latency_ms = probe.latency.as_millis() as u64,
+                "LLM backend probe succeeded"
+            );
+        }
+
         Ok(Self {
             database: DatabaseConfig::resolve()?,
-            llm: LlmConfig::resolve(settings)?,
+            llm,
             embeddings: EmbeddingsConfig::resolve(settings)?,
             tunnel: TunnelConfig::resolve(settings)?,
-            channels: ChannelsConfig::resolve(settings)?,
+            channels,
             agent: AgentConfig::resolve(settings)?,
             safety: SafetyConfig::resolve()?,
             wasm: WasmConfig::resolve()?,
@@ -623,14 +1095,12 @@ impl Config {
             heartbeat: HeartbeatConfig::resolve(settings)?,
             hyperliquid_runtime: HyperliquidRuntimeConfig::resolve(settings)?,
             wallet_vault_policy: WalletVaultPolicyConfig::resolve(settings)?,
-            verification_backend: VerificationBackendConfig::resolve(settings)?,
+            verification_backend,
             routines: RoutineConfig::resolve()?,
             sandbox: SandboxModeConfig::resolve()?,
             claude_code: ClaudeCodeConfig::resolve()?,
             skills: SkillsConfig::resolve()?,
-            observability: crate::observability::ObservabilityConfig {
-                backend: std::env::var("OBSERVABILITY_BACKEND").unwrap_or_else(|_| "none".into()),
-            },
+            observability: crate::observability::ObservabilityConfig::resolve()?,
         })
     }
 }
@@ -641,17 +1111,22 @@ impl Config {
 /// env-var-first resolution in `LlmConfig::resolve()`. Keys in the overlay
 /// are read by `optional_env()` before falling back to `std::env::var()`,
 /// so explicit env vars always win.
+///
+/// Each call replaces the previous overlay entries, so calling it again after
+/// a key is rotated or deleted takes effect on the next `optional_env()` read.
 pub async fn inject_llm_keys_from_secrets(
     secrets: &dyn crate::secrets::SecretsStore,
     user_id: &str,
 ) {
-    let mappings = [
-        ("llm_openai_api_key", "OPENAI_API_KEY"),
-        ("llm_anthropic_api_key", "ANTHROPIC_API_KEY"),
-        ("llm_compatible_api_key", "LLM_API_KEY"),
-    ];
+    refresh_injected_vars(secrets, user_id, &LLM_SECRET_ENV_MAPPINGS).await;
+}
 
-    let mut injected = HashMap::new();
+async fn refresh_injected_vars(
+    secrets: &dyn crate::secrets::SecretsStore,
+    user_id: &str,
+    mappings: &[(&str, &str)],
+) {
+    let mut loaded = HashMap::new();
 
     for (secret_name, env_var) in mappings {
         match std::env::var(env_var) {
@@ -660,7 +1135,7 @@ pub async fn inject_llm_keys_from_secrets(
         }
         match secrets.get_decrypted(user_id, secret_name).await {
             Ok(decrypted) => {
-                injected.insert(env_var.to_string(), decrypted.expose().to_string());
+                loaded.insert(env_var.to_string(), decrypted.expose().to_string());
                 tracing::debug!("Loaded secret '{}' for env var '{}'", secret_name, env_var);
             }
             Err(_) => {
@@ -669,17 +1144,21 @@ pub async fn inject_llm_keys_from_secrets(
         }
     }
 
-    let _ = INJECTED_VARS.set(injected);
+    let mut injected = INJECTED_VARS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    for (_, env_var) in mappings {
+        injected.remove(*env_var);
+    }
+    injected.extend(loaded);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::helpers::ENV_MUTEX;
     use crate::error::ConfigError;
     use crate::settings::Settings;
-    use std::sync::Mutex;
-
-    static ENV_MUTEX: Mutex<()> = Mutex::new(());
 
     fn clear_hl_policy_env() {
         // SAFETY: Guarded by ENV_MUTEX in tests.
@@ -699,6 +1178,8 @@ mod tests {
             std::env::remove_var("HYPERLIQUID_LEVERAGE_CAP");
             std::env::remove_var("HYPERLIQUID_KILL_SWITCH_ENABLED");
             std::env::remove_var("HYPERLIQUID_KILL_SWITCH_BEHAVIOR");
+            std::env::remove_var("HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN");
+            std::env::remove_var("HYPERLIQUID_PER_SYMBOL_POSITION_CAPS");
             std::env::remove_var("VERIFICATION_BACKEND");
             std::env::remove_var("EIGENCLOUD_ENDPOINT");
             std::env::remove_var("EIGENCLOUD_AUTH_SCHEME");
@@ -708,7 +1189,76 @@ mod tests {
             std::env::remove_var("VERIFICATION_FALLBACK_SIGNING_KEY_ID");
             std::env::remove_var("VERIFICATION_FALLBACK_CHAIN_PATH");
             std::env::remove_var("VERIFICATION_FALLBACK_REQUIRE_SIGNED_RECEIPTS");
+            std::env::remove_var("VERIFICATION_FORCE_SIGNED_RECEIPTS");
+        }
+    }
+
+    #[test]
+    fn validate_all_reports_every_invalid_value() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("HYPERLIQUID_NETWORK", "moonnet");
+            std::env::set_var("HYPERLIQUID_CUSTODY_MODE", "shared_wallet");
+            std::env::set_var("VERIFICATION_BACKEND", "trust_me");
         }
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime");
+        let errors = rt
+            .block_on(Config::validate_all(&Settings::default()))
+            .expect_err("invalid env should fail validation");
+        clear_hl_policy_env();
+
+        let invalid_keys: Vec<&str> = errors
+            .iter()
+            .filter_map(|err| match err {
+                ConfigError::InvalidValue { key, .. } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+        for key in [
+            "HYPERLIQUID_NETWORK",
+            "HYPERLIQUID_CUSTODY_MODE",
+            "VERIFICATION_BACKEND",
+        ] {
+            assert!(invalid_keys.contains(&key), "{key} missing from {errors:?}");
+        }
+    }
+
+    #[test]
+    fn toml_layers_apply_in_order_with_fallthrough() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let base = dir.path().join("base.toml");
+        let local = dir.path().join("local.toml");
+        std::fs::write(
+            &base,
+            "[agent]\nname = \"base-bot\"\nmax_parallel_jobs = 9\n\n[heartbeat]\nenabled = true\n",
+        )
+        .expect("write base");
+        std::fs::write(&local, "[agent]\nname = \"local-bot\"\n").expect("write local");
+
+        let mut settings = Settings::default();
+        Config::apply_toml_layers(&mut settings, &[base.as_path(), local.as_path()])
+            .expect("apply layers");
+        assert_eq!(settings.agent.name, "local-bot");
+        assert_eq!(settings.agent.max_parallel_jobs, 9);
+        assert!(settings.heartbeat.enabled);
+
+        // A missing override layer is skipped, a missing first layer is fatal.
+        let missing = dir.path().join("missing.toml");
+        let mut settings = Settings::default();
+        Config::apply_toml_layers(&mut settings, &[base.as_path(), missing.as_path()])
+            .expect("missing optional layer is skipped");
+        assert_eq!(settings.agent.name, "base-bot");
+        let mut settings = Settings::default();
+        assert!(
+            Config::apply_toml_layers(&mut settings, &[missing.as_path(), local.as_path()])
+                .is_err()
+        );
     }
 
     #[test]
@@ -784,6 +1334,336 @@ mod tests {
         clear_hl_policy_env();
     }
 
+    #[test]
+    fn eigencloud_primary_requires_endpoint_without_fallback() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("VERIFICATION_BACKEND", "eigencloud_primary");
+            std::env::set_var("VERIFICATION_FALLBACK_ENABLED", "false");
+        }
+        let err =
+            VerificationBackendConfig::resolve(&Settings::default()).expect_err("missing endpoint");
+        clear_hl_policy_env();
+
+        assert!(
+            matches!(&err, ConfigError::InvalidValue { key, .. } if key == "EIGENCLOUD_ENDPOINT"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn eigencloud_primary_auth_token_requirement_depends_on_fallback() {
+        #[derive(Clone, Default)]
+        struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+        impl std::io::Write for CapturedLogs {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().expect("log buffer").extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        let resolve_with = |token: Option<&str>, fallback: bool| {
+            clear_hl_policy_env();
+            // SAFETY: Guarded by ENV_MUTEX in tests.
+            unsafe {
+                std::env::set_var("VERIFICATION_BACKEND", "eigencloud_primary");
+                std::env::set_var("EIGENCLOUD_ENDPOINT", "https://verifier.eigencloud.xyz/v1");
+                std::env::set_var("VERIFICATION_FALLBACK_ENABLED", fallback.to_string());
+                if let Some(token) = token {
+                    std::env::set_var("EIGENCLOUD_AUTH_TOKEN", token);
+                }
+            }
+            let logs = CapturedLogs::default();
+            let subscriber = tracing_subscriber::fmt()
+                .with_writer({
+                    let logs = logs.clone();
+                    move || logs.clone()
+                })
+                .with_ansi(false)
+                .finish();
+            let result = tracing::subscriber::with_default(subscriber, || {
+                VerificationBackendConfig::resolve(&Settings::default())
+            });
+            clear_hl_policy_env();
+            let logs =
+                String::from_utf8(logs.0.lock().expect("log buffer").clone()).expect("utf8 logs");
+            (result, logs)
+        };
+
+        let (result, logs) = resolve_with(Some("ec-token"), false);
+        let verification = result.expect("primary with token");
+        assert_eq!(
+            verification.eigencloud.auth_token.as_deref(),
+            Some("ec-token")
+        );
+        assert!(!logs.contains("EIGENCLOUD_AUTH_TOKEN"), "{logs}");
+
+        let (result, logs) = resolve_with(None, true);
+        let verification = result.expect("fallback covers the missing token");
+        assert!(verification.eigencloud.auth_token.is_none());
+        assert!(logs.contains("WARN"), "{logs}");
+        assert!(logs.contains("EIGENCLOUD_AUTH_TOKEN is not set"), "{logs}");
+
+        for token in [None, Some("  ")] {
+            let (result, _) = resolve_with(token, false);
+            let err = result.expect_err("token required without fallback");
+            assert!(
+                matches!(&err, ConfigError::InvalidValue { key, .. } if key == "EIGENCLOUD_AUTH_TOKEN"),
+                "{token:?}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_eigencloud_endpoint_is_rejected() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+
+        for endpoint in [
+            "verifier.eigencloud.xyz/v1",
+            "ftp://verifier.eigencloud.xyz",
+        ] {
+            clear_hl_policy_env();
+            // SAFETY: Guarded by ENV_MUTEX in tests.
+            unsafe {
+                std::env::set_var("EIGENCLOUD_ENDPOINT", endpoint);
+            }
+            let err = VerificationBackendConfig::resolve(&Settings::default())
+                .expect_err("malformed endpoint");
+            assert!(
+                matches!(&err, ConfigError::InvalidValue { key, .. } if key == "EIGENCLOUD_ENDPOINT"),
+                "{endpoint}: {err:?}"
+            );
+        }
+        clear_hl_policy_env();
+    }
+
+    #[test]
+    fn https_eigencloud_endpoint_resolves() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("VERIFICATION_BACKEND", "eigencloud_primary");
+            std::env::set_var("VERIFICATION_FALLBACK_ENABLED", "false");
+            std::env::set_var(
+                "EIGENCLOUD_ENDPOINT",
+                " https://verifier.eigencloud.xyz/v1 ",
+            );
+            std::env::set_var("EIGENCLOUD_AUTH_TOKEN", "ec-token");
+        }
+        let verification =
+            VerificationBackendConfig::resolve(&Settings::default()).expect("verify resolve");
+        clear_hl_policy_env();
+
+        assert_eq!(
+            verification.eigencloud.endpoint.as_deref(),
+            Some("https://verifier.eigencloud.xyz/v1")
+        );
+    }
+
+    #[test]
+    fn per_symbol_position_caps_parse_and_fall_back() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var(
+                "HYPERLIQUID_PER_SYMBOL_POSITION_CAPS",
+                r#"{"btc": 50000, "ETH": 20000}"#,
+            );
+        }
+        let mut settings = Settings::default();
+        settings.wallet_vault_policy.max_position_size_usd = 5_000;
+        let wallet = WalletVaultPolicyConfig::resolve(&settings).expect("wallet resolve");
+        assert_eq!(wallet.position_cap_for("BTC"), 50_000);
+        assert_eq!(wallet.position_cap_for("eth"), 20_000);
+        assert_eq!(wallet.position_cap_for("SOL"), 5_000);
+
+        // The DB/TOML setting applies when the env var is unset.
+        clear_hl_policy_env();
+        settings
+            .wallet_vault_policy
+            .per_symbol_position_caps
+            .insert("SOL".to_string(), 7_500);
+        let wallet = WalletVaultPolicyConfig::resolve(&settings).expect("wallet resolve");
+        assert_eq!(wallet.position_cap_for("SOL"), 7_500);
+        assert_eq!(wallet.position_cap_for("BTC"), 5_000);
+    }
+
+    #[test]
+    fn env_files_load_in_order_without_overriding_process_env() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        let dir = tempfile::tempdir().expect("tempdir");
+        let first = dir.path().join("first.env");
+        let second = dir.path().join("second.env");
+        std::fs::write(
+            &first,
+            "ENCLAGENT_TEST_ENV_FILE_VAR=from_first\nENCLAGENT_TEST_ENV_FILE_WINS=from_file\n",
+        )
+        .expect("write first env file");
+        std::fs::write(&second, "ENCLAGENT_TEST_ENV_FILE_VAR=from_second\n")
+            .expect("write second env file");
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("ENCLAGENT_TEST_ENV_FILE_VAR");
+            std::env::set_var("ENCLAGENT_TEST_ENV_FILE_WINS", "from_process");
+        }
+
+        Config::load_env_files(&[first.as_path(), second.as_path()]).expect("load env files");
+        let from_file = helpers::optional_env("ENCLAGENT_TEST_ENV_FILE_VAR");
+        let process_wins = helpers::optional_env("ENCLAGENT_TEST_ENV_FILE_WINS");
+        let missing = Config::load_env_files(&[dir.path().join("missing.env").as_path()]);
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("ENCLAGENT_TEST_ENV_FILE_VAR");
+            std::env::remove_var("ENCLAGENT_TEST_ENV_FILE_WINS");
+        }
+        assert_eq!(from_file.unwrap().as_deref(), Some("from_first"));
+        assert_eq!(process_wins.unwrap().as_deref(), Some("from_process"));
+        assert!(matches!(missing, Err(ConfigError::ParseError(_))));
+    }
+
+    #[test]
+    fn worst_case_notional_rounds_slippage_up() {
+        assert_eq!(worst_case_notional(1_000, 25), 1_003);
+        assert_eq!(worst_case_notional(10_000, 25), 10_025);
+        assert_eq!(worst_case_notional(500, 0), 500);
+        assert_eq!(worst_case_notional(u64::MAX, 10_000), u64::MAX);
+    }
+
+    #[test]
+    fn within_trade_caps_applies_slippage_ceiling() {
+        let cfg = CopyTradingSettings {
+            per_trade_notional_cap_usd: 750,
+            max_slippage_bps: 25,
+            ..CopyTradingSettings::default()
+        };
+        // 748 + ceil(1.87) = 750: just under the cap with slippage applied.
+        assert!(within_trade_caps(748, &cfg).is_ok());
+        // 749 + ceil(1.8725) = 751: under the raw cap, over once slipped.
+        let err = within_trade_caps(749, &cfg).expect_err("slippage breaches cap");
+        assert!(err.contains("worst-case notional 751"), "{err}");
+        let err = within_trade_caps(751, &cfg).expect_err("raw notional breaches cap");
+        assert!(err.contains("exceeds per-trade cap 750"), "{err}");
+    }
+
+    #[test]
+    fn per_symbol_position_caps_reject_zero_and_over_ceiling() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+
+        for raw in [
+            r#"{"BTC": 0}"#.to_string(),
+            format!(r#"{{"BTC": {}}}"#, PER_SYMBOL_POSITION_CAP_CEILING_USD + 1),
+        ] {
+            // SAFETY: Guarded by ENV_MUTEX in tests.
+            unsafe {
+                std::env::set_var("HYPERLIQUID_PER_SYMBOL_POSITION_CAPS", &raw);
+            }
+            let err = WalletVaultPolicyConfig::resolve(&Settings::default()).unwrap_err();
+            match err {
+                ConfigError::InvalidValue { key, message } => {
+                    assert_eq!(key, "HYPERLIQUID_PER_SYMBOL_POSITION_CAPS");
+                    assert!(message.contains("BTC"), "{message}");
+                }
+                other => panic!("unexpected error: {other:?}"),
+            }
+        }
+        clear_hl_policy_env();
+    }
+
+    #[test]
+    fn cancel_and_flatten_requires_explicit_confirmation() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+
+        let mut settings = Settings::default();
+        settings.wallet_vault_policy.kill_switch_behavior = "cancel_and_flatten".to_string();
+        let err = WalletVaultPolicyConfig::resolve(&settings).unwrap_err();
+        match err {
+            ConfigError::InvalidValue { key, message } => {
+                assert_eq!(key, "HYPERLIQUID_KILL_SWITCH_BEHAVIOR");
+                assert!(message.contains("HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        // Confirmation via the DB/TOML setting.
+        settings.wallet_vault_policy.kill_switch_confirm_flatten = true;
+        let wallet = WalletVaultPolicyConfig::resolve(&settings).expect("wallet resolve");
+        assert_eq!(
+            wallet.kill_switch_behavior,
+            KillSwitchBehavior::CancelAndFlatten
+        );
+        assert!(wallet.flatten_confirmed);
+
+        // Confirmation via env, with the behavior also coming from env.
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("HYPERLIQUID_KILL_SWITCH_BEHAVIOR", "flatten");
+            std::env::set_var("HYPERLIQUID_KILL_SWITCH_CONFIRM_FLATTEN", "true");
+        }
+        let wallet = WalletVaultPolicyConfig::resolve(&Settings::default()).expect("env confirm");
+        assert_eq!(
+            wallet.kill_switch_behavior,
+            KillSwitchBehavior::CancelAndFlatten
+        );
+
+        // The other behaviors need no confirmation.
+        clear_hl_policy_env();
+        for (raw, expected) in [
+            ("pause_agent", KillSwitchBehavior::PauseAgent),
+            ("cancel_open_orders", KillSwitchBehavior::CancelOpenOrders),
+        ] {
+            let mut settings = Settings::default();
+            settings.wallet_vault_policy.kill_switch_behavior = raw.to_string();
+            let wallet = WalletVaultPolicyConfig::resolve(&settings).expect(raw);
+            assert_eq!(wallet.kill_switch_behavior, expected);
+            assert!(!wallet.flatten_confirmed);
+        }
+
+        clear_hl_policy_env();
+    }
+
+    #[test]
+    fn eigencloud_auth_header_follows_scheme() {
+        let mut eigencloud = EigenCloudConfig {
+            endpoint: Some("https://verify.eigencloud.xyz".to_string()),
+            auth_scheme: EigenCloudAuthScheme::Bearer,
+            auth_token: Some("tok-123".to_string()),
+            timeout_ms: 5_000,
+        };
+        assert_eq!(eigencloud.auth_scheme(), EigenCloudAuthScheme::Bearer);
+        assert_eq!(
+            eigencloud.auth_header(),
+            Some(("Authorization".to_string(), "Bearer tok-123".to_string()))
+        );
+
+        eigencloud.auth_scheme = EigenCloudAuthScheme::ApiKey;
+        assert_eq!(eigencloud.auth_scheme(), EigenCloudAuthScheme::ApiKey);
+        assert_eq!(
+            eigencloud.auth_header(),
+            Some(("X-API-Key".to_string(), "tok-123".to_string()))
+        );
+
+        eigencloud.auth_token = None;
+        assert_eq!(eigencloud.auth_header(), None);
+        eigencloud.auth_token = Some("   ".to_string());
+        assert_eq!(eigencloud.auth_header(), None);
+    }
+
     #[test]
     fn ws1_resolvers_reject_invalid_policy_values() {
         let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
@@ -804,4 +1684,146 @@ mod tests {
 
         clear_hl_policy_env();
     }
+
+    #[tokio::test]
+    async fn refreshed_secret_replaces_injected_value() {
+        use secrecy::SecretString;
+
+        use crate::secrets::{
+            CreateSecretParams, InMemorySecretsStore, SecretsCrypto, SecretsStore,
+        };
+
+        const MAPPINGS: [(&str, &str); 1] =
+            [("test_rotated_api_key", "ENCLAGENT_TEST_ROTATED_API_KEY")];
+        let crypto = SecretsCrypto::new(SecretString::from(
+            "0123456789abcdef0123456789abcdef".to_string(),
+        ))
+        .unwrap();
+        let store = InMemorySecretsStore::new(std::sync::Arc::new(crypto));
+        store
+            .create(
+                "default",
+                CreateSecretParams::new("test_rotated_api_key", "sk-before"),
+            )
+            .await
+            .unwrap();
+
+        refresh_injected_vars(&store, "default", &MAPPINGS).await;
+        assert_eq!(
+            helpers::optional_env("ENCLAGENT_TEST_ROTATED_API_KEY").unwrap(),
+            Some("sk-before".to_string())
+        );
+
+        store
+            .create(
+                "default",
+                CreateSecretParams::new("test_rotated_api_key", "sk-after"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            helpers::optional_env("ENCLAGENT_TEST_ROTATED_API_KEY").unwrap(),
+            Some("sk-before".to_string())
+        );
+        refresh_injected_vars(&store, "default", &MAPPINGS).await;
+        assert_eq!(
+            helpers::optional_env("ENCLAGENT_TEST_ROTATED_API_KEY").unwrap(),
+            Some("sk-after".to_string())
+        );
+
+        store
+            .delete("default", "test_rotated_api_key")
+            .await
+            .unwrap();
+        refresh_injected_vars(&store, "default", &MAPPINGS).await;
+        assert_eq!(
+            helpers::optional_env("ENCLAGENT_TEST_ROTATED_API_KEY").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn dual_mode_custody_requires_two_distinct_wallets() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_hl_policy_env();
+
+        let mut settings = Settings::default();
+        settings.wallet_vault_policy.custody_mode = "dual_mode".to_string();
+        settings.wallet_vault_policy.user_wallet_address =
+            Some("0x9431Cf5DA0CE60664661341db650763B08286B18".to_string());
+
+        let missing = WalletVaultPolicyConfig::resolve(&settings).unwrap_err();
+        assert!(
+            matches!(&missing, ConfigError::InvalidValue { key, message }
+                if key == "HYPERLIQUID_CUSTODY_MODE" && message.contains("requires both")),
+            "unexpected error: {missing:?}"
+        );
+
+        settings.wallet_vault_policy.operator_wallet_address =
+            Some("0x9431cf5da0ce60664661341db650763b08286b18".to_string());
+        let identical = WalletVaultPolicyConfig::resolve(&settings).unwrap_err();
+        assert!(
+            matches!(&identical, ConfigError::InvalidValue { message, .. }
+                if message.contains("distinct")),
+            "unexpected error: {identical:?}"
+        );
+
+        settings.wallet_vault_policy.operator_wallet_address =
+            Some("0x1111111111111111111111111111111111111111".to_string());
+        let wallet = WalletVaultPolicyConfig::resolve(&settings).expect("dual mode resolve");
+        assert_eq!(wallet.custody_mode, CustodyMode::DualMode);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[test]
+    fn explain_reports_env_over_conflicting_db_setting() {
+        use crate::db::libsql::LibSqlBackend;
+        use crate::db::{Database, SettingsStore};
+
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("config.toml");
+        std::fs::write(&toml_path, "").unwrap();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("AGENT_NAME", "env-agent");
+        }
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let resolved = runtime.block_on(async {
+            let backend = LibSqlBackend::new_memory().await.unwrap();
+            backend.run_migrations().await.unwrap();
+            backend
+                .set_setting("default", "agent.name", &serde_json::json!("db-agent"))
+                .await
+                .unwrap();
+            backend
+                .set_setting("default", "agent.max_parallel_jobs", &serde_json::json!(7))
+                .await
+                .unwrap();
+            Config::explain_with_toml_layers(&backend, "default", &[toml_path.as_path()]).await
+        });
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("AGENT_NAME");
+        }
+        let resolved = resolved.unwrap();
+
+        let find = |key: &str| {
+            resolved
+                .iter()
+                .find(|r| r.key == key)
+                .unwrap_or_else(|| panic!("missing {key}"))
+        };
+        let name = find("agent.name");
+        assert_eq!(name.value, "env-agent");
+        assert_eq!(name.source, ConfigSource::Env);
+        let jobs = find("agent.max_parallel_jobs");
+        assert_eq!(jobs.value, "7");
+        assert_eq!(jobs.source, ConfigSource::Db);
+        assert_eq!(find("heartbeat.enabled").source, ConfigSource::Default);
+    }
 }
@@ -0,0 +1,192 @@
+//! Runtime override for the Hyperliquid paper/live policy.
+//!
+//! `HyperliquidRuntimeConfig::paper_live_policy` is resolved once at startup.
+//! Operators can layer a [`PolicyOverride`] on top of it through the settings
+//! table without restarting, but only ever to make the policy stricter:
+//! `PaperOnly < PaperFirst < LiveAllowed`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{HyperliquidRuntimeConfig, PaperLivePolicy};
+use crate::db::SettingsStore;
+use crate::error::DatabaseError;
+
+/// An operator-set policy downgrade, persisted as its own audit record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyOverride {
+    pub effective_policy: PaperLivePolicy,
+    pub reason: String,
+    pub set_at: DateTime<Utc>,
+    pub set_by: String,
+}
+
+impl PolicyOverride {
+    /// Settings key holding the active override for a user.
+    pub const SETTINGS_KEY: &'static str = "hyperliquid.paper_live_policy_override";
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyOverrideError {
+    #[error(
+        "policy override cannot loosen '{}' to '{}'",
+        base.as_str(),
+        requested.as_str()
+    )]
+    Loosening {
+        base: PaperLivePolicy,
+        requested: PaperLivePolicy,
+    },
+
+    #[error("policy override reason must not be empty")]
+    MissingReason,
+
+    #[error("stored policy override is malformed: {0}")]
+    Malformed(String),
+
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+/// Resolve the policy in effect when `override_` is layered on `base`.
+///
+/// An override may keep or tighten the base policy; anything looser is
+/// rejected rather than silently clamped.
+pub fn apply_policy_override(
+    base: PaperLivePolicy,
+    override_: Option<&PolicyOverride>,
+) -> Result<PaperLivePolicy, PolicyOverrideError> {
+    match override_ {
+        None => Ok(base),
+        Some(o) if o.effective_policy > base => Err(PolicyOverrideError::Loosening {
+            base,
+            requested: o.effective_policy,
+        }),
+        Some(o) => Ok(o.effective_policy),
+    }
+}
+
+/// Read the stored override for `user_id`, if any.
+pub async fn load_policy_override(
+    store: &dyn SettingsStore,
+    user_id: &str,
+) -> Result<Option<PolicyOverride>, PolicyOverrideError> {
+    store
+        .get_setting(user_id, PolicyOverride::SETTINGS_KEY)
+        .await?
+        .map(|value| {
+            serde_json::from_value(value).map_err(|e| PolicyOverrideError::Malformed(e.to_string()))
+        })
+        .transpose()
+}
+
+/// Validate `override_` against `base` and persist it for `user_id`.
+pub async fn set_policy_override(
+    store: &dyn SettingsStore,
+    user_id: &str,
+    base: PaperLivePolicy,
+    override_: &PolicyOverride,
+) -> Result<PaperLivePolicy, PolicyOverrideError> {
+    if override_.reason.trim().is_empty() {
+        return Err(PolicyOverrideError::MissingReason);
+    }
+    let effective = apply_policy_override(base, Some(override_))?;
+    let value = serde_json::to_value(override_)
+        .map_err(|e| PolicyOverrideError::Malformed(e.to_string()))?;
+    store
+        .set_setting(user_id, PolicyOverride::SETTINGS_KEY, &value)
+        .await?;
+    tracing::info!(
+        user_id,
+        base = base.as_str(),
+        effective = effective.as_str(),
+        set_by = %override_.set_by,
+        reason = %override_.reason,
+        "paper/live policy override set"
+    );
+    Ok(effective)
+}
+
+impl HyperliquidRuntimeConfig {
+    /// Merge a stored override into this config.
+    ///
+    /// A stored override that would loosen the policy (e.g. the base was
+    /// tightened after it was set) is ignored and the base policy kept.
+    pub fn with_policy_override(mut self, override_: Option<&PolicyOverride>) -> Self {
+        match apply_policy_override(self.paper_live_policy, override_) {
+            Ok(policy) => self.paper_live_policy = policy,
+            Err(err) => tracing::warn!("ignoring stored policy override: {err}"),
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn override_to(policy: PaperLivePolicy) -> PolicyOverride {
+        PolicyOverride {
+            effective_policy: policy,
+            reason: "incident review".to_string(),
+            set_at: Utc::now(),
+            set_by: "operator@example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn tightening_override_applies() {
+        let tighten = override_to(PaperLivePolicy::PaperOnly);
+        assert_eq!(
+            apply_policy_override(PaperLivePolicy::LiveAllowed, Some(&tighten)).unwrap(),
+            PaperLivePolicy::PaperOnly
+        );
+        assert_eq!(
+            apply_policy_override(PaperLivePolicy::PaperFirst, None).unwrap(),
+            PaperLivePolicy::PaperFirst
+        );
+    }
+
+    #[test]
+    fn loosening_override_is_rejected() {
+        let loosen = override_to(PaperLivePolicy::LiveAllowed);
+        let err = apply_policy_override(PaperLivePolicy::PaperFirst, Some(&loosen)).unwrap_err();
+        assert!(matches!(
+            err,
+            PolicyOverrideError::Loosening {
+                base: PaperLivePolicy::PaperFirst,
+                requested: PaperLivePolicy::LiveAllowed,
+            }
+        ));
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn stored_override_round_trips_and_merges() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let base = PaperLivePolicy::LiveAllowed;
+
+        let loosen = override_to(PaperLivePolicy::PaperFirst);
+        let err = set_policy_override(db.as_ref(), "op", PaperLivePolicy::PaperOnly, &loosen)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PolicyOverrideError::Loosening { .. }));
+        assert!(
+            load_policy_override(db.as_ref(), "op")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let tighten = override_to(PaperLivePolicy::PaperOnly);
+        set_policy_override(db.as_ref(), "op", base, &tighten)
+            .await
+            .unwrap();
+        let stored = load_policy_override(db.as_ref(), "op").await.unwrap();
+        assert_eq!(stored.as_ref(), Some(&tighten));
+        assert_eq!(
+            apply_policy_override(base, stored.as_ref()).unwrap(),
+            PaperLivePolicy::PaperOnly
+        );
+    }
+}
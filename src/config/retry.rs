@@ -0,0 +1,185 @@
+//! Retry helper that applies the `HyperliquidRuntimeConfig` retry budget.
+//!
+//! `max_retries`, `retry_backoff_ms`, and `timeout_ms` are resolved once at
+//! startup and read through [`HttpPolicy`](crate::config::HttpPolicy);
+//! [`retry_with_backoff`] is the single place that runs the retry loop so
+//! callers don't hand-roll their own.
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::config::HyperliquidRuntimeConfig;
+
+/// Classifies an error as transient (worth another attempt) or terminal.
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+/// An attempt that exceeded `timeout_ms`.
+///
+/// Timeouts are always treated as retryable; the operation's error type
+/// converts from this so the final timeout can be surfaced to the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("attempt {attempt} timed out after {timeout_ms}ms")]
+pub struct AttemptTimedOut {
+    pub attempt: u32,
+    pub timeout_ms: u64,
+}
+
+/// Delay before the retry that follows failed attempt `attempt` (1-based).
+pub fn retry_delay(cfg: &HyperliquidRuntimeConfig, attempt: u32) -> Duration {
+    cfg.http_policy().retry_delay(attempt)
+}
+
+/// Run `op` until it succeeds, fails terminally, or the retry budget runs out.
+///
+/// Makes at most `max_retries + 1` attempts, each bounded by `timeout_ms`.
+/// After failed attempt `n` it sleeps `retry_backoff_ms * n` before retrying.
+/// Terminal errors (per [`Retryable`]) are returned immediately.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    cfg: &HyperliquidRuntimeConfig,
+    mut op: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Retryable + From<AttemptTimedOut>,
+{
+    let policy = cfg.http_policy();
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        let result = match tokio::time::timeout(policy.timeout, op()).await {
+            Ok(result) => result,
+            Err(_) => Err(E::from(AttemptTimedOut {
+                attempt,
+                timeout_ms: cfg.timeout_ms,
+            })),
+        };
+        match result {
+            Ok(value) => return Ok(value),
+            Err(err) if err.is_retryable() && attempt <= policy.max_retries => {
+                tracing::debug!(
+                    attempt,
+                    max_retries = policy.max_retries,
+                    "retrying after error"
+                );
+                tokio::time::sleep(policy.retry_delay(attempt)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Instant;
+
+    use super::*;
+    use crate::config::{HyperliquidNetwork, PaperLivePolicy};
+
+    #[derive(Debug, PartialEq)]
+    enum MockError {
+        Transient,
+        Terminal,
+        TimedOut(AttemptTimedOut),
+    }
+
+    impl Retryable for MockError {
+        fn is_retryable(&self) -> bool {
+            !matches!(self, MockError::Terminal)
+        }
+    }
+
+    impl From<AttemptTimedOut> for MockError {
+        fn from(err: AttemptTimedOut) -> Self {
+            MockError::TimedOut(err)
+        }
+    }
+
+    fn runtime_config(max_retries: u32, retry_backoff_ms: u64) -> HyperliquidRuntimeConfig {
+        HyperliquidRuntimeConfig {
+            network: HyperliquidNetwork::Testnet,
+            api_base_url: HyperliquidNetwork::Testnet
+                .default_api_base_url()
+                .to_string(),
+            ws_url: HyperliquidNetwork::Testnet.default_ws_url().to_string(),
+            timeout_ms: 1_000,
+            max_retries,
+            retry_backoff_ms,
+            paper_live_policy: PaperLivePolicy::PaperFirst,
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures_with_increasing_backoff() {
+        let cfg = runtime_config(3, 20);
+        let attempts = AtomicU32::new(0);
+        let started = Mutex::new(Vec::new());
+
+        let result: Result<&str, MockError> = retry_with_backoff(&cfg, || {
+            started.lock().unwrap().push(Instant::now());
+            let n = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if n <= 2 {
+                    Err(MockError::Transient)
+                } else {
+                    Ok("filled")
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok("filled"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        assert!(retry_delay(&cfg, 1) < retry_delay(&cfg, 2));
+        let started = started.lock().unwrap();
+        let first_gap = started[1] - started[0];
+        let second_gap = started[2] - started[1];
+        assert!(first_gap >= retry_delay(&cfg, 1), "{first_gap:?}");
+        assert!(second_gap >= retry_delay(&cfg, 2), "{second_gap:?}");
+    }
+
+    #[tokio::test]
+    async fn terminal_error_stops_immediately() {
+        let cfg = runtime_config(3, 1);
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), MockError> = retry_with_backoff(&cfg, || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(MockError::Terminal) }
+        })
+        .await;
+
+        assert_eq!(result, Err(MockError::Terminal));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_returns_last_timeout() {
+        let mut cfg = runtime_config(1, 1);
+        cfg.timeout_ms = 5;
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), MockError> = retry_with_backoff(&cfg, || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            }
+        })
+        .await;
+
+        assert_eq!(
+            result,
+            Err(MockError::TimedOut(AttemptTimedOut {
+                attempt: 2,
+                timeout_ms: 5,
+            }))
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+}
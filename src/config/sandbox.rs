@@ -20,6 +20,8 @@ pub struct SandboxModeConfig {
     pub auto_pull_image: bool,
     /// Additional domains to allow through the network proxy.
     pub extra_allowed_domains: Vec<String>,
+    /// Plan commands without executing them (for CI).
+    pub dry_run: bool,
 }
 
 impl Default for SandboxModeConfig {
@@ -33,6 +35,7 @@ impl Default for SandboxModeConfig {
             image: "ghcr.io/nearai/sandbox:latest".to_string(),
             auto_pull_image: true,
             extra_allowed_domains: Vec::new(),
+            dry_run: false,
         }
     }
 }
@@ -67,6 +70,14 @@ impl SandboxModeConfig {
                 })?
                 .unwrap_or(true),
             extra_allowed_domains: extra_domains,
+            dry_run: optional_env("SANDBOX_DRY_RUN")?
+                .map(|s| s.parse())
+                .transpose()
+                .map_err(|e| ConfigError::InvalidValue {
+                    key: "SANDBOX_DRY_RUN".to_string(),
+                    message: format!("must be 'true' or 'false': {e}"),
+                })?
+                .unwrap_or(false),
         })
     }
 
@@ -90,6 +101,7 @@ impl SandboxModeConfig {
             image: self.image.clone(),
             auto_pull_image: self.auto_pull_image,
             proxy_port: 0, // Auto-assign
+            dry_run: self.dry_run,
         }
     }
 }
@@ -259,3 +271,35 @@ fn parse_oauth_access_token(json: &str) -> Option<String> {
         .as_str()
         .map(String::from)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::helpers::ENV_MUTEX;
+
+    #[test]
+    fn sandbox_dry_run_resolves_from_env_and_defaults_off() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("SANDBOX_DRY_RUN");
+        }
+        let cfg = SandboxModeConfig::resolve().expect("sandbox resolve");
+        assert!(!cfg.dry_run);
+        assert!(!cfg.to_sandbox_config().dry_run);
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("SANDBOX_DRY_RUN", "true");
+        }
+        let cfg = SandboxModeConfig::resolve().expect("sandbox resolve");
+        assert!(cfg.dry_run);
+        assert!(cfg.to_sandbox_config().dry_run);
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("SANDBOX_DRY_RUN");
+        }
+    }
+}
@@ -4,6 +4,10 @@ use std::time::Duration;
 use crate::config::helpers::{optional_env, parse_optional_env};
 use crate::error::ConfigError;
 
+const DEFAULT_MAX_FUEL: u64 = 100_000_000;
+const DEFAULT_MAX_MEMORY_BYTES: u64 = 64 * 1024 * 1024;
+const DEFAULT_MAX_EXECUTION_MS: u64 = 300_000;
+
 /// WASM sandbox configuration.
 #[derive(Debug, Clone)]
 pub struct WasmConfig {
@@ -17,6 +21,12 @@ pub struct WasmConfig {
     pub default_timeout_secs: u64,
     /// Default fuel limit for CPU metering (default: 10M).
     pub default_fuel_limit: u64,
+    /// Ceiling on fuel any tool may be granted (default: 100M).
+    pub max_fuel: u64,
+    /// Ceiling on memory any tool may be granted, in bytes (default: 64 MB).
+    pub max_memory_bytes: u64,
+    /// Ceiling on wall-clock execution time, in milliseconds (default: 300000).
+    pub max_execution_ms: u64,
     /// Whether to cache compiled modules.
     pub cache_compiled: bool,
     /// Directory for compiled module cache.
@@ -31,6 +41,9 @@ impl Default for WasmConfig {
             default_memory_limit: 10 * 1024 * 1024, // 10 MB
             default_timeout_secs: 60,
             default_fuel_limit: 10_000_000,
+            max_fuel: DEFAULT_MAX_FUEL,
+            max_memory_bytes: DEFAULT_MAX_MEMORY_BYTES,
+            max_execution_ms: DEFAULT_MAX_EXECUTION_MS,
             cache_compiled: true,
             cache_dir: None,
         }
@@ -47,7 +60,7 @@ fn default_tools_dir() -> PathBuf {
 
 impl WasmConfig {
     pub(crate) fn resolve() -> Result<Self, ConfigError> {
-        Ok(Self {
+        let config = Self {
             enabled: optional_env("WASM_ENABLED")?
                 .map(|s| s.parse())
                 .transpose()
@@ -65,6 +78,15 @@ impl WasmConfig {
             )?,
             default_timeout_secs: parse_optional_env("WASM_DEFAULT_TIMEOUT_SECS", 60)?,
             default_fuel_limit: parse_optional_env("WASM_DEFAULT_FUEL_LIMIT", 10_000_000)?,
+            max_fuel: parse_optional_env("WASM_MAX_FUEL", DEFAULT_MAX_FUEL)?,
+            max_memory_bytes: parse_optional_env(
+                "WASM_MAX_MEMORY_BYTES",
+                DEFAULT_MAX_MEMORY_BYTES,
+            )?,
+            max_execution_ms: parse_optional_env(
+                "WASM_MAX_EXECUTION_MS",
+                DEFAULT_MAX_EXECUTION_MS,
+            )?,
             cache_compiled: optional_env("WASM_CACHE_COMPILED")?
                 .map(|s| s.parse())
                 .transpose()
@@ -74,7 +96,53 @@ impl WasmConfig {
                 })?
                 .unwrap_or(true),
             cache_dir: optional_env("WASM_CACHE_DIR")?.map(PathBuf::from),
-        })
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that every ceiling is positive and the defaults fit under them.
+    pub(crate) fn validate(&self) -> Result<(), ConfigError> {
+        for (key, max) in [
+            ("WASM_MAX_FUEL", self.max_fuel),
+            ("WASM_MAX_MEMORY_BYTES", self.max_memory_bytes),
+            ("WASM_MAX_EXECUTION_MS", self.max_execution_ms),
+        ] {
+            if max == 0 {
+                return Err(ConfigError::InvalidValue {
+                    key: key.to_string(),
+                    message: "must be greater than 0".to_string(),
+                });
+            }
+        }
+        for (key, value, max_key, max) in [
+            (
+                "WASM_DEFAULT_FUEL_LIMIT",
+                self.default_fuel_limit,
+                "WASM_MAX_FUEL",
+                self.max_fuel,
+            ),
+            (
+                "WASM_DEFAULT_MEMORY_LIMIT",
+                self.default_memory_limit,
+                "WASM_MAX_MEMORY_BYTES",
+                self.max_memory_bytes,
+            ),
+            (
+                "WASM_DEFAULT_TIMEOUT_SECS",
+                self.default_timeout_secs.saturating_mul(1000),
+                "WASM_MAX_EXECUTION_MS",
+                self.max_execution_ms,
+            ),
+        ] {
+            if value > max {
+                return Err(ConfigError::InvalidValue {
+                    key: key.to_string(),
+                    message: format!("must not exceed {max_key} ({max})"),
+                });
+            }
+        }
+        Ok(())
     }
 
     /// Convert to WasmRuntimeConfig.
@@ -87,6 +155,11 @@ impl WasmConfig {
                 fuel: self.default_fuel_limit,
                 timeout: Duration::from_secs(self.default_timeout_secs),
             },
+            max_limits: ResourceLimits {
+                memory_bytes: self.max_memory_bytes,
+                fuel: self.max_fuel,
+                timeout: Duration::from_millis(self.max_execution_ms),
+            },
             fuel_config: FuelConfig {
                 initial_fuel: self.default_fuel_limit,
                 enabled: true,
@@ -97,3 +170,95 @@ impl WasmConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::helpers::ENV_MUTEX;
+
+    const LIMIT_VARS: [&str; 6] = [
+        "WASM_MAX_FUEL",
+        "WASM_MAX_MEMORY_BYTES",
+        "WASM_MAX_EXECUTION_MS",
+        "WASM_DEFAULT_FUEL_LIMIT",
+        "WASM_DEFAULT_MEMORY_LIMIT",
+        "WASM_DEFAULT_TIMEOUT_SECS",
+    ];
+
+    fn clear_limit_env() {
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            for key in LIMIT_VARS {
+                std::env::remove_var(key);
+            }
+        }
+    }
+
+    #[test]
+    fn limits_resolve_from_env_and_reach_the_runtime() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_limit_env();
+
+        let defaults = WasmConfig::resolve().expect("default limits");
+        assert_eq!(defaults.max_fuel, DEFAULT_MAX_FUEL);
+        assert_eq!(defaults.max_memory_bytes, DEFAULT_MAX_MEMORY_BYTES);
+        assert_eq!(defaults.max_execution_ms, DEFAULT_MAX_EXECUTION_MS);
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("WASM_MAX_FUEL", "20000000");
+            std::env::set_var("WASM_MAX_MEMORY_BYTES", "16777216");
+            std::env::set_var("WASM_MAX_EXECUTION_MS", "90000");
+        }
+        let config = WasmConfig::resolve().expect("configured limits");
+        clear_limit_env();
+
+        assert_eq!(config.max_fuel, 20_000_000);
+        assert_eq!(config.max_memory_bytes, 16 * 1024 * 1024);
+        assert_eq!(config.max_execution_ms, 90_000);
+        let runtime = config.to_runtime_config();
+        assert_eq!(runtime.max_limits.fuel, 20_000_000);
+        assert_eq!(runtime.max_limits.memory_bytes, 16 * 1024 * 1024);
+        assert_eq!(runtime.max_limits.timeout, Duration::from_millis(90_000));
+    }
+
+    #[test]
+    fn zero_limit_is_rejected() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_limit_env();
+
+        // SAFETY: Guarded by ENV_MUTEX in tests.
+        unsafe {
+            std::env::set_var("WASM_MAX_EXECUTION_MS", "0");
+        }
+        let err = WasmConfig::resolve().expect_err("zero limit");
+        clear_limit_env();
+
+        assert!(
+            matches!(&err, ConfigError::InvalidValue { key, .. } if key == "WASM_MAX_EXECUTION_MS")
+        );
+    }
+
+    #[test]
+    fn defaults_over_the_ceiling_are_rejected() {
+        let over_fuel = WasmConfig {
+            default_fuel_limit: 200_000_000,
+            ..WasmConfig::default()
+        };
+        assert!(matches!(
+            over_fuel.validate(),
+            Err(ConfigError::InvalidValue { key, .. }) if key == "WASM_DEFAULT_FUEL_LIMIT"
+        ));
+
+        let over_timeout = WasmConfig {
+            max_execution_ms: 30_000,
+            ..WasmConfig::default()
+        };
+        assert!(matches!(
+            over_timeout.validate(),
+            Err(ConfigError::InvalidValue { key, .. }) if key == "WASM_DEFAULT_TIMEOUT_SECS"
+        ));
+
+        assert!(WasmConfig::default().validate().is_ok());
+    }
+}
@@ -52,6 +52,26 @@ impl JobState {
         )
     }
 
+    /// Whether the job state graph allows moving `from` -> `to`.
+    pub fn is_valid_transition(from: JobState, to: JobState) -> bool {
+        from.can_transition_to(to)
+    }
+
+    /// Parse the snake_case name written by `Display`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "in_progress" => Some(Self::InProgress),
+            "completed" => Some(Self::Completed),
+            "submitted" => Some(Self::Submitted),
+            "accepted" => Some(Self::Accepted),
+            "failed" => Some(Self::Failed),
+            "stuck" => Some(Self::Stuck),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+
     /// Check if this is a terminal state.
     pub fn is_terminal(&self) -> bool {
         matches!(self, Self::Accepted | Self::Failed | Self::Cancelled)
@@ -301,6 +321,39 @@ mod tests {
         assert!(!JobState::Accepted.can_transition_to(JobState::InProgress));
     }
 
+    #[test]
+    fn test_is_valid_transition_and_parse() {
+        use JobState::*;
+
+        for (from, to) in [
+            (Pending, InProgress),
+            (InProgress, Completed),
+            (InProgress, Failed),
+            (InProgress, Stuck),
+            (Stuck, InProgress),
+            (Completed, Submitted),
+            (Submitted, Accepted),
+        ] {
+            assert!(JobState::is_valid_transition(from, to), "{from} -> {to}");
+        }
+        for (from, to) in [
+            (Completed, InProgress),
+            (Pending, Completed),
+            (Failed, InProgress),
+            (Cancelled, Pending),
+            (Accepted, Submitted),
+        ] {
+            assert!(!JobState::is_valid_transition(from, to), "{from} -> {to}");
+        }
+
+        for state in [
+            Pending, InProgress, Completed, Submitted, Accepted, Failed, Stuck, Cancelled,
+        ] {
+            assert_eq!(JobState::parse(&state.to_string()), Some(state));
+        }
+        assert_eq!(JobState::parse("in-progress"), None);
+    }
+
     #[test]
     fn test_terminal_states() {
         assert!(JobState::Accepted.is_terminal());
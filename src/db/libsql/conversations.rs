@@ -8,7 +8,57 @@ use uuid::Uuid;
 use super::{LibSqlBackend, fmt_ts, get_i64, get_json, get_opt_text, get_text, get_ts, opt_text};
 use crate::db::ConversationStore;
 use crate::error::DatabaseError;
-use crate::history::{ConversationMessage, ConversationSummary};
+use crate::history::{ConversationMessage, ConversationRollingSummary, ConversationSummary};
+
+/// Empty conversations idle since before `?1`, optionally for user `?2`, that
+/// no job or LLM call record still points at.
+const PRUNABLE_CONVERSATIONS: &str = r#"
+    SELECT c.id FROM conversations c
+    WHERE julianday(c.last_activity) < julianday(?1)
+      AND (?2 IS NULL OR c.user_id = ?2)
+      AND NOT EXISTS (SELECT 1 FROM conversation_messages m WHERE m.conversation_id = c.id)
+      AND NOT EXISTS (SELECT 1 FROM agent_jobs j WHERE j.conversation_id = c.id)
+      AND NOT EXISTS (SELECT 1 FROM llm_calls l WHERE l.conversation_id = c.id)
+"#;
+
+impl LibSqlBackend {
+    async fn prune_conversations_tx(
+        conn: &libsql::Connection,
+        cutoff: &str,
+        user_id: Option<&str>,
+    ) -> Result<u64, DatabaseError> {
+        // `created_at` holds both SQLite `datetime()` defaults and RFC 3339
+        // values, which do not sort together as text; compare as julian days.
+        let messages = conn
+            .execute(
+                r#"
+                DELETE FROM conversation_messages
+                WHERE julianday(created_at) < julianday(?1)
+                  AND (?2 IS NULL OR conversation_id IN
+                      (SELECT id FROM conversations WHERE user_id = ?2))
+                "#,
+                params![cutoff, opt_text(user_id)],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        conn.execute(
+            &format!(
+                "DELETE FROM conversation_summaries WHERE conversation_id IN ({PRUNABLE_CONVERSATIONS})"
+            ),
+            params![cutoff, opt_text(user_id)],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let conversations = conn
+            .execute(
+                &format!("DELETE FROM conversations WHERE id IN ({PRUNABLE_CONVERSATIONS})"),
+                params![cutoff, opt_text(user_id)],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(messages + conversations)
+    }
+}
 
 #[async_trait]
 impl ConversationStore for LibSqlBackend {
@@ -87,7 +137,7 @@ impl ConversationStore for LibSqlBackend {
         channel: &str,
         limit: i64,
     ) -> Result<Vec<ConversationSummary>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 r#"
@@ -206,7 +256,7 @@ impl ConversationStore for LibSqlBackend {
         before: Option<DateTime<Utc>>,
         limit: i64,
     ) -> Result<(Vec<ConversationMessage>, bool), DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let fetch_limit = limit + 1;
         let cid = conversation_id.to_string();
 
@@ -279,7 +329,7 @@ impl ConversationStore for LibSqlBackend {
         &self,
         id: Uuid,
     ) -> Result<Option<serde_json::Value>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 "SELECT metadata FROM conversations WHERE id = ?1",
@@ -302,7 +352,7 @@ impl ConversationStore for LibSqlBackend {
         &self,
         conversation_id: Uuid,
     ) -> Result<Vec<ConversationMessage>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 r#"
@@ -351,4 +401,95 @@ impl ConversationStore for LibSqlBackend {
             .map_err(|e| DatabaseError::Query(e.to_string()))?;
         Ok(found.is_some())
     }
+
+    async fn upsert_summary(
+        &self,
+        conversation_id: Uuid,
+        summary: &str,
+        token_count: i64,
+        covered_through_message_id: Uuid,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            r#"
+            INSERT INTO conversation_summaries
+                (conversation_id, summary, token_count, covered_through_message_id, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (conversation_id) DO UPDATE SET
+                summary = excluded.summary,
+                token_count = excluded.token_count,
+                covered_through_message_id = excluded.covered_through_message_id,
+                updated_at = excluded.updated_at
+            "#,
+            params![
+                conversation_id.to_string(),
+                summary,
+                token_count,
+                covered_through_message_id.to_string(),
+                fmt_ts(&Utc::now())
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_summary(
+        &self,
+        conversation_id: Uuid,
+    ) -> Result<Option<ConversationRollingSummary>, DatabaseError> {
+        let conn = self.connect_read().await?;
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT summary, token_count, covered_through_message_id, updated_at
+                FROM conversation_summaries
+                WHERE conversation_id = ?1
+                "#,
+                params![conversation_id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        match rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            Some(row) => Ok(Some(ConversationRollingSummary {
+                conversation_id,
+                summary: get_text(&row, 0),
+                token_count: get_i64(&row, 1),
+                covered_through_message_id: get_text(&row, 2).parse().map_err(|e| {
+                    DatabaseError::Serialization(format!("invalid covered_through_message_id: {e}"))
+                })?,
+                updated_at: get_ts(&row, 3),
+            })),
+            None => Ok(None),
+        }
+    }
+
+    async fn prune_conversations_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+        user_id: Option<&str>,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute("BEGIN IMMEDIATE", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let removed = match Self::prune_conversations_tx(&conn, &fmt_ts(&cutoff), user_id).await {
+            Ok(removed) => removed,
+            Err(e) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                return Err(e);
+            }
+        };
+
+        conn.execute("COMMIT", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(removed)
+    }
 }
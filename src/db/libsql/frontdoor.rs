@@ -0,0 +1,127 @@
+//! Frontdoor session FrontdoorSessionStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use libsql::params;
+
+use super::{LibSqlBackend, fmt_ts, get_i64, get_json, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{FrontdoorSessionRecord, FrontdoorSessionStore};
+use crate::error::DatabaseError;
+
+#[async_trait]
+impl FrontdoorSessionStore for LibSqlBackend {
+    async fn upsert_frontdoor_session(
+        &self,
+        record: &FrontdoorSessionRecord,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            r#"
+                INSERT INTO frontdoor_sessions (
+                    id, user_id, wallet_address, version, status, detail,
+                    privy_user_id, profile_name, instance_url, verify_url,
+                    eigen_app_id, error, payload, created_at, updated_at, expires_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+                ON CONFLICT (id) DO UPDATE SET
+                    user_id = excluded.user_id,
+                    wallet_address = excluded.wallet_address,
+                    version = excluded.version,
+                    status = excluded.status,
+                    detail = excluded.detail,
+                    privy_user_id = excluded.privy_user_id,
+                    profile_name = excluded.profile_name,
+                    instance_url = excluded.instance_url,
+                    verify_url = excluded.verify_url,
+                    eigen_app_id = excluded.eigen_app_id,
+                    error = excluded.error,
+                    payload = excluded.payload,
+                    updated_at = excluded.updated_at,
+                    expires_at = excluded.expires_at
+                "#,
+            params![
+                record.session_id.to_string(),
+                record.user_id.as_str(),
+                record.wallet_address.as_str(),
+                record.version,
+                record.status.as_str(),
+                record.detail.as_str(),
+                opt_text(record.privy_user_id.as_deref()),
+                opt_text(record.profile_name.as_deref()),
+                opt_text(record.instance_url.as_deref()),
+                opt_text(record.verify_url.as_deref()),
+                opt_text(record.eigen_app_id.as_deref()),
+                opt_text(record.error.as_deref()),
+                record.payload.to_string(),
+                fmt_ts(&record.created_at),
+                fmt_ts(&record.updated_at),
+                fmt_ts(&record.expires_at),
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list_frontdoor_sessions(&self) -> Result<Vec<FrontdoorSessionRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT id, user_id, wallet_address, version, status, detail,
+                       privy_user_id, profile_name, instance_url, verify_url,
+                       eigen_app_id, error, payload, created_at, updated_at, expires_at
+                FROM frontdoor_sessions
+                "#,
+                (),
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let mut records = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            let raw_id = get_text(&row, 0);
+            let Ok(session_id) = raw_id.parse() else {
+                tracing::warn!(session_id = %raw_id, "Skipping frontdoor session with invalid id");
+                continue;
+            };
+            records.push(FrontdoorSessionRecord {
+                session_id,
+                user_id: get_text(&row, 1),
+                wallet_address: get_text(&row, 2),
+                version: get_i64(&row, 3),
+                status: get_text(&row, 4),
+                detail: get_text(&row, 5),
+                privy_user_id: get_opt_text(&row, 6),
+                profile_name: get_opt_text(&row, 7),
+                instance_url: get_opt_text(&row, 8),
+                verify_url: get_opt_text(&row, 9),
+                eigen_app_id: get_opt_text(&row, 10),
+                error: get_opt_text(&row, 11),
+                payload: get_json(&row, 12),
+                created_at: get_ts(&row, 13),
+                updated_at: get_ts(&row, 14),
+                expires_at: get_ts(&row, 15),
+            });
+        }
+        Ok(records)
+    }
+
+    async fn delete_frontdoor_sessions_expired_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        let count = conn
+            .execute(
+                "DELETE FROM frontdoor_sessions WHERE expires_at < ?1",
+                params![fmt_ts(&cutoff)],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(count)
+    }
+}
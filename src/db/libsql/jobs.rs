@@ -90,7 +90,7 @@ impl JobStore for LibSqlBackend {
         {
             Some(row) => {
                 let status_str = get_text(&row, 5);
-                let state = parse_job_state(&status_str);
+                let state = parse_job_state(&status_str)?;
                 let estimated_time_secs: Option<i64> = row.get::<i64>(11).ok();
 
                 Ok(Some(JobContext {
@@ -139,6 +139,57 @@ impl JobStore for LibSqlBackend {
         Ok(())
     }
 
+    async fn update_job_state_checked(
+        &self,
+        id: Uuid,
+        status: JobState,
+        failure_reason: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT status FROM agent_jobs WHERE id = ?1",
+                params![id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        else {
+            return Err(DatabaseError::NotFound {
+                entity: "job".to_string(),
+                id: id.to_string(),
+            });
+        };
+        let current = parse_job_state(&get_text(&row, 0))?;
+        if !JobState::is_valid_transition(current, status) {
+            return Err(DatabaseError::Constraint(format!(
+                "job {id} cannot transition from {current} to {status}"
+            )));
+        }
+
+        let updated = conn
+            .execute(
+                "UPDATE agent_jobs SET status = ?2, failure_reason = ?3 WHERE id = ?1 AND status = ?4",
+                params![
+                    id.to_string(),
+                    status.to_string(),
+                    opt_text(failure_reason),
+                    current.to_string()
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        if updated == 0 {
+            return Err(DatabaseError::Constraint(format!(
+                "job {id} changed state concurrently; expected {current}"
+            )));
+        }
+        Ok(())
+    }
+
     async fn mark_job_stuck(&self, id: Uuid) -> Result<(), DatabaseError> {
         let conn = self.connect().await?;
         let now = fmt_ts(&Utc::now());
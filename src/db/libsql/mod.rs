@@ -7,6 +7,7 @@
 //! - In-memory (for testing)
 
 mod conversations;
+mod frontdoor;
 mod jobs;
 mod routines;
 mod sandbox;
@@ -14,8 +15,11 @@ mod settings;
 mod tool_failures;
 mod workspace;
 
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -29,8 +33,13 @@ use crate::agent::{
         NotifyConfig, Routine, RoutineAction, RoutineGuardrails, RoutineRun, RunStatus, Trigger,
     },
 };
+use crate::config::{IntentAuditQuota, IntentAuditQuotaPolicy};
 use crate::context::JobState;
-use crate::db::{Database, IntentAuditStore};
+use crate::db::{
+    ChainVerificationReport, Database, ExportFormat, IntentAuditChainHead, IntentAuditCheckpoint,
+    IntentAuditPage, IntentAuditStore, encode_intent_audit_cursor, link_intent_audit_record,
+    verify_intent_audit_chain, write_intent_audit_export_header, write_intent_audit_export_row,
+};
 use crate::error::DatabaseError;
 use crate::workspace::MemoryDocument;
 
@@ -51,16 +60,203 @@ pub(crate) const ROUTINE_RUN_COLUMNS: &str = "\
     id, routine_id, trigger_type, trigger_detail, started_at, \
     status, completed_at, result_summary, tokens_used, job_id, created_at";
 
+/// Default cap on connections `LibSqlBackend` has checked out at once; the
+/// same number of idle connections is kept for reuse.
+pub const DEFAULT_POOL_MAX_SIZE: usize = 8;
+
+/// How long `connect()` waits for a free connection before giving up.
+pub const POOL_CHECKOUT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How often a dedicated read replica pulls new frames from Turso.
+pub const READ_REPLICA_SYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Retries for the initial replica sync done at startup.
+pub const STARTUP_SYNC_MAX_RETRIES: u32 = 3;
+
+/// Base backoff between startup sync retries (multiplied by the attempt number).
+pub const STARTUP_SYNC_BACKOFF: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Outcome of one embedded-replica sync with Turso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    /// WAL frames pulled from the remote during this sync.
+    pub frames_synced: usize,
+    /// Replication index (frame number) the replica is at after syncing.
+    pub replication_index: Option<u64>,
+}
+
+/// Connections handed out by `LibSqlBackend::connect`: at most `max_size`
+/// checked out at once, and up to `max_size` idle ones kept for reuse.
+struct ConnectionPool {
+    idle: std::sync::Mutex<Vec<Connection>>,
+    max_size: usize,
+    permits: Arc<tokio::sync::Semaphore>,
+    created: AtomicUsize,
+    checked_out: AtomicUsize,
+    peak_checked_out: AtomicUsize,
+}
+
+impl ConnectionPool {
+    fn new(max_size: usize) -> Arc<Self> {
+        let max_size = max_size.max(1);
+        Arc::new(Self {
+            idle: std::sync::Mutex::new(Vec::new()),
+            max_size,
+            permits: Arc::new(tokio::sync::Semaphore::new(max_size)),
+            created: AtomicUsize::new(0),
+            checked_out: AtomicUsize::new(0),
+            peak_checked_out: AtomicUsize::new(0),
+        })
+    }
+
+    /// Wait for a checkout slot, failing after [`POOL_CHECKOUT_TIMEOUT`].
+    async fn acquire(&self) -> Result<tokio::sync::OwnedSemaphorePermit, DatabaseError> {
+        let permit = tokio::time::timeout(
+            POOL_CHECKOUT_TIMEOUT,
+            Arc::clone(&self.permits).acquire_owned(),
+        )
+        .await
+        .map_err(|_| {
+            DatabaseError::Pool(format!(
+                "timed out waiting for one of {} libSQL connections",
+                self.max_size
+            ))
+        })?
+        .map_err(|e| DatabaseError::Pool(format!("connection pool closed: {}", e)))?;
+        let now = self.checked_out.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_checked_out.fetch_max(now, Ordering::SeqCst);
+        Ok(permit)
+    }
+
+    fn checkout(&self) -> Option<Connection> {
+        self.idle.lock().ok()?.pop()
+    }
+
+    fn release(&self, conn: Connection) {
+        if let Ok(mut idle) = self.idle.lock()
+            && idle.len() < self.max_size
+        {
+            idle.push(conn);
+        }
+    }
+}
+
+/// A connection checked out of the `LibSqlBackend` pool.
+///
+/// Derefs to `libsql::Connection` and goes back to the pool on drop, freeing
+/// its checkout slot. A connection dropped mid-transaction is discarded
+/// rather than reused.
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    pool: Arc<ConnectionPool>,
+    _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("pooled connection used after release")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+            .as_mut()
+            .expect("pooled connection used after release")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take()
+            && conn.is_autocommit()
+        {
+            self.pool.release(conn);
+        }
+        self.pool.checked_out.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// libSQL/Turso database backend.
 ///
 /// Stores the `Database` handle in an `Arc` so that the same underlying
 /// database can be shared with stores (SecretsStore, WasmToolStore) that
-/// create their own connections per-operation.
+/// create their own connections per-operation. Connections handed out by
+/// `connect()` come from a small pool of reusable connections.
+///
+/// An optional read replica can be attached with `with_read_replica()`;
+/// list/get queries then go through `connect_read()` so heavy read traffic
+/// does not contend with writes on the primary handle.
 pub struct LibSqlBackend {
     db: Arc<LibSqlDatabase>,
+    pool: Arc<ConnectionPool>,
+    read_db: Option<Arc<LibSqlDatabase>>,
+    read_pool: Arc<ConnectionPool>,
+    last_sync_at: std::sync::Mutex<Option<DateTime<Utc>>>,
+    intent_audit_quota: Option<IntentAuditQuota>,
 }
 
 impl LibSqlBackend {
+    fn from_database(db: LibSqlDatabase) -> Self {
+        Self {
+            db: Arc::new(db),
+            pool: ConnectionPool::new(DEFAULT_POOL_MAX_SIZE),
+            read_db: None,
+            read_pool: ConnectionPool::new(DEFAULT_POOL_MAX_SIZE),
+            last_sync_at: std::sync::Mutex::new(None),
+            intent_audit_quota: None,
+        }
+    }
+
+    /// Cap how many intent audit records each user may keep.
+    pub fn with_intent_audit_quota(mut self, quota: Option<IntentAuditQuota>) -> Self {
+        self.intent_audit_quota = quota;
+        self
+    }
+
+    /// Set how many connections may be checked out at once (minimum 1).
+    ///
+    /// When every slot is taken, `connect()` waits up to
+    /// [`POOL_CHECKOUT_TIMEOUT`] for one to be released and then fails, so
+    /// code must not hold a connection while checking out another.
+    pub fn with_max_pool_size(mut self, max_size: usize) -> Self {
+        self.pool = ConnectionPool::new(max_size);
+        self.read_pool = ConnectionPool::new(max_size);
+        self
+    }
+
+    /// Attach a dedicated Turso embedded replica used only for reads.
+    ///
+    /// The replica lives in its own file at `path`, separate from the
+    /// primary's, and syncs every [`READ_REPLICA_SYNC_INTERVAL`]. Writes keep
+    /// going through `connect()`; `connect_read()` hands out replica
+    /// connections.
+    pub async fn with_read_replica(
+        mut self,
+        path: &Path,
+        url: &str,
+        auth_token: &str,
+    ) -> Result<Self, DatabaseError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                DatabaseError::Pool(format!("Failed to create database directory: {}", e))
+            })?;
+        }
+
+        let db = libsql::Builder::new_remote_replica(path, url.to_string(), auth_token.to_string())
+            .sync_interval(READ_REPLICA_SYNC_INTERVAL)
+            .build()
+            .await
+            .map_err(|e| DatabaseError::Pool(format!("Failed to open read replica: {}", e)))?;
+
+        self.read_db = Some(Arc::new(db));
+        Ok(self)
+    }
+
     /// Create a new local embedded database.
     pub async fn new_local(path: &Path) -> Result<Self, DatabaseError> {
         // Ensure parent directory exists
@@ -75,7 +271,7 @@ impl LibSqlBackend {
             .await
             .map_err(|e| DatabaseError::Pool(format!("Failed to open libSQL database: {}", e)))?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self::from_database(db))
     }
 
     /// Create a new in-memory database (for testing).
@@ -87,7 +283,7 @@ impl LibSqlBackend {
                 DatabaseError::Pool(format!("Failed to create in-memory database: {}", e))
             })?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self::from_database(db))
     }
 
     /// Create with Turso cloud sync (embedded replica).
@@ -107,7 +303,41 @@ impl LibSqlBackend {
             .await
             .map_err(|e| DatabaseError::Pool(format!("Failed to open remote replica: {}", e)))?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self::from_database(db))
+    }
+
+    /// Pull new frames from Turso into the embedded replica.
+    ///
+    /// Only replicas built with `new_remote_replica()` can sync; local and
+    /// in-memory databases return an error.
+    pub async fn sync(&self) -> Result<SyncReport, DatabaseError> {
+        let replicated =
+            self.db.sync().await.map_err(|e| {
+                DatabaseError::Pool(format!("Failed to sync remote replica: {}", e))
+            })?;
+        if let Ok(mut last_sync_at) = self.last_sync_at.lock() {
+            *last_sync_at = Some(Utc::now());
+        }
+        Ok(SyncReport {
+            frames_synced: replicated.frames_synced(),
+            replication_index: replicated.frame_no(),
+        })
+    }
+
+    /// `sync()`, retried up to `max_retries` times on failure.
+    ///
+    /// After failed attempt `n` it sleeps `backoff * n` before retrying.
+    pub async fn sync_with_retry(
+        &self,
+        max_retries: u32,
+        backoff: std::time::Duration,
+    ) -> Result<SyncReport, DatabaseError> {
+        retry_sync(max_retries, backoff, || self.sync()).await
+    }
+
+    /// When the last successful `sync()` finished, if any.
+    pub fn last_sync_at(&self) -> Option<DateTime<Utc>> {
+        self.last_sync_at.lock().ok().and_then(|last| *last)
     }
 
     /// Get a shared reference to the underlying database handle.
@@ -118,81 +348,188 @@ impl LibSqlBackend {
         Arc::clone(&self.db)
     }
 
-    /// Create a new connection to the database.
+    /// Check out a connection, reusing an idle pooled one when available.
     ///
-    /// Sets `PRAGMA busy_timeout = 5000` on every connection so concurrent
-    /// writers wait up to 5 seconds instead of failing instantly with
-    /// "database is locked".
-    pub async fn connect(&self) -> Result<Connection, DatabaseError> {
-        let conn = self
-            .db
+    /// New connections get `PRAGMA busy_timeout = 5000` once at creation so
+    /// concurrent writers wait up to 5 seconds instead of failing instantly
+    /// with "database is locked".
+    pub async fn connect(&self) -> Result<PooledConnection, DatabaseError> {
+        Self::checkout_from(&self.db, &self.pool).await
+    }
+
+    /// Check out a connection for read-only queries.
+    ///
+    /// Uses the read replica attached by `with_read_replica()` when there is
+    /// one; otherwise (local embedded and in-memory modes) this is the same
+    /// as `connect()`.
+    pub async fn connect_read(&self) -> Result<PooledConnection, DatabaseError> {
+        match &self.read_db {
+            Some(read_db) => Self::checkout_from(read_db, &self.read_pool).await,
+            None => self.connect().await,
+        }
+    }
+
+    /// Run `SELECT 1` on a pooled connection to confirm the database answers.
+    pub async fn ping(&self) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query("SELECT 1", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        rows.next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| DatabaseError::Query("SELECT 1 returned no rows".to_string()))?;
+        Ok(())
+    }
+
+    async fn checkout_from(
+        db: &LibSqlDatabase,
+        pool: &Arc<ConnectionPool>,
+    ) -> Result<PooledConnection, DatabaseError> {
+        let permit = pool.acquire().await?;
+        if let Some(conn) = pool.checkout() {
+            return Ok(PooledConnection {
+                conn: Some(conn),
+                pool: Arc::clone(pool),
+                _permit: permit,
+            });
+        }
+
+        let conn = db
             .connect()
             .map_err(|e| DatabaseError::Pool(format!("Failed to create connection: {}", e)))?;
         conn.query("PRAGMA busy_timeout = 5000", ())
             .await
             .map_err(|e| DatabaseError::Pool(format!("Failed to set busy_timeout: {}", e)))?;
-        Ok(conn)
+        pool.created.fetch_add(1, Ordering::Relaxed);
+        Ok(PooledConnection {
+            conn: Some(conn),
+            pool: Arc::clone(pool),
+            _permit: permit,
+        })
     }
 
-    async fn ensure_intent_audit_table(&self, conn: &mut Connection) -> Result<(), DatabaseError> {
-        conn.execute(
-            r#"
-            CREATE TABLE IF NOT EXISTS intent_audit_records (
-                intent_id TEXT PRIMARY KEY,
-                agent_id TEXT,
-                user_id TEXT NOT NULL,
-                signal_hash TEXT,
-                intent_hash TEXT NOT NULL,
-                receipt_id TEXT NOT NULL,
-                receipt_hash TEXT NOT NULL,
-                verification_id TEXT,
-                verification_hash TEXT,
-                verification_status TEXT,
-                settlement_id TEXT,
-                settlement_hash TEXT,
-                provider_attributions TEXT NOT NULL DEFAULT '[]',
-                mirrored_pnl_usd TEXT,
-                revenue_share_fee_usd TEXT,
-                chain_hash TEXT NOT NULL,
-                workspace_path TEXT NOT NULL,
-                created_at TEXT NOT NULL
+    /// Number of connections opened against the database so far.
+    pub fn connections_created(&self) -> usize {
+        self.pool.created.load(Ordering::Relaxed)
+    }
+
+    /// Most primary connections that were checked out at the same time.
+    pub fn peak_connections_checked_out(&self) -> usize {
+        self.pool.peak_checked_out.load(Ordering::SeqCst)
+    }
+
+    async fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, DatabaseError> {
+        let mut rows = conn
+            .query(
+                "SELECT name FROM pragma_table_info(?1)",
+                libsql::params![table],
             )
-            "#,
-            (),
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?;
+        let mut columns = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?
+        {
+            columns.push(get_text(&row, 0));
+        }
+        Ok(columns)
+    }
+
+    /// Highest migration version recorded in `schema_migrations` (0 if none).
+    pub async fn current_schema_version(&self) -> Result<i64, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute_batch(libsql_migrations::SCHEMA_MIGRATIONS_TABLE)
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?;
+        let mut rows = conn
+            .query(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+                (),
+            )
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?;
+        Ok(row.map(|row| get_i64(&row, 0)).unwrap_or(0))
+    }
+
+    async fn applied_migration_versions(conn: &Connection) -> Result<Vec<i64>, DatabaseError> {
+        let mut rows = conn
+            .query("SELECT version FROM schema_migrations ORDER BY version", ())
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?;
+        let mut versions = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Migration(e.to_string()))?
+        {
+            versions.push(get_i64(&row, 0));
+        }
+        Ok(versions)
+    }
+
+    async fn record_migration(conn: &Connection, version: i64) -> Result<(), DatabaseError> {
+        conn.execute(
+            "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, ?2)",
+            libsql::params![version, fmt_ts(&Utc::now())],
         )
-        .await?;
-        for stmt in [
-            "ALTER TABLE intent_audit_records ADD COLUMN signal_hash TEXT",
-            "ALTER TABLE intent_audit_records ADD COLUMN settlement_id TEXT",
-            "ALTER TABLE intent_audit_records ADD COLUMN settlement_hash TEXT",
-            "ALTER TABLE intent_audit_records ADD COLUMN provider_attributions TEXT NOT NULL DEFAULT '[]'",
-            "ALTER TABLE intent_audit_records ADD COLUMN mirrored_pnl_usd TEXT",
-            "ALTER TABLE intent_audit_records ADD COLUMN revenue_share_fee_usd TEXT",
+        .await
+        .map_err(|e| DatabaseError::Migration(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Earlier builds added some migrations' columns ad hoc, outside
+    /// `schema_migrations`. Record those migrations as applied wherever their
+    /// column already exists so they are not replayed.
+    async fn baseline_unversioned_schema(&self, conn: &Connection) -> Result<(), DatabaseError> {
+        let applied = Self::applied_migration_versions(conn).await?;
+        for (version, table, column) in [
+            (2, "intent_audit_records", "signal_hash"),
+            (8, "settings", "agent_id"),
+            (9, "frontdoor_sessions", "payload"),
         ] {
-            if let Err(e) = conn.execute(stmt, ()).await {
-                // Existing deployments may already have these columns.
-                if !e
-                    .to_string()
-                    .to_lowercase()
-                    .contains("duplicate column name")
-                {
-                    return Err(DatabaseError::Query(format!(
-                        "failed intent_audit_records schema upgrade: {} ({})",
-                        stmt, e
-                    )));
-                }
+            if applied.contains(&version) {
+                continue;
+            }
+            let columns = Self::table_columns(conn, table).await?;
+            if columns.iter().any(|name| name == column) {
+                Self::record_migration(conn, version).await?;
             }
         }
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_intent_audit_user ON intent_audit_records(user_id)",
-            (),
-        )
-        .await?;
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_intent_audit_chain ON intent_audit_records(chain_hash)",
-            (),
-        )
-        .await?;
+        Ok(())
+    }
+
+    /// Apply every pending migration in version order, one transaction each.
+    async fn apply_pending_migrations(&self, conn: &Connection) -> Result<(), DatabaseError> {
+        let applied = Self::applied_migration_versions(conn).await?;
+        for migration in libsql_migrations::migrations() {
+            if applied.contains(&migration.version) {
+                continue;
+            }
+            let tx = conn.transaction().await.map_err(|e| {
+                DatabaseError::Migration(format!(
+                    "migration {} could not start: {}",
+                    migration.version, e
+                ))
+            })?;
+            tx.execute_batch(migration.up_sql).await.map_err(|e| {
+                DatabaseError::Migration(format!("migration {} failed: {}", migration.version, e))
+            })?;
+            Self::record_migration(&tx, migration.version).await?;
+            tx.commit().await.map_err(|e| {
+                DatabaseError::Migration(format!(
+                    "migration {} could not commit: {}",
+                    migration.version, e
+                ))
+            })?;
+        }
         Ok(())
     }
 }
@@ -205,6 +542,9 @@ impl LibSqlBackend {
 /// 1. RFC 3339 with timezone (e.g. `2024-01-15T10:30:00.123Z`)
 /// 2. Naive datetime with fractional seconds (e.g. `2024-01-15 10:30:00.123`)
 /// 3. Naive datetime without fractional seconds (e.g. `2024-01-15 10:30:00`)
+/// 4. Offset without a colon, `T` or space separated, optional fractional
+///    seconds (e.g. `2024-01-15T10:30:00+0200`, `2024-01-15 10:30:00.5-0530`)
+/// 5. Naive `T`-separated datetime (e.g. `2024-01-15T10:30:00.123`)
 ///
 /// Returns an error if none of the formats match.
 pub(crate) fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, String> {
@@ -220,6 +560,16 @@ pub(crate) fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, String> {
     if let Ok(ndt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
         return Ok(ndt.and_utc());
     }
+    // Offset without a colon, e.g. `+0200` (imported data; RFC 3339 requires `+02:00`)
+    for format in ["%Y-%m-%dT%H:%M:%S%.f%z", "%Y-%m-%d %H:%M:%S%.f%z"] {
+        if let Ok(dt) = DateTime::parse_from_str(s, format) {
+            return Ok(dt.with_timezone(&Utc));
+        }
+    }
+    // Naive with a `T` separator (imported data)
+    if let Ok(ndt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Ok(ndt.and_utc());
+    }
     Err(format!("unparseable timestamp: {:?}", s))
 }
 
@@ -236,18 +586,10 @@ pub(crate) fn fmt_opt_ts(dt: &Option<DateTime<Utc>>) -> libsql::Value {
     }
 }
 
-pub(crate) fn parse_job_state(s: &str) -> JobState {
-    match s {
-        "pending" => JobState::Pending,
-        "in_progress" => JobState::InProgress,
-        "completed" => JobState::Completed,
-        "submitted" => JobState::Submitted,
-        "accepted" => JobState::Accepted,
-        "failed" => JobState::Failed,
-        "stuck" => JobState::Stuck,
-        "cancelled" => JobState::Cancelled,
-        _ => JobState::Pending,
-    }
+/// Parse a stored job status, rejecting values that would otherwise mask corruption.
+pub(crate) fn parse_job_state(s: &str) -> Result<JobState, DatabaseError> {
+    JobState::parse(s)
+        .ok_or_else(|| DatabaseError::Serialization(format!("unknown job status {s:?}")))
 }
 
 fn parse_uuid_opt(value: Option<String>) -> Result<Option<Uuid>, DatabaseError> {
@@ -275,14 +617,18 @@ fn row_to_intent_audit_record(row: &libsql::Row) -> Result<IntentAuditRecord, Da
         get_opt_text(row, 9).and_then(|value| VerificationStatus::parse(&value));
     let settlement_id = parse_uuid_opt(get_opt_text(row, 10))?;
     let settlement_hash = get_opt_text(row, 11);
-    let provider_attributions = get_opt_text(row, 12)
-        .and_then(|raw| serde_json::from_str(&raw).ok())
-        .unwrap_or_default();
+    let provider_attributions = match get_opt_text(row, 12) {
+        Some(raw) => serde_json::from_str(&raw).map_err(|e| {
+            DatabaseError::Serialization(format!("provider_attributions is malformed: {e}"))
+        })?,
+        None => Vec::new(),
+    };
     let mirrored_pnl_usd = get_opt_text(row, 13);
     let revenue_share_fee_usd = get_opt_text(row, 14);
     let chain_hash = get_text(row, 15);
     let workspace_path = get_text(row, 16);
     let created_at = get_ts(row, 17);
+    let verification_level = get_opt_text(row, 18);
 
     Ok(IntentAuditRecord {
         intent_id,
@@ -295,6 +641,7 @@ fn row_to_intent_audit_record(row: &libsql::Row) -> Result<IntentAuditRecord, Da
         verification_id,
         verification_hash,
         verification_status,
+        verification_level,
         settlement_id,
         settlement_hash,
         provider_attributions,
@@ -412,9 +759,11 @@ impl Database for LibSqlBackend {
         conn.query("PRAGMA journal_mode=WAL", ())
             .await
             .map_err(|e| DatabaseError::Migration(format!("Failed to enable WAL mode: {}", e)))?;
-        conn.execute_batch(libsql_migrations::SCHEMA)
+        conn.execute_batch(libsql_migrations::SCHEMA_MIGRATIONS_TABLE)
             .await
             .map_err(|e| DatabaseError::Migration(format!("libSQL migration failed: {}", e)))?;
+        self.baseline_unversioned_schema(&conn).await?;
+        self.apply_pending_migrations(&conn).await?;
         Ok(())
     }
 }
@@ -499,122 +848,502 @@ pub(crate) fn row_to_routine_run_libsql(row: &libsql::Row) -> Result<RoutineRun,
     })
 }
 
-#[async_trait]
-impl IntentAuditStore for LibSqlBackend {
-    async fn persist_intent_audit_record(
+impl LibSqlBackend {
+    /// Upsert an intent audit record and mirror it into settings.
+    ///
+    /// With `checked`, an existing row is only replaced by a strictly newer
+    /// `created_at`, and a differing stored `chain_hash` is rejected. The
+    /// configured quota is enforced in the same transaction. Returns whether
+    /// the row was written. Lock contention is retried.
+    async fn write_intent_audit_record(
         &self,
         record: &IntentAuditRecord,
-    ) -> Result<(), DatabaseError> {
-        let mut conn = self.connect().await?;
-        self.ensure_intent_audit_table(&mut conn).await?;
+        checked: bool,
+    ) -> Result<bool, DatabaseError> {
+        crate::db::validate_provider_attributions(&record.provider_attributions)?;
+        with_write_retry(|| self.write_intent_audit_record_once(record, checked)).await
+    }
 
-        let verification_status = record.verification_status.map(|status| status.as_str());
-        conn.execute(
-            r#"
-            INSERT INTO intent_audit_records (
-                intent_id,
-                agent_id,
-                user_id,
-                signal_hash,
-                intent_hash,
-                receipt_id,
-                receipt_hash,
-                verification_id,
-                verification_hash,
-                verification_status,
-                settlement_id,
-                settlement_hash,
-                provider_attributions,
-                mirrored_pnl_usd,
-                revenue_share_fee_usd,
-                chain_hash,
-                workspace_path,
-                created_at
-            ) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18)
-            ON CONFLICT (intent_id) DO UPDATE SET
-                agent_id = excluded.agent_id,
-                signal_hash = excluded.signal_hash,
-                intent_hash = excluded.intent_hash,
-                receipt_id = excluded.receipt_id,
-                receipt_hash = excluded.receipt_hash,
-                verification_id = excluded.verification_id,
-                verification_hash = excluded.verification_hash,
-                verification_status = excluded.verification_status,
-                settlement_id = excluded.settlement_id,
-                settlement_hash = excluded.settlement_hash,
-                provider_attributions = excluded.provider_attributions,
-                mirrored_pnl_usd = excluded.mirrored_pnl_usd,
-                revenue_share_fee_usd = excluded.revenue_share_fee_usd,
-                chain_hash = excluded.chain_hash,
-                workspace_path = excluded.workspace_path,
-                created_at = excluded.created_at
-            "#,
-            libsql::params![
-                record.intent_id.to_string(),
-                record.agent_id.map(|id| id.to_string()),
-                record.user_id.as_str(),
-                record.signal_hash.as_deref(),
-                record.intent_hash.as_str(),
-                record.receipt_id.to_string(),
-                record.receipt_hash.as_str(),
-                record.verification_id.map(|id| id.to_string()),
-                record.verification_hash.as_deref(),
-                verification_status,
-                record.settlement_id.map(|id| id.to_string()),
-                record.settlement_hash.as_deref(),
-                serde_json::to_string(&record.provider_attributions)
-                    .unwrap_or_else(|_| "[]".to_string()),
-                record.mirrored_pnl_usd.as_deref(),
-                record.revenue_share_fee_usd.as_deref(),
-                record.chain_hash.as_str(),
-                record.workspace_path.as_str(),
-                fmt_ts(&record.created_at),
-            ],
-        )
-        .await?;
+    async fn write_intent_audit_record_once(
+        &self,
+        record: &IntentAuditRecord,
+        checked: bool,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute("BEGIN IMMEDIATE", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
 
-        let settings_payload = json!({
-            "intent_id": record.intent_id,
-            "receipt_id": record.receipt_id,
-            "chain_hash": record.chain_hash,
-            "workspace_path": record.workspace_path,
-            "verification_status": verification_status,
-            "signal_hash": record.signal_hash,
-            "settlement_id": record.settlement_id,
-            "settlement_hash": record.settlement_hash,
-            "provider_attributions": record.provider_attributions,
-            "mirrored_pnl_usd": record.mirrored_pnl_usd,
-            "revenue_share_fee_usd": record.revenue_share_fee_usd,
-            "created_at": record.created_at,
-        });
+        let written = match self.write_intent_audit_tx(&conn, record, checked).await {
+            Ok(written) => written,
+            Err(e) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                return Err(e);
+            }
+        };
+
+        conn.execute("COMMIT", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(written)
+    }
+
+    async fn write_intent_audit_tx(
+        &self,
+        conn: &Connection,
+        record: &IntentAuditRecord,
+        checked: bool,
+    ) -> Result<bool, DatabaseError> {
+        if let Some(quota) = self.intent_audit_quota {
+            Self::enforce_intent_audit_quota(conn, record, quota).await?;
+        }
+        if !Self::insert_intent_audit_row(conn, record, checked).await? {
+            return Ok(false);
+        }
+        Self::mirror_intent_audit_setting(conn, record).await?;
+        Ok(true)
+    }
+
+    /// Make room for `record` under `quota`, or refuse it.
+    ///
+    /// Rewrites of an existing intent never add a row, so they always pass.
+    async fn enforce_intent_audit_quota(
+        conn: &Connection,
+        record: &IntentAuditRecord,
+        quota: IntentAuditQuota,
+    ) -> Result<(), DatabaseError> {
+        let mut rows = conn
+            .query(
+                "SELECT 1 FROM intent_audit_records WHERE intent_id = ?1",
+                libsql::params![record.intent_id.to_string()],
+            )
+            .await?;
+        if rows.next().await?.is_some() {
+            return Ok(());
+        }
+
+        let count = Self::count_intent_audit_rows(conn, &record.user_id).await?;
+        let limit = i64::from(quota.max_records_per_user);
+        if count < limit {
+            return Ok(());
+        }
+        match quota.policy {
+            IntentAuditQuotaPolicy::Reject => Err(DatabaseError::QuotaExceeded {
+                user_id: record.user_id.clone(),
+                limit: quota.max_records_per_user,
+            }),
+            IntentAuditQuotaPolicy::PruneOldest => {
+                // The newest pruned record becomes the chain's checkpoint.
+                let mut rows = conn
+                    .query(
+                        r#"
+                        SELECT chain_hash, intent_id, created_at FROM intent_audit_records
+                        WHERE user_id = ?1
+                        ORDER BY created_at ASC, intent_id ASC
+                        LIMIT 1 OFFSET ?2
+                        "#,
+                        libsql::params![record.user_id.as_str(), count - limit],
+                    )
+                    .await?;
+                if let Some(row) = rows.next().await? {
+                    let checkpoint = IntentAuditCheckpoint {
+                        chain_hash: get_text(&row, 0),
+                        intent_id: get_text(&row, 1).parse().unwrap_or_default(),
+                        created_at: get_ts(&row, 2),
+                    };
+                    Self::write_intent_audit_checkpoint(conn, &record.user_id, &checkpoint).await?;
+                }
+                conn.execute(
+                    r#"
+                    DELETE FROM intent_audit_records
+                    WHERE intent_id IN (
+                        SELECT intent_id FROM intent_audit_records
+                        WHERE user_id = ?1
+                        ORDER BY created_at ASC, intent_id ASC
+                        LIMIT ?2
+                    )
+                    "#,
+                    libsql::params![record.user_id.as_str(), count - limit + 1],
+                )
+                .await?;
+                Ok(())
+            }
+        }
+    }
 
+    async fn write_intent_audit_checkpoint(
+        conn: &Connection,
+        user_id: &str,
+        checkpoint: &IntentAuditCheckpoint,
+    ) -> Result<(), DatabaseError> {
+        let value = serde_json::to_string(checkpoint)
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
         conn.execute(
             r#"
             INSERT INTO settings (user_id, key, value, updated_at)
             VALUES (?1, ?2, ?3, ?4)
-            ON CONFLICT (user_id, key) DO UPDATE SET
+            ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
                 value = excluded.value,
                 updated_at = excluded.updated_at
             "#,
             libsql::params![
-                record.user_id.as_str(),
-                IntentAuditRecord::SETTINGS_KEY,
-                settings_payload.to_string(),
+                user_id,
+                IntentAuditRecord::CHECKPOINT_SETTINGS_KEY,
+                value,
                 fmt_ts(&Utc::now()),
             ],
         )
         .await?;
-
         Ok(())
     }
 
-    async fn get_intent_audit_record(
-        &self,
-        intent_id: Uuid,
-    ) -> Result<Option<IntentAuditRecord>, DatabaseError> {
-        let mut conn = self.connect().await?;
-        self.ensure_intent_audit_table(&mut conn).await?;
-
+    async fn read_intent_audit_checkpoint(
+        conn: &Connection,
+        user_id: &str,
+    ) -> Result<Option<IntentAuditCheckpoint>, DatabaseError> {
+        let mut rows = conn
+            .query(
+                "SELECT value FROM settings WHERE user_id = ?1 AND key = ?2 AND agent_id IS NULL",
+                libsql::params![user_id, IntentAuditRecord::CHECKPOINT_SETTINGS_KEY],
+            )
+            .await?;
+        let Some(row) = rows.next().await? else {
+            return Ok(None);
+        };
+        serde_json::from_str(&get_text(&row, 0))
+            .map(Some)
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))
+    }
+
+    /// Newest record of `user_id`'s chain other than `intent_id`.
+    async fn intent_audit_chain_head(
+        conn: &Connection,
+        user_id: &str,
+        intent_id: Uuid,
+    ) -> Result<Option<IntentAuditChainHead>, DatabaseError> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT chain_hash, created_at, intent_id FROM intent_audit_records
+                WHERE user_id = ?1 AND intent_id != ?2
+                ORDER BY created_at DESC, intent_id DESC
+                LIMIT 1
+                "#,
+                libsql::params![user_id, intent_id.to_string()],
+            )
+            .await?;
+        Ok(rows.next().await?.map(|row| IntentAuditChainHead {
+            chain_hash: get_text(&row, 0),
+            created_at: get_ts(&row, 1),
+            intent_id: get_text(&row, 2).parse().unwrap_or_default(),
+        }))
+    }
+
+    async fn append_intent_audit_record_once(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<IntentAuditRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute("BEGIN IMMEDIATE", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let appended = async {
+            let head =
+                Self::intent_audit_chain_head(&conn, &record.user_id, record.intent_id).await?;
+            let linked = link_intent_audit_record(record, head.as_ref())?;
+            self.write_intent_audit_tx(&conn, &linked, false).await?;
+            Ok(linked)
+        }
+        .await;
+        let linked = match appended {
+            Ok(linked) => linked,
+            Err(e) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                return Err(e);
+            }
+        };
+
+        conn.execute("COMMIT", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(linked)
+    }
+
+    async fn verify_chain_tx(
+        conn: &Connection,
+        user_id: &str,
+    ) -> Result<ChainVerificationReport, DatabaseError> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT intent_id, agent_id, user_id, signal_hash, intent_hash, receipt_id, receipt_hash,
+                       verification_id, verification_hash, verification_status,
+                       settlement_id, settlement_hash, provider_attributions,
+                       mirrored_pnl_usd, revenue_share_fee_usd,
+                       chain_hash, workspace_path, created_at, verification_level
+                FROM intent_audit_records
+                WHERE user_id = ?1
+                ORDER BY created_at ASC, intent_id ASC
+                "#,
+                libsql::params![user_id],
+            )
+            .await?;
+
+        let mut records = Vec::new();
+        loop {
+            match rows.next().await {
+                Ok(Some(row)) => records.push(row_to_intent_audit_record(&row)?),
+                Ok(None) => break,
+                Err(e) => return Err(DatabaseError::Query(e.to_string())),
+            }
+        }
+        let checkpoint = Self::read_intent_audit_checkpoint(conn, user_id).await?;
+        verify_intent_audit_chain(&records, checkpoint.as_ref())
+    }
+
+    async fn count_intent_audit_rows(
+        conn: &Connection,
+        user_id: &str,
+    ) -> Result<i64, DatabaseError> {
+        let mut rows = conn
+            .query(
+                "SELECT COUNT(*) FROM intent_audit_records WHERE user_id = ?1",
+                libsql::params![user_id],
+            )
+            .await?;
+        Ok(rows.next().await?.map(|row| get_i64(&row, 0)).unwrap_or(0))
+    }
+
+    async fn write_intent_audit_batch(
+        &self,
+        conn: &Connection,
+        records: &[IntentAuditRecord],
+    ) -> Result<(), DatabaseError> {
+        let mut latest: HashMap<&str, &IntentAuditRecord> = HashMap::new();
+        for record in records {
+            crate::db::validate_provider_attributions(&record.provider_attributions)?;
+            if let Some(quota) = self.intent_audit_quota {
+                Self::enforce_intent_audit_quota(conn, record, quota).await?;
+            }
+            Self::insert_intent_audit_row(conn, record, false).await?;
+            latest
+                .entry(record.user_id.as_str())
+                .and_modify(|newest| {
+                    if record.created_at >= newest.created_at {
+                        *newest = record;
+                    }
+                })
+                .or_insert(record);
+        }
+        for record in latest.values() {
+            Self::mirror_intent_audit_setting(conn, record).await?;
+        }
+        Ok(())
+    }
+
+    /// Upsert the `intent_audit_records` row; returns whether it was written.
+    async fn insert_intent_audit_row(
+        conn: &Connection,
+        record: &IntentAuditRecord,
+        checked: bool,
+    ) -> Result<bool, DatabaseError> {
+        let verification_status = record.verification_status.map(|status| status.as_str());
+        let mut sql = r#"
+            INSERT INTO intent_audit_records (
+                intent_id,
+                agent_id,
+                user_id,
+                signal_hash,
+                intent_hash,
+                receipt_id,
+                receipt_hash,
+                verification_id,
+                verification_hash,
+                verification_status,
+                settlement_id,
+                settlement_hash,
+                provider_attributions,
+                mirrored_pnl_usd,
+                revenue_share_fee_usd,
+                chain_hash,
+                workspace_path,
+                created_at,
+                verification_level
+            ) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19)
+            ON CONFLICT (intent_id) DO UPDATE SET
+                agent_id = excluded.agent_id,
+                signal_hash = excluded.signal_hash,
+                intent_hash = excluded.intent_hash,
+                receipt_id = excluded.receipt_id,
+                receipt_hash = excluded.receipt_hash,
+                verification_id = excluded.verification_id,
+                verification_hash = excluded.verification_hash,
+                verification_status = excluded.verification_status,
+                settlement_id = excluded.settlement_id,
+                settlement_hash = excluded.settlement_hash,
+                provider_attributions = excluded.provider_attributions,
+                mirrored_pnl_usd = excluded.mirrored_pnl_usd,
+                revenue_share_fee_usd = excluded.revenue_share_fee_usd,
+                chain_hash = excluded.chain_hash,
+                workspace_path = excluded.workspace_path,
+                created_at = excluded.created_at,
+                verification_level = excluded.verification_level
+            "#
+        .to_string();
+        if checked {
+            sql.push_str(
+                "WHERE excluded.created_at > intent_audit_records.created_at \
+                 AND intent_audit_records.chain_hash IN ('', excluded.chain_hash)",
+            );
+        }
+        let written = conn
+            .execute(
+                sql.as_str(),
+                libsql::params![
+                    record.intent_id.to_string(),
+                    record.agent_id.map(|id| id.to_string()),
+                    record.user_id.as_str(),
+                    record.signal_hash.as_deref(),
+                    record.intent_hash.as_str(),
+                    record.receipt_id.to_string(),
+                    record.receipt_hash.as_str(),
+                    record.verification_id.map(|id| id.to_string()),
+                    record.verification_hash.as_deref(),
+                    verification_status,
+                    record.settlement_id.map(|id| id.to_string()),
+                    record.settlement_hash.as_deref(),
+                    serde_json::to_string(&record.provider_attributions)
+                        .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
+                    record.mirrored_pnl_usd.as_deref(),
+                    record.revenue_share_fee_usd.as_deref(),
+                    record.chain_hash.as_str(),
+                    record.workspace_path.as_str(),
+                    fmt_ts(&record.created_at),
+                    record.verification_level.as_deref(),
+                ],
+            )
+            .await?;
+        if written == 0 {
+            let mut rows = conn
+                .query(
+                    "SELECT chain_hash FROM intent_audit_records WHERE intent_id = ?1",
+                    libsql::params![record.intent_id.to_string()],
+                )
+                .await?;
+            if let Some(row) = rows.next().await? {
+                let stored = get_text(&row, 0);
+                if !stored.is_empty() && stored != record.chain_hash {
+                    return Err(DatabaseError::Constraint(format!(
+                        "intent {} already has chain_hash {}; refusing {}",
+                        record.intent_id, stored, record.chain_hash
+                    )));
+                }
+            }
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Mirror `record` into the user's `IntentAuditRecord::SETTINGS_KEY` setting.
+    async fn mirror_intent_audit_setting(
+        conn: &Connection,
+        record: &IntentAuditRecord,
+    ) -> Result<(), DatabaseError> {
+        let verification_status = record.verification_status.map(|status| status.as_str());
+        let settings_payload = json!({
+            "intent_id": record.intent_id,
+            "receipt_id": record.receipt_id,
+            "chain_hash": record.chain_hash,
+            "workspace_path": record.workspace_path,
+            "verification_status": verification_status,
+            "verification_level": record.verification_level,
+            "signal_hash": record.signal_hash,
+            "settlement_id": record.settlement_id,
+            "settlement_hash": record.settlement_hash,
+            "provider_attributions": record.provider_attributions,
+            "mirrored_pnl_usd": record.mirrored_pnl_usd,
+            "revenue_share_fee_usd": record.revenue_share_fee_usd,
+            "created_at": record.created_at,
+        });
+
+        conn.execute(
+            r#"
+            INSERT INTO settings (user_id, key, value, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
+                value = excluded.value,
+                updated_at = excluded.updated_at
+            "#,
+            libsql::params![
+                record.user_id.as_str(),
+                IntentAuditRecord::SETTINGS_KEY,
+                settings_payload.to_string(),
+                fmt_ts(&Utc::now()),
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IntentAuditStore for LibSqlBackend {
+    async fn persist_intent_audit_record(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<(), DatabaseError> {
+        self.write_intent_audit_record(record, false).await?;
+        Ok(())
+    }
+
+    async fn persist_intent_audit_record_checked(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<bool, DatabaseError> {
+        self.write_intent_audit_record(record, true).await
+    }
+
+    async fn append_intent_audit_record(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<IntentAuditRecord, DatabaseError> {
+        crate::db::validate_provider_attributions(&record.provider_attributions)?;
+        with_write_retry(|| self.append_intent_audit_record_once(record)).await
+    }
+
+    async fn persist_intent_audit_records_batch(
+        &self,
+        records: &[IntentAuditRecord],
+    ) -> Result<(), DatabaseError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        with_write_retry(|| async {
+            let conn = self.connect().await?;
+            conn.execute("BEGIN IMMEDIATE", ())
+                .await
+                .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+            if let Err(e) = self.write_intent_audit_batch(&conn, records).await {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                return Err(e);
+            }
+
+            conn.execute("COMMIT", ())
+                .await
+                .map_err(|e| DatabaseError::Query(e.to_string()))?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_intent_audit_record(
+        &self,
+        intent_id: Uuid,
+    ) -> Result<Option<IntentAuditRecord>, DatabaseError> {
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 r#"
@@ -622,7 +1351,7 @@ impl IntentAuditStore for LibSqlBackend {
                        verification_id, verification_hash, verification_status,
                        settlement_id, settlement_hash, provider_attributions,
                        mirrored_pnl_usd, revenue_share_fee_usd,
-                       chain_hash, workspace_path, created_at
+                       chain_hash, workspace_path, created_at, verification_level
                 FROM intent_audit_records
                 WHERE intent_id = ?1
                 "#,
@@ -637,14 +1366,17 @@ impl IntentAuditStore for LibSqlBackend {
         }
     }
 
+    async fn count_intent_audit_records(&self, user_id: &str) -> Result<i64, DatabaseError> {
+        let conn = self.connect_read().await?;
+        Self::count_intent_audit_rows(&conn, user_id).await
+    }
+
     async fn list_intent_audit_records(
         &self,
         user_id: &str,
         limit: i64,
     ) -> Result<Vec<IntentAuditRecord>, DatabaseError> {
-        let mut conn = self.connect().await?;
-        self.ensure_intent_audit_table(&mut conn).await?;
-
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 r#"
@@ -652,10 +1384,10 @@ impl IntentAuditStore for LibSqlBackend {
                        verification_id, verification_hash, verification_status,
                        settlement_id, settlement_hash, provider_attributions,
                        mirrored_pnl_usd, revenue_share_fee_usd,
-                       chain_hash, workspace_path, created_at
+                       chain_hash, workspace_path, created_at, verification_level
                 FROM intent_audit_records
                 WHERE user_id = ?1
-                ORDER BY created_at DESC
+                ORDER BY created_at DESC, intent_id DESC
                 LIMIT ?2
                 "#,
                 libsql::params![user_id, limit],
@@ -672,10 +1404,182 @@ impl IntentAuditStore for LibSqlBackend {
         }
         Ok(records)
     }
+
+    async fn list_intent_audit_records_paged(
+        &self,
+        user_id: &str,
+        limit: i64,
+        before: Option<(DateTime<Utc>, Uuid)>,
+    ) -> Result<IntentAuditPage, DatabaseError> {
+        let conn = self.connect_read().await?;
+        // `created_at` is always written through `fmt_ts`, so the text column
+        // sorts chronologically and compares exactly against a formatted cursor.
+        let (before_ts, before_id) = match before {
+            Some((created_at, intent_id)) => (
+                libsql::Value::Text(fmt_ts(&created_at)),
+                libsql::Value::Text(intent_id.to_string()),
+            ),
+            None => (libsql::Value::Null, libsql::Value::Null),
+        };
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT intent_id, agent_id, user_id, signal_hash, intent_hash, receipt_id, receipt_hash,
+                       verification_id, verification_hash, verification_status,
+                       settlement_id, settlement_hash, provider_attributions,
+                       mirrored_pnl_usd, revenue_share_fee_usd,
+                       chain_hash, workspace_path, created_at, verification_level
+                FROM intent_audit_records
+                WHERE user_id = ?1
+                  AND (?2 IS NULL OR created_at < ?2 OR (created_at = ?2 AND intent_id < ?3))
+                ORDER BY created_at DESC, intent_id DESC
+                LIMIT ?4
+                "#,
+                libsql::params![user_id, before_ts, before_id, limit],
+            )
+            .await?;
+
+        let mut records = Vec::new();
+        loop {
+            match rows.next().await {
+                Ok(Some(row)) => records.push(row_to_intent_audit_record(&row)?),
+                Ok(None) => break,
+                Err(e) => return Err(DatabaseError::Query(e.to_string())),
+            }
+        }
+        let next_cursor = match records.last() {
+            Some(last) if records.len() as i64 == limit => {
+                Some(encode_intent_audit_cursor(&last.created_at, last.intent_id))
+            }
+            _ => None,
+        };
+        Ok(IntentAuditPage {
+            records,
+            next_cursor,
+        })
+    }
+
+    async fn verify_chain(&self, user_id: &str) -> Result<ChainVerificationReport, DatabaseError> {
+        let conn = self.connect_read().await?;
+        // One read transaction, so a concurrent prune cannot land between
+        // reading the checkpoint and reading the records.
+        conn.execute("BEGIN", ())
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let result = Self::verify_chain_tx(&conn, user_id).await;
+        let _ = conn.execute("COMMIT", ()).await;
+        result
+    }
+
+    async fn export_records(
+        &self,
+        user_id: &str,
+        format: ExportFormat,
+        writer: &mut (dyn std::io::Write + Send),
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.connect_read().await?;
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT intent_id, agent_id, user_id, signal_hash, intent_hash, receipt_id, receipt_hash,
+                       verification_id, verification_hash, verification_status,
+                       settlement_id, settlement_hash, provider_attributions,
+                       mirrored_pnl_usd, revenue_share_fee_usd,
+                       chain_hash, workspace_path, created_at, verification_level
+                FROM intent_audit_records
+                WHERE user_id = ?1
+                ORDER BY created_at ASC, intent_id ASC
+                "#,
+                libsql::params![user_id],
+            )
+            .await?;
+
+        write_intent_audit_export_header(format, writer)?;
+        let mut written: u64 = 0;
+        loop {
+            match rows.next().await {
+                Ok(Some(row)) => {
+                    let record = row_to_intent_audit_record(&row)?;
+                    write_intent_audit_export_row(format, &record, writer)?;
+                    written += 1;
+                }
+                Ok(None) => break,
+                Err(e) => return Err(DatabaseError::Query(e.to_string())),
+            }
+        }
+        writer
+            .flush()
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        Ok(written)
+    }
+}
+
+/// Attempts [`with_write_retry`] makes before returning a lock error.
+const WRITE_LOCK_MAX_ATTEMPTS: u32 = 4;
+/// Base delay between lock retries; attempt `n` waits `n` times this plus
+/// up to half as much jitter.
+const WRITE_LOCK_BACKOFF_MS: u64 = 50;
+
+/// Whether `err` is SQLite reporting lock contention that outlived
+/// `busy_timeout`.
+fn is_database_locked(err: &DatabaseError) -> bool {
+    let message = err.to_string().to_ascii_lowercase();
+    message.contains("database is locked") || message.contains("database table is locked")
+}
+
+/// Run the write `write`, retrying with jittered backoff while it fails with
+/// "database is locked". Any other error is returned on the first attempt.
+///
+/// `write` must be safe to re-run: a failed attempt has to leave nothing
+/// behind, e.g. by rolling back its transaction.
+pub(crate) async fn with_write_retry<T, F, Fut>(mut write: F) -> Result<T, DatabaseError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, DatabaseError>>,
+{
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        match write().await {
+            Err(e) if attempt < WRITE_LOCK_MAX_ATTEMPTS && is_database_locked(&e) => {
+                let base = WRITE_LOCK_BACKOFF_MS.saturating_mul(u64::from(attempt));
+                let jitter = rand::Rng::gen_range(&mut rand::thread_rng(), 0..=base / 2);
+                tracing::debug!(attempt, "Database locked, retrying write: {}", e);
+                tokio::time::sleep(std::time::Duration::from_millis(base + jitter)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Run `sync` until it succeeds or `max_retries` retries have failed.
+async fn retry_sync<F, Fut>(
+    max_retries: u32,
+    backoff: std::time::Duration,
+    mut sync: F,
+) -> Result<SyncReport, DatabaseError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<SyncReport, DatabaseError>>,
+{
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        match sync().await {
+            Ok(report) => return Ok(report),
+            Err(e) if attempt <= max_retries => {
+                tracing::warn!(attempt, max_retries, "Replica sync failed, retrying: {}", e);
+                tokio::time::sleep(backoff.saturating_mul(attempt)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use crate::db::Database;
     use crate::db::libsql::LibSqlBackend;
 
@@ -697,9 +1601,48 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn test_busy_timeout_set_on_connect() {
-        let backend = LibSqlBackend::new_memory().await.unwrap();
+    #[tokio::test(start_paused = true)]
+    async fn test_write_retry_recovers_from_database_locked() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        use crate::error::DatabaseError;
+
+        let calls = AtomicU32::new(0);
+        let result = super::with_write_retry(|| async {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(DatabaseError::Query("database is locked".to_string()))
+            } else {
+                Ok(7)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = super::with_write_retry(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(DatabaseError::Constraint(
+                "UNIQUE constraint failed".to_string(),
+            ))
+        })
+        .await;
+        assert!(matches!(result, Err(DatabaseError::Constraint(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = super::with_write_retry(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(DatabaseError::Query("database is locked".to_string()))
+        })
+        .await;
+        assert!(super::is_database_locked(&result.unwrap_err()));
+        assert_eq!(calls.load(Ordering::SeqCst), super::WRITE_LOCK_MAX_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn test_busy_timeout_set_on_connect() {
+        let backend = LibSqlBackend::new_memory().await.unwrap();
         backend.run_migrations().await.unwrap();
 
         let conn = backend.connect().await.unwrap();
@@ -714,14 +1657,21 @@ mod tests {
         // Use a temp file so connections share state (in-memory DBs are connection-local)
         let dir = tempfile::tempdir().unwrap();
         let db_path = dir.path().join("test_concurrent.db");
-        let backend = LibSqlBackend::new_local(&db_path).await.unwrap();
+        let backend = Arc::new(
+            LibSqlBackend::new_local(&db_path)
+                .await
+                .unwrap()
+                .with_max_pool_size(4),
+        );
         backend.run_migrations().await.unwrap();
 
-        // Spawn 20 concurrent inserts into the conversations table
+        // Spawn 20 concurrent inserts into the conversations table; each task
+        // checks out its own connection, so only 4 may run at a time.
         let mut handles = Vec::new();
         for i in 0..20 {
-            let conn = backend.connect().await.unwrap();
+            let backend = Arc::clone(&backend);
             let handle = tokio::spawn(async move {
+                let conn = backend.connect().await?;
                 let id = uuid::Uuid::new_v4().to_string();
                 let val = format!("ch_{}", i);
                 conn.execute(
@@ -729,6 +1679,9 @@ mod tests {
                     libsql::params![id, val, "test_user"],
                 )
                 .await
+                .map_err(|e| crate::error::DatabaseError::Query(e.to_string()))?;
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                Ok::<_, crate::error::DatabaseError>(())
             });
             handles.push(handle);
         }
@@ -754,5 +1707,1628 @@ mod tests {
         let row = rows.next().await.unwrap().unwrap();
         let count: i64 = row.get(0).unwrap();
         assert_eq!(count, 20);
+        assert!(backend.peak_connections_checked_out() <= 4);
+        assert!(backend.connections_created() <= 4);
+    }
+
+    #[tokio::test]
+    async fn test_pool_checkout_waits_for_a_released_connection() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = Arc::new(
+            LibSqlBackend::new_local(&dir.path().join("test_pool_wait.db"))
+                .await
+                .unwrap()
+                .with_max_pool_size(1),
+        );
+
+        let held = backend.connect().await.unwrap();
+        let waiter = {
+            let backend = Arc::clone(&backend);
+            tokio::spawn(async move { backend.connect().await.map(|_| ()) })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished(), "second checkout must wait");
+
+        drop(held);
+        waiter.await.unwrap().unwrap();
+        assert_eq!(backend.peak_connections_checked_out(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_ts_converts_offset_timestamps_to_utc() {
+        use chrono::{DateTime, Utc};
+
+        use crate::db::libsql::get_ts;
+
+        let backend = LibSqlBackend::new_memory().await.unwrap();
+        let conn = backend.connect().await.unwrap();
+
+        let cases = [
+            ("2024-01-15T10:30:00+02:00", "2024-01-15T08:30:00Z"),
+            ("2024-01-15 10:30:00+02:00", "2024-01-15T08:30:00Z"),
+            ("2024-01-15T10:30:00-05:30", "2024-01-15T16:00:00Z"),
+            ("2024-01-15 10:30:00-05:30", "2024-01-15T16:00:00Z"),
+            ("2024-01-15T10:30:00+0200", "2024-01-15T08:30:00Z"),
+            ("2024-01-15 10:30:00-0530", "2024-01-15T16:00:00Z"),
+            ("2024-01-15 10:30:00.250+0200", "2024-01-15T08:30:00.250Z"),
+            ("2024-01-15T10:30:00", "2024-01-15T10:30:00Z"),
+            ("2024-01-15T10:30:00.123", "2024-01-15T10:30:00.123Z"),
+        ];
+        for (raw, expected) in cases {
+            let mut rows = conn.query("SELECT ?1", libsql::params![raw]).await.unwrap();
+            let row = rows.next().await.unwrap().unwrap();
+            let expected = DateTime::parse_from_rfc3339(expected)
+                .unwrap()
+                .with_timezone(&Utc);
+            assert_eq!(get_ts(&row, 0), expected, "{raw}");
+        }
+
+        let mut rows = conn
+            .query("SELECT ?1", libsql::params!["2024-01-15T10:30+02"])
+            .await
+            .unwrap();
+        let row = rows.next().await.unwrap().unwrap();
+        assert_eq!(get_ts(&row, 0), DateTime::UNIX_EPOCH);
+    }
+
+    #[tokio::test]
+    async fn test_checked_intent_audit_write_keeps_newest_and_guards_chain() {
+        use crate::agent::{IntentAuditRecord, VerificationStatus};
+        use crate::db::IntentAuditStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_checked.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let base = chrono::Utc::now();
+        let original = IntentAuditRecord {
+            intent_id: uuid::Uuid::new_v4(),
+            agent_id: None,
+            user_id: "checked".to_string(),
+            signal_hash: None,
+            intent_hash: "intent".to_string(),
+            receipt_id: uuid::Uuid::new_v4(),
+            receipt_hash: "receipt".to_string(),
+            verification_id: None,
+            verification_hash: None,
+            verification_status: None,
+            verification_level: None,
+            settlement_id: None,
+            settlement_hash: None,
+            provider_attributions: Vec::new(),
+            mirrored_pnl_usd: None,
+            revenue_share_fee_usd: None,
+            chain_hash: "chain".to_string(),
+            workspace_path: "audit/intents".to_string(),
+            created_at: base,
+        };
+        assert!(
+            backend
+                .persist_intent_audit_record_checked(&original)
+                .await
+                .unwrap()
+        );
+
+        let mut newer = original.clone();
+        newer.verification_status = Some(VerificationStatus::Verified);
+        newer.created_at = base + chrono::Duration::seconds(5);
+        assert!(
+            backend
+                .persist_intent_audit_record_checked(&newer)
+                .await
+                .unwrap()
+        );
+
+        let mut stale = original.clone();
+        stale.receipt_hash = "stale-receipt".to_string();
+        stale.created_at = base + chrono::Duration::seconds(1);
+        assert!(
+            !backend
+                .persist_intent_audit_record_checked(&stale)
+                .await
+                .unwrap()
+        );
+
+        let stored = backend
+            .get_intent_audit_record(original.intent_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.verification_status,
+            Some(VerificationStatus::Verified)
+        );
+        assert_eq!(stored.receipt_hash, "receipt");
+
+        let mut forked = newer.clone();
+        forked.chain_hash = "forked-chain".to_string();
+        forked.created_at = base + chrono::Duration::seconds(10);
+        let err = backend
+            .persist_intent_audit_record_checked(&forked)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::error::DatabaseError::Constraint(_)),
+            "{err:?}"
+        );
+        let stored = backend
+            .get_intent_audit_record(original.intent_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.chain_hash, "chain");
+    }
+
+    #[tokio::test]
+    async fn test_verify_chain_reports_first_tampered_record() {
+        use crate::agent::IntentAuditRecord;
+        use crate::db::IntentAuditStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_verify.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let base = chrono::Utc::now();
+        let mut records = Vec::new();
+        let mut previous: Option<String> = None;
+        for i in 0..3 {
+            let record = IntentAuditRecord {
+                intent_id: uuid::Uuid::new_v4(),
+                agent_id: None,
+                user_id: "verify".to_string(),
+                signal_hash: None,
+                intent_hash: format!("intent-{i}"),
+                receipt_id: uuid::Uuid::new_v4(),
+                receipt_hash: format!("receipt-{i}"),
+                verification_id: None,
+                verification_hash: None,
+                verification_status: None,
+                verification_level: None,
+                settlement_id: None,
+                settlement_hash: None,
+                provider_attributions: Vec::new(),
+                mirrored_pnl_usd: None,
+                revenue_share_fee_usd: None,
+                chain_hash: String::new(),
+                workspace_path: format!("audit/intents/{i}"),
+                created_at: base + chrono::Duration::seconds(i),
+            }
+            .linked_to(previous.as_deref())
+            .unwrap();
+            previous = Some(record.chain_hash.clone());
+            backend.persist_intent_audit_record(&record).await.unwrap();
+            records.push(record);
+        }
+
+        let report = backend.verify_chain("verify").await.unwrap();
+        assert!(report.is_intact(), "{report:?}");
+        assert_eq!(report.records_checked, 3);
+
+        let mut tampered = records[1].clone();
+        tampered.intent_hash = "intent-forged".to_string();
+        backend
+            .persist_intent_audit_record(&tampered)
+            .await
+            .unwrap();
+
+        let report = backend.verify_chain("verify").await.unwrap();
+        assert_eq!(report.first_broken_index, Some(1));
+        assert_eq!(report.first_broken_intent_id, Some(records[1].intent_id));
+        assert_eq!(report.records_checked, 2);
+
+        let empty = backend.verify_chain("nobody").await.unwrap();
+        assert_eq!(empty.records_checked, 0);
+        assert!(empty.is_intact());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_appends_keep_a_single_chain() {
+        use crate::agent::IntentAuditRecord;
+        use crate::db::IntentAuditStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = Arc::new(
+            LibSqlBackend::new_local(&dir.path().join("test_audit_append.db"))
+                .await
+                .unwrap()
+                .with_max_pool_size(4),
+        );
+        backend.run_migrations().await.unwrap();
+
+        // Every record shares one timestamp, as intents built in the same
+        // millisecond would, so only the append transaction orders them.
+        let created_at = chrono::Utc::now();
+        let mut handles = Vec::new();
+        for i in 0..12 {
+            let backend = Arc::clone(&backend);
+            handles.push(tokio::spawn(async move {
+                let record = IntentAuditRecord {
+                    intent_id: uuid::Uuid::new_v4(),
+                    agent_id: None,
+                    user_id: "append".to_string(),
+                    signal_hash: None,
+                    intent_hash: format!("intent-{i}"),
+                    receipt_id: uuid::Uuid::new_v4(),
+                    receipt_hash: format!("receipt-{i}"),
+                    verification_id: None,
+                    verification_hash: None,
+                    verification_status: None,
+                    verification_level: None,
+                    settlement_id: None,
+                    settlement_hash: None,
+                    provider_attributions: Vec::new(),
+                    mirrored_pnl_usd: None,
+                    revenue_share_fee_usd: None,
+                    chain_hash: String::new(),
+                    workspace_path: format!("audit/intents/{i}"),
+                    created_at,
+                };
+                backend.append_intent_audit_record(&record).await
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap().expect("append");
+        }
+
+        let report = backend.verify_chain("append").await.unwrap();
+        assert!(report.is_intact(), "{report:?}");
+        assert_eq!(report.records_checked, 12);
+
+        // Exactly one record starts the chain; every other one has a parent.
+        let records = backend
+            .list_intent_audit_records("append", 100)
+            .await
+            .unwrap();
+        let genesis = records
+            .iter()
+            .filter(|record| record.expected_chain_hash(None).unwrap() == record.chain_hash)
+            .count();
+        assert_eq!(genesis, 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_records_csv_and_ndjson() {
+        use crate::agent::{IntentAuditRecord, VerificationStatus};
+        use crate::db::{ExportFormat, INTENT_AUDIT_EXPORT_COLUMNS, IntentAuditStore};
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_export.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let base = chrono::Utc::now();
+        for i in 0..2 {
+            let record = IntentAuditRecord {
+                intent_id: uuid::Uuid::new_v4(),
+                agent_id: Some(uuid::Uuid::new_v4()),
+                user_id: "export".to_string(),
+                signal_hash: None,
+                intent_hash: format!("intent-{i}"),
+                receipt_id: uuid::Uuid::new_v4(),
+                receipt_hash: format!("receipt-{i}"),
+                verification_id: None,
+                verification_hash: None,
+                verification_status: Some(VerificationStatus::Pending),
+                verification_level: (i == 1).then(|| "primary_plus_signed_fallback".to_string()),
+                settlement_id: None,
+                settlement_hash: None,
+                provider_attributions: Vec::new(),
+                mirrored_pnl_usd: None,
+                revenue_share_fee_usd: None,
+                chain_hash: format!("chain-{i}"),
+                workspace_path: format!("audit/intents/{i},json"),
+                created_at: base + chrono::Duration::seconds(i),
+            };
+            backend.persist_intent_audit_record(&record).await.unwrap();
+        }
+
+        let mut csv = Vec::new();
+        let written = backend
+            .export_records("export", ExportFormat::Csv, &mut csv)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], INTENT_AUDIT_EXPORT_COLUMNS.join(","));
+        assert!(lines[0].contains(",verification_status,verification_level,settlement_id,"));
+        assert!(lines[1].contains("intent-0"));
+        assert!(lines[1].contains("\"audit/intents/0,json\""));
+        assert!(lines[1].contains(",[],"));
+        assert!(lines[1].contains(",pending,,"));
+        assert!(lines[2].contains(",pending,primary_plus_signed_fallback,"));
+
+        let mut ndjson = Vec::new();
+        let written = backend
+            .export_records("export", ExportFormat::Ndjson, &mut ndjson)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+        let records: Vec<IntentAuditRecord> = String::from_utf8(ndjson)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].intent_hash, "intent-0");
+        assert_eq!(records[1].chain_hash, "chain-1");
+        assert_eq!(
+            records[1].verification_status,
+            Some(VerificationStatus::Pending)
+        );
+        assert_eq!(
+            records[1].verification_level.as_deref(),
+            Some("primary_plus_signed_fallback")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_intent_audit_pages_walk_all_records_once() {
+        use std::collections::HashSet;
+
+        use crate::agent::IntentAuditRecord;
+        use crate::db::{IntentAuditStore, decode_intent_audit_cursor};
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_pages.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        // Five records per timestamp so pages have to split shared timestamps.
+        let base = chrono::Utc::now();
+        let mut inserted = HashSet::new();
+        for i in 0..50i64 {
+            let record = IntentAuditRecord {
+                intent_id: uuid::Uuid::new_v4(),
+                agent_id: None,
+                user_id: "pager".to_string(),
+                signal_hash: None,
+                intent_hash: format!("intent-{i}"),
+                receipt_id: uuid::Uuid::new_v4(),
+                receipt_hash: format!("receipt-{i}"),
+                verification_id: None,
+                verification_hash: None,
+                verification_status: None,
+                verification_level: None,
+                settlement_id: None,
+                settlement_hash: None,
+                provider_attributions: Vec::new(),
+                mirrored_pnl_usd: None,
+                revenue_share_fee_usd: None,
+                chain_hash: format!("chain-{i}"),
+                workspace_path: "audit/intents".to_string(),
+                created_at: base - chrono::Duration::seconds(i / 5),
+            };
+            inserted.insert(record.intent_id);
+            backend.persist_intent_audit_record(&record).await.unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut before = None;
+        loop {
+            let page = backend
+                .list_intent_audit_records_paged("pager", 7, before)
+                .await
+                .unwrap();
+            seen.extend(page.records.iter().map(|record| record.intent_id));
+            match page.next_cursor {
+                Some(cursor) => before = Some(decode_intent_audit_cursor(&cursor).unwrap()),
+                None => break,
+            }
+        }
+
+        assert_eq!(seen.len(), 50);
+        assert_eq!(seen.iter().copied().collect::<HashSet<_>>(), inserted);
+    }
+
+    #[tokio::test]
+    async fn test_agent_setting_shadows_global_only_for_that_agent() {
+        use serde_json::json;
+
+        use crate::db::SettingsStore;
+        use crate::settings::Settings;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_agent_settings.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let agent_a = uuid::Uuid::new_v4();
+        let agent_b = uuid::Uuid::new_v4();
+        backend
+            .set_setting("user1", "agent.name", &json!("global"))
+            .await
+            .unwrap();
+        backend
+            .set_setting("user1", "agent.max_parallel_jobs", &json!(3))
+            .await
+            .unwrap();
+        backend
+            .set_setting_for_agent("user1", agent_a, "agent.name", &json!("first"))
+            .await
+            .unwrap();
+        backend
+            .set_setting_for_agent("user1", agent_a, "agent.name", &json!("alpha"))
+            .await
+            .unwrap();
+
+        let global = backend.get_all_settings("user1").await.unwrap();
+        assert_eq!(global.get("agent.name"), Some(&json!("global")));
+        assert_eq!(
+            backend.get_setting("user1", "agent.name").await.unwrap(),
+            Some(json!("global"))
+        );
+
+        let a = backend
+            .get_all_settings_for_agent("user1", agent_a)
+            .await
+            .unwrap();
+        assert_eq!(a.len(), 1);
+        let (merged_a, _) = Settings::from_db_maps(&global, &a);
+        assert_eq!(merged_a.agent.name, "alpha");
+        assert_eq!(merged_a.agent.max_parallel_jobs, 3);
+
+        let b = backend
+            .get_all_settings_for_agent("user1", agent_b)
+            .await
+            .unwrap();
+        assert!(b.is_empty());
+        let (merged_b, _) = Settings::from_db_maps(&global, &b);
+        assert_eq!(merged_b.agent.name, "global");
+    }
+
+    #[tokio::test]
+    async fn test_legacy_settings_table_upgrades_to_agent_scope() {
+        use serde_json::json;
+
+        use crate::db::SettingsStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_settings_upgrade.db"))
+            .await
+            .unwrap();
+        let conn = backend.connect().await.unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE settings (
+                user_id TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (user_id, key)
+            );
+            INSERT INTO settings (user_id, key, value) VALUES ('user1', 'agent.name', '"legacy"');
+            "#,
+        )
+        .await
+        .unwrap();
+        drop(conn);
+
+        backend.run_migrations().await.unwrap();
+        backend.run_migrations().await.unwrap();
+
+        assert_eq!(
+            backend.get_setting("user1", "agent.name").await.unwrap(),
+            Some(json!("legacy"))
+        );
+        backend
+            .set_setting_for_agent("user1", uuid::Uuid::new_v4(), "agent.name", &json!("x"))
+            .await
+            .unwrap();
+        backend
+            .set_setting("user1", "agent.name", &json!("updated"))
+            .await
+            .unwrap();
+        assert_eq!(backend.list_settings("user1").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_migrations_are_versioned_and_idempotent() {
+        use crate::db::libsql_migrations::migrations;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_versions.db"))
+            .await
+            .unwrap();
+        assert_eq!(backend.current_schema_version().await.unwrap(), 0);
+
+        backend.run_migrations().await.unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let latest = migrations().iter().map(|m| m.version).max().unwrap();
+        assert_eq!(backend.current_schema_version().await.unwrap(), latest);
+
+        let conn = backend.connect().await.unwrap();
+        let mut rows = conn
+            .query("SELECT COUNT(*) FROM schema_migrations", ())
+            .await
+            .unwrap();
+        let row = rows.next().await.unwrap().unwrap();
+        let recorded: i64 = row.get(0).unwrap();
+        assert_eq!(recorded as usize, migrations().len());
+    }
+
+    #[tokio::test]
+    async fn test_unversioned_database_skips_already_applied_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_unversioned.db"))
+            .await
+            .unwrap();
+        let conn = backend.connect().await.unwrap();
+        conn.execute_batch(crate::db::libsql_migrations::SCHEMA)
+            .await
+            .unwrap();
+        conn.execute_batch(crate::db::libsql_migrations::INTENT_AUDIT_LINEAGE_COLUMNS)
+            .await
+            .unwrap();
+        drop(conn);
+
+        backend.run_migrations().await.unwrap();
+        assert_eq!(backend.current_schema_version().await.unwrap(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_pool_reuses_connections_across_sequential_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_pool.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+        let baseline = backend.connections_created();
+
+        for _ in 0..50 {
+            let conn = backend.connect().await.unwrap();
+            let mut rows = conn.query("PRAGMA busy_timeout", ()).await.unwrap();
+            let row = rows.next().await.unwrap().unwrap();
+            let timeout: i64 = row.get(0).unwrap();
+            assert_eq!(timeout, 5000);
+        }
+
+        assert!(backend.connections_created() <= baseline.max(1));
+    }
+
+    #[tokio::test]
+    async fn test_pool_discards_connection_left_in_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_pool_tx.db"))
+            .await
+            .unwrap()
+            .with_max_pool_size(2);
+
+        {
+            let conn = backend.connect().await.unwrap();
+            conn.execute("BEGIN", ()).await.unwrap();
+        }
+        assert_eq!(backend.connections_created(), 1);
+
+        let conn = backend.connect().await.unwrap();
+        assert!(conn.is_autocommit());
+        assert_eq!(backend.connections_created(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_memory_documents_yields_rows_incrementally() {
+        use futures::StreamExt;
+
+        use crate::db::WorkspaceStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_stream_docs.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+        for i in 0..30 {
+            backend
+                .get_or_create_document_by_path("user-1", None, &format!("notes/{i}.md"))
+                .await
+                .unwrap();
+        }
+        backend
+            .get_or_create_document_by_path("user-2", None, "notes/other.md")
+            .await
+            .unwrap();
+
+        let docs: Vec<_> = backend
+            .stream_memory_documents("user-1", None)
+            .collect()
+            .await;
+        assert_eq!(docs.len(), 30);
+        assert!(
+            docs.iter()
+                .all(|doc| doc.as_ref().unwrap().user_id == "user-1")
+        );
+
+        let mut partial = Box::pin(backend.stream_memory_documents("user-1", None));
+        for _ in 0..3 {
+            assert!(partial.next().await.unwrap().is_ok());
+        }
+        drop(partial);
+        let baseline = backend.connections_created();
+        assert_eq!(
+            backend.list_documents("user-1", None).await.unwrap().len(),
+            30
+        );
+        assert_eq!(backend.connections_created(), baseline);
+    }
+
+    #[tokio::test]
+    async fn test_provider_attributions_are_validated_on_write() {
+        use crate::agent::{
+            IntentAuditRecord, ProviderRevenueAttribution, SignalFeeSchedule,
+            SignalProviderIdentity,
+        };
+        use crate::db::IntentAuditStore;
+        use crate::error::DatabaseError;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_attributions.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let attribution = |provider_id: &str, weight_bps: u32| ProviderRevenueAttribution {
+            provider: SignalProviderIdentity {
+                provider_id: provider_id.to_string(),
+                payout_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                display_name: None,
+            },
+            signal_id: format!("signal-{provider_id}"),
+            signal_hash: "a".repeat(64),
+            attribution_weight_bps: weight_bps,
+            fee_schedule: SignalFeeSchedule {
+                fixed_fee_bps: 10,
+                performance_fee_bps: 1_000,
+                max_fee_usd: rust_decimal::Decimal::new(250, 0),
+            },
+        };
+        let record = |provider_attributions: Vec<ProviderRevenueAttribution>| IntentAuditRecord {
+            intent_id: uuid::Uuid::new_v4(),
+            agent_id: None,
+            user_id: "attributions".to_string(),
+            signal_hash: None,
+            intent_hash: "intent".to_string(),
+            receipt_id: uuid::Uuid::new_v4(),
+            receipt_hash: "receipt".to_string(),
+            verification_id: None,
+            verification_hash: None,
+            verification_status: None,
+            verification_level: None,
+            settlement_id: None,
+            settlement_hash: None,
+            provider_attributions,
+            mirrored_pnl_usd: None,
+            revenue_share_fee_usd: None,
+            chain_hash: "chain".to_string(),
+            workspace_path: "audit/intents".to_string(),
+            created_at: chrono::Utc::now(),
+        };
+
+        let valid = record(vec![
+            attribution("alpha", 6_000),
+            attribution("beta", 4_000),
+        ]);
+        backend.persist_intent_audit_record(&valid).await.unwrap();
+        let stored = backend
+            .get_intent_audit_record(valid.intent_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.provider_attributions, valid.provider_attributions);
+
+        let short = record(vec![
+            attribution("alpha", 6_000),
+            attribution("beta", 3_000),
+        ]);
+        let err = backend
+            .persist_intent_audit_record(&short)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DatabaseError::Serialization(_)), "{err:?}");
+        assert!(
+            backend
+                .get_intent_audit_record(short.intent_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let unnamed = record(vec![attribution(" ", 10_000)]);
+        let err = backend
+            .persist_intent_audit_record(&unnamed)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("provider_id"), "{err}");
+
+        let legacy = record(Vec::new());
+        backend.persist_intent_audit_record(&legacy).await.unwrap();
+        let conn = backend.connect().await.unwrap();
+        conn.execute(
+            "UPDATE intent_audit_records SET provider_attributions = '[]' WHERE intent_id = ?1",
+            libsql::params![legacy.intent_id.to_string()],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+        let stored = backend
+            .get_intent_audit_record(legacy.intent_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.provider_attributions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_connect_read_falls_back_to_primary_and_sees_writes() {
+        use crate::db::WorkspaceStore;
+
+        let backend = LibSqlBackend::new_memory().await.unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let doc = backend
+            .get_or_create_document_by_path("user-1", None, "notes/read.md")
+            .await
+            .unwrap();
+        backend
+            .update_document(doc.id, "committed content")
+            .await
+            .unwrap();
+
+        let baseline = backend.connections_created();
+        let conn = backend.connect_read().await.unwrap();
+        let mut rows = conn
+            .query(
+                "SELECT content FROM memory_documents WHERE id = ?1",
+                libsql::params![doc.id.to_string()],
+            )
+            .await
+            .unwrap();
+        let content: String = rows.next().await.unwrap().unwrap().get(0).unwrap();
+        assert_eq!(content, "committed content");
+        drop(rows);
+        drop(conn);
+        // Without a replica, reads share the primary pool.
+        assert_eq!(backend.connections_created(), baseline);
+
+        let read_back = backend
+            .get_document_by_path("user-1", None, "notes/read.md")
+            .await
+            .unwrap();
+        assert_eq!(read_back.content, "committed content");
+    }
+
+    #[tokio::test]
+    async fn test_list_due_routines_returns_only_due_in_fire_order() {
+        use chrono::{Duration, Utc};
+        use uuid::Uuid;
+
+        use crate::agent::routine::{
+            NotifyConfig, Routine, RoutineAction, RoutineGuardrails, Trigger,
+        };
+        use crate::db::RoutineStore;
+
+        let backend = LibSqlBackend::new_memory().await.unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let now = Utc::now();
+        let routine = |name: &str, enabled: bool, next_fire_at| Routine {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: String::new(),
+            user_id: "default".to_string(),
+            enabled,
+            trigger: Trigger::Cron {
+                schedule: "0 0 * * * *".to_string(),
+            },
+            action: RoutineAction::Lightweight {
+                prompt: "check".to_string(),
+                context_paths: Vec::new(),
+                max_tokens: 128,
+            },
+            guardrails: RoutineGuardrails::default(),
+            notify: NotifyConfig::default(),
+            last_run_at: None,
+            next_fire_at,
+            run_count: 0,
+            consecutive_failures: 0,
+            state: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        };
+        for r in [
+            routine("recent", true, Some(now - Duration::minutes(5))),
+            routine("future", true, Some(now + Duration::hours(1))),
+            routine("oldest", true, Some(now - Duration::hours(2))),
+            routine("disabled", false, Some(now - Duration::hours(3))),
+            routine("unscheduled", true, None),
+        ] {
+            backend.create_routine(&r).await.unwrap();
+        }
+
+        let due = backend.list_due_routines(now, 10).await.unwrap();
+        let names: Vec<&str> = due.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["oldest", "recent"]);
+
+        let limited = backend.list_due_routines(now, 1).await.unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].name, "oldest");
+    }
+
+    #[tokio::test]
+    async fn test_retry_sync_retries_until_success_or_budget_spent() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        use crate::db::libsql::{SyncReport, retry_sync};
+        use crate::error::DatabaseError;
+
+        let report = SyncReport {
+            frames_synced: 3,
+            replication_index: Some(42),
+        };
+        let backoff = std::time::Duration::from_millis(1);
+
+        let calls = AtomicU32::new(0);
+        let result = retry_sync(3, backoff, || {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if call < 3 {
+                    Err(DatabaseError::Pool("replica unreachable".to_string()))
+                } else {
+                    Ok(report)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), report);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result = retry_sync(2, backoff, || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(DatabaseError::Pool("replica unreachable".to_string())) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_sync_requires_remote_replica() {
+        let backend = LibSqlBackend::new_memory().await.unwrap();
+        assert!(backend.sync().await.is_err());
+        assert!(backend.last_sync_at().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remote_replica_sync_records_last_sync_at() {
+        let (Ok(url), Ok(auth_token)) = (
+            std::env::var("LIBSQL_TEST_REPLICA_URL"),
+            std::env::var("LIBSQL_TEST_REPLICA_AUTH_TOKEN"),
+        ) else {
+            eprintln!("Skipping replica sync test: LIBSQL_TEST_REPLICA_URL not set");
+            return;
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend =
+            LibSqlBackend::new_remote_replica(&dir.path().join("replica.db"), &url, &auth_token)
+                .await
+                .unwrap();
+        let before = chrono::Utc::now();
+        backend
+            .sync_with_retry(3, std::time::Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert!(backend.last_sync_at().unwrap() >= before);
+    }
+
+    #[tokio::test]
+    async fn test_intent_audit_batch_is_all_or_nothing() {
+        use crate::agent::{
+            IntentAuditRecord, ProviderRevenueAttribution, SignalFeeSchedule,
+            SignalProviderIdentity,
+        };
+        use crate::db::{IntentAuditStore, SettingsStore};
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_batch.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let base = chrono::Utc::now();
+        let record = |user_id: &str, offset_secs: i64| IntentAuditRecord {
+            intent_id: uuid::Uuid::new_v4(),
+            agent_id: None,
+            user_id: user_id.to_string(),
+            signal_hash: None,
+            intent_hash: "intent".to_string(),
+            receipt_id: uuid::Uuid::new_v4(),
+            receipt_hash: "receipt".to_string(),
+            verification_id: None,
+            verification_hash: None,
+            verification_status: None,
+            verification_level: None,
+            settlement_id: None,
+            settlement_hash: None,
+            provider_attributions: Vec::new(),
+            mirrored_pnl_usd: None,
+            revenue_share_fee_usd: None,
+            chain_hash: format!("chain-{offset_secs}"),
+            workspace_path: "audit/intents".to_string(),
+            created_at: base + chrono::Duration::seconds(offset_secs),
+        };
+
+        let batch: Vec<IntentAuditRecord> = (0..100).map(|i| record("importer", i)).collect();
+        backend
+            .persist_intent_audit_records_batch(&batch)
+            .await
+            .unwrap();
+        let stored = backend
+            .list_intent_audit_records("importer", 200)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 100);
+        let mirrored = backend
+            .get_setting("importer", IntentAuditRecord::SETTINGS_KEY)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(mirrored["chain_hash"], "chain-99");
+
+        let mut poisoned: Vec<IntentAuditRecord> = (0..10).map(|i| record("rollback", i)).collect();
+        poisoned[5].provider_attributions = vec![ProviderRevenueAttribution {
+            provider: SignalProviderIdentity {
+                provider_id: " ".to_string(),
+                payout_address: "0x9431Cf5DA0CE60664661341db650763B08286B18".to_string(),
+                display_name: None,
+            },
+            signal_id: "signal".to_string(),
+            signal_hash: "a".repeat(64),
+            attribution_weight_bps: 10_000,
+            fee_schedule: SignalFeeSchedule {
+                fixed_fee_bps: 10,
+                performance_fee_bps: 1_000,
+                max_fee_usd: rust_decimal::Decimal::new(250, 0),
+            },
+        }];
+        assert!(
+            backend
+                .persist_intent_audit_records_batch(&poisoned)
+                .await
+                .is_err()
+        );
+        assert!(
+            backend
+                .list_intent_audit_records("rollback", 20)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            backend
+                .get_setting("rollback", IntentAuditRecord::SETTINGS_KEY)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_intent_audit_verification_level_round_trips() {
+        use crate::agent::IntentAuditRecord;
+        use crate::db::IntentAuditStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_level.db"))
+            .await
+            .unwrap();
+        let conn = backend.connect().await.unwrap();
+        conn.execute_batch(crate::db::libsql_migrations::SCHEMA)
+            .await
+            .unwrap();
+        conn.execute_batch(crate::db::libsql_migrations::INTENT_AUDIT_LINEAGE_COLUMNS)
+            .await
+            .unwrap();
+        let legacy_id = uuid::Uuid::new_v4();
+        conn.execute(
+            "INSERT INTO intent_audit_records (intent_id, user_id, intent_hash, receipt_id, \
+             receipt_hash, chain_hash, workspace_path, created_at) \
+             VALUES (?1, 'levels', 'intent', ?2, 'receipt', 'chain-legacy', 'audit/intents', ?3)",
+            libsql::params![
+                legacy_id.to_string(),
+                uuid::Uuid::new_v4().to_string(),
+                crate::db::libsql::fmt_ts(&chrono::Utc::now()),
+            ],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+        backend.run_migrations().await.unwrap();
+
+        let legacy = backend
+            .get_intent_audit_record(legacy_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(legacy.verification_level, None);
+
+        let record = IntentAuditRecord {
+            intent_id: uuid::Uuid::new_v4(),
+            agent_id: None,
+            user_id: "levels".to_string(),
+            signal_hash: None,
+            intent_hash: "intent".to_string(),
+            receipt_id: uuid::Uuid::new_v4(),
+            receipt_hash: "receipt".to_string(),
+            verification_id: None,
+            verification_hash: None,
+            verification_status: None,
+            verification_level: None,
+            settlement_id: None,
+            settlement_hash: None,
+            provider_attributions: Vec::new(),
+            mirrored_pnl_usd: None,
+            revenue_share_fee_usd: None,
+            chain_hash: "chain".to_string(),
+            workspace_path: "audit/intents".to_string(),
+            created_at: chrono::Utc::now(),
+        }
+        .with_verification_level("primary_plus_signed_fallback");
+        backend.persist_intent_audit_record(&record).await.unwrap();
+
+        let stored = backend
+            .get_intent_audit_record(record.intent_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.verification_level.as_deref(),
+            Some("primary_plus_signed_fallback")
+        );
+        let listed = backend
+            .list_intent_audit_records("levels", 10)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_job_state_checked_rejects_illegal_transitions() {
+        use crate::context::{JobContext, JobState};
+        use crate::db::JobStore;
+        use crate::error::DatabaseError;
+
+        let backend = LibSqlBackend::new_memory().await.unwrap();
+        backend.run_migrations().await.unwrap();
+        let job = JobContext::new("checked", "transition guard");
+        backend.save_job(&job).await.unwrap();
+
+        backend
+            .update_job_state_checked(job.job_id, JobState::InProgress, None)
+            .await
+            .unwrap();
+        backend
+            .update_job_state_checked(job.job_id, JobState::Completed, None)
+            .await
+            .unwrap();
+        let err = backend
+            .update_job_state_checked(job.job_id, JobState::InProgress, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DatabaseError::Constraint(_)), "{err:?}");
+        let stored = backend.get_job(job.job_id).await.unwrap().unwrap();
+        assert_eq!(stored.state, JobState::Completed);
+
+        let missing = backend
+            .update_job_state_checked(uuid::Uuid::new_v4(), JobState::InProgress, None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(missing, DatabaseError::NotFound { .. }),
+            "{missing:?}"
+        );
+
+        let conn = backend.connect().await.unwrap();
+        conn.execute(
+            "UPDATE agent_jobs SET status = 'bogus' WHERE id = ?1",
+            libsql::params![job.job_id.to_string()],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+        assert!(matches!(
+            backend.get_job(job.job_id).await,
+            Err(DatabaseError::Serialization(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_conversation_summary_upsert_replaces_watermark() {
+        use crate::db::ConversationStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_summaries.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let conversation = backend
+            .create_conversation("web", "user1", None)
+            .await
+            .unwrap();
+        assert!(backend.get_summary(conversation).await.unwrap().is_none());
+
+        let first = backend
+            .add_conversation_message(conversation, "user", "hello")
+            .await
+            .unwrap();
+        backend
+            .upsert_summary(conversation, "User said hello.", 4, first)
+            .await
+            .unwrap();
+        let stored = backend.get_summary(conversation).await.unwrap().unwrap();
+        assert_eq!(stored.conversation_id, conversation);
+        assert_eq!(stored.summary, "User said hello.");
+        assert_eq!(stored.token_count, 4);
+        assert_eq!(stored.covered_through_message_id, first);
+
+        let second = backend
+            .add_conversation_message(conversation, "assistant", "hi there")
+            .await
+            .unwrap();
+        backend
+            .upsert_summary(conversation, "Greetings exchanged.", 3, second)
+            .await
+            .unwrap();
+        let replaced = backend.get_summary(conversation).await.unwrap().unwrap();
+        assert_eq!(replaced.summary, "Greetings exchanged.");
+        assert_eq!(replaced.token_count, 3);
+        assert_eq!(replaced.covered_through_message_id, second);
+        assert!(replaced.updated_at >= stored.updated_at);
+
+        let conn = backend.connect().await.unwrap();
+        let mut rows = conn
+            .query("SELECT COUNT(*) FROM conversation_summaries", ())
+            .await
+            .unwrap();
+        let row = rows.next().await.unwrap().unwrap();
+        let count: i64 = row.get(0).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_should_skip_tool_backs_off_after_repeated_failures() {
+        use crate::db::{TOOL_BACKOFF_BASE, TOOL_BACKOFF_THRESHOLD, ToolFailureStore};
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_tool_backoff.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        // Whole seconds, so the stored millisecond timestamps round-trip exactly.
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        for i in 0..TOOL_BACKOFF_THRESHOLD {
+            assert!(
+                backend
+                    .should_skip_tool("http", "user1", start)
+                    .await
+                    .unwrap()
+                    .is_none()
+            );
+            backend
+                .record_tool_failure_streak("http", "user1", start + chrono::Duration::seconds(i))
+                .await
+                .unwrap();
+        }
+        let last = start + chrono::Duration::seconds(TOOL_BACKOFF_THRESHOLD - 1);
+        let first_backoff = backend
+            .should_skip_tool("http", "user1", last)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first_backoff, TOOL_BACKOFF_BASE.to_std().unwrap());
+
+        // One more failure doubles the backoff.
+        backend
+            .record_tool_failure_streak("http", "user1", last)
+            .await
+            .unwrap();
+        let second_backoff = backend
+            .should_skip_tool("http", "user1", last)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second_backoff, first_backoff * 2);
+
+        // Backoff expires with time and is scoped to the user.
+        assert!(
+            backend
+                .should_skip_tool("http", "user1", last + TOOL_BACKOFF_BASE * 2)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            backend
+                .should_skip_tool("http", "user2", last)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // A success clears the streak entirely.
+        backend.record_tool_success("http", "user1").await.unwrap();
+        assert!(
+            backend
+                .should_skip_tool("http", "user1", last)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        backend
+            .record_tool_failure_streak("http", "user1", last)
+            .await
+            .unwrap();
+        assert!(
+            backend
+                .should_skip_tool("http", "user1", last)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_skip_tool_unseen_tool_is_none() {
+        use crate::db::ToolFailureStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_tool_unseen.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        assert!(
+            backend
+                .should_skip_tool("never_ran", "user1", chrono::Utc::now())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_failure_streak_resets_outside_window() {
+        use crate::db::{TOOL_BACKOFF_THRESHOLD, TOOL_BACKOFF_WINDOW, ToolFailureStore};
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_tool_window.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let start = chrono::Utc::now();
+        for _ in 0..TOOL_BACKOFF_THRESHOLD - 1 {
+            backend
+                .record_tool_failure_streak("shell", "user1", start)
+                .await
+                .unwrap();
+        }
+        // A stale streak does not combine with a fresh failure.
+        let later = start + TOOL_BACKOFF_WINDOW + chrono::Duration::seconds(1);
+        backend
+            .record_tool_failure_streak("shell", "user1", later)
+            .await
+            .unwrap();
+        assert!(
+            backend
+                .should_skip_tool("shell", "user1", later)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    fn quota_test_record(user_id: &str, i: i64) -> crate::agent::IntentAuditRecord {
+        crate::agent::IntentAuditRecord {
+            intent_id: uuid::Uuid::new_v4(),
+            agent_id: None,
+            user_id: user_id.to_string(),
+            signal_hash: None,
+            intent_hash: format!("intent-{i}"),
+            receipt_id: uuid::Uuid::new_v4(),
+            receipt_hash: format!("receipt-{i}"),
+            verification_id: None,
+            verification_hash: None,
+            verification_status: None,
+            verification_level: None,
+            settlement_id: None,
+            settlement_hash: None,
+            provider_attributions: Vec::new(),
+            mirrored_pnl_usd: None,
+            revenue_share_fee_usd: None,
+            chain_hash: format!("chain-{i}"),
+            workspace_path: format!("audit/intents/{i}"),
+            created_at: chrono::DateTime::from_timestamp(1_700_000_000 + i, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_intent_audit_quota_prune_keeps_count_at_cap() {
+        use crate::config::{IntentAuditQuota, IntentAuditQuotaPolicy};
+        use crate::db::IntentAuditStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_prune.db"))
+            .await
+            .unwrap()
+            .with_intent_audit_quota(Some(IntentAuditQuota {
+                max_records_per_user: 3,
+                policy: IntentAuditQuotaPolicy::PruneOldest,
+            }));
+        backend.run_migrations().await.unwrap();
+
+        let mut records = Vec::new();
+        for i in 0..5 {
+            records.push(
+                backend
+                    .append_intent_audit_record(&quota_test_record("pruned", i))
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert_eq!(
+            backend.count_intent_audit_records("pruned").await.unwrap(),
+            3
+        );
+
+        let kept: Vec<_> = backend
+            .list_intent_audit_records("pruned", 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.intent_id)
+            .collect();
+        assert_eq!(
+            kept,
+            vec![
+                records[4].intent_id,
+                records[3].intent_id,
+                records[2].intent_id
+            ]
+        );
+
+        // The kept records still verify from the newest pruned record.
+        let report = backend.verify_chain("pruned").await.unwrap();
+        assert!(report.is_intact(), "{report:?}");
+        assert_eq!(report.records_checked, 3);
+        assert_eq!(
+            report.checkpoint_chain_hash.as_deref(),
+            Some(records[1].chain_hash.as_str())
+        );
+
+        // Rewriting a kept record does not count against the cap.
+        let mut rewritten = records[2].clone();
+        rewritten.receipt_hash = "receipt-rewritten".to_string();
+        backend
+            .persist_intent_audit_record(&rewritten)
+            .await
+            .unwrap();
+        assert_eq!(
+            backend.count_intent_audit_records("pruned").await.unwrap(),
+            3
+        );
+        assert!(
+            backend
+                .get_intent_audit_record(records[2].intent_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_intent_audit_quota_reject_errors_over_limit() {
+        use crate::config::{IntentAuditQuota, IntentAuditQuotaPolicy};
+        use crate::db::IntentAuditStore;
+        use crate::error::DatabaseError;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_audit_reject.db"))
+            .await
+            .unwrap()
+            .with_intent_audit_quota(Some(IntentAuditQuota {
+                max_records_per_user: 2,
+                policy: IntentAuditQuotaPolicy::Reject,
+            }));
+        backend.run_migrations().await.unwrap();
+
+        for i in 0..2 {
+            backend
+                .persist_intent_audit_record(&quota_test_record("capped", i))
+                .await
+                .unwrap();
+        }
+        let over = quota_test_record("capped", 2);
+        let err = backend
+            .persist_intent_audit_record(&over)
+            .await
+            .expect_err("third record exceeds the quota");
+        assert!(
+            matches!(err, DatabaseError::QuotaExceeded { ref user_id, limit: 2 } if user_id == "capped"),
+            "{err:?}"
+        );
+        assert_eq!(
+            backend.count_intent_audit_records("capped").await.unwrap(),
+            2
+        );
+        assert!(
+            backend
+                .get_intent_audit_record(over.intent_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // A batch that would overflow the quota is refused as a whole.
+        let batch: Vec<_> = (0..3).map(|i| quota_test_record("batched", i)).collect();
+        let err = backend
+            .persist_intent_audit_records_batch(&batch)
+            .await
+            .expect_err("third batched record exceeds the quota");
+        assert!(
+            matches!(err, DatabaseError::QuotaExceeded { limit: 2, .. }),
+            "{err:?}"
+        );
+        assert_eq!(
+            backend.count_intent_audit_records("batched").await.unwrap(),
+            0
+        );
+
+        // Other users have their own allowance.
+        backend
+            .persist_intent_audit_record(&quota_test_record("other", 0))
+            .await
+            .unwrap();
+        assert_eq!(
+            backend.count_intent_audit_records("other").await.unwrap(),
+            1
+        );
+        assert_eq!(
+            backend.count_intent_audit_records("nobody").await.unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_conversations_removes_old_messages_and_empty_shells() {
+        use crate::db::ConversationStore;
+        use crate::db::libsql::fmt_ts;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("test_prune.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let old = chrono::Utc::now() - chrono::Duration::days(60);
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(30);
+
+        // Fully stale conversation: both messages and the shell go.
+        let stale = backend
+            .create_conversation("web", "alice", None)
+            .await
+            .unwrap();
+        backend
+            .add_conversation_message(stale, "user", "old question")
+            .await
+            .unwrap();
+        backend
+            .add_conversation_message(stale, "assistant", "old answer")
+            .await
+            .unwrap();
+        // Mixed conversation: the old message goes, the recent one stays.
+        let mixed = backend
+            .create_conversation("web", "alice", None)
+            .await
+            .unwrap();
+        let old_msg = backend
+            .add_conversation_message(mixed, "user", "old")
+            .await
+            .unwrap();
+        backend
+            .add_conversation_message(mixed, "user", "recent")
+            .await
+            .unwrap();
+        // Another user's stale conversation.
+        let other = backend
+            .create_conversation("web", "bob", None)
+            .await
+            .unwrap();
+        backend
+            .add_conversation_message(other, "user", "bob's old message")
+            .await
+            .unwrap();
+
+        let conn = backend.connect().await.unwrap();
+        for id in [stale, other] {
+            conn.execute(
+                "UPDATE conversation_messages SET created_at = ?1 WHERE conversation_id = ?2",
+                libsql::params![fmt_ts(&old), id.to_string()],
+            )
+            .await
+            .unwrap();
+            conn.execute(
+                "UPDATE conversations SET last_activity = ?1 WHERE id = ?2",
+                libsql::params![fmt_ts(&old), id.to_string()],
+            )
+            .await
+            .unwrap();
+        }
+        conn.execute(
+            "UPDATE conversation_messages SET created_at = ?1 WHERE id = ?2",
+            libsql::params![fmt_ts(&old), old_msg.to_string()],
+        )
+        .await
+        .unwrap();
+
+        // Scoped to alice: three messages plus the stale shell.
+        let removed = backend
+            .prune_conversations_older_than(cutoff, Some("alice"))
+            .await
+            .unwrap();
+        assert_eq!(removed, 4);
+        assert!(
+            !backend
+                .conversation_belongs_to_user(stale, "alice")
+                .await
+                .unwrap()
+        );
+        let remaining = backend.list_conversation_messages(mixed).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].content, "recent");
+        assert_eq!(
+            backend
+                .list_conversation_messages(other)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Unscoped: bob's message and shell go; alice's recent data stays.
+        let removed = backend
+            .prune_conversations_older_than(cutoff, None)
+            .await
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert!(
+            !backend
+                .conversation_belongs_to_user(other, "bob")
+                .await
+                .unwrap()
+        );
+        assert!(
+            backend
+                .conversation_belongs_to_user(mixed, "alice")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            backend
+                .prune_conversations_older_than(cutoff, None)
+                .await
+                .unwrap(),
+            0
+        );
     }
 }
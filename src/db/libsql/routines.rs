@@ -71,7 +71,7 @@ impl RoutineStore for LibSqlBackend {
     }
 
     async fn get_routine(&self, id: Uuid) -> Result<Option<Routine>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 &format!("SELECT {} FROM routines WHERE id = ?1", ROUTINE_COLUMNS),
@@ -95,7 +95,7 @@ impl RoutineStore for LibSqlBackend {
         user_id: &str,
         name: &str,
     ) -> Result<Option<Routine>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 &format!(
@@ -118,7 +118,7 @@ impl RoutineStore for LibSqlBackend {
     }
 
     async fn list_routines(&self, user_id: &str) -> Result<Vec<Routine>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 &format!(
@@ -190,6 +190,34 @@ impl RoutineStore for LibSqlBackend {
         Ok(routines)
     }
 
+    async fn list_due_routines(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Routine>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {} FROM routines WHERE enabled = 1 AND next_fire_at IS NOT NULL AND next_fire_at <= ?1 ORDER BY next_fire_at ASC LIMIT ?2",
+                    ROUTINE_COLUMNS
+                ),
+                params![fmt_ts(&now), limit],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let mut routines = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            routines.push(row_to_routine_libsql(&row)?);
+        }
+        Ok(routines)
+    }
+
     async fn update_routine(&self, routine: &Routine) -> Result<(), DatabaseError> {
         let conn = self.connect().await?;
         let trigger_type = routine.trigger.type_tag();
@@ -345,7 +373,7 @@ impl RoutineStore for LibSqlBackend {
         routine_id: Uuid,
         limit: i64,
     ) -> Result<Vec<RoutineRun>, DatabaseError> {
-        let conn = self.connect().await?;
+        let conn = self.connect_read().await?;
         let mut rows = conn
             .query(
                 &format!(
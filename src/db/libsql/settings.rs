@@ -5,12 +5,13 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use libsql::params;
 
-use super::{LibSqlBackend, fmt_ts, get_i64, get_json, get_text, get_ts};
+use super::{LibSqlBackend, fmt_ts, get_i64, get_json, get_text, get_ts, with_write_retry};
 use crate::db::SettingsStore;
 use crate::error::DatabaseError;
 use crate::history::SettingRow;
 
 use chrono::Utc;
+use uuid::Uuid;
 
 #[async_trait]
 impl SettingsStore for LibSqlBackend {
@@ -22,7 +23,7 @@ impl SettingsStore for LibSqlBackend {
         let conn = self.connect().await?;
         let mut rows = conn
             .query(
-                "SELECT value FROM settings WHERE user_id = ?1 AND key = ?2",
+                "SELECT value FROM settings WHERE user_id = ?1 AND agent_id IS NULL AND key = ?2",
                 params![user_id, key],
             )
             .await
@@ -46,7 +47,7 @@ impl SettingsStore for LibSqlBackend {
         let conn = self.connect().await?;
         let mut rows = conn
             .query(
-                "SELECT key, value, updated_at FROM settings WHERE user_id = ?1 AND key = ?2",
+                "SELECT key, value, updated_at FROM settings WHERE user_id = ?1 AND agent_id IS NULL AND key = ?2",
                 params![user_id, key],
             )
             .await
@@ -74,18 +75,21 @@ impl SettingsStore for LibSqlBackend {
     ) -> Result<(), DatabaseError> {
         let conn = self.connect().await?;
         let now = fmt_ts(&Utc::now());
-        conn.execute(
-            r#"
-                INSERT INTO settings (user_id, key, value, updated_at)
-                VALUES (?1, ?2, ?3, ?4)
-                ON CONFLICT (user_id, key) DO UPDATE SET
-                    value = excluded.value,
-                    updated_at = ?4
-                "#,
-            params![user_id, key, value.to_string(), now],
-        )
-        .await
-        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        with_write_retry(|| async {
+            conn.execute(
+                r#"
+                    INSERT INTO settings (user_id, key, value, updated_at)
+                    VALUES (?1, ?2, ?3, ?4)
+                    ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
+                        value = excluded.value,
+                        updated_at = ?4
+                    "#,
+                params![user_id, key, value.to_string(), now.as_str()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))
+        })
+        .await?;
         Ok(())
     }
 
@@ -93,7 +97,7 @@ impl SettingsStore for LibSqlBackend {
         let conn = self.connect().await?;
         let count = conn
             .execute(
-                "DELETE FROM settings WHERE user_id = ?1 AND key = ?2",
+                "DELETE FROM settings WHERE user_id = ?1 AND agent_id IS NULL AND key = ?2",
                 params![user_id, key],
             )
             .await
@@ -105,7 +109,7 @@ impl SettingsStore for LibSqlBackend {
         let conn = self.connect().await?;
         let mut rows = conn
             .query(
-                "SELECT key, value, updated_at FROM settings WHERE user_id = ?1 ORDER BY key",
+                "SELECT key, value, updated_at FROM settings WHERE user_id = ?1 AND agent_id IS NULL ORDER BY key",
                 params![user_id],
             )
             .await
@@ -133,7 +137,7 @@ impl SettingsStore for LibSqlBackend {
         let conn = self.connect().await?;
         let mut rows = conn
             .query(
-                "SELECT key, value FROM settings WHERE user_id = ?1",
+                "SELECT key, value FROM settings WHERE user_id = ?1 AND agent_id IS NULL",
                 params![user_id],
             )
             .await
@@ -150,6 +154,64 @@ impl SettingsStore for LibSqlBackend {
         Ok(map)
     }
 
+    async fn get_all_settings_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+    ) -> Result<HashMap<String, serde_json::Value>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT key, value FROM settings WHERE user_id = ?1 AND agent_id = ?2",
+                params![user_id, agent_id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let mut map = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            map.insert(get_text(&row, 0), get_json(&row, 1));
+        }
+        Ok(map)
+    }
+
+    async fn set_setting_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        let now = fmt_ts(&Utc::now());
+        with_write_retry(|| async {
+            conn.execute(
+                r#"
+                    INSERT INTO settings (user_id, agent_id, key, value, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT (user_id, agent_id, key) DO UPDATE SET
+                        value = excluded.value,
+                        updated_at = ?5
+                    "#,
+                params![
+                    user_id,
+                    agent_id.to_string(),
+                    key,
+                    value.to_string(),
+                    now.as_str()
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))
+        })
+        .await?;
+        Ok(())
+    }
+
     async fn set_all_settings(
         &self,
         user_id: &str,
@@ -167,7 +229,7 @@ impl SettingsStore for LibSqlBackend {
                     r#"
                     INSERT INTO settings (user_id, key, value, updated_at)
                     VALUES (?1, ?2, ?3, ?4)
-                    ON CONFLICT (user_id, key) DO UPDATE SET
+                    ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
                         value = excluded.value,
                         updated_at = ?4
                     "#,
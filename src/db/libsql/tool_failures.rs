@@ -6,10 +6,10 @@ use uuid::Uuid;
 
 use super::{LibSqlBackend, fmt_ts, get_i64, get_opt_text, get_text, get_ts};
 use crate::agent::BrokenTool;
-use crate::db::ToolFailureStore;
+use crate::db::{TOOL_BACKOFF_WINDOW, ToolFailureStore, tool_failure_backoff};
 use crate::error::DatabaseError;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 #[async_trait]
 impl ToolFailureStore for LibSqlBackend {
@@ -94,4 +94,80 @@ impl ToolFailureStore for LibSqlBackend {
         .map_err(|e| DatabaseError::Query(e.to_string()))?;
         Ok(())
     }
+
+    async fn record_tool_failure_streak(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        let window_start = fmt_ts(&(now - TOOL_BACKOFF_WINDOW));
+        conn.execute(
+            r#"
+                INSERT INTO tool_failure_streaks
+                    (tool_name, user_id, consecutive_failures, first_failure_at, last_failure_at)
+                VALUES (?1, ?2, 1, ?3, ?3)
+                ON CONFLICT (tool_name, user_id) DO UPDATE SET
+                    consecutive_failures = CASE
+                        WHEN tool_failure_streaks.last_failure_at >= ?4
+                            THEN tool_failure_streaks.consecutive_failures + 1
+                        ELSE 1
+                    END,
+                    first_failure_at = CASE
+                        WHEN tool_failure_streaks.last_failure_at >= ?4
+                            THEN tool_failure_streaks.first_failure_at
+                        ELSE ?3
+                    END,
+                    last_failure_at = ?3
+                "#,
+            params![tool_name, user_id, fmt_ts(&now), window_start],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn record_tool_success(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "DELETE FROM tool_failure_streaks WHERE tool_name = ?1 AND user_id = ?2",
+            params![tool_name, user_id],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn should_skip_tool(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<std::time::Duration>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT consecutive_failures, last_failure_at
+                FROM tool_failure_streaks
+                WHERE tool_name = ?1 AND user_id = ?2
+                "#,
+                params![tool_name, user_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        Ok(tool_failure_backoff(get_i64(&row, 0), get_ts(&row, 1), now))
+    }
 }
@@ -3,7 +3,8 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-use libsql::params;
+use futures::{Stream, TryStreamExt};
+use libsql::{Connection, params};
 use uuid::Uuid;
 
 use super::{
@@ -11,7 +12,7 @@ use super::{
     row_to_memory_document,
 };
 use crate::db::WorkspaceStore;
-use crate::error::WorkspaceError;
+use crate::error::{DatabaseError, WorkspaceError};
 use crate::workspace::{
     MemoryChunk, MemoryDocument, RankedResult, SearchConfig, SearchResult, WorkspaceEntry,
     reciprocal_rank_fusion,
@@ -19,20 +20,58 @@ use crate::workspace::{
 
 use chrono::Utc;
 
-#[async_trait]
-impl WorkspaceStore for LibSqlBackend {
-    async fn get_document_by_path(
+impl LibSqlBackend {
+    /// Stream a user's documents, newest first, decoding one row per poll.
+    ///
+    /// Unlike [`WorkspaceStore::list_documents`] this never holds the whole
+    /// result set in memory; the connection returns to the pool when the
+    /// stream is exhausted or dropped.
+    pub fn stream_memory_documents(
         &self,
         user_id: &str,
         agent_id: Option<Uuid>,
+    ) -> impl Stream<Item = Result<MemoryDocument, DatabaseError>> + Send + '_ {
+        let user_id = user_id.to_string();
+        let agent_id_str = agent_id.map(|id| id.to_string());
+        futures::stream::once(async move {
+            let conn = self.connect_read().await?;
+            let rows = conn
+                .query(
+                    r#"
+                    SELECT id, user_id, agent_id, path, content,
+                           created_at, updated_at, metadata
+                    FROM memory_documents
+                    WHERE user_id = ?1 AND agent_id IS ?2
+                    ORDER BY updated_at DESC
+                    "#,
+                    params![user_id, agent_id_str],
+                )
+                .await
+                .map_err(|e| DatabaseError::Query(e.to_string()))?;
+            Ok::<_, DatabaseError>((conn, rows))
+        })
+        .map_ok(|(conn, rows)| {
+            futures::stream::try_unfold((conn, rows), |(conn, mut rows)| async move {
+                match rows
+                    .next()
+                    .await
+                    .map_err(|e| DatabaseError::Query(e.to_string()))?
+                {
+                    Some(row) => Ok(Some((row_to_memory_document(&row), (conn, rows)))),
+                    None => Ok(None),
+                }
+            })
+        })
+        .try_flatten()
+    }
+
+    /// Look up one document by path on an already checked-out connection.
+    async fn query_document_by_path(
+        conn: &Connection,
+        user_id: &str,
+        agent_id: Option<Uuid>,
         path: &str,
     ) -> Result<MemoryDocument, WorkspaceError> {
-        let conn = self
-            .connect()
-            .await
-            .map_err(|e| WorkspaceError::SearchFailed {
-                reason: e.to_string(),
-            })?;
         let agent_id_str = agent_id.map(|id| id.to_string());
         let mut rows = conn
             .query(
@@ -62,10 +101,28 @@ impl WorkspaceStore for LibSqlBackend {
             }),
         }
     }
+}
+
+#[async_trait]
+impl WorkspaceStore for LibSqlBackend {
+    async fn get_document_by_path(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path: &str,
+    ) -> Result<MemoryDocument, WorkspaceError> {
+        let conn = self
+            .connect_read()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: e.to_string(),
+            })?;
+        Self::query_document_by_path(&conn, user_id, agent_id, path).await
+    }
 
     async fn get_document_by_id(&self, id: Uuid) -> Result<MemoryDocument, WorkspaceError> {
         let conn = self
-            .connect()
+            .connect_read()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
@@ -133,7 +190,8 @@ impl WorkspaceStore for LibSqlBackend {
             reason: format!("Insert failed: {}", e),
         })?;
 
-        self.get_document_by_path(user_id, agent_id, path).await
+        // Re-read on the primary: a read replica may not have the row yet.
+        Self::query_document_by_path(&conn, user_id, agent_id, path).await
     }
 
     async fn update_document(&self, id: Uuid, content: &str) -> Result<(), WorkspaceError> {
@@ -189,7 +247,7 @@ impl WorkspaceStore for LibSqlBackend {
         directory: &str,
     ) -> Result<Vec<WorkspaceEntry>, WorkspaceError> {
         let conn = self
-            .connect()
+            .connect_read()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
@@ -293,7 +351,7 @@ impl WorkspaceStore for LibSqlBackend {
         agent_id: Option<Uuid>,
     ) -> Result<Vec<String>, WorkspaceError> {
         let conn = self
-            .connect()
+            .connect_read()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
@@ -328,7 +386,7 @@ impl WorkspaceStore for LibSqlBackend {
         agent_id: Option<Uuid>,
     ) -> Result<Vec<MemoryDocument>, WorkspaceError> {
         let conn = self
-            .connect()
+            .connect_read()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
@@ -451,7 +509,7 @@ impl WorkspaceStore for LibSqlBackend {
         limit: usize,
     ) -> Result<Vec<MemoryChunk>, WorkspaceError> {
         let conn = self
-            .connect()
+            .connect_read()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
@@ -503,7 +561,7 @@ impl WorkspaceStore for LibSqlBackend {
         config: &SearchConfig,
     ) -> Result<Vec<SearchResult>, WorkspaceError> {
         let conn = self
-            .connect()
+            .connect_read()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
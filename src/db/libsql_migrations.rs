@@ -1,7 +1,8 @@
 //! SQLite-dialect migrations for the libSQL/Turso backend.
 //!
-//! Consolidates all PostgreSQL migrations (V1-V8) into a single SQLite-compatible
-//! schema. Run once on database creation; idempotent via `IF NOT EXISTS`.
+//! [`SCHEMA`] consolidates all PostgreSQL migrations (V1-V8) into a single
+//! SQLite-compatible baseline and is migration 1. Later changes are appended
+//! to [`migrations`] and recorded in `schema_migrations` once applied.
 
 /// Consolidated schema for libSQL.
 ///
@@ -712,3 +713,135 @@ INSERT OR IGNORE INTO leak_detection_patterns (id, name, pattern, severity, acti
     ('550e8400-e29b-41d4-a716-446655440012', 'high_entropy_hex', '(?<![a-fA-F0-9])[a-fA-F0-9]{64}(?![a-fA-F0-9])', 'medium', 'warn', 1, datetime('now'));
 
 "#;
+
+/// Tracks which [`Migration`] versions have been applied to this database.
+pub const SCHEMA_MIGRATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version INTEGER PRIMARY KEY,
+    applied_at TEXT NOT NULL
+);
+"#;
+
+/// Copytrade lineage and settlement columns on `intent_audit_records`.
+pub const INTENT_AUDIT_LINEAGE_COLUMNS: &str = r#"
+ALTER TABLE intent_audit_records ADD COLUMN signal_hash TEXT;
+ALTER TABLE intent_audit_records ADD COLUMN settlement_id TEXT;
+ALTER TABLE intent_audit_records ADD COLUMN settlement_hash TEXT;
+ALTER TABLE intent_audit_records ADD COLUMN provider_attributions TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE intent_audit_records ADD COLUMN mirrored_pnl_usd TEXT;
+ALTER TABLE intent_audit_records ADD COLUMN revenue_share_fee_usd TEXT;
+"#;
+
+/// Composite index backing the due-routines scheduler query.
+pub const ROUTINES_DUE_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_routines_enabled_next_fire ON routines(enabled, next_fire_at);
+"#;
+
+/// Verification assurance level captured on `intent_audit_records`.
+pub const INTENT_AUDIT_VERIFICATION_LEVEL_COLUMN: &str =
+    "ALTER TABLE intent_audit_records ADD COLUMN verification_level TEXT;";
+
+/// Rolling conversation summaries, one per conversation.
+pub const CONVERSATION_SUMMARIES: &str = r#"
+CREATE TABLE IF NOT EXISTS conversation_summaries (
+    conversation_id TEXT PRIMARY KEY REFERENCES conversations(id) ON DELETE CASCADE,
+    summary TEXT NOT NULL,
+    token_count INTEGER NOT NULL,
+    covered_through_message_id TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+"#;
+
+/// Per-user consecutive tool failures backing tool backoff.
+pub const TOOL_FAILURE_STREAKS: &str = r#"
+CREATE TABLE IF NOT EXISTS tool_failure_streaks (
+    tool_name TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    consecutive_failures INTEGER NOT NULL,
+    first_failure_at TEXT NOT NULL,
+    last_failure_at TEXT NOT NULL,
+    PRIMARY KEY (tool_name, user_id)
+);
+"#;
+
+/// Keyset pagination index for intent audit listings.
+pub const INTENT_AUDIT_USER_CREATED_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_intent_audit_user_created ON intent_audit_records(user_id, created_at, intent_id);
+"#;
+
+/// Rebuilds `settings` (primary key `(user_id, key)`) with a nullable
+/// `agent_id`. NULL rows are user-global; non-NULL rows override the global
+/// value for that agent. Existing rows become user-global.
+pub const SETTINGS_AGENT_SCOPE: &str = r#"
+ALTER TABLE settings RENAME TO settings_legacy;
+DROP INDEX IF EXISTS idx_settings_user;
+CREATE TABLE settings (
+    user_id TEXT NOT NULL,
+    agent_id TEXT,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+INSERT INTO settings (user_id, agent_id, key, value, updated_at)
+    SELECT user_id, NULL, key, value, updated_at FROM settings_legacy;
+DROP TABLE settings_legacy;
+CREATE INDEX IF NOT EXISTS idx_settings_user ON settings(user_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_settings_user_agent_key ON settings(user_id, agent_id, key);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_settings_user_global_key ON settings(user_id, key) WHERE agent_id IS NULL;
+"#;
+
+/// Session snapshot column restored by the frontdoor on startup.
+pub const FRONTDOOR_SESSIONS_PAYLOAD: &str = r#"
+ALTER TABLE frontdoor_sessions ADD COLUMN payload TEXT NOT NULL DEFAULT '{}';
+CREATE INDEX IF NOT EXISTS idx_frontdoor_sessions_wallet ON frontdoor_sessions(wallet_address);
+"#;
+
+/// A forward-only schema step, applied at most once per database.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: i64,
+    pub up_sql: &'static str,
+}
+
+/// All migrations in application order. Versions must be strictly increasing;
+/// never edit or renumber an entry once it has shipped.
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            up_sql: SCHEMA,
+        },
+        Migration {
+            version: 2,
+            up_sql: INTENT_AUDIT_LINEAGE_COLUMNS,
+        },
+        Migration {
+            version: 3,
+            up_sql: ROUTINES_DUE_INDEX,
+        },
+        Migration {
+            version: 4,
+            up_sql: INTENT_AUDIT_VERIFICATION_LEVEL_COLUMN,
+        },
+        Migration {
+            version: 5,
+            up_sql: CONVERSATION_SUMMARIES,
+        },
+        Migration {
+            version: 6,
+            up_sql: TOOL_FAILURE_STREAKS,
+        },
+        Migration {
+            version: 7,
+            up_sql: INTENT_AUDIT_USER_CREATED_INDEX,
+        },
+        Migration {
+            version: 8,
+            up_sql: SETTINGS_AGENT_SCOPE,
+        },
+        Migration {
+            version: 9,
+            up_sql: FRONTDOOR_SESSIONS_PAYLOAD,
+        },
+    ]
+}
@@ -27,14 +27,14 @@ use rust_decimal::Decimal;
 use uuid::Uuid;
 
 use crate::agent::BrokenTool;
-use crate::agent::IntentAuditRecord;
 use crate::agent::routine::{Routine, RoutineRun, RunStatus};
+use crate::agent::{IntentAuditRecord, ProviderRevenueAttribution};
 use crate::context::{ActionRecord, JobContext, JobState};
 use crate::error::DatabaseError;
 use crate::error::WorkspaceError;
 use crate::history::{
-    ConversationMessage, ConversationSummary, JobEventRecord, LlmCallRecord, SandboxJobRecord,
-    SandboxJobSummary, SettingRow,
+    ConversationMessage, ConversationRollingSummary, ConversationSummary, JobEventRecord,
+    LlmCallRecord, SandboxJobRecord, SandboxJobSummary, SettingRow,
 };
 use crate::workspace::{MemoryChunk, MemoryDocument, WorkspaceEntry};
 use crate::workspace::{SearchConfig, SearchResult};
@@ -63,14 +63,35 @@ pub async fn connect_from_config(
                         "LIBSQL_AUTH_TOKEN required when LIBSQL_URL is set".to_string(),
                     )
                 })?;
-                libsql::LibSqlBackend::new_remote_replica(db_path, url, token.expose_secret())
+                let backend =
+                    libsql::LibSqlBackend::new_remote_replica(db_path, url, token.expose_secret())
+                        .await
+                        .map_err(|e| DatabaseError::Pool(e.to_string()))?;
+                if let Err(e) = backend
+                    .sync_with_retry(
+                        libsql::STARTUP_SYNC_MAX_RETRIES,
+                        libsql::STARTUP_SYNC_BACKOFF,
+                    )
                     .await
-                    .map_err(|e| DatabaseError::Pool(e.to_string()))?
+                {
+                    tracing::warn!("Initial remote replica sync failed: {}", e);
+                }
+                match config.libsql_read_replica_path {
+                    Some(ref read_path) => {
+                        backend
+                            .with_read_replica(read_path, url, token.expose_secret())
+                            .await?
+                    }
+                    None => backend,
+                }
             } else {
                 libsql::LibSqlBackend::new_local(db_path)
                     .await
                     .map_err(|e| DatabaseError::Pool(e.to_string()))?
             };
+            let backend = backend
+                .with_max_pool_size(config.pool_size)
+                .with_intent_audit_quota(config.intent_audit_quota);
             backend.run_migrations().await?;
             Ok(Arc::new(backend))
         }
@@ -159,6 +180,30 @@ pub trait ConversationStore: Send + Sync {
         conversation_id: Uuid,
         user_id: &str,
     ) -> Result<bool, DatabaseError>;
+    /// Store the rolling summary for a conversation, replacing any earlier
+    /// summary and its watermark.
+    async fn upsert_summary(
+        &self,
+        conversation_id: Uuid,
+        summary: &str,
+        token_count: i64,
+        covered_through_message_id: Uuid,
+    ) -> Result<(), DatabaseError>;
+    async fn get_summary(
+        &self,
+        conversation_id: Uuid,
+    ) -> Result<Option<ConversationRollingSummary>, DatabaseError>;
+    /// Delete messages created before `cutoff`, then the conversations left
+    /// empty whose last activity is also before `cutoff`, in one transaction.
+    ///
+    /// `user_id` limits pruning to that user's conversations. Conversations
+    /// still referenced by jobs or LLM call records are kept. Returns the
+    /// number of message and conversation rows removed.
+    async fn prune_conversations_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+        user_id: Option<&str>,
+    ) -> Result<u64, DatabaseError>;
 }
 
 #[async_trait]
@@ -171,6 +216,17 @@ pub trait JobStore: Send + Sync {
         status: JobState,
         failure_reason: Option<&str>,
     ) -> Result<(), DatabaseError>;
+    /// Like `update_job_status`, but only when the job's current state may
+    /// move to `status` (see [`JobState::is_valid_transition`]).
+    ///
+    /// Illegal transitions, and jobs whose state changed concurrently, fail
+    /// with [`DatabaseError::Constraint`]; unknown jobs with `NotFound`.
+    async fn update_job_state_checked(
+        &self,
+        id: Uuid,
+        status: JobState,
+        failure_reason: Option<&str>,
+    ) -> Result<(), DatabaseError>;
     async fn mark_job_stuck(&self, id: Uuid) -> Result<(), DatabaseError>;
     async fn get_stuck_jobs(&self) -> Result<Vec<Uuid>, DatabaseError>;
     async fn save_action(&self, job_id: Uuid, action: &ActionRecord) -> Result<(), DatabaseError>;
@@ -250,6 +306,13 @@ pub trait RoutineStore: Send + Sync {
     async fn list_routines(&self, user_id: &str) -> Result<Vec<Routine>, DatabaseError>;
     async fn list_event_routines(&self) -> Result<Vec<Routine>, DatabaseError>;
     async fn list_due_cron_routines(&self) -> Result<Vec<Routine>, DatabaseError>;
+    /// Enabled routines whose `next_fire_at` is at or before `now`, soonest
+    /// first, at most `limit` of them.
+    async fn list_due_routines(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Routine>, DatabaseError>;
     async fn update_routine(&self, routine: &Routine) -> Result<(), DatabaseError>;
     async fn update_routine_runtime(
         &self,
@@ -287,6 +350,55 @@ pub trait ToolFailureStore: Send + Sync {
     async fn get_broken_tools(&self, threshold: i32) -> Result<Vec<BrokenTool>, DatabaseError>;
     async fn mark_tool_repaired(&self, tool_name: &str) -> Result<(), DatabaseError>;
     async fn increment_repair_attempts(&self, tool_name: &str) -> Result<(), DatabaseError>;
+    /// Extend the user's consecutive-failure streak for a tool, starting a
+    /// new streak when the previous failure is outside [`TOOL_BACKOFF_WINDOW`].
+    async fn record_tool_failure_streak(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DatabaseError>;
+    /// Clear the user's failure streak for a tool.
+    async fn record_tool_success(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+    ) -> Result<(), DatabaseError>;
+    /// How much longer the tool should be skipped for this user, per
+    /// [`tool_failure_backoff`]; `None` when it may run now.
+    async fn should_skip_tool(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<std::time::Duration>, DatabaseError>;
+}
+
+/// Consecutive failures before a tool is backed off.
+pub const TOOL_BACKOFF_THRESHOLD: i64 = 3;
+/// Failures further apart than this do not extend a streak.
+pub const TOOL_BACKOFF_WINDOW: chrono::Duration = chrono::Duration::minutes(15);
+/// Backoff after reaching [`TOOL_BACKOFF_THRESHOLD`]; doubles per further failure.
+pub const TOOL_BACKOFF_BASE: chrono::Duration = chrono::Duration::seconds(30);
+/// Upper bound on a single backoff.
+pub const TOOL_BACKOFF_MAX: chrono::Duration = chrono::Duration::minutes(30);
+
+/// Remaining backoff for a streak of `consecutive_failures` ending at
+/// `last_failure`, or `None` when the tool may run at `now`.
+pub fn tool_failure_backoff(
+    consecutive_failures: i64,
+    last_failure: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<std::time::Duration> {
+    if consecutive_failures < TOOL_BACKOFF_THRESHOLD || now - last_failure > TOOL_BACKOFF_WINDOW {
+        return None;
+    }
+    let doublings = (consecutive_failures - TOOL_BACKOFF_THRESHOLD).min(16) as u32;
+    let backoff = (TOOL_BACKOFF_BASE * 2i32.pow(doublings)).min(TOOL_BACKOFF_MAX);
+    (last_failure + backoff - now)
+        .to_std()
+        .ok()
+        .filter(|d| !d.is_zero())
 }
 
 #[async_trait]
@@ -313,6 +425,22 @@ pub trait SettingsStore: Send + Sync {
         &self,
         user_id: &str,
     ) -> Result<HashMap<String, serde_json::Value>, DatabaseError>;
+    /// Settings stored for a single agent, excluding user-global rows.
+    ///
+    /// Callers merge this over [`SettingsStore::get_all_settings`] so global
+    /// values stay visible wherever the agent has no override.
+    async fn get_all_settings_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+    ) -> Result<HashMap<String, serde_json::Value>, DatabaseError>;
+    async fn set_setting_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<(), DatabaseError>;
     async fn set_all_settings(
         &self,
         user_id: &str,
@@ -388,21 +516,378 @@ pub trait WorkspaceStore: Send + Sync {
     ) -> Result<Vec<SearchResult>, WorkspaceError>;
 }
 
+/// One page of intent audit records plus the cursor for the next page.
+#[derive(Debug, Clone)]
+pub struct IntentAuditPage {
+    pub records: Vec<IntentAuditRecord>,
+    /// Opaque cursor for the page after this one; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Encode a `(created_at, intent_id)` keyset position as an opaque cursor.
+pub fn encode_intent_audit_cursor(created_at: &DateTime<Utc>, intent_id: Uuid) -> String {
+    use base64::Engine as _;
+
+    let raw = format!(
+        "{}|{}",
+        created_at.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+        intent_id
+    );
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(raw.as_bytes())
+}
+
+/// Decode a cursor produced by [`encode_intent_audit_cursor`].
+pub fn decode_intent_audit_cursor(cursor: &str) -> Result<(DateTime<Utc>, Uuid), DatabaseError> {
+    use base64::Engine as _;
+
+    let invalid = || DatabaseError::Serialization(format!("invalid intent audit cursor: {cursor}"));
+    let raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor.trim())
+        .map_err(|_| invalid())?;
+    let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+    let (created_at, intent_id) = raw.split_once('|').ok_or_else(invalid)?;
+    let created_at = DateTime::parse_from_rfc3339(created_at)
+        .map_err(|_| invalid())?
+        .with_timezone(&Utc);
+    let intent_id = Uuid::parse_str(intent_id).map_err(|_| invalid())?;
+    Ok((created_at, intent_id))
+}
+
+/// Outcome of walking a user's intent audit chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainVerificationReport {
+    /// Records walked, oldest first.
+    pub records_checked: usize,
+    /// Hash of the newest pruned record the walk started from, when quota
+    /// pruning has removed the start of the chain.
+    pub checkpoint_chain_hash: Option<String>,
+    /// Position (oldest = 0) of the first record whose hash diverges.
+    pub first_broken_index: Option<usize>,
+    pub first_broken_intent_id: Option<Uuid>,
+}
+
+impl ChainVerificationReport {
+    /// True when every record's `chain_hash` matched its recomputed value.
+    pub fn is_intact(&self) -> bool {
+        self.first_broken_index.is_none()
+    }
+}
+
+/// Attribution weights of a non-empty provider set must sum to 100%.
+pub const PROVIDER_ATTRIBUTION_TOTAL_BPS: u32 = 10_000;
+
+/// Validate provider attributions before they are written.
+///
+/// An empty set (legacy records without copy-trade lineage) is valid.
+/// Otherwise every entry must pass [`ProviderRevenueAttribution::validate`]
+/// and the weights must sum to [`PROVIDER_ATTRIBUTION_TOTAL_BPS`].
+pub fn validate_provider_attributions(
+    attributions: &[ProviderRevenueAttribution],
+) -> Result<(), DatabaseError> {
+    if attributions.is_empty() {
+        return Ok(());
+    }
+    for (index, attribution) in attributions.iter().enumerate() {
+        attribution.validate().map_err(|err| {
+            DatabaseError::Serialization(format!("provider_attributions[{index}]: {err}"))
+        })?;
+    }
+    let total_bps: u64 = attributions
+        .iter()
+        .map(|attribution| u64::from(attribution.attribution_weight_bps))
+        .sum();
+    if total_bps != u64::from(PROVIDER_ATTRIBUTION_TOTAL_BPS) {
+        return Err(DatabaseError::Serialization(format!(
+            "provider_attributions weights sum to {total_bps} bps, expected {PROVIDER_ATTRIBUTION_TOTAL_BPS}"
+        )));
+    }
+    Ok(())
+}
+
+/// Recompute each record's `chain_hash` from its predecessor and report the
+/// first divergence. `records` must be ordered oldest first.
+pub fn verify_intent_audit_chain(
+    records: &[IntentAuditRecord],
+    checkpoint: Option<&IntentAuditCheckpoint>,
+) -> Result<ChainVerificationReport, DatabaseError> {
+    let checkpoint_chain_hash = checkpoint.map(|checkpoint| checkpoint.chain_hash.clone());
+    let mut previous = checkpoint_chain_hash.as_deref();
+    for (index, record) in records.iter().enumerate() {
+        let expected = record
+            .expected_chain_hash(previous)
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        if expected != record.chain_hash {
+            return Ok(ChainVerificationReport {
+                records_checked: index + 1,
+                checkpoint_chain_hash,
+                first_broken_index: Some(index),
+                first_broken_intent_id: Some(record.intent_id),
+            });
+        }
+        previous = Some(&record.chain_hash);
+    }
+    Ok(ChainVerificationReport {
+        records_checked: records.len(),
+        checkpoint_chain_hash,
+        first_broken_index: None,
+        first_broken_intent_id: None,
+    })
+}
+
+/// Newest record removed by quota pruning, stored under
+/// [`IntentAuditRecord::CHECKPOINT_SETTINGS_KEY`]. Its `chain_hash` is the
+/// genesis [`verify_intent_audit_chain`] links the oldest kept record to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IntentAuditCheckpoint {
+    pub chain_hash: String,
+    pub intent_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Newest record of a user's chain, by `(created_at, intent_id)`.
+#[derive(Debug, Clone)]
+pub(crate) struct IntentAuditChainHead {
+    pub chain_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub intent_id: Uuid,
+}
+
+/// Link `record` after `head` for [`IntentAuditStore::append_intent_audit_record`].
+///
+/// `created_at` is truncated to the millisecond precision the stores keep
+/// and, when it would not sort after `head`, moved 1ms past it so
+/// [`verify_intent_audit_chain`] walks records in link order.
+pub(crate) fn link_intent_audit_record(
+    record: &IntentAuditRecord,
+    head: Option<&IntentAuditChainHead>,
+) -> Result<IntentAuditRecord, DatabaseError> {
+    use chrono::SubsecRound;
+
+    let mut linked = record.clone();
+    linked.created_at = linked.created_at.trunc_subsecs(3);
+    if let Some(head) = head
+        && (linked.created_at, linked.intent_id) <= (head.created_at, head.intent_id)
+    {
+        linked.created_at = head.created_at.trunc_subsecs(3) + chrono::Duration::milliseconds(1);
+    }
+    linked
+        .linked_to(head.map(|head| head.chain_hash.as_str()))
+        .map_err(|e| DatabaseError::Serialization(e.to_string()))
+}
+
+/// Flat formats for [`IntentAuditStore::export_records`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One serde-encoded [`IntentAuditRecord`] per line.
+    Ndjson,
+    /// Header row of [`INTENT_AUDIT_EXPORT_COLUMNS`] followed by one row per record.
+    Csv,
+}
+
+/// `intent_audit_records` columns, in CSV export order.
+pub const INTENT_AUDIT_EXPORT_COLUMNS: &[&str] = &[
+    "intent_id",
+    "agent_id",
+    "user_id",
+    "signal_hash",
+    "intent_hash",
+    "receipt_id",
+    "receipt_hash",
+    "verification_id",
+    "verification_hash",
+    "verification_status",
+    "verification_level",
+    "settlement_id",
+    "settlement_hash",
+    "provider_attributions",
+    "mirrored_pnl_usd",
+    "revenue_share_fee_usd",
+    "chain_hash",
+    "workspace_path",
+    "created_at",
+];
+
+fn export_io_error(err: std::io::Error) -> DatabaseError {
+    DatabaseError::Serialization(format!("intent audit export write failed: {err}"))
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Write the leading header for `format` (CSV only).
+pub fn write_intent_audit_export_header(
+    format: ExportFormat,
+    writer: &mut (dyn std::io::Write + Send),
+) -> Result<(), DatabaseError> {
+    match format {
+        ExportFormat::Ndjson => Ok(()),
+        ExportFormat::Csv => {
+            writeln!(writer, "{}", INTENT_AUDIT_EXPORT_COLUMNS.join(",")).map_err(export_io_error)
+        }
+    }
+}
+
+/// Write one record as a line of `format`.
+pub fn write_intent_audit_export_row(
+    format: ExportFormat,
+    record: &IntentAuditRecord,
+    writer: &mut (dyn std::io::Write + Send),
+) -> Result<(), DatabaseError> {
+    match format {
+        ExportFormat::Ndjson => {
+            let line = serde_json::to_string(record)
+                .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+            writeln!(writer, "{line}").map_err(export_io_error)
+        }
+        ExportFormat::Csv => {
+            let attributions = serde_json::to_string(&record.provider_attributions)
+                .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+            let opt_uuid = |id: Option<Uuid>| id.map(|id| id.to_string()).unwrap_or_default();
+            let cells = [
+                record.intent_id.to_string(),
+                opt_uuid(record.agent_id),
+                record.user_id.clone(),
+                record.signal_hash.clone().unwrap_or_default(),
+                record.intent_hash.clone(),
+                record.receipt_id.to_string(),
+                record.receipt_hash.clone(),
+                opt_uuid(record.verification_id),
+                record.verification_hash.clone().unwrap_or_default(),
+                record
+                    .verification_status
+                    .map(|status| status.as_str().to_string())
+                    .unwrap_or_default(),
+                record.verification_level.clone().unwrap_or_default(),
+                opt_uuid(record.settlement_id),
+                record.settlement_hash.clone().unwrap_or_default(),
+                attributions,
+                record.mirrored_pnl_usd.clone().unwrap_or_default(),
+                record.revenue_share_fee_usd.clone().unwrap_or_default(),
+                record.chain_hash.clone(),
+                record.workspace_path.clone(),
+                record.created_at.to_rfc3339(),
+            ];
+            let line = cells
+                .iter()
+                .map(|cell| csv_cell(cell))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(writer, "{line}").map_err(export_io_error)
+        }
+    }
+}
+
 #[async_trait]
 pub trait IntentAuditStore: Send + Sync {
     async fn persist_intent_audit_record(
         &self,
         record: &IntentAuditRecord,
     ) -> Result<(), DatabaseError>;
+    /// Retry-safe variant of [`IntentAuditStore::persist_intent_audit_record`].
+    ///
+    /// An existing record is only replaced when the incoming `created_at` is
+    /// strictly newer; stale writes are skipped and return `Ok(false)`. A
+    /// `chain_hash` that differs from the stored one fails with
+    /// [`DatabaseError::Constraint`] instead of rewriting the chain.
+    async fn persist_intent_audit_record_checked(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<bool, DatabaseError>;
+    /// Link `record` to its user's chain head and persist it, in one
+    /// transaction on the primary so concurrent appends cannot fork the
+    /// chain. See [`link_intent_audit_record`] for how `created_at` may be
+    /// adjusted. Returns the record as written.
+    async fn append_intent_audit_record(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<IntentAuditRecord, DatabaseError>;
+    /// Persist many records in a single transaction.
+    ///
+    /// Records are upserted like [`IntentAuditStore::persist_intent_audit_record`],
+    /// including the quota check, but the settings mirror is written once per
+    /// user, for that user's newest record. Any failure rolls back the whole
+    /// batch.
+    async fn persist_intent_audit_records_batch(
+        &self,
+        records: &[IntentAuditRecord],
+    ) -> Result<(), DatabaseError>;
     async fn get_intent_audit_record(
         &self,
         intent_id: Uuid,
     ) -> Result<Option<IntentAuditRecord>, DatabaseError>;
+    /// Number of records stored for `user_id`.
+    async fn count_intent_audit_records(&self, user_id: &str) -> Result<i64, DatabaseError>;
     async fn list_intent_audit_records(
         &self,
         user_id: &str,
         limit: i64,
     ) -> Result<Vec<IntentAuditRecord>, DatabaseError>;
+    /// Newest-first page of records strictly older than `before`, keyed on
+    /// `(created_at, intent_id)` so records sharing a timestamp page stably.
+    async fn list_intent_audit_records_paged(
+        &self,
+        user_id: &str,
+        limit: i64,
+        before: Option<(DateTime<Utc>, Uuid)>,
+    ) -> Result<IntentAuditPage, DatabaseError>;
+    /// Walk `user_id`'s records in `created_at` order and check that each
+    /// `chain_hash` links to the one before it. The oldest record links to
+    /// the pruning checkpoint, if quota pruning has left one.
+    async fn verify_chain(&self, user_id: &str) -> Result<ChainVerificationReport, DatabaseError>;
+    /// Stream every record for `user_id`, oldest first, into `writer`.
+    ///
+    /// Rows are written as they are read rather than collected first.
+    /// Returns the number of records written (excluding the CSV header).
+    async fn export_records(
+        &self,
+        user_id: &str,
+        format: ExportFormat,
+        writer: &mut (dyn std::io::Write + Send),
+    ) -> Result<u64, DatabaseError>;
+}
+
+/// A row in `frontdoor_sessions`.
+///
+/// `payload` is the full session snapshot owned by the frontdoor service and
+/// is opaque to the database layer; the other fields fill the table's summary
+/// columns so rows stay queryable without decoding it.
+#[derive(Debug, Clone)]
+pub struct FrontdoorSessionRecord {
+    pub session_id: Uuid,
+    pub user_id: String,
+    pub wallet_address: String,
+    pub version: i64,
+    pub status: String,
+    pub detail: String,
+    pub privy_user_id: Option<String>,
+    pub profile_name: Option<String>,
+    pub instance_url: Option<String>,
+    pub verify_url: Option<String>,
+    pub eigen_app_id: Option<String>,
+    pub error: Option<String>,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait FrontdoorSessionStore: Send + Sync {
+    async fn upsert_frontdoor_session(
+        &self,
+        record: &FrontdoorSessionRecord,
+    ) -> Result<(), DatabaseError>;
+    async fn list_frontdoor_sessions(&self) -> Result<Vec<FrontdoorSessionRecord>, DatabaseError>;
+    /// Delete sessions whose `expires_at` is before `cutoff`; returns rows removed.
+    async fn delete_frontdoor_sessions_expired_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, DatabaseError>;
 }
 
 /// Backend-agnostic database supertrait.
@@ -419,6 +904,7 @@ pub trait Database:
     + SettingsStore
     + WorkspaceStore
     + IntentAuditStore
+    + FrontdoorSessionStore
     + Send
     + Sync
 {
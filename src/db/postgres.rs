@@ -13,16 +13,19 @@ use uuid::Uuid;
 
 use crate::agent::routine::{Routine, RoutineRun, RunStatus};
 use crate::agent::{BrokenTool, IntentAuditRecord, VerificationStatus};
-use crate::config::DatabaseConfig;
+use crate::config::{DatabaseConfig, IntentAuditQuota, IntentAuditQuotaPolicy};
 use crate::context::{ActionRecord, JobContext, JobState};
 use crate::db::{
-    ConversationStore, Database, IntentAuditStore, JobStore, RoutineStore, SandboxStore,
-    SettingsStore, ToolFailureStore, WorkspaceStore,
+    ChainVerificationReport, ConversationStore, Database, ExportFormat, FrontdoorSessionRecord,
+    FrontdoorSessionStore, IntentAuditChainHead, IntentAuditCheckpoint, IntentAuditPage,
+    IntentAuditStore, JobStore, RoutineStore, SandboxStore, SettingsStore, ToolFailureStore,
+    WorkspaceStore, encode_intent_audit_cursor, link_intent_audit_record,
+    verify_intent_audit_chain, write_intent_audit_export_header, write_intent_audit_export_row,
 };
 use crate::error::{DatabaseError, WorkspaceError};
 use crate::history::{
-    ConversationMessage, ConversationSummary, JobEventRecord, LlmCallRecord, SandboxJobRecord,
-    SandboxJobSummary, SettingRow, Store,
+    ConversationMessage, ConversationRollingSummary, ConversationSummary, JobEventRecord,
+    LlmCallRecord, SandboxJobRecord, SandboxJobSummary, SettingRow, Store,
 };
 use crate::workspace::{
     MemoryChunk, MemoryDocument, Repository, SearchConfig, SearchResult, WorkspaceEntry,
@@ -38,6 +41,7 @@ use tokio_postgres::Row;
 pub struct PgBackend {
     store: Store,
     repo: Repository,
+    intent_audit_quota: Option<IntentAuditQuota>,
 }
 
 impl PgBackend {
@@ -45,7 +49,11 @@ impl PgBackend {
     pub async fn new(config: &DatabaseConfig) -> Result<Self, DatabaseError> {
         let store = Store::new(config).await?;
         let repo = Repository::new(store.pool());
-        Ok(Self { store, repo })
+        Ok(Self {
+            store,
+            repo,
+            intent_audit_quota: config.intent_audit_quota,
+        })
     }
 
     /// Get a clone of the connection pool.
@@ -184,6 +192,40 @@ impl ConversationStore for PgBackend {
             .conversation_belongs_to_user(conversation_id, user_id)
             .await
     }
+
+    async fn upsert_summary(
+        &self,
+        conversation_id: Uuid,
+        summary: &str,
+        token_count: i64,
+        covered_through_message_id: Uuid,
+    ) -> Result<(), DatabaseError> {
+        self.store
+            .upsert_summary(
+                conversation_id,
+                summary,
+                token_count,
+                covered_through_message_id,
+            )
+            .await
+    }
+
+    async fn get_summary(
+        &self,
+        conversation_id: Uuid,
+    ) -> Result<Option<ConversationRollingSummary>, DatabaseError> {
+        self.store.get_summary(conversation_id).await
+    }
+
+    async fn prune_conversations_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+        user_id: Option<&str>,
+    ) -> Result<u64, DatabaseError> {
+        self.store
+            .prune_conversations_older_than(cutoff, user_id)
+            .await
+    }
 }
 
 // ==================== JobStore ====================
@@ -209,6 +251,17 @@ impl JobStore for PgBackend {
             .await
     }
 
+    async fn update_job_state_checked(
+        &self,
+        id: Uuid,
+        status: JobState,
+        failure_reason: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        self.store
+            .update_job_state_checked(id, status, failure_reason)
+            .await
+    }
+
     async fn mark_job_stuck(&self, id: Uuid) -> Result<(), DatabaseError> {
         self.store.mark_job_stuck(id).await
     }
@@ -383,6 +436,14 @@ impl RoutineStore for PgBackend {
         self.store.list_due_cron_routines().await
     }
 
+    async fn list_due_routines(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Routine>, DatabaseError> {
+        self.store.list_due_routines(now, limit).await
+    }
+
     async fn update_routine(&self, routine: &Routine) -> Result<(), DatabaseError> {
         self.store.update_routine(routine).await
     }
@@ -466,6 +527,34 @@ impl ToolFailureStore for PgBackend {
     async fn increment_repair_attempts(&self, tool_name: &str) -> Result<(), DatabaseError> {
         self.store.increment_repair_attempts(tool_name).await
     }
+
+    async fn record_tool_failure_streak(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DatabaseError> {
+        self.store
+            .record_tool_failure_streak(tool_name, user_id, now)
+            .await
+    }
+
+    async fn record_tool_success(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+    ) -> Result<(), DatabaseError> {
+        self.store.record_tool_success(tool_name, user_id).await
+    }
+
+    async fn should_skip_tool(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<std::time::Duration>, DatabaseError> {
+        self.store.should_skip_tool(tool_name, user_id, now).await
+    }
 }
 
 // ==================== SettingsStore ====================
@@ -512,6 +601,28 @@ impl SettingsStore for PgBackend {
         self.store.get_all_settings(user_id).await
     }
 
+    async fn get_all_settings_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+    ) -> Result<HashMap<String, serde_json::Value>, DatabaseError> {
+        self.store
+            .get_all_settings_for_agent(user_id, agent_id)
+            .await
+    }
+
+    async fn set_setting_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<(), DatabaseError> {
+        self.store
+            .set_setting_for_agent(user_id, agent_id, key, value)
+            .await
+    }
+
     async fn set_all_settings(
         &self,
         user_id: &str,
@@ -673,66 +784,56 @@ impl PgBackend {
             );
             CREATE INDEX IF NOT EXISTS idx_intent_audit_user ON intent_audit_records(user_id);
             CREATE INDEX IF NOT EXISTS idx_intent_audit_chain ON intent_audit_records(chain_hash);
+            CREATE INDEX IF NOT EXISTS idx_intent_audit_user_created ON intent_audit_records(user_id, created_at, intent_id);
             ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS signal_hash TEXT;
             ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS settlement_id UUID;
             ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS settlement_hash TEXT;
             ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS provider_attributions JSONB NOT NULL DEFAULT '[]'::jsonb;
             ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS mirrored_pnl_usd TEXT;
             ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS revenue_share_fee_usd TEXT;
+            ALTER TABLE intent_audit_records ADD COLUMN IF NOT EXISTS verification_level TEXT;
             "#,
         )
         .await?;
         Ok(())
     }
 
-    fn row_to_intent_audit_record(row: &Row) -> IntentAuditRecord {
-        let verification_status = row
-            .get::<_, Option<String>>("verification_status")
-            .and_then(|value| VerificationStatus::parse(&value));
-        let provider_attributions = row
-            .get::<_, serde_json::Value>("provider_attributions")
-            .as_array()
-            .cloned()
-            .and_then(|arr| serde_json::from_value(serde_json::Value::Array(arr)).ok())
-            .unwrap_or_default();
-
-        IntentAuditRecord {
-            intent_id: row.get("intent_id"),
-            agent_id: row.get("agent_id"),
-            user_id: row.get("user_id"),
-            signal_hash: row.get("signal_hash"),
-            intent_hash: row.get("intent_hash"),
-            receipt_id: row.get("receipt_id"),
-            receipt_hash: row.get("receipt_hash"),
-            verification_id: row.get("verification_id"),
-            verification_hash: row.get("verification_hash"),
-            verification_status,
-            settlement_id: row.get("settlement_id"),
-            settlement_hash: row.get("settlement_hash"),
-            provider_attributions,
-            mirrored_pnl_usd: row.get("mirrored_pnl_usd"),
-            revenue_share_fee_usd: row.get("revenue_share_fee_usd"),
-            workspace_path: row.get("workspace_path"),
-            chain_hash: row.get("chain_hash"),
-            created_at: row.get("created_at"),
-        }
-    }
-}
-
-#[async_trait]
-impl IntentAuditStore for PgBackend {
-    async fn persist_intent_audit_record(
+    /// Upsert an intent audit record and mirror it into settings.
+    ///
+    /// With `checked`, an existing row is only replaced by a strictly newer
+    /// `created_at`, and a differing stored `chain_hash` is rejected. The
+    /// configured quota is enforced in the same transaction. Returns whether
+    /// the row was written.
+    async fn write_intent_audit_record(
         &self,
         record: &IntentAuditRecord,
-    ) -> Result<(), DatabaseError> {
-        let conn = self.store.conn().await?;
+        checked: bool,
+    ) -> Result<bool, DatabaseError> {
+        crate::db::validate_provider_attributions(&record.provider_attributions)?;
+        let mut conn = self.store.conn().await?;
         self.ensure_intent_audit_table(&conn).await?;
+        let tx = conn.transaction().await?;
+        if let Some(quota) = self.intent_audit_quota {
+            Self::enforce_intent_audit_quota(&tx, record, quota).await?;
+        }
+        if !Self::insert_intent_audit_row(&tx, record, checked).await? {
+            return Ok(false);
+        }
+        Self::mirror_intent_audit_setting(&tx, record).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
 
+    /// Upsert the `intent_audit_records` row; returns whether it was written.
+    async fn insert_intent_audit_row(
+        tx: &deadpool_postgres::Transaction<'_>,
+        record: &IntentAuditRecord,
+        checked: bool,
+    ) -> Result<bool, DatabaseError> {
         let verification_status = record.verification_status.map(|status| status.as_str());
         let provider_attributions = serde_json::to_value(&record.provider_attributions)
-            .map_err(|e| DatabaseError::Query(e.to_string()))?;
-        conn.execute(
-            r#"
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        let mut sql = r#"
             INSERT INTO intent_audit_records (
                 intent_id,
                 agent_id,
@@ -751,8 +852,9 @@ impl IntentAuditStore for PgBackend {
                 revenue_share_fee_usd,
                 chain_hash,
                 workspace_path,
-                created_at
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18)
+                created_at,
+                verification_level
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18,$19)
             ON CONFLICT (intent_id) DO UPDATE SET
                 agent_id = EXCLUDED.agent_id,
                 signal_hash = EXCLUDED.signal_hash,
@@ -769,37 +871,75 @@ impl IntentAuditStore for PgBackend {
                 revenue_share_fee_usd = EXCLUDED.revenue_share_fee_usd,
                 chain_hash = EXCLUDED.chain_hash,
                 workspace_path = EXCLUDED.workspace_path,
-                created_at = EXCLUDED.created_at
-            "#,
-            &[
-                &record.intent_id,
-                &record.agent_id,
-                &record.user_id,
-                &record.signal_hash,
-                &record.intent_hash,
-                &record.receipt_id,
-                &record.receipt_hash,
-                &record.verification_id,
-                &record.verification_hash,
-                &verification_status,
-                &record.settlement_id,
-                &record.settlement_hash,
-                &provider_attributions,
-                &record.mirrored_pnl_usd,
-                &record.revenue_share_fee_usd,
-                &record.chain_hash,
-                &record.workspace_path,
-                &record.created_at,
-            ],
-        )
-        .await?;
+                created_at = EXCLUDED.created_at,
+                verification_level = EXCLUDED.verification_level
+            "#
+        .to_string();
+        if checked {
+            sql.push_str(
+                "WHERE EXCLUDED.created_at > intent_audit_records.created_at \
+                 AND intent_audit_records.chain_hash IN ('', EXCLUDED.chain_hash)",
+            );
+        }
+        let written = tx
+            .execute(
+                sql.as_str(),
+                &[
+                    &record.intent_id,
+                    &record.agent_id,
+                    &record.user_id,
+                    &record.signal_hash,
+                    &record.intent_hash,
+                    &record.receipt_id,
+                    &record.receipt_hash,
+                    &record.verification_id,
+                    &record.verification_hash,
+                    &verification_status,
+                    &record.settlement_id,
+                    &record.settlement_hash,
+                    &provider_attributions,
+                    &record.mirrored_pnl_usd,
+                    &record.revenue_share_fee_usd,
+                    &record.chain_hash,
+                    &record.workspace_path,
+                    &record.created_at,
+                    &record.verification_level,
+                ],
+            )
+            .await?;
+        if written == 0 {
+            let row = tx
+                .query_opt(
+                    "SELECT chain_hash FROM intent_audit_records WHERE intent_id = $1",
+                    &[&record.intent_id],
+                )
+                .await?;
+            if let Some(row) = row {
+                let stored: String = row.get("chain_hash");
+                if !stored.is_empty() && stored != record.chain_hash {
+                    return Err(DatabaseError::Constraint(format!(
+                        "intent {} already has chain_hash {}; refusing {}",
+                        record.intent_id, stored, record.chain_hash
+                    )));
+                }
+            }
+            return Ok(false);
+        }
+        Ok(true)
+    }
 
+    async fn mirror_intent_audit_setting(
+        tx: &deadpool_postgres::Transaction<'_>,
+        record: &IntentAuditRecord,
+    ) -> Result<(), DatabaseError> {
+        let verification_status = record.verification_status.map(|status| status.as_str());
         let settings_payload = json!({
             "intent_id": record.intent_id,
             "receipt_id": record.receipt_id,
             "chain_hash": record.chain_hash,
             "workspace_path": record.workspace_path,
             "verification_status": verification_status,
+            "verification_level": record.verification_level,
             "signal_hash": record.signal_hash,
             "settlement_id": record.settlement_id,
             "settlement_hash": record.settlement_hash,
@@ -809,11 +949,11 @@ impl IntentAuditStore for PgBackend {
             "created_at": record.created_at,
         });
 
-        conn.execute(
+        tx.execute(
             r#"
             INSERT INTO settings (user_id, key, value, updated_at)
             VALUES ($1, $2, $3, NOW())
-            ON CONFLICT (user_id, key) DO UPDATE SET
+            ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
                 value = EXCLUDED.value,
                 updated_at = NOW()
             "#,
@@ -824,7 +964,232 @@ impl IntentAuditStore for PgBackend {
             ],
         )
         .await?;
+        Ok(())
+    }
+
+    /// Make room for `record` under `quota`, or refuse it.
+    ///
+    /// Rewrites of an existing intent never add a row, so they always pass.
+    async fn enforce_intent_audit_quota(
+        tx: &deadpool_postgres::Transaction<'_>,
+        record: &IntentAuditRecord,
+        quota: IntentAuditQuota,
+    ) -> Result<(), DatabaseError> {
+        // Serialize quota checks per user so concurrent writers cannot both
+        // see room for one more record.
+        tx.execute(
+            "SELECT pg_advisory_xact_lock(hashtext($1))",
+            &[&record.user_id],
+        )
+        .await?;
+        let exists = tx
+            .query_opt(
+                "SELECT 1 FROM intent_audit_records WHERE intent_id = $1",
+                &[&record.intent_id],
+            )
+            .await?
+            .is_some();
+        if exists {
+            return Ok(());
+        }
+
+        let count: i64 = tx
+            .query_one(
+                "SELECT COUNT(*) FROM intent_audit_records WHERE user_id = $1",
+                &[&record.user_id],
+            )
+            .await?
+            .get(0);
+        let limit = i64::from(quota.max_records_per_user);
+        if count < limit {
+            return Ok(());
+        }
+        match quota.policy {
+            IntentAuditQuotaPolicy::Reject => Err(DatabaseError::QuotaExceeded {
+                user_id: record.user_id.clone(),
+                limit: quota.max_records_per_user,
+            }),
+            IntentAuditQuotaPolicy::PruneOldest => {
+                // The newest pruned record becomes the chain's checkpoint.
+                let newest_pruned = tx
+                    .query_opt(
+                        r#"
+                        SELECT chain_hash, intent_id, created_at FROM intent_audit_records
+                        WHERE user_id = $1
+                        ORDER BY created_at ASC, intent_id ASC
+                        LIMIT 1 OFFSET $2
+                        "#,
+                        &[&record.user_id, &(count - limit)],
+                    )
+                    .await?;
+                if let Some(row) = newest_pruned {
+                    let checkpoint = IntentAuditCheckpoint {
+                        chain_hash: row.get("chain_hash"),
+                        intent_id: row.get("intent_id"),
+                        created_at: row.get("created_at"),
+                    };
+                    let value = serde_json::to_value(&checkpoint)
+                        .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+                    tx.execute(
+                        r#"
+                        INSERT INTO settings (user_id, key, value, updated_at)
+                        VALUES ($1, $2, $3, NOW())
+                        ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
+                            value = EXCLUDED.value,
+                            updated_at = NOW()
+                        "#,
+                        &[
+                            &record.user_id,
+                            &IntentAuditRecord::CHECKPOINT_SETTINGS_KEY,
+                            &value,
+                        ],
+                    )
+                    .await?;
+                }
+                tx.execute(
+                    r#"
+                    DELETE FROM intent_audit_records
+                    WHERE intent_id IN (
+                        SELECT intent_id FROM intent_audit_records
+                        WHERE user_id = $1
+                        ORDER BY created_at ASC, intent_id ASC
+                        LIMIT $2
+                    )
+                    "#,
+                    &[&record.user_id, &(count - limit + 1)],
+                )
+                .await?;
+                Ok(())
+            }
+        }
+    }
+
+    fn row_to_intent_audit_record(row: &Row) -> IntentAuditRecord {
+        let verification_status = row
+            .get::<_, Option<String>>("verification_status")
+            .and_then(|value| VerificationStatus::parse(&value));
+        let provider_attributions = row
+            .get::<_, serde_json::Value>("provider_attributions")
+            .as_array()
+            .cloned()
+            .and_then(|arr| serde_json::from_value(serde_json::Value::Array(arr)).ok())
+            .unwrap_or_default();
+
+        IntentAuditRecord {
+            intent_id: row.get("intent_id"),
+            agent_id: row.get("agent_id"),
+            user_id: row.get("user_id"),
+            signal_hash: row.get("signal_hash"),
+            intent_hash: row.get("intent_hash"),
+            receipt_id: row.get("receipt_id"),
+            receipt_hash: row.get("receipt_hash"),
+            verification_id: row.get("verification_id"),
+            verification_hash: row.get("verification_hash"),
+            verification_status,
+            verification_level: row
+                .try_get::<_, Option<String>>("verification_level")
+                .ok()
+                .flatten(),
+            settlement_id: row.get("settlement_id"),
+            settlement_hash: row.get("settlement_hash"),
+            provider_attributions,
+            mirrored_pnl_usd: row.get("mirrored_pnl_usd"),
+            revenue_share_fee_usd: row.get("revenue_share_fee_usd"),
+            workspace_path: row.get("workspace_path"),
+            chain_hash: row.get("chain_hash"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl IntentAuditStore for PgBackend {
+    async fn persist_intent_audit_record(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<(), DatabaseError> {
+        self.write_intent_audit_record(record, false).await?;
+        Ok(())
+    }
+
+    async fn persist_intent_audit_record_checked(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<bool, DatabaseError> {
+        self.write_intent_audit_record(record, true).await
+    }
+
+    async fn append_intent_audit_record(
+        &self,
+        record: &IntentAuditRecord,
+    ) -> Result<IntentAuditRecord, DatabaseError> {
+        crate::db::validate_provider_attributions(&record.provider_attributions)?;
+        let mut conn = self.store.conn().await?;
+        self.ensure_intent_audit_table(&conn).await?;
+        let tx = conn.transaction().await?;
+        // Same per-user lock as the quota check, so appends see each other.
+        tx.execute(
+            "SELECT pg_advisory_xact_lock(hashtext($1))",
+            &[&record.user_id],
+        )
+        .await?;
+        let head = tx
+            .query_opt(
+                r#"
+                SELECT chain_hash, created_at, intent_id FROM intent_audit_records
+                WHERE user_id = $1 AND intent_id <> $2
+                ORDER BY created_at DESC, intent_id DESC
+                LIMIT 1
+                "#,
+                &[&record.user_id, &record.intent_id],
+            )
+            .await?
+            .map(|row| IntentAuditChainHead {
+                chain_hash: row.get("chain_hash"),
+                created_at: row.get("created_at"),
+                intent_id: row.get("intent_id"),
+            });
+        let linked = link_intent_audit_record(record, head.as_ref())?;
+        if let Some(quota) = self.intent_audit_quota {
+            Self::enforce_intent_audit_quota(&tx, &linked, quota).await?;
+        }
+        Self::insert_intent_audit_row(&tx, &linked, false).await?;
+        Self::mirror_intent_audit_setting(&tx, &linked).await?;
+        tx.commit().await?;
+        Ok(linked)
+    }
 
+    async fn persist_intent_audit_records_batch(
+        &self,
+        records: &[IntentAuditRecord],
+    ) -> Result<(), DatabaseError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.store.conn().await?;
+        self.ensure_intent_audit_table(&conn).await?;
+        // Dropping the transaction without committing rolls the batch back.
+        let tx = conn.transaction().await?;
+        let mut latest: HashMap<&str, &IntentAuditRecord> = HashMap::new();
+        for record in records {
+            crate::db::validate_provider_attributions(&record.provider_attributions)?;
+            if let Some(quota) = self.intent_audit_quota {
+                Self::enforce_intent_audit_quota(&tx, record, quota).await?;
+            }
+            Self::insert_intent_audit_row(&tx, record, false).await?;
+            latest
+                .entry(record.user_id.as_str())
+                .and_modify(|newest| {
+                    if record.created_at >= newest.created_at {
+                        *newest = record;
+                    }
+                })
+                .or_insert(record);
+        }
+        for record in latest.values() {
+            Self::mirror_intent_audit_setting(&tx, record).await?;
+        }
+        tx.commit().await?;
         Ok(())
     }
 
@@ -845,6 +1210,19 @@ impl IntentAuditStore for PgBackend {
         Ok(row.map(|row| Self::row_to_intent_audit_record(&row)))
     }
 
+    async fn count_intent_audit_records(&self, user_id: &str) -> Result<i64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        self.ensure_intent_audit_table(&conn).await?;
+
+        let row = conn
+            .query_one(
+                "SELECT COUNT(*) FROM intent_audit_records WHERE user_id = $1",
+                &[&user_id],
+            )
+            .await?;
+        Ok(row.get(0))
+    }
+
     async fn list_intent_audit_records(
         &self,
         user_id: &str,
@@ -855,11 +1233,226 @@ impl IntentAuditStore for PgBackend {
 
         let rows = conn
             .query(
-                "SELECT * FROM intent_audit_records WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2",
+                "SELECT * FROM intent_audit_records WHERE user_id = $1 ORDER BY created_at DESC, intent_id DESC LIMIT $2",
                 &[&user_id, &limit],
             )
             .await?;
 
         Ok(rows.iter().map(Self::row_to_intent_audit_record).collect())
     }
+
+    async fn list_intent_audit_records_paged(
+        &self,
+        user_id: &str,
+        limit: i64,
+        before: Option<(DateTime<Utc>, Uuid)>,
+    ) -> Result<IntentAuditPage, DatabaseError> {
+        let conn = self.store.conn().await?;
+        self.ensure_intent_audit_table(&conn).await?;
+
+        let (before_ts, before_id) = before.unzip();
+        let rows = conn
+            .query(
+                r#"
+                SELECT * FROM intent_audit_records
+                WHERE user_id = $1
+                  AND ($2::timestamptz IS NULL OR (created_at, intent_id) < ($2, $3))
+                ORDER BY created_at DESC, intent_id DESC
+                LIMIT $4
+                "#,
+                &[&user_id, &before_ts, &before_id, &limit],
+            )
+            .await?;
+
+        let records: Vec<IntentAuditRecord> =
+            rows.iter().map(Self::row_to_intent_audit_record).collect();
+        let next_cursor = match records.last() {
+            Some(last) if records.len() as i64 == limit => {
+                Some(encode_intent_audit_cursor(&last.created_at, last.intent_id))
+            }
+            _ => None,
+        };
+        Ok(IntentAuditPage {
+            records,
+            next_cursor,
+        })
+    }
+
+    async fn verify_chain(&self, user_id: &str) -> Result<ChainVerificationReport, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        self.ensure_intent_audit_table(&conn).await?;
+        // A single snapshot, so a concurrent prune cannot land between
+        // reading the records and reading the checkpoint.
+        let tx = conn
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::RepeatableRead)
+            .read_only(true)
+            .start()
+            .await?;
+
+        let rows = tx
+            .query(
+                "SELECT * FROM intent_audit_records WHERE user_id = $1 ORDER BY created_at ASC, intent_id ASC",
+                &[&user_id],
+            )
+            .await?;
+        let checkpoint = tx
+            .query_opt(
+                "SELECT value FROM settings WHERE user_id = $1 AND key = $2 AND agent_id IS NULL",
+                &[&user_id, &IntentAuditRecord::CHECKPOINT_SETTINGS_KEY],
+            )
+            .await?
+            .map(|row| serde_json::from_value::<IntentAuditCheckpoint>(row.get("value")))
+            .transpose()
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        tx.commit().await?;
+
+        let records: Vec<IntentAuditRecord> =
+            rows.iter().map(Self::row_to_intent_audit_record).collect();
+        verify_intent_audit_chain(&records, checkpoint.as_ref())
+    }
+
+    async fn export_records(
+        &self,
+        user_id: &str,
+        format: ExportFormat,
+        writer: &mut (dyn std::io::Write + Send),
+    ) -> Result<u64, DatabaseError> {
+        use futures::TryStreamExt;
+
+        let conn = self.store.conn().await?;
+        self.ensure_intent_audit_table(&conn).await?;
+
+        let rows = conn
+            .query_raw(
+                "SELECT * FROM intent_audit_records WHERE user_id = $1 ORDER BY created_at ASC, intent_id ASC",
+                [user_id],
+            )
+            .await?;
+        futures::pin_mut!(rows);
+
+        write_intent_audit_export_header(format, writer)?;
+        let mut written: u64 = 0;
+        while let Some(row) = rows.try_next().await? {
+            let record = Self::row_to_intent_audit_record(&row);
+            write_intent_audit_export_row(format, &record, writer)?;
+            written += 1;
+        }
+        writer
+            .flush()
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        Ok(written)
+    }
+}
+
+// ==================== FrontdoorSessionStore ====================
+
+#[async_trait]
+impl FrontdoorSessionStore for PgBackend {
+    async fn upsert_frontdoor_session(
+        &self,
+        record: &FrontdoorSessionRecord,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.store.conn().await?;
+        conn.execute(
+            r#"
+            INSERT INTO frontdoor_sessions (
+                id, user_id, wallet_address, version, status, detail,
+                privy_user_id, profile_name, instance_url, verify_url,
+                eigen_app_id, error, payload, created_at, updated_at, expires_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT (id) DO UPDATE SET
+                user_id = EXCLUDED.user_id,
+                wallet_address = EXCLUDED.wallet_address,
+                version = EXCLUDED.version,
+                status = EXCLUDED.status,
+                detail = EXCLUDED.detail,
+                privy_user_id = EXCLUDED.privy_user_id,
+                profile_name = EXCLUDED.profile_name,
+                instance_url = EXCLUDED.instance_url,
+                verify_url = EXCLUDED.verify_url,
+                eigen_app_id = EXCLUDED.eigen_app_id,
+                error = EXCLUDED.error,
+                payload = EXCLUDED.payload,
+                updated_at = EXCLUDED.updated_at,
+                expires_at = EXCLUDED.expires_at
+            "#,
+            &[
+                &record.session_id.to_string(),
+                &record.user_id,
+                &record.wallet_address,
+                &record.version,
+                &record.status,
+                &record.detail,
+                &record.privy_user_id,
+                &record.profile_name,
+                &record.instance_url,
+                &record.verify_url,
+                &record.eigen_app_id,
+                &record.error,
+                &record.payload,
+                &record.created_at,
+                &record.updated_at,
+                &record.expires_at,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list_frontdoor_sessions(&self) -> Result<Vec<FrontdoorSessionRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                r#"
+                SELECT id, user_id, wallet_address, version, status, detail,
+                       privy_user_id, profile_name, instance_url, verify_url,
+                       eigen_app_id, error, payload, created_at, updated_at, expires_at
+                FROM frontdoor_sessions
+                "#,
+                &[],
+            )
+            .await?;
+        let mut records = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let raw_id: String = row.get("id");
+            let Ok(session_id) = raw_id.parse() else {
+                tracing::warn!(session_id = %raw_id, "Skipping frontdoor session with invalid id");
+                continue;
+            };
+            records.push(FrontdoorSessionRecord {
+                session_id,
+                user_id: row.get("user_id"),
+                wallet_address: row.get("wallet_address"),
+                version: row.get("version"),
+                status: row.get("status"),
+                detail: row.get("detail"),
+                privy_user_id: row.get("privy_user_id"),
+                profile_name: row.get("profile_name"),
+                instance_url: row.get("instance_url"),
+                verify_url: row.get("verify_url"),
+                eigen_app_id: row.get("eigen_app_id"),
+                error: row.get("error"),
+                payload: row.get("payload"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+                expires_at: row.get("expires_at"),
+            });
+        }
+        Ok(records)
+    }
+
+    async fn delete_frontdoor_sessions_expired_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let count = conn
+            .execute(
+                "DELETE FROM frontdoor_sessions WHERE expires_at < $1",
+                &[&cutoff],
+            )
+            .await?;
+        Ok(count)
+    }
 }
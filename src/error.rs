@@ -49,6 +49,12 @@ pub enum Error {
 
     #[error("Worker error: {0}")]
     Worker(#[from] WorkerError),
+
+    #[error("Platform error: {0}")]
+    Platform(#[from] PlatformError),
+
+    #[error("Verification error: {0}")]
+    Verification(#[from] VerificationError),
 }
 
 /// Runtime pipeline stage for WS-2 status reporting.
@@ -211,6 +217,9 @@ pub enum DatabaseError {
     #[error("Serialization error: {0}")]
     Serialization(String),
 
+    #[error("Quota exceeded: {user_id} already has {limit} records")]
+    QuotaExceeded { user_id: String, limit: u32 },
+
     #[cfg(feature = "postgres")]
     #[error("PostgreSQL error: {0}")]
     Postgres(#[from] tokio_postgres::Error),
@@ -486,6 +495,63 @@ pub enum WorkerError {
     MissingToken,
 }
 
+/// Platform module governance errors.
+#[derive(Debug, thiserror::Error)]
+pub enum PlatformError {
+    #[error("Persisted module state lists {dropped} duplicate entries for: {}", module_ids.join(", "))]
+    DuplicateModuleIds {
+        module_ids: Vec<String>,
+        dropped: usize,
+    },
+
+    #[error("Module manifest file {path} is invalid: {reason}")]
+    ModuleManifestFile { path: String, reason: String },
+
+    #[error("Extra module '{module_id}' collides with an existing module id")]
+    ModuleIdCollision { module_id: String },
+
+    #[error("Module transition rejected: {}", violations.join(" "))]
+    ModuleTransitionRejected { violations: Vec<String> },
+
+    #[error("Module state is not valid JSON: {0}")]
+    ModuleStateSerialization(#[from] serde_json::Error),
+
+    #[error("Module state store error: {0}")]
+    ModuleStateStore(#[from] DatabaseError),
+}
+
+/// Verification receipt chain errors.
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+    #[error("Signed receipts are required but no signing key is configured")]
+    SigningKeyMissing,
+
+    #[error("Signing key {key_id} is invalid: {reason}")]
+    InvalidSigningKey { key_id: String, reason: String },
+
+    #[error("Receipt chain {path} is corrupt at line {line}: {reason}")]
+    ChainCorrupt {
+        path: String,
+        line: usize,
+        reason: String,
+    },
+
+    #[error(
+        "Receipt chain {path} line {line} is signed by {key_id:?} but no signing key is configured to check it"
+    )]
+    UnverifiedSignature {
+        path: String,
+        line: usize,
+        key_id: String,
+    },
+
+    #[error("Failed to serialize receipt: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Receipt chain IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
 fn is_mcp_related(value: &str) -> bool {
     value.to_ascii_lowercase().contains("mcp")
 }
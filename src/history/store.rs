@@ -211,7 +211,7 @@ impl Store {
         match row {
             Some(row) => {
                 let status_str: String = row.get("status");
-                let state = parse_job_state(&status_str);
+                let state = parse_job_state(&status_str)?;
                 let estimated_time_secs: Option<i32> = row.get("estimated_time_secs");
 
                 Ok(Some(JobContext {
@@ -265,6 +265,42 @@ impl Store {
         Ok(())
     }
 
+    /// Update job status only if the transition from its current state is legal.
+    pub async fn update_job_state_checked(
+        &self,
+        id: Uuid,
+        status: JobState,
+        failure_reason: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        let row = conn
+            .query_opt("SELECT status FROM agent_jobs WHERE id = $1", &[&id])
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound {
+                entity: "job".to_string(),
+                id: id.to_string(),
+            })?;
+        let current = parse_job_state(&row.get::<_, String>("status"))?;
+        if !JobState::is_valid_transition(current, status) {
+            return Err(DatabaseError::Constraint(format!(
+                "job {id} cannot transition from {current} to {status}"
+            )));
+        }
+
+        let updated = conn
+            .execute(
+                "UPDATE agent_jobs SET status = $2, failure_reason = $3 WHERE id = $1 AND status = $4",
+                &[&id, &status.to_string(), &failure_reason, &current.to_string()],
+            )
+            .await?;
+        if updated == 0 {
+            return Err(DatabaseError::Constraint(format!(
+                "job {id} changed state concurrently; expected {current}"
+            )));
+        }
+        Ok(())
+    }
+
     /// Mark job as stuck.
     pub async fn mark_job_stuck(&self, id: Uuid) -> Result<(), DatabaseError> {
         let conn = self.conn().await?;
@@ -994,6 +1030,29 @@ impl Store {
         rows.iter().map(row_to_routine).collect()
     }
 
+    /// List enabled routines whose next_fire_at <= now, soonest first.
+    pub async fn list_due_routines(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Routine>, DatabaseError> {
+        let conn = self.conn().await?;
+        let rows = conn
+            .query(
+                r#"
+                SELECT * FROM routines
+                WHERE enabled
+                  AND next_fire_at IS NOT NULL
+                  AND next_fire_at <= $1
+                ORDER BY next_fire_at ASC
+                LIMIT $2
+                "#,
+                &[&now, &limit],
+            )
+            .await?;
+        rows.iter().map(row_to_routine).collect()
+    }
+
     /// Update a routine (full replacement of mutable fields).
     pub async fn update_routine(&self, routine: &Routine) -> Result<(), DatabaseError> {
         let conn = self.conn().await?;
@@ -1260,6 +1319,19 @@ pub struct ConversationMessage {
     pub created_at: DateTime<Utc>,
 }
 
+/// Rolling summary of a conversation's older messages.
+///
+/// Covers every message up to and including `covered_through_message_id`;
+/// context is rebuilt from this summary plus the messages after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationRollingSummary {
+    pub conversation_id: Uuid,
+    pub summary: String,
+    pub token_count: i64,
+    pub covered_through_message_id: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[cfg(feature = "postgres")]
 impl Store {
     /// Ensure a conversation row exists for a given UUID.
@@ -1398,6 +1470,103 @@ impl Store {
         Ok(id)
     }
 
+    /// Insert or replace the rolling summary for a conversation.
+    pub async fn upsert_summary(
+        &self,
+        conversation_id: Uuid,
+        summary: &str,
+        token_count: i64,
+        covered_through_message_id: Uuid,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        let token_count = token_count as i32;
+        conn.execute(
+            r#"
+            INSERT INTO conversation_summaries
+                (conversation_id, summary, token_count, covered_through_message_id, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT (conversation_id) DO UPDATE SET
+                summary = EXCLUDED.summary,
+                token_count = EXCLUDED.token_count,
+                covered_through_message_id = EXCLUDED.covered_through_message_id,
+                updated_at = NOW()
+            "#,
+            &[
+                &conversation_id,
+                &summary,
+                &token_count,
+                &covered_through_message_id,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Read the rolling summary for a conversation, if one has been stored.
+    pub async fn get_summary(
+        &self,
+        conversation_id: Uuid,
+    ) -> Result<Option<ConversationRollingSummary>, DatabaseError> {
+        let conn = self.conn().await?;
+        let row = conn
+            .query_opt(
+                r#"
+                SELECT summary, token_count, covered_through_message_id, updated_at
+                FROM conversation_summaries
+                WHERE conversation_id = $1
+                "#,
+                &[&conversation_id],
+            )
+            .await?;
+        Ok(row.map(|r| ConversationRollingSummary {
+            conversation_id,
+            summary: r.get("summary"),
+            token_count: i64::from(r.get::<_, i32>("token_count")),
+            covered_through_message_id: r.get("covered_through_message_id"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Delete messages older than `cutoff` and the conversation shells they
+    /// leave behind. See `ConversationStore::prune_conversations_older_than`.
+    pub async fn prune_conversations_older_than(
+        &self,
+        cutoff: DateTime<Utc>,
+        user_id: Option<&str>,
+    ) -> Result<u64, DatabaseError> {
+        let mut conn = self.conn().await?;
+        let tx = conn.transaction().await?;
+
+        let messages = tx
+            .execute(
+                r#"
+                DELETE FROM conversation_messages m
+                USING conversations c
+                WHERE m.conversation_id = c.id
+                  AND m.created_at < $1
+                  AND ($2::TEXT IS NULL OR c.user_id = $2)
+                "#,
+                &[&cutoff, &user_id],
+            )
+            .await?;
+        let conversations = tx
+            .execute(
+                r#"
+                DELETE FROM conversations c
+                WHERE c.last_activity < $1
+                  AND ($2::TEXT IS NULL OR c.user_id = $2)
+                  AND NOT EXISTS (SELECT 1 FROM conversation_messages m WHERE m.conversation_id = c.id)
+                  AND NOT EXISTS (SELECT 1 FROM agent_jobs j WHERE j.conversation_id = c.id)
+                  AND NOT EXISTS (SELECT 1 FROM llm_calls l WHERE l.conversation_id = c.id)
+                "#,
+                &[&cutoff, &user_id],
+            )
+            .await?;
+
+        tx.commit().await?;
+        Ok(messages + conversations)
+    }
+
     /// Check whether a conversation belongs to the given user.
     pub async fn conversation_belongs_to_user(
         &self,
@@ -1532,18 +1701,9 @@ impl Store {
 }
 
 #[cfg(feature = "postgres")]
-fn parse_job_state(s: &str) -> JobState {
-    match s {
-        "pending" => JobState::Pending,
-        "in_progress" => JobState::InProgress,
-        "completed" => JobState::Completed,
-        "submitted" => JobState::Submitted,
-        "accepted" => JobState::Accepted,
-        "failed" => JobState::Failed,
-        "stuck" => JobState::Stuck,
-        "cancelled" => JobState::Cancelled,
-        _ => JobState::Pending,
-    }
+fn parse_job_state(s: &str) -> Result<JobState, DatabaseError> {
+    JobState::parse(s)
+        .ok_or_else(|| DatabaseError::Serialization(format!("unknown job status {s:?}")))
 }
 
 // ==================== Tool Failures ====================
@@ -1633,6 +1793,85 @@ impl Store {
 
         Ok(())
     }
+
+    /// Extend the user's failure streak for a tool; see
+    /// [`crate::db::ToolFailureStore::record_tool_failure_streak`].
+    pub async fn record_tool_failure_streak(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        let window_start = now - crate::db::TOOL_BACKOFF_WINDOW;
+
+        conn.execute(
+            r#"
+            INSERT INTO tool_failure_streaks
+                (tool_name, user_id, consecutive_failures, first_failure_at, last_failure_at)
+            VALUES ($1, $2, 1, $3, $3)
+            ON CONFLICT (tool_name, user_id) DO UPDATE SET
+                consecutive_failures = CASE
+                    WHEN tool_failure_streaks.last_failure_at >= $4
+                        THEN tool_failure_streaks.consecutive_failures + 1
+                    ELSE 1
+                END,
+                first_failure_at = CASE
+                    WHEN tool_failure_streaks.last_failure_at >= $4
+                        THEN tool_failure_streaks.first_failure_at
+                    ELSE $3
+                END,
+                last_failure_at = $3
+            "#,
+            &[&tool_name, &user_id, &now, &window_start],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Clear the user's failure streak for a tool.
+    pub async fn record_tool_success(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+
+        conn.execute(
+            "DELETE FROM tool_failure_streaks WHERE tool_name = $1 AND user_id = $2",
+            &[&tool_name, &user_id],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remaining backoff for the tool and user, if any.
+    pub async fn should_skip_tool(
+        &self,
+        tool_name: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<std::time::Duration>, DatabaseError> {
+        let conn = self.conn().await?;
+
+        let row = conn
+            .query_opt(
+                r#"
+                SELECT consecutive_failures, last_failure_at
+                FROM tool_failure_streaks
+                WHERE tool_name = $1 AND user_id = $2
+                "#,
+                &[&tool_name, &user_id],
+            )
+            .await?;
+
+        Ok(row.and_then(|row| {
+            let failures: i32 = row.get("consecutive_failures");
+            crate::db::tool_failure_backoff(i64::from(failures), row.get("last_failure_at"), now)
+        }))
+    }
 }
 
 // ==================== Settings ====================
@@ -1656,7 +1895,7 @@ impl Store {
         let conn = self.conn().await?;
         let row = conn
             .query_opt(
-                "SELECT value FROM settings WHERE user_id = $1 AND key = $2",
+                "SELECT value FROM settings WHERE user_id = $1 AND agent_id IS NULL AND key = $2",
                 &[&user_id, &key],
             )
             .await?;
@@ -1672,7 +1911,7 @@ impl Store {
         let conn = self.conn().await?;
         let row = conn
             .query_opt(
-                "SELECT key, value, updated_at FROM settings WHERE user_id = $1 AND key = $2",
+                "SELECT key, value, updated_at FROM settings WHERE user_id = $1 AND agent_id IS NULL AND key = $2",
                 &[&user_id, &key],
             )
             .await?;
@@ -1695,7 +1934,7 @@ impl Store {
             r#"
             INSERT INTO settings (user_id, key, value, updated_at)
             VALUES ($1, $2, $3, NOW())
-            ON CONFLICT (user_id, key) DO UPDATE SET
+            ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
                 value = EXCLUDED.value,
                 updated_at = NOW()
             "#,
@@ -1710,7 +1949,7 @@ impl Store {
         let conn = self.conn().await?;
         let count = conn
             .execute(
-                "DELETE FROM settings WHERE user_id = $1 AND key = $2",
+                "DELETE FROM settings WHERE user_id = $1 AND agent_id IS NULL AND key = $2",
                 &[&user_id, &key],
             )
             .await?;
@@ -1722,7 +1961,7 @@ impl Store {
         let conn = self.conn().await?;
         let rows = conn
             .query(
-                "SELECT key, value, updated_at FROM settings WHERE user_id = $1 ORDER BY key",
+                "SELECT key, value, updated_at FROM settings WHERE user_id = $1 AND agent_id IS NULL ORDER BY key",
                 &[&user_id],
             )
             .await?;
@@ -1744,7 +1983,7 @@ impl Store {
         let conn = self.conn().await?;
         let rows = conn
             .query(
-                "SELECT key, value FROM settings WHERE user_id = $1",
+                "SELECT key, value FROM settings WHERE user_id = $1 AND agent_id IS NULL",
                 &[&user_id],
             )
             .await?;
@@ -1758,6 +1997,52 @@ impl Store {
             .collect())
     }
 
+    /// Get the settings stored for a single agent (excluding user-global rows).
+    pub async fn get_all_settings_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+    ) -> Result<std::collections::HashMap<String, serde_json::Value>, DatabaseError> {
+        let conn = self.conn().await?;
+        let rows = conn
+            .query(
+                "SELECT key, value FROM settings WHERE user_id = $1 AND agent_id = $2",
+                &[&user_id, &agent_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let key: String = r.get("key");
+                let value: serde_json::Value = r.get("value");
+                (key, value)
+            })
+            .collect())
+    }
+
+    /// Set a single agent-scoped setting (upsert).
+    pub async fn set_setting_for_agent(
+        &self,
+        user_id: &str,
+        agent_id: Uuid,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        conn.execute(
+            r#"
+            INSERT INTO settings (user_id, agent_id, key, value, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT (user_id, agent_id, key) DO UPDATE SET
+                value = EXCLUDED.value,
+                updated_at = NOW()
+            "#,
+            &[&user_id, &agent_id, &key, value],
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Bulk-write settings (used for migration/import).
     ///
     /// Each entry is upserted individually within a single transaction.
@@ -1774,7 +2059,7 @@ impl Store {
                 r#"
                 INSERT INTO settings (user_id, key, value, updated_at)
                 VALUES ($1, $2, $3, NOW())
-                ON CONFLICT (user_id, key) DO UPDATE SET
+                ON CONFLICT (user_id, key) WHERE agent_id IS NULL DO UPDATE SET
                     value = EXCLUDED.value,
                     updated_at = NOW()
                 "#,
@@ -69,6 +69,7 @@ pub mod tools;
 pub mod tracing_fmt;
 pub mod tunnel;
 pub mod util;
+pub mod verification;
 pub mod worker;
 pub mod workspace;
 
@@ -169,7 +169,7 @@ fn create_ollama_provider(config: &LlmConfig) -> Result<Arc<dyn LlmProvider>, Ll
     Ok(Arc::new(RigAdapter::new(model, &oll.model)))
 }
 
-const TINFOIL_BASE_URL: &str = "https://inference.tinfoil.sh/v1";
+pub(crate) const TINFOIL_BASE_URL: &str = "https://inference.tinfoil.sh/v1";
 
 fn create_tinfoil_provider(config: &LlmConfig) -> Result<Arc<dyn LlmProvider>, LlmError> {
     let tf = config
@@ -298,12 +298,15 @@ mod tests {
     fn test_llm_config() -> LlmConfig {
         LlmConfig {
             backend: LlmBackend::NearAi,
+            fallbacks: Vec::new(),
             nearai: test_nearai_config(),
             openai: None,
             anthropic: None,
             ollama: None,
             openai_compatible: None,
             tinfoil: None,
+            startup_probe: false,
+            probe_timeout_ms: 5_000,
         }
     }
 
@@ -556,6 +556,7 @@ async fn main() -> anyhow::Result<()> {
                 } else {
                     LibSqlBackend::new_local(db_path).await?
                 };
+                let backend = backend.with_max_pool_size(config.database.pool_size);
                 backend.run_migrations().await?;
                 tracing::info!("libSQL database connected and migrations applied");
 
@@ -1419,11 +1420,26 @@ async fn main() -> anyhow::Result<()> {
         (None, None)
     };
 
+    let module_catalog = Arc::new(enclagent::platform::ModuleCatalog::load(
+        config.agent.module_manifests_path.as_deref(),
+    )?);
+    if let Some(ref path) = config.agent.module_manifests_path {
+        tracing::info!(
+            "Loaded {} module(s) into catalog from {}",
+            module_catalog.modules().len(),
+            path.display()
+        );
+    }
+
     // Add web gateway channel if configured
     let mut gateway_url: Option<String> = None;
     if let Some(ref gw_config) = config.channels.gateway {
-        let mut gw = GatewayChannel::new(gw_config.clone());
+        let mut gw = GatewayChannel::new(
+            gw_config.clone(),
+            enclagent::observability::create_metrics(&config.observability),
+        );
         gw = gw.with_llm_provider(Arc::clone(&llm));
+        gw = gw.with_module_catalog(Arc::clone(&module_catalog));
         if let Some(ref ws) = workspace {
             gw = gw.with_workspace(Arc::clone(ws));
         }
@@ -1460,6 +1476,10 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
+        if let Some(ref frontdoor) = gw.state().frontdoor {
+            frontdoor.spawn_reaper(&config.heartbeat);
+        }
+
         gateway_url = Some(format!(
             "http://{}:{}/?token={}",
             gw_config.host,
@@ -1502,6 +1522,7 @@ async fn main() -> anyhow::Result<()> {
         skills_config: config.skills.clone(),
         hooks,
         cost_guard,
+        module_catalog,
     };
     let agent = Agent::new(
         config.agent.clone(),
@@ -0,0 +1,361 @@
+//! Aggregate health report across configured subsystems.
+//!
+//! Each subsystem is checked by a [`HealthProbe`]. [`health_report`] wires the
+//! built-in probes (database, LLM, verification backend, secrets) from a
+//! [`Config`]; [`run_health_probes`] runs any set of probes and folds their
+//! outcomes into one [`HealthReport`].
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::config::{Config, LlmConfig, VerificationBackendConfig, VerificationBackendKind};
+#[cfg(feature = "libsql")]
+use crate::db::libsql::LibSqlBackend;
+use crate::secrets::SecretsCrypto;
+
+/// Health of one component, or of the whole report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Up,
+    Degraded,
+    Down,
+}
+
+impl HealthStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Degraded => "degraded",
+            Self::Down => "down",
+        }
+    }
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Result of probing a single component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    pub detail: String,
+    pub latency_ms: u64,
+}
+
+/// Per-component results plus the aggregate status.
+///
+/// The aggregate is `Up` when every component is up, `Down` when every
+/// component is down, and `Degraded` otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+}
+
+impl HealthReport {
+    pub fn component(&self, name: &str) -> Option<&ComponentHealth> {
+        self.components.iter().find(|c| c.name == name)
+    }
+}
+
+/// What a probe found, before timing is attached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeOutcome {
+    pub status: HealthStatus,
+    pub detail: String,
+}
+
+impl ProbeOutcome {
+    pub fn up(detail: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Up,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn degraded(detail: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Degraded,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn down(detail: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Down,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// A cheap liveness check for one subsystem.
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn check(&self) -> ProbeOutcome;
+}
+
+/// Probe every configured subsystem.
+///
+/// `db` and `secrets` are the live handles the caller already holds; a
+/// missing handle is reported rather than opened here.
+pub async fn health_report(
+    config: &Config,
+    #[cfg(feature = "libsql")] db: Option<&LibSqlBackend>,
+    secrets: Option<&SecretsCrypto>,
+) -> HealthReport {
+    #[cfg(feature = "libsql")]
+    let database = DatabaseProbe { db };
+    let llm = LlmProbe {
+        config: &config.llm,
+    };
+    let verification = VerificationProbe {
+        config: &config.verification_backend,
+    };
+    let secrets = SecretsProbe { crypto: secrets };
+
+    run_health_probes(&[
+        #[cfg(feature = "libsql")]
+        &database,
+        &llm,
+        &verification,
+        &secrets,
+    ])
+    .await
+}
+
+/// Run probes concurrently and aggregate their outcomes in input order.
+pub async fn run_health_probes(probes: &[&dyn HealthProbe]) -> HealthReport {
+    let components = futures::future::join_all(probes.iter().map(|probe| async move {
+        let started = Instant::now();
+        let outcome = probe.check().await;
+        ComponentHealth {
+            name: probe.name().to_string(),
+            status: outcome.status,
+            detail: outcome.detail,
+            latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }))
+    .await;
+
+    HealthReport {
+        status: aggregate_status(&components),
+        components,
+    }
+}
+
+fn aggregate_status(components: &[ComponentHealth]) -> HealthStatus {
+    if components.iter().all(|c| c.status == HealthStatus::Up) {
+        HealthStatus::Up
+    } else if components.iter().all(|c| c.status == HealthStatus::Down) {
+        HealthStatus::Down
+    } else {
+        HealthStatus::Degraded
+    }
+}
+
+#[cfg(feature = "libsql")]
+struct DatabaseProbe<'a> {
+    db: Option<&'a LibSqlBackend>,
+}
+
+#[cfg(feature = "libsql")]
+#[async_trait]
+impl HealthProbe for DatabaseProbe<'_> {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    async fn check(&self) -> ProbeOutcome {
+        let Some(db) = self.db else {
+            return ProbeOutcome::down("libSQL backend not connected");
+        };
+        match db.ping().await {
+            Ok(()) => ProbeOutcome::up("SELECT 1 succeeded"),
+            Err(e) => ProbeOutcome::down(format!("SELECT 1 failed: {e}")),
+        }
+    }
+}
+
+struct LlmProbe<'a> {
+    config: &'a LlmConfig,
+}
+
+#[async_trait]
+impl HealthProbe for LlmProbe<'_> {
+    fn name(&self) -> &str {
+        "llm"
+    }
+
+    async fn check(&self) -> ProbeOutcome {
+        match self.config.probe().await {
+            Ok(result) => ProbeOutcome::up(format!("{} serving {}", result.backend, result.model)),
+            Err(e) => ProbeOutcome::down(e.to_string()),
+        }
+    }
+}
+
+struct VerificationProbe<'a> {
+    config: &'a VerificationBackendConfig,
+}
+
+#[async_trait]
+impl HealthProbe for VerificationProbe<'_> {
+    fn name(&self) -> &str {
+        "verification"
+    }
+
+    async fn check(&self) -> ProbeOutcome {
+        if self.config.backend == VerificationBackendKind::FallbackOnly {
+            return ProbeOutcome::up("fallback_only; receipts are produced locally");
+        }
+
+        // With the signed fallback chain enabled an unreachable EigenCloud
+        // only degrades verification instead of stopping it.
+        let unavailable = |detail: String| {
+            if self.config.fallback.enabled {
+                ProbeOutcome::degraded(format!("{detail}; signed fallback receipts in use"))
+            } else {
+                ProbeOutcome::down(detail)
+            }
+        };
+
+        let Some(endpoint) = self
+            .config
+            .eigencloud
+            .endpoint
+            .as_deref()
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty())
+        else {
+            return unavailable("EigenCloud endpoint not configured".to_string());
+        };
+        let timeout = Duration::from_millis(self.config.eigencloud.timeout_ms.clamp(1_000, 20_000));
+        let client = match reqwest::Client::builder().timeout(timeout).build() {
+            Ok(client) => client,
+            Err(e) => return unavailable(format!("cannot construct HTTP client: {e}")),
+        };
+        let mut request = client.get(endpoint);
+        if let Some((name, value)) = self.config.eigencloud.auth_header() {
+            request = request.header(name, value);
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_server_error() => {
+                unavailable(format!("EigenCloud returned {}", response.status()))
+            }
+            Ok(response) => {
+                ProbeOutcome::up(format!("EigenCloud reachable ({})", response.status()))
+            }
+            Err(e) => unavailable(format!("EigenCloud unreachable: {e}")),
+        }
+    }
+}
+
+struct SecretsProbe<'a> {
+    crypto: Option<&'a SecretsCrypto>,
+}
+
+#[async_trait]
+impl HealthProbe for SecretsProbe<'_> {
+    fn name(&self) -> &str {
+        "secrets"
+    }
+
+    async fn check(&self) -> ProbeOutcome {
+        const CANARY: &str = "enclagent-health-check";
+
+        let Some(crypto) = self.crypto else {
+            return ProbeOutcome::degraded("secrets master key not configured");
+        };
+        let round_trip = crypto
+            .encrypt(CANARY.as_bytes())
+            .and_then(|(value, salt)| crypto.decrypt(&value, &salt));
+        match round_trip {
+            Ok(decrypted) if decrypted.expose() == CANARY => {
+                ProbeOutcome::up("encrypt/decrypt round trip succeeded")
+            }
+            Ok(_) => ProbeOutcome::down("decrypted canary did not match"),
+            Err(e) => ProbeOutcome::down(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secrecy::SecretString;
+
+    use super::*;
+
+    struct FakeProbe {
+        name: &'static str,
+        outcome: ProbeOutcome,
+    }
+
+    #[async_trait]
+    impl HealthProbe for FakeProbe {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn check(&self) -> ProbeOutcome {
+            self.outcome.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn db_up_llm_down_reports_degraded() {
+        let db = FakeProbe {
+            name: "database",
+            outcome: ProbeOutcome::up("SELECT 1 succeeded"),
+        };
+        let llm = FakeProbe {
+            name: "llm",
+            outcome: ProbeOutcome::down("Authentication failed for provider openai"),
+        };
+
+        let report = run_health_probes(&[&db, &llm]).await;
+
+        assert_eq!(report.status, HealthStatus::Degraded);
+        let names: Vec<&str> = report.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["database", "llm"]);
+        let db_health = report.component("database").expect("database component");
+        assert_eq!(db_health.status, HealthStatus::Up);
+        assert_eq!(db_health.detail, "SELECT 1 succeeded");
+        let llm_health = report.component("llm").expect("llm component");
+        assert_eq!(llm_health.status, HealthStatus::Down);
+        assert_eq!(
+            llm_health.detail,
+            "Authentication failed for provider openai"
+        );
+
+        assert_eq!(run_health_probes(&[&db]).await.status, HealthStatus::Up);
+        assert_eq!(run_health_probes(&[&llm]).await.status, HealthStatus::Down);
+    }
+
+    #[tokio::test]
+    async fn secrets_probe_round_trips_canary() {
+        let crypto = SecretsCrypto::new(SecretString::from(
+            "0123456789abcdef0123456789abcdef".to_string(),
+        ))
+        .expect("crypto");
+
+        let up = SecretsProbe {
+            crypto: Some(&crypto),
+        }
+        .check()
+        .await;
+        assert_eq!(up.status, HealthStatus::Up);
+
+        let missing = SecretsProbe { crypto: None }.check().await;
+        assert_eq!(missing.status, HealthStatus::Degraded);
+    }
+}
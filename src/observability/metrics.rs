@@ -0,0 +1,74 @@
+//! Named counter and histogram handle for component-level metrics.
+//!
+//! [`Observer`](crate::observability::Observer) covers agent lifecycle
+//! events with a fixed metric set; [`Metrics`] is the open-ended handle
+//! components such as the frontdoor use for labelled counters and latency
+//! histograms.
+
+use std::sync::Arc;
+
+use crate::observability::{ObservabilityBackend, ObservabilityConfig};
+
+/// Label set attached to a metric sample, as `(name, value)` pairs.
+pub type MetricLabels<'a> = &'a [(&'a str, &'a str)];
+
+/// Sink for labelled counters and histograms.
+pub trait Metrics: Send + Sync {
+    /// Add one to the counter `name` for `labels`.
+    fn increment_counter(&self, name: &str, labels: MetricLabels<'_>);
+
+    /// Record one `value` sample in the histogram `name` for `labels`.
+    fn record_histogram(&self, name: &str, value: f64, labels: MetricLabels<'_>);
+}
+
+/// Metrics handle that discards every sample.
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {
+    #[inline(always)]
+    fn increment_counter(&self, _name: &str, _labels: MetricLabels<'_>) {}
+
+    #[inline(always)]
+    fn record_histogram(&self, _name: &str, _value: f64, _labels: MetricLabels<'_>) {}
+}
+
+/// Metrics handle that emits each sample as a `tracing` debug event.
+pub struct LogMetrics;
+
+fn format_labels(labels: MetricLabels<'_>) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Metrics for LogMetrics {
+    fn increment_counter(&self, name: &str, labels: MetricLabels<'_>) {
+        tracing::debug!(
+            metric = name,
+            labels = %format_labels(labels),
+            "metrics: counter.increment"
+        );
+    }
+
+    fn record_histogram(&self, name: &str, value: f64, labels: MetricLabels<'_>) {
+        tracing::debug!(
+            metric = name,
+            value,
+            labels = %format_labels(labels),
+            "metrics: histogram.record"
+        );
+    }
+}
+
+/// Create a metrics handle matching the configured backend.
+///
+/// Returns [`LogMetrics`] for `stdout` and [`NoopMetrics`] otherwise, in
+/// step with [`create_observer`](crate::observability::create_observer).
+pub fn create_metrics(config: &ObservabilityConfig) -> Arc<dyn Metrics> {
+    match config.backend {
+        ObservabilityBackend::Stdout => Arc::new(LogMetrics),
+        _ => Arc::new(NoopMetrics),
+    }
+}
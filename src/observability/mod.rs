@@ -12,94 +12,296 @@
 //! [`ObservabilityConfig`]. Future backends (OpenTelemetry, Prometheus)
 //! can be added by implementing [`Observer`].
 
+pub mod health;
 mod log;
+mod metrics;
 mod multi;
 mod noop;
 pub mod traits;
 
+use std::net::SocketAddr;
+
+pub use self::health::{
+    ComponentHealth, HealthProbe, HealthReport, HealthStatus, ProbeOutcome, health_report,
+    run_health_probes,
+};
 pub use self::log::LogObserver;
+pub use self::metrics::{LogMetrics, MetricLabels, Metrics, NoopMetrics, create_metrics};
 pub use self::multi::MultiObserver;
 pub use self::noop::NoopObserver;
 pub use self::traits::{Observer, ObserverEvent, ObserverMetric};
 
+use crate::error::ConfigError;
+
+/// Default Prometheus scrape address when `PROMETHEUS_BIND` is unset.
+const DEFAULT_PROMETHEUS_BIND: &str = "127.0.0.1:9464";
+
+/// Telemetry backend selected by `OBSERVABILITY_BACKEND`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ObservabilityBackend {
+    /// Discard everything (`none`, `noop`, or unset).
+    #[default]
+    None,
+    /// Structured events via `tracing` (`stdout`, or the legacy `log`).
+    Stdout,
+    /// OpenTelemetry export (`otlp`) to `OTLP_ENDPOINT`, with optional
+    /// comma-separated `key=value` pairs from `OTLP_HEADERS`.
+    Otlp {
+        endpoint: String,
+        headers: Vec<(String, String)>,
+    },
+    /// Prometheus scrape endpoint (`prometheus`) bound to `PROMETHEUS_BIND`.
+    Prometheus { bind: SocketAddr },
+}
+
+impl ObservabilityBackend {
+    /// Canonical backend name as accepted by `OBSERVABILITY_BACKEND`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Stdout => "stdout",
+            Self::Otlp { .. } => "otlp",
+            Self::Prometheus { .. } => "prometheus",
+        }
+    }
+}
+
 /// Configuration for the observability backend.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ObservabilityConfig {
-    /// Backend name: "none", "noop", "log".
-    pub backend: String,
+    pub backend: ObservabilityBackend,
 }
 
-impl Default for ObservabilityConfig {
-    fn default() -> Self {
-        Self {
-            backend: "none".into(),
-        }
+impl ObservabilityConfig {
+    pub(crate) fn resolve() -> Result<Self, ConfigError> {
+        Self::from_lookup(crate::config::helpers::optional_env)
+    }
+
+    /// Parse the backend and its backend-specific variables through `lookup`.
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Result<Option<String>, ConfigError>,
+    ) -> Result<Self, ConfigError> {
+        let raw = lookup("OBSERVABILITY_BACKEND")?.unwrap_or_default();
+        let backend = match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "none" | "noop" => ObservabilityBackend::None,
+            "stdout" | "log" => ObservabilityBackend::Stdout,
+            "otlp" => {
+                let endpoint = lookup("OTLP_ENDPOINT")?
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| ConfigError::InvalidValue {
+                        key: "OTLP_ENDPOINT".to_string(),
+                        message: "required when OBSERVABILITY_BACKEND=otlp".to_string(),
+                    })?;
+                if !(endpoint.starts_with("http://") || endpoint.starts_with("https://")) {
+                    return Err(ConfigError::InvalidValue {
+                        key: "OTLP_ENDPOINT".to_string(),
+                        message: format!("must be an http(s) URL, got '{endpoint}'"),
+                    });
+                }
+                let headers = parse_otlp_headers(&lookup("OTLP_HEADERS")?.unwrap_or_default())?;
+                ObservabilityBackend::Otlp { endpoint, headers }
+            }
+            "prometheus" => {
+                let raw_bind = lookup("PROMETHEUS_BIND")?
+                    .unwrap_or_else(|| DEFAULT_PROMETHEUS_BIND.to_string());
+                let bind = raw_bind
+                    .trim()
+                    .parse()
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "PROMETHEUS_BIND".to_string(),
+                        message: format!("must be a socket address: {e}"),
+                    })?;
+                ObservabilityBackend::Prometheus { bind }
+            }
+            other => {
+                return Err(ConfigError::InvalidValue {
+                    key: "OBSERVABILITY_BACKEND".to_string(),
+                    message: format!(
+                        "unknown backend '{other}'; expected none, stdout, otlp, or prometheus"
+                    ),
+                });
+            }
+        };
+        Ok(Self { backend })
     }
 }
 
+fn parse_otlp_headers(raw: &str) -> Result<Vec<(String, String)>, ConfigError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(ConfigError::InvalidValue {
+                key: "OTLP_HEADERS".to_string(),
+                message: format!("expected comma-separated key=value pairs, got '{pair}'"),
+            }),
+        })
+        .collect()
+}
+
 /// Create an observer from configuration.
 ///
-/// Returns a [`NoopObserver`] for "none"/"noop" (or unknown values),
-/// and a [`LogObserver`] for "log".
+/// Returns a [`LogObserver`] for `stdout` and a [`NoopObserver`] otherwise;
+/// the OTLP and Prometheus exporters are not built in yet.
 pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
-    match config.backend.as_str() {
-        "log" => Box::new(LogObserver),
-        _ => Box::new(NoopObserver),
+    match &config.backend {
+        ObservabilityBackend::Stdout => Box::new(LogObserver),
+        ObservabilityBackend::None => Box::new(NoopObserver),
+        backend @ (ObservabilityBackend::Otlp { .. } | ObservabilityBackend::Prometheus { .. }) => {
+            tracing::warn!(
+                backend = backend.name(),
+                "Observability exporter is not available; discarding telemetry"
+            );
+            Box::new(NoopObserver)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use crate::error::ConfigError;
     use crate::observability::*;
 
+    fn resolve_with(vars: &[(&str, &str)]) -> Result<ObservabilityConfig, ConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        ObservabilityConfig::from_lookup(|key| Ok(vars.get(key).cloned()))
+    }
+
     #[test]
     fn default_config_is_none() {
         let cfg = ObservabilityConfig::default();
-        assert_eq!(cfg.backend, "none");
+        assert_eq!(cfg.backend, ObservabilityBackend::None);
     }
 
     #[test]
     fn factory_returns_noop_for_none() {
         let cfg = ObservabilityConfig {
-            backend: "none".into(),
+            backend: ObservabilityBackend::None,
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "noop");
     }
 
     #[test]
-    fn factory_returns_noop_for_empty() {
+    fn factory_returns_noop_for_unbuilt_exporter() {
         let cfg = ObservabilityConfig {
-            backend: String::new(),
+            backend: ObservabilityBackend::Prometheus {
+                bind: "127.0.0.1:9464".parse().unwrap(),
+            },
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "noop");
     }
 
     #[test]
-    fn factory_returns_noop_for_unknown() {
+    fn factory_returns_log_for_stdout() {
         let cfg = ObservabilityConfig {
-            backend: "prometheus".into(),
+            backend: ObservabilityBackend::Stdout,
         };
         let obs = create_observer(&cfg);
-        assert_eq!(obs.name(), "noop");
+        assert_eq!(obs.name(), "log");
     }
 
     #[test]
-    fn factory_returns_log_for_log() {
-        let cfg = ObservabilityConfig {
-            backend: "log".into(),
-        };
-        let obs = create_observer(&cfg);
-        assert_eq!(obs.name(), "log");
+    fn resolve_accepts_none_aliases() {
+        for value in ["", "none", "noop", " NONE "] {
+            let cfg = resolve_with(&[("OBSERVABILITY_BACKEND", value)]).unwrap();
+            assert_eq!(cfg.backend, ObservabilityBackend::None, "{value:?}");
+        }
+        assert_eq!(
+            resolve_with(&[]).unwrap().backend,
+            ObservabilityBackend::None
+        );
     }
 
     #[test]
-    fn factory_returns_noop_for_noop() {
-        let cfg = ObservabilityConfig {
-            backend: "noop".into(),
-        };
-        let obs = create_observer(&cfg);
-        assert_eq!(obs.name(), "noop");
+    fn resolve_maps_stdout_and_legacy_log() {
+        for value in ["stdout", "log"] {
+            let cfg = resolve_with(&[("OBSERVABILITY_BACKEND", value)]).unwrap();
+            assert_eq!(cfg.backend, ObservabilityBackend::Stdout);
+        }
+    }
+
+    #[test]
+    fn resolve_reads_otlp_endpoint_and_headers() {
+        let cfg = resolve_with(&[
+            ("OBSERVABILITY_BACKEND", "otlp"),
+            ("OTLP_ENDPOINT", "https://otel.example:4318"),
+            ("OTLP_HEADERS", "authorization=Bearer abc, x-tenant=ops"),
+        ])
+        .unwrap();
+        assert_eq!(
+            cfg.backend,
+            ObservabilityBackend::Otlp {
+                endpoint: "https://otel.example:4318".to_string(),
+                headers: vec![
+                    ("authorization".to_string(), "Bearer abc".to_string()),
+                    ("x-tenant".to_string(), "ops".to_string()),
+                ],
+            }
+        );
+
+        let missing = resolve_with(&[("OBSERVABILITY_BACKEND", "otlp")]).unwrap_err();
+        assert!(
+            matches!(&missing, ConfigError::InvalidValue { key, .. } if key == "OTLP_ENDPOINT")
+        );
+        let bad_headers = resolve_with(&[
+            ("OBSERVABILITY_BACKEND", "otlp"),
+            ("OTLP_ENDPOINT", "http://localhost:4318"),
+            ("OTLP_HEADERS", "no-equals-sign"),
+        ])
+        .unwrap_err();
+        assert!(
+            matches!(&bad_headers, ConfigError::InvalidValue { key, .. } if key == "OTLP_HEADERS")
+        );
+    }
+
+    #[test]
+    fn resolve_reads_prometheus_bind_with_default() {
+        let cfg = resolve_with(&[("OBSERVABILITY_BACKEND", "prometheus")]).unwrap();
+        assert_eq!(
+            cfg.backend,
+            ObservabilityBackend::Prometheus {
+                bind: "127.0.0.1:9464".parse().unwrap(),
+            }
+        );
+        let cfg = resolve_with(&[
+            ("OBSERVABILITY_BACKEND", "prometheus"),
+            ("PROMETHEUS_BIND", "0.0.0.0:9100"),
+        ])
+        .unwrap();
+        assert_eq!(
+            cfg.backend,
+            ObservabilityBackend::Prometheus {
+                bind: "0.0.0.0:9100".parse().unwrap(),
+            }
+        );
+        let err = resolve_with(&[
+            ("OBSERVABILITY_BACKEND", "prometheus"),
+            ("PROMETHEUS_BIND", "not-an-address"),
+        ])
+        .unwrap_err();
+        assert!(matches!(&err, ConfigError::InvalidValue { key, .. } if key == "PROMETHEUS_BIND"));
+    }
+
+    #[test]
+    fn resolve_rejects_unknown_backend() {
+        let err = resolve_with(&[("OBSERVABILITY_BACKEND", "otel")]).unwrap_err();
+        match err {
+            ConfigError::InvalidValue { key, message } => {
+                assert_eq!(key, "OBSERVABILITY_BACKEND");
+                assert!(message.contains("'otel'"), "{message}");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
     }
 }
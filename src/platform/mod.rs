@@ -1,15 +1,19 @@
 //! Platform primitives for module governance and org tenancy.
 //!
 //! This layer is intentionally lightweight and runtime-native:
-//! - Curated module catalog (Core-8)
+//! - Curated module catalog (Core-8), optionally extended from disk
 //! - Module state defaults/merge helpers
 //! - Org workspace + membership role helpers
 
 use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+use crate::db::SettingsStore;
+use crate::error::PlatformError;
+
 /// Capability descriptor exposed by a module manifest.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleCapability {
@@ -72,12 +76,28 @@ pub struct InferenceRouteDecision {
     pub rationale: String,
 }
 
+/// Machine-readable outcome of [`resolve_inference_route`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteReasonCode {
+    /// The requested module is enabled.
+    Allowed,
+    /// The requested module is a disabled optional addon.
+    AddonDisabled,
+    /// The requested core module is disabled; routed to `general` instead.
+    GeneralFallback,
+    /// The requested module and `general` are both disabled.
+    NoFallbackAvailable,
+}
+
 /// Result of intent routing after module-state enforcement.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceRouteResolution {
     pub requested_module_id: String,
     pub decision: InferenceRouteDecision,
     pub allowed: bool,
+    pub reason_code: RouteReasonCode,
+    /// Human-readable explanation of `reason_code`.
     pub reason: String,
 }
 
@@ -253,11 +273,117 @@ pub fn curated_module_catalog() -> Vec<ModuleManifest> {
     ]
 }
 
+/// Module catalog plus its capability index.
+///
+/// Built once at startup and shared by the agent and the web gateway; every
+/// catalog helper in this module takes it explicitly.
+#[derive(Debug, Clone)]
+pub struct ModuleCatalog {
+    modules: Vec<ModuleManifest>,
+    /// Capability key → ids of the modules providing it, in catalog order.
+    capability_index: HashMap<String, Vec<String>>,
+}
+
+impl ModuleCatalog {
+    pub fn new(modules: Vec<ModuleManifest>) -> Self {
+        let mut capability_index: HashMap<String, Vec<String>> = HashMap::new();
+        for module in &modules {
+            for cap in &module.capabilities {
+                let module_ids = capability_index.entry(cap.key.clone()).or_default();
+                if module_ids.last() != Some(&module.id) {
+                    module_ids.push(module.id.clone());
+                }
+            }
+        }
+        Self {
+            modules,
+            capability_index,
+        }
+    }
+
+    /// The curated Core-8 catalog with no extra modules.
+    pub fn curated() -> Self {
+        Self::new(curated_module_catalog())
+    }
+
+    /// The curated catalog, plus the extra manifests at `extra_manifests`
+    /// when set (see [`load_module_catalog`]).
+    pub fn load(extra_manifests: Option<&Path>) -> Result<Self, PlatformError> {
+        match extra_manifests {
+            Some(path) => Ok(Self::new(load_module_catalog(path)?)),
+            None => Ok(Self::curated()),
+        }
+    }
+
+    pub fn modules(&self) -> &[ModuleManifest] {
+        &self.modules
+    }
+
+    pub fn manifest(&self, module_id: &str) -> Option<&ModuleManifest> {
+        self.modules.iter().find(|m| m.id == module_id)
+    }
+
+    pub fn contains(&self, module_id: &str) -> bool {
+        self.manifest(module_id).is_some()
+    }
+
+    /// Ids of the modules declaring `capability`, in catalog order.
+    pub fn module_ids_for_capability(&self, capability: &str) -> &[String] {
+        self.capability_index
+            .get(capability)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// On-disk list of extra module manifests (`modules = [...]`).
+#[derive(Debug, Deserialize)]
+struct ModuleManifestFile {
+    modules: Vec<ModuleManifest>,
+}
+
+/// Load extra module manifests from `extra_manifests` and merge them after
+/// the Core-8.
+///
+/// The file holds a `modules` list as TOML (`.toml`) or JSON (anything
+/// else). Extra ids must be non-empty and may not collide with the curated
+/// catalog or with each other.
+pub fn load_module_catalog(extra_manifests: &Path) -> Result<Vec<ModuleManifest>, PlatformError> {
+    let read_error = |reason: String| PlatformError::ModuleManifestFile {
+        path: extra_manifests.display().to_string(),
+        reason,
+    };
+    let raw = std::fs::read_to_string(extra_manifests).map_err(|e| read_error(e.to_string()))?;
+    let is_toml = extra_manifests
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+    let file: ModuleManifestFile = if is_toml {
+        toml::from_str(&raw).map_err(|e| read_error(e.to_string()))?
+    } else {
+        serde_json::from_str(&raw).map_err(|e| read_error(e.to_string()))?
+    };
+
+    let mut catalog = curated_module_catalog();
+    for manifest in file.modules {
+        if manifest.id.trim().is_empty() {
+            return Err(read_error("module id must not be empty".to_string()));
+        }
+        if catalog.iter().any(|m| m.id == manifest.id) {
+            return Err(PlatformError::ModuleIdCollision {
+                module_id: manifest.id,
+            });
+        }
+        catalog.push(manifest);
+    }
+    Ok(catalog)
+}
+
 /// Default module state vector in catalog order.
-pub fn default_module_states() -> Vec<ModuleState> {
+pub fn default_module_states(catalog: &ModuleCatalog) -> Vec<ModuleState> {
     let now = now_rfc3339();
-    curated_module_catalog()
-        .into_iter()
+    catalog
+        .modules()
+        .iter()
         .map(|manifest| ModuleState {
             module_id: manifest.id.clone(),
             enabled: manifest.enabled_by_default,
@@ -272,23 +398,41 @@ pub fn default_module_states() -> Vec<ModuleState> {
         .collect()
 }
 
-/// Merge persisted module state into the curated catalog and return normalized state.
+/// Merge persisted module state into the module catalog and return normalized state.
 ///
 /// Unknown module IDs in persisted data are discarded; missing catalog modules are
-/// populated from defaults.
-pub fn merge_module_states(persisted: Vec<ModuleState>) -> Vec<ModuleState> {
+/// populated from defaults. Repeated module IDs indicate a corrupt write and are
+/// rejected rather than letting the last entry silently win.
+pub fn merge_module_states(
+    catalog: &ModuleCatalog,
+    persisted: Vec<ModuleState>,
+) -> Result<Vec<ModuleState>, PlatformError> {
     let mut persisted_map: HashMap<String, ModuleState> = HashMap::new();
+    let mut duplicate_ids = BTreeSet::new();
+    let mut dropped = 0usize;
     for item in persisted {
+        if persisted_map.contains_key(&item.module_id) {
+            duplicate_ids.insert(item.module_id.clone());
+            dropped += 1;
+            continue;
+        }
         persisted_map.insert(item.module_id.clone(), item);
     }
+    if dropped > 0 {
+        return Err(PlatformError::DuplicateModuleIds {
+            module_ids: duplicate_ids.into_iter().collect(),
+            dropped,
+        });
+    }
 
     let now = now_rfc3339();
-    curated_module_catalog()
-        .into_iter()
+    Ok(catalog
+        .modules()
+        .iter()
         .map(|manifest| {
             if let Some(item) = persisted_map.remove(&manifest.id) {
                 ModuleState {
-                    module_id: manifest.id,
+                    module_id: manifest.id.clone(),
                     enabled: item.enabled,
                     status: if item.enabled {
                         "enabled".to_string()
@@ -300,7 +444,7 @@ pub fn merge_module_states(persisted: Vec<ModuleState>) -> Vec<ModuleState> {
                 }
             } else {
                 ModuleState {
-                    module_id: manifest.id,
+                    module_id: manifest.id.clone(),
                     enabled: manifest.enabled_by_default,
                     status: if manifest.enabled_by_default {
                         "enabled".to_string()
@@ -312,26 +456,24 @@ pub fn merge_module_states(persisted: Vec<ModuleState>) -> Vec<ModuleState> {
                 }
             }
         })
-        .collect()
+        .collect())
 }
 
-/// Return true if the module ID is part of the curated catalog.
-pub fn module_exists(module_id: &str) -> bool {
-    curated_module_catalog().iter().any(|m| m.id == module_id)
+/// Return true if the module ID is part of the module catalog.
+pub fn module_exists(catalog: &ModuleCatalog, module_id: &str) -> bool {
+    catalog.contains(module_id)
 }
 
 /// Lookup a module manifest by ID.
-pub fn module_manifest_by_id(module_id: &str) -> Option<ModuleManifest> {
-    curated_module_catalog()
-        .into_iter()
-        .find(|m| m.id == module_id)
+pub fn module_manifest_by_id(catalog: &ModuleCatalog, module_id: &str) -> Option<ModuleManifest> {
+    catalog.manifest(module_id).cloned()
 }
 
 /// Return true if a module is an optional addon module.
-pub fn module_is_optional_addon(module_id: &str) -> bool {
-    module_manifest_by_id(module_id)
-        .map(|manifest| manifest.optional_addon)
-        .unwrap_or(false)
+pub fn module_is_optional_addon(catalog: &ModuleCatalog, module_id: &str) -> bool {
+    catalog
+        .manifest(module_id)
+        .is_some_and(|manifest| manifest.optional_addon)
 }
 
 /// Return true if the given module is enabled in runtime state.
@@ -344,18 +486,57 @@ pub fn module_is_enabled(states: &[ModuleState], module_id: &str) -> bool {
 }
 
 /// Return capability keys for a module ID.
-pub fn module_capability_keys(module_id: &str) -> Vec<String> {
-    module_manifest_by_id(module_id)
+pub fn module_capability_keys(catalog: &ModuleCatalog, module_id: &str) -> Vec<String> {
+    catalog
+        .manifest(module_id)
         .map(|manifest| {
             manifest
                 .capabilities
-                .into_iter()
-                .map(|capability| capability.key)
+                .iter()
+                .map(|capability| capability.key.clone())
                 .collect()
         })
         .unwrap_or_default()
 }
 
+/// Slash commands known to the agent command router.
+pub const COMMAND_TABLE: &[&str] = &[
+    "help",
+    "model",
+    "version",
+    "tools",
+    "ping",
+    "debug",
+    "positions",
+    "exposure",
+    "funding",
+    "vault",
+    "risk",
+    "pause-agent",
+    "resume-agent",
+    "verify",
+    "receipts",
+    "copy-policy",
+    "copy-status",
+    "connectors",
+    "heartbeat",
+    "summarize",
+    "suggest",
+    "quit",
+    "job",
+    "status",
+    "cancel",
+    "list",
+    "undo",
+    "redo",
+    "compact",
+    "clear",
+    "interrupt",
+    "new",
+    "thread",
+    "resume",
+];
+
 /// Return capability requirements for a command.
 pub fn command_required_capabilities(command: &str) -> &'static [&'static str] {
     const HYPERLIQUID_CAPS: &[&str] = &["hyperliquid_execute"];
@@ -378,24 +559,150 @@ pub fn command_required_capabilities(command: &str) -> &'static [&'static str] {
 /// Return capability requirements for a tool.
 pub fn tool_required_capabilities(tool_name: &str) -> &'static [&'static str] {
     const HYPERLIQUID_CAPS: &[&str] = &["hyperliquid_execute"];
-    const EIGENDA_CAPS: &[&str] = &["artifact_commitment"];
+    const EIGENDA_COMMIT_CAPS: &[&str] = &["artifact_commitment"];
+    const EIGENDA_POINTER_CAPS: &[&str] = &["da_pointer"];
 
     let normalized = tool_name.trim().to_ascii_lowercase();
     if normalized == "hyperliquid_execute" || normalized.starts_with("hyperliquid_") {
         return HYPERLIQUID_CAPS;
     }
+    // Pointer reads only need `da_pointer`; anything else under `eigenda_`
+    // is treated as a commit.
+    if normalized.starts_with("eigenda_pointer") || normalized.starts_with("eigenda_get") {
+        return EIGENDA_POINTER_CAPS;
+    }
     if normalized.starts_with("eigenda_") {
-        return EIGENDA_CAPS;
+        return EIGENDA_COMMIT_CAPS;
     }
     &[]
 }
 
-fn module_ids_for_capability(capability: &str) -> Vec<String> {
-    curated_module_catalog()
+/// Validate a module enable/disable transition before it is persisted.
+///
+/// Returns every violation as a human-readable message:
+/// - `general` cannot be disabled while other core modules are disabled,
+///   since routing would have no fallback for those domains.
+/// - An addon cannot be enabled while a required capability it declares is
+///   also provided by a disabled module.
+pub fn validate_module_transition(
+    catalog: &ModuleCatalog,
+    current: &[ModuleState],
+    desired: &[ModuleState],
+) -> Result<(), Vec<String>> {
+    let catalog = catalog.modules();
+    let newly_disabled = |module_id: &str| {
+        module_is_enabled(current, module_id) && !module_is_enabled(desired, module_id)
+    };
+    let newly_enabled = |module_id: &str| {
+        !module_is_enabled(current, module_id) && module_is_enabled(desired, module_id)
+    };
+
+    let mut violations = Vec::new();
+
+    let disabled_core: Vec<&str> = catalog
+        .iter()
+        .filter(|m| !m.optional_addon && m.id != "general")
+        .filter(|m| !module_is_enabled(desired, &m.id))
+        .map(|m| m.id.as_str())
+        .collect();
+    if !module_is_enabled(desired, "general")
+        && !disabled_core.is_empty()
+        && (newly_disabled("general") || disabled_core.iter().any(|id| newly_disabled(id)))
+    {
+        violations.push(format!(
+            "Cannot disable 'general' while other core modules are disabled: {}.",
+            disabled_core.join(", ")
+        ));
+    }
+
+    for addon in catalog
+        .iter()
+        .filter(|m| m.optional_addon && newly_enabled(&m.id))
+    {
+        for capability in addon.capabilities.iter().filter(|cap| cap.required) {
+            let disabled_providers: Vec<&str> = catalog
+                .iter()
+                .filter(|m| m.id != addon.id)
+                .filter(|m| m.capabilities.iter().any(|cap| cap.key == capability.key))
+                .filter(|m| !module_is_enabled(desired, &m.id))
+                .map(|m| m.id.as_str())
+                .collect();
+            if !disabled_providers.is_empty() {
+                violations.push(format!(
+                    "Cannot enable addon '{}': required capability '{}' maps to disabled module(s): {}.",
+                    addon.id,
+                    capability.key,
+                    disabled_providers.join(", ")
+                ));
+            }
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Apply several module toggles for `user_id` as one update.
+///
+/// `states` is overlaid on the stored state, normalized with
+/// [`merge_module_states`], and checked with [`validate_module_transition`]
+/// before the whole vector is written under [`PLATFORM_MODULE_STATE_KEY`] in
+/// a single settings write. A rejected update writes nothing. Returns the
+/// persisted state.
+pub async fn set_module_states<S>(
+    store: &S,
+    catalog: &ModuleCatalog,
+    user_id: &str,
+    states: Vec<ModuleState>,
+) -> Result<Vec<ModuleState>, PlatformError>
+where
+    S: SettingsStore + ?Sized,
+{
+    let current = match store
+        .get_setting(user_id, PLATFORM_MODULE_STATE_KEY)
+        .await?
+    {
+        Some(value) => {
+            let stored: Vec<ModuleState> = serde_json::from_value(value)?;
+            if stored.is_empty() {
+                default_module_states(catalog)
+            } else {
+                merge_module_states(catalog, stored)?
+            }
+        }
+        None => default_module_states(catalog),
+    };
+
+    let now = now_rfc3339();
+    let mut overlay: Vec<ModuleState> = states
         .into_iter()
-        .filter(|module| module.capabilities.iter().any(|cap| cap.key == capability))
-        .map(|module| module.id)
-        .collect()
+        .map(|state| ModuleState {
+            updated_at: now.clone(),
+            ..state
+        })
+        .collect();
+    let requested: BTreeSet<String> = overlay.iter().map(|s| s.module_id.clone()).collect();
+    overlay.extend(
+        current
+            .iter()
+            .filter(|state| !requested.contains(&state.module_id))
+            .cloned(),
+    );
+    let desired = merge_module_states(catalog, overlay)?;
+    validate_module_transition(catalog, &current, &desired)
+        .map_err(|violations| PlatformError::ModuleTransitionRejected { violations })?;
+
+    store
+        .set_setting(
+            user_id,
+            PLATFORM_MODULE_STATE_KEY,
+            &serde_json::to_value(&desired)?,
+        )
+        .await?;
+    Ok(desired)
 }
 
 /// Resolve capability requirements against current module state.
@@ -403,6 +710,7 @@ fn module_ids_for_capability(capability: &str) -> Vec<String> {
 /// This is the execution-layer policy gate for commands and tools. Every
 /// required capability must be backed by at least one enabled module.
 pub fn resolve_capability_guard(
+    catalog: &ModuleCatalog,
     required_capabilities: &[&str],
     states: &[ModuleState],
 ) -> CapabilityGuardResolution {
@@ -427,7 +735,7 @@ pub fn resolve_capability_guard(
     let mut blocked_capabilities = Vec::new();
     let mut reason_segments = Vec::new();
     for capability in &required {
-        let module_ids = module_ids_for_capability(capability);
+        let module_ids = catalog.module_ids_for_capability(capability);
         if module_ids.is_empty() {
             blocked_capabilities.push(capability.clone());
             reason_segments.push(format!(
@@ -467,16 +775,63 @@ pub fn resolve_capability_guard(
     }
 }
 
-fn contains_any_lower(haystack: &str, patterns: &[&str]) -> bool {
-    patterns.iter().any(|pattern| haystack.contains(pattern))
+/// Resolve capability guards for a batch of commands in one pass.
+///
+/// Lookups go through the catalog's capability index, so pre-computing a
+/// whole command table never rescans the manifests.
+pub fn resolve_capability_guards_batch(
+    catalog: &ModuleCatalog,
+    commands: &[&str],
+    states: &[ModuleState],
+) -> HashMap<String, CapabilityGuardResolution> {
+    commands
+        .iter()
+        .map(|command| {
+            let resolution =
+                resolve_capability_guard(catalog, command_required_capabilities(command), states);
+            (command.to_string(), resolution)
+        })
+        .collect()
 }
 
-/// Layer-2 intent/domain router that maps user input into a module decision.
-pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
-    let lower = input.to_ascii_lowercase();
+/// Return the commands from [`COMMAND_TABLE`] that current module state allows.
+pub fn allowed_commands(catalog: &ModuleCatalog, states: &[ModuleState]) -> Vec<String> {
+    let resolutions = resolve_capability_guards_batch(catalog, COMMAND_TABLE, states);
+    COMMAND_TABLE
+        .iter()
+        .filter(|command| {
+            resolutions
+                .get(**command)
+                .is_some_and(|guard| guard.allowed)
+        })
+        .map(|command| command.to_string())
+        .collect()
+}
+
+/// One keyword rule for the layer-2 intent router.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouterRule {
+    pub module_id: String,
+    pub keywords: Vec<String>,
+    pub confidence: f64,
+    #[serde(default)]
+    pub rationale: Option<String>,
+}
+
+/// Keyword table for the layer-2 intent router, evaluated in priority order.
+///
+/// The first rule with a keyword contained in the (lowercased) input wins;
+/// input matching no rule routes to `general`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouterRules {
+    pub rules: Vec<RouterRule>,
+}
 
-    let (module_id, confidence, rationale) = if contains_any_lower(
-        &lower,
+const DEFAULT_ROUTER_RULES: &[(&str, f64, &str, &[&str])] = &[
+    (
+        "hyperliquid_addon",
+        0.93,
+        "Matched trading or Hyperliquid intent markers.",
         &[
             "hyperliquid",
             "/vault",
@@ -493,14 +848,11 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "liquidation",
             "funding rate",
         ],
-    ) {
-        (
-            "hyperliquid_addon",
-            0.93,
-            "Matched trading or Hyperliquid intent markers.",
-        )
-    } else if contains_any_lower(
-        &lower,
+    ),
+    (
+        "eigenda_addon",
+        0.91,
+        "Matched data-availability commitment intent markers.",
         &[
             "eigenda",
             "data availability",
@@ -508,14 +860,11 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "blob commitment",
             "availability layer",
         ],
-    ) {
-        (
-            "eigenda_addon",
-            0.91,
-            "Matched data-availability commitment intent markers.",
-        )
-    } else if contains_any_lower(
-        &lower,
+    ),
+    (
+        "developer",
+        0.82,
+        "Matched software development workflow markers.",
         &[
             "code",
             "repo",
@@ -532,14 +881,11 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "stack trace",
             "api",
         ],
-    ) {
-        (
-            "developer",
-            0.82,
-            "Matched software development workflow markers.",
-        )
-    } else if contains_any_lower(
-        &lower,
+    ),
+    (
+        "creative",
+        0.8,
+        "Matched creative ideation or content markers.",
         &[
             "story",
             "poem",
@@ -551,14 +897,11 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "ad copy",
             "moodboard",
         ],
-    ) {
-        (
-            "creative",
-            0.8,
-            "Matched creative ideation or content markers.",
-        )
-    } else if contains_any_lower(
-        &lower,
+    ),
+    (
+        "research",
+        0.81,
+        "Matched synthesis, evidence, or research markers.",
         &[
             "research",
             "analyze",
@@ -570,14 +913,11 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "whitepaper",
             "summarize",
         ],
-    ) {
-        (
-            "research",
-            0.81,
-            "Matched synthesis, evidence, or research markers.",
-        )
-    } else if contains_any_lower(
-        &lower,
+    ),
+    (
+        "business_ops",
+        0.79,
+        "Matched planning and business-operations markers.",
         &[
             "roadmap",
             "okr",
@@ -589,14 +929,11 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "project plan",
             "process",
         ],
-    ) {
-        (
-            "business_ops",
-            0.79,
-            "Matched planning and business-operations markers.",
-        )
-    } else if contains_any_lower(
-        &lower,
+    ),
+    (
+        "communications",
+        0.78,
+        "Matched communication drafting markers.",
         &[
             "email",
             "message",
@@ -608,25 +945,61 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
             "reply",
             "draft",
         ],
-    ) {
-        (
-            "communications",
-            0.78,
-            "Matched communication drafting markers.",
-        )
-    } else {
-        (
-            "general",
+    ),
+];
+
+impl Default for RouterRules {
+    fn default() -> Self {
+        Self {
+            rules: DEFAULT_ROUTER_RULES
+                .iter()
+                .map(|(module_id, confidence, rationale, keywords)| RouterRule {
+                    module_id: module_id.to_string(),
+                    keywords: keywords.iter().map(|k| k.to_string()).collect(),
+                    confidence: *confidence,
+                    rationale: Some(rationale.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Layer-2 intent/domain router that maps user input into a module decision.
+pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
+    infer_route_decision_with_rules(input, &RouterRules::default())
+}
+
+/// Layer-2 router driven by a caller-supplied keyword table.
+pub fn infer_route_decision_with_rules(input: &str, rules: &RouterRules) -> InferenceRouteDecision {
+    let lower = input.to_ascii_lowercase();
+
+    let matched = rules.rules.iter().find(|rule| {
+        rule.keywords.iter().any(|keyword| {
+            let keyword = keyword.trim().to_ascii_lowercase();
+            !keyword.is_empty() && lower.contains(&keyword)
+        })
+    });
+
+    let (module_id, confidence, rationale) = match matched {
+        Some(rule) => (
+            rule.module_id.clone(),
+            rule.confidence,
+            rule.rationale
+                .clone()
+                .unwrap_or_else(|| format!("Matched configured markers for '{}'.", rule.module_id)),
+        ),
+        None => (
+            "general".to_string(),
             0.55,
-            "No domain-specific markers matched; using general baseline.",
-        )
+            "No domain-specific markers matched; using general baseline.".to_string(),
+        ),
     };
 
     InferenceRouteDecision {
         layer: "layer2_intent_domain_router".to_string(),
-        module_id: module_id.to_string(),
+        module_id,
         confidence,
-        rationale: rationale.to_string(),
+        rationale,
     }
 }
 
@@ -637,8 +1010,22 @@ pub fn infer_route_decision(input: &str) -> InferenceRouteDecision {
 /// - If a disabled module is an addon, block.
 /// - If a disabled module is core and `general` is enabled, fallback to `general`.
 /// - Otherwise block.
-pub fn resolve_inference_route(input: &str, states: &[ModuleState]) -> InferenceRouteResolution {
-    let mut decision = infer_route_decision(input);
+pub fn resolve_inference_route(
+    catalog: &ModuleCatalog,
+    input: &str,
+    states: &[ModuleState],
+) -> InferenceRouteResolution {
+    resolve_inference_route_with_rules(catalog, input, states, &RouterRules::default())
+}
+
+/// [`resolve_inference_route`] with a caller-supplied router keyword table.
+pub fn resolve_inference_route_with_rules(
+    catalog: &ModuleCatalog,
+    input: &str,
+    states: &[ModuleState],
+    rules: &RouterRules,
+) -> InferenceRouteResolution {
+    let mut decision = infer_route_decision_with_rules(input, rules);
     let requested_module_id = decision.module_id.clone();
 
     if module_is_enabled(states, &requested_module_id) {
@@ -646,15 +1033,20 @@ pub fn resolve_inference_route(input: &str, states: &[ModuleState]) -> Inference
             requested_module_id,
             decision,
             allowed: true,
+            reason_code: RouteReasonCode::Allowed,
             reason: "Requested module is enabled.".to_string(),
         };
     }
 
-    if module_is_optional_addon(&requested_module_id) {
+    if catalog
+        .manifest(&requested_module_id)
+        .is_some_and(|manifest| manifest.optional_addon)
+    {
         return InferenceRouteResolution {
             requested_module_id: requested_module_id.clone(),
             decision,
             allowed: false,
+            reason_code: RouteReasonCode::AddonDisabled,
             reason: format!("Optional addon '{requested_module_id}' is disabled."),
         };
     }
@@ -669,6 +1061,7 @@ pub fn resolve_inference_route(input: &str, states: &[ModuleState]) -> Inference
             requested_module_id,
             decision,
             allowed: true,
+            reason_code: RouteReasonCode::GeneralFallback,
             reason: "Requested module disabled; general fallback applied.".to_string(),
         };
     }
@@ -677,6 +1070,7 @@ pub fn resolve_inference_route(input: &str, states: &[ModuleState]) -> Inference
         requested_module_id,
         decision,
         allowed: false,
+        reason_code: RouteReasonCode::NoFallbackAvailable,
         reason: "Requested module disabled and general fallback is unavailable.".to_string(),
     }
 }
@@ -743,6 +1137,10 @@ fn slugify(value: &str) -> String {
 mod tests {
     use super::*;
 
+    fn catalog() -> ModuleCatalog {
+        ModuleCatalog::curated()
+    }
+
     #[test]
     fn curated_catalog_contains_core_8() {
         let catalog = curated_module_catalog();
@@ -754,7 +1152,7 @@ mod tests {
 
     #[test]
     fn defaults_disable_addons() {
-        let states = default_module_states();
+        let states = default_module_states(&catalog());
         let hl = states
             .iter()
             .find(|m| m.module_id == "hyperliquid_addon")
@@ -767,6 +1165,181 @@ mod tests {
         assert!(!eigenda.enabled);
     }
 
+    #[test]
+    fn merge_reports_duplicate_module_ids() {
+        let state = |enabled: bool| ModuleState {
+            module_id: "developer".to_string(),
+            enabled,
+            status: if enabled { "enabled" } else { "disabled" }.to_string(),
+            updated_at: now_rfc3339(),
+            config: serde_json::json!({}),
+        };
+
+        let err = merge_module_states(&catalog(), vec![state(true), state(false)]).unwrap_err();
+        match err {
+            PlatformError::DuplicateModuleIds {
+                module_ids,
+                dropped,
+            } => {
+                assert_eq!(module_ids, vec!["developer".to_string()]);
+                assert_eq!(dropped, 1);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let mut unknown = state(true);
+        unknown.module_id = "retired_module".to_string();
+        let merged = merge_module_states(&catalog(), vec![state(false), unknown]).expect("merge");
+        assert_eq!(merged.len(), curated_module_catalog().len());
+        assert!(!module_is_enabled(&merged, "developer"));
+        assert!(!merged.iter().any(|m| m.module_id == "retired_module"));
+    }
+
+    fn with_enabled(states: &[ModuleState], module_id: &str, enabled: bool) -> Vec<ModuleState> {
+        let mut states = states.to_vec();
+        let state = states
+            .iter_mut()
+            .find(|s| s.module_id == module_id)
+            .expect("module state");
+        state.enabled = enabled;
+        state.status = if enabled { "enabled" } else { "disabled" }.to_string();
+        states
+    }
+
+    #[test]
+    fn transition_rejects_disabling_general_with_core_module_disabled() {
+        let current = with_enabled(&default_module_states(&catalog()), "developer", false);
+        let desired = with_enabled(&current, "general", false);
+
+        let violations = validate_module_transition(&catalog(), &current, &desired).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("Cannot disable 'general'"));
+        assert!(violations[0].contains("developer"));
+
+        // Same end state reached by disabling the core module second.
+        let current = with_enabled(&default_module_states(&catalog()), "general", false);
+        let desired = with_enabled(&current, "research", false);
+        assert!(validate_module_transition(&catalog(), &current, &desired).is_err());
+    }
+
+    #[test]
+    fn transition_rejects_addon_whose_capability_maps_to_disabled_module() {
+        let mut modules = curated_module_catalog();
+        let addon = modules
+            .iter_mut()
+            .find(|m| m.id == "hyperliquid_addon")
+            .expect("hyperliquid addon");
+        addon
+            .capabilities
+            .push(capability("verification_lineage", "Shared lineage", true));
+
+        let current = with_enabled(&default_module_states(&catalog()), "general", false);
+        let desired = with_enabled(&current, "hyperliquid_addon", true);
+
+        let violations =
+            validate_module_transition(&ModuleCatalog::new(modules), &current, &desired)
+                .unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("hyperliquid_addon"));
+        assert!(violations[0].contains("verification_lineage"));
+        assert!(violations[0].contains("general"));
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn bulk_module_update_is_all_or_nothing() {
+        use crate::db::libsql::LibSqlBackend;
+        use crate::db::{Database, SettingsStore};
+
+        let store = LibSqlBackend::new_memory().await.unwrap();
+        store.run_migrations().await.unwrap();
+        let toggle = |module_id: &str, enabled: bool| ModuleState {
+            module_id: module_id.to_string(),
+            enabled,
+            status: String::new(),
+            updated_at: String::new(),
+            config: serde_json::json!({}),
+        };
+
+        let updated = set_module_states(
+            &store,
+            &catalog(),
+            "default",
+            vec![
+                toggle("developer", false),
+                toggle("research", false),
+                toggle("hyperliquid_addon", true),
+            ],
+        )
+        .await
+        .expect("valid bulk update");
+        let stored: Vec<ModuleState> = serde_json::from_value(
+            store
+                .get_setting("default", PLATFORM_MODULE_STATE_KEY)
+                .await
+                .unwrap()
+                .expect("persisted state"),
+        )
+        .unwrap();
+        assert_eq!(stored.len(), catalog().modules().len());
+        for (module_id, enabled) in [
+            ("developer", false),
+            ("research", false),
+            ("hyperliquid_addon", true),
+            ("general", true),
+        ] {
+            assert_eq!(
+                module_is_enabled(&stored, module_id),
+                enabled,
+                "{module_id}"
+            );
+            assert_eq!(
+                module_is_enabled(&updated, module_id),
+                enabled,
+                "{module_id}"
+            );
+        }
+        let developer = stored.iter().find(|s| s.module_id == "developer").unwrap();
+        assert_eq!(developer.status, "disabled");
+        assert!(!developer.updated_at.is_empty());
+
+        let before = store
+            .get_setting("default", PLATFORM_MODULE_STATE_KEY)
+            .await
+            .unwrap();
+        let err = set_module_states(
+            &store,
+            &catalog(),
+            "default",
+            vec![toggle("creative", false), toggle("general", false)],
+        )
+        .await
+        .expect_err("general cannot go while core modules are disabled");
+        assert!(
+            matches!(&err, PlatformError::ModuleTransitionRejected { violations }
+                if violations[0].contains("Cannot disable 'general'")),
+            "{err:?}"
+        );
+        let after = store
+            .get_setting("default", PLATFORM_MODULE_STATE_KEY)
+            .await
+            .unwrap();
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn clean_transition_passes_validation() {
+        let current = default_module_states(&catalog());
+        let desired = with_enabled(&current, "hyperliquid_addon", true);
+        assert!(validate_module_transition(&catalog(), &current, &desired).is_ok());
+
+        let desired = with_enabled(&current, "developer", false);
+        assert!(validate_module_transition(&catalog(), &current, &desired).is_ok());
+
+        let desired = with_enabled(&current, "general", false);
+        assert!(validate_module_transition(&catalog(), &current, &desired).is_ok());
+    }
+
     #[test]
     fn role_normalization_accepts_known_roles() {
         assert_eq!(normalize_org_role("owner").as_deref(), Some("owner"));
@@ -782,28 +1355,90 @@ mod tests {
         assert!(decision.confidence >= 0.9);
     }
 
+    #[test]
+    fn custom_router_rules_route_invoice_to_business_ops() {
+        let rules: RouterRules = serde_json::from_str(
+            r#"{
+                "rules": [
+                    {"module_id": "business_ops", "keywords": ["Invoice", "receivables"], "confidence": 0.88},
+                    {"module_id": "developer", "keywords": ["code"], "confidence": 0.7}
+                ]
+            }"#,
+        )
+        .expect("router rules");
+
+        let decision = infer_route_decision_with_rules("reconcile the March invoice", &rules);
+        assert_eq!(decision.module_id, "business_ops");
+        assert_eq!(decision.confidence, 0.88);
+        assert!(decision.rationale.contains("business_ops"));
+
+        // The default table has no invoice marker.
+        assert_eq!(
+            infer_route_decision("reconcile the March invoice").module_id,
+            "general"
+        );
+
+        let resolved = resolve_inference_route_with_rules(
+            &catalog(),
+            "send the invoice",
+            &default_module_states(&catalog()),
+            &rules,
+        );
+        assert_eq!(resolved.requested_module_id, "business_ops");
+        assert!(resolved.allowed);
+    }
+
     #[test]
     fn resolve_route_blocks_disabled_addon() {
-        let states = default_module_states();
-        let resolved = resolve_inference_route("check hyperliquid funding rate", &states);
+        let states = default_module_states(&catalog());
+        let resolved =
+            resolve_inference_route(&catalog(), "check hyperliquid funding rate", &states);
         assert_eq!(resolved.requested_module_id, "hyperliquid_addon");
         assert!(!resolved.allowed);
+        assert_eq!(resolved.reason_code, RouteReasonCode::AddonDisabled);
         assert!(resolved.reason.contains("disabled"));
     }
 
     #[test]
     fn resolve_route_falls_back_to_general_when_core_module_disabled() {
-        let mut states = default_module_states();
+        let mut states = default_module_states(&catalog());
         if let Some(dev) = states.iter_mut().find(|s| s.module_id == "developer") {
             dev.enabled = false;
             dev.status = "disabled".to_string();
         }
-        let resolved = resolve_inference_route("debug this rust compile error", &states);
+        let resolved =
+            resolve_inference_route(&catalog(), "debug this rust compile error", &states);
         assert!(resolved.allowed);
+        assert_eq!(resolved.reason_code, RouteReasonCode::GeneralFallback);
         assert_eq!(resolved.requested_module_id, "developer");
         assert_eq!(resolved.decision.module_id, "general");
     }
 
+    #[test]
+    fn resolve_route_reason_codes_cover_allowed_and_no_fallback() {
+        let mut states = default_module_states(&catalog());
+        let resolved =
+            resolve_inference_route(&catalog(), "debug this rust compile error", &states);
+        assert!(resolved.allowed);
+        assert_eq!(resolved.reason_code, RouteReasonCode::Allowed);
+
+        for state in states
+            .iter_mut()
+            .filter(|s| s.module_id == "developer" || s.module_id == "general")
+        {
+            state.enabled = false;
+            state.status = "disabled".to_string();
+        }
+        let resolved =
+            resolve_inference_route(&catalog(), "debug this rust compile error", &states);
+        assert!(!resolved.allowed);
+        assert_eq!(resolved.reason_code, RouteReasonCode::NoFallbackAvailable);
+        assert_eq!(
+            serde_json::to_value(resolved.reason_code).expect("serialize"),
+            serde_json::json!("no_fallback_available")
+        );
+    }
+
     #[test]
     fn command_requirements_cover_trading_commands() {
         assert_eq!(
@@ -830,10 +1465,46 @@ mod tests {
         assert!(tool_required_capabilities("json").is_empty());
     }
 
+    #[test]
+    fn eigenda_pointer_tools_require_da_pointer() {
+        assert_eq!(
+            tool_required_capabilities("eigenda_commit_artifact"),
+            &["artifact_commitment"]
+        );
+        assert_eq!(
+            tool_required_capabilities("eigenda_pointer"),
+            &["da_pointer"]
+        );
+        assert_eq!(
+            tool_required_capabilities("EigenDA_Get_Blob"),
+            &["da_pointer"]
+        );
+
+        let mut states = default_module_states(&catalog());
+        for tool in ["eigenda_commit", "eigenda_get_pointer"] {
+            let guard =
+                resolve_capability_guard(&catalog(), tool_required_capabilities(tool), &states);
+            assert!(!guard.allowed, "{tool}");
+            assert!(guard.reason.contains("eigenda_addon"), "{tool}");
+        }
+
+        let eigenda = states
+            .iter_mut()
+            .find(|module| module.module_id == "eigenda_addon")
+            .expect("eigenda addon state");
+        eigenda.enabled = true;
+        eigenda.status = "enabled".to_string();
+        for tool in ["eigenda_commit", "eigenda_get_pointer"] {
+            let guard =
+                resolve_capability_guard(&catalog(), tool_required_capabilities(tool), &states);
+            assert!(guard.allowed, "{tool}");
+        }
+    }
+
     #[test]
     fn capability_guard_blocks_disabled_addon_capability() {
-        let states = default_module_states();
-        let guard = resolve_capability_guard(&["hyperliquid_execute"], &states);
+        let states = default_module_states(&catalog());
+        let guard = resolve_capability_guard(&catalog(), &["hyperliquid_execute"], &states);
         assert!(!guard.allowed);
         assert_eq!(guard.blocked_capabilities, vec!["hyperliquid_execute"]);
         assert!(guard.reason.contains("requires enabled module(s)"));
@@ -842,7 +1513,7 @@ mod tests {
 
     #[test]
     fn capability_guard_allows_enabled_addon_capability() {
-        let mut states = default_module_states();
+        let mut states = default_module_states(&catalog());
         if let Some(module) = states
             .iter_mut()
             .find(|module| module.module_id == "hyperliquid_addon")
@@ -851,8 +1522,145 @@ mod tests {
             module.status = "enabled".to_string();
         }
 
-        let guard = resolve_capability_guard(&["hyperliquid_execute"], &states);
+        let guard = resolve_capability_guard(&catalog(), &["hyperliquid_execute"], &states);
         assert!(guard.allowed);
         assert!(guard.blocked_capabilities.is_empty());
     }
+
+    #[test]
+    fn capability_index_matches_catalog_scan() {
+        let catalog = curated_module_catalog();
+        let index = ModuleCatalog::curated();
+        let keys: BTreeSet<&str> = catalog
+            .iter()
+            .flat_map(|module| module.capabilities.iter().map(|cap| cap.key.as_str()))
+            .collect();
+        assert_eq!(index.capability_index.len(), keys.len());
+        for key in keys {
+            let naive: Vec<String> = catalog
+                .iter()
+                .filter(|module| module.capabilities.iter().any(|cap| cap.key == key))
+                .map(|module| module.id.clone())
+                .collect();
+            assert_eq!(
+                index.module_ids_for_capability(key),
+                naive,
+                "capability {key}"
+            );
+        }
+        assert!(
+            index
+                .module_ids_for_capability("no.such.capability")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn extra_module_manifests_merge_after_core_8() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("modules.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "modules": [{
+                    "id": "legal_addon",
+                    "name": "Legal",
+                    "category": "addon",
+                    "description": "Contract review workflows",
+                    "enabled_by_default": false,
+                    "optional_addon": true,
+                    "capabilities": [
+                        {"key": "contract_review", "description": "Review contracts", "required": true}
+                    ]
+                }]
+            })
+            .to_string(),
+        )
+        .expect("write manifests");
+
+        let modules = load_module_catalog(&path).expect("load catalog");
+        assert_eq!(modules.len(), 9);
+        assert_eq!(modules.last().map(|m| m.id.as_str()), Some("legal_addon"));
+
+        let catalog = ModuleCatalog::new(modules);
+        assert!(catalog.contains("legal_addon"));
+        assert_eq!(
+            catalog.module_ids_for_capability("contract_review"),
+            ["legal_addon".to_string()]
+        );
+
+        let states = default_module_states(&catalog);
+        assert!(!module_is_enabled(&states, "legal_addon"));
+        let guard = resolve_capability_guard(&catalog, &["contract_review"], &states);
+        assert!(!guard.allowed);
+        assert!(guard.reason.contains("legal_addon"), "{}", guard.reason);
+
+        let rules = RouterRules {
+            rules: vec![RouterRule {
+                module_id: "legal_addon".to_string(),
+                keywords: vec!["contract".to_string()],
+                confidence: 0.9,
+                rationale: None,
+            }],
+        };
+        let route =
+            resolve_inference_route_with_rules(&catalog, "review this contract", &states, &rules);
+        assert!(!route.allowed, "disabled addon must not fall back");
+
+        let states = with_enabled(&states, "legal_addon", true);
+        assert!(resolve_capability_guard(&catalog, &["contract_review"], &states).allowed);
+        let route =
+            resolve_inference_route_with_rules(&catalog, "review this contract", &states, &rules);
+        assert!(route.allowed);
+        assert_eq!(route.decision.module_id, "legal_addon");
+    }
+
+    #[test]
+    fn extra_module_manifest_cannot_reuse_core_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("modules.toml");
+        std::fs::write(
+            &path,
+            r#"
+[[modules]]
+id = "general"
+name = "Shadow General"
+category = "core"
+description = "Tries to replace the general module"
+enabled_by_default = true
+optional_addon = false
+capabilities = []
+"#,
+        )
+        .expect("write manifests");
+
+        let err = load_module_catalog(&path).expect_err("collision");
+        assert!(
+            matches!(&err, PlatformError::ModuleIdCollision { module_id } if module_id == "general"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn batch_guards_block_trading_commands_by_default() {
+        let states = default_module_states(&catalog());
+        let guards =
+            resolve_capability_guards_batch(&catalog(), &["vault", "/positions", "help"], &states);
+        assert_eq!(guards.len(), 3);
+        assert!(!guards["vault"].allowed);
+        assert!(!guards["/positions"].allowed);
+        assert!(guards["help"].allowed);
+        assert_eq!(
+            guards["vault"],
+            resolve_capability_guard(&catalog(), command_required_capabilities("vault"), &states)
+        );
+
+        let allowed = allowed_commands(&catalog(), &states);
+        for command in ["help", "model", "version", "status"] {
+            assert!(allowed.iter().any(|c| c == command), "{command}");
+        }
+        for command in ["positions", "vault", "copy-policy", "connectors"] {
+            assert!(!allowed.iter().any(|c| c == command), "{command}");
+        }
+    }
 }
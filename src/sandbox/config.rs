@@ -23,6 +23,8 @@ pub struct SandboxConfig {
     pub auto_pull_image: bool,
     /// Port for the HTTP proxy (0 = auto-assign).
     pub proxy_port: u16,
+    /// Record commands as [`crate::sandbox::PlannedCommand`]s instead of running them.
+    pub dry_run: bool,
 }
 
 impl Default for SandboxConfig {
@@ -37,6 +39,7 @@ impl Default for SandboxConfig {
             image: "ghcr.io/nearai/sandbox:latest".to_string(),
             auto_pull_image: true,
             proxy_port: 0,
+            dry_run: false,
         }
     }
 }
@@ -29,10 +29,11 @@
 //! ```
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use async_trait::async_trait;
 use tokio::sync::RwLock;
 
 use bollard::Docker;
@@ -80,12 +81,34 @@ impl From<ContainerOutput> for ExecOutput {
     }
 }
 
+/// A command the manager was asked to run, as resolved before execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedCommand {
+    /// Shell command line.
+    pub command: String,
+    /// Working directory.
+    pub cwd: PathBuf,
+    /// Policy the command would run under.
+    pub policy: SandboxPolicy,
+    /// Names of the environment variables passed in (values are not recorded).
+    pub env_keys: Vec<String>,
+}
+
+/// Runs commands on behalf of the manager in place of its Docker and host paths.
+#[async_trait]
+pub trait CommandExecutor: Send + Sync {
+    async fn run(&self, plan: &PlannedCommand, env: HashMap<String, String>) -> Result<ExecOutput>;
+}
+
 /// Main sandbox manager.
 pub struct SandboxManager {
     config: SandboxConfig,
     proxy: Arc<RwLock<Option<HttpProxy>>>,
     docker: Arc<RwLock<Option<Docker>>>,
     initialized: std::sync::atomic::AtomicBool,
+    executor: Option<Arc<dyn CommandExecutor>>,
+    /// Commands recorded instead of executed while `config.dry_run` is set.
+    dry_run_plan: std::sync::Mutex<Vec<PlannedCommand>>,
 }
 
 impl SandboxManager {
@@ -96,9 +119,17 @@ impl SandboxManager {
             proxy: Arc::new(RwLock::new(None)),
             docker: Arc::new(RwLock::new(None)),
             initialized: std::sync::atomic::AtomicBool::new(false),
+            executor: None,
+            dry_run_plan: std::sync::Mutex::new(Vec::new()),
         }
     }
 
+    /// Route execution through `executor` instead of Docker or the host shell.
+    pub fn with_executor(mut self, executor: Arc<dyn CommandExecutor>) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
     /// Create with default configuration.
     pub fn with_defaults() -> Self {
         Self::new(SandboxConfig::default())
@@ -207,6 +238,23 @@ impl SandboxManager {
         policy: SandboxPolicy,
         env: HashMap<String, String>,
     ) -> Result<ExecOutput> {
+        let mut env_keys: Vec<String> = env.keys().cloned().collect();
+        env_keys.sort();
+        let plan = PlannedCommand {
+            command: command.to_string(),
+            cwd: cwd.to_path_buf(),
+            policy,
+            env_keys,
+        };
+
+        if self.config.dry_run {
+            return Ok(self.record_dry_run(plan));
+        }
+
+        if let Some(executor) = &self.executor {
+            return executor.run(&plan, env).await;
+        }
+
         // FullAccess policy bypasses the sandbox entirely
         if policy == SandboxPolicy::FullAccess {
             return self.execute_direct(command, cwd, env).await;
@@ -247,6 +295,38 @@ impl SandboxManager {
         Ok(container_output.into())
     }
 
+    /// Record `plan` without running it and report it as a successful no-op.
+    fn record_dry_run(&self, plan: PlannedCommand) -> ExecOutput {
+        let summary = format!(
+            "[dry-run] {:?} in {}: {}",
+            plan.policy,
+            plan.cwd.display(),
+            plan.command
+        );
+        tracing::info!("{}", summary);
+        self.dry_run_plan
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(plan);
+
+        ExecOutput {
+            exit_code: 0,
+            stdout: summary.clone(),
+            stderr: String::new(),
+            output: summary,
+            duration: Duration::ZERO,
+            truncated: false,
+        }
+    }
+
+    /// Commands recorded so far in dry-run mode, oldest first.
+    pub fn planned_commands(&self) -> Vec<PlannedCommand> {
+        self.dry_run_plan
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
     /// Execute a command directly on the host (no sandbox).
     async fn execute_direct(
         &self,
@@ -397,6 +477,12 @@ impl SandboxManagerBuilder {
         self
     }
 
+    /// Record commands instead of executing them.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.config.dry_run = dry_run;
+        self
+    }
+
     /// Add domains to the network allowlist.
     pub fn allow_domains(mut self, domains: Vec<String>) -> Self {
         self.config.network_allowlist.extend(domains);
@@ -498,6 +584,73 @@ mod tests {
         assert!(output.stdout.contains("hello"));
     }
 
+    /// Counts calls instead of running anything.
+    #[derive(Default)]
+    struct FakeExecutor {
+        runs: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CommandExecutor for FakeExecutor {
+        async fn run(
+            &self,
+            plan: &PlannedCommand,
+            _env: HashMap<String, String>,
+        ) -> Result<ExecOutput> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(ExecOutput {
+                exit_code: 0,
+                stdout: plan.command.clone(),
+                stderr: String::new(),
+                output: plan.command.clone(),
+                duration: Duration::ZERO,
+                truncated: false,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_records_plan_without_executing() {
+        let fake = Arc::new(FakeExecutor::default());
+        let manager = SandboxManagerBuilder::new()
+            .enabled(true)
+            .policy(SandboxPolicy::WorkspaceWrite)
+            .dry_run(true)
+            .build()
+            .with_executor(fake.clone());
+
+        let env = HashMap::from([("API_TOKEN".to_string(), "secret".to_string())]);
+        let output = manager
+            .execute("cargo test", Path::new("/workspace"), env)
+            .await
+            .expect("dry run succeeds without Docker");
+
+        assert_eq!(fake.runs.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert_eq!(output.exit_code, 0);
+        assert!(output.stdout.contains("cargo test"));
+        assert!(!output.stdout.contains("secret"));
+        assert_eq!(
+            manager.planned_commands(),
+            vec![PlannedCommand {
+                command: "cargo test".to_string(),
+                cwd: PathBuf::from("/workspace"),
+                policy: SandboxPolicy::WorkspaceWrite,
+                env_keys: vec!["API_TOKEN".to_string()],
+            }]
+        );
+
+        // With dry-run off the same manager setup hands the command to the executor.
+        let live = SandboxManagerBuilder::new()
+            .enabled(true)
+            .build()
+            .with_executor(fake.clone());
+        live.execute("cargo test", Path::new("/workspace"), HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(fake.runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(live.planned_commands().is_empty());
+    }
+
     #[tokio::test]
     async fn test_direct_execution_truncates_large_output() {
         let manager = SandboxManager::new(SandboxConfig {
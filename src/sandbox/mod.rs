@@ -94,7 +94,9 @@ pub mod proxy;
 pub use config::{ResourceLimits, SandboxConfig, SandboxPolicy};
 pub use container::{ContainerOutput, ContainerRunner, connect_docker};
 pub use error::{Result, SandboxError};
-pub use manager::{ExecOutput, SandboxManager, SandboxManagerBuilder};
+pub use manager::{
+    CommandExecutor, ExecOutput, PlannedCommand, SandboxManager, SandboxManagerBuilder,
+};
 pub use proxy::{
     CredentialResolver, DefaultPolicyDecider, DomainAllowlist, EnvCredentialResolver, HttpProxy,
     NetworkDecision, NetworkPolicyDecider, NetworkProxyBuilder, NetworkRequest,
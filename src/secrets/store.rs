@@ -63,6 +63,22 @@ pub trait SecretsStore: Send + Sync {
         allowed_secrets: &[String],
     ) -> Result<bool, SecretError>;
 
+    /// Re-encrypt every stored secret, across all users, from `old_key` to
+    /// `new_key`.
+    ///
+    /// Every secret must decrypt under `old_key` before anything is written,
+    /// and the writes are applied atomically. With `dry_run` only that check
+    /// runs. Returns the number of secrets rotated (or that would be).
+    ///
+    /// The store keeps using the crypto it was built with; callers rebuild it
+    /// with the new key afterwards.
+    async fn rotate_encryption_key(
+        &self,
+        old_key: &SecretsCrypto,
+        new_key: &SecretsCrypto,
+        dry_run: bool,
+    ) -> Result<usize, SecretError>;
+
     /// Resolve and decrypt verification credentials for Eigen jobs.
     async fn get_verification_job_secret(
         &self,
@@ -75,6 +91,21 @@ pub trait SecretsStore: Send + Sync {
     }
 }
 
+/// Decrypt `encrypted_value` with `old_key` and encrypt the plaintext with
+/// `new_key`, returning the new (encrypted_value, salt).
+fn reencrypt_value(
+    name: &str,
+    encrypted_value: &[u8],
+    key_salt: &[u8],
+    old_key: &SecretsCrypto,
+    new_key: &SecretsCrypto,
+) -> Result<(Vec<u8>, Vec<u8>), SecretError> {
+    let decrypted = old_key
+        .decrypt(encrypted_value, key_salt)
+        .map_err(|e| SecretError::DecryptionFailed(format!("{}: {}", name, e)))?;
+    new_key.encrypt(decrypted.expose().as_bytes())
+}
+
 /// PostgreSQL implementation of SecretsStore.
 #[cfg(feature = "postgres")]
 pub struct PostgresSecretsStore {
@@ -299,6 +330,63 @@ impl SecretsStore for PostgresSecretsStore {
 
         Ok(false)
     }
+
+    async fn rotate_encryption_key(
+        &self,
+        old_key: &SecretsCrypto,
+        new_key: &SecretsCrypto,
+        dry_run: bool,
+    ) -> Result<usize, SecretError> {
+        let mut client = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+
+        let rows = tx
+            .query(
+                "SELECT id, name, encrypted_value, key_salt FROM secrets FOR UPDATE",
+                &[],
+            )
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+
+        let mut rotated = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let id: Uuid = row.get("id");
+            let name: String = row.get("name");
+            let encrypted_value: Vec<u8> = row.get("encrypted_value");
+            let key_salt: Vec<u8> = row.get("key_salt");
+            let (value, salt) =
+                reencrypt_value(&name, &encrypted_value, &key_salt, old_key, new_key)?;
+            rotated.push((id, value, salt));
+        }
+        if dry_run {
+            return Ok(rotated.len());
+        }
+
+        for (id, value, salt) in &rotated {
+            tx.execute(
+                r#"
+                UPDATE secrets
+                SET encrypted_value = $1, key_salt = $2, updated_at = NOW()
+                WHERE id = $3
+                "#,
+                &[value, salt, id],
+            )
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+
+        Ok(rotated.len())
+    }
 }
 
 #[cfg(feature = "postgres")]
@@ -569,6 +657,78 @@ impl SecretsStore for LibSqlSecretsStore {
 
         Ok(false)
     }
+
+    async fn rotate_encryption_key(
+        &self,
+        old_key: &SecretsCrypto,
+        new_key: &SecretsCrypto,
+        dry_run: bool,
+    ) -> Result<usize, SecretError> {
+        let conn = self.connect().await?;
+        let tx = conn
+            .transaction()
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+
+        let mut rows = tx
+            .query(
+                "SELECT id, name, encrypted_value, key_salt FROM secrets",
+                (),
+            )
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+
+        let mut rotated = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?
+        {
+            let id: String = row
+                .get(0)
+                .map_err(|e| SecretError::Database(e.to_string()))?;
+            let name: String = row
+                .get(1)
+                .map_err(|e| SecretError::Database(e.to_string()))?;
+            let encrypted_value: Vec<u8> = row
+                .get(2)
+                .map_err(|e| SecretError::Database(e.to_string()))?;
+            let key_salt: Vec<u8> = row
+                .get(3)
+                .map_err(|e| SecretError::Database(e.to_string()))?;
+            let (value, salt) =
+                reencrypt_value(&name, &encrypted_value, &key_salt, old_key, new_key)?;
+            rotated.push((id, value, salt));
+        }
+        drop(rows);
+        if dry_run {
+            return Ok(rotated.len());
+        }
+
+        let now = Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        for (id, value, salt) in &rotated {
+            tx.execute(
+                r#"
+                UPDATE secrets
+                SET encrypted_value = ?1, key_salt = ?2, updated_at = ?3
+                WHERE id = ?4
+                "#,
+                libsql::params![
+                    libsql::Value::Blob(value.clone()),
+                    libsql::Value::Blob(salt.clone()),
+                    now.as_str(),
+                    id.as_str(),
+                ],
+            )
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| SecretError::Database(e.to_string()))?;
+
+        Ok(rotated.len())
+    }
 }
 
 #[cfg(feature = "libsql")]
@@ -797,6 +957,37 @@ pub mod testing {
             }
             Ok(false)
         }
+
+        async fn rotate_encryption_key(
+            &self,
+            old_key: &SecretsCrypto,
+            new_key: &SecretsCrypto,
+            dry_run: bool,
+        ) -> Result<usize, SecretError> {
+            let mut secrets = self.secrets.write().await;
+            let mut rotated = Vec::with_capacity(secrets.len());
+            for (key, secret) in secrets.iter() {
+                let (value, salt) = super::reencrypt_value(
+                    &secret.name,
+                    &secret.encrypted_value,
+                    &secret.key_salt,
+                    old_key,
+                    new_key,
+                )?;
+                rotated.push((key.clone(), value, salt));
+            }
+            if !dry_run {
+                let now = Utc::now();
+                for (key, value, salt) in &rotated {
+                    if let Some(secret) = secrets.get_mut(key) {
+                        secret.encrypted_value = value.clone();
+                        secret.key_salt = salt.clone();
+                        secret.updated_at = now;
+                    }
+                }
+            }
+            Ok(rotated.len())
+        }
     }
 }
 
@@ -809,7 +1000,7 @@ mod tests {
     use crate::secrets::crypto::SecretsCrypto;
     use crate::secrets::store::SecretsStore;
     use crate::secrets::store::testing::InMemorySecretsStore;
-    use crate::secrets::types::CreateSecretParams;
+    use crate::secrets::types::{CreateSecretParams, SecretError};
 
     fn test_store() -> InMemorySecretsStore {
         let key = "0123456789abcdef0123456789abcdef";
@@ -970,4 +1161,127 @@ mod tests {
         assert_eq!(v1.expose(), "user1_value");
         assert_eq!(v2.expose(), "user2_value");
     }
+
+    #[tokio::test]
+    async fn test_rotate_encryption_key() {
+        let store = test_store();
+        let old_key = SecretsCrypto::new(SecretString::from(
+            "0123456789abcdef0123456789abcdef".to_string(),
+        ))
+        .unwrap();
+        let new_key = SecretsCrypto::new(SecretString::from(
+            "fedcba9876543210fedcba9876543210".to_string(),
+        ))
+        .unwrap();
+        let secrets = [
+            ("user1", "openai_key", "sk-openai"),
+            ("user1", "anthropic_key", "sk-ant"),
+            ("user2", "openai_key", "sk-other"),
+        ];
+        for (user, name, value) in secrets {
+            store
+                .create(user, CreateSecretParams::new(name, value))
+                .await
+                .unwrap();
+        }
+
+        // A wrong old key fails the dry run and leaves every secret untouched.
+        let err = store
+            .rotate_encryption_key(&new_key, &old_key, true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SecretError::DecryptionFailed(_)));
+        let before = store.get("user1", "openai_key").await.unwrap();
+
+        assert_eq!(
+            store
+                .rotate_encryption_key(&old_key, &new_key, true)
+                .await
+                .unwrap(),
+            3
+        );
+        let after_dry_run = store.get("user1", "openai_key").await.unwrap();
+        assert_eq!(after_dry_run.encrypted_value, before.encrypted_value);
+
+        assert_eq!(
+            store
+                .rotate_encryption_key(&old_key, &new_key, false)
+                .await
+                .unwrap(),
+            3
+        );
+        for (user, name, value) in secrets {
+            let secret = store.get(user, name).await.unwrap();
+            let decrypted = new_key
+                .decrypt(&secret.encrypted_value, &secret.key_salt)
+                .unwrap();
+            assert_eq!(decrypted.expose(), value);
+            assert!(
+                old_key
+                    .decrypt(&secret.encrypted_value, &secret.key_salt)
+                    .is_err()
+            );
+        }
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn test_libsql_rotate_encryption_key() {
+        use crate::db::Database;
+        use crate::db::libsql::LibSqlBackend;
+        use crate::secrets::LibSqlSecretsStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LibSqlBackend::new_local(&dir.path().join("secrets.db"))
+            .await
+            .unwrap();
+        backend.run_migrations().await.unwrap();
+
+        let old_key = Arc::new(
+            SecretsCrypto::new(SecretString::from(
+                "0123456789abcdef0123456789abcdef".to_string(),
+            ))
+            .unwrap(),
+        );
+        let new_key = Arc::new(
+            SecretsCrypto::new(SecretString::from(
+                "fedcba9876543210fedcba9876543210".to_string(),
+            ))
+            .unwrap(),
+        );
+        let store = LibSqlSecretsStore::new(backend.shared_db(), Arc::clone(&old_key));
+        for (name, value) in [("openai_key", "sk-openai"), ("telegram_token", "tg-123")] {
+            store
+                .create("user1", CreateSecretParams::new(name, value))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            store
+                .rotate_encryption_key(&old_key, &new_key, false)
+                .await
+                .unwrap(),
+            2
+        );
+
+        let rotated = LibSqlSecretsStore::new(backend.shared_db(), new_key);
+        assert_eq!(
+            rotated
+                .get_decrypted("user1", "openai_key")
+                .await
+                .unwrap()
+                .expose(),
+            "sk-openai"
+        );
+        assert_eq!(
+            rotated
+                .get_decrypted("user1", "telegram_token")
+                .await
+                .unwrap()
+                .expose(),
+            "tg-123"
+        );
+        assert!(store.get_decrypted("user1", "openai_key").await.is_err());
+    }
 }
@@ -82,6 +82,10 @@ pub struct Settings {
     #[serde(default, alias = "llm_base_url")]
     pub openai_compatible_base_url: Option<String>,
 
+    /// Backends tried in order when `llm_backend` fails.
+    #[serde(default)]
+    pub llm_fallback_backends: Vec<String>,
+
     // === Step 4: Model Selection ===
     /// Currently selected model.
     #[serde(default)]
@@ -153,6 +157,27 @@ pub struct Settings {
     pub builder: BuilderSettings,
 }
 
+/// A stored setting that could not be applied and was left at its default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingWarning {
+    /// Dotted settings path, e.g. `heartbeat.interval_secs`.
+    pub key: String,
+    /// The stored value, as it was handed to [`Settings::set`].
+    pub raw_value: String,
+    /// The type the settings schema expects at `key`.
+    pub expected: String,
+}
+
+impl std::fmt::Display for SettingWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "stored setting '{}' = '{}' is not a valid {}; using default",
+            self.key, self.raw_value, self.expected
+        )
+    }
+}
+
 /// Source for the secrets master key.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -430,6 +455,15 @@ pub struct WalletVaultPolicySettings {
     /// Kill switch action policy: "pause_agent", "cancel_open_orders", "cancel_and_flatten".
     #[serde(default = "default_kill_switch_behavior")]
     pub kill_switch_behavior: String,
+
+    /// Explicit acknowledgement required before "cancel_and_flatten" is accepted.
+    #[serde(default)]
+    pub kill_switch_confirm_flatten: bool,
+
+    /// Per-symbol position caps in USD units; symbols not listed use
+    /// `max_position_size_usd`.
+    #[serde(default)]
+    pub per_symbol_position_caps: std::collections::HashMap<String, u64>,
 }
 
 fn default_wallet_custody_mode() -> String {
@@ -459,6 +493,8 @@ impl Default for WalletVaultPolicySettings {
             leverage_cap: default_leverage_cap(),
             kill_switch_enabled: true,
             kill_switch_behavior: default_kill_switch_behavior(),
+            kill_switch_confirm_flatten: false,
+            per_symbol_position_caps: std::collections::HashMap::new(),
         }
     }
 }
@@ -501,6 +537,10 @@ pub struct VerificationBackendSettings {
     /// Require signed fallback receipts.
     #[serde(default = "default_true")]
     pub fallback_require_signed_receipts: bool,
+
+    /// Force signed fallback receipts for every frontdoor user config.
+    #[serde(default)]
+    pub force_signed_receipts: bool,
 }
 
 fn default_verification_backend() -> String {
@@ -527,6 +567,7 @@ impl Default for VerificationBackendSettings {
             fallback_signing_key_id: None,
             fallback_chain_path: None,
             fallback_require_signed_receipts: true,
+            force_signed_receipts: false,
         }
     }
 }
@@ -1051,18 +1092,26 @@ impl Settings {
     /// Reconstruct Settings from a flat key-value map (as stored in the DB).
     ///
     /// Each key is a dotted path (e.g., "agent.name"), value is a JSONB value.
-    /// Missing keys get their default value.
-    pub fn from_db_map(map: &std::collections::HashMap<String, serde_json::Value>) -> Self {
+    /// Missing keys get their default value. Values that cannot be coerced to
+    /// the schema type keep the default and are reported as warnings.
+    pub fn from_db_map(
+        map: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> (Self, Vec<SettingWarning>) {
         // Start with defaults, then overlay each DB setting.
         //
         // The settings table stores both Settings struct fields and app-specific
         // data (e.g. nearai.session_token). Skip keys that don't correspond to
         // a known Settings path.
         let mut settings = Self::default();
+        let mut warnings = Vec::new();
 
-        for (key, value) in map {
+        // Sorted so warnings come out in a stable order.
+        let mut keys: Vec<&String> = map.keys().collect();
+        keys.sort();
+
+        for key in keys {
             // Convert the JSONB value to a string for the existing set() method
-            let value_str = match value {
+            let value_str = match &map[key] {
                 serde_json::Value::String(s) => s.clone(),
                 serde_json::Value::Bool(b) => b.to_string(),
                 serde_json::Value::Number(n) => n.to_string(),
@@ -1070,24 +1119,59 @@ impl Settings {
                 other => other.to_string(),
             };
 
+            let expected = settings.schema_type(key);
             match settings.set(key, &value_str) {
                 Ok(()) => {}
                 // The settings table stores both Settings fields and app-specific
                 // data (e.g. nearai.session_token). Silently skip unknown paths.
                 Err(e) if e.starts_with("Path not found") => {}
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to apply DB setting '{}' = '{}': {}",
-                        key,
-                        value_str,
-                        e
-                    );
-                }
+                Err(_) => warnings.push(SettingWarning {
+                    key: key.clone(),
+                    raw_value: value_str,
+                    expected: expected.to_string(),
+                }),
             }
         }
 
         settings.sanitize_llm_backend();
-        settings
+        (settings, warnings)
+    }
+
+    /// Reconstruct Settings from user-global rows overlaid with agent-scoped rows.
+    ///
+    /// Agent values win key-by-key; anything the agent has not set keeps the
+    /// global value (or the default when neither is present).
+    pub fn from_db_maps(
+        global: &std::collections::HashMap<String, serde_json::Value>,
+        agent: &std::collections::HashMap<String, serde_json::Value>,
+    ) -> (Self, Vec<SettingWarning>) {
+        let mut merged = global.clone();
+        merged.extend(agent.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Self::from_db_map(&merged)
+    }
+
+    /// Name of the JSON type currently held at `path`, used to describe what
+    /// a rejected value should have looked like.
+    fn schema_type(&self, path: &str) -> &'static str {
+        let Ok(json) = serde_json::to_value(self) else {
+            return "value";
+        };
+        let mut current = &json;
+        for part in path.split('.') {
+            match current.get(part) {
+                Some(next) => current = next,
+                None => return "value",
+            }
+        }
+        match current {
+            serde_json::Value::Bool(_) => "boolean",
+            serde_json::Value::Number(n) if n.is_f64() => "number",
+            serde_json::Value::Number(_) => "integer",
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Array(_) => "array",
+            serde_json::Value::Object(_) => "object",
+            serde_json::Value::Null => "value",
+        }
     }
 
     /// Flatten Settings into a key-value map suitable for DB storage.
@@ -1499,13 +1583,46 @@ mod tests {
         };
 
         let map = settings.to_db_map();
-        let restored = Settings::from_db_map(&map);
+        let (restored, warnings) = Settings::from_db_map(&map);
+        assert!(warnings.is_empty(), "{warnings:?}");
         assert_eq!(
             restored.selected_model,
             Some("claude-3-5-sonnet-20241022".to_string())
         );
     }
 
+    #[test]
+    fn test_db_map_reports_unparseable_values() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(
+            "agent.max_parallel_jobs".to_string(),
+            serde_json::json!("lots"),
+        );
+        map.insert("agent.name".to_string(), serde_json::json!("atlas"));
+        map.insert("heartbeat.enabled".to_string(), serde_json::json!(true));
+        // App-specific keys are not Settings fields and are not warnings.
+        map.insert(
+            "nearai.session_token".to_string(),
+            serde_json::json!("secret"),
+        );
+
+        let (restored, warnings) = Settings::from_db_map(&map);
+        assert_eq!(
+            warnings,
+            vec![SettingWarning {
+                key: "agent.max_parallel_jobs".to_string(),
+                raw_value: "lots".to_string(),
+                expected: "integer".to_string(),
+            }]
+        );
+        assert_eq!(
+            restored.agent.max_parallel_jobs,
+            Settings::default().agent.max_parallel_jobs
+        );
+        assert_eq!(restored.agent.name, "atlas");
+        assert!(restored.heartbeat.enabled);
+    }
+
     #[test]
     fn test_get_setting() {
         let settings = Settings::default();
@@ -1601,7 +1718,7 @@ mod tests {
         };
 
         let map = settings.to_db_map();
-        let restored = Settings::from_db_map(&map);
+        let (restored, _) = Settings::from_db_map(&map);
         assert_eq!(restored.intent_runtime.contract_version, "v2");
         assert_eq!(restored.intent_runtime.hash_algorithm, "sha256");
         assert!(!restored.intent_runtime.deterministic_serialization);
@@ -1616,7 +1733,7 @@ mod tests {
         settings.channels.telegram_owner_id = Some(123456789);
 
         let map = settings.to_db_map();
-        let restored = Settings::from_db_map(&map);
+        let (restored, _) = Settings::from_db_map(&map);
         assert_eq!(restored.channels.telegram_owner_id, Some(123456789));
     }
 
@@ -1715,7 +1832,7 @@ mod tests {
         };
 
         let map = settings.to_db_map();
-        let restored = Settings::from_db_map(&map);
+        let (restored, _) = Settings::from_db_map(&map);
 
         assert_eq!(
             restored.llm_backend,
@@ -1864,7 +1981,7 @@ mod tests {
 
         // DB map round-trip
         let map = settings.to_db_map();
-        let from_db = Settings::from_db_map(&map);
+        let (from_db, _) = Settings::from_db_map(&map);
         assert_eq!(from_db.tunnel.provider, Some("ngrok".to_string()));
         assert_eq!(from_db.tunnel.ngrok_token, Some("tok_abc123".to_string()));
 
@@ -1933,6 +2050,11 @@ mod tests {
                 leverage_cap: 4,
                 kill_switch_enabled: true,
                 kill_switch_behavior: "cancel_and_flatten".to_string(),
+                kill_switch_confirm_flatten: true,
+                per_symbol_position_caps: std::collections::HashMap::from([(
+                    "BTC".to_string(),
+                    50_000,
+                )]),
             },
             verification_backend: VerificationBackendSettings {
                 backend: "fallback_only".to_string(),
@@ -1944,6 +2066,7 @@ mod tests {
                 fallback_signing_key_id: Some("receipt-key-1".to_string()),
                 fallback_chain_path: Some(PathBuf::from("/tmp/receipt-chain.ndjson")),
                 fallback_require_signed_receipts: true,
+                force_signed_receipts: false,
             },
             copytrading: CopyTradingSettings {
                 max_allocation_usd: 20_000,
@@ -1958,7 +2081,7 @@ mod tests {
         };
 
         let map = settings.to_db_map();
-        let restored = Settings::from_db_map(&map);
+        let (restored, _) = Settings::from_db_map(&map);
 
         assert_eq!(restored.hyperliquid_runtime.network, "mainnet");
         assert_eq!(
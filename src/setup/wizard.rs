@@ -13,7 +13,6 @@
 //! 10. Verification backend
 
 use std::collections::{HashMap, HashSet};
-use std::fmt::Write as _;
 use std::sync::Arc;
 
 #[cfg(feature = "postgres")]
@@ -617,7 +616,7 @@ impl SetupWizard {
         }
 
         if let Some(map) = db_map {
-            let mut loaded = Settings::from_db_map(&map);
+            let (mut loaded, _) = Settings::from_db_map(&map);
 
             // Preserve Step 1 bootstrap selections as authoritative for this run.
             loaded.database_backend = selected_backend;
@@ -1230,6 +1229,7 @@ impl SetupWizard {
 
         let config = LlmConfig {
             backend: crate::config::LlmBackend::NearAi,
+            fallbacks: Vec::new(),
             nearai: crate::config::NearAiConfig {
                 model: "dummy".to_string(),
                 cheap_model: None,
@@ -1253,6 +1253,8 @@ impl SetupWizard {
             ollama: None,
             openai_compatible: None,
             tinfoil: None,
+            startup_probe: false,
+            probe_timeout_ms: 5_000,
         };
 
         match create_llm_provider(&config, session) {
@@ -2213,6 +2215,21 @@ impl SetupWizard {
             kill_switch_behavior = "pause_agent".to_string();
         }
 
+        let mut kill_switch_confirm_flatten = false;
+        if kill_switch_behavior == "cancel_and_flatten" {
+            kill_switch_confirm_flatten = confirm(
+                "cancel_and_flatten closes open positions when the kill switch trips. Confirm?",
+                self.settings
+                    .wallet_vault_policy
+                    .kill_switch_confirm_flatten,
+            )
+            .map_err(SetupError::Io)?;
+            if !kill_switch_confirm_flatten {
+                print_info("Flatten not confirmed; using cancel_open_orders instead.");
+                kill_switch_behavior = "cancel_open_orders".to_string();
+            }
+        }
+
         validate_wallet_policy_requirements(
             &custody_mode,
             operator_wallet_address.as_deref(),
@@ -2228,6 +2245,9 @@ impl SetupWizard {
         self.settings.wallet_vault_policy.leverage_cap = leverage_cap;
         self.settings.wallet_vault_policy.kill_switch_enabled = kill_switch_enabled;
         self.settings.wallet_vault_policy.kill_switch_behavior = kill_switch_behavior.clone();
+        self.settings
+            .wallet_vault_policy
+            .kill_switch_confirm_flatten = kill_switch_confirm_flatten;
 
         print_success(&format!(
             "Wallet policy set: custody={}, max_position={} USD, leverage={}x",
@@ -2905,19 +2925,10 @@ fn mask_wallet_address(value: &str) -> String {
     format!("{prefix}...{suffix}")
 }
 
-fn to_hex_prefixed(bytes: &[u8]) -> String {
-    let mut output = String::with_capacity(2 + bytes.len() * 2);
-    output.push_str("0x");
-    for byte in bytes {
-        let _ = write!(&mut output, "{:02x}", byte);
-    }
-    output
-}
-
 fn generate_wallet_address() -> String {
     let mut bytes = [0u8; 20];
     OsRng.fill_bytes(&mut bytes);
-    to_hex_prefixed(&bytes)
+    crate::util::hex::encode(&bytes, true)
 }
 
 fn parse_positive_u64(raw: &str, field: &str) -> Result<u64, String> {
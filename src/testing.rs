@@ -294,6 +294,7 @@ impl TestHarnessBuilder {
             skills_config: SkillsConfig::default(),
             hooks,
             cost_guard,
+            module_catalog: Arc::new(crate::platform::ModuleCatalog::curated()),
         };
 
         TestHarness {
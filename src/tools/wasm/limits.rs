@@ -16,6 +16,15 @@ pub const DEFAULT_FUEL_LIMIT: u64 = 10_000_000;
 /// Default execution timeout: 60 seconds.
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Ceiling on memory any tool may be granted: 64 MB.
+pub const MAX_MEMORY_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// Ceiling on fuel any tool may be granted: 100 million instructions.
+pub const MAX_FUEL_LIMIT: u64 = 100_000_000;
+
+/// Ceiling on wall-clock execution time any tool may be granted: 5 minutes.
+pub const MAX_TIMEOUT: Duration = Duration::from_secs(300);
+
 /// Resource limits for a single WASM execution.
 #[derive(Debug, Clone)]
 pub struct ResourceLimits {
@@ -55,6 +64,24 @@ impl ResourceLimits {
         self.timeout = timeout;
         self
     }
+
+    /// The runtime-wide ceilings a tool's limits are capped at.
+    pub fn max() -> Self {
+        Self {
+            memory_bytes: MAX_MEMORY_LIMIT,
+            fuel: MAX_FUEL_LIMIT,
+            timeout: MAX_TIMEOUT,
+        }
+    }
+
+    /// Lower each limit to `max` where it asks for more.
+    pub fn capped_at(self, max: &ResourceLimits) -> Self {
+        Self {
+            memory_bytes: self.memory_bytes.min(max.memory_bytes),
+            fuel: self.fuel.min(max.fuel),
+            timeout: self.timeout.min(max.timeout),
+        }
+    }
 }
 
 /// Wasmtime ResourceLimiter implementation for enforcing memory limits.
@@ -234,6 +261,23 @@ mod tests {
         assert_eq!(limits.timeout, std::time::Duration::from_secs(30));
     }
 
+    #[test]
+    fn test_limits_capped_at_max() {
+        let max = ResourceLimits::default()
+            .with_memory(2 * 1024 * 1024)
+            .with_fuel(1_000)
+            .with_timeout(std::time::Duration::from_secs(10));
+        let limits = ResourceLimits::default()
+            .with_memory(1024 * 1024)
+            .with_fuel(5_000)
+            .with_timeout(std::time::Duration::from_secs(30))
+            .capped_at(&max);
+
+        assert_eq!(limits.memory_bytes, 1024 * 1024);
+        assert_eq!(limits.fuel, 1_000);
+        assert_eq!(limits.timeout, std::time::Duration::from_secs(10));
+    }
+
     #[test]
     #[cfg(feature = "wasm-runtime")]
     fn test_resource_limiter_allows_growth_within_limit() {
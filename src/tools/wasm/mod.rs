@@ -99,8 +99,8 @@ mod wrapper_stub;
 pub use error::{TrapCode, TrapInfo, WasmError};
 pub use host::{HostState, LogEntry, LogLevel};
 pub use limits::{
-    DEFAULT_FUEL_LIMIT, DEFAULT_MEMORY_LIMIT, DEFAULT_TIMEOUT, FuelConfig, ResourceLimits,
-    WasmResourceLimiter,
+    DEFAULT_FUEL_LIMIT, DEFAULT_MEMORY_LIMIT, DEFAULT_TIMEOUT, FuelConfig, MAX_FUEL_LIMIT,
+    MAX_MEMORY_LIMIT, MAX_TIMEOUT, ResourceLimits, WasmResourceLimiter,
 };
 #[cfg(feature = "wasm-runtime")]
 pub use runtime::{OptLevel, PreparedModule, WasmRuntimeConfig, WasmToolRuntime};
@@ -9,8 +9,8 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::sync::RwLock;
-use wasmtime::{Config, Engine};
 pub use wasmtime::OptLevel;
+use wasmtime::{Config, Engine, Store};
 
 use crate::tools::wasm::error::WasmError;
 use crate::tools::wasm::limits::{FuelConfig, ResourceLimits};
@@ -24,6 +24,8 @@ pub const EPOCH_TICK_INTERVAL: Duration = Duration::from_millis(500);
 pub struct WasmRuntimeConfig {
     /// Default resource limits for tools.
     pub default_limits: ResourceLimits,
+    /// Ceilings every tool's limits are capped at, whatever it requests.
+    pub max_limits: ResourceLimits,
     /// Fuel configuration.
     pub fuel_config: FuelConfig,
     /// Whether to cache compiled modules.
@@ -38,6 +40,7 @@ impl Default for WasmRuntimeConfig {
     fn default() -> Self {
         Self {
             default_limits: ResourceLimits::default(),
+            max_limits: ResourceLimits::max(),
             fuel_config: FuelConfig::default(),
             cache_compiled: true,
             cache_dir: None,
@@ -54,6 +57,10 @@ impl WasmRuntimeConfig {
                 .with_memory(1024 * 1024) // 1 MB
                 .with_fuel(100_000)
                 .with_timeout(Duration::from_secs(5)),
+            max_limits: ResourceLimits::default()
+                .with_memory(1024 * 1024)
+                .with_fuel(100_000)
+                .with_timeout(Duration::from_secs(5)),
             fuel_config: FuelConfig::with_limit(100_000),
             cache_compiled: false,
             cache_dir: None,
@@ -164,10 +171,32 @@ impl WasmToolRuntime {
         &self.config
     }
 
+    /// Create a store metered by `limits`: fuel when enabled, plus an epoch
+    /// deadline derived from the timeout as a hard backstop.
+    pub fn new_store<T>(&self, data: T, limits: &ResourceLimits) -> Result<Store<T>, WasmError> {
+        let mut store = Store::new(&self.engine, data);
+
+        if self.config.fuel_config.enabled {
+            store
+                .set_fuel(limits.fuel)
+                .map_err(|e| WasmError::ConfigError(format!("Failed to set fuel: {}", e)))?;
+        }
+
+        // The epoch ticker thread increments the engine epoch every EPOCH_TICK_INTERVAL.
+        // Setting deadline to N means "trap after N ticks", so we compute the number
+        // of ticks that fit in the tool's timeout. Minimum 1 to always have a backstop.
+        store.epoch_deadline_trap();
+        let ticks = (limits.timeout.as_millis() / EPOCH_TICK_INTERVAL.as_millis()).max(1) as u64;
+        store.set_epoch_deadline(ticks);
+
+        Ok(store)
+    }
+
     /// Prepare a WASM component for execution.
     ///
     /// This validates and compiles the component, extracting metadata.
-    /// The compiled component is cached for fast instantiation.
+    /// The compiled component is cached for fast instantiation. Requested
+    /// limits are capped at the runtime's `max_limits`.
     pub async fn prepare(
         &self,
         name: &str,
@@ -182,7 +211,9 @@ impl WasmToolRuntime {
         let name = name.to_string();
         let wasm_bytes = wasm_bytes.to_vec();
         let engine = self.engine.clone();
-        let default_limits = self.config.default_limits.clone();
+        let limits = limits
+            .unwrap_or_else(|| self.config.default_limits.clone())
+            .capped_at(&self.config.max_limits);
 
         // Compile in blocking task (Wasmtime compilation is synchronous)
         let prepared = tokio::task::spawn_blocking(move || {
@@ -201,7 +232,7 @@ impl WasmToolRuntime {
                 description,
                 schema,
                 component_bytes: wasm_bytes,
-                limits: limits.unwrap_or(default_limits),
+                limits,
             })
         })
         .await
@@ -246,6 +277,20 @@ impl WasmToolRuntime {
     }
 }
 
+/// Map an error raised by a guest call to the matching `WasmError`.
+///
+/// The trap code sits in the error's context chain, below the backtrace
+/// message, so it is matched by type rather than by the top-level text.
+pub(crate) fn execution_error(error: &wasmtime::Error, limits: &ResourceLimits) -> WasmError {
+    match error.downcast_ref::<wasmtime::Trap>() {
+        Some(wasmtime::Trap::OutOfFuel) => WasmError::FuelExhausted { limit: limits.fuel },
+        Some(wasmtime::Trap::UnreachableCodeReached) => {
+            WasmError::Trapped("unreachable code executed".to_string())
+        }
+        _ => WasmError::Trapped(error.to_string()),
+    }
+}
+
 /// Extract tool description from a compiled component.
 ///
 /// In a full implementation, this would use WIT bindgen to call the description() export.
@@ -287,8 +332,9 @@ impl std::fmt::Debug for WasmToolRuntime {
 
 #[cfg(test)]
 mod tests {
+    use crate::tools::wasm::error::WasmError;
     use crate::tools::wasm::limits::ResourceLimits;
-    use crate::tools::wasm::runtime::{WasmRuntimeConfig, WasmToolRuntime};
+    use crate::tools::wasm::runtime::{WasmRuntimeConfig, WasmToolRuntime, execution_error};
 
     #[test]
     fn test_runtime_config_default() {
@@ -331,4 +377,33 @@ mod tests {
         assert_eq!(limits.memory_bytes, 5 * 1024 * 1024);
         assert_eq!(limits.fuel, 500_000);
     }
+
+    #[test]
+    fn test_spinning_module_traps_when_fuel_runs_out() {
+        let runtime = WasmToolRuntime::new(WasmRuntimeConfig::for_testing()).unwrap();
+        let module = wasmtime::Module::new(
+            runtime.engine(),
+            r#"(module (func (export "spin") (loop $l (br $l))))"#,
+        )
+        .unwrap();
+        let limits = ResourceLimits::default()
+            .with_fuel(10_000)
+            .with_timeout(std::time::Duration::from_secs(60));
+        let mut store = runtime.new_store((), &limits).unwrap();
+        let instance = wasmtime::Instance::new(&mut store, &module, &[]).unwrap();
+        let spin = instance
+            .get_typed_func::<(), ()>(&mut store, "spin")
+            .unwrap();
+
+        let err = spin.call(&mut store, ()).expect_err("fuel should run out");
+        assert_eq!(
+            err.downcast_ref::<wasmtime::Trap>(),
+            Some(&wasmtime::Trap::OutOfFuel)
+        );
+        assert!(matches!(
+            execution_error(&err, &limits),
+            WasmError::FuelExhausted { limit: 10_000 }
+        ));
+        assert_eq!(store.get_fuel().unwrap(), 0);
+    }
 }
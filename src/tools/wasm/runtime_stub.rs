@@ -21,6 +21,7 @@ pub enum OptLevel {
 #[derive(Debug, Clone)]
 pub struct WasmRuntimeConfig {
     pub default_limits: ResourceLimits,
+    pub max_limits: ResourceLimits,
     pub fuel_config: FuelConfig,
     pub cache_compiled: bool,
     pub cache_dir: Option<PathBuf>,
@@ -31,6 +32,7 @@ impl Default for WasmRuntimeConfig {
     fn default() -> Self {
         Self {
             default_limits: ResourceLimits::default(),
+            max_limits: ResourceLimits::max(),
             fuel_config: FuelConfig::default(),
             cache_compiled: false,
             cache_dir: None,
@@ -46,6 +48,10 @@ impl WasmRuntimeConfig {
                 .with_memory(1024 * 1024)
                 .with_fuel(100_000)
                 .with_timeout(Duration::from_secs(5)),
+            max_limits: ResourceLimits::default()
+                .with_memory(1024 * 1024)
+                .with_fuel(100_000)
+                .with_timeout(Duration::from_secs(5)),
             fuel_config: FuelConfig::with_limit(100_000),
             cache_compiled: false,
             cache_dir: None,
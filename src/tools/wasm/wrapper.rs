@@ -12,7 +12,6 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use wasmtime::Store;
 use wasmtime::component::{Component, Linker};
 use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
 
@@ -27,7 +26,7 @@ use crate::tools::wasm::credential_injector::{
 use crate::tools::wasm::error::WasmError;
 use crate::tools::wasm::host::{HostState, LogLevel};
 use crate::tools::wasm::limits::{ResourceLimits, WasmResourceLimiter};
-use crate::tools::wasm::runtime::{EPOCH_TICK_INTERVAL, PreparedModule, WasmToolRuntime};
+use crate::tools::wasm::runtime::{PreparedModule, WasmToolRuntime, execution_error};
 
 // Generate component model bindings from the WIT file.
 //
@@ -561,22 +560,8 @@ impl WasmToolWrapper {
             self.credentials.clone(),
             host_credentials,
         );
-        let mut store = Store::new(engine, store_data);
-
-        // Configure fuel if enabled
-        if self.runtime.config().fuel_config.enabled {
-            store
-                .set_fuel(limits.fuel)
-                .map_err(|e| WasmError::ConfigError(format!("Failed to set fuel: {}", e)))?;
-        }
-
-        // Configure epoch deadline as a hard timeout backup.
-        // The epoch ticker thread increments the engine epoch every EPOCH_TICK_INTERVAL.
-        // Setting deadline to N means "trap after N ticks", so we compute the number
-        // of ticks that fit in the tool's timeout. Minimum 1 to always have a backstop.
-        store.epoch_deadline_trap();
-        let ticks = (limits.timeout.as_millis() / EPOCH_TICK_INTERVAL.as_millis()).max(1) as u64;
-        store.set_epoch_deadline(ticks);
+        // Fuel and the epoch deadline (a hard timeout backup) come from the runtime.
+        let mut store = self.runtime.new_store(store_data, limits)?;
 
         // Set up resource limiter
         store.limiter(|data| &mut data.limiter);
@@ -604,16 +589,9 @@ impl WasmToolWrapper {
 
         // Call execute using the generated typed interface
         let tool_iface = instance.near_agent_tool();
-        let response = tool_iface.call_execute(&mut store, &request).map_err(|e| {
-            let error_str = e.to_string();
-            if error_str.contains("out of fuel") {
-                WasmError::FuelExhausted { limit: limits.fuel }
-            } else if error_str.contains("unreachable") {
-                WasmError::Trapped("unreachable code executed".to_string())
-            } else {
-                WasmError::Trapped(error_str)
-            }
-        })?;
+        let response = tool_iface
+            .call_execute(&mut store, &request)
+            .map_err(|e| execution_error(&e, limits))?;
 
         // Get logs from host state
         let logs = store.data_mut().host_state.take_logs();
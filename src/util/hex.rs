@@ -0,0 +1,101 @@
+//! Hex encoding for `0x`-prefixed wire values (signatures, addresses, keys).
+
+/// Why a hex string failed to decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum HexError {
+    #[error("hex string must be 0x-prefixed")]
+    MissingPrefix,
+
+    #[error("hex string is empty")]
+    Empty,
+
+    #[error("hex string must have an even number of characters")]
+    OddLength,
+
+    #[error("invalid hex character")]
+    InvalidChar,
+}
+
+/// Lowercase hex for `bytes`, with a leading `0x` when `prefixed`.
+pub fn encode(bytes: &[u8], prefixed: bool) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2 + if prefixed { 2 } else { 0 });
+    if prefixed {
+        out.push_str("0x");
+    }
+    for b in bytes {
+        out.push(DIGITS[usize::from(b >> 4)] as char);
+        out.push(DIGITS[usize::from(b & 0x0f)] as char);
+    }
+    out
+}
+
+/// Decode a `0x`-prefixed hex string of either case. Surrounding whitespace
+/// is ignored; an empty payload is rejected.
+pub fn decode(s: &str) -> Result<Vec<u8>, HexError> {
+    let hex = s.trim().strip_prefix("0x").ok_or(HexError::MissingPrefix)?;
+    if hex.is_empty() {
+        return Err(HexError::Empty);
+    }
+    if !hex.len().is_multiple_of(2) {
+        return Err(HexError::OddLength);
+    }
+    hex.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| Ok((decode_nibble(pair[0])? << 4) | decode_nibble(pair[1])?))
+        .collect()
+}
+
+fn decode_nibble(byte: u8) -> Result<u8, HexError> {
+    match byte {
+        b'0'..=b'9' => Ok(byte - b'0'),
+        b'a'..=b'f' => Ok(byte - b'a' + 10),
+        b'A'..=b'F' => Ok(byte - b'A' + 10),
+        _ => Err(HexError::InvalidChar),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_byte_value() {
+        let bytes: Vec<u8> = (0..=u8::MAX).collect();
+        let encoded = encode(&bytes, true);
+        assert_eq!(encoded.len(), 2 + 512);
+        assert!(encoded.starts_with("0x000102"));
+        assert!(encoded.ends_with("fdfeff"));
+        assert_eq!(decode(&encoded), Ok(bytes.clone()));
+
+        let bare = encode(&bytes, false);
+        assert_eq!(bare, encoded[2..]);
+        assert_eq!(decode(&format!("0x{bare}")), Ok(bytes));
+    }
+
+    #[test]
+    fn encodes_empty_input() {
+        assert_eq!(encode(&[], true), "0x");
+        assert_eq!(encode(&[], false), "");
+    }
+
+    #[test]
+    fn decodes_uppercase_and_mixed_case() {
+        assert_eq!(decode("0xDEADBEEF"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode("0xDeAdBeEf"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode("  0x0a  "), Ok(vec![0x0a]));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(decode(""), Err(HexError::MissingPrefix));
+        assert_eq!(decode("0x"), Err(HexError::Empty));
+        assert_eq!(decode("deadbeef"), Err(HexError::MissingPrefix));
+        assert_eq!(decode("0Xdeadbeef"), Err(HexError::MissingPrefix));
+        assert_eq!(decode("0xabc"), Err(HexError::OddLength));
+        assert_eq!(decode("0xzz"), Err(HexError::InvalidChar));
+        assert_eq!(decode("0x0g"), Err(HexError::InvalidChar));
+        assert_eq!(decode("0x 0"), Err(HexError::InvalidChar));
+        assert_eq!(decode("0x+1"), Err(HexError::InvalidChar));
+    }
+}
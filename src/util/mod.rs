@@ -1,5 +1,7 @@
 //! Shared utility functions used across the codebase.
 
+pub mod hex;
+
 /// Find the largest valid UTF-8 char boundary at or before `pos`.
 ///
 /// Polyfill for `str::floor_char_boundary` (nightly-only). Use when
@@ -16,6 +18,64 @@ pub fn floor_char_boundary(s: &str, pos: usize) -> usize {
     i
 }
 
+/// Write `data` to a sibling temp file and rename it over `path`, so readers
+/// only ever observe the previous or the new contents in full.
+///
+/// The temp file is synced before the rename and the parent directory after
+/// it, so the new contents survive a power loss once this returns.
+pub fn write_file_atomically(path: &std::path::Path, data: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("artifact");
+    let tmp_path = path.with_file_name(format!(
+        ".{file_name}.{}.tmp",
+        uuid::Uuid::new_v4().simple()
+    ));
+    let written = std::fs::File::create(&tmp_path).and_then(|mut file| {
+        file.write_all(data)?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    std::fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp_path);
+    })?;
+    sync_parent_dir(path)
+}
+
+/// Flush `path`'s directory entry so a rename or create in it is durable.
+#[cfg(unix)]
+pub(crate) fn sync_parent_dir(path: &std::path::Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => std::fs::File::open(parent)?.sync_all(),
+        _ => std::fs::File::open(".")?.sync_all(),
+    }
+}
+
+/// Directories cannot be opened for syncing outside unix.
+#[cfg(not(unix))]
+pub(crate) fn sync_parent_dir(_path: &std::path::Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Process-wide lock for `path`, shared by every caller that passes the same
+/// path. Hold it across a read-modify-write so writers never interleave.
+pub fn path_lock(path: &std::path::Path) -> std::sync::Arc<std::sync::Mutex<()>> {
+    type PathLocks =
+        std::collections::HashMap<std::path::PathBuf, std::sync::Arc<std::sync::Mutex<()>>>;
+    static LOCKS: std::sync::OnceLock<std::sync::Mutex<PathLocks>> = std::sync::OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    std::sync::Arc::clone(locks.entry(path.to_path_buf()).or_default())
+}
+
 /// Check if an LLM response explicitly signals that a job/task is complete.
 ///
 /// Uses phrase-level matching to avoid false positives from bare words like
@@ -72,7 +132,7 @@ pub fn llm_signals_completion(response: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::util::{floor_char_boundary, llm_signals_completion};
+    use crate::util::{floor_char_boundary, llm_signals_completion, path_lock};
 
     // ── floor_char_boundary ──
 
@@ -103,6 +163,17 @@ mod tests {
         assert_eq!(floor_char_boundary("", 5), 0);
     }
 
+    // ── path_lock ──
+
+    #[test]
+    fn path_lock_is_shared_per_path() {
+        let a = path_lock(std::path::Path::new("/tmp/enclagent-path-lock-a"));
+        let again = path_lock(std::path::Path::new("/tmp/enclagent-path-lock-a"));
+        let b = path_lock(std::path::Path::new("/tmp/enclagent-path-lock-b"));
+        assert!(std::sync::Arc::ptr_eq(&a, &again));
+        assert!(!std::sync::Arc::ptr_eq(&a, &b));
+    }
+
     // ── llm_signals_completion ──
 
     #[test]
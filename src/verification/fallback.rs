@@ -0,0 +1,497 @@
+//! Signed fallback receipt chain.
+//!
+//! Each line of the NDJSON chain file is a [`ReceiptEntry`] whose hash covers
+//! the previous entry's hash, so editing, dropping, or reordering any line
+//! breaks every hash after it. When signed receipts are required, each entry
+//! hash is also signed with the secp256k1 key named by `signing_key_id`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use k256::ecdsa::signature::{Signer, Verifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::agent::intent::deterministic_hash;
+use crate::config::SignedFallbackReceiptChainConfig;
+use crate::error::VerificationError;
+use crate::secrets::SecretsStore;
+use crate::util::{path_lock, sync_parent_dir};
+
+/// `prev_hash` of the first entry in a chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One line of the receipt chain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReceiptEntry {
+    /// Zero-based position in the chain.
+    pub seq: u64,
+    /// `entry_hash` of the previous line, or [`GENESIS_HASH`].
+    pub prev_hash: String,
+    pub receipt_hash: String,
+    pub receipt: serde_json::Value,
+    pub recorded_at: DateTime<Utc>,
+    /// Hash over every field above plus `signing_key_id`.
+    pub entry_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key_id: Option<String>,
+    /// Base64 ECDSA signature over `entry_hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Fields covered by `entry_hash`.
+#[derive(Serialize)]
+struct EntryBody<'a> {
+    seq: u64,
+    prev_hash: &'a str,
+    receipt_hash: &'a str,
+    recorded_at: &'a DateTime<Utc>,
+    signing_key_id: Option<&'a str>,
+}
+
+impl ReceiptEntry {
+    fn compute_hash(&self) -> Result<String, serde_json::Error> {
+        deterministic_hash(&EntryBody {
+            seq: self.seq,
+            prev_hash: &self.prev_hash,
+            receipt_hash: &self.receipt_hash,
+            recorded_at: &self.recorded_at,
+            signing_key_id: self.signing_key_id.as_deref(),
+        })
+    }
+}
+
+/// A secp256k1 receipt signing key and the id it is configured under.
+#[derive(Clone)]
+pub struct ReceiptSigner {
+    key_id: String,
+    key: SigningKey,
+}
+
+impl std::fmt::Debug for ReceiptSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReceiptSigner")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ReceiptSigner {
+    pub fn new(key_id: impl Into<String>, key: SigningKey) -> Self {
+        Self {
+            key_id: key_id.into(),
+            key,
+        }
+    }
+
+    /// Parse a hex-encoded (optionally `0x`-prefixed) 32-byte private key.
+    pub fn from_hex(key_id: impl Into<String>, hex: &str) -> Result<Self, VerificationError> {
+        let key_id = key_id.into();
+        let invalid = |reason: &str| VerificationError::InvalidSigningKey {
+            key_id: key_id.clone(),
+            reason: reason.to_string(),
+        };
+        let hex = hex.trim();
+        let prefixed = if hex.starts_with("0x") {
+            hex.to_string()
+        } else {
+            format!("0x{hex}")
+        };
+        let bytes = crate::util::hex::decode(&prefixed).map_err(|e| invalid(&e.to_string()))?;
+        let key = SigningKey::from_slice(&bytes).map_err(|e| invalid(&e.to_string()))?;
+        Ok(Self::new(key_id, key))
+    }
+
+    /// Load the private key stored as the secret named `key_id`.
+    pub async fn from_secrets(
+        secrets: &dyn SecretsStore,
+        user_id: &str,
+        key_id: &str,
+    ) -> Result<Self, VerificationError> {
+        let secret = secrets.get_decrypted(user_id, key_id).await.map_err(|e| {
+            VerificationError::InvalidSigningKey {
+                key_id: key_id.to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        Self::from_hex(key_id, secret.expose())
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        self.key.verifying_key()
+    }
+}
+
+/// Append-only, hash-linked receipt chain at `chain_path`.
+#[derive(Debug)]
+pub struct FallbackReceiptChain {
+    path: PathBuf,
+    require_signed_receipts: bool,
+    signer: Option<ReceiptSigner>,
+    head: Mutex<Option<ChainHead>>,
+}
+
+/// Last appended entry and the file length right after it was written.
+#[derive(Debug)]
+struct ChainHead {
+    seq: u64,
+    entry_hash: String,
+    len: u64,
+}
+
+impl FallbackReceiptChain {
+    /// Build a chain for `config`.
+    ///
+    /// When `require_signed_receipts` is set, `signer` must be present and
+    /// carry the configured `signing_key_id`.
+    pub fn new(
+        config: &SignedFallbackReceiptChainConfig,
+        signer: Option<ReceiptSigner>,
+    ) -> Result<Self, VerificationError> {
+        if config.require_signed_receipts {
+            let key_id = config
+                .signing_key_id
+                .as_deref()
+                .ok_or(VerificationError::SigningKeyMissing)?;
+            let signer = signer
+                .as_ref()
+                .ok_or(VerificationError::SigningKeyMissing)?;
+            if signer.key_id != key_id {
+                return Err(VerificationError::InvalidSigningKey {
+                    key_id: signer.key_id.clone(),
+                    reason: format!("configured signing key is {key_id}"),
+                });
+            }
+        }
+        Ok(Self {
+            path: config.chain_path.clone(),
+            require_signed_receipts: config.require_signed_receipts,
+            signer: signer.filter(|_| config.require_signed_receipts),
+            head: Mutex::new(None),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Link `receipt` to the current chain head and append it.
+    ///
+    /// Appends on the same path are serialized, so two writers never link to
+    /// the same head. The head is cached and only re-read when the file
+    /// length shows another writer has appended since.
+    pub fn append<T: Serialize>(&self, receipt: &T) -> Result<ReceiptEntry, VerificationError> {
+        use std::io::Write;
+
+        let receipt = serde_json::to_value(receipt)?;
+
+        let lock = path_lock(&self.path);
+        let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut cached = self.head.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let len = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        let (head, needs_newline) = match cached.as_ref() {
+            Some(head) if head.len == len => (Some((head.seq, head.entry_hash.clone())), false),
+            _ => self.read_head()?,
+        };
+
+        let mut entry = ReceiptEntry {
+            seq: head.as_ref().map_or(0, |(seq, _)| seq + 1),
+            prev_hash: head.map_or_else(|| GENESIS_HASH.to_string(), |(_, hash)| hash),
+            receipt_hash: deterministic_hash(&receipt)?,
+            receipt,
+            recorded_at: Utc::now(),
+            entry_hash: String::new(),
+            signing_key_id: self.signer.as_ref().map(|s| s.key_id.clone()),
+            signature: None,
+        };
+        entry.entry_hash = entry.compute_hash()?;
+        if let Some(signer) = &self.signer {
+            let signature: Signature = signer.key.sign(entry.entry_hash.as_bytes());
+            entry.signature =
+                Some(base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()));
+        }
+
+        let mut line = String::new();
+        if needs_newline {
+            line.push('\n');
+        }
+        line.push_str(&serde_json::to_string(&entry)?);
+        line.push('\n');
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+        if len == 0 {
+            sync_parent_dir(&self.path)?;
+        }
+
+        *cached = Some(ChainHead {
+            seq: entry.seq,
+            entry_hash: entry.entry_hash.clone(),
+            len: len + line.len() as u64,
+        });
+        Ok(entry)
+    }
+
+    /// Last entry's `(seq, entry_hash)` as stored on disk, and whether the
+    /// file is missing its trailing newline.
+    fn read_head(&self) -> Result<(Option<(u64, String)>, bool), VerificationError> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((None, false)),
+            Err(e) => return Err(e.into()),
+        };
+        let head = match contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .last()
+        {
+            Some((index, line)) => {
+                let entry = serde_json::from_str::<ReceiptEntry>(line)
+                    .map_err(|e| self.corrupt(index + 1, e.to_string()))?;
+                Some((entry.seq, entry.entry_hash))
+            }
+            None => None,
+        };
+        Ok((head, !contents.is_empty() && !contents.ends_with('\n')))
+    }
+
+    /// Read the whole chain and check every hash link and signature.
+    ///
+    /// A missing file is an empty chain. A signed entry fails with
+    /// [`VerificationError::UnverifiedSignature`] when this chain has no
+    /// signer to check it against, rather than passing unchecked.
+    pub fn verify_chain(&self) -> Result<Vec<ReceiptEntry>, VerificationError> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut entries: Vec<ReceiptEntry> = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_no = index + 1;
+            let entry: ReceiptEntry =
+                serde_json::from_str(line).map_err(|e| self.corrupt(line_no, e.to_string()))?;
+
+            let expected_prev = entries
+                .last()
+                .map_or(GENESIS_HASH, |prev| prev.entry_hash.as_str());
+            if entry.seq != entries.len() as u64 {
+                return Err(self.corrupt(line_no, format!("expected seq {}", entries.len())));
+            }
+            if entry.prev_hash != expected_prev {
+                return Err(self.corrupt(line_no, "prev_hash does not match prior entry"));
+            }
+            if entry.receipt_hash != deterministic_hash(&entry.receipt)? {
+                return Err(self.corrupt(line_no, "receipt_hash does not match receipt"));
+            }
+            if entry.entry_hash != entry.compute_hash()? {
+                return Err(self.corrupt(line_no, "entry_hash does not match entry"));
+            }
+            self.verify_signature(&entry, line_no)?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    fn verify_signature(&self, entry: &ReceiptEntry, line: usize) -> Result<(), VerificationError> {
+        let Some(signature) = entry.signature.as_deref() else {
+            if self.require_signed_receipts {
+                return Err(self.corrupt(line, "entry is unsigned"));
+            }
+            return Ok(());
+        };
+        let Some(signer) = &self.signer else {
+            return Err(VerificationError::UnverifiedSignature {
+                path: self.path.display().to_string(),
+                line,
+                key_id: entry.signing_key_id.clone().unwrap_or_default(),
+            });
+        };
+        if entry.signing_key_id.as_deref() != Some(signer.key_id.as_str()) {
+            return Err(self.corrupt(line, "entry was signed with a different key"));
+        }
+        let signature = base64::engine::general_purpose::STANDARD
+            .decode(signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| self.corrupt(line, "signature is malformed"))?;
+        signer
+            .verifying_key()
+            .verify(entry.entry_hash.as_bytes(), &signature)
+            .map_err(|_| self.corrupt(line, "signature does not verify"))
+    }
+
+    fn corrupt(&self, line: usize, reason: impl Into<String>) -> VerificationError {
+        VerificationError::ChainCorrupt {
+            path: self.path.display().to_string(),
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_config(path: PathBuf) -> SignedFallbackReceiptChainConfig {
+        SignedFallbackReceiptChainConfig {
+            enabled: true,
+            signing_key_id: Some("receipt-key".to_string()),
+            chain_path: path,
+            require_signed_receipts: true,
+        }
+    }
+
+    fn signer() -> ReceiptSigner {
+        ReceiptSigner::from_hex(
+            "receipt-key",
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .expect("signing key")
+    }
+
+    #[test]
+    fn appends_link_and_sign_each_receipt() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("receipts").join("receipt-chain.ndjson");
+        let chain =
+            FallbackReceiptChain::new(&signed_config(path.clone()), Some(signer())).expect("chain");
+
+        let appended: Vec<ReceiptEntry> = (0..3)
+            .map(|i| {
+                chain
+                    .append(&serde_json::json!({ "intent": i, "status": "filled" }))
+                    .expect("append")
+            })
+            .collect();
+
+        assert_eq!(appended[0].prev_hash, GENESIS_HASH);
+        assert_eq!(appended[1].prev_hash, appended[0].entry_hash);
+        assert_eq!(appended[2].prev_hash, appended[1].entry_hash);
+        assert!(appended.iter().all(|e| e.signature.is_some()));
+        assert!(
+            appended
+                .iter()
+                .all(|e| e.signing_key_id.as_deref() == Some("receipt-key"))
+        );
+
+        let verified = chain.verify_chain().expect("chain verifies");
+        assert_eq!(verified, appended);
+        assert_eq!(
+            std::fs::read_to_string(&path)
+                .expect("chain file")
+                .lines()
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn tampered_receipt_fails_verification() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("receipt-chain.ndjson");
+        let chain =
+            FallbackReceiptChain::new(&signed_config(path.clone()), Some(signer())).expect("chain");
+        for i in 0..3 {
+            chain
+                .append(&serde_json::json!({ "intent": i, "notional_usd": 100 }))
+                .expect("append");
+        }
+
+        let contents = std::fs::read_to_string(&path).expect("chain file");
+        let tampered = contents.replacen("\"notional_usd\":100", "\"notional_usd\":900", 1);
+        assert_ne!(contents, tampered);
+        std::fs::write(&path, tampered).expect("write tampered chain");
+
+        match chain.verify_chain() {
+            Err(VerificationError::ChainCorrupt { line, .. }) => assert_eq!(line, 1),
+            other => panic!("expected corrupt chain, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn appends_from_separate_handles_stay_linked() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("receipt-chain.ndjson");
+        let first =
+            FallbackReceiptChain::new(&signed_config(path.clone()), Some(signer())).expect("chain");
+        let second =
+            FallbackReceiptChain::new(&signed_config(path.clone()), Some(signer())).expect("chain");
+
+        let a = first
+            .append(&serde_json::json!({ "intent": 0 }))
+            .expect("append");
+        let b = second
+            .append(&serde_json::json!({ "intent": 1 }))
+            .expect("append");
+        // `first` cached its own head; it must notice `second` appended since.
+        let c = first
+            .append(&serde_json::json!({ "intent": 2 }))
+            .expect("append");
+
+        assert_eq!(b.prev_hash, a.entry_hash);
+        assert_eq!(c.prev_hash, b.entry_hash);
+        assert_eq!(c.seq, 2);
+        assert_eq!(first.verify_chain().expect("chain verifies").len(), 3);
+    }
+
+    #[test]
+    fn signed_entries_are_not_passed_without_a_signer() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("receipt-chain.ndjson");
+        let chain =
+            FallbackReceiptChain::new(&signed_config(path.clone()), Some(signer())).expect("chain");
+        chain
+            .append(&serde_json::json!({ "intent": 0 }))
+            .expect("append");
+
+        let mut unsigned_config = signed_config(path);
+        unsigned_config.require_signed_receipts = false;
+        let reader = FallbackReceiptChain::new(&unsigned_config, None).expect("chain");
+        match reader.verify_chain() {
+            Err(VerificationError::UnverifiedSignature { line, key_id, .. }) => {
+                assert_eq!(line, 1);
+                assert_eq!(key_id, "receipt-key");
+            }
+            other => panic!("expected an unverified signature, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn required_signing_rejects_missing_or_mismatched_key() {
+        let config = signed_config(PathBuf::from("unused.ndjson"));
+        assert!(matches!(
+            FallbackReceiptChain::new(&config, None),
+            Err(VerificationError::SigningKeyMissing)
+        ));
+        let other = ReceiptSigner::new("other-key", signer().key);
+        assert!(matches!(
+            FallbackReceiptChain::new(&config, Some(other)),
+            Err(VerificationError::InvalidSigningKey { .. })
+        ));
+    }
+}
@@ -0,0 +1,8 @@
+//! Verification receipt storage.
+//!
+//! When EigenCloud verification is unavailable, receipts are appended to a
+//! local hash-linked chain so the record stays tamper-evident.
+
+pub mod fallback;
+
+pub use fallback::{FallbackReceiptChain, ReceiptEntry, ReceiptSigner};
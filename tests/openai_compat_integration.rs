@@ -120,6 +120,7 @@ async fn start_test_server() -> Option<(SocketAddr, Arc<GatewayState>)> {
         llm_provider: Some(Arc::new(MockLlmProvider)),
         skill_registry: None,
         skill_catalog: None,
+        module_catalog: Arc::new(enclagent::platform::ModuleCatalog::curated()),
         frontdoor: None,
         chat_rate_limiter: enclagent::channels::web::server::RateLimiter::new(30, 60),
     });
@@ -461,6 +462,7 @@ async fn test_no_llm_provider_returns_503() {
         llm_provider: None, // No LLM!
         skill_registry: None,
         skill_catalog: None,
+        module_catalog: Arc::new(enclagent::platform::ModuleCatalog::curated()),
         frontdoor: None,
         chat_rate_limiter: enclagent::channels::web::server::RateLimiter::new(30, 60),
     });
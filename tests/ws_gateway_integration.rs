@@ -59,6 +59,7 @@ async fn start_test_server() -> Option<(
         llm_provider: None,
         skill_registry: None,
         skill_catalog: None,
+        module_catalog: Arc::new(enclagent::platform::ModuleCatalog::curated()),
         frontdoor: None,
         chat_rate_limiter: enclagent::channels::web::server::RateLimiter::new(30, 60),
     });